@@ -16,6 +16,7 @@ import {
   PeerReviewAcceptingStrategy,
   PeerReviewQuestion,
   PeerReviewQuestionType,
+  PeerReviewTimeoutAction,
 } from "../shared-module/bindings"
 import Button from "../shared-module/components/Button"
 import ErrorBanner from "../shared-module/components/ErrorBanner"
@@ -26,6 +27,8 @@ import TextField from "../shared-module/components/InputFields/TextField"
 import Spinner from "../shared-module/components/Spinner"
 import { baseTheme } from "../shared-module/styles"
 
+import PeerReviewRubricEditor, { newRubricCriterion } from "./PeerReviewRubricEditor"
+
 const Wrapper = styled.div`
   margin: 0 auto;
   max-width: 1000px;
@@ -153,6 +156,7 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
   const peerReviewQuestionTypeoptions: { label: string; value: PeerReviewQuestionType }[] = [
     { label: t("essay"), value: "Essay" },
     { label: t("likert-scale"), value: "Scale" },
+    { label: t("rubric"), value: "Rubric" },
   ]
 
   const peerReviewAcceptingStrategyOptions: {
@@ -171,6 +175,28 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
       label: "Manual review everything",
       value: "ManualReviewEverything",
     },
+    {
+      label: "Automatically grade by rubric average",
+      value: "AutomaticallyGradeByRubricAverage",
+    },
+  ]
+
+  const peerReviewTimeoutActionOptions: {
+    label: string
+    value: PeerReviewTimeoutAction
+  }[] = [
+    {
+      label: "Move to manual review",
+      value: "MoveToManualReview",
+    },
+    {
+      label: "Give full points",
+      value: "GiveFullPoints",
+    },
+    {
+      label: "Accept by current average",
+      value: "AcceptByCurrentAverage",
+    },
   ]
 
   const handlePeerReviewValueChange = (value: string, field: keyof CmsPeerReviewConfig) => {
@@ -188,6 +214,24 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
       case "peer_reviews_to_receive":
         peerReviewConfig = { ...parsedPeerReviewConfig, peer_reviews_to_receive: Number(value) }
         break
+      case "calibration_samples_to_review":
+        peerReviewConfig = {
+          ...parsedPeerReviewConfig,
+          calibration_samples_to_review: Number(value),
+        }
+        break
+      case "timeout_in_days":
+        peerReviewConfig = { ...parsedPeerReviewConfig, timeout_in_days: Number(value) }
+        break
+      case "timeout_action":
+        peerReviewConfig = { ...parsedPeerReviewConfig, timeout_action: value }
+        break
+      case "manual_review_timeout_in_days":
+        peerReviewConfig = {
+          ...parsedPeerReviewConfig,
+          manual_review_timeout_in_days: value === "" ? null : Number(value),
+        }
+        break
       default:
         break
     }
@@ -209,7 +253,15 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
           case "question":
             return { ...prq, question: value }
           case "question_type":
-            return { ...prq, question_type: value }
+            return {
+              ...prq,
+              question_type: value,
+              // Only rubric questions have a rubric
+              rubric:
+                value === "Rubric" ? prq.rubric ?? { criteria: [newRubricCriterion()] } : null,
+            }
+          case "rubric":
+            return { ...prq, rubric: value }
           case "answer_required":
             return { ...prq, answer_required: value }
           default:
@@ -235,6 +287,10 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
       accepting_threshold: 2.1,
       peer_reviews_to_give: 3,
       peer_reviews_to_receive: 2,
+      calibration_samples_to_review: 0,
+      timeout_in_days: 21,
+      timeout_action: "MoveToManualReview",
+      manual_review_timeout_in_days: 90,
     }
     setExerciseAttributes({
       ...exerciseAttributes,
@@ -271,6 +327,7 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
           peer_review_config_id: peerReviewId,
           answer_required: true,
           order_number: parsedPeerReviewQuestionConfig.length,
+          rubric: null,
         },
       ]),
       peer_review_config: JSON.stringify(parsedPeerReviewConfig),
@@ -398,10 +455,50 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
                     handlePeerReviewValueChange(e, "accepting_threshold")
                   }}
                 />
+                <TextField
+                  label={t("peer-review-calibration-samples-to-review")}
+                  type={"number"}
+                  min={0}
+                  required
+                  value={parsedPeerReviewConfig.calibration_samples_to_review}
+                  onChange={(e) => {
+                    handlePeerReviewValueChange(e, "calibration_samples_to_review")
+                  }}
+                />
+                <TextField
+                  label={t("peer-review-timeout-in-days")}
+                  type={"number"}
+                  min={1}
+                  required
+                  value={parsedPeerReviewConfig.timeout_in_days}
+                  onChange={(e) => {
+                    handlePeerReviewValueChange(e, "timeout_in_days")
+                  }}
+                />
+                <SelectField
+                  id={`peer-review-timeout-action-${id}`}
+                  label={t("peer-review-timeout-action")}
+                  onChange={(e) => {
+                    handlePeerReviewValueChange(e, "timeout_action")
+                  }}
+                  options={peerReviewTimeoutActionOptions}
+                  defaultValue={parsedPeerReviewConfig.timeout_action}
+                />
+                {parsedPeerReviewConfig.timeout_action !== "GiveFullPoints" && (
+                  <TextField
+                    label={t("peer-review-manual-review-timeout-in-days")}
+                    type={"number"}
+                    min={1}
+                    value={parsedPeerReviewConfig.manual_review_timeout_in_days ?? ""}
+                    onChange={(e) => {
+                      handlePeerReviewValueChange(e, "manual_review_timeout_in_days")
+                    }}
+                  />
+                )}
                 <h2>{HEADING_TEXT}</h2>
                 {parsedPeerReviewQuestionConfig &&
                   parsedPeerReviewQuestionConfig.map(
-                    ({ id, question, question_type, answer_required }) => (
+                    ({ id, question, question_type, answer_required, rubric }) => (
                       <List key={id} id={id}>
                         <StyledQuestion>
                           <StyledSelectField
@@ -440,6 +537,14 @@ const PeerReviewEditor: React.FC<PeerReviewEditorProps> = ({
                         >
                           <FontAwesomeIcon icon={faXmark} />
                         </DeleteBtn>
+                        {question_type === "Rubric" && rubric && (
+                          <PeerReviewRubricEditor
+                            rubric={rubric}
+                            onChange={(newRubric) =>
+                              handlePeerReviewQuestionValueChange(id, newRubric, "rubric")
+                            }
+                          />
+                        )}
                       </List>
                     ),
                   )}
@@ -477,6 +582,10 @@ function defaultPeerReviewConfig(
     accepting_threshold: 2.1,
     peer_reviews_to_give: 3,
     peer_reviews_to_receive: 2,
+    calibration_samples_to_review: 0,
+    timeout_in_days: 21,
+    timeout_action: "MoveToManualReview",
+    manual_review_timeout_in_days: 90,
   }
 }
 
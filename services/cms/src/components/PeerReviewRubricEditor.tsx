@@ -0,0 +1,173 @@
+import { css } from "@emotion/css"
+import { faXmark } from "@fortawesome/free-solid-svg-icons"
+import { FontAwesomeIcon } from "@fortawesome/react-fontawesome"
+import React from "react"
+import { useTranslation } from "react-i18next"
+import { v4 } from "uuid"
+
+import {
+  PeerReviewRubric,
+  PeerReviewRubricCriterion,
+  PeerReviewRubricLevel,
+} from "../shared-module/bindings"
+import Button from "../shared-module/components/Button"
+import TextField from "../shared-module/components/InputFields/TextField"
+import { baseTheme } from "../shared-module/styles"
+
+export interface PeerReviewRubricEditorProps {
+  rubric: PeerReviewRubric
+  onChange: (rubric: PeerReviewRubric) => void
+}
+
+export const newRubricLevel = (points: number): PeerReviewRubricLevel => ({
+  id: v4(),
+  description: "",
+  points,
+})
+
+export const newRubricCriterion = (): PeerReviewRubricCriterion => ({
+  id: v4(),
+  name: "",
+  levels: [newRubricLevel(0), newRubricLevel(1)],
+})
+
+const PeerReviewRubricEditor: React.FC<PeerReviewRubricEditorProps> = ({ rubric, onChange }) => {
+  const { t } = useTranslation()
+
+  const updateCriterion = (criterionId: string, update: Partial<PeerReviewRubricCriterion>) => {
+    onChange({
+      ...rubric,
+      criteria: rubric.criteria.map((criterion) =>
+        criterion.id === criterionId ? { ...criterion, ...update } : criterion,
+      ),
+    })
+  }
+
+  const updateLevel = (
+    criterion: PeerReviewRubricCriterion,
+    levelId: string,
+    update: Partial<PeerReviewRubricLevel>,
+  ) => {
+    updateCriterion(criterion.id, {
+      levels: criterion.levels.map((level) =>
+        level.id === levelId ? { ...level, ...update } : level,
+      ),
+    })
+  }
+
+  return (
+    <div
+      className={css`
+        grid-column: 1 / -1;
+        padding: 0.5rem 1rem;
+        border-left: 4px solid ${baseTheme.colors.green[300]};
+      `}
+    >
+      {rubric.criteria.map((criterion) => (
+        <div
+          key={criterion.id}
+          className={css`
+            margin-bottom: 1rem;
+          `}
+        >
+          <div
+            className={css`
+              display: flex;
+              gap: 0.5rem;
+              align-items: center;
+            `}
+          >
+            <TextField
+              className={css`
+                flex: 1;
+              `}
+              label={t("rubric-criterion")}
+              value={criterion.name}
+              onChange={(value) => updateCriterion(criterion.id, { name: value })}
+            />
+            <Button
+              variant="reject"
+              size="medium"
+              aria-label={t("delete")}
+              onClick={() =>
+                onChange({
+                  ...rubric,
+                  criteria: rubric.criteria.filter((c) => c.id !== criterion.id),
+                })
+              }
+            >
+              <FontAwesomeIcon icon={faXmark} />
+            </Button>
+          </div>
+          {criterion.levels.map((level) => (
+            <div
+              key={level.id}
+              className={css`
+                display: flex;
+                gap: 0.5rem;
+                align-items: center;
+                margin-left: 2rem;
+              `}
+            >
+              <TextField
+                className={css`
+                  flex: 3;
+                `}
+                label={t("rubric-level-description")}
+                value={level.description}
+                onChange={(value) => updateLevel(criterion, level.id, { description: value })}
+              />
+              <TextField
+                className={css`
+                  flex: 1;
+                `}
+                type="number"
+                step="0.5"
+                label={t("points")}
+                value={level.points}
+                onChange={(value) => updateLevel(criterion, level.id, { points: Number(value) })}
+              />
+              <Button
+                variant="reject"
+                size="medium"
+                aria-label={t("delete")}
+                onClick={() =>
+                  updateCriterion(criterion.id, {
+                    levels: criterion.levels.filter((l) => l.id !== level.id),
+                  })
+                }
+              >
+                <FontAwesomeIcon icon={faXmark} />
+              </Button>
+            </div>
+          ))}
+          <Button
+            className={css`
+              margin-left: 2rem;
+            `}
+            variant="secondary"
+            size="medium"
+            onClick={() =>
+              updateCriterion(criterion.id, {
+                levels: [...criterion.levels, newRubricLevel(criterion.levels.length)],
+              })
+            }
+          >
+            {t("add-rubric-level")}
+          </Button>
+        </div>
+      ))}
+      <Button
+        variant="secondary"
+        size="medium"
+        onClick={() =>
+          onChange({ ...rubric, criteria: [...rubric.criteria, newRubricCriterion()] })
+        }
+      >
+        {t("add-rubric-criterion")}
+      </Button>
+    </div>
+  )
+}
+
+export default PeerReviewRubricEditor
@@ -0,0 +1,84 @@
+import { css } from "@emotion/css"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import { PageEditLock, PageEditor } from "../../shared-module/bindings"
+import Button from "../../shared-module/components/Button"
+import { baseTheme } from "../../shared-module/styles"
+
+export interface PageEditLockNoticeProps {
+  lock: PageEditLock | null
+  lockedBySomeoneElse: boolean
+  otherEditors: PageEditor[]
+  onTakeOver: () => Promise<void>
+}
+
+const displayName = (user: {
+  first_name: string | null
+  last_name: string | null
+  email: string
+}): string => {
+  const name = [user.first_name, user.last_name].filter((part) => !!part).join(" ")
+  return name === "" ? user.email : name
+}
+
+const PageEditLockNotice: React.FC<React.PropsWithChildren<PageEditLockNoticeProps>> = ({
+  lock,
+  lockedBySomeoneElse,
+  otherEditors,
+  onTakeOver,
+}) => {
+  const { t } = useTranslation()
+  const [takingOver, setTakingOver] = useState(false)
+
+  if (!lockedBySomeoneElse && otherEditors.length === 0) {
+    return null
+  }
+  return (
+    <div
+      className={css`
+        background: #f1f1f1;
+        border-left: 4px solid
+          ${lockedBySomeoneElse ? baseTheme.colors.crimson[700] : baseTheme.colors.blue[500]};
+        padding: 1rem 2rem;
+        margin-bottom: 1rem;
+      `}
+    >
+      {lockedBySomeoneElse && lock && (
+        <>
+          <h2>{t("page-is-being-edited-by", { name: displayName(lock) })}</h2>
+          <p>{t("page-edit-lock-explanation")}</p>
+        </>
+      )}
+      {otherEditors.length > 0 && (
+        <p>
+          {t("also-editing-this-page", {
+            names: otherEditors.map((editor) => displayName(editor)).join(", "),
+          })}
+        </p>
+      )}
+      {lockedBySomeoneElse && (
+        <Button
+          variant="primary"
+          size="medium"
+          disabled={takingOver}
+          onClick={async () => {
+            if (!confirm(t("are-you-sure-you-want-to-take-over-editing"))) {
+              return
+            }
+            setTakingOver(true)
+            try {
+              await onTakeOver()
+            } finally {
+              setTakingOver(false)
+            }
+          }}
+        >
+          {t("take-over-editing")}
+        </Button>
+      )}
+    </div>
+  )
+}
+
+export default PageEditLockNotice
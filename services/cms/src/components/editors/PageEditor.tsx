@@ -1,6 +1,6 @@
 /* eslint-disable i18next/no-literal-string */
 import { css } from "@emotion/css"
-import { UseMutationResult, useQuery } from "@tanstack/react-query"
+import { UseMutationResult, useQuery, useQueryClient } from "@tanstack/react-query"
 import { BlockInstance } from "@wordpress/blocks"
 import { isEqual } from "lodash"
 import dynamic from "next/dynamic"
@@ -15,10 +15,17 @@ import {
 } from "../../blocks"
 import { allowedBlockVariants, supportedCoreBlocks } from "../../blocks/supportedGutenbergBlocks"
 import { EditorContentDispatch, editorContentReducer } from "../../contexts/EditorContentContext"
+import usePageEditLock from "../../hooks/usePageEditLock"
 import usePageInfo from "../../hooks/usePageInfo"
 import mediaUploadBuilder from "../../services/backend/media/mediaUpload"
 import { fetchNextPageRoutingData } from "../../services/backend/pages"
-import { CmsPageUpdate, ContentManagementPage, Page } from "../../shared-module/bindings"
+import {
+  CmsPageUpdate,
+  ContentManagementPage,
+  Page,
+  PageSaveConflict,
+} from "../../shared-module/bindings"
+import { isErrorResponse } from "../../shared-module/bindings.guard"
 import Button from "../../shared-module/components/Button"
 import BreakFromCentered from "../../shared-module/components/Centering/BreakFromCentered"
 import DebugModal from "../../shared-module/components/DebugModal"
@@ -33,8 +40,14 @@ import { coursePageRoute } from "../../utils/routing"
 import SerializeGutenbergModal from "../SerializeGutenbergModal"
 import UpdatePageDetailsForm from "../forms/UpdatePageDetailsForm"
 
+import PageEditLockNotice from "./PageEditLockNotice"
+import PagePublishingPanel from "./PagePublishingPanel"
+import PageSaveConflictNotice from "./PageSaveConflictNotice"
+
 interface PageEditorProps {
   data: Page
+  /** The version of the page the editor was loaded with, see `ContentManagementPage.history_id`. */
+  historyId: string | null
   saveMutation: UseMutationResult<ContentManagementPage, unknown, CmsPageUpdate, unknown>
   needToRunMigrationsAndValidations: boolean
   setNeedToRunMigrationsAndValidations: React.Dispatch<boolean>
@@ -61,16 +74,49 @@ const supportedBlocks = (chapter_id: string | null, exam_id: string | null): str
   return allSupportedBlocks
 }
 
+/**
+ * The backend rejects a save with a conflict if someone else has saved the page after the editor was opened.
+ */
+const getPageSaveConflict = (error: unknown): PageSaveConflict | null => {
+  if (typeof error !== "object" || error === null || !("data" in error)) {
+    return null
+  }
+  const errorResponse = (error as { data: unknown }).data
+  if (
+    isErrorResponse(errorResponse) &&
+    errorResponse.data !== null &&
+    "page_save_conflict" in errorResponse.data
+  ) {
+    return errorResponse.data.page_save_conflict
+  }
+  return null
+}
+
+const isPageEditLockError = (error: unknown): boolean => {
+  if (typeof error !== "object" || error === null || !("data" in error)) {
+    return false
+  }
+  const errorResponse = (error as { data: unknown }).data
+  return (
+    isErrorResponse(errorResponse) &&
+    errorResponse.data !== null &&
+    "page_edit_lock" in errorResponse.data
+  )
+}
+
 const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
   data,
+  historyId,
   saveMutation,
   needToRunMigrationsAndValidations,
   setNeedToRunMigrationsAndValidations,
 }) => {
   const { t } = useTranslation()
   const router = useRouter()
+  const queryClient = useQueryClient()
   const prefix = router.asPath.split("/")[1]
   const pageInfo = usePageInfo(data.id, prefix)
+  const editLock = usePageEditLock(data.id)
   const [title, setTitle] = useState(data.title)
   const savedTitle = data.title
   const savedContent = modifyBlocks(
@@ -83,7 +129,7 @@ const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
   )
   const currentContentStateSaved = isEqual(savedContent, content) && savedTitle === title
   const [currentlySaving, setCurrentlySaving] = useState(false)
-  const handleOnSave = async () => {
+  const handleOnSave = async (baseHistoryId: string | null) => {
     setCurrentlySaving(true)
     saveMutation.mutate(
       normalizeDocument({
@@ -91,6 +137,7 @@ const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
         content: removeUnsupportedBlockType(content),
         title,
         urlPath: data.url_path,
+        baseHistoryId,
       }),
       {
         onSuccess: (data) => {
@@ -104,10 +151,17 @@ const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
               url_path: data.page.url_path,
               title: data.page.title,
               chapter_id: data.page.chapter_id,
+              base_history_id: data.history_id,
             }).content,
           })
           setNeedToRunMigrationsAndValidations(true)
         },
+        onError: (error) => {
+          if (isPageEditLockError(error)) {
+            // Someone took over the editing, show who it was
+            editLock.refresh()
+          }
+        },
         onSettled: () => {
           setCurrentlySaving(false)
         },
@@ -115,6 +169,16 @@ const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
     )
   }
 
+  const saveConflict = saveMutation.isError ? getPageSaveConflict(saveMutation.error) : null
+  const loadVersion = (current: ContentManagementPage, version: CmsPageUpdate) => {
+    // The editor is based on the current version of the page from now on
+    queryClient.setQueryData([`page-${data.id}`], current)
+    contentDispatch({ type: "setContent", payload: denormalizeDocument(version).content })
+    setTitle(version.title)
+    saveMutation.reset()
+    setNeedToRunMigrationsAndValidations(true)
+  }
+
   let mediaUpload
   if (data.course_id) {
     mediaUpload = mediaUploadBuilder({ courseId: data.course_id })
@@ -176,8 +240,8 @@ const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
             border: 1px black solid;
             pointer-events: auto;
           `}
-          onClick={handleOnSave}
-          disabled={currentContentStateSaved || currentlySaving}
+          onClick={() => handleOnSave(historyId)}
+          disabled={currentContentStateSaved || currentlySaving || editLock.lockedBySomeoneElse}
         >
           {t("save")}
         </Button>
@@ -218,7 +282,70 @@ const PageEditor: React.FC<React.PropsWithChildren<PageEditorProps>> = ({
       </BreakFromCentered>
       <div className="editor__component">
         <div>
-          {saveMutation.isError && <ErrorBanner variant={"text"} error={saveMutation.error} />}
+          <PageEditLockNotice
+            lock={editLock.lock}
+            lockedBySomeoneElse={editLock.lockedBySomeoneElse}
+            otherEditors={editLock.otherEditors}
+            onTakeOver={editLock.takeOver}
+          />
+          {saveConflict ? (
+            <PageSaveConflictNotice
+              conflict={saveConflict}
+              onLoadMerged={() => {
+                if (saveConflict.merged) {
+                  loadVersion(saveConflict.current, saveConflict.merged)
+                }
+              }}
+              onLoadCurrent={() =>
+                loadVersion(saveConflict.current, {
+                  content: saveConflict.current.page.content,
+                  exercises: saveConflict.current.exercises,
+                  exercise_slides: saveConflict.current.exercise_slides,
+                  exercise_tasks: saveConflict.current.exercise_tasks,
+                  url_path: saveConflict.current.page.url_path,
+                  title: saveConflict.current.page.title,
+                  chapter_id: saveConflict.current.page.chapter_id,
+                  base_history_id: saveConflict.current.history_id,
+                })
+              }
+              onOverwrite={() => handleOnSave(saveConflict.current.history_id)}
+            />
+          ) : (
+            saveMutation.isError && <ErrorBanner variant={"text"} error={saveMutation.error} />
+          )}
+          <PagePublishingPanel
+            page={data}
+            getDraftContent={() =>
+              normalizeDocument({
+                chapterId: data.chapter_id,
+                content: removeUnsupportedBlockType(content),
+                title,
+                urlPath: data.url_path,
+                baseHistoryId: historyId,
+              })
+            }
+            onLoadDraft={(draft) => {
+              contentDispatch({
+                type: "setContent",
+                payload: denormalizeDocument(draft.content).content,
+              })
+              setTitle(draft.content.title)
+              setNeedToRunMigrationsAndValidations(true)
+            }}
+            onDraftPublished={(published) =>
+              loadVersion(published, {
+                content: published.page.content,
+                exercises: published.exercises,
+                exercise_slides: published.exercise_slides,
+                exercise_tasks: published.exercise_tasks,
+                url_path: published.page.url_path,
+                title: published.page.title,
+                chapter_id: published.page.chapter_id,
+                base_history_id: published.history_id,
+              })
+            }
+            disabled={editLock.lockedBySomeoneElse}
+          />
           <UpdatePageDetailsForm title={title} setTitle={setTitle} />
         </div>
       </div>
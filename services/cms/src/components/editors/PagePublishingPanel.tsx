@@ -0,0 +1,179 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  deletePageDraft,
+  fetchPageDraft,
+  publishPageDraft,
+  savePageDraft,
+  setPagePublishingSchedule,
+} from "../../services/backend/pages"
+import { CmsPageUpdate, ContentManagementPage, Page, PageDraft } from "../../shared-module/bindings"
+import Button from "../../shared-module/components/Button"
+import ErrorBanner from "../../shared-module/components/ErrorBanner"
+import DateTimeLocal from "../../shared-module/components/InputFields/DateTimeLocal"
+import useToastMutation from "../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../shared-module/styles"
+import { dateToDateTimeLocalString } from "../../shared-module/utils/time"
+
+export interface PagePublishingPanelProps {
+  page: Page
+  /** The current content of the editor. */
+  getDraftContent: () => CmsPageUpdate
+  onLoadDraft: (draft: PageDraft) => void
+  onDraftPublished: (page: ContentManagementPage) => void
+  disabled: boolean
+}
+
+const toDate = (value: string): Date | null => (value ? new Date(value) : null)
+
+const PagePublishingPanel: React.FC<React.PropsWithChildren<PagePublishingPanelProps>> = ({
+  page,
+  getDraftContent,
+  onLoadDraft,
+  onDraftPublished,
+  disabled,
+}) => {
+  const { t } = useTranslation()
+  const draftQuery = useQuery([`page-${page.id}-draft`], () => fetchPageDraft(page.id))
+  const [draftPublishAt, setDraftPublishAt] = useState("")
+  const [publishAt, setPublishAt] = useState(
+    page.publish_at ? dateToDateTimeLocalString(page.publish_at) : "",
+  )
+  const [unpublishAt, setUnpublishAt] = useState(
+    page.unpublish_at ? dateToDateTimeLocalString(page.unpublish_at) : "",
+  )
+
+  const saveDraftMutation = useToastMutation(
+    () =>
+      savePageDraft(page.id, {
+        content: getDraftContent(),
+        publish_at: toDate(draftPublishAt),
+      }),
+    { notify: true, method: "PUT" },
+    { onSuccess: () => draftQuery.refetch() },
+  )
+  const publishDraftMutation = useToastMutation(
+    () => publishPageDraft(page.id),
+    { notify: true, method: "POST" },
+    {
+      onSuccess: (data) => {
+        onDraftPublished(data)
+        draftQuery.refetch()
+      },
+    },
+  )
+  const discardDraftMutation = useToastMutation(
+    () => deletePageDraft(page.id),
+    { notify: true, method: "DELETE" },
+    { onSuccess: () => draftQuery.refetch() },
+  )
+  const scheduleMutation = useToastMutation(
+    () =>
+      setPagePublishingSchedule(page.id, {
+        publish_at: toDate(publishAt),
+        unpublish_at: toDate(unpublishAt),
+      }),
+    { notify: true, method: "PUT" },
+  )
+
+  const draft = draftQuery.data
+  return (
+    <div
+      className={css`
+        background: #f1f1f1;
+        border-left: 4px solid ${baseTheme.colors.blue[600]};
+        padding: 1rem 2rem;
+        margin-bottom: 1rem;
+      `}
+    >
+      <h2>{t("publishing")}</h2>
+      {draftQuery.isError && <ErrorBanner variant={"text"} error={draftQuery.error} />}
+      {draft && (
+        <div>
+          <p>
+            {draft.publish_at
+              ? t("page-has-a-draft-published-at", {
+                  updatedAt: draft.updated_at.toLocaleString(),
+                  publishAt: draft.publish_at.toLocaleString(),
+                })
+              : t("page-has-a-draft", { updatedAt: draft.updated_at.toLocaleString() })}
+          </p>
+          <div
+            className={css`
+              display: flex;
+              flex-wrap: wrap;
+              gap: 1rem;
+              margin-bottom: 1rem;
+            `}
+          >
+            <Button variant="secondary" size="medium" onClick={() => onLoadDraft(draft)}>
+              {t("load-draft")}
+            </Button>
+            <Button
+              variant="primary"
+              size="medium"
+              disabled={publishDraftMutation.isLoading}
+              onClick={() => {
+                if (confirm(t("are-you-sure-you-want-to-publish-the-draft"))) {
+                  publishDraftMutation.mutate()
+                }
+              }}
+            >
+              {t("publish-draft")}
+            </Button>
+            <Button
+              variant="reject"
+              size="medium"
+              disabled={discardDraftMutation.isLoading}
+              onClick={() => {
+                if (confirm(t("are-you-sure-you-want-to-discard-the-draft"))) {
+                  discardDraftMutation.mutate()
+                }
+              }}
+            >
+              {t("discard-draft")}
+            </Button>
+          </div>
+        </div>
+      )}
+      <DateTimeLocal
+        label={t("label-publish-draft-at")}
+        defaultValue={draftPublishAt}
+        onChange={setDraftPublishAt}
+      />
+      <Button
+        variant="secondary"
+        size="medium"
+        disabled={disabled || saveDraftMutation.isLoading}
+        onClick={() => saveDraftMutation.mutate()}
+      >
+        {t("save-as-draft")}
+      </Button>
+      <h3>{t("publishing-schedule")}</h3>
+      <p>{t("publishing-schedule-explanation")}</p>
+      <DateTimeLocal
+        label={t("label-publish-at")}
+        defaultValue={publishAt}
+        onChange={setPublishAt}
+      />
+      <DateTimeLocal
+        label={t("label-unpublish-at")}
+        defaultValue={unpublishAt}
+        onChange={setUnpublishAt}
+      />
+      <Button
+        variant="secondary"
+        size="medium"
+        disabled={scheduleMutation.isLoading}
+        onClick={() => scheduleMutation.mutate()}
+      >
+        {t("save-publishing-schedule")}
+      </Button>
+    </div>
+  )
+}
+
+export default PagePublishingPanel
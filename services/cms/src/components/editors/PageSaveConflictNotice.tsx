@@ -0,0 +1,70 @@
+import { css } from "@emotion/css"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import { PageSaveConflict } from "../../shared-module/bindings"
+import Button from "../../shared-module/components/Button"
+import { baseTheme } from "../../shared-module/styles"
+
+export interface PageSaveConflictNoticeProps {
+  conflict: PageSaveConflict
+  onLoadMerged: () => void
+  onLoadCurrent: () => void
+  onOverwrite: () => void
+}
+
+const PageSaveConflictNotice: React.FC<React.PropsWithChildren<PageSaveConflictNoticeProps>> = ({
+  conflict,
+  onLoadMerged,
+  onLoadCurrent,
+  onOverwrite,
+}) => {
+  const { t } = useTranslation()
+  return (
+    <div
+      className={css`
+        background: #f1f1f1;
+        border-left: 4px solid ${baseTheme.colors.crimson[700]};
+        padding: 1rem 2rem;
+        margin-bottom: 1rem;
+      `}
+    >
+      <h2>{t("page-save-conflict-title")}</h2>
+      <p>
+        {conflict.merged
+          ? t("page-save-conflict-can-be-combined")
+          : t("page-save-conflict-cannot-be-combined")}
+      </p>
+      <div
+        className={css`
+          display: flex;
+          flex-wrap: wrap;
+          gap: 1rem;
+          margin-top: 1rem;
+        `}
+      >
+        {conflict.merged && (
+          <Button variant="primary" size="medium" onClick={onLoadMerged}>
+            {t("load-combined-version")}
+          </Button>
+        )}
+        <Button variant="secondary" size="medium" onClick={onLoadCurrent}>
+          {t("discard-my-changes-and-load-their-version")}
+        </Button>
+        <Button
+          variant="reject"
+          size="medium"
+          onClick={() => {
+            if (confirm(t("are-you-sure-you-want-to-overwrite-their-changes"))) {
+              onOverwrite()
+            }
+          }}
+        >
+          {t("overwrite-their-changes")}
+        </Button>
+      </div>
+    </div>
+  )
+}
+
+export default PageSaveConflictNotice
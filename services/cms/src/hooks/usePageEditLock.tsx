@@ -0,0 +1,67 @@
+import { useQuery, useQueryClient } from "@tanstack/react-query"
+import { useEffect } from "react"
+
+import {
+  acquirePageEditLock,
+  releasePageEditLock,
+  renewPageEditLock,
+  stealPageEditLock,
+} from "../services/backend/pages"
+import { PageEditStatus } from "../shared-module/bindings"
+import useUserInfo from "../shared-module/hooks/useUserInfo"
+
+const HEARTBEAT_INTERVAL_MS = 20000
+
+/**
+ * Holds the edit lock of the page while the editor is open and tells who else has the page open.
+ */
+const usePageEditLock = (pageId: string) => {
+  const queryClient = useQueryClient()
+  const userInfo = useUserInfo()
+  const userId = userInfo.data?.user_id ?? null
+  const queryKey = [`page-edit-lock-${pageId}`]
+
+  const status = useQuery(
+    queryKey,
+    () => {
+      const previous = queryClient.getQueryData<PageEditStatus>(queryKey)
+      // Only acquire the lock when we don't hold it so that the heartbeat does not take the lock back from someone who took it over
+      if (previous?.lock && previous.lock.user_id === userId) {
+        return renewPageEditLock(pageId)
+      }
+      return acquirePageEditLock(pageId)
+    },
+    {
+      enabled: userId !== null,
+      refetchInterval: HEARTBEAT_INTERVAL_MS,
+      refetchIntervalInBackground: true,
+      refetchOnWindowFocus: false,
+      retry: false,
+    },
+  )
+
+  useEffect(() => {
+    const releaseOnPageHide = () => {
+      // The request has to outlive the page, so it can't be sent with axios
+      navigator.sendBeacon(`/api/v0/cms/pages/${pageId}/edit-lock/release`)
+    }
+    window.addEventListener("pagehide", releaseOnPageHide)
+    return () => {
+      window.removeEventListener("pagehide", releaseOnPageHide)
+      releasePageEditLock(pageId).catch((err) => console.error("Failed to release edit lock", err))
+    }
+  }, [pageId])
+
+  const takeOver = async () => {
+    const newStatus = await stealPageEditLock(pageId)
+    queryClient.setQueryData(queryKey, newStatus)
+  }
+
+  const lock = status.data?.lock ?? null
+  const lockedBySomeoneElse = lock !== null && lock.user_id !== userId
+  const otherEditors = (status.data?.editors ?? []).filter((editor) => editor.user_id !== userId)
+
+  return { status, lock, lockedBySomeoneElse, otherEditors, takeOver, refresh: status.refetch }
+}
+
+export default usePageEditLock
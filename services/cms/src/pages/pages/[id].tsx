@@ -43,9 +43,10 @@ const Pages = ({ query }: PagesProps) => {
           url_path: data.page.url_path,
           title: data.page.title,
           chapter_id: data.page.chapter_id,
+          base_history_id: data.history_id,
         }).content,
       }
-      return page
+      return { page, historyId: data.history_id }
     },
     onSuccess: () => {
       setNeedToRunMigrationsAndValidations(true)
@@ -74,9 +75,10 @@ const Pages = ({ query }: PagesProps) => {
       {getPage.isError && <ErrorBanner variant={"readOnly"} error={getPage.error} />}
       {getPage.isLoading && <Spinner variant={"medium"} />}
       {getPage.isSuccess && (
-        <PageContext.Provider value={{ page: getPage.data }}>
+        <PageContext.Provider value={{ page: getPage.data.page }}>
           <PageEditor
-            data={getPage.data}
+            data={getPage.data.page}
+            historyId={getPage.data.historyId}
             saveMutation={mutate}
             needToRunMigrationsAndValidations={needToRunMigrationsAndValidations}
             setNeedToRunMigrationsAndValidations={setNeedToRunMigrationsAndValidations}
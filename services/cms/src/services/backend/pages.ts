@@ -1,11 +1,19 @@
 import {
   CmsPageUpdate,
   ContentManagementPage,
+  Page,
+  PageDraft,
+  PageDraftUpdate,
+  PageEditStatus,
   PageInfo,
   PageNavigationInformation,
+  PagePublishingSchedule,
 } from "../../shared-module/bindings"
 import {
   isContentManagementPage,
+  isPage,
+  isPageDraft,
+  isPageEditStatus,
   isPageInfo,
   isPageNavigationInformation,
 } from "../../shared-module/bindings.guard"
@@ -39,3 +47,53 @@ export const updateExistingPage = async (
   })
   return validateResponse(response, isContentManagementPage)
 }
+
+export const acquirePageEditLock = async (pageId: string): Promise<PageEditStatus> => {
+  const response = await cmsClient.post(`/pages/${pageId}/edit-lock/acquire`)
+  return validateResponse(response, isPageEditStatus)
+}
+
+export const renewPageEditLock = async (pageId: string): Promise<PageEditStatus> => {
+  const response = await cmsClient.post(`/pages/${pageId}/edit-lock/renew`)
+  return validateResponse(response, isPageEditStatus)
+}
+
+export const stealPageEditLock = async (pageId: string): Promise<PageEditStatus> => {
+  const response = await cmsClient.post(`/pages/${pageId}/edit-lock/steal`)
+  return validateResponse(response, isPageEditStatus)
+}
+
+export const releasePageEditLock = async (pageId: string): Promise<void> => {
+  await cmsClient.post(`/pages/${pageId}/edit-lock/release`)
+}
+
+export const fetchPageDraft = async (pageId: string): Promise<PageDraft | null> => {
+  const response = await cmsClient.get(`/pages/${pageId}/draft`, { responseType: "json" })
+  return validateResponse(response, isUnion(isPageDraft, isNull))
+}
+
+export const savePageDraft = async (pageId: string, data: PageDraftUpdate): Promise<PageDraft> => {
+  const response = await cmsClient.put(`/pages/${pageId}/draft`, data, {
+    headers: { "Content-Type": "application/json" },
+  })
+  return validateResponse(response, isPageDraft)
+}
+
+export const deletePageDraft = async (pageId: string): Promise<void> => {
+  await cmsClient.delete(`/pages/${pageId}/draft`)
+}
+
+export const publishPageDraft = async (pageId: string): Promise<ContentManagementPage> => {
+  const response = await cmsClient.post(`/pages/${pageId}/draft/publish`)
+  return validateResponse(response, isContentManagementPage)
+}
+
+export const setPagePublishingSchedule = async (
+  pageId: string,
+  data: PagePublishingSchedule,
+): Promise<Page> => {
+  const response = await cmsClient.put(`/pages/${pageId}/publishing-schedule`, data, {
+    headers: { "Content-Type": "application/json" },
+  })
+  return validateResponse(response, isPage)
+}
@@ -28,6 +28,11 @@ export interface UnnormalizedDocument {
   title: string
   urlPath: string
   chapterId: string | null
+  /**
+   * The version of the page the document is based on. The backend rejects the save if someone else has saved
+   * the page after this version.
+   */
+  baseHistoryId: string | null
 }
 
 /**
@@ -121,6 +126,7 @@ export function normalizeDocument(args: UnnormalizedDocument): CmsPageUpdate {
     exercise_tasks: exerciseTasks,
     title: args.title,
     url_path: args.urlPath,
+    base_history_id: args.baseHistoryId,
   }
 }
 
@@ -207,6 +213,7 @@ export function denormalizeDocument(input: CmsPageUpdate): UnnormalizedDocument
     title: input.title,
     urlPath: input.url_path,
     chapterId: input.chapter_id,
+    baseHistoryId: input.base_history_id,
   }
 
   return res
@@ -70,6 +70,10 @@ const exampleCMSPageUpdate: CmsPageUpdate = {
         accepting_threshold: 0.5,
         peer_reviews_to_give: 1,
         peer_reviews_to_receive: 1,
+        calibration_samples_to_review: 0,
+        timeout_in_days: 21,
+        timeout_action: "MoveToManualReview",
+        manual_review_timeout_in_days: 90,
       },
       peer_review_questions: [
         {
@@ -79,6 +83,7 @@ const exampleCMSPageUpdate: CmsPageUpdate = {
           order_number: 0,
           question: "how about...",
           question_type: "Essay",
+          rubric: null,
         },
       ],
       use_course_default_peer_review_config: false,
@@ -118,6 +123,7 @@ const exampleCMSPageUpdate: CmsPageUpdate = {
   url_path: "/path/to/page",
   title: "Example page",
   chapter_id: "babb2322-8bdf-417f-be05-89b2263f4851",
+  base_history_id: "4b6a3b8e-6f4d-4c1a-9a59-2d1f6b8e7c3d",
 }
 
 // Doing this separately so that we get type errors when the type changes
@@ -210,6 +216,7 @@ const exampleUnnormalizedDocument: UnnormalizedDocument = {
   title: "Example page",
   urlPath: "/path/to/page",
   chapterId: "babb2322-8bdf-417f-be05-89b2263f4851",
+  baseHistoryId: "4b6a3b8e-6f4d-4c1a-9a59-2d1f6b8e7c3d",
 }
 
 test("We get the original document if we first denormalize and then normalize", async () => {
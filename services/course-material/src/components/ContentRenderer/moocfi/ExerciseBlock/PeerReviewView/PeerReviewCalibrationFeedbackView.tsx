@@ -0,0 +1,85 @@
+import { css } from "@emotion/css"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  PeerReviewCalibrationFeedback,
+  PeerReviewQuestion,
+} from "../../../../../shared-module/bindings"
+import Button from "../../../../../shared-module/components/Button"
+import { baseTheme } from "../../../../../shared-module/styles"
+
+interface PeerReviewCalibrationFeedbackViewProps {
+  feedback: PeerReviewCalibrationFeedback
+  peerReviewQuestions: PeerReviewQuestion[]
+  onContinue: () => void
+}
+
+const PeerReviewCalibrationFeedbackView: React.FC<
+  React.PropsWithChildren<PeerReviewCalibrationFeedbackViewProps>
+> = ({ feedback, peerReviewQuestions, onContinue }) => {
+  const { t } = useTranslation()
+
+  return (
+    <div
+      className={css`
+        margin-top: 3rem;
+      `}
+    >
+      <h4
+        className={css`
+          padding-bottom: 0.5rem;
+          font-weight: 600;
+          font-size: 20px;
+        `}
+      >
+        {t("title-calibration-feedback")}
+      </h4>
+      <p>
+        {t("calibration-samples-reviewed", {
+          reviewed: feedback.calibration_samples_reviewed,
+          total: feedback.calibration_samples_to_review,
+        })}
+      </p>
+      {feedback.question_feedback.map((questionFeedback) => {
+        const question = peerReviewQuestions.find(
+          (q) => q.id === questionFeedback.peer_review_question_id,
+        )
+        return (
+          <div
+            key={questionFeedback.peer_review_question_id}
+            className={css`
+              border-left: 4px solid ${baseTheme.colors.blue[300]};
+              padding: 0.5rem 1rem;
+              margin-bottom: 1rem;
+            `}
+          >
+            <p
+              className={css`
+                font-weight: 600;
+              `}
+            >
+              {question?.question}
+            </p>
+            <p>
+              {questionFeedback.given_number_data === null
+                ? t("calibration-question-not-answered", {
+                    reference: questionFeedback.reference_number_data,
+                  })
+                : t("calibration-question-answer-compared-to-reference", {
+                    given: questionFeedback.given_number_data,
+                    reference: questionFeedback.reference_number_data,
+                  })}
+            </p>
+            {questionFeedback.explanation && <p>{questionFeedback.explanation}</p>}
+          </div>
+        )
+      })}
+      <Button variant="primary" size="medium" onClick={onContinue}>
+        {t("continue")}
+      </Button>
+    </div>
+  )
+}
+
+export default PeerReviewCalibrationFeedbackView
@@ -29,6 +29,7 @@ const EssayPeerReviewQuestion: React.FC<React.PropsWithChildren<PeerReviewQuesti
           setPeerReviewQuestionAnswer({
             text_data: value,
             number_data: null,
+            rubric_level_ids: null,
           })
         }
         value={peerReviewQuestionAnswer?.text_data ?? ""}
@@ -0,0 +1,97 @@
+import { css } from "@emotion/css"
+import { useTranslation } from "react-i18next"
+
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+import { PeerReviewQuestionProps } from "."
+
+const RubricPeerReviewQuestion: React.FC<React.PropsWithChildren<PeerReviewQuestionProps>> = ({
+  question,
+  setPeerReviewQuestionAnswer,
+  peerReviewQuestionAnswer,
+}) => {
+  const { t } = useTranslation()
+  const selectedLevelIds = peerReviewQuestionAnswer?.rubric_level_ids ?? []
+
+  const selectLevel = (criterionLevelIds: string[], levelId: string) => {
+    // Only one level can be selected for each criterion
+    const newSelectedLevelIds = [
+      ...selectedLevelIds.filter((id) => !criterionLevelIds.includes(id)),
+      levelId,
+    ]
+    setPeerReviewQuestionAnswer({
+      text_data: null,
+      number_data: null,
+      rubric_level_ids: newSelectedLevelIds,
+    })
+  }
+
+  return (
+    <div
+      className={css`
+        margin-bottom: 2rem;
+      `}
+    >
+      <div
+        className={css`
+          margin-bottom: 1rem;
+        `}
+      >
+        {question.question}
+        {question.answer_required && " *"}
+      </div>
+      {question.rubric?.criteria.map((criterion) => {
+        const criterionLevelIds = criterion.levels.map((level) => level.id)
+        return (
+          <fieldset
+            key={criterion.id}
+            className={css`
+              border: 1px solid ${baseTheme.colors.clear[300]};
+              margin-bottom: 1rem;
+              padding: 0.5rem 1rem;
+            `}
+          >
+            <legend
+              className={css`
+                font-weight: 500;
+                padding: 0 0.5rem;
+              `}
+            >
+              {criterion.name}
+            </legend>
+            {criterion.levels.map((level) => (
+              <label
+                key={level.id}
+                className={css`
+                  display: flex;
+                  gap: 0.5rem;
+                  align-items: baseline;
+                  margin: 0.5rem 0;
+                `}
+              >
+                <input
+                  type="radio"
+                  name={`rubric-criterion-${criterion.id}`}
+                  checked={selectedLevelIds.includes(level.id)}
+                  onChange={() => selectLevel(criterionLevelIds, level.id)}
+                />
+                <span>
+                  {level.description}{" "}
+                  <span
+                    className={css`
+                      color: ${baseTheme.colors.gray[400]};
+                    `}
+                  >
+                    ({t("n-points", { n: level.points })})
+                  </span>
+                </span>
+              </label>
+            ))}
+          </fieldset>
+        )
+      })}
+    </div>
+  )
+}
+
+export default RubricPeerReviewQuestion
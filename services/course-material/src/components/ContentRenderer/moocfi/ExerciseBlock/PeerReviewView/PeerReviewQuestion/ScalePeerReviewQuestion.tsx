@@ -17,6 +17,7 @@ const ScalePeerReviewQuestion: React.FC<React.PropsWithChildren<PeerReviewQuesti
           setPeerReviewQuestionAnswer({
             text_data: null,
             number_data: value,
+            rubric_level_ids: null,
           })
         }
       />
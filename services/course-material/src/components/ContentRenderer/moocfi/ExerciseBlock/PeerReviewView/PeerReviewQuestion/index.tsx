@@ -4,6 +4,7 @@ import {
 } from "../../../../../../shared-module/bindings"
 
 import EssayPeerReviewQuestion from "./EssayPeerReviewQuestion"
+import RubricPeerReviewQuestion from "./RubricPeerReviewQuestion"
 import ScalePeerReviewQuestion from "./ScalePeerReviewQuestion"
 
 export interface PeerReviewQuestionProps {
@@ -37,6 +38,15 @@ const PeerReviewQuestion: React.FC<React.PropsWithChildren<PeerReviewQuestionPro
       />
     )
   }
+  if (question.question_type === "Rubric") {
+    return (
+      <RubricPeerReviewQuestion
+        question={question}
+        setPeerReviewQuestionAnswer={setPeerReviewQuestionAnswer}
+        peerReviewQuestionAnswer={peerReviewQuestionAnswer}
+      />
+    )
+  }
   return null
 }
 
@@ -5,14 +5,23 @@ import { useTranslation } from "react-i18next"
 import {
   PeerReviewQuestion,
   PeerReviewQuestionSubmission,
+  PeerReviewSubmissionFlag,
+  PeerReviewSubmissionRating,
 } from "../../../../../../shared-module/bindings"
 
 import Essay from "./Essay"
 import Likert from "./Likert"
+import ReceivedPeerReviewFeedback from "./ReceivedPeerReviewFeedback"
+import Rubric from "./Rubric"
 interface ReviewProps {
+  exerciseId: string
+  peerReviewSubmissionId: string
   orderNumber: number
   review: PeerReviewQuestionSubmission[]
   questions: PeerReviewQuestion[]
+  rating: PeerReviewSubmissionRating | undefined
+  flag: PeerReviewSubmissionFlag | undefined
+  onFeedbackChange: () => void
 }
 
 const Wrapper = styled.div`
@@ -26,9 +35,14 @@ const Heading = styled.div`
 `
 
 const PeerReviewQuestionAnswer: React.FunctionComponent<ReviewProps> = ({
+  exerciseId,
+  peerReviewSubmissionId,
   orderNumber,
   review,
   questions,
+  rating,
+  flag,
+  onFeedbackChange,
 }) => {
   const { t } = useTranslation()
   // const { id, number_data, text_data, peer_review_question_id } = review
@@ -36,18 +50,39 @@ const PeerReviewQuestionAnswer: React.FunctionComponent<ReviewProps> = ({
   return (
     <Wrapper>
       <Heading>{`${t("peer-review")} #${orderNumber + 1}`}</Heading>
-      {review?.map(({ id, number_data, text_data, peer_review_question_id }, index) => {
-        const questionIndex = questions.findIndex((q) => q.id === peer_review_question_id)
-        const question = questions[questionIndex].question
-        return (
-          <>
-            {text_data && <Essay key={id} question={question} content={text_data} index={index} />}
-            {number_data !== null && (
-              <Likert key={id} question={question} content={number_data} index={index} />
-            )}
-          </>
-        )
-      })}
+      {review?.map(
+        ({ id, number_data, text_data, rubric_level_ids, peer_review_question_id }, index) => {
+          const questionIndex = questions.findIndex((q) => q.id === peer_review_question_id)
+          const question = questions[questionIndex].question
+          const rubric = questions[questionIndex].rubric
+          return (
+            <>
+              {text_data && (
+                <Essay key={id} question={question} content={text_data} index={index} />
+              )}
+              {number_data !== null && (
+                <Likert key={id} question={question} content={number_data} index={index} />
+              )}
+              {rubric !== null && rubric_level_ids !== null && (
+                <Rubric
+                  key={id}
+                  question={question}
+                  rubric={rubric}
+                  selectedLevelIds={rubric_level_ids}
+                  index={index}
+                />
+              )}
+            </>
+          )
+        },
+      )}
+      <ReceivedPeerReviewFeedback
+        exerciseId={exerciseId}
+        peerReviewSubmissionId={peerReviewSubmissionId}
+        rating={rating}
+        flag={flag}
+        onChange={onFeedbackChange}
+      />
     </Wrapper>
   )
 }
@@ -0,0 +1,132 @@
+import { css } from "@emotion/css"
+import styled from "@emotion/styled"
+import * as React from "react"
+import { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  postReceivedPeerReviewFlag,
+  putReceivedPeerReviewRating,
+} from "../../../../../../services/backend"
+import {
+  PeerReviewSubmissionFlag,
+  PeerReviewSubmissionRating,
+} from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import TextAreaField from "../../../../../../shared-module/components/InputFields/TextAreaField"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+const HELPFULNESS_OPTIONS = [1, 2, 3, 4, 5]
+
+const Wrapper = styled.div`
+  padding: 1rem;
+  border-top: 2px solid #ebedee;
+
+  p {
+    color: #535a66;
+    margin-bottom: 0.5rem;
+  }
+`
+
+interface ReceivedPeerReviewFeedbackProps {
+  exerciseId: string
+  peerReviewSubmissionId: string
+  rating: PeerReviewSubmissionRating | undefined
+  flag: PeerReviewSubmissionFlag | undefined
+  onChange: () => void
+}
+
+const ReceivedPeerReviewFeedback: React.FunctionComponent<ReceivedPeerReviewFeedbackProps> = ({
+  exerciseId,
+  peerReviewSubmissionId,
+  rating,
+  flag,
+  onChange,
+}) => {
+  const { t } = useTranslation()
+  const [reportFormOpen, setReportFormOpen] = useState(false)
+  const [reason, setReason] = useState("")
+
+  const rateMutation = useToastMutation(
+    (helpfulness: number) =>
+      putReceivedPeerReviewRating(exerciseId, peerReviewSubmissionId, { helpfulness }),
+    { notify: false },
+    { onSuccess: onChange },
+  )
+
+  const reportMutation = useToastMutation(
+    () => postReceivedPeerReviewFlag(exerciseId, peerReviewSubmissionId, { reason }),
+    { notify: true, method: "POST" },
+    {
+      onSuccess: () => {
+        setReportFormOpen(false)
+        setReason("")
+        onChange()
+      },
+    },
+  )
+
+  return (
+    <Wrapper>
+      <p>{t("peer-review-helpfulness-question")}</p>
+      <div
+        className={css`
+          display: flex;
+          gap: 0.5rem;
+          margin-bottom: 1rem;
+        `}
+      >
+        {HELPFULNESS_OPTIONS.map((helpfulness) => (
+          <Button
+            key={helpfulness}
+            variant={rating?.helpfulness === helpfulness ? "primary" : "secondary"}
+            size="medium"
+            aria-pressed={rating?.helpfulness === helpfulness}
+            disabled={rateMutation.isLoading}
+            onClick={() => rateMutation.mutate(helpfulness)}
+          >
+            {helpfulness}
+          </Button>
+        ))}
+      </div>
+      {flag && flag.resolution === null && <p>{t("peer-review-reported-waiting-for-teacher")}</p>}
+      {flag && flag.resolution === "ReviewKept" && (
+        <p>{t("peer-review-report-resolved-review-kept")}</p>
+      )}
+      {!flag && !reportFormOpen && (
+        <Button variant="tertiary" size="medium" onClick={() => setReportFormOpen(true)}>
+          {t("button-text-report-peer-review")}
+        </Button>
+      )}
+      {!flag && reportFormOpen && (
+        <div
+          className={css`
+            border-left: 4px solid ${baseTheme.colors.red[300]};
+            padding-left: 1rem;
+          `}
+        >
+          <TextAreaField
+            label={t("label-report-reason")}
+            value={reason}
+            onChange={(value) => setReason(value)}
+            autoResize
+          />
+          <Button
+            variant="primary"
+            size="medium"
+            disabled={reason.trim() === "" || reportMutation.isLoading}
+            onClick={() => reportMutation.mutate()}
+          >
+            {t("submit")}
+          </Button>
+          <Button variant="secondary" size="medium" onClick={() => setReportFormOpen(false)}>
+            {t("cancel")}
+          </Button>
+        </div>
+      )}
+    </Wrapper>
+  )
+}
+
+export default ReceivedPeerReviewFeedback
@@ -0,0 +1,68 @@
+import styled from "@emotion/styled"
+import * as React from "react"
+import { useTranslation } from "react-i18next"
+
+import { PeerReviewRubric } from "../../../../../../shared-module/bindings"
+
+const Wrapper = styled.div`
+  padding: 1rem;
+
+  h4 {
+    font-size: 16px !important;
+    color: #215887;
+    line-height: 1.4;
+    margin-bottom: 0.8rem;
+    font-weight: 500;
+  }
+
+  ul {
+    margin: 0 0 0.5rem 0;
+    padding-left: 1.2rem;
+  }
+
+  li,
+  span {
+    color: #535a66;
+  }
+`
+
+interface RubricProps {
+  question: string
+  index: number
+  rubric: PeerReviewRubric
+  selectedLevelIds: string[]
+}
+
+const Rubric: React.FunctionComponent<RubricProps> = ({
+  question,
+  index,
+  rubric,
+  selectedLevelIds,
+}) => {
+  const { t } = useTranslation()
+  const selectedLevels = rubric.criteria.flatMap((criterion) =>
+    criterion.levels
+      .filter((level) => selectedLevelIds.includes(level.id))
+      .map((level) => ({ criterion, level })),
+  )
+  const points = selectedLevels.reduce((sum, { level }) => sum + level.points, 0)
+  const maxPoints = rubric.criteria.reduce(
+    (sum, criterion) => sum + Math.max(0, ...criterion.levels.map((level) => level.points)),
+    0,
+  )
+  return (
+    <Wrapper>
+      <h4>{`${t("question")} ${index + 1}: ${question}`}</h4>
+      <ul>
+        {selectedLevels.map(({ criterion, level }) => (
+          <li key={level.id}>
+            {`${criterion.name}: ${level.description} (${t("n-points", { n: level.points })})`}
+          </li>
+        ))}
+      </ul>
+      <span>{t("rubric-points-total", { points, maxPoints })}</span>
+    </Wrapper>
+  )
+}
+
+export default Rubric
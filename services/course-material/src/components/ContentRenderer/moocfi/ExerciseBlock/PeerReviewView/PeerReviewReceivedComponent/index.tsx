@@ -9,6 +9,8 @@ import { fetchPeerReviewDataReceivedByExerciseId } from "../../../../../../servi
 import {
   PeerReviewQuestion,
   PeerReviewQuestionSubmission,
+  PeerReviewSubmissionFlag,
+  PeerReviewSubmissionRating,
 } from "../../../../../../shared-module/bindings"
 import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
 import Spinner from "../../../../../../shared-module/components/Spinner"
@@ -112,6 +114,8 @@ const PeerReview: React.FunctionComponent<PeerReviewProps> = ({ id, submissionId
   const { t } = useTranslation()
   let result: PeerReviewQuestionSubmission[] = []
   let questions: PeerReviewQuestion[] = []
+  let ratings: PeerReviewSubmissionRating[] = []
+  let flags: PeerReviewSubmissionFlag[] = []
 
   const getPeerReviewReceived = useQuery(
     [`exercise-${id}-exercise-slide-submission-${submissionId}-peer-reviews-received`],
@@ -130,9 +134,16 @@ const PeerReview: React.FunctionComponent<PeerReviewProps> = ({ id, submissionId
     getPeerReviewReceived.isSuccess &&
     getPeerReviewReceived.data.peer_review_question_submissions.length > 0
   ) {
-    const { peer_review_questions, peer_review_question_submissions } = getPeerReviewReceived.data
+    const {
+      peer_review_questions,
+      peer_review_question_submissions,
+      peer_review_submission_ratings,
+      peer_review_submission_flags,
+    } = getPeerReviewReceived.data
     result = peer_review_question_submissions
     questions = peer_review_questions
+    ratings = peer_review_submission_ratings
+    flags = peer_review_submission_flags
   }
 
   const ordered = result.sort((a, b) => b.created_at.getTime() - a.created_at.getTime())
@@ -149,14 +160,21 @@ const PeerReview: React.FunctionComponent<PeerReviewProps> = ({ id, submissionId
           {t("peer-reviews-received-from-other-student")}
           <Notification>{Object.keys(groupByPeerReviewSubmissionId).length ?? "0"}</Notification>
         </summary>
-        {Object.values(groupByPeerReviewSubmissionId).map((item, index) => (
-          <PeerReviewQuestionAnswer
-            orderNumber={index}
-            key={index}
-            review={item}
-            questions={questions}
-          />
-        ))}
+        {Object.entries(groupByPeerReviewSubmissionId).map(
+          ([peerReviewSubmissionId, item], index) => (
+            <PeerReviewQuestionAnswer
+              exerciseId={id}
+              peerReviewSubmissionId={peerReviewSubmissionId}
+              orderNumber={index}
+              key={index}
+              review={item}
+              questions={questions}
+              rating={ratings.find((r) => r.peer_review_submission_id === peerReviewSubmissionId)}
+              flag={flags.find((f) => f.peer_review_submission_id === peerReviewSubmissionId)}
+              onFeedbackChange={() => getPeerReviewReceived.refetch()}
+            />
+          ),
+        )}
       </details>
     </Wrapper>
   )
@@ -6,9 +6,13 @@ import { useTranslation } from "react-i18next"
 import { getExerciseBlockBeginningScrollingId } from ".."
 import {
   fetchPeerReviewDataByExerciseId,
+  postPeerReviewCalibrationSubmission,
   postPeerReviewSubmission,
 } from "../../../../../services/backend"
-import { CourseMaterialPeerReviewQuestionAnswer } from "../../../../../shared-module/bindings"
+import {
+  CourseMaterialPeerReviewQuestionAnswer,
+  PeerReviewCalibrationFeedback,
+} from "../../../../../shared-module/bindings"
 import Button from "../../../../../shared-module/components/Button"
 import BreakFromCentered from "../../../../../shared-module/components/Centering/BreakFromCentered"
 import Centered from "../../../../../shared-module/components/Centering/Centered"
@@ -22,6 +26,7 @@ import getGuestPseudonymousUserId from "../../../../../shared-module/utils/getGu
 import { exerciseTaskGradingToExerciseTaskGradingResult } from "../../../../../shared-module/utils/typeMappter"
 import ExerciseTaskIframe from "../ExerciseTaskIframe"
 
+import PeerReviewCalibrationFeedbackView from "./PeerReviewCalibrationFeedbackView"
 import PeerReviewQuestion from "./PeerReviewQuestion"
 
 import { getPeerReviewBeginningScrollingId, PeerReviewViewProps } from "."
@@ -36,6 +41,8 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
   const [answers, setAnswers] = useState<Map<string, CourseMaterialPeerReviewQuestionAnswer>>(
     new Map(),
   )
+  const [calibrationFeedback, setCalibrationFeedback] =
+    useState<PeerReviewCalibrationFeedback | null>(null)
 
   const query = useQuery([`exercise-${exerciseId}-peer-review`], () => {
     return fetchPeerReviewDataByExerciseId(exerciseId)
@@ -60,6 +67,11 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
         return true
       }
 
+      if (answer.rubric_level_ids !== null) {
+        // Every criterion of a required rubric needs a selected level
+        return answer.rubric_level_ids.length >= (question.rubric?.criteria.length ?? 0)
+      }
+
       if (answer.text_data !== null && answer.text_data.trim() !== "") {
         return true
       }
@@ -73,6 +85,15 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
       if (!peerReviewData || !peerReviewData.answer_to_review) {
         return
       }
+      if (peerReviewData.is_calibration_sample) {
+        const feedback = await postPeerReviewCalibrationSubmission(exerciseId, {
+          exercise_slide_submission_id: peerReviewData.answer_to_review.exercise_slide_submission_id,
+          peer_review_config_id: peerReviewData.peer_review_config.id,
+          peer_review_question_answers: Array.from(answers.values()),
+        })
+        setCalibrationFeedback(feedback)
+        return
+      }
       return await postPeerReviewSubmission(exerciseId, {
         exercise_slide_submission_id: peerReviewData.answer_to_review.exercise_slide_submission_id,
         peer_review_config_id: peerReviewData.peer_review_config.id,
@@ -82,6 +103,11 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
     { notify: true, method: "POST" },
     {
       onSuccess: async () => {
+        if (peerReviewData?.is_calibration_sample) {
+          // The student reads the feedback first and then continues to the next answer
+          setAnswers(new Map())
+          return
+        }
         // still old data because we have't refetched yet
         const givenEnoughReviews =
           (peerReviewData?.peer_review_config.peer_reviews_to_give ?? Number.MAX_VALUE) <=
@@ -124,6 +150,25 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
     return <Spinner variant="medium" />
   }
 
+  if (calibrationFeedback) {
+    return (
+      <PeerReviewCalibrationFeedbackView
+        feedback={calibrationFeedback}
+        peerReviewQuestions={query.data.peer_review_questions}
+        onContinue={async () => {
+          await query.refetch()
+          setCalibrationFeedback(null)
+          setTimeout(() => {
+            document
+              .getElementById(getPeerReviewBeginningScrollingId(exerciseId))
+              // eslint-disable-next-line i18next/no-literal-string
+              ?.scrollIntoView({ behavior: "smooth" })
+          }, 100)
+        }}
+      />
+    )
+  }
+
   if (!peerReviewData?.answer_to_review?.course_material_exercise_tasks) {
     return (
       <div>
@@ -178,7 +223,11 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
           </h4>
         </div>
         <div>
-          <p>{t("peer-review-instructions")}</p>
+          <p>
+            {peerReviewData.is_calibration_sample
+              ? t("peer-review-calibration-instructions")
+              : t("peer-review-instructions")}
+          </p>
         </div>
       </div>
 
@@ -253,6 +302,8 @@ const PeerReviewViewImpl: React.FC<React.PropsWithChildren<PeerReviewViewProps>>
                 const answers = new Map(prev)
                 if (
                   newAnswer.number_data === null &&
+                  (newAnswer.rubric_level_ids === null ||
+                    newAnswer.rubric_level_ids.length === 0) &&
                   (newAnswer.text_data === null || newAnswer.text_data.trim() === "")
                 ) {
                   // If everything in the answer is null, transform the answer to not answered
@@ -0,0 +1,55 @@
+import { css } from "@emotion/css"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import Button from "../../shared-module/components/Button"
+import useToastMutation from "../../shared-module/hooks/useToastMutation"
+import useUserInfo from "../../shared-module/hooks/useUserInfo"
+import { stopImpersonation } from "../../shared-module/services/backend/auth"
+import { baseTheme } from "../../shared-module/styles"
+
+/** Shown while a teacher is viewing the course material as one of the students. */
+const ImpersonationBanner: React.FC = () => {
+  const { t } = useTranslation()
+  const userInfo = useUserInfo()
+  const stopMutation = useToastMutation(
+    stopImpersonation,
+    { notify: false },
+    { onSuccess: () => window.location.reload() },
+  )
+
+  const impersonation = userInfo.data?.impersonation
+  if (!impersonation) {
+    return null
+  }
+  return (
+    <div
+      role="status"
+      className={css`
+        display: flex;
+        align-items: center;
+        justify-content: space-between;
+        gap: 1rem;
+        padding: 0.5rem 1rem;
+        background: ${baseTheme.colors.yellow[200]};
+        color: ${baseTheme.colors.gray[700]};
+      `}
+    >
+      <span>
+        {t("impersonation-banner-text", {
+          "expires-at": impersonation.expires_at.toLocaleTimeString(),
+        })}
+      </span>
+      <Button
+        variant="secondary"
+        size="medium"
+        disabled={stopMutation.isLoading}
+        onClick={() => stopMutation.mutate()}
+      >
+        {t("button-text-stop-impersonation")}
+      </Button>
+    </div>
+  )
+}
+
+export default ImpersonationBanner
@@ -19,6 +19,7 @@ import {
 import SearchDialog from "../SearchDialog"
 import UserNavigationControls from "../navigation/UserNavigationControls"
 
+import ImpersonationBanner from "./ImpersonationBanner"
 import ScrollIndicator from "./ScrollIndicator"
 
 const LANGUAGE_SELECTION_PLACEMENTPLACEMENT = "bottom-end"
@@ -65,6 +66,7 @@ const Layout: React.FC<React.PropsWithChildren<LayoutProps>> = ({
           min-height: 100vh;
         `}
       >
+        <ImpersonationBanner />
         <ScrollIndicator />
         <NavBar variant={navVariant ?? "simple"}>
           <NavContainer>
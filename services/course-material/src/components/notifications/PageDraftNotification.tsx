@@ -0,0 +1,37 @@
+import { css } from "@emotion/css"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import BreakFromCentered from "../../shared-module/components/Centering/BreakFromCentered"
+import { baseTheme } from "../../shared-module/styles"
+
+interface PageDraftNotificationProps {
+  isDraft: boolean
+}
+
+const PageDraftNotification: React.FC<React.PropsWithChildren<PageDraftNotificationProps>> = ({
+  isDraft,
+}) => {
+  const { t } = useTranslation()
+  if (!isDraft) {
+    return null
+  }
+
+  return (
+    <BreakFromCentered sidebar={false}>
+      <div
+        className={css`
+          text-align: center;
+          background: ${baseTheme.colors.blue[600]};
+          color: ${baseTheme.colors.clear[100]};
+          padding: 2rem 0rem;
+          font-size: ${baseTheme.fontSizes[3]}px;
+        `}
+      >
+        {t("page-draft-text")}
+      </div>
+    </BreakFromCentered>
+  )
+}
+
+export default PageDraftNotification
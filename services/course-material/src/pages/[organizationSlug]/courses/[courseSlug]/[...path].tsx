@@ -8,6 +8,7 @@ import PageNotFound from "../../../../components/PageNotFound"
 import Layout from "../../../../components/layout/Layout"
 import CourseMaterialPageBreadcrumbs from "../../../../components/navigation/CourseMaterialPageBreadcrumbs"
 import CourseTestModeNotification from "../../../../components/notifications/CourseTestModeNotification"
+import PageDraftNotification from "../../../../components/notifications/PageDraftNotification"
 import PageContext, {
   CoursePageDispatch,
   getDefaultPageState,
@@ -79,7 +80,13 @@ const PagePage: React.FC<React.PropsWithChildren<PagePageProps>> = ({ query }) =
     if (!getCoursePageByPath.data) {
       return
     }
-    if (getCoursePageByPath.data.was_redirected) {
+    const redirectedCourseSlug = getCoursePageByPath.data.redirected_course_slug
+    if (getCoursePageByPath.data.was_redirected && redirectedCourseSlug) {
+      // the page is in another language version of the course, so the whole course context
+      // needs to be loaded again
+      const newPath = `/${query.organizationSlug}/courses/${redirectedCourseSlug}${getCoursePageByPath.data.page.url_path}`
+      router.replace(newPath)
+    } else if (getCoursePageByPath.data.was_redirected) {
       // want to keep the same page, since the page content is already correct, just
       // want to fix the url without creating a history entry
       const currentPathName = document.location.pathname
@@ -95,7 +102,7 @@ const PagePage: React.FC<React.PropsWithChildren<PagePageProps>> = ({ query }) =
         shallow: true,
       })
     }
-  }, [courseSlug, getCoursePageByPath.data, router])
+  }, [courseSlug, getCoursePageByPath.data, query.organizationSlug, router])
 
   // Handle scrolling to selector if window has anchor
   useScrollToSelector(path)
@@ -139,6 +146,7 @@ const PagePage: React.FC<React.PropsWithChildren<PagePageProps>> = ({ query }) =
           >
             <CourseMaterialPageBreadcrumbs currentPagePath={path} page={pageState.pageData} />
             {<CourseTestModeNotification isTestMode={pageState.isTest} />}
+            <PageDraftNotification isDraft={getCoursePageByPath.data.is_draft} />
           </PageMarginOffset>
           <Page onRefresh={handleRefresh} organizationSlug={query.organizationSlug} />
         </Layout>
@@ -16,6 +16,8 @@ import {
   MaterialReference,
   NewFeedback,
   NewMaterialReference,
+  NewPeerReviewSubmissionFlag,
+  NewPeerReviewSubmissionRating,
   NewProposedPageEdits,
   OEmbedResponse,
   Page,
@@ -24,7 +26,10 @@ import {
   PageSearchRequest,
   PageSearchResult,
   PageWithExercises,
+  PeerReviewCalibrationFeedback,
   PeerReviewsRecieved,
+  PeerReviewSubmissionFlag,
+  PeerReviewSubmissionRating,
   SaveCourseSettingsPayload,
   StudentExerciseSlideSubmission,
   StudentExerciseSlideSubmissionResult,
@@ -53,7 +58,10 @@ import {
   isPageNavigationInformation,
   isPageSearchResult,
   isPageWithExercises,
+  isPeerReviewCalibrationFeedback,
   isPeerReviewsRecieved,
+  isPeerReviewSubmissionFlag,
+  isPeerReviewSubmissionRating,
   isStudentExerciseSlideSubmissionResult,
   isTerm,
   isUserCourseInstanceChapterExerciseProgress,
@@ -265,6 +273,36 @@ export const fetchPeerReviewDataReceivedByExerciseId = async (
   return validateResponse(response, isPeerReviewsRecieved)
 }
 
+export const putReceivedPeerReviewRating = async (
+  exerciseId: string,
+  peerReviewSubmissionId: string,
+  rating: NewPeerReviewSubmissionRating,
+): Promise<PeerReviewSubmissionRating> => {
+  const response = await courseMaterialClient.put(
+    `/exercises/${exerciseId}/peer-reviews-received/${peerReviewSubmissionId}/rating`,
+    rating,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isPeerReviewSubmissionRating)
+}
+
+export const postReceivedPeerReviewFlag = async (
+  exerciseId: string,
+  peerReviewSubmissionId: string,
+  flag: NewPeerReviewSubmissionFlag,
+): Promise<PeerReviewSubmissionFlag> => {
+  const response = await courseMaterialClient.post(
+    `/exercises/${exerciseId}/peer-reviews-received/${peerReviewSubmissionId}/flag`,
+    flag,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isPeerReviewSubmissionFlag)
+}
+
 export const fetchChaptersPagesWithExercises = async (
   chapterId: string,
 ): Promise<Array<PageWithExercises>> => {
@@ -373,6 +411,20 @@ export const postPeerReviewSubmission = async (
   })
 }
 
+export const postPeerReviewCalibrationSubmission = async (
+  exerciseId: string,
+  peerReviewSubmission: CourseMaterialPeerReviewSubmission,
+): Promise<PeerReviewCalibrationFeedback> => {
+  const response = await courseMaterialClient.post(
+    `/exercises/${exerciseId}/peer-reviews/calibration`,
+    peerReviewSubmission,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isPeerReviewCalibrationFeedback)
+}
+
 export const postStartPeerReview = async (exerciseId: string): Promise<void> => {
   await courseMaterialClient.post(`/exercises/${exerciseId}/peer-reviews/start`)
 }
@@ -8,18 +8,28 @@ async fn main() -> anyhow::Result<()> {
         .nth(1)
         .expect("No program name provided as the first argument.");
     match program_name.as_str() {
+        "content-linter" => programs::content_linter::main().await?,
         "doc-file-generator" => programs::doc_file_generator::main().await?,
         "email-deliver" => programs::email_deliver::main().await?,
         "ended-exams-processor" => programs::ended_exams_processor::main().await?,
+        "exercise-service-contract-tester" => {
+            programs::exercise_service_contract_tester::main().await?
+        }
+        "lti-score-passback" => programs::lti_score_passback::main().await?,
         "open-university-registration-link-fetcher" => {
             programs::open_university_registration_link_fetcher::main().await?
         }
+        "page-draft-publisher" => programs::page_draft_publisher::main().await?,
+        "rate-limit-counter-cleaner" => programs::rate_limit_counter_cleaner::main().await?,
         "regrader" => programs::regrader::main().await?,
         "seed" => programs::seed::main().await?,
         "service-info-fetcher" => programs::service_info_fetcher::main().await?,
         "peer-review-updater" => programs::peer_review_updater::main().await?,
+        "plagiarism-detector" => programs::plagiarism_detector::main().await?,
         "start-server" => programs::start_server::main().await?,
         "sorter" => programs::sorter::sort()?,
+        "user-data-exporter" => programs::user_data_exporter::main().await?,
+        "webhook-deliverer" => programs::webhook_deliverer::main().await?,
         _ => panic!("Unknown program name: {}", program_name),
     };
 
@@ -0,0 +1,4 @@
+DROP TABLE peer_review_calibration_answers;
+DROP TABLE peer_review_calibration_reference_answers;
+DROP TABLE peer_review_calibration_samples;
+ALTER TABLE peer_review_configs DROP COLUMN calibration_samples_to_review;
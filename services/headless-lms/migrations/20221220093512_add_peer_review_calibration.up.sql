@@ -0,0 +1,85 @@
+ALTER TABLE peer_review_configs
+ADD COLUMN calibration_samples_to_review INTEGER NOT NULL DEFAULT 0 CHECK (calibration_samples_to_review >= 0);
+COMMENT ON COLUMN peer_review_configs.calibration_samples_to_review IS 'How many calibration samples a student has to peer review before they are given answers from other students to review. If there are fewer calibration samples available, the student reviews all of them.';
+-- samples
+CREATE TABLE peer_review_calibration_samples (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  exercise_slide_submission_id UUID NOT NULL REFERENCES exercise_slide_submissions,
+  exercise_id UUID NOT NULL REFERENCES exercises
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON peer_review_calibration_samples FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX unique_peer_review_calibration_samples ON peer_review_calibration_samples (exercise_slide_submission_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE peer_review_calibration_samples IS 'An exercise slide submission that a teacher has marked as a calibration sample. Students peer review calibration samples before reviewing real answers, and their reviews are compared to the reference answers given by the teacher.';
+COMMENT ON COLUMN peer_review_calibration_samples.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN peer_review_calibration_samples.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN peer_review_calibration_samples.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN peer_review_calibration_samples.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN peer_review_calibration_samples.exercise_slide_submission_id IS 'The submission that is used as the calibration sample.';
+COMMENT ON COLUMN peer_review_calibration_samples.exercise_id IS 'The exercise the calibration sample is for.';
+-- reference answers
+CREATE TABLE peer_review_calibration_reference_answers (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  peer_review_calibration_sample_id UUID NOT NULL REFERENCES peer_review_calibration_samples,
+  peer_review_question_id UUID NOT NULL REFERENCES peer_review_questions,
+  reference_number_data REAL NOT NULL,
+  explanation TEXT
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON peer_review_calibration_reference_answers FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX unique_peer_review_calibration_reference_answers ON peer_review_calibration_reference_answers (
+  peer_review_calibration_sample_id,
+  peer_review_question_id
+)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE peer_review_calibration_reference_answers IS 'The score a teacher has given to a calibration sample for a single peer review question. Students are shown how far their own answer was from this reference.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.peer_review_calibration_sample_id IS 'The calibration sample this reference answer belongs to.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.peer_review_question_id IS 'The peer review question this reference answer is for.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.reference_number_data IS 'The score the teacher thinks the calibration sample deserves for this question.';
+COMMENT ON COLUMN peer_review_calibration_reference_answers.explanation IS 'Optional explanation shown to the student after they have reviewed the calibration sample.';
+-- student answers
+CREATE TABLE peer_review_calibration_answers (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  user_id UUID NOT NULL REFERENCES users,
+  exercise_id UUID NOT NULL REFERENCES exercises,
+  course_instance_id UUID NOT NULL REFERENCES course_instances,
+  peer_review_calibration_sample_id UUID NOT NULL REFERENCES peer_review_calibration_samples,
+  peer_review_question_id UUID NOT NULL REFERENCES peer_review_questions,
+  text_data TEXT,
+  number_data REAL
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON peer_review_calibration_answers FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX unique_peer_review_calibration_answers ON peer_review_calibration_answers (
+  user_id,
+  course_instance_id,
+  peer_review_calibration_sample_id,
+  peer_review_question_id
+)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE peer_review_calibration_answers IS 'A student''s answer to a peer review question when reviewing a calibration sample. These are kept separate from peer_review_question_submissions so that calibration reviews do not affect the grading of anyone.';
+COMMENT ON COLUMN peer_review_calibration_answers.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN peer_review_calibration_answers.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN peer_review_calibration_answers.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN peer_review_calibration_answers.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN peer_review_calibration_answers.user_id IS 'The student who reviewed the calibration sample.';
+COMMENT ON COLUMN peer_review_calibration_answers.exercise_id IS 'The exercise the calibration sample is for.';
+COMMENT ON COLUMN peer_review_calibration_answers.course_instance_id IS 'The course instance the student was on when they reviewed the calibration sample.';
+COMMENT ON COLUMN peer_review_calibration_answers.peer_review_calibration_sample_id IS 'The calibration sample that was reviewed.';
+COMMENT ON COLUMN peer_review_calibration_answers.peer_review_question_id IS 'The peer review question that was answered.';
+COMMENT ON COLUMN peer_review_calibration_answers.text_data IS 'Answer to an essay question.';
+COMMENT ON COLUMN peer_review_calibration_answers.number_data IS 'Answer to a scale question.';
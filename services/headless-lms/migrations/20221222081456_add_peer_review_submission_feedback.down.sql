@@ -0,0 +1,3 @@
+DROP TABLE peer_review_submission_flags;
+DROP TYPE peer_review_submission_flag_resolution;
+DROP TABLE peer_review_submission_ratings;
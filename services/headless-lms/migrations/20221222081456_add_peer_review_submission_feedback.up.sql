@@ -0,0 +1,67 @@
+-- helpfulness ratings
+CREATE TABLE peer_review_submission_ratings (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  peer_review_submission_id UUID NOT NULL REFERENCES peer_review_submissions,
+  user_id UUID NOT NULL REFERENCES users,
+  helpfulness INTEGER NOT NULL CHECK (
+    helpfulness >= 1
+    AND helpfulness <= 5
+  )
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON peer_review_submission_ratings FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX unique_peer_review_submission_ratings ON peer_review_submission_ratings (peer_review_submission_id, user_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE peer_review_submission_ratings IS 'A rating given by the student who received a peer review about how helpful the peer review was.';
+COMMENT ON COLUMN peer_review_submission_ratings.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN peer_review_submission_ratings.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN peer_review_submission_ratings.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN peer_review_submission_ratings.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN peer_review_submission_ratings.peer_review_submission_id IS 'The peer review that was rated.';
+COMMENT ON COLUMN peer_review_submission_ratings.user_id IS 'The user who received the peer review and gave the rating.';
+COMMENT ON COLUMN peer_review_submission_ratings.helpfulness IS 'How helpful the peer review was, from 1 (not helpful at all) to 5 (very helpful).';
+-- flags
+CREATE TYPE peer_review_submission_flag_resolution AS ENUM ('review_kept', 'review_removed');
+COMMENT ON TYPE peer_review_submission_flag_resolution IS 'What a teacher decided to do with a flagged peer review.';
+CREATE TABLE peer_review_submission_flags (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  peer_review_submission_id UUID NOT NULL REFERENCES peer_review_submissions,
+  flagged_by UUID NOT NULL REFERENCES users,
+  reason TEXT NOT NULL,
+  resolved_at TIMESTAMP WITH TIME ZONE,
+  resolved_by UUID REFERENCES users,
+  resolution peer_review_submission_flag_resolution,
+  CHECK (
+    (
+      resolved_at IS NULL
+      AND resolved_by IS NULL
+      AND resolution IS NULL
+    )
+    OR (
+      resolved_at IS NOT NULL
+      AND resolved_by IS NOT NULL
+      AND resolution IS NOT NULL
+    )
+  )
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON peer_review_submission_flags FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX unique_peer_review_submission_flags ON peer_review_submission_flags (peer_review_submission_id, flagged_by)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE peer_review_submission_flags IS 'A report by the student who received a peer review that the peer review is inappropriate. Peer reviews with unresolved flags are not counted when deciding whether an answer has received enough peer reviews or when calculating the peer review average.';
+COMMENT ON COLUMN peer_review_submission_flags.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN peer_review_submission_flags.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN peer_review_submission_flags.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN peer_review_submission_flags.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN peer_review_submission_flags.peer_review_submission_id IS 'The peer review that was flagged.';
+COMMENT ON COLUMN peer_review_submission_flags.flagged_by IS 'The user who received the peer review and flagged it.';
+COMMENT ON COLUMN peer_review_submission_flags.reason IS 'Why the student thinks the peer review is inappropriate.';
+COMMENT ON COLUMN peer_review_submission_flags.resolved_at IS 'Timestamp when a teacher resolved the flag. If null, the flag has not been resolved yet.';
+COMMENT ON COLUMN peer_review_submission_flags.resolved_by IS 'The teacher who resolved the flag.';
+COMMENT ON COLUMN peer_review_submission_flags.resolution IS 'What the teacher decided to do with the flagged peer review. If the review was removed, it will never be counted again.';
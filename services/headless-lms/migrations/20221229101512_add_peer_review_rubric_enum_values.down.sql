@@ -0,0 +1,38 @@
+DELETE FROM peer_review_question_submissions
+WHERE peer_review_question_id IN (
+    SELECT id
+    FROM peer_review_questions
+    WHERE question_type = 'rubric'
+  );
+DELETE FROM peer_review_calibration_reference_answers
+WHERE peer_review_question_id IN (
+    SELECT id
+    FROM peer_review_questions
+    WHERE question_type = 'rubric'
+  );
+DELETE FROM peer_review_questions
+WHERE question_type = 'rubric';
+ALTER TYPE peer_review_question_type
+RENAME TO peer_review_question_type_old;
+CREATE TYPE peer_review_question_type AS ENUM ('essay', 'scale');
+ALTER TABLE peer_review_questions
+ALTER COLUMN question_type TYPE peer_review_question_type USING question_type::text::peer_review_question_type;
+DROP TYPE peer_review_question_type_old;
+UPDATE peer_review_configs
+SET accepting_strategy = 'manual_review_everything'
+WHERE accepting_strategy = 'automatically_grade_by_rubric_average';
+ALTER TABLE peer_review_configs
+ALTER COLUMN accepting_strategy DROP DEFAULT;
+ALTER TYPE peer_review_accepting_strategy
+RENAME TO peer_review_accepting_strategy_old;
+CREATE TYPE peer_review_accepting_strategy AS ENUM (
+  'automatically_accept_or_reject_by_average',
+  'automatically_accept_or_manual_review_by_average',
+  'manual_review_everything'
+);
+ALTER TABLE peer_review_configs
+ALTER COLUMN accepting_strategy TYPE peer_review_accepting_strategy USING accepting_strategy::text::peer_review_accepting_strategy;
+ALTER TABLE peer_review_configs
+ALTER COLUMN accepting_strategy
+SET DEFAULT 'automatically_accept_or_reject_by_average';
+DROP TYPE peer_review_accepting_strategy_old;
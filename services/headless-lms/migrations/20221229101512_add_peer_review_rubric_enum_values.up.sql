@@ -0,0 +1,4 @@
+ALTER TYPE peer_review_question_type
+ADD VALUE 'rubric';
+ALTER TYPE peer_review_accepting_strategy
+ADD VALUE 'automatically_grade_by_rubric_average';
@@ -0,0 +1,3 @@
+ALTER TABLE peer_review_question_submissions DROP COLUMN rubric_level_ids;
+ALTER TABLE peer_review_questions DROP CONSTRAINT rubric_questions_have_rubric,
+  DROP COLUMN rubric;
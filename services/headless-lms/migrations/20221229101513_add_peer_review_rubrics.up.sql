@@ -0,0 +1,9 @@
+ALTER TABLE peer_review_questions
+ADD COLUMN rubric JSONB,
+  ADD CONSTRAINT rubric_questions_have_rubric CHECK (
+    (question_type = 'rubric') = (rubric IS NOT NULL)
+  );
+COMMENT ON COLUMN peer_review_questions.rubric IS 'The criteria of a rubric question. Each criterion has descriptive levels that are worth a specific amount of points. Required for rubric questions and null for other question types.';
+ALTER TABLE peer_review_question_submissions
+ADD COLUMN rubric_level_ids UUID [];
+COMMENT ON COLUMN peer_review_question_submissions.rubric_level_ids IS 'The rubric levels the reviewer selected, at most one for each criterion of the rubric. Only used for answers to rubric questions.';
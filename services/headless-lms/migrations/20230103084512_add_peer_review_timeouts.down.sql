@@ -0,0 +1,4 @@
+ALTER TABLE peer_review_configs DROP COLUMN timeout_in_days,
+  DROP COLUMN timeout_action,
+  DROP COLUMN manual_review_timeout_in_days;
+DROP TYPE peer_review_timeout_action;
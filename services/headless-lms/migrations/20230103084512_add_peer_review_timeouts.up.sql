@@ -0,0 +1,12 @@
+CREATE TYPE peer_review_timeout_action AS ENUM (
+  'move_to_manual_review',
+  'give_full_points',
+  'accept_by_current_average'
+);
+ALTER TABLE peer_review_configs
+ADD COLUMN timeout_in_days INTEGER NOT NULL DEFAULT 21 CHECK (timeout_in_days > 0),
+  ADD COLUMN timeout_action peer_review_timeout_action NOT NULL DEFAULT 'move_to_manual_review',
+  ADD COLUMN manual_review_timeout_in_days INTEGER DEFAULT 90 CHECK (manual_review_timeout_in_days > 0);
+COMMENT ON COLUMN peer_review_configs.timeout_in_days IS 'How many days an answer can wait in the peer review queue without receiving enough peer reviews before the timeout action is taken.';
+COMMENT ON COLUMN peer_review_configs.timeout_action IS 'What is done to an answer that has waited in the peer review queue for longer than timeout_in_days.';
+COMMENT ON COLUMN peer_review_configs.manual_review_timeout_in_days IS 'Used when timeout_action moves answers to manual review, that is with move_to_manual_review and accept_by_current_average. How many days after the timeout we wait for a teacher to review the answer before giving it full points. If null, the answer waits for the teacher indefinitely.';
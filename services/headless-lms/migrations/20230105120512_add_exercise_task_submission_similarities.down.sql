@@ -0,0 +1 @@
+DROP TABLE exercise_task_submission_similarities;
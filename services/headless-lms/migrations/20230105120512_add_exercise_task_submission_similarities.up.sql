@@ -0,0 +1,38 @@
+CREATE TABLE exercise_task_submission_similarities (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  exercise_id UUID NOT NULL REFERENCES exercises,
+  course_instance_id UUID NOT NULL REFERENCES course_instances,
+  exercise_task_id UUID NOT NULL REFERENCES exercise_tasks,
+  first_exercise_task_submission_id UUID NOT NULL REFERENCES exercise_task_submissions,
+  second_exercise_task_submission_id UUID NOT NULL REFERENCES exercise_task_submissions,
+  similarity REAL NOT NULL CHECK (
+    similarity >= 0.0
+    AND similarity <= 1.0
+  ),
+  CONSTRAINT submissions_in_order CHECK (
+    first_exercise_task_submission_id < second_exercise_task_submission_id
+  )
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON exercise_task_submission_similarities FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX unique_exercise_task_submission_similarities ON exercise_task_submission_similarities (
+  first_exercise_task_submission_id,
+  second_exercise_task_submission_id
+)
+WHERE deleted_at IS NULL;
+CREATE INDEX exercise_task_submission_similarities_exercise_id ON exercise_task_submission_similarities (exercise_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE exercise_task_submission_similarities IS 'A pair of textual exercise task submissions from different students that the plagiarism detector found to be similar. Used for showing teachers answers that might be plagiarized. The pairs of an exercise and course instance are replaced every time the plagiarism detector processes them.';
+COMMENT ON COLUMN exercise_task_submission_similarities.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN exercise_task_submission_similarities.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN exercise_task_submission_similarities.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN exercise_task_submission_similarities.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN exercise_task_submission_similarities.exercise_id IS 'The exercise the submissions are for.';
+COMMENT ON COLUMN exercise_task_submission_similarities.course_instance_id IS 'The course instance the submissions were made in. Submissions are only compared to other submissions from the same course instance.';
+COMMENT ON COLUMN exercise_task_submission_similarities.exercise_task_id IS 'The exercise task the submissions are for.';
+COMMENT ON COLUMN exercise_task_submission_similarities.first_exercise_task_submission_id IS 'The first submission of the pair. Always the one with the smaller id so that each pair is stored only once.';
+COMMENT ON COLUMN exercise_task_submission_similarities.second_exercise_task_submission_id IS 'The second submission of the pair.';
+COMMENT ON COLUMN exercise_task_submission_similarities.similarity IS 'How similar the texts of the submissions are, from 0.0 (nothing in common) to 1.0 (identical). The share of the text fingerprint hashes the submissions have in common.';
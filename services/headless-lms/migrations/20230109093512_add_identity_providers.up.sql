@@ -0,0 +1,52 @@
+CREATE TABLE identity_providers (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  organization_id UUID NOT NULL REFERENCES organizations(id),
+  name VARCHAR(255) NOT NULL CHECK (TRIM(name) <> ''),
+  issuer_url VARCHAR(2048) NOT NULL CHECK (issuer_url ~ '^https?://'),
+  client_id VARCHAR(255) NOT NULL,
+  client_secret VARCHAR(255),
+  scopes VARCHAR(255) NOT NULL DEFAULT 'openid email profile',
+  email_claim VARCHAR(255) NOT NULL DEFAULT 'email',
+  first_name_claim VARCHAR(255) NOT NULL DEFAULT 'given_name',
+  last_name_claim VARCHAR(255) NOT NULL DEFAULT 'family_name'
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON identity_providers FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE identity_providers IS 'OpenID Connect identity providers that users can log in with in addition to mooc.fi accounts. Each provider is configured for an organization, for example a partner organization that uses its own identity provider.';
+COMMENT ON COLUMN identity_providers.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN identity_providers.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN identity_providers.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN identity_providers.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN identity_providers.organization_id IS 'The organization that uses the identity provider.';
+COMMENT ON COLUMN identity_providers.name IS 'The name of the identity provider that is shown to the users on the login page.';
+COMMENT ON COLUMN identity_providers.issuer_url IS 'The issuer identifier of the provider. The provider configuration is discovered from {issuer_url}/.well-known/openid-configuration and the issuer of the ID tokens must match this value.';
+COMMENT ON COLUMN identity_providers.client_id IS 'The client id registered for us at the identity provider. The audience of the ID tokens must contain this value.';
+COMMENT ON COLUMN identity_providers.client_secret IS 'The client secret registered for us at the identity provider. Null if we are registered as a public client, in which case the login relies only on PKCE.';
+COMMENT ON COLUMN identity_providers.scopes IS 'Space-separated scopes requested from the identity provider. Must contain openid.';
+COMMENT ON COLUMN identity_providers.email_claim IS 'The ID token claim that contains the email of the user.';
+COMMENT ON COLUMN identity_providers.first_name_claim IS 'The ID token claim that contains the first name of the user.';
+COMMENT ON COLUMN identity_providers.last_name_claim IS 'The ID token claim that contains the last name of the user.';
+CREATE TABLE user_identities (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  user_id UUID NOT NULL REFERENCES users(id),
+  identity_provider_id UUID NOT NULL REFERENCES identity_providers(id),
+  subject VARCHAR(255) NOT NULL
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON user_identities FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX user_identities_identity_provider_id_subject ON user_identities (identity_provider_id, subject)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE user_identities IS 'Links users to their accounts in identity providers. A user logging in with an identity provider for the first time is linked to an existing user with the same verified email, or a new user is created for them.';
+COMMENT ON COLUMN user_identities.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN user_identities.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN user_identities.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN user_identities.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN user_identities.user_id IS 'The user the identity belongs to.';
+COMMENT ON COLUMN user_identities.identity_provider_id IS 'The identity provider the identity is from.';
+COMMENT ON COLUMN user_identities.subject IS 'The identifier of the user at the identity provider (the sub claim of the ID token). Unique within the identity provider.';
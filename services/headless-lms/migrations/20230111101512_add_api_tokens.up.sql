@@ -0,0 +1,29 @@
+CREATE TYPE api_token_scope AS ENUM ('read', 'grade', 'edit_content');
+COMMENT ON TYPE api_token_scope IS 'What an API token can be used for. read = any request that does not modify anything, for example fetching points, grade = grading submissions, edit_content = editing course content and uploading files.';
+CREATE TABLE api_tokens (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  user_id UUID NOT NULL REFERENCES users(id),
+  name VARCHAR(255) NOT NULL CHECK (TRIM(name) <> ''),
+  token_hash VARCHAR(64) NOT NULL,
+  scopes api_token_scope [] NOT NULL CHECK (cardinality(scopes) > 0),
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  last_used_at TIMESTAMP WITH TIME ZONE
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON api_tokens FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX api_tokens_token_hash ON api_tokens (token_hash);
+CREATE INDEX api_tokens_user_id ON api_tokens (user_id);
+COMMENT ON TABLE api_tokens IS 'Personal access tokens that users can create for accessing the API from scripts. The token is sent in the Authorization header as a bearer token, and the request is authorized as the user who created the token, limited by the scopes of the token.';
+COMMENT ON COLUMN api_tokens.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN api_tokens.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN api_tokens.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN api_tokens.deleted_at IS 'Timestamp when the record was deleted. Deleted tokens have been revoked and cannot be used anymore. If null, the record is not deleted.';
+COMMENT ON COLUMN api_tokens.user_id IS 'The user who created the token. Requests made with the token are authorized as this user.';
+COMMENT ON COLUMN api_tokens.name IS 'A name given by the user so that they can recognize the token, for example the name of the script that uses it.';
+COMMENT ON COLUMN api_tokens.token_hash IS 'Hex-encoded SHA-256 hash of the token. The token itself is shown to the user only once when it is created and it is not stored.';
+COMMENT ON COLUMN api_tokens.scopes IS 'What the token can be used for. A request made with the token is only allowed if one of the scopes allows the action.';
+COMMENT ON COLUMN api_tokens.expires_at IS 'Timestamp after which the token cannot be used anymore.';
+COMMENT ON COLUMN api_tokens.last_used_at IS 'Timestamp when the token was last used for a request. Null if the token has not been used.';
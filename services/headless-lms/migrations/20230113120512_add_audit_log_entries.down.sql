@@ -0,0 +1,3 @@
+DROP TABLE audit_log_entries;
+DROP FUNCTION trigger_prevent_audit_log_modification;
+DROP TYPE audit_log_action;
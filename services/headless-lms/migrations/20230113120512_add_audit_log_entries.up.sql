@@ -0,0 +1,43 @@
+CREATE TYPE audit_log_action AS ENUM (
+  'role_added',
+  'role_removed',
+  'manual_completions_added',
+  'teacher_grading_decision_added',
+  'regrading_created',
+  'page_restored',
+  'course_deleted'
+);
+COMMENT ON TYPE audit_log_action IS 'A privileged action recorded in the audit log. The action also determines what the resource_id of the entry refers to: role_added and role_removed = the user whose role changed, manual_completions_added = course instance, teacher_grading_decision_added = user exercise state, regrading_created = regrading, page_restored = page, course_deleted = course.';
+CREATE TABLE audit_log_entries (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  actor_user_id UUID REFERENCES users(id),
+  api_token_id UUID REFERENCES api_tokens(id),
+  request_id UUID,
+  action audit_log_action NOT NULL,
+  resource_id UUID NOT NULL,
+  course_id UUID REFERENCES courses(id),
+  before_summary JSONB,
+  after_summary JSONB
+);
+CREATE INDEX audit_log_entries_created_at ON audit_log_entries (created_at);
+CREATE INDEX audit_log_entries_actor_user_id ON audit_log_entries (actor_user_id);
+CREATE INDEX audit_log_entries_resource_id ON audit_log_entries (resource_id);
+CREATE INDEX audit_log_entries_course_id ON audit_log_entries (course_id);
+CREATE FUNCTION trigger_prevent_audit_log_modification() RETURNS TRIGGER AS $$ BEGIN RAISE EXCEPTION 'The audit log is append-only, entries cannot be updated or deleted.';
+END;
+$$ LANGUAGE plpgsql;
+CREATE TRIGGER prevent_modification BEFORE
+UPDATE
+  OR DELETE ON audit_log_entries FOR EACH ROW EXECUTE PROCEDURE trigger_prevent_audit_log_modification();
+COMMENT ON TABLE audit_log_entries IS 'An append-only log of privileged actions, such as role changes, manual completions and course deletions, recording who did what and when. The entries cannot be updated or deleted, which is enforced by the prevent_modification trigger.';
+COMMENT ON COLUMN audit_log_entries.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN audit_log_entries.created_at IS 'Timestamp when the action was performed.';
+COMMENT ON COLUMN audit_log_entries.actor_user_id IS 'The user who performed the action. Null if the action was performed by the system.';
+COMMENT ON COLUMN audit_log_entries.api_token_id IS 'The API token the request was authenticated with. Null if the action was performed using a session.';
+COMMENT ON COLUMN audit_log_entries.request_id IS 'The id of the HTTP request, which is attached to the log messages and returned in the request-id response header. Can be used for finding the related log messages. Null if the action was not performed in an HTTP request.';
+COMMENT ON COLUMN audit_log_entries.action IS 'What was done. Also determines what the resource_id refers to.';
+COMMENT ON COLUMN audit_log_entries.resource_id IS 'The id of the resource the action targeted, see the audit_log_action type for what it refers to.';
+COMMENT ON COLUMN audit_log_entries.course_id IS 'The course the action is related to, if any. Used for filtering the log.';
+COMMENT ON COLUMN audit_log_entries.before_summary IS 'A summary of the relevant state of the resource before the action. Null if the resource did not exist or the state is not relevant.';
+COMMENT ON COLUMN audit_log_entries.after_summary IS 'A summary of the relevant state of the resource after the action, or of the action itself. Null if the resource does not exist anymore.';
@@ -0,0 +1,34 @@
+CREATE TABLE user_data_exports (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  user_id UUID NOT NULL REFERENCES users(id),
+  file_path VARCHAR(255),
+  completed_at TIMESTAMP WITH TIME ZONE,
+  expires_at TIMESTAMP WITH TIME ZONE,
+  CHECK (
+    (
+      file_path IS NULL
+      AND completed_at IS NULL
+      AND expires_at IS NULL
+    )
+    OR (
+      file_path IS NOT NULL
+      AND completed_at IS NOT NULL
+      AND expires_at IS NOT NULL
+    )
+  )
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON user_data_exports FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE INDEX user_data_exports_user_id ON user_data_exports (user_id);
+COMMENT ON TABLE user_data_exports IS 'A request by a user to get a copy of all the data the system holds about them. The user-data-exporter program generates a ZIP file of the data for each pending request, stores it in the file store and deletes it once the export expires.';
+COMMENT ON COLUMN user_data_exports.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN user_data_exports.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN user_data_exports.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN user_data_exports.deleted_at IS 'Timestamp when the record was deleted. The file of an export is deleted from the file store when the export expires and the record is marked as deleted at the same time. If null, the record is not deleted.';
+COMMENT ON COLUMN user_data_exports.user_id IS 'The user whose data is exported. Only this user can download the export.';
+COMMENT ON COLUMN user_data_exports.file_path IS 'Path to the generated ZIP file in the file store. Null if the export has not been generated yet.';
+COMMENT ON COLUMN user_data_exports.completed_at IS 'Timestamp when the export was generated. Null if the export has not been generated yet.';
+COMMENT ON COLUMN user_data_exports.expires_at IS 'Timestamp after which the export cannot be downloaded anymore and its file is deleted. Null if the export has not been generated yet.';
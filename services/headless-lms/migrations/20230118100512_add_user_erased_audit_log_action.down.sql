@@ -0,0 +1,20 @@
+-- the log is append-only, so the trigger has to be disabled for removing the entries
+ALTER TABLE audit_log_entries DISABLE TRIGGER prevent_modification;
+DELETE FROM audit_log_entries
+WHERE action = 'user_erased';
+ALTER TABLE audit_log_entries ENABLE TRIGGER prevent_modification;
+ALTER TYPE audit_log_action
+RENAME TO audit_log_action_old;
+CREATE TYPE audit_log_action AS ENUM (
+  'role_added',
+  'role_removed',
+  'manual_completions_added',
+  'teacher_grading_decision_added',
+  'regrading_created',
+  'page_restored',
+  'course_deleted'
+);
+ALTER TABLE audit_log_entries
+ALTER COLUMN action TYPE audit_log_action USING action::text::audit_log_action;
+DROP TYPE audit_log_action_old;
+COMMENT ON TYPE audit_log_action IS 'A privileged action recorded in the audit log. The action also determines what the resource_id of the entry refers to: role_added and role_removed = the user whose role changed, manual_completions_added = course instance, teacher_grading_decision_added = user exercise state, regrading_created = regrading, page_restored = page, course_deleted = course.';
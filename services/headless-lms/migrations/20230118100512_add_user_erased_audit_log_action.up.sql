@@ -0,0 +1,3 @@
+ALTER TYPE audit_log_action
+ADD VALUE 'user_erased';
+COMMENT ON TYPE audit_log_action IS 'A privileged action recorded in the audit log. The action also determines what the resource_id of the entry refers to: role_added and role_removed = the user whose role changed, manual_completions_added = course instance, teacher_grading_decision_added = user exercise state, regrading_created = regrading, page_restored = page, course_deleted = course, user_erased = the user whose personal data was erased.';
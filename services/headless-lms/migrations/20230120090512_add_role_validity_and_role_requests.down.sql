@@ -0,0 +1,4 @@
+DROP TABLE role_requests;
+DROP TYPE role_request_status;
+ALTER TABLE roles DROP COLUMN valid_from,
+  DROP COLUMN valid_until;
@@ -0,0 +1,57 @@
+ALTER TABLE roles
+ADD COLUMN valid_from TIMESTAMP WITH TIME ZONE,
+  ADD COLUMN valid_until TIMESTAMP WITH TIME ZONE,
+  ADD CONSTRAINT roles_valid_from_before_valid_until CHECK (valid_from < valid_until);
+COMMENT ON COLUMN roles.valid_from IS 'The role grants permissions only from this time on. If null, the role is valid from the moment it is created.';
+COMMENT ON COLUMN roles.valid_until IS 'The role grants no permissions after this time. If null, the role is valid until it is removed.';
+CREATE TYPE role_request_status AS ENUM ('pending', 'approved', 'rejected');
+COMMENT ON TYPE role_request_status IS 'Whether a role request is waiting for a decision, or has been approved or rejected.';
+CREATE TABLE role_requests (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  user_id UUID NOT NULL REFERENCES users(id),
+  course_id UUID NOT NULL REFERENCES courses(id),
+  role user_role NOT NULL,
+  valid_from TIMESTAMP WITH TIME ZONE,
+  valid_until TIMESTAMP WITH TIME ZONE,
+  reason VARCHAR(1000) NOT NULL,
+  status role_request_status NOT NULL DEFAULT 'pending',
+  decided_by_user_id UUID REFERENCES users(id),
+  decided_at TIMESTAMP WITH TIME ZONE,
+  role_id UUID REFERENCES roles(id),
+  CHECK (valid_from < valid_until),
+  CHECK (
+    (
+      status = 'pending'
+      AND decided_by_user_id IS NULL
+      AND decided_at IS NULL
+    )
+    OR (
+      status <> 'pending'
+      AND decided_by_user_id IS NOT NULL
+      AND decided_at IS NOT NULL
+    )
+  ),
+  CHECK ((status = 'approved') = (role_id IS NOT NULL))
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON role_requests FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE INDEX role_requests_user_id ON role_requests (user_id);
+CREATE INDEX role_requests_course_id ON role_requests (course_id);
+COMMENT ON TABLE role_requests IS 'A request by a user for a role on a course. Someone who is allowed to manage the roles of the organization of the course approves or rejects the request, and approving it gives the user the role with the requested validity window.';
+COMMENT ON COLUMN role_requests.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN role_requests.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN role_requests.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN role_requests.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN role_requests.user_id IS 'The user who requested the role and who gets it if the request is approved.';
+COMMENT ON COLUMN role_requests.course_id IS 'The course the role is requested for.';
+COMMENT ON COLUMN role_requests.role IS 'The requested role.';
+COMMENT ON COLUMN role_requests.valid_from IS 'When the requested role should start to be valid. If null, the role is valid from the approval on.';
+COMMENT ON COLUMN role_requests.valid_until IS 'When the requested role should stop being valid. If null, the role is valid until it is removed.';
+COMMENT ON COLUMN role_requests.reason IS 'Why the user needs the role, shown to the person deciding on the request.';
+COMMENT ON COLUMN role_requests.status IS 'Whether the request is waiting for a decision, or has been approved or rejected.';
+COMMENT ON COLUMN role_requests.decided_by_user_id IS 'The user who approved or rejected the request. Null while the request is pending.';
+COMMENT ON COLUMN role_requests.decided_at IS 'When the request was approved or rejected. Null while the request is pending.';
+COMMENT ON COLUMN role_requests.role_id IS 'The role that was given when the request was approved. Null unless the request has been approved.';
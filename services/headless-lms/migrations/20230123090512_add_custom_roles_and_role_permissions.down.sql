@@ -0,0 +1,5 @@
+DROP TABLE role_permissions;
+DROP TYPE permission_resource_type;
+DROP TYPE permission_action;
+ALTER TABLE roles DROP COLUMN custom_role_id;
+DROP TABLE custom_roles;
@@ -0,0 +1,132 @@
+CREATE TABLE custom_roles (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  organization_id UUID NOT NULL REFERENCES organizations(id),
+  name VARCHAR(255) NOT NULL,
+  base_role user_role NOT NULL
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON custom_roles FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX custom_roles_organization_id_name ON custom_roles (organization_id, name)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE custom_roles IS 'A role defined by an organization. The permissions of a custom role are a subset of the permissions of its base role, so a custom role can only restrict what the base role allows.';
+COMMENT ON COLUMN custom_roles.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN custom_roles.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN custom_roles.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN custom_roles.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN custom_roles.organization_id IS 'The organization that defined the role. The role can only be given in the organization and its courses and exams.';
+COMMENT ON COLUMN custom_roles.name IS 'The name of the role, e.g. Grader.';
+COMMENT ON COLUMN custom_roles.base_role IS 'The built-in role the custom role restricts. Roles given with the custom role have this role, and the custom role can be given by anyone who can give the base role.';
+ALTER TABLE roles
+ADD COLUMN custom_role_id UUID REFERENCES custom_roles(id);
+COMMENT ON COLUMN roles.custom_role_id IS 'If not null, the permissions of the role are the permissions of the custom role instead of the permissions of the built-in role.';
+CREATE TYPE permission_action AS ENUM (
+  'view_material',
+  'view',
+  'edit',
+  'grade',
+  'teach',
+  'download',
+  'duplicate',
+  'delete_answer',
+  'edit_role',
+  'create_courses_or_exams',
+  'usually_unacceptable_deletion',
+  'upload_file'
+);
+COMMENT ON TYPE permission_action IS 'An action a role can be permitted to take. Matches the actions that are authorized in the backend.';
+CREATE TYPE permission_resource_type AS ENUM (
+  'global_permissions',
+  'chapter',
+  'course',
+  'course_instance',
+  'exam',
+  'exercise',
+  'exercise_slide_submission',
+  'exercise_task',
+  'exercise_task_grading',
+  'exercise_task_submission',
+  'organization',
+  'page',
+  'study_registry',
+  'any_course',
+  'role',
+  'user',
+  'playground_example',
+  'exercise_service',
+  'material_reference'
+);
+COMMENT ON TYPE permission_resource_type IS 'A type of resource a permission can be limited to. Matches the resources that are authorized in the backend.';
+CREATE TABLE role_permissions (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  built_in_role user_role,
+  custom_role_id UUID REFERENCES custom_roles(id),
+  action permission_action NOT NULL,
+  action_role user_role,
+  resource_type permission_resource_type,
+  CHECK ((built_in_role IS NULL) <> (custom_role_id IS NULL)),
+  CHECK ((action = 'edit_role') = (action_role IS NOT NULL))
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON role_permissions FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE INDEX role_permissions_built_in_role ON role_permissions (built_in_role);
+CREATE INDEX role_permissions_custom_role_id ON role_permissions (custom_role_id);
+COMMENT ON TABLE role_permissions IS 'A permission to take an action, given either to a built-in role or to a custom role. A role can take an action if it has a permission for it.';
+COMMENT ON COLUMN role_permissions.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN role_permissions.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN role_permissions.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN role_permissions.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN role_permissions.built_in_role IS 'The built-in role that has the permission. Null if the permission belongs to a custom role.';
+COMMENT ON COLUMN role_permissions.custom_role_id IS 'The custom role that has the permission. Null if the permission belongs to a built-in role.';
+COMMENT ON COLUMN role_permissions.action IS 'The permitted action.';
+COMMENT ON COLUMN role_permissions.action_role IS 'For the edit_role action, the role that can be given and removed. Null for the other actions.';
+COMMENT ON COLUMN role_permissions.resource_type IS 'If not null, the action is only permitted on resources of this type. If null, the action is permitted on all resources the role applies to.';
+-- the built-in roles have the same permissions they had when the permissions were hard-coded
+INSERT INTO role_permissions (built_in_role, action, action_role)
+VALUES ('admin', 'view_material', NULL),
+  ('admin', 'view', NULL),
+  ('admin', 'edit', NULL),
+  ('admin', 'grade', NULL),
+  ('admin', 'teach', NULL),
+  ('admin', 'download', NULL),
+  ('admin', 'duplicate', NULL),
+  ('admin', 'delete_answer', NULL),
+  ('admin', 'create_courses_or_exams', NULL),
+  ('admin', 'usually_unacceptable_deletion', NULL),
+  ('admin', 'upload_file', NULL),
+  ('admin', 'edit_role', 'admin'),
+  ('admin', 'edit_role', 'assistant'),
+  ('admin', 'edit_role', 'teacher'),
+  ('admin', 'edit_role', 'reviewer'),
+  ('admin', 'edit_role', 'course_or_exam_creator'),
+  ('admin', 'edit_role', 'material_viewer'),
+  ('teacher', 'view', NULL),
+  ('teacher', 'teach', NULL),
+  ('teacher', 'edit', NULL),
+  ('teacher', 'grade', NULL),
+  ('teacher', 'duplicate', NULL),
+  ('teacher', 'delete_answer', NULL),
+  ('teacher', 'create_courses_or_exams', NULL),
+  ('teacher', 'view_material', NULL),
+  ('teacher', 'upload_file', NULL),
+  ('teacher', 'edit_role', 'teacher'),
+  ('teacher', 'edit_role', 'assistant'),
+  ('teacher', 'edit_role', 'reviewer'),
+  ('assistant', 'view', NULL),
+  ('assistant', 'edit', NULL),
+  ('assistant', 'grade', NULL),
+  ('assistant', 'delete_answer', NULL),
+  ('assistant', 'teach', NULL),
+  ('assistant', 'view_material', NULL),
+  ('assistant', 'edit_role', 'assistant'),
+  ('assistant', 'edit_role', 'reviewer'),
+  ('reviewer', 'view', NULL),
+  ('reviewer', 'grade', NULL),
+  ('reviewer', 'view_material', NULL),
+  ('course_or_exam_creator', 'create_courses_or_exams', NULL),
+  ('material_viewer', 'view_material', NULL);
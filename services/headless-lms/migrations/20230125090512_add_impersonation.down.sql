@@ -0,0 +1,46 @@
+-- the log is append-only, so the trigger has to be disabled for removing the entries
+ALTER TABLE audit_log_entries DISABLE TRIGGER prevent_modification;
+DELETE FROM audit_log_entries
+WHERE action IN ('impersonation_started', 'impersonation_ended');
+ALTER TABLE audit_log_entries ENABLE TRIGGER prevent_modification;
+ALTER TYPE audit_log_action
+RENAME TO audit_log_action_old;
+CREATE TYPE audit_log_action AS ENUM (
+  'role_added',
+  'role_removed',
+  'manual_completions_added',
+  'teacher_grading_decision_added',
+  'regrading_created',
+  'page_restored',
+  'course_deleted',
+  'user_erased'
+);
+ALTER TABLE audit_log_entries
+ALTER COLUMN action TYPE audit_log_action USING action::text::audit_log_action;
+DROP TYPE audit_log_action_old;
+COMMENT ON TYPE audit_log_action IS 'A privileged action recorded in the audit log. The action also determines what the resource_id of the entry refers to: role_added and role_removed = the user whose role changed, manual_completions_added = course instance, teacher_grading_decision_added = user exercise state, regrading_created = regrading, page_restored = page, course_deleted = course, user_erased = the user whose personal data was erased.';
+DELETE FROM role_permissions
+WHERE action = 'impersonate';
+ALTER TABLE role_permissions DROP CONSTRAINT role_permissions_check1;
+ALTER TYPE permission_action
+RENAME TO permission_action_old;
+CREATE TYPE permission_action AS ENUM (
+  'view_material',
+  'view',
+  'edit',
+  'grade',
+  'teach',
+  'download',
+  'duplicate',
+  'delete_answer',
+  'edit_role',
+  'create_courses_or_exams',
+  'usually_unacceptable_deletion',
+  'upload_file'
+);
+ALTER TABLE role_permissions
+ALTER COLUMN action TYPE permission_action USING action::text::permission_action;
+DROP TYPE permission_action_old;
+ALTER TABLE role_permissions
+ADD CONSTRAINT role_permissions_check1 CHECK ((action = 'edit_role') = (action_role IS NOT NULL));
+COMMENT ON TYPE permission_action IS 'An action a role can be permitted to take. Matches the actions that are authorized in the backend.';
@@ -0,0 +1,33 @@
+-- a value added with ADD VALUE can't be used in the same transaction, so the type is recreated to be able to seed the permissions
+ALTER TABLE role_permissions DROP CONSTRAINT role_permissions_check1;
+ALTER TYPE permission_action
+RENAME TO permission_action_old;
+CREATE TYPE permission_action AS ENUM (
+  'view_material',
+  'view',
+  'edit',
+  'grade',
+  'teach',
+  'download',
+  'duplicate',
+  'delete_answer',
+  'edit_role',
+  'create_courses_or_exams',
+  'usually_unacceptable_deletion',
+  'upload_file',
+  'impersonate'
+);
+ALTER TABLE role_permissions
+ALTER COLUMN action TYPE permission_action USING action::text::permission_action;
+DROP TYPE permission_action_old;
+ALTER TABLE role_permissions
+ADD CONSTRAINT role_permissions_check1 CHECK ((action = 'edit_role') = (action_role IS NOT NULL));
+COMMENT ON TYPE permission_action IS 'An action a role can be permitted to take. Matches the actions that are authorized in the backend.';
+INSERT INTO role_permissions (built_in_role, action)
+VALUES ('admin', 'impersonate'),
+  ('teacher', 'impersonate');
+ALTER TYPE audit_log_action
+ADD VALUE 'impersonation_started';
+ALTER TYPE audit_log_action
+ADD VALUE 'impersonation_ended';
+COMMENT ON TYPE audit_log_action IS 'A privileged action recorded in the audit log. The action also determines what the resource_id of the entry refers to: role_added and role_removed = the user whose role changed, manual_completions_added = course instance, teacher_grading_decision_added = user exercise state, regrading_created = regrading, page_restored = page, course_deleted = course, user_erased = the user whose personal data was erased, impersonation_started and impersonation_ended = the impersonated user.';
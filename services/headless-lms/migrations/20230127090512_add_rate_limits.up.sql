@@ -0,0 +1,44 @@
+CREATE TABLE rate_limit_policies (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  route_group VARCHAR(255) NOT NULL,
+  interval_seconds INTEGER NOT NULL CHECK (interval_seconds > 0),
+  max_requests INTEGER NOT NULL CHECK (max_requests > 0)
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON rate_limit_policies FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX rate_limit_policies_route_group_interval_seconds ON rate_limit_policies (route_group, interval_seconds)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE rate_limit_policies IS 'A limit for how many requests a single user or IP address can make to a group of routes in a time window. All the policies of a route group apply at the same time, e.g. one per minute and one per day.';
+COMMENT ON COLUMN rate_limit_policies.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN rate_limit_policies.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN rate_limit_policies.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN rate_limit_policies.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN rate_limit_policies.route_group IS 'The name the routes are rate limited with in the backend, e.g. login.';
+COMMENT ON COLUMN rate_limit_policies.interval_seconds IS 'The length of the time window in seconds.';
+COMMENT ON COLUMN rate_limit_policies.max_requests IS 'How many requests are allowed in a time window.';
+CREATE TABLE rate_limit_counters (
+  policy_id UUID NOT NULL REFERENCES rate_limit_policies(id),
+  key VARCHAR(255) NOT NULL,
+  window_start TIMESTAMP WITH TIME ZONE NOT NULL,
+  window_end TIMESTAMP WITH TIME ZONE NOT NULL,
+  request_count INTEGER NOT NULL,
+  PRIMARY KEY (policy_id, key)
+);
+CREATE INDEX rate_limit_counters_window_end ON rate_limit_counters (window_end);
+COMMENT ON TABLE rate_limit_counters IS 'How many requests a user or an IP address has made in the current time window of a rate limit policy. Shared by all the server instances so that the limits hold no matter which instance handles the request. Counters of ended windows are deleted periodically.';
+COMMENT ON COLUMN rate_limit_counters.policy_id IS 'The policy the requests are counted for.';
+COMMENT ON COLUMN rate_limit_counters.key IS 'Who made the requests, either user:<user id> or ip:<ip address>.';
+COMMENT ON COLUMN rate_limit_counters.window_start IS 'When the current time window started. The windows are aligned to the interval of the policy.';
+COMMENT ON COLUMN rate_limit_counters.window_end IS 'When the current time window ends and the count is reset.';
+COMMENT ON COLUMN rate_limit_counters.request_count IS 'How many requests have been made in the current time window.';
+INSERT INTO rate_limit_policies (route_group, interval_seconds, max_requests)
+VALUES ('signup', 60, 15),
+  ('signup', 86400, 1000),
+  ('login', 60, 20),
+  ('login', 3600, 100),
+  ('login', 86400, 500),
+  ('oidc_login', 60, 20),
+  ('oidc_login', 86400, 500);
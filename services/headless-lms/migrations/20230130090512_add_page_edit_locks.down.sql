@@ -0,0 +1,2 @@
+DROP TABLE page_edit_presences;
+DROP TABLE page_edit_locks;
@@ -0,0 +1,31 @@
+CREATE TABLE page_edit_locks (
+  page_id UUID PRIMARY KEY REFERENCES pages(id),
+  user_id UUID NOT NULL REFERENCES users(id),
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON page_edit_locks FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE page_edit_locks IS 'Tells who is editing a page in the CMS. Only the user holding the lock can save the page. The editor renews the lock periodically, and if it stops doing so, e.g. because the browser was closed, the lock expires and someone else can acquire it. A lock can also be taken over before it expires.';
+COMMENT ON COLUMN page_edit_locks.page_id IS 'The page that is locked. A page has at most one lock.';
+COMMENT ON COLUMN page_edit_locks.user_id IS 'The user holding the lock.';
+COMMENT ON COLUMN page_edit_locks.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN page_edit_locks.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN page_edit_locks.expires_at IS 'When the lock expires unless it is renewed. An expired lock does not prevent others from saving the page.';
+CREATE TABLE page_edit_presences (
+  page_id UUID NOT NULL REFERENCES pages(id),
+  user_id UUID NOT NULL REFERENCES users(id),
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  PRIMARY KEY (page_id, user_id)
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON page_edit_presences FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE page_edit_presences IS 'Tells who has a page open in the CMS, whether they hold the edit lock of the page or not. The editor sends a heartbeat periodically, and users whose heartbeat has not been seen in a while are no longer considered to have the page open.';
+COMMENT ON COLUMN page_edit_presences.page_id IS 'The page that is open in the CMS.';
+COMMENT ON COLUMN page_edit_presences.user_id IS 'The user who has the page open.';
+COMMENT ON COLUMN page_edit_presences.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN page_edit_presences.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN page_edit_presences.last_seen_at IS 'When the latest heartbeat from the editor of the user was received.';
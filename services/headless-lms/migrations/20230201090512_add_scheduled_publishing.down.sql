@@ -0,0 +1,15 @@
+DROP TABLE page_drafts;
+UPDATE page_history
+SET history_change_reason = 'page-saved'
+WHERE history_change_reason = 'draft-published';
+ALTER TYPE history_change_reason
+RENAME TO history_change_reason_old;
+CREATE TYPE history_change_reason AS ENUM('page-saved', 'history-restored');
+ALTER TABLE page_history
+ALTER COLUMN history_change_reason TYPE history_change_reason USING history_change_reason::text::history_change_reason;
+DROP TYPE history_change_reason_old;
+COMMENT ON TYPE history_change_reason IS 'Contains all the methods that a page''s content can be changed.';
+ALTER TABLE chapters DROP COLUMN publish_at,
+  DROP COLUMN unpublish_at;
+ALTER TABLE pages DROP COLUMN publish_at,
+  DROP COLUMN unpublish_at;
@@ -0,0 +1,39 @@
+ALTER TABLE pages
+ADD COLUMN publish_at TIMESTAMP WITH TIME ZONE,
+  ADD COLUMN unpublish_at TIMESTAMP WITH TIME ZONE,
+  ADD CONSTRAINT pages_publish_at_before_unpublish_at CHECK (publish_at < unpublish_at);
+COMMENT ON COLUMN pages.publish_at IS 'When the page becomes visible to students. If null, the page is visible from the start. Users who can edit the page can view it before it is published.';
+COMMENT ON COLUMN pages.unpublish_at IS 'When the page stops being visible to students. If null, the page stays visible.';
+ALTER TABLE chapters
+ADD COLUMN publish_at TIMESTAMP WITH TIME ZONE,
+  ADD COLUMN unpublish_at TIMESTAMP WITH TIME ZONE,
+  ADD CONSTRAINT chapters_publish_at_before_unpublish_at CHECK (publish_at < unpublish_at);
+COMMENT ON COLUMN chapters.publish_at IS 'When the chapter and its pages become visible to students. If null, the chapter is visible from the start. Unlike with opens_at, students do not see the chapter at all before it has been published.';
+COMMENT ON COLUMN chapters.unpublish_at IS 'When the chapter and its pages stop being visible to students. If null, the chapter stays visible.';
+ALTER TYPE history_change_reason
+ADD VALUE 'draft-published';
+CREATE TABLE page_drafts (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  page_id UUID NOT NULL REFERENCES pages(id),
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  author_user_id UUID NOT NULL REFERENCES users(id),
+  content JSONB NOT NULL,
+  publish_at TIMESTAMP WITH TIME ZONE
+);
+CREATE UNIQUE INDEX page_drafts_page_id_unique ON page_drafts (page_id)
+WHERE deleted_at IS NULL;
+CREATE INDEX page_drafts_publish_at ON page_drafts (publish_at)
+WHERE deleted_at IS NULL;
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON page_drafts FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE page_drafts IS 'Unpublished changes to a page. The published version of the page stays visible to students until the draft is published, either manually or at publish_at. A page has at most one draft, and the draft is deleted when it is published or discarded.';
+COMMENT ON COLUMN page_drafts.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN page_drafts.page_id IS 'The page the draft is for.';
+COMMENT ON COLUMN page_drafts.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN page_drafts.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN page_drafts.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN page_drafts.author_user_id IS 'The user who saved the draft last. The page history entry of the published draft is attributed to them.';
+COMMENT ON COLUMN page_drafts.content IS 'The page update the draft consists of, in the same format the CMS saves pages in: the content of the page together with its exercises, slides and tasks, the title, the url path and the chapter.';
+COMMENT ON COLUMN page_drafts.publish_at IS 'When the draft is published automatically. If null, the draft is only published manually.';
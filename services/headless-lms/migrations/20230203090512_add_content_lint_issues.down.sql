@@ -0,0 +1,3 @@
+DROP TABLE content_lint_issues;
+DROP TYPE content_lint_issue_type;
+DROP TABLE content_lint_runs;
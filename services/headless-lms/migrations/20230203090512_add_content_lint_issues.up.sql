@@ -0,0 +1,57 @@
+CREATE TABLE content_lint_runs (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  course_id UUID NOT NULL REFERENCES courses(id),
+  pages_checked INTEGER NOT NULL
+);
+CREATE UNIQUE INDEX content_lint_runs_course_id_unique ON content_lint_runs (course_id)
+WHERE deleted_at IS NULL;
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON content_lint_runs FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE content_lint_runs IS 'A check of the content of all the pages of a course, done by the content linter. A course has at most one run that has not been deleted: the previous run is deleted when the course is checked again.';
+COMMENT ON COLUMN content_lint_runs.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN content_lint_runs.created_at IS 'Timestamp when the record was created. Tells when the course was checked.';
+COMMENT ON COLUMN content_lint_runs.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN content_lint_runs.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN content_lint_runs.course_id IS 'The course that was checked.';
+COMMENT ON COLUMN content_lint_runs.pages_checked IS 'How many pages of the course were checked.';
+CREATE TYPE content_lint_issue_type AS ENUM (
+  'link-to-missing-page',
+  'link-to-deleted-page',
+  'link-to-hidden-page',
+  'link-through-redirection',
+  'missing-media-file',
+  'unsupported-embed',
+  'image-without-alt-text',
+  'missing-exercise'
+);
+COMMENT ON TYPE content_lint_issue_type IS 'The kinds of problems the content linter finds in pages.';
+CREATE TABLE content_lint_issues (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  content_lint_run_id UUID NOT NULL REFERENCES content_lint_runs(id),
+  page_id UUID NOT NULL REFERENCES pages(id),
+  block_id UUID,
+  issue_type content_lint_issue_type NOT NULL,
+  target VARCHAR(2048) NOT NULL,
+  suggestion VARCHAR(2048)
+);
+CREATE INDEX content_lint_issues_content_lint_run_id ON content_lint_issues (content_lint_run_id)
+WHERE deleted_at IS NULL;
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON content_lint_issues FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE content_lint_issues IS 'A problem the content linter found in a page, such as a broken link or an image without alternative text.';
+COMMENT ON COLUMN content_lint_issues.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN content_lint_issues.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN content_lint_issues.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN content_lint_issues.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN content_lint_issues.content_lint_run_id IS 'The run that found the issue.';
+COMMENT ON COLUMN content_lint_issues.page_id IS 'The page the issue was found in.';
+COMMENT ON COLUMN content_lint_issues.block_id IS 'The client id of the block the issue was found in. Null if the block does not have an id.';
+COMMENT ON COLUMN content_lint_issues.issue_type IS 'What kind of a problem was found.';
+COMMENT ON COLUMN content_lint_issues.target IS 'What the issue concerns: the link, the media url, the embedded url, the image url or the exercise id.';
+COMMENT ON COLUMN content_lint_issues.suggestion IS 'How to fix the issue, if known. For links through redirections, this is the current url path of the page the link leads to.';
@@ -0,0 +1,13 @@
+DELETE FROM url_redirections
+WHERE destination_page_id IS NULL;
+ALTER TABLE url_redirections DROP CONSTRAINT url_redirections_one_destination,
+  DROP CONSTRAINT url_redirections_destination_course_with_url_path,
+  DROP COLUMN destination_course_id,
+  DROP COLUMN destination_url_path,
+  DROP COLUMN hit_count,
+  DROP COLUMN last_hit_at,
+  ALTER COLUMN destination_page_id
+SET NOT NULL;
+COMMENT ON TABLE url_redirections IS 'A url redirection is typically created when a page is moved. The redirection will be used to redirect old links to the new location. If there''s a conflict with a page url and a redirection, the page will win the conflict.';
+COMMENT ON COLUMN url_redirections.destination_page_id IS 'Which page the redirection will redirect to.';
+COMMENT ON COLUMN url_redirections.course_id IS 'Course where the redirection is valid. A redirection only works within one course.';
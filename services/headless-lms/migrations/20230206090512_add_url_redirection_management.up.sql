@@ -0,0 +1,20 @@
+ALTER TABLE url_redirections
+ALTER COLUMN destination_page_id DROP NOT NULL,
+  ADD COLUMN destination_course_id UUID REFERENCES courses(id),
+  ADD COLUMN destination_url_path TEXT,
+  ADD COLUMN hit_count INTEGER NOT NULL DEFAULT 0,
+  ADD COLUMN last_hit_at TIMESTAMP WITH TIME ZONE,
+  ADD CONSTRAINT url_redirections_one_destination CHECK (
+    (destination_page_id IS NULL) <> (destination_url_path IS NULL)
+  ),
+  ADD CONSTRAINT url_redirections_destination_course_with_url_path CHECK (
+    destination_course_id IS NULL
+    OR destination_url_path IS NOT NULL
+  );
+COMMENT ON TABLE url_redirections IS 'A url redirection is typically created when a page is moved, but teachers can also manage redirections manually. The redirection will be used to redirect old links to the new location. If there''s a conflict with a page url and a redirection, the page will win the conflict. A redirection leads either to a page or to a url path, which may be redirected further.';
+COMMENT ON COLUMN url_redirections.destination_page_id IS 'Which page the redirection will redirect to. The redirection follows the page when it is moved. Null if the redirection leads to a url path instead.';
+COMMENT ON COLUMN url_redirections.course_id IS 'Course where the redirection is valid, i.e. the course of old_url_path. The destination may be in another course of the same language group.';
+COMMENT ON COLUMN url_redirections.destination_course_id IS 'The course of destination_url_path, if it is in another course of the same language group. If null, the url path is in the same course.';
+COMMENT ON COLUMN url_redirections.destination_url_path IS 'Which url path the redirection will redirect to. If there is no page at the path, redirections from the path are followed. Null if the redirection leads to a page.';
+COMMENT ON COLUMN url_redirections.hit_count IS 'How many times the redirection has been used to find a page.';
+COMMENT ON COLUMN url_redirections.last_hit_at IS 'When the redirection was last used to find a page. Null if it has never been used.';
@@ -0,0 +1,89 @@
+CREATE TYPE webhook_event_type AS ENUM (
+  'enrollment_created',
+  'module_completion_granted',
+  'exam_ended',
+  'submission_graded',
+  'peer_review_received'
+);
+COMMENT ON TYPE webhook_event_type IS 'Events that external systems can subscribe to with webhooks. enrollment_created = a user enrolled to a course instance, module_completion_granted = a user was granted a completion for a course module, exam_ended = the end time of an exam passed and its completions were processed, submission_graded = a submission to an exercise task was fully graded, peer_review_received = a user received a peer review for their submission.';
+CREATE TABLE webhook_subscriptions (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  organization_id UUID NOT NULL REFERENCES organizations(id),
+  url VARCHAR(2048) NOT NULL CHECK (url ~ '^https?://'),
+  secret VARCHAR(255) NOT NULL,
+  event_types webhook_event_type [] NOT NULL CHECK (cardinality(event_types) > 0),
+  enabled BOOLEAN NOT NULL DEFAULT TRUE
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON webhook_subscriptions FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE INDEX webhook_subscriptions_organization_id ON webhook_subscriptions (organization_id);
+COMMENT ON TABLE webhook_subscriptions IS 'An external system that wants to be notified about events in the courses and exams of an organization. When an event the subscription is interested in happens, a webhook delivery is created for it and the webhook deliverer sends the event to the url.';
+COMMENT ON COLUMN webhook_subscriptions.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN webhook_subscriptions.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN webhook_subscriptions.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN webhook_subscriptions.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN webhook_subscriptions.organization_id IS 'The organization whose events are sent to the url.';
+COMMENT ON COLUMN webhook_subscriptions.url IS 'Where the events are sent with a POST request.';
+COMMENT ON COLUMN webhook_subscriptions.secret IS 'Shared secret used for signing the requests with HMAC-SHA256, so that the receiver can check that the events come from us.';
+COMMENT ON COLUMN webhook_subscriptions.event_types IS 'The events that are sent to the url.';
+COMMENT ON COLUMN webhook_subscriptions.enabled IS 'If false, no new deliveries are created for the subscription, but already created deliveries are still sent.';
+CREATE TYPE webhook_delivery_status AS ENUM ('pending', 'delivered', 'failed');
+COMMENT ON TYPE webhook_delivery_status IS 'pending = the event has not been delivered yet and the delivery will be attempted at next_attempt_at, delivered = the receiver accepted the event, failed = all the attempts failed and the delivery will not be retried unless it is redelivered manually.';
+CREATE TABLE webhook_deliveries (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  webhook_subscription_id UUID NOT NULL REFERENCES webhook_subscriptions(id),
+  event_type webhook_event_type NOT NULL,
+  payload JSONB NOT NULL,
+  status webhook_delivery_status NOT NULL DEFAULT 'pending',
+  attempts INTEGER NOT NULL DEFAULT 0,
+  next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  delivered_at TIMESTAMP WITH TIME ZONE
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON webhook_deliveries FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE INDEX webhook_deliveries_webhook_subscription_id ON webhook_deliveries (webhook_subscription_id);
+CREATE INDEX webhook_deliveries_pending ON webhook_deliveries (next_attempt_at)
+WHERE status = 'pending'
+  AND deleted_at IS NULL;
+COMMENT ON TABLE webhook_deliveries IS 'An event that is sent or has been sent to a webhook subscription. Failed attempts are retried with an exponential backoff. Each attempt is logged in webhook_delivery_attempts.';
+COMMENT ON COLUMN webhook_deliveries.id IS 'A unique, stable identifier for the record. Sent to the receiver so that it can ignore events it has already received.';
+COMMENT ON COLUMN webhook_deliveries.created_at IS 'Timestamp when the record was created, i.e. when the event happened.';
+COMMENT ON COLUMN webhook_deliveries.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN webhook_deliveries.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN webhook_deliveries.webhook_subscription_id IS 'The subscription the event is sent to.';
+COMMENT ON COLUMN webhook_deliveries.event_type IS 'What happened.';
+COMMENT ON COLUMN webhook_deliveries.payload IS 'The data of the event, for example the ids of the user and the course. Sent to the receiver as the data field of the request body.';
+COMMENT ON COLUMN webhook_deliveries.status IS 'Whether the event has been delivered.';
+COMMENT ON COLUMN webhook_deliveries.attempts IS 'How many times the delivery has been attempted since it was created or redelivered manually.';
+COMMENT ON COLUMN webhook_deliveries.next_attempt_at IS 'When the delivery is attempted next if it is pending.';
+COMMENT ON COLUMN webhook_deliveries.delivered_at IS 'When the receiver accepted the event. Null if the event has not been delivered.';
+CREATE TABLE webhook_delivery_attempts (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  webhook_delivery_id UUID NOT NULL REFERENCES webhook_deliveries(id),
+  response_status INTEGER,
+  response_body TEXT,
+  error TEXT,
+  duration_ms INTEGER NOT NULL
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON webhook_delivery_attempts FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE INDEX webhook_delivery_attempts_webhook_delivery_id ON webhook_delivery_attempts (webhook_delivery_id);
+COMMENT ON TABLE webhook_delivery_attempts IS 'The log of the attempts to deliver webhook events.';
+COMMENT ON COLUMN webhook_delivery_attempts.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN webhook_delivery_attempts.created_at IS 'Timestamp when the record was created, i.e. when the attempt was made.';
+COMMENT ON COLUMN webhook_delivery_attempts.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN webhook_delivery_attempts.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN webhook_delivery_attempts.webhook_delivery_id IS 'The delivery that was attempted.';
+COMMENT ON COLUMN webhook_delivery_attempts.response_status IS 'The HTTP status code of the response. Null if no response was received.';
+COMMENT ON COLUMN webhook_delivery_attempts.response_body IS 'The beginning of the response body, for debugging failed deliveries.';
+COMMENT ON COLUMN webhook_delivery_attempts.error IS 'Why the attempt failed, if it failed. Null if the receiver accepted the event.';
+COMMENT ON COLUMN webhook_delivery_attempts.duration_ms IS 'How long the request took in milliseconds.';
@@ -0,0 +1,9 @@
+DELETE FROM rate_limit_policies
+WHERE route_group IN ('lti_login', 'lti_launch');
+DROP TABLE lti_deep_linking_requests;
+DROP TABLE lti_login_states;
+DROP TABLE lti_scores;
+DROP TABLE lti_resource_links;
+DROP TABLE lti_contexts;
+DROP TABLE lti_users;
+DROP TABLE lti_registrations;
@@ -0,0 +1,186 @@
+CREATE TABLE lti_registrations (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  organization_id UUID NOT NULL REFERENCES organizations(id),
+  name VARCHAR(255) NOT NULL CHECK (TRIM(name) <> ''),
+  issuer VARCHAR(2048) NOT NULL CHECK (issuer ~ '^https?://'),
+  client_id VARCHAR(255) NOT NULL,
+  auth_login_url VARCHAR(2048) NOT NULL CHECK (auth_login_url ~ '^https?://'),
+  auth_token_url VARCHAR(2048) NOT NULL CHECK (auth_token_url ~ '^https?://'),
+  jwks_url VARCHAR(2048) NOT NULL CHECK (jwks_url ~ '^https?://'),
+  link_existing_users_by_email BOOLEAN NOT NULL DEFAULT FALSE
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_registrations FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX lti_registrations_issuer_client_id ON lti_registrations (issuer, client_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE lti_registrations IS 'LTI 1.3 platforms, such as Moodle or Canvas, that can launch the courses of an organization. We act as an LTI tool for the registered platforms.';
+COMMENT ON COLUMN lti_registrations.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN lti_registrations.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN lti_registrations.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_registrations.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_registrations.organization_id IS 'The organization whose courses the platform can launch.';
+COMMENT ON COLUMN lti_registrations.name IS 'A name for the platform, for example the name of the partner university.';
+COMMENT ON COLUMN lti_registrations.issuer IS 'The issuer identifier of the platform. The issuer of the launch messages must match this value.';
+COMMENT ON COLUMN lti_registrations.client_id IS 'The client id the platform has assigned to us. The audience of the launch messages must contain this value.';
+COMMENT ON COLUMN lti_registrations.auth_login_url IS 'The OpenID Connect authorization endpoint of the platform where the user is redirected to during the login initiation.';
+COMMENT ON COLUMN lti_registrations.auth_token_url IS 'The OAuth 2 token endpoint of the platform, used for getting access tokens for passing back scores with Assignment and Grade Services.';
+COMMENT ON COLUMN lti_registrations.jwks_url IS 'Where the public keys of the platform are fetched from for validating the launch messages.';
+COMMENT ON COLUMN lti_registrations.link_existing_users_by_email IS 'If true, a platform user launching a course for the first time is linked to the existing user with the same email. Should only be enabled for platforms that are trusted to have verified the emails of their users. If false, launches from users whose email is already in use are rejected.';
+CREATE TABLE lti_users (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  lti_registration_id UUID NOT NULL REFERENCES lti_registrations(id),
+  subject VARCHAR(255) NOT NULL,
+  user_id UUID NOT NULL REFERENCES users(id)
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_users FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX lti_users_lti_registration_id_subject ON lti_users (lti_registration_id, subject)
+WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX lti_users_lti_registration_id_user_id ON lti_users (lti_registration_id, user_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE lti_users IS 'Links the users of LTI platforms to our users. A user is created or linked when they launch a course from the platform for the first time.';
+COMMENT ON COLUMN lti_users.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN lti_users.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN lti_users.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_users.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_users.lti_registration_id IS 'The platform the user is from.';
+COMMENT ON COLUMN lti_users.subject IS 'The identifier of the user at the platform (the sub claim of the launch message). Also used as the user id when passing back scores.';
+COMMENT ON COLUMN lti_users.user_id IS 'Our user the platform user is linked to.';
+CREATE TABLE lti_contexts (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  lti_registration_id UUID NOT NULL REFERENCES lti_registrations(id),
+  deployment_id VARCHAR(255) NOT NULL,
+  context_id VARCHAR(255) NOT NULL,
+  title VARCHAR(255),
+  course_id UUID NOT NULL REFERENCES courses(id),
+  course_instance_id UUID NOT NULL REFERENCES course_instances(id)
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_contexts FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX lti_contexts_unique_context_course ON lti_contexts (
+  lti_registration_id,
+  deployment_id,
+  context_id,
+  course_id
+)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE lti_contexts IS 'Maps the contexts (usually courses) of LTI platforms to our course instances. When a course is launched from a context for the first time, a new course instance is created for the context, so that the students of each platform course can be followed separately.';
+COMMENT ON COLUMN lti_contexts.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN lti_contexts.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN lti_contexts.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_contexts.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_contexts.lti_registration_id IS 'The platform the context is in.';
+COMMENT ON COLUMN lti_contexts.deployment_id IS 'The deployment of the tool in the platform the context was launched through.';
+COMMENT ON COLUMN lti_contexts.context_id IS 'The identifier of the context at the platform.';
+COMMENT ON COLUMN lti_contexts.title IS 'The title of the context at the platform, if the platform sent one.';
+COMMENT ON COLUMN lti_contexts.course_id IS 'The course that was launched from the context.';
+COMMENT ON COLUMN lti_contexts.course_instance_id IS 'The course instance the users launching the course from the context are enrolled to.';
+CREATE TABLE lti_resource_links (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  lti_context_id UUID NOT NULL REFERENCES lti_contexts(id),
+  resource_link_id VARCHAR(255) NOT NULL,
+  title VARCHAR(255),
+  page_id UUID REFERENCES pages(id),
+  chapter_id UUID REFERENCES chapters(id),
+  line_item_url VARCHAR(2048),
+  CHECK ((page_id IS NULL) <> (chapter_id IS NULL))
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_resource_links FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX lti_resource_links_lti_context_id_resource_link_id ON lti_resource_links (lti_context_id, resource_link_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE lti_resource_links IS 'Links in LTI platforms that launch a page or a chapter of a course. The links are usually created with deep linking. The scores of the exercises in the page or the chapter are passed back to the line item of the link.';
+COMMENT ON COLUMN lti_resource_links.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN lti_resource_links.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN lti_resource_links.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_resource_links.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_resource_links.lti_context_id IS 'The context the link is in.';
+COMMENT ON COLUMN lti_resource_links.resource_link_id IS 'The identifier of the link at the platform.';
+COMMENT ON COLUMN lti_resource_links.title IS 'The title of the link at the platform, if the platform sent one.';
+COMMENT ON COLUMN lti_resource_links.page_id IS 'The page the link launches. Null if the link launches a chapter.';
+COMMENT ON COLUMN lti_resource_links.chapter_id IS 'The chapter the link launches. Null if the link launches a page.';
+COMMENT ON COLUMN lti_resource_links.line_item_url IS 'The Assignment and Grade Services line item of the link where the scores are passed back to. Null if the platform did not give a line item for the link, in which case the scores are not passed back.';
+CREATE TABLE lti_scores (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  lti_resource_link_id UUID NOT NULL REFERENCES lti_resource_links(id),
+  user_id UUID NOT NULL REFERENCES users(id),
+  score_given REAL,
+  score_maximum INTEGER,
+  synced_at TIMESTAMP WITH TIME ZONE
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_scores FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+CREATE UNIQUE INDEX lti_scores_lti_resource_link_id_user_id ON lti_scores (lti_resource_link_id, user_id)
+WHERE deleted_at IS NULL;
+COMMENT ON TABLE lti_scores IS 'The users who have launched an LTI resource link, and the scores that have been passed back to the platform for them. The score passback program sends the score again when the user_exercise_states of the exercises in the linked page or chapter have been updated after the score was last synced.';
+COMMENT ON COLUMN lti_scores.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN lti_scores.created_at IS 'Timestamp when the record was created.';
+COMMENT ON COLUMN lti_scores.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_scores.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_scores.lti_resource_link_id IS 'The link the user launched.';
+COMMENT ON COLUMN lti_scores.user_id IS 'The user whose score is passed back.';
+COMMENT ON COLUMN lti_scores.score_given IS 'The score that was last passed back to the platform. Null if no score has been passed back yet.';
+COMMENT ON COLUMN lti_scores.score_maximum IS 'The maximum score that was last passed back to the platform. Null if no score has been passed back yet.';
+COMMENT ON COLUMN lti_scores.synced_at IS 'When the score was last computed and passed back to the platform. Null if no score has been passed back yet.';
+CREATE TABLE lti_login_states (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  lti_registration_id UUID NOT NULL REFERENCES lti_registrations(id),
+  state VARCHAR(255) NOT NULL UNIQUE,
+  nonce VARCHAR(255) NOT NULL
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_login_states FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE lti_login_states IS 'LTI launches that have been initiated but not finished. Stored in the database instead of the session, because the session cookie is not sent with the cross-site launch request from the platform. The records are deleted when the launch is finished.';
+COMMENT ON COLUMN lti_login_states.id IS 'A unique, stable identifier for the record.';
+COMMENT ON COLUMN lti_login_states.created_at IS 'Timestamp when the record was created. The launch has to be finished within a few minutes from this.';
+COMMENT ON COLUMN lti_login_states.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_login_states.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_login_states.lti_registration_id IS 'The platform that initiated the launch.';
+COMMENT ON COLUMN lti_login_states.state IS 'The random state sent to the platform in the authentication request. The platform sends it back with the launch message.';
+COMMENT ON COLUMN lti_login_states.nonce IS 'The random nonce sent to the platform in the authentication request. The launch message must contain the same nonce.';
+CREATE TABLE lti_deep_linking_requests (
+  id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  deleted_at TIMESTAMP WITH TIME ZONE,
+  lti_registration_id UUID NOT NULL REFERENCES lti_registrations(id),
+  deployment_id VARCHAR(255) NOT NULL,
+  deep_link_return_url VARCHAR(2048) NOT NULL,
+  data TEXT,
+  responded_at TIMESTAMP WITH TIME ZONE
+);
+CREATE TRIGGER set_timestamp BEFORE
+UPDATE ON lti_deep_linking_requests FOR EACH ROW EXECUTE PROCEDURE trigger_set_timestamp();
+COMMENT ON TABLE lti_deep_linking_requests IS 'Deep linking requests from LTI platforms. A teacher creating a link in the platform selects the page or the chapter to link to, after which a signed deep linking response is sent back to the platform.';
+COMMENT ON COLUMN lti_deep_linking_requests.id IS 'A unique, stable identifier for the record. Random, so it is used in the url of the content selection page.';
+COMMENT ON COLUMN lti_deep_linking_requests.created_at IS 'Timestamp when the record was created. The content has to be selected within an hour from this.';
+COMMENT ON COLUMN lti_deep_linking_requests.updated_at IS 'Timestamp when the record was last updated. The field is updated automatically by the set_timestamp trigger.';
+COMMENT ON COLUMN lti_deep_linking_requests.deleted_at IS 'Timestamp when the record was deleted. If null, the record is not deleted.';
+COMMENT ON COLUMN lti_deep_linking_requests.lti_registration_id IS 'The platform that sent the request.';
+COMMENT ON COLUMN lti_deep_linking_requests.deployment_id IS 'The deployment of the tool in the platform the request was sent through. Included in the response.';
+COMMENT ON COLUMN lti_deep_linking_requests.deep_link_return_url IS 'Where the deep linking response is posted to.';
+COMMENT ON COLUMN lti_deep_linking_requests.data IS 'Opaque data from the platform that has to be included in the response as is.';
+COMMENT ON COLUMN lti_deep_linking_requests.responded_at IS 'When the response was created. A request can only be responded to once.';
+INSERT INTO rate_limit_policies (route_group, interval_seconds, max_requests)
+VALUES ('lti_login', 60, 20),
+  ('lti_login', 86400, 500),
+  ('lti_launch', 60, 20),
+  ('lti_launch', 86400, 500);
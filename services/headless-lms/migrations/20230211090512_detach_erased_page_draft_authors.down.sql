@@ -0,0 +1,7 @@
+-- drafts whose author has been erased cannot be attributed to anyone
+DELETE FROM page_drafts
+WHERE author_user_id IS NULL;
+ALTER TABLE page_drafts
+ALTER COLUMN author_user_id
+SET NOT NULL;
+COMMENT ON COLUMN page_drafts.author_user_id IS 'The user who saved the draft last. The page history entry of the published draft is attributed to them.';
@@ -0,0 +1,3 @@
+ALTER TABLE page_drafts
+ALTER COLUMN author_user_id DROP NOT NULL;
+COMMENT ON COLUMN page_drafts.author_user_id IS 'The user who saved the draft last. The page history entry of the published draft is attributed to them. If null, the personal data of the author has been erased, and the draft is attributed to the user who publishes it.';
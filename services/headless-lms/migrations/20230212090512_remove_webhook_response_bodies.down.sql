@@ -0,0 +1,3 @@
+ALTER TABLE webhook_delivery_attempts
+ADD COLUMN response_body TEXT;
+COMMENT ON COLUMN webhook_delivery_attempts.response_body IS 'The beginning of the response body, for debugging failed deliveries.';
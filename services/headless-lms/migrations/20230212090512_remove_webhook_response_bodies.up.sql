@@ -0,0 +1,2 @@
+-- the receivers of webhooks are chosen by organization users, so their responses should not be shown back to them
+ALTER TABLE webhook_delivery_attempts DROP COLUMN response_body;
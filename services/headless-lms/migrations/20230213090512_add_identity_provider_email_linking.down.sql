@@ -0,0 +1 @@
+ALTER TABLE identity_providers DROP COLUMN link_existing_users_by_email;
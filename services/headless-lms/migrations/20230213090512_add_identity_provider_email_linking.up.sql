@@ -0,0 +1,3 @@
+ALTER TABLE identity_providers
+ADD COLUMN link_existing_users_by_email BOOLEAN NOT NULL DEFAULT FALSE;
+COMMENT ON COLUMN identity_providers.link_existing_users_by_email IS 'If true, users logging in with the identity provider for the first time are linked to existing users with the same email, if the identity provider says that the email has been verified.';
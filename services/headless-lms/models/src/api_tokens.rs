@@ -0,0 +1,158 @@
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
+
+use crate::prelude::*;
+
+/// What an API token can be used for. The server maps the scopes to the actions the token is allowed to perform.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "api_token_scope", rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    Read,
+    Grade,
+    EditContent,
+}
+
+impl PgHasArrayType for ApiTokenScope {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_api_token_scope")
+    }
+}
+
+/// A personal access token. The token itself is only known by the user, we only store its hash.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewApiToken {
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    new_api_token: &NewApiToken,
+    token_hash: &str,
+) -> ModelResult<ApiToken> {
+    let res = sqlx::query_as!(
+        ApiToken,
+        r#"
+INSERT INTO api_tokens (user_id, name, token_hash, scopes, expires_at)
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id,
+  created_at,
+  updated_at,
+  user_id,
+  name,
+  scopes AS "scopes: Vec<ApiTokenScope>",
+  expires_at,
+  last_used_at
+        "#,
+        user_id,
+        new_api_token.name,
+        token_hash,
+        &new_api_token.scopes as &[ApiTokenScope],
+        new_api_token.expires_at
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the token with the hash if it has not been revoked and has not expired.
+pub async fn try_get_valid_by_token_hash(
+    conn: &mut PgConnection,
+    token_hash: &str,
+) -> ModelResult<Option<ApiToken>> {
+    let res = sqlx::query_as!(
+        ApiToken,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  user_id,
+  name,
+  scopes AS "scopes: Vec<ApiTokenScope>",
+  expires_at,
+  last_used_at
+FROM api_tokens
+WHERE token_hash = $1
+  AND expires_at > now()
+  AND deleted_at IS NULL
+        "#,
+        token_hash
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the user's tokens that have not been revoked, including the expired ones.
+pub async fn get_by_user_id(conn: &mut PgConnection, user_id: Uuid) -> ModelResult<Vec<ApiToken>> {
+    let res = sqlx::query_as!(
+        ApiToken,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  user_id,
+  name,
+  scopes AS "scopes: Vec<ApiTokenScope>",
+  expires_at,
+  last_used_at
+FROM api_tokens
+WHERE user_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn update_last_used_at(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE api_tokens
+SET last_used_at = now()
+WHERE id = $1
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Revokes the user's token. Fails if the user has no such token.
+pub async fn revoke(conn: &mut PgConnection, user_id: Uuid, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE api_tokens
+SET deleted_at = now()
+WHERE id = $1
+  AND user_id = $2
+  AND deleted_at IS NULL
+RETURNING id
+        ",
+        id,
+        user_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use futures::Stream;
+
+use crate::prelude::*;
+
+/// A privileged action recorded in the audit log. The action determines what the `resource_id` of the entry refers to.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "audit_log_action", rename_all = "snake_case")]
+pub enum AuditLogAction {
+    /// The resource is the user who was given the role.
+    RoleAdded,
+    /// The resource is the user whose role was removed.
+    RoleRemoved,
+    /// The resource is the course instance.
+    ManualCompletionsAdded,
+    /// The resource is the user exercise state.
+    TeacherGradingDecisionAdded,
+    /// The resource is the regrading.
+    RegradingCreated,
+    /// The resource is the page.
+    PageRestored,
+    /// The resource is the course.
+    CourseDeleted,
+    /// The resource is the user whose personal data was erased.
+    UserErased,
+    /// The resource is the impersonated user.
+    ImpersonationStarted,
+    /// The resource is the impersonated user.
+    ImpersonationEnded,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct AuditLogEntry {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_email: Option<String>,
+    pub api_token_id: Option<Uuid>,
+    pub request_id: Option<Uuid>,
+    pub action: AuditLogAction,
+    pub resource_id: Uuid,
+    pub course_id: Option<Uuid>,
+    pub before_summary: Option<serde_json::Value>,
+    pub after_summary: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAuditLogEntry {
+    pub actor_user_id: Option<Uuid>,
+    pub api_token_id: Option<Uuid>,
+    pub request_id: Option<Uuid>,
+    pub action: AuditLogAction,
+    pub resource_id: Uuid,
+    pub course_id: Option<Uuid>,
+    pub before_summary: Option<serde_json::Value>,
+    pub after_summary: Option<serde_json::Value>,
+}
+
+/// Filters for listing the audit log. Entries match if they match all the given filters.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct AuditLogFilter {
+    pub actor_user_id: Option<Uuid>,
+    pub action: Option<AuditLogAction>,
+    pub course_id: Option<Uuid>,
+    pub resource_id: Option<Uuid>,
+    /// Inclusive.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive.
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+pub async fn insert(conn: &mut PgConnection, entry: &NewAuditLogEntry) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO audit_log_entries (
+    actor_user_id,
+    api_token_id,
+    request_id,
+    action,
+    resource_id,
+    course_id,
+    before_summary,
+    after_summary
+  )
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+RETURNING id
+        ",
+        entry.actor_user_id,
+        entry.api_token_id,
+        entry.request_id,
+        entry.action as AuditLogAction,
+        entry.resource_id,
+        entry.course_id,
+        entry.before_summary,
+        entry.after_summary
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+/// Returns the matching entries, newest first.
+pub async fn get_filtered_paginated(
+    conn: &mut PgConnection,
+    filter: &AuditLogFilter,
+    pagination: Pagination,
+) -> ModelResult<Vec<AuditLogEntry>> {
+    let res = sqlx::query_as!(
+        AuditLogEntry,
+        r#"
+SELECT audit_log_entries.id,
+  audit_log_entries.created_at,
+  audit_log_entries.actor_user_id,
+  users.email AS "actor_email?",
+  audit_log_entries.api_token_id,
+  audit_log_entries.request_id,
+  audit_log_entries.action AS "action: AuditLogAction",
+  audit_log_entries.resource_id,
+  audit_log_entries.course_id,
+  audit_log_entries.before_summary,
+  audit_log_entries.after_summary
+FROM audit_log_entries
+  LEFT JOIN users ON users.id = audit_log_entries.actor_user_id
+WHERE ($1::uuid IS NULL OR audit_log_entries.actor_user_id = $1)
+  AND ($2::audit_log_action IS NULL OR audit_log_entries.action = $2)
+  AND ($3::uuid IS NULL OR audit_log_entries.course_id = $3)
+  AND ($4::uuid IS NULL OR audit_log_entries.resource_id = $4)
+  AND ($5::timestamptz IS NULL OR audit_log_entries.created_at >= $5)
+  AND ($6::timestamptz IS NULL OR audit_log_entries.created_at < $6)
+ORDER BY audit_log_entries.created_at DESC,
+  audit_log_entries.id
+LIMIT $7 OFFSET $8
+        "#,
+        filter.actor_user_id,
+        filter.action as Option<AuditLogAction>,
+        filter.course_id,
+        filter.resource_id,
+        filter.created_after,
+        filter.created_before,
+        pagination.limit(),
+        pagination.offset()
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_filtered_count(
+    conn: &mut PgConnection,
+    filter: &AuditLogFilter,
+) -> ModelResult<i64> {
+    let res = sqlx::query!(
+        r#"
+SELECT COUNT(*) AS count
+FROM audit_log_entries
+WHERE ($1::uuid IS NULL OR actor_user_id = $1)
+  AND ($2::audit_log_action IS NULL OR action = $2)
+  AND ($3::uuid IS NULL OR course_id = $3)
+  AND ($4::uuid IS NULL OR resource_id = $4)
+  AND ($5::timestamptz IS NULL OR created_at >= $5)
+  AND ($6::timestamptz IS NULL OR created_at < $6)
+        "#,
+        filter.actor_user_id,
+        filter.action as Option<AuditLogAction>,
+        filter.course_id,
+        filter.resource_id,
+        filter.created_after,
+        filter.created_before
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.count.unwrap_or(0))
+}
+
+/// Streams all the matching entries, oldest first. Used for exporting the log.
+pub fn stream_filtered<'a>(
+    conn: &'a mut PgConnection,
+    filter: &'a AuditLogFilter,
+) -> impl Stream<Item = sqlx::Result<AuditLogEntry>> + 'a {
+    sqlx::query_as!(
+        AuditLogEntry,
+        r#"
+SELECT audit_log_entries.id,
+  audit_log_entries.created_at,
+  audit_log_entries.actor_user_id,
+  users.email AS "actor_email?",
+  audit_log_entries.api_token_id,
+  audit_log_entries.request_id,
+  audit_log_entries.action AS "action: AuditLogAction",
+  audit_log_entries.resource_id,
+  audit_log_entries.course_id,
+  audit_log_entries.before_summary,
+  audit_log_entries.after_summary
+FROM audit_log_entries
+  LEFT JOIN users ON users.id = audit_log_entries.actor_user_id
+WHERE ($1::uuid IS NULL OR audit_log_entries.actor_user_id = $1)
+  AND ($2::audit_log_action IS NULL OR audit_log_entries.action = $2)
+  AND ($3::uuid IS NULL OR audit_log_entries.course_id = $3)
+  AND ($4::uuid IS NULL OR audit_log_entries.resource_id = $4)
+  AND ($5::timestamptz IS NULL OR audit_log_entries.created_at >= $5)
+  AND ($6::timestamptz IS NULL OR audit_log_entries.created_at < $6)
+ORDER BY audit_log_entries.created_at,
+  audit_log_entries.id
+        "#,
+        filter.actor_user_id,
+        filter.action as Option<AuditLogAction>,
+        filter.course_id,
+        filter.resource_id,
+        filter.created_after,
+        filter.created_before
+    )
+    .fetch(conn)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_helper::*;
+
+    #[tokio::test]
+    async fn entries_are_append_only() {
+        insert_data!(:tx, :user, :org, :course);
+
+        let entry = NewAuditLogEntry {
+            actor_user_id: Some(user),
+            api_token_id: None,
+            request_id: None,
+            action: AuditLogAction::CourseDeleted,
+            resource_id: course,
+            course_id: Some(course),
+            before_summary: Some(json!({ "name": "course" })),
+            after_summary: None,
+        };
+        let id = insert(tx.as_mut(), &entry).await.unwrap();
+        let filter = AuditLogFilter {
+            course_id: Some(course),
+            ..Default::default()
+        };
+        let entries = get_filtered_paginated(tx.as_mut(), &filter, Pagination::default())
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, id);
+        assert_eq!(entries[0].action, AuditLogAction::CourseDeleted);
+        let filter = AuditLogFilter {
+            course_id: Some(course),
+            action: Some(AuditLogAction::RoleAdded),
+            ..Default::default()
+        };
+        assert_eq!(get_filtered_count(tx.as_mut(), &filter).await.unwrap(), 0);
+
+        let mut tx1 = tx.begin().await;
+        // the trigger prevents modifying the log
+        sqlx::query!("DELETE FROM audit_log_entries WHERE id = $1", id)
+            .execute(tx1.as_mut())
+            .await
+            .unwrap_err();
+        tx1.rollback().await;
+    }
+}
@@ -28,6 +28,17 @@ pub struct DatabaseChapter {
     pub deadline: Option<DateTime<Utc>>,
     pub copied_from: Option<Uuid>,
     pub course_module_id: Uuid,
+    /// When the chapter and its pages become visible to students. Unlike before `opens_at`, the chapter is not shown to students at all before this.
+    pub publish_at: Option<DateTime<Utc>>,
+    /// When the chapter and its pages stop being visible to students.
+    pub unpublish_at: Option<DateTime<Utc>>,
+}
+
+impl DatabaseChapter {
+    /// Checks the publishing schedule of the chapter.
+    pub fn is_published_at(&self, timestamp: DateTime<Utc>) -> bool {
+        crate::pages::is_within_publishing_schedule(self.publish_at, self.unpublish_at, timestamp)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -47,6 +58,10 @@ pub struct Chapter {
     pub deadline: Option<DateTime<Utc>>,
     pub copied_from: Option<Uuid>,
     pub course_module_id: Uuid,
+    /// When the chapter and its pages become visible to students. Unlike before `opens_at`, the chapter is not shown to students at all before this.
+    pub publish_at: Option<DateTime<Utc>>,
+    /// When the chapter and its pages stop being visible to students.
+    pub unpublish_at: Option<DateTime<Utc>>,
 }
 
 impl Chapter {
@@ -74,6 +89,8 @@ impl Chapter {
             copied_from: chapter.copied_from,
             deadline: chapter.deadline,
             course_module_id: chapter.course_module_id,
+            publish_at: chapter.publish_at,
+            unpublish_at: chapter.unpublish_at,
         }
     }
 }
@@ -130,6 +147,8 @@ pub struct ChapterUpdate {
     pub opens_at: Option<DateTime<Utc>>,
     /// CHANGE TO NON NULL WHEN FRONTEND MODULE EDITING IMPLEMENTED
     pub course_module_id: Option<Uuid>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
 }
 
 pub struct ChapterInfo {
@@ -260,6 +279,17 @@ pub async fn update_chapter(
     chapter_id: Uuid,
     chapter_update: ChapterUpdate,
 ) -> ModelResult<DatabaseChapter> {
+    if let (Some(publish_at), Some(unpublish_at)) =
+        (chapter_update.publish_at, chapter_update.unpublish_at)
+    {
+        if publish_at >= unpublish_at {
+            return Err(ModelError::new(
+                ModelErrorType::PreconditionFailed,
+                "The chapter has to be published before it is unpublished.".to_string(),
+                None,
+            ));
+        }
+    }
     let res = sqlx::query_as!(
         DatabaseChapter,
         r#"
@@ -268,7 +298,9 @@ SET name = $2,
   deadline = $3,
   opens_at = $4,
   course_module_id = $5,
-  color = $6
+  color = $6,
+  publish_at = $7,
+  unpublish_at = $8
 WHERE id = $1
 RETURNING *;
     "#,
@@ -278,6 +310,8 @@ RETURNING *;
         chapter_update.opens_at,
         chapter_update.course_module_id,
         chapter_update.color,
+        chapter_update.publish_at,
+        chapter_update.unpublish_at,
     )
     .fetch_one(conn)
     .await?;
@@ -384,7 +418,9 @@ SELECT id,
   opens_at,
   copied_from,
   deadline,
-  course_module_id
+  course_module_id,
+  publish_at,
+  unpublish_at
 FROM chapters
 WHERE course_id = $1
   AND deleted_at IS NULL;
@@ -416,7 +452,9 @@ SELECT id,
   opens_at,
   copied_from,
   deadline,
-  course_module_id
+  course_module_id,
+  publish_at,
+  unpublish_at
 FROM chapters
 WHERE course_id = (SELECT course_id FROM course_instances WHERE id = $1)
   AND deleted_at IS NULL;
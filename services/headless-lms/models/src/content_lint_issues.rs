@@ -0,0 +1,227 @@
+/*!
+Problems the content linter has found in the pages of courses.
+
+The linter checks all the pages of a course at once. Each check is stored as a run, and the issues found in the check belong to the run. When a course is checked again, the previous run and its issues are replaced.
+*/
+
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "content_lint_issue_type", rename_all = "kebab-case")]
+pub enum ContentLintIssueType {
+    /// A link to a page of the course that does not exist.
+    LinkToMissingPage,
+    /// A link to a page that has been deleted.
+    LinkToDeletedPage,
+    /// A link to a page that is hidden from the students.
+    LinkToHiddenPage,
+    /// A link to the old url path of a page that has been moved. The link works, but should point to the current url path.
+    LinkThroughRedirection,
+    /// A link to an uploaded file that cannot be found in the file store.
+    MissingMediaFile,
+    /// An embed whose url is not from a supported oEmbed provider.
+    UnsupportedEmbed,
+    /// An image without alternative text.
+    ImageWithoutAltText,
+    /// An exercise block whose exercise has been deleted or does not belong to the page.
+    MissingExercise,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ContentLintRun {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub course_id: Uuid,
+    pub pages_checked: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ContentLintIssue {
+    pub id: Uuid,
+    pub page_id: Uuid,
+    pub page_title: String,
+    pub page_url_path: String,
+    /// The client id of the block the issue was found in.
+    pub block_id: Option<Uuid>,
+    pub issue_type: ContentLintIssueType,
+    /// The link, the media url, the embedded url, the image url or the exercise id the issue concerns.
+    pub target: String,
+    /// How to fix the issue, if known. For links through redirections, this is the link to the current url path of the page.
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewContentLintIssue {
+    pub page_id: Uuid,
+    pub block_id: Option<Uuid>,
+    pub issue_type: ContentLintIssueType,
+    pub target: String,
+    pub suggestion: Option<String>,
+}
+
+/// The latest check of a course and the issues it found.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct CourseContentLintReport {
+    /// None if the course has not been checked yet.
+    pub run: Option<ContentLintRun>,
+    pub issues: Vec<ContentLintIssue>,
+}
+
+/// Stores a check of the course and the issues it found, replacing the previous check.
+pub async fn insert_run(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    pages_checked: i32,
+    issues: &[NewContentLintIssue],
+) -> ModelResult<ContentLintRun> {
+    let mut tx = conn.begin().await?;
+    sqlx::query!(
+        "
+UPDATE content_lint_issues
+SET deleted_at = now()
+WHERE content_lint_run_id IN (
+    SELECT id
+    FROM content_lint_runs
+    WHERE course_id = $1
+      AND deleted_at IS NULL
+  )
+  AND deleted_at IS NULL
+        ",
+        course_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+UPDATE content_lint_runs
+SET deleted_at = now()
+WHERE course_id = $1
+  AND deleted_at IS NULL
+        ",
+        course_id
+    )
+    .execute(&mut tx)
+    .await?;
+    let run = sqlx::query_as!(
+        ContentLintRun,
+        "
+INSERT INTO content_lint_runs (course_id, pages_checked)
+VALUES ($1, $2)
+RETURNING id,
+  created_at,
+  course_id,
+  pages_checked
+        ",
+        course_id,
+        pages_checked
+    )
+    .fetch_one(&mut tx)
+    .await?;
+    for issue in issues {
+        sqlx::query!(
+            "
+INSERT INTO content_lint_issues (
+    content_lint_run_id,
+    page_id,
+    block_id,
+    issue_type,
+    target,
+    suggestion
+  )
+VALUES ($1, $2, $3, $4, $5, $6)
+            ",
+            run.id,
+            issue.page_id,
+            issue.block_id,
+            issue.issue_type as ContentLintIssueType,
+            issue.target,
+            issue.suggestion
+        )
+        .execute(&mut tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(run)
+}
+
+/// Returns the latest check of the course and its issues, ordered by the pages they were found in.
+pub async fn get_report_for_course(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+) -> ModelResult<CourseContentLintReport> {
+    let run = sqlx::query_as!(
+        ContentLintRun,
+        "
+SELECT id,
+  created_at,
+  course_id,
+  pages_checked
+FROM content_lint_runs
+WHERE course_id = $1
+  AND deleted_at IS NULL
+        ",
+        course_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let issues = if let Some(run) = &run {
+        sqlx::query_as!(
+            ContentLintIssue,
+            r#"
+SELECT i.id,
+  i.page_id,
+  p.title AS page_title,
+  p.url_path AS page_url_path,
+  i.block_id,
+  i.issue_type AS "issue_type: ContentLintIssueType",
+  i.target,
+  i.suggestion
+FROM content_lint_issues i
+  JOIN pages p ON p.id = i.page_id
+WHERE i.content_lint_run_id = $1
+  AND i.deleted_at IS NULL
+ORDER BY p.url_path,
+  i.created_at
+            "#,
+            run.id
+        )
+        .fetch_all(&mut *conn)
+        .await?
+    } else {
+        vec![]
+    };
+    Ok(CourseContentLintReport { run, issues })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[tokio::test]
+    async fn new_run_replaces_previous_run() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+
+        let issue = NewContentLintIssue {
+            page_id: page,
+            block_id: None,
+            issue_type: ContentLintIssueType::LinkToMissingPage,
+            target: "/missing".to_string(),
+            suggestion: None,
+        };
+        insert_run(tx.as_mut(), course, 1, &[issue.clone(), issue])
+            .await
+            .unwrap();
+        let report = get_report_for_course(tx.as_mut(), course).await.unwrap();
+        assert_eq!(report.issues.len(), 2);
+
+        let run = insert_run(tx.as_mut(), course, 1, &[]).await.unwrap();
+        let report = get_report_for_course(tx.as_mut(), course).await.unwrap();
+        assert_eq!(report.run, Some(run));
+        assert!(report.issues.is_empty());
+    }
+}
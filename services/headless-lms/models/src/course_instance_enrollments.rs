@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{prelude::*, webhook_deliveries::WebhookEvent};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "ts_rs", derive(TS))]
@@ -71,9 +71,28 @@ pub async fn insert_enrollment_and_set_as_current(
 ) -> ModelResult<CourseInstanceEnrollment> {
     let mut tx = conn.begin().await?;
 
+    let existing_enrollment = get_by_user_and_course_instance_id(
+        &mut tx,
+        new_enrollment.user_id,
+        new_enrollment.course_instance_id,
+    )
+    .await
+    .optional()?;
     let enrollment = insert_enrollment_if_it_doesnt_exist(&mut tx, new_enrollment).await?;
     crate::user_course_settings::upsert_user_course_settings_for_enrollment(&mut tx, &enrollment)
         .await?;
+    if existing_enrollment.is_none() {
+        crate::webhook_deliveries::insert_for_event(
+            &mut tx,
+            CourseOrExamId::Course(enrollment.course_id),
+            &WebhookEvent::EnrollmentCreated {
+                user_id: enrollment.user_id,
+                course_id: enrollment.course_id,
+                course_instance_id: enrollment.course_instance_id,
+            },
+        )
+        .await?;
+    }
     tx.commit().await?;
 
     Ok(enrollment)
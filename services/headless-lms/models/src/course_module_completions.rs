@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use futures::Stream;
 
-use crate::prelude::*;
+use crate::{prelude::*, webhook_deliveries::WebhookEvent};
 
 #[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(feature = "ts_rs", derive(TS))]
@@ -111,7 +111,20 @@ RETURNING id
         new_course_module_completion.passed,
         completion_granter.to_database_field(),
     )
-    .fetch_one(conn)
+    .fetch_one(&mut *conn)
+    .await?;
+    crate::webhook_deliveries::insert_for_event(
+        conn,
+        CourseOrExamId::Course(new_course_module_completion.course_id),
+        &WebhookEvent::ModuleCompletionGranted {
+            user_id: new_course_module_completion.user_id,
+            course_id: new_course_module_completion.course_id,
+            course_module_id: new_course_module_completion.course_module_id,
+            course_module_completion_id: res.id,
+            passed: new_course_module_completion.passed,
+            grade: new_course_module_completion.grade,
+        },
+    )
     .await?;
     Ok(res.id)
 }
@@ -0,0 +1,244 @@
+use crate::{
+    prelude::*,
+    role_permissions::{self, NewRolePermission, RolePermission},
+    roles::UserRole,
+};
+
+/// A role defined by an organization. It can only restrict the permissions of its base role.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct CustomRole {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub base_role: UserRole,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct CustomRoleWithPermissions {
+    pub custom_role: CustomRole,
+    pub permissions: Vec<RolePermission>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewCustomRole {
+    pub name: String,
+    pub base_role: UserRole,
+    pub permissions: Vec<NewRolePermission>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct CustomRoleUpdate {
+    pub name: String,
+    pub permissions: Vec<NewRolePermission>,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    new_custom_role: &NewCustomRole,
+) -> ModelResult<Uuid> {
+    let mut tx = conn.begin().await?;
+    let id = sqlx::query!(
+        "
+INSERT INTO custom_roles (organization_id, name, base_role)
+VALUES ($1, $2, $3)
+RETURNING id
+",
+        organization_id,
+        new_custom_role.name,
+        new_custom_role.base_role as UserRole
+    )
+    .fetch_one(&mut tx)
+    .await?
+    .id;
+    for permission in &new_custom_role.permissions {
+        role_permissions::insert_for_custom_role(&mut tx, id, permission).await?;
+    }
+    tx.commit().await?;
+    Ok(id)
+}
+
+pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<CustomRole> {
+    let res = sqlx::query_as!(
+        CustomRole,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  name,
+  base_role AS "base_role: UserRole"
+FROM custom_roles
+WHERE id = $1
+  AND deleted_at IS NULL
+"#,
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_organization_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+) -> ModelResult<Vec<CustomRole>> {
+    let res = sqlx::query_as!(
+        CustomRole,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  name,
+  base_role AS "base_role: UserRole"
+FROM custom_roles
+WHERE organization_id = $1
+  AND deleted_at IS NULL
+ORDER BY name
+"#,
+        organization_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_with_permissions_by_organization_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+) -> ModelResult<Vec<CustomRoleWithPermissions>> {
+    let custom_roles = get_by_organization_id(&mut *conn, organization_id).await?;
+    let mut res = Vec::with_capacity(custom_roles.len());
+    for custom_role in custom_roles {
+        let permissions =
+            role_permissions::get_by_custom_role_id(&mut *conn, custom_role.id).await?;
+        res.push(CustomRoleWithPermissions {
+            custom_role,
+            permissions,
+        });
+    }
+    Ok(res)
+}
+
+/// Renames the custom role and replaces its permissions. The change applies immediately to everyone who has the role.
+pub async fn update(
+    conn: &mut PgConnection,
+    id: Uuid,
+    custom_role_update: &CustomRoleUpdate,
+) -> ModelResult<()> {
+    let mut tx = conn.begin().await?;
+    sqlx::query!(
+        "
+UPDATE custom_roles
+SET name = $2
+WHERE id = $1
+  AND deleted_at IS NULL
+",
+        id,
+        custom_role_update.name
+    )
+    .execute(&mut tx)
+    .await?;
+    role_permissions::delete_by_custom_role_id(&mut tx, id).await?;
+    for permission in &custom_role_update.permissions {
+        role_permissions::insert_for_custom_role(&mut tx, id, permission).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Deletes the custom role along with its permissions and the roles that have been given with it.
+pub async fn delete(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    let mut tx = conn.begin().await?;
+    sqlx::query!(
+        "
+UPDATE roles
+SET deleted_at = now()
+WHERE custom_role_id = $1
+  AND deleted_at IS NULL
+",
+        id
+    )
+    .execute(&mut tx)
+    .await?;
+    role_permissions::delete_by_custom_role_id(&mut tx, id).await?;
+    sqlx::query!(
+        "
+UPDATE custom_roles
+SET deleted_at = now()
+WHERE id = $1
+",
+        id
+    )
+    .execute(&mut tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        role_permissions::{PermissionAction, PermissionResourceType},
+        roles::{self, RoleDomain},
+        test_helper::*,
+    };
+
+    #[tokio::test]
+    async fn deleting_removes_the_roles_given_with_it() {
+        insert_data!(:tx, :user, :org, :course);
+        let id = insert(
+            tx.as_mut(),
+            org,
+            &NewCustomRole {
+                name: "Grader".to_string(),
+                base_role: UserRole::Assistant,
+                permissions: vec![NewRolePermission {
+                    action: PermissionAction::Grade,
+                    action_role: None,
+                    resource_type: Some(PermissionResourceType::ExerciseSlideSubmission),
+                }],
+            },
+        )
+        .await
+        .unwrap();
+        let role_id = roles::insert(
+            tx.as_mut(),
+            user,
+            UserRole::Assistant,
+            RoleDomain::Course(course),
+        )
+        .await
+        .unwrap();
+        roles::set_custom_role(tx.as_mut(), role_id, Some(id))
+            .await
+            .unwrap();
+        let user_roles = roles::get_roles(tx.as_mut(), user).await.unwrap();
+        assert_eq!(user_roles.len(), 1);
+        assert_eq!(user_roles[0].custom_role_id, Some(id));
+
+        let with_permissions = get_with_permissions_by_organization_id(tx.as_mut(), org)
+            .await
+            .unwrap();
+        assert_eq!(with_permissions.len(), 1);
+        assert_eq!(with_permissions[0].permissions.len(), 1);
+
+        delete(tx.as_mut(), id).await.unwrap();
+        assert!(roles::get_roles(tx.as_mut(), user)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(get_by_organization_id(tx.as_mut(), org)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -45,6 +45,22 @@ LIMIT 10000;
     Ok(emails)
 }
 
+/// The number of emails that are waiting to be sent.
+pub async fn get_unsent_count(conn: &mut PgConnection) -> ModelResult<i64> {
+    let res = sqlx::query!(
+        "
+SELECT COUNT(*) AS count
+FROM email_deliveries
+WHERE deleted_at IS NULL
+  AND sent = FALSE
+  AND error IS NULL
+"
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.count.unwrap_or(0))
+}
+
 pub async fn mark_as_sent(email_id: Uuid, conn: &mut PgConnection) -> ModelResult<()> {
     sqlx::query!(
         "
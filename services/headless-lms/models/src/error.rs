@@ -9,6 +9,8 @@ use headless_lms_utils::error::{backend_error::BackendError, util_error::UtilErr
 use tracing_error::SpanTrace;
 use uuid::Uuid;
 
+use crate::{page_edit_locks::PageEditLock, pages::PageSaveConflict};
+
 /**
 Used as the result types for all models.
 
@@ -167,7 +169,7 @@ impl BackendError for ModelError {
 }
 
 /// The type of [ModelError] that occured.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub enum ModelErrorType {
     RecordNotFound,
     NotFound,
@@ -181,6 +183,10 @@ pub enum ModelErrorType {
         description: &'static str,
     },
     InvalidRequest,
+    /// The page was saved by someone else after the update was started.
+    PageSaveConflict(Box<PageSaveConflict>),
+    /// Someone else holds the edit lock of the page.
+    PageEditLocked(Box<PageEditLock>),
     Conversion,
     Database,
     Json,
@@ -112,6 +112,12 @@ pub struct ExerciseSlideSubmissionInfo {
     pub exercise_slide_submission: ExerciseSlideSubmission,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExerciseAndCourseInstanceIds {
+    pub exercise_id: Uuid,
+    pub course_instance_id: Uuid,
+}
+
 pub async fn insert_exercise_slide_submission(
     conn: &mut PgConnection,
     exercise_slide_submission: NewExerciseSlideSubmission,
@@ -342,6 +348,28 @@ pub async fn try_to_get_users_latest_exercise_slide_submission(
         .optional()
 }
 
+/// Returns the exercises and course instances that have received submissions after the given time.
+pub async fn get_exercise_and_course_instance_ids_with_submissions_since(
+    conn: &mut PgConnection,
+    since: DateTime<Utc>,
+) -> ModelResult<Vec<ExerciseAndCourseInstanceIds>> {
+    let res = sqlx::query_as!(
+        ExerciseAndCourseInstanceIds,
+        r#"
+SELECT DISTINCT exercise_id,
+  course_instance_id AS "course_instance_id!"
+FROM exercise_slide_submissions
+WHERE created_at > $1
+  AND course_instance_id IS NOT NULL
+  AND deleted_at IS NULL
+        "#,
+        since
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
 pub async fn get_course_and_exam_id(
     conn: &mut PgConnection,
     id: Uuid,
@@ -0,0 +1,194 @@
+use crate::{prelude::*, teacher_grading_decisions::TeacherDecisionType};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ExerciseTaskSubmissionSimilarity {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub exercise_id: Uuid,
+    pub course_instance_id: Uuid,
+    pub exercise_task_id: Uuid,
+    pub first_exercise_task_submission_id: Uuid,
+    pub second_exercise_task_submission_id: Uuid,
+    pub similarity: f32,
+}
+
+/// One of the submissions in a pair of similar submissions, with the information the teacher needs for reviewing it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct SimilarSubmission {
+    pub exercise_task_submission_id: Uuid,
+    pub exercise_slide_submission_id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub data_json: Option<serde_json::Value>,
+    /// Needed for recording a teacher grading decision for the submission.
+    pub user_exercise_state_id: Option<Uuid>,
+    pub latest_teacher_decision: Option<TeacherDecisionType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ExerciseTaskSubmissionSimilarityInfo {
+    pub id: Uuid,
+    pub course_instance_id: Uuid,
+    pub exercise_task_id: Uuid,
+    pub similarity: f32,
+    pub first: SimilarSubmission,
+    pub second: SimilarSubmission,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+    exercise_task_id: Uuid,
+    exercise_task_submission_ids: (Uuid, Uuid),
+    similarity: f32,
+) -> ModelResult<Uuid> {
+    // Each pair is stored only once, with the smaller id first
+    let (first_exercise_task_submission_id, second_exercise_task_submission_id) =
+        if exercise_task_submission_ids.0 < exercise_task_submission_ids.1 {
+            exercise_task_submission_ids
+        } else {
+            (
+                exercise_task_submission_ids.1,
+                exercise_task_submission_ids.0,
+            )
+        };
+    let res = sqlx::query!(
+        "
+INSERT INTO exercise_task_submission_similarities (
+    exercise_id,
+    course_instance_id,
+    exercise_task_id,
+    first_exercise_task_submission_id,
+    second_exercise_task_submission_id,
+    similarity
+  )
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING id
+        ",
+        exercise_id,
+        course_instance_id,
+        exercise_task_id,
+        first_exercise_task_submission_id,
+        second_exercise_task_submission_id,
+        similarity,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn delete_by_exercise_and_course_instance_ids(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE exercise_task_submission_similarities
+SET deleted_at = now()
+WHERE exercise_id = $1
+  AND course_instance_id = $2
+  AND deleted_at IS NULL
+        ",
+        exercise_id,
+        course_instance_id,
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Returns the similar submission pairs of the exercise, the most similar first.
+pub async fn get_by_exercise_id(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+) -> ModelResult<Vec<ExerciseTaskSubmissionSimilarityInfo>> {
+    let res = sqlx::query!(
+        r#"
+SELECT etss.id,
+  etss.course_instance_id,
+  etss.exercise_task_id,
+  etss.similarity,
+  first_ets.id AS first_exercise_task_submission_id,
+  first_ets.exercise_slide_submission_id AS first_exercise_slide_submission_id,
+  first_ess.user_id AS first_user_id,
+  first_ets.created_at AS first_created_at,
+  first_ets.data_json AS first_data_json,
+  first_ues.id AS "first_user_exercise_state_id?",
+  (
+    SELECT tgd.teacher_decision
+    FROM teacher_grading_decisions tgd
+    WHERE tgd.user_exercise_state_id = first_ues.id
+      AND tgd.deleted_at IS NULL
+    ORDER BY tgd.created_at DESC
+    LIMIT 1
+  ) AS "first_latest_teacher_decision?: TeacherDecisionType",
+  second_ets.id AS second_exercise_task_submission_id,
+  second_ets.exercise_slide_submission_id AS second_exercise_slide_submission_id,
+  second_ess.user_id AS second_user_id,
+  second_ets.created_at AS second_created_at,
+  second_ets.data_json AS second_data_json,
+  second_ues.id AS "second_user_exercise_state_id?",
+  (
+    SELECT tgd.teacher_decision
+    FROM teacher_grading_decisions tgd
+    WHERE tgd.user_exercise_state_id = second_ues.id
+      AND tgd.deleted_at IS NULL
+    ORDER BY tgd.created_at DESC
+    LIMIT 1
+  ) AS "second_latest_teacher_decision?: TeacherDecisionType"
+FROM exercise_task_submission_similarities etss
+  JOIN exercise_task_submissions first_ets ON first_ets.id = etss.first_exercise_task_submission_id
+  JOIN exercise_slide_submissions first_ess ON first_ess.id = first_ets.exercise_slide_submission_id
+  LEFT JOIN user_exercise_states first_ues ON first_ues.user_id = first_ess.user_id
+  AND first_ues.exercise_id = etss.exercise_id
+  AND first_ues.course_instance_id = etss.course_instance_id
+  AND first_ues.deleted_at IS NULL
+  JOIN exercise_task_submissions second_ets ON second_ets.id = etss.second_exercise_task_submission_id
+  JOIN exercise_slide_submissions second_ess ON second_ess.id = second_ets.exercise_slide_submission_id
+  LEFT JOIN user_exercise_states second_ues ON second_ues.user_id = second_ess.user_id
+  AND second_ues.exercise_id = etss.exercise_id
+  AND second_ues.course_instance_id = etss.course_instance_id
+  AND second_ues.deleted_at IS NULL
+WHERE etss.exercise_id = $1
+  AND etss.deleted_at IS NULL
+ORDER BY etss.similarity DESC
+        "#,
+        exercise_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|r| ExerciseTaskSubmissionSimilarityInfo {
+        id: r.id,
+        course_instance_id: r.course_instance_id,
+        exercise_task_id: r.exercise_task_id,
+        similarity: r.similarity,
+        first: SimilarSubmission {
+            exercise_task_submission_id: r.first_exercise_task_submission_id,
+            exercise_slide_submission_id: r.first_exercise_slide_submission_id,
+            user_id: r.first_user_id,
+            created_at: r.first_created_at,
+            data_json: r.first_data_json,
+            user_exercise_state_id: r.first_user_exercise_state_id,
+            latest_teacher_decision: r.first_latest_teacher_decision,
+        },
+        second: SimilarSubmission {
+            exercise_task_submission_id: r.second_exercise_task_submission_id,
+            exercise_slide_submission_id: r.second_exercise_slide_submission_id,
+            user_id: r.second_user_id,
+            created_at: r.second_created_at,
+            data_json: r.second_data_json,
+            user_exercise_state_id: r.second_user_exercise_state_id,
+            latest_teacher_decision: r.second_latest_teacher_decision,
+        },
+    })
+    .collect();
+    Ok(res)
+}
@@ -10,6 +10,8 @@ use crate::{
     exercise_tasks::{CourseMaterialExerciseTask, ExerciseTask},
     peer_review_question_submissions::PeerReviewQuestionSubmission,
     peer_review_questions::PeerReviewQuestion,
+    peer_review_submission_flags::{self, PeerReviewSubmissionFlag},
+    peer_review_submission_ratings::{self, PeerReviewSubmissionRating},
     prelude::*,
     CourseOrExamId,
 };
@@ -34,6 +36,10 @@ pub struct ExerciseTaskSubmission {
 pub struct PeerReviewsRecieved {
     pub peer_review_questions: Vec<PeerReviewQuestion>,
     pub peer_review_question_submissions: Vec<PeerReviewQuestionSubmission>,
+    /// Helpfulness ratings the user has given to the received peer reviews.
+    pub peer_review_submission_ratings: Vec<PeerReviewSubmissionRating>,
+    /// Flags the user has given to the received peer reviews.
+    pub peer_review_submission_flags: Vec<PeerReviewSubmissionFlag>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -204,6 +210,46 @@ WHERE exercise_slide_submission_id = $1
     }
 }
 
+/// The latest submission of a user to an exercise task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsersLatestExerciseTaskSubmission {
+    pub id: Uuid,
+    pub exercise_task_id: Uuid,
+    pub user_id: Uuid,
+    pub data_json: Option<serde_json::Value>,
+}
+
+/// Returns the latest submission of each user to each task of the exercise in the course instance.
+pub async fn get_users_latest_exercise_task_submissions_by_exercise_and_course_instance_ids(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+) -> ModelResult<Vec<UsersLatestExerciseTaskSubmission>> {
+    let res = sqlx::query_as!(
+        UsersLatestExerciseTaskSubmission,
+        "
+SELECT DISTINCT ON (ets.exercise_task_id, ess.user_id) ets.id,
+  ets.exercise_task_id,
+  ess.user_id,
+  ets.data_json
+FROM exercise_task_submissions ets
+  JOIN exercise_slide_submissions ess ON ess.id = ets.exercise_slide_submission_id
+WHERE ess.exercise_id = $1
+  AND ess.course_instance_id = $2
+  AND ets.deleted_at IS NULL
+  AND ess.deleted_at IS NULL
+ORDER BY ets.exercise_task_id,
+  ess.user_id,
+  ets.created_at DESC
+        ",
+        exercise_id,
+        course_instance_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
 pub async fn get_course_and_exam_id(
     conn: &mut PgConnection,
     id: Uuid,
@@ -261,10 +307,26 @@ pub async fn get_peer_reviews_received(
             exercise_slide_submission_id,
         )
         .await?;
+    let peer_review_submission_ratings =
+        peer_review_submission_ratings::get_by_exercise_slide_submission_id_and_user_id(
+            &mut *conn,
+            exercise_slide_submission_id,
+            user_id,
+        )
+        .await?;
+    let peer_review_submission_flags =
+        peer_review_submission_flags::get_by_exercise_slide_submission_id_and_flagged_by(
+            &mut *conn,
+            exercise_slide_submission_id,
+            user_id,
+        )
+        .await?;
 
     Ok(PeerReviewsRecieved {
         peer_review_questions,
         peer_review_question_submissions,
+        peer_review_submission_ratings,
+        peer_review_submission_flags,
     })
 }
 
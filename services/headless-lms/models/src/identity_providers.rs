@@ -0,0 +1,165 @@
+use crate::prelude::*;
+
+/// An OpenID Connect identity provider configured for an organization. Contains the client secret, so this should not be returned from the API, see `IdentityProviderInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityProvider {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub scopes: String,
+    pub email_claim: String,
+    pub first_name_claim: String,
+    pub last_name_claim: String,
+    /// If true, users are linked to existing users with the same email if the identity provider has verified the email.
+    pub link_existing_users_by_email: bool,
+}
+
+/// The public information about an identity provider, for example for showing the provider on the login page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct IdentityProviderInfo {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub issuer_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewIdentityProvider {
+    pub name: String,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub scopes: String,
+    pub email_claim: String,
+    pub first_name_claim: String,
+    pub last_name_claim: String,
+    pub link_existing_users_by_email: bool,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    pkey_policy: PKeyPolicy<Uuid>,
+    organization_id: Uuid,
+    new_identity_provider: &NewIdentityProvider,
+) -> ModelResult<IdentityProviderInfo> {
+    let res = sqlx::query_as!(
+        IdentityProviderInfo,
+        "
+INSERT INTO identity_providers (
+    id,
+    organization_id,
+    name,
+    issuer_url,
+    client_id,
+    client_secret,
+    scopes,
+    email_claim,
+    first_name_claim,
+    last_name_claim,
+    link_existing_users_by_email
+  )
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+RETURNING id,
+  organization_id,
+  name,
+  issuer_url
+        ",
+        pkey_policy.into_uuid(),
+        organization_id,
+        new_identity_provider.name,
+        new_identity_provider.issuer_url,
+        new_identity_provider.client_id,
+        new_identity_provider.client_secret,
+        new_identity_provider.scopes,
+        new_identity_provider.email_claim,
+        new_identity_provider.first_name_claim,
+        new_identity_provider.last_name_claim,
+        new_identity_provider.link_existing_users_by_email,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<IdentityProvider> {
+    let res = sqlx::query_as!(
+        IdentityProvider,
+        "
+SELECT *
+FROM identity_providers
+WHERE id = $1
+  AND deleted_at IS NULL
+        ",
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_all(conn: &mut PgConnection) -> ModelResult<Vec<IdentityProviderInfo>> {
+    let res = sqlx::query_as!(
+        IdentityProviderInfo,
+        "
+SELECT id,
+  organization_id,
+  name,
+  issuer_url
+FROM identity_providers
+WHERE deleted_at IS NULL
+ORDER BY name
+        "
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_organization_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+) -> ModelResult<Vec<IdentityProviderInfo>> {
+    let res = sqlx::query_as!(
+        IdentityProviderInfo,
+        "
+SELECT id,
+  organization_id,
+  name,
+  issuer_url
+FROM identity_providers
+WHERE organization_id = $1
+  AND deleted_at IS NULL
+ORDER BY name
+        ",
+        organization_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete(conn: &mut PgConnection, organization_id: Uuid, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE identity_providers
+SET deleted_at = now()
+WHERE id = $1
+  AND organization_id = $2
+  AND deleted_at IS NULL
+RETURNING id
+        ",
+        id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
@@ -3,7 +3,10 @@ Functions and structs for interacting with the database.
 
 Each submodule corresponds to a database table.
 */
+pub mod api_tokens;
+pub mod audit_log_entries;
 pub mod chapters;
+pub mod content_lint_issues;
 pub mod course_background_question_answers;
 pub mod course_background_questions;
 pub mod course_exams;
@@ -14,6 +17,7 @@ pub mod course_module_completion_registered_to_study_registries;
 pub mod course_module_completions;
 pub mod course_modules;
 pub mod courses;
+pub mod custom_roles;
 pub mod email_deliveries;
 pub mod email_templates;
 pub mod ended_processed_exams;
@@ -25,40 +29,64 @@ pub mod exercise_slide_submissions;
 pub mod exercise_slides;
 pub mod exercise_task_gradings;
 pub mod exercise_task_regrading_submissions;
+pub mod exercise_task_submission_similarities;
 pub mod exercise_task_submissions;
 pub mod exercise_tasks;
 pub mod exercises;
 pub mod feedback;
 pub mod glossary;
+pub mod identity_providers;
 pub mod library;
+pub mod lti_contexts;
+pub mod lti_deep_linking_requests;
+pub mod lti_login_states;
+pub mod lti_registrations;
+pub mod lti_resource_links;
+pub mod lti_scores;
+pub mod lti_users;
 pub mod material_references;
 pub mod open_university_registration_links;
 pub mod organizations;
+pub mod page_drafts;
+pub mod page_edit_locks;
 pub mod page_history;
 pub mod page_visit_datum;
 pub mod page_visit_datum_daily_visit_hashing_keys;
 pub mod pages;
+pub mod peer_review_calibration_answers;
+pub mod peer_review_calibration_reference_answers;
+pub mod peer_review_calibration_samples;
 pub mod peer_review_configs;
 pub mod peer_review_question_submissions;
 pub mod peer_review_questions;
 pub mod peer_review_queue_entries;
+pub mod peer_review_submission_flags;
+pub mod peer_review_submission_ratings;
 pub mod peer_review_submissions;
 pub mod pending_roles;
 pub mod playground_examples;
 pub mod proposed_block_edits;
 pub mod proposed_page_edits;
+pub mod rate_limits;
 pub mod regradings;
 pub mod repository_exercises;
+pub mod role_permissions;
+pub mod role_requests;
 pub mod roles;
 pub mod study_registry_registrars;
 pub mod teacher_grading_decisions;
 pub mod url_redirections;
 pub mod user_course_instance_exercise_service_variables;
 pub mod user_course_settings;
+pub mod user_data_exports;
 pub mod user_exercise_slide_states;
 pub mod user_exercise_states;
 pub mod user_exercise_task_states;
+pub mod user_identities;
 pub mod users;
+pub mod webhook_deliveries;
+pub mod webhook_delivery_attempts;
+pub mod webhook_subscriptions;
 
 pub mod error;
 pub mod prelude;
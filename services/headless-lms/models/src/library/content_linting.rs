@@ -0,0 +1,533 @@
+/*!
+Finds problems in the content of the pages of a course: broken internal links, links through redirections, missing media files, unsupported embeds, images without alternative text and exercise blocks whose exercise is gone.
+*/
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use headless_lms_utils::{
+    document_schema_processor::GutenbergBlock, file_store::FileStore,
+    url_to_oembed_endpoint::url_to_oembed_endpoint, ApplicationConfiguration,
+};
+use url::Url;
+
+use crate::{
+    content_lint_issues::{self, ContentLintIssueType, ContentLintRun, NewContentLintIssue},
+    courses, exercises, organizations,
+    pages::{self, PageVisibility},
+    prelude::*,
+    url_redirections,
+};
+
+/// The path under which the files in the file store are served.
+const FILE_STORE_PATH_PREFIX: &str = "/api/v0/files/";
+
+/// A url found in the content of a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ContentReference {
+    /// A link to a page of the course, as a url path of the course.
+    CoursePage { link: String, url_path: String },
+    /// A file in the file store, as a path in the file store.
+    Media { link: String, path: String },
+    /// Anything else, such as a link to another site or to another course.
+    Other,
+}
+
+/// What the linter knows about the pages of the course.
+struct CourseLinkTargets {
+    /// Path of the course pages on the site, e.g. `/org/uh-cs/courses/introduction-to-everything`.
+    course_path: String,
+    /// Whether the page with the url path is hidden, for each page that has not been deleted.
+    hidden_by_url_path: HashMap<String, bool>,
+    deleted_url_paths: HashSet<String>,
+    /// The link to the page each redirected url path finally leads to, or None if the redirection is broken.
+    redirections: HashMap<String, Option<String>>,
+}
+
+/// Checks all the pages of the course and stores the found issues, replacing the results of the previous check.
+pub async fn lint_course(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    file_store: &dyn FileStore,
+    app_conf: &ApplicationConfiguration,
+) -> ModelResult<ContentLintRun> {
+    let course = courses::get_course(conn, course_id).await?;
+    let organization = organizations::get_organization(conn, course.organization_id).await?;
+    let pages =
+        pages::get_all_by_course_id_and_visibility(conn, course_id, PageVisibility::Any).await?;
+    let deleted_url_paths = pages::get_deleted_url_paths_by_course_id(conn, course_id).await?;
+    let redirections = url_redirections::get_by_course_id_with_resolutions(conn, course_id).await?;
+    let course_slugs: HashMap<Uuid, String> =
+        courses::get_all_language_versions_of_course(conn, &course)
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c.slug))
+            .collect();
+    let exercises = exercises::get_exercises_by_course_id(conn, course_id).await?;
+
+    let targets = CourseLinkTargets {
+        course_path: format!("/org/{}/courses/{}", organization.slug, course.slug),
+        hidden_by_url_path: pages
+            .iter()
+            .map(|page| (page.url_path.clone(), page.hidden))
+            .collect(),
+        deleted_url_paths: deleted_url_paths.into_iter().collect(),
+        redirections: redirections
+            .into_iter()
+            .map(|r| {
+                let link = match (r.resolution.course_id, r.resolution.url_path) {
+                    (Some(course_id), Some(url_path)) => {
+                        course_slugs.get(&course_id).map(|course_slug| {
+                            format!(
+                                "/org/{}/courses/{}{}",
+                                organization.slug, course_slug, url_path
+                            )
+                        })
+                    }
+                    _ => None,
+                };
+                (r.redirection.old_url_path, link)
+            })
+            .collect(),
+    };
+    let base_url = Url::parse(&app_conf.base_url)?;
+
+    let mut issues = vec![];
+    let mut media_exists: HashMap<String, bool> = HashMap::new();
+    for page in pages.iter() {
+        let blocks: Vec<GutenbergBlock> = match serde_json::from_value(page.content.clone()) {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                warn!(page_id = ?page.id, "Failed to parse the content of the page: {:#?}", err);
+                continue;
+            }
+        };
+        let exercise_ids = exercises
+            .iter()
+            .filter(|exercise| exercise.page_id == page.id)
+            .map(|exercise| exercise.id)
+            .collect::<HashSet<_>>();
+        let mut page_issues = vec![];
+        lint_blocks(
+            &blocks,
+            &base_url,
+            &targets,
+            &exercise_ids,
+            &mut |block_id, issue_type, target, suggestion| {
+                page_issues.push(NewContentLintIssue {
+                    page_id: page.id,
+                    block_id: Some(block_id),
+                    issue_type,
+                    target,
+                    suggestion,
+                })
+            },
+        );
+        for (block_id, link, path) in collect_media(&blocks, &base_url, &targets) {
+            let exists = match media_exists.get(&path) {
+                Some(exists) => *exists,
+                None => {
+                    // the file store fails to create a download url for files it does not have
+                    let exists = file_store
+                        .get_direct_download_url(Path::new(&path))
+                        .await
+                        .is_ok();
+                    media_exists.insert(path, exists);
+                    exists
+                }
+            };
+            if !exists {
+                page_issues.push(NewContentLintIssue {
+                    page_id: page.id,
+                    block_id: Some(block_id),
+                    issue_type: ContentLintIssueType::MissingMediaFile,
+                    target: link,
+                    suggestion: None,
+                });
+            }
+        }
+        issues.extend(page_issues);
+    }
+    let run = content_lint_issues::insert_run(conn, course_id, pages.len() as i32, &issues).await?;
+    Ok(run)
+}
+
+/// Reports the issues that can be found without the file store.
+fn lint_blocks(
+    blocks: &[GutenbergBlock],
+    base_url: &Url,
+    targets: &CourseLinkTargets,
+    exercise_ids: &HashSet<Uuid>,
+    report: &mut impl FnMut(Uuid, ContentLintIssueType, String, Option<String>),
+) {
+    for block in blocks {
+        match block.name.as_str() {
+            "core/image" => {
+                let alt = block.attributes.get("alt").and_then(|alt| alt.as_str());
+                if alt.map(|alt| alt.trim().is_empty()).unwrap_or(true) {
+                    let url = block
+                        .attributes
+                        .get("url")
+                        .and_then(|url| url.as_str())
+                        .unwrap_or_default();
+                    report(
+                        block.client_id,
+                        ContentLintIssueType::ImageWithoutAltText,
+                        url.to_string(),
+                        None,
+                    );
+                }
+            }
+            "core/embed" => {
+                if let Some(url) = block.attributes.get("url").and_then(|url| url.as_str()) {
+                    if url_to_oembed_endpoint(url.to_string(), Some(base_url.to_string())).is_err()
+                    {
+                        report(
+                            block.client_id,
+                            ContentLintIssueType::UnsupportedEmbed,
+                            url.to_string(),
+                            None,
+                        );
+                    }
+                }
+            }
+            "moocfi/exercise" => {
+                let id = block
+                    .attributes
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .unwrap_or_default();
+                let exists = Uuid::parse_str(id)
+                    .map(|id| exercise_ids.contains(&id))
+                    .unwrap_or(false);
+                if !exists {
+                    report(
+                        block.client_id,
+                        ContentLintIssueType::MissingExercise,
+                        id.to_string(),
+                        None,
+                    );
+                }
+            }
+            _ => {}
+        }
+        if block.name != "core/embed" {
+            for link in block_links(block) {
+                if let ContentReference::CoursePage { link, url_path } =
+                    classify_link(&link, base_url, &targets.course_path)
+                {
+                    if let Some((issue_type, suggestion)) = check_course_link(&url_path, targets) {
+                        report(block.client_id, issue_type, link, suggestion);
+                    }
+                }
+            }
+        }
+        lint_blocks(&block.inner_blocks, base_url, targets, exercise_ids, report);
+    }
+}
+
+/// Returns the block ids, links and file store paths of the media files referenced in the blocks.
+fn collect_media(
+    blocks: &[GutenbergBlock],
+    base_url: &Url,
+    targets: &CourseLinkTargets,
+) -> Vec<(Uuid, String, String)> {
+    let mut res = vec![];
+    for block in blocks {
+        for link in block_links(block) {
+            if let ContentReference::Media { link, path } =
+                classify_link(&link, base_url, &targets.course_path)
+            {
+                res.push((block.client_id, link, path));
+            }
+        }
+        res.extend(collect_media(&block.inner_blocks, base_url, targets));
+    }
+    res
+}
+
+/// Tells whether a link to the url path of the course has a problem.
+fn check_course_link(
+    url_path: &str,
+    targets: &CourseLinkTargets,
+) -> Option<(ContentLintIssueType, Option<String>)> {
+    if let Some(hidden) = targets.hidden_by_url_path.get(url_path) {
+        if *hidden {
+            return Some((ContentLintIssueType::LinkToHiddenPage, None));
+        }
+        return None;
+    }
+    if let Some(destination) = targets.redirections.get(url_path) {
+        return match destination {
+            Some(link) => Some((
+                ContentLintIssueType::LinkThroughRedirection,
+                Some(link.clone()),
+            )),
+            None => Some((ContentLintIssueType::LinkToDeletedPage, None)),
+        };
+    }
+    if targets.deleted_url_paths.contains(url_path) {
+        return Some((ContentLintIssueType::LinkToDeletedPage, None));
+    }
+    Some((ContentLintIssueType::LinkToMissingPage, None))
+}
+
+/// Returns the urls in the attributes of the block: the values of the url-like attributes and the `href` and `src` attributes of the HTML in the other attributes.
+fn block_links(block: &GutenbergBlock) -> Vec<String> {
+    let mut res = vec![];
+    for (key, value) in block.attributes.iter() {
+        if let Some(value) = value.as_str() {
+            if matches!(key.as_str(), "url" | "href" | "src" | "mediaLink") {
+                res.push(value.to_string());
+            } else {
+                res.extend(html_links(value));
+            }
+        }
+    }
+    res
+}
+
+/// Returns the values of the `href` and `src` attributes in the HTML.
+fn html_links(html: &str) -> Vec<String> {
+    let mut res = vec![];
+    for attribute in ["href=\"", "src=\""] {
+        let mut rest = html;
+        while let Some(start) = rest.find(attribute) {
+            rest = &rest[start + attribute.len()..];
+            if let Some(end) = rest.find('"') {
+                res.push(rest[..end].replace("&amp;", "&"));
+                rest = &rest[end..];
+            }
+        }
+    }
+    res
+}
+
+/// Figures out whether the link points to a page of the course, to the file store or somewhere else. Only absolute urls and absolute paths are considered.
+fn classify_link(link: &str, base_url: &Url, course_path: &str) -> ContentReference {
+    let url = if link.starts_with('/') && !link.starts_with("//") {
+        base_url.join(link)
+    } else {
+        Url::parse(link)
+    };
+    let url = match url {
+        Ok(url) => url,
+        Err(_) => return ContentReference::Other,
+    };
+    if url.host_str() != base_url.host_str() || !matches!(url.scheme(), "http" | "https") {
+        return ContentReference::Other;
+    }
+    let path = url.path();
+    if let Some(file_path) = path.strip_prefix(FILE_STORE_PATH_PREFIX) {
+        return ContentReference::Media {
+            link: link.to_string(),
+            path: file_path.to_string(),
+        };
+    }
+    if let Some(url_path) = path.strip_prefix(course_path) {
+        if url_path.is_empty() || url_path.starts_with('/') {
+            let url_path = url_path.trim_end_matches('/');
+            return ContentReference::CoursePage {
+                link: link.to_string(),
+                url_path: if url_path.is_empty() {
+                    "/".to_string()
+                } else {
+                    url_path.to_string()
+                },
+            };
+        }
+    }
+    ContentReference::Other
+}
+
+#[cfg(test)]
+mod test {
+    use headless_lms_utils::attributes;
+
+    use super::*;
+
+    fn targets() -> CourseLinkTargets {
+        CourseLinkTargets {
+            course_path: "/org/uh-cs/courses/course".to_string(),
+            hidden_by_url_path: HashMap::from([
+                ("/".to_string(), false),
+                ("/chapter-1/page".to_string(), false),
+                ("/chapter-1/hidden".to_string(), true),
+            ]),
+            deleted_url_paths: HashSet::from(["/chapter-1/deleted".to_string()]),
+            redirections: HashMap::from([(
+                "/chapter-1/old".to_string(),
+                Some("/org/uh-cs/courses/course/chapter-1/page".to_string()),
+            )]),
+        }
+    }
+
+    fn lint(blocks: &[GutenbergBlock]) -> Vec<(ContentLintIssueType, String, Option<String>)> {
+        let base_url = Url::parse("http://project-331.local").unwrap();
+        let mut res = vec![];
+        lint_blocks(
+            blocks,
+            &base_url,
+            &targets(),
+            &HashSet::new(),
+            &mut |_, issue_type, target, suggestion| res.push((issue_type, target, suggestion)),
+        );
+        res
+    }
+
+    #[test]
+    fn classifies_links() {
+        let base_url = Url::parse("http://project-331.local").unwrap();
+        let course_path = "/org/uh-cs/courses/course";
+        assert_eq!(
+            classify_link(
+                "/org/uh-cs/courses/course/chapter-1/",
+                &base_url,
+                course_path
+            ),
+            ContentReference::CoursePage {
+                link: "/org/uh-cs/courses/course/chapter-1/".to_string(),
+                url_path: "/chapter-1".to_string()
+            }
+        );
+        assert_eq!(
+            classify_link(
+                "http://project-331.local/org/uh-cs/courses/course",
+                &base_url,
+                course_path
+            ),
+            ContentReference::CoursePage {
+                link: "http://project-331.local/org/uh-cs/courses/course".to_string(),
+                url_path: "/".to_string()
+            }
+        );
+        assert_eq!(
+            classify_link(
+                "http://project-331.local/api/v0/files/organizations/images/a.png",
+                &base_url,
+                course_path
+            ),
+            ContentReference::Media {
+                link: "http://project-331.local/api/v0/files/organizations/images/a.png"
+                    .to_string(),
+                path: "organizations/images/a.png".to_string()
+            }
+        );
+        assert_eq!(
+            classify_link("/org/uh-cs/courses/course-2/page", &base_url, course_path),
+            ContentReference::Other
+        );
+        assert_eq!(
+            classify_link(
+                "https://example.com/org/uh-cs/courses/course",
+                &base_url,
+                course_path
+            ),
+            ContentReference::Other
+        );
+        assert_eq!(
+            classify_link("#heading", &base_url, course_path),
+            ContentReference::Other
+        );
+    }
+
+    #[test]
+    fn finds_links_in_html() {
+        assert_eq!(
+            html_links(r#"See <a href="/a?b=1&amp;c=2">this</a> and <img src="/b.png" />"#),
+            vec!["/a?b=1&c=2".to_string(), "/b.png".to_string()]
+        );
+    }
+
+    #[test]
+    fn reports_broken_course_links() {
+        let html = [
+            "/chapter-1/page",
+            "/chapter-1/hidden",
+            "/chapter-1/old",
+            "/chapter-1/deleted",
+            "/chapter-1/missing",
+        ]
+        .iter()
+        .map(|path| format!(r#"<a href="/org/uh-cs/courses/course{}">link</a>"#, path))
+        .collect::<String>();
+        let blocks = vec![GutenbergBlock::paragraph(&html)];
+        assert_eq!(
+            lint(&blocks),
+            vec![
+                (
+                    ContentLintIssueType::LinkToHiddenPage,
+                    "/org/uh-cs/courses/course/chapter-1/hidden".to_string(),
+                    None
+                ),
+                (
+                    ContentLintIssueType::LinkThroughRedirection,
+                    "/org/uh-cs/courses/course/chapter-1/old".to_string(),
+                    Some("/org/uh-cs/courses/course/chapter-1/page".to_string())
+                ),
+                (
+                    ContentLintIssueType::LinkToDeletedPage,
+                    "/org/uh-cs/courses/course/chapter-1/deleted".to_string(),
+                    None
+                ),
+                (
+                    ContentLintIssueType::LinkToMissingPage,
+                    "/org/uh-cs/courses/course/chapter-1/missing".to_string(),
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_images_embeds_and_exercises() {
+        let blocks = vec![GutenbergBlock::block_with_name_attributes_and_inner_blocks(
+            "core/columns",
+            attributes! {},
+            vec![
+                GutenbergBlock::block_with_name_and_attributes(
+                    "core/image",
+                    attributes! { "url": "https://example.com/a.png", "alt": "" },
+                ),
+                GutenbergBlock::block_with_name_and_attributes(
+                    "core/image",
+                    attributes! { "url": "https://example.com/b.png", "alt": "A cat" },
+                ),
+                GutenbergBlock::block_with_name_and_attributes(
+                    "core/embed",
+                    attributes! { "url": "https://example.com/video" },
+                ),
+                GutenbergBlock::block_with_name_and_attributes(
+                    "core/embed",
+                    attributes! { "url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ" },
+                ),
+                GutenbergBlock::block_with_name_and_attributes(
+                    "moocfi/exercise",
+                    attributes! { "id": "b3a2c5b0-0c4e-4a4b-8b8e-6a1f2a3b4c5d" },
+                ),
+            ],
+        )];
+        assert_eq!(
+            lint(&blocks),
+            vec![
+                (
+                    ContentLintIssueType::ImageWithoutAltText,
+                    "https://example.com/a.png".to_string(),
+                    None
+                ),
+                (
+                    ContentLintIssueType::UnsupportedEmbed,
+                    "https://example.com/video".to_string(),
+                    None
+                ),
+                (
+                    ContentLintIssueType::MissingExercise,
+                    "b3a2c5b0-0c4e-4a4b-8b8e-6a1f2a3b4c5d".to_string(),
+                    None
+                ),
+            ]
+        );
+    }
+}
@@ -117,6 +117,7 @@ pub async fn create_new_course(
                 question: "General comments".to_string(),
                 question_type: crate::peer_review_questions::PeerReviewQuestionType::Essay,
                 answer_required: false,
+                rubric: None,
             },
             CmsPeerReviewQuestion {
                 id: Uuid::new_v4(),
@@ -125,6 +126,7 @@ pub async fn create_new_course(
                 question: "The answer was correct".to_string(),
                 question_type: crate::peer_review_questions::PeerReviewQuestionType::Scale,
                 answer_required: true,
+                rubric: None,
             },
             CmsPeerReviewQuestion {
                 id: Uuid::new_v4(),
@@ -133,6 +135,7 @@ pub async fn create_new_course(
                 question: "The answer was easy to read".to_string(),
                 question_type: crate::peer_review_questions::PeerReviewQuestionType::Scale,
                 answer_required: true,
+                rubric: None,
             },
         ],
     )
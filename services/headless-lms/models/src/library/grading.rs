@@ -26,6 +26,7 @@ use crate::{
         self, CourseInstanceOrExamId, ExerciseWithUserState, UserExerciseState,
     },
     user_exercise_task_states,
+    webhook_deliveries::{self, WebhookEvent},
 };
 
 use super::user_exercise_state_updater;
@@ -499,6 +500,25 @@ pub async fn propagate_user_exercise_state_update_from_exercise_task_grading_res
         user_points_update_strategy,
     )
     .await?;
+    if updated_exercise_task_grading.grading_progress == GradingProgress::FullyGraded {
+        webhook_deliveries::insert_for_event(
+            conn,
+            CourseOrExamId::from(
+                updated_exercise_task_grading.course_id,
+                updated_exercise_task_grading.exam_id,
+            )?,
+            &WebhookEvent::SubmissionGraded {
+                user_id: user_exercise_state.user_id,
+                course_id: updated_exercise_task_grading.course_id,
+                exam_id: updated_exercise_task_grading.exam_id,
+                exercise_id: updated_exercise_task_grading.exercise_id,
+                exercise_task_submission_id: updated_exercise_task_grading
+                    .exercise_task_submission_id,
+                score_given: updated_exercise_task_grading.score_given,
+            },
+        )
+        .await?;
+    }
     Ok(user_exercise_state)
 }
 
@@ -1,7 +1,11 @@
+pub mod content_linting;
 pub mod content_management;
 pub mod copying;
 pub mod grading;
 pub mod peer_reviewing;
+pub mod plagiarism_detection;
 pub mod progressing;
 pub mod regrading;
+pub mod user_data_export;
+pub mod user_erasure;
 pub mod user_exercise_state_updater;
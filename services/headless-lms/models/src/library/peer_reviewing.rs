@@ -10,13 +10,23 @@ use crate::{
     exercise_task_submissions,
     exercise_tasks::CourseMaterialExerciseTask,
     exercises::Exercise,
+    peer_review_calibration_answers,
+    peer_review_calibration_reference_answers::{
+        self, NewPeerReviewCalibrationReferenceAnswer, PeerReviewCalibrationReferenceAnswer,
+    },
+    peer_review_calibration_samples::{self, PeerReviewCalibrationSampleWithReferenceAnswers},
     peer_review_configs::{self, PeerReviewConfig},
-    peer_review_question_submissions,
-    peer_review_questions::{self, PeerReviewQuestion},
+    peer_review_question_submissions::{self, PeerReviewWithQuestionsAndAnswers},
+    peer_review_questions::{self, PeerReviewQuestion, PeerReviewQuestionType},
     peer_review_queue_entries::{self, PeerReviewQueueEntry},
-    peer_review_submissions,
+    peer_review_submission_flags::{
+        self, PeerReviewSubmissionFlag, PeerReviewSubmissionFlagResolution,
+    },
+    peer_review_submission_ratings::{self, PeerReviewSubmissionRating},
+    peer_review_submissions::{self, PeerReviewSubmission},
     prelude::*,
     user_exercise_states::{self, CourseInstanceOrExamId, ReviewingStage, UserExerciseState},
+    webhook_deliveries::{self, WebhookEvent},
 };
 
 use super::user_exercise_state_updater::{
@@ -61,6 +71,8 @@ pub struct CourseMaterialPeerReviewQuestionAnswer {
     pub peer_review_question_id: Uuid,
     pub text_data: Option<String>,
     pub number_data: Option<f32>,
+    /// The selected level for each criterion if the question is a rubric question.
+    pub rubric_level_ids: Option<Vec<Uuid>>,
 }
 
 pub async fn create_peer_review_submission_for_user(
@@ -100,6 +112,7 @@ pub async fn create_peer_review_submission_for_user(
             peer_review_submission_id,
             answer.text_data,
             answer.number_data,
+            answer.rubric_level_ids,
         )
         .await?;
     }
@@ -147,6 +160,19 @@ pub async fn create_peer_review_submission_for_user(
     if let Some(entry) = receiver_peer_review_queue_entry {
         update_peer_review_receiver_exercise_status(&mut tx, exercise, &peer_review, entry).await?;
     }
+    let course_id = exercise.get_course_id()?;
+    webhook_deliveries::insert_for_event(
+        &mut tx,
+        CourseOrExamId::Course(course_id),
+        &WebhookEvent::PeerReviewReceived {
+            user_id: exercise_slide_submission.user_id,
+            course_id,
+            exercise_id: exercise.id,
+            exercise_slide_submission_id: exercise_slide_submission.id,
+            peer_review_submission_id,
+        },
+    )
+    .await?;
     tx.commit().await?;
 
     Ok(giver_exercise_state)
@@ -157,14 +183,15 @@ fn validate_and_sanitize_peer_review_submission_answers(
     mut peer_review_questions: HashMap<Uuid, PeerReviewQuestion>,
     peer_review_submission_question_answers: Vec<CourseMaterialPeerReviewQuestionAnswer>,
 ) -> ModelResult<Vec<CourseMaterialPeerReviewQuestionAnswer>> {
-    let valid_peer_review_question_answers = peer_review_submission_question_answers
-        .into_iter()
-        .filter(|answer| {
-            peer_review_questions
-                .remove(&answer.peer_review_question_id)
-                .is_some()
-        })
-        .collect();
+    let mut valid_peer_review_question_answers = vec![];
+    for answer in peer_review_submission_question_answers {
+        if let Some(question) = peer_review_questions.remove(&answer.peer_review_question_id) {
+            if question.question_type == PeerReviewQuestionType::Rubric {
+                validate_rubric_answer(&question, &answer)?;
+            }
+            valid_peer_review_question_answers.push(answer);
+        }
+    }
     if peer_review_questions
         .into_iter()
         .all(|question| !question.1.answer_required)
@@ -180,6 +207,49 @@ fn validate_and_sanitize_peer_review_submission_answers(
     }
 }
 
+/// Makes sure that the selected levels belong to the question's rubric and that at most one level is selected for each criterion. If the question is required, every criterion needs a selected level.
+fn validate_rubric_answer(
+    question: &PeerReviewQuestion,
+    answer: &CourseMaterialPeerReviewQuestionAnswer,
+) -> ModelResult<()> {
+    let rubric = question.rubric.as_ref().ok_or_else(|| {
+        ModelError::new(
+            ModelErrorType::Generic,
+            "Rubric question is missing its rubric.".to_string(),
+            None,
+        )
+    })?;
+    let selected_level_ids = answer.rubric_level_ids.as_deref().unwrap_or_default();
+    let mut answered_criteria = vec![];
+    for level_id in selected_level_ids {
+        let criterion = rubric
+            .find_criterion_by_level_id(*level_id)
+            .ok_or_else(|| {
+                ModelError::new(
+                    ModelErrorType::PreconditionFailed,
+                    "Selected rubric level does not belong to the question.".to_string(),
+                    None,
+                )
+            })?;
+        if answered_criteria.contains(&criterion.id) {
+            return Err(ModelError::new(
+                ModelErrorType::PreconditionFailed,
+                "Only one level can be selected for each rubric criterion.".to_string(),
+                None,
+            ));
+        }
+        answered_criteria.push(criterion.id);
+    }
+    if question.answer_required && answered_criteria.len() < rubric.criteria.len() {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "All required questions need to be answered.".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
+
 /// Creates or updates submitter's exercise state and peer review queue entry.
 async fn update_peer_review_giver_exercise_progress(
     conn: &mut PgConnection,
@@ -296,6 +366,8 @@ pub struct CourseMaterialPeerReviewData {
     pub peer_review_questions: Vec<PeerReviewQuestion>,
     #[cfg_attr(feature = "ts_rs", ts(type = "number"))]
     pub num_peer_reviews_given: i64,
+    /// If true, the answer to review is a calibration sample and the review should be submitted as a calibration review.
+    pub is_calibration_sample: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -325,7 +397,27 @@ pub async fn try_to_select_exercise_slide_submission_for_peer_review(
     )
     .await?;
     let course_instance_id = reviewer_user_exercise_state.get_course_instance_id()?;
-    let excluded_exercise_slide_submission_ids =
+    if let Some(calibration_sample_submission) = try_to_select_calibration_sample_for_peer_review(
+        conn,
+        &peer_review_config,
+        reviewer_user_exercise_state,
+    )
+    .await?
+    {
+        let data = get_course_material_peer_review_data(
+            conn,
+            &peer_review_config,
+            &Some(calibration_sample_submission),
+            reviewer_user_exercise_state.user_id,
+            course_instance_id,
+            exercise.id,
+            true,
+            fetch_service_info,
+        )
+        .await?;
+        return Ok(data);
+    }
+    let mut excluded_exercise_slide_submission_ids =
         peer_review_submissions::get_users_submission_ids_for_exercise_and_course_instance(
             conn,
             reviewer_user_exercise_state.user_id,
@@ -333,6 +425,16 @@ pub async fn try_to_select_exercise_slide_submission_for_peer_review(
             course_instance_id,
         )
         .await?;
+    // Answers already seen as calibration samples would not be reviewed impartially.
+    excluded_exercise_slide_submission_ids.extend(
+        peer_review_calibration_answers::get_reviewed_exercise_slide_submission_ids(
+            conn,
+            reviewer_user_exercise_state.user_id,
+            reviewer_user_exercise_state.exercise_id,
+            course_instance_id,
+        )
+        .await?,
+    );
     let candidate_submission_id = try_to_select_peer_review_candidate_from_queue(
         conn,
         reviewer_user_exercise_state.exercise_id,
@@ -361,6 +463,7 @@ pub async fn try_to_select_exercise_slide_submission_for_peer_review(
         reviewer_user_exercise_state.user_id,
         course_instance_id,
         exercise.id,
+        false,
         fetch_service_info,
     )
     .await?;
@@ -368,6 +471,46 @@ pub async fn try_to_select_exercise_slide_submission_for_peer_review(
     Ok(data)
 }
 
+/// Returns a calibration sample for the user to review if the peer review config requires the user to review more calibration samples.
+async fn try_to_select_calibration_sample_for_peer_review(
+    conn: &mut PgConnection,
+    peer_review_config: &PeerReviewConfig,
+    reviewer_user_exercise_state: &UserExerciseState,
+) -> ModelResult<Option<ExerciseSlideSubmission>> {
+    if peer_review_config.calibration_samples_to_review <= 0 {
+        return Ok(None);
+    }
+    let course_instance_id = reviewer_user_exercise_state.get_course_instance_id()?;
+    let calibration_samples_reviewed = peer_review_calibration_answers::count_reviewed_samples_by_user_and_exercise_and_course_instance(
+        conn,
+        reviewer_user_exercise_state.user_id,
+        reviewer_user_exercise_state.exercise_id,
+        course_instance_id,
+    )
+    .await?;
+    if calibration_samples_reviewed >= i64::from(peer_review_config.calibration_samples_to_review) {
+        return Ok(None);
+    }
+    let calibration_sample = peer_review_calibration_samples::try_to_get_random_unreviewed_by_user(
+        conn,
+        reviewer_user_exercise_state.exercise_id,
+        reviewer_user_exercise_state.user_id,
+        course_instance_id,
+    )
+    .await?;
+    match calibration_sample {
+        Some(calibration_sample) => Ok(Some(
+            exercise_slide_submissions::get_by_id(
+                conn,
+                calibration_sample.exercise_slide_submission_id,
+            )
+            .await?,
+        )),
+        // Not enough calibration samples have been marked, so the student moves on to real answers.
+        None => Ok(None),
+    }
+}
+
 async fn try_to_select_peer_review_candidate_from_queue(
     conn: &mut PgConnection,
     exercise_id: Uuid,
@@ -447,6 +590,7 @@ async fn try_to_select_peer_review_candidate_from_queue_impl(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn get_course_material_peer_review_data(
     conn: &mut PgConnection,
     peer_review_config: &PeerReviewConfig,
@@ -454,6 +598,7 @@ async fn get_course_material_peer_review_data(
     reviewer_user_id: Uuid,
     reviewer_course_instance_id: Uuid,
     exercise_id: Uuid,
+    is_calibration_sample: bool,
     fetch_service_info: impl Fn(Url) -> BoxFuture<'static, ModelResult<ExerciseServiceInfoApi>>,
 ) -> ModelResult<CourseMaterialPeerReviewData> {
     let peer_review_questions =
@@ -490,9 +635,552 @@ async fn get_course_material_peer_review_data(
         peer_review_config: peer_review_config.clone(),
         peer_review_questions,
         num_peer_reviews_given,
+        is_calibration_sample,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationFeedback {
+    pub question_feedback: Vec<PeerReviewCalibrationQuestionFeedback>,
+    #[cfg_attr(feature = "ts_rs", ts(type = "number"))]
+    pub calibration_samples_reviewed: i64,
+    pub calibration_samples_to_review: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationQuestionFeedback {
+    pub peer_review_question_id: Uuid,
+    pub given_number_data: Option<f32>,
+    pub reference_number_data: f32,
+    /// How far the given answer was from the reference answer. Positive if the student was more generous than the teacher. None if the question was left unanswered.
+    pub difference: Option<f32>,
+    pub explanation: Option<String>,
+}
+
+/// Saves the student's review of a calibration sample and compares it to the reference answers given by the teacher.
+///
+/// Calibration reviews are stored separately from real peer reviews so they don't count towards reviews given or received.
+pub async fn create_peer_review_calibration_submission_for_user(
+    conn: &mut PgConnection,
+    exercise: &Exercise,
+    giver_exercise_state: UserExerciseState,
+    peer_review_submission: CourseMaterialPeerReviewSubmission,
+) -> ModelResult<PeerReviewCalibrationFeedback> {
+    let peer_review_config = peer_review_configs::get_by_exercise_or_course_id(
+        conn,
+        exercise,
+        exercise.get_course_id()?,
+    )
+    .await?;
+    let course_instance_id = giver_exercise_state.get_course_instance_id()?;
+    let calibration_sample = peer_review_calibration_samples::get_by_exercise_slide_submission_id(
+        conn,
+        peer_review_submission.exercise_slide_submission_id,
+    )
+    .await
+    .optional()?
+    .filter(|sample| sample.exercise_id == exercise.id)
+    .ok_or_else(|| {
+        ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The submission is not a calibration sample for this exercise.".to_string(),
+            None,
+        )
+    })?;
+    if peer_review_calibration_answers::has_user_reviewed_sample(
+        conn,
+        giver_exercise_state.user_id,
+        course_instance_id,
+        calibration_sample.id,
+    )
+    .await?
+    {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "You have already reviewed this calibration sample.".to_string(),
+            None,
+        ));
+    }
+    let sanitized_answers = validate_and_sanitize_peer_review_submission_answers(
+        peer_review_questions::get_all_by_peer_review_config_id_as_map(conn, peer_review_config.id)
+            .await?,
+        peer_review_submission.peer_review_question_answers,
+    )?;
+    // The reviewed samples are counted from the saved answers, so a review without answers would be served again.
+    if sanitized_answers.is_empty() {
+        return Err(ModelError::new(
+            ModelErrorType::InvalidRequest,
+            "A calibration review needs to answer at least one question.".to_string(),
+            None,
+        ));
+    }
+
+    let mut tx = conn.begin().await?;
+    for answer in sanitized_answers.iter() {
+        peer_review_calibration_answers::insert(
+            &mut tx,
+            PKeyPolicy::Generate,
+            giver_exercise_state.user_id,
+            exercise.id,
+            course_instance_id,
+            calibration_sample.id,
+            answer.peer_review_question_id,
+            answer.text_data.clone(),
+            answer.number_data,
+        )
+        .await?;
+    }
+    let reference_answers =
+        peer_review_calibration_reference_answers::get_by_peer_review_calibration_sample_id(
+            &mut tx,
+            calibration_sample.id,
+        )
+        .await?;
+    let calibration_samples_reviewed = peer_review_calibration_answers::count_reviewed_samples_by_user_and_exercise_and_course_instance(
+        &mut tx,
+        giver_exercise_state.user_id,
+        exercise.id,
+        course_instance_id,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(PeerReviewCalibrationFeedback {
+        question_feedback: build_peer_review_calibration_question_feedback(
+            reference_answers,
+            &sanitized_answers,
+        ),
+        calibration_samples_reviewed,
+        calibration_samples_to_review: peer_review_config.calibration_samples_to_review,
+    })
+}
+
+/// Compares the given answers to the reference answers. Only questions with a reference answer get feedback.
+fn build_peer_review_calibration_question_feedback(
+    reference_answers: Vec<PeerReviewCalibrationReferenceAnswer>,
+    given_answers: &[CourseMaterialPeerReviewQuestionAnswer],
+) -> Vec<PeerReviewCalibrationQuestionFeedback> {
+    reference_answers
+        .into_iter()
+        .map(|reference_answer| {
+            let given_number_data = given_answers
+                .iter()
+                .find(|answer| {
+                    answer.peer_review_question_id == reference_answer.peer_review_question_id
+                })
+                .and_then(|answer| answer.number_data);
+            PeerReviewCalibrationQuestionFeedback {
+                peer_review_question_id: reference_answer.peer_review_question_id,
+                given_number_data,
+                reference_number_data: reference_answer.reference_number_data,
+                difference: given_number_data
+                    .map(|given| given - reference_answer.reference_number_data),
+                explanation: reference_answer.explanation,
+            }
+        })
+        .collect()
+}
+
+/// Marks the exercise slide submission as a calibration sample, replacing any previous reference answers.
+pub async fn upsert_peer_review_calibration_sample(
+    conn: &mut PgConnection,
+    exercise_slide_submission_id: Uuid,
+    reference_answers: Vec<NewPeerReviewCalibrationReferenceAnswer>,
+) -> ModelResult<PeerReviewCalibrationSampleWithReferenceAnswers> {
+    let exercise_slide_submission =
+        exercise_slide_submissions::get_by_id(conn, exercise_slide_submission_id).await?;
+    let exercise = crate::exercises::get_by_id(conn, exercise_slide_submission.exercise_id).await?;
+    if !exercise.needs_peer_review {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "Only submissions to peer reviewed exercises can be calibration samples.".to_string(),
+            None,
+        ));
+    }
+    let peer_review_config = peer_review_configs::get_by_exercise_or_course_id(
+        conn,
+        &exercise,
+        exercise.get_course_id()?,
+    )
+    .await?;
+    let peer_review_questions =
+        peer_review_questions::get_all_by_peer_review_config_id_as_map(conn, peer_review_config.id)
+            .await?;
+    for reference_answer in reference_answers.iter() {
+        match peer_review_questions.get(&reference_answer.peer_review_question_id) {
+            Some(question) if question.question_type == PeerReviewQuestionType::Scale => (),
+            _ => {
+                return Err(ModelError::new(
+                    ModelErrorType::InvalidRequest,
+                    "Reference answers can only be given to the scale questions of the exercise's peer review.".to_string(),
+                    None,
+                ))
+            }
+        }
+    }
+
+    let mut tx = conn.begin().await?;
+    let existing_sample = peer_review_calibration_samples::get_by_exercise_slide_submission_id(
+        &mut tx,
+        exercise_slide_submission_id,
+    )
+    .await
+    .optional()?;
+    let calibration_sample_id = match existing_sample {
+        Some(sample) => {
+            peer_review_calibration_reference_answers::delete_by_peer_review_calibration_sample_id(
+                &mut tx, sample.id,
+            )
+            .await?;
+            sample.id
+        }
+        None => {
+            peer_review_calibration_samples::insert(
+                &mut tx,
+                PKeyPolicy::Generate,
+                exercise_slide_submission_id,
+                exercise.id,
+            )
+            .await?
+        }
+    };
+    for reference_answer in reference_answers.iter() {
+        peer_review_calibration_reference_answers::insert(
+            &mut tx,
+            PKeyPolicy::Generate,
+            calibration_sample_id,
+            reference_answer,
+        )
+        .await?;
+    }
+    let calibration_sample =
+        peer_review_calibration_samples::get_by_id(&mut tx, calibration_sample_id).await?;
+    let reference_answers =
+        peer_review_calibration_reference_answers::get_by_peer_review_calibration_sample_id(
+            &mut tx,
+            calibration_sample_id,
+        )
+        .await?;
+    tx.commit().await?;
+
+    Ok(PeerReviewCalibrationSampleWithReferenceAnswers {
+        calibration_sample,
+        reference_answers,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationSampleInfo {
+    /// None if the submission has not been marked as a calibration sample.
+    pub calibration_sample: Option<PeerReviewCalibrationSampleWithReferenceAnswers>,
+    /// The scale questions of the exercise's peer review. These are the questions that can have reference answers.
+    pub peer_review_questions: Vec<PeerReviewQuestion>,
+}
+
+pub async fn get_peer_review_calibration_sample_info(
+    conn: &mut PgConnection,
+    exercise_slide_submission_id: Uuid,
+) -> ModelResult<PeerReviewCalibrationSampleInfo> {
+    let exercise_slide_submission =
+        exercise_slide_submissions::get_by_id(conn, exercise_slide_submission_id).await?;
+    let exercise = crate::exercises::get_by_id(conn, exercise_slide_submission.exercise_id).await?;
+    let peer_review_questions = if exercise.needs_peer_review {
+        let peer_review_config = peer_review_configs::get_by_exercise_or_course_id(
+            conn,
+            &exercise,
+            exercise.get_course_id()?,
+        )
+        .await?;
+        peer_review_questions::get_all_by_peer_review_config_id(conn, peer_review_config.id)
+            .await?
+            .into_iter()
+            .filter(|question| question.question_type == PeerReviewQuestionType::Scale)
+            .collect()
+    } else {
+        vec![]
+    };
+    let calibration_sample = peer_review_calibration_samples::get_by_exercise_slide_submission_id(
+        conn,
+        exercise_slide_submission_id,
+    )
+    .await
+    .optional()?;
+    let calibration_sample = match calibration_sample {
+        Some(calibration_sample) => {
+            let reference_answers =
+                peer_review_calibration_reference_answers::get_by_peer_review_calibration_sample_id(
+                    conn,
+                    calibration_sample.id,
+                )
+                .await?;
+            Some(PeerReviewCalibrationSampleWithReferenceAnswers {
+                calibration_sample,
+                reference_answers,
+            })
+        }
+        None => None,
+    };
+    Ok(PeerReviewCalibrationSampleInfo {
+        calibration_sample,
+        peer_review_questions,
     })
 }
 
+/// Stops using the exercise slide submission as a calibration sample. Reviews students have already given to it are kept.
+pub async fn remove_peer_review_calibration_sample(
+    conn: &mut PgConnection,
+    exercise_slide_submission_id: Uuid,
+) -> ModelResult<()> {
+    let mut tx = conn.begin().await?;
+    let calibration_sample = peer_review_calibration_samples::get_by_exercise_slide_submission_id(
+        &mut tx,
+        exercise_slide_submission_id,
+    )
+    .await?;
+    peer_review_calibration_reference_answers::delete_by_peer_review_calibration_sample_id(
+        &mut tx,
+        calibration_sample.id,
+    )
+    .await?;
+    peer_review_calibration_samples::delete(&mut tx, calibration_sample.id).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewPeerReviewSubmissionRating {
+    /// From 1 (not helpful at all) to 5 (very helpful).
+    pub helpfulness: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewPeerReviewSubmissionFlag {
+    pub reason: String,
+}
+
+/// Fetches the peer review and makes sure that it was given to the user's answer in the exercise.
+async fn get_peer_review_submission_received_by_user(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    peer_review_submission_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<PeerReviewSubmission> {
+    let peer_review_submission =
+        peer_review_submissions::get_by_id(conn, peer_review_submission_id).await?;
+    let reviewed_submission = exercise_slide_submissions::get_by_id(
+        conn,
+        peer_review_submission.exercise_slide_submission_id,
+    )
+    .await?;
+    if peer_review_submission.exercise_id != exercise_id || reviewed_submission.user_id != user_id {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "You can only give feedback on peer reviews you have received.".to_string(),
+            None,
+        ));
+    }
+    Ok(peer_review_submission)
+}
+
+/// Saves the reviewee's rating on how helpful a received peer review was. Rating the same review again replaces the previous rating.
+pub async fn rate_received_peer_review(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    peer_review_submission_id: Uuid,
+    user_id: Uuid,
+    rating: NewPeerReviewSubmissionRating,
+) -> ModelResult<PeerReviewSubmissionRating> {
+    if !(1..=5).contains(&rating.helpfulness) {
+        return Err(ModelError::new(
+            ModelErrorType::InvalidRequest,
+            "Helpfulness must be between 1 and 5.".to_string(),
+            None,
+        ));
+    }
+    let peer_review_submission = get_peer_review_submission_received_by_user(
+        conn,
+        exercise_id,
+        peer_review_submission_id,
+        user_id,
+    )
+    .await?;
+    let res = peer_review_submission_ratings::upsert(
+        conn,
+        peer_review_submission.id,
+        user_id,
+        rating.helpfulness,
+    )
+    .await?;
+    Ok(res)
+}
+
+/// Flags a received peer review as inappropriate. The review won't be counted until a teacher has resolved the flag.
+pub async fn flag_received_peer_review(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    peer_review_submission_id: Uuid,
+    user_id: Uuid,
+    flag: NewPeerReviewSubmissionFlag,
+) -> ModelResult<PeerReviewSubmissionFlag> {
+    let reason = flag.reason.trim();
+    if reason.is_empty() {
+        return Err(ModelError::new(
+            ModelErrorType::InvalidRequest,
+            "A reason is required for flagging a peer review.".to_string(),
+            None,
+        ));
+    }
+    let peer_review_submission = get_peer_review_submission_received_by_user(
+        conn,
+        exercise_id,
+        peer_review_submission_id,
+        user_id,
+    )
+    .await?;
+    let already_flagged =
+        peer_review_submission_flags::get_by_exercise_slide_submission_id_and_flagged_by(
+            conn,
+            peer_review_submission.exercise_slide_submission_id,
+            user_id,
+        )
+        .await?
+        .iter()
+        .any(|flag| flag.peer_review_submission_id == peer_review_submission.id);
+    if already_flagged {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "You have already flagged this peer review.".to_string(),
+            None,
+        ));
+    }
+    let flag_id = peer_review_submission_flags::insert(
+        conn,
+        PKeyPolicy::Generate,
+        peer_review_submission.id,
+        user_id,
+        reason,
+    )
+    .await?;
+    let res = peer_review_submission_flags::get_by_id(conn, flag_id).await?;
+    Ok(res)
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ResolvePeerReviewSubmissionFlag {
+    pub resolution: PeerReviewSubmissionFlagResolution,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct FlaggedPeerReview {
+    pub flag: PeerReviewSubmissionFlag,
+    pub reviewer_user_id: Uuid,
+    pub exercise_slide_submission_id: Uuid,
+    /// None if the flagged review has no answers.
+    pub peer_review: Option<PeerReviewWithQuestionsAndAnswers>,
+}
+
+/// Gets the flagged peer reviews of the exercise that are waiting for a teacher to resolve them.
+pub async fn get_unresolved_flagged_peer_reviews(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+) -> ModelResult<Vec<FlaggedPeerReview>> {
+    let flags =
+        peer_review_submission_flags::get_unresolved_by_exercise_id(conn, exercise_id).await?;
+    let mut res = Vec::with_capacity(flags.len());
+    for flag in flags {
+        let peer_review_submission =
+            peer_review_submissions::get_by_id(conn, flag.peer_review_submission_id).await?;
+        let peer_review =
+            peer_review_question_submissions::get_questions_and_answers_by_submission_id(
+                conn,
+                peer_review_submission.exercise_slide_submission_id,
+            )
+            .await?
+            .into_iter()
+            .find(|review| review.peer_review_submission_id == peer_review_submission.id);
+        res.push(FlaggedPeerReview {
+            flag,
+            reviewer_user_id: peer_review_submission.user_id,
+            exercise_slide_submission_id: peer_review_submission.exercise_slide_submission_id,
+            peer_review,
+        });
+    }
+    Ok(res)
+}
+
+/// Resolves a flag on a peer review. If the review is removed, it is deleted and will never be counted again. Otherwise it is counted again like any other review.
+///
+/// The status of the answer that received the review is updated right away so that the student doesn't have to wait for the next peer review queue update.
+pub async fn resolve_peer_review_submission_flag(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    flag_id: Uuid,
+    resolved_by: Uuid,
+    resolve: ResolvePeerReviewSubmissionFlag,
+) -> ModelResult<PeerReviewSubmissionFlag> {
+    let resolution = resolve.resolution;
+    let mut tx = conn.begin().await?;
+    let flag = peer_review_submission_flags::get_by_id(&mut tx, flag_id).await?;
+    let peer_review_submission =
+        peer_review_submissions::get_by_id(&mut tx, flag.peer_review_submission_id).await?;
+    if peer_review_submission.exercise_id != exercise_id {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The flag is not for a peer review of this exercise.".to_string(),
+            None,
+        ));
+    }
+    if flag.resolved_at.is_some() {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The flag has already been resolved.".to_string(),
+            None,
+        ));
+    }
+    let resolved_flag =
+        peer_review_submission_flags::resolve(&mut tx, flag.id, resolved_by, resolution).await?;
+    if resolution == PeerReviewSubmissionFlagResolution::ReviewRemoved {
+        peer_review_question_submissions::delete_by_peer_review_submission_id(
+            &mut tx,
+            peer_review_submission.id,
+        )
+        .await?;
+        peer_review_submissions::delete(&mut tx, peer_review_submission.id).await?;
+    }
+    let peer_review_queue_entry =
+        peer_review_queue_entries::try_to_get_by_receiving_submission_and_course_instance_ids(
+            &mut tx,
+            peer_review_submission.exercise_slide_submission_id,
+            peer_review_submission.course_instance_id,
+        )
+        .await?;
+    if let Some(peer_review_queue_entry) = peer_review_queue_entry {
+        let exercise = crate::exercises::get_by_id(&mut tx, exercise_id).await?;
+        let peer_review_config = peer_review_configs::get_by_exercise_or_course_id(
+            &mut tx,
+            &exercise,
+            exercise.get_course_id()?,
+        )
+        .await?;
+        update_peer_review_receiver_exercise_status(
+            &mut tx,
+            &exercise,
+            &peer_review_config,
+            peer_review_queue_entry,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(resolved_flag)
+}
+
 #[instrument(skip(conn))]
 pub async fn update_peer_review_queue_reviews_received(
     conn: &mut PgConnection,
@@ -542,7 +1230,10 @@ mod tests {
     mod validate_peer_review_submissions_answers {
         use chrono::TimeZone;
 
-        use crate::peer_review_questions::PeerReviewQuestionType;
+        use crate::peer_review_questions::{
+            PeerReviewQuestionType, PeerReviewRubric, PeerReviewRubricCriterion,
+            PeerReviewRubricLevel,
+        };
 
         use super::*;
 
@@ -592,6 +1283,124 @@ mod tests {
             )
         }
 
+        #[test]
+        fn accepts_rubric_answer_with_level_for_each_criterion() {
+            let (question_id, questions) = create_rubric_questions();
+            let answers = vec![create_rubric_answer(
+                question_id,
+                &[
+                    "10c1d4f6-a3a4-4ed9-8cee-0a55d2dbd8b3",
+                    "2cb9f3fd-0c1b-4f27-8bf8-7d7d1e2e8c55",
+                ],
+            )];
+            assert_eq!(
+                validate_and_sanitize_peer_review_submission_answers(questions, answers)
+                    .unwrap()
+                    .len(),
+                1
+            );
+        }
+
+        #[test]
+        fn errors_on_rubric_answer_with_two_levels_for_one_criterion() {
+            let (question_id, questions) = create_rubric_questions();
+            let answers = vec![create_rubric_answer(
+                question_id,
+                &[
+                    "10c1d4f6-a3a4-4ed9-8cee-0a55d2dbd8b3",
+                    "b6c5f1c4-4b8e-4bd4-9d55-8f31e5f1a1a0",
+                ],
+            )];
+            assert!(
+                validate_and_sanitize_peer_review_submission_answers(questions, answers).is_err()
+            );
+        }
+
+        #[test]
+        fn errors_on_rubric_answer_with_unknown_level() {
+            let (question_id, questions) = create_rubric_questions();
+            let answers = vec![create_rubric_answer(
+                question_id,
+                &[
+                    "10c1d4f6-a3a4-4ed9-8cee-0a55d2dbd8b3",
+                    "5f464818-1e68-4839-ae86-850b310f508c",
+                ],
+            )];
+            assert!(
+                validate_and_sanitize_peer_review_submission_answers(questions, answers).is_err()
+            );
+        }
+
+        #[test]
+        fn errors_on_required_rubric_answer_with_unanswered_criteria() {
+            let (question_id, questions) = create_rubric_questions();
+            let answers = vec![create_rubric_answer(
+                question_id,
+                &["10c1d4f6-a3a4-4ed9-8cee-0a55d2dbd8b3"],
+            )];
+            assert!(
+                validate_and_sanitize_peer_review_submission_answers(questions, answers).is_err()
+            );
+        }
+
+        fn create_rubric_questions() -> (Uuid, HashMap<Uuid, PeerReviewQuestion>) {
+            let peer_review_config_id =
+                Uuid::parse_str("5f464818-1e68-4839-ae86-850b310f508c").unwrap();
+            let question_id = Uuid::parse_str("68d5cda3-6ad8-464b-9af1-bd1692fcbee1").unwrap();
+            let mut question =
+                create_peer_review_question(question_id, peer_review_config_id, true).unwrap();
+            question.question_type = PeerReviewQuestionType::Rubric;
+            question.rubric = Some(PeerReviewRubric {
+                criteria: vec![
+                    PeerReviewRubricCriterion {
+                        id: Uuid::parse_str("0d0f5a4e-3d7f-4f0b-9b0e-7f1c3c0b5f11").unwrap(),
+                        name: "Correctness".to_string(),
+                        levels: vec![
+                            PeerReviewRubricLevel {
+                                id: Uuid::parse_str("10c1d4f6-a3a4-4ed9-8cee-0a55d2dbd8b3")
+                                    .unwrap(),
+                                description: "Correct".to_string(),
+                                points: 2.0,
+                            },
+                            PeerReviewRubricLevel {
+                                id: Uuid::parse_str("b6c5f1c4-4b8e-4bd4-9d55-8f31e5f1a1a0")
+                                    .unwrap(),
+                                description: "Incorrect".to_string(),
+                                points: 0.0,
+                            },
+                        ],
+                    },
+                    PeerReviewRubricCriterion {
+                        id: Uuid::parse_str("8f3e2b9a-6a1f-4c5e-9d3a-2b7c4e1f0a22").unwrap(),
+                        name: "Clarity".to_string(),
+                        levels: vec![PeerReviewRubricLevel {
+                            id: Uuid::parse_str("2cb9f3fd-0c1b-4f27-8bf8-7d7d1e2e8c55").unwrap(),
+                            description: "Clear".to_string(),
+                            points: 1.0,
+                        }],
+                    },
+                ],
+            });
+            (question_id, HashMap::from([(question_id, question)]))
+        }
+
+        fn create_rubric_answer(
+            peer_review_question_id: Uuid,
+            level_ids: &[&str],
+        ) -> CourseMaterialPeerReviewQuestionAnswer {
+            CourseMaterialPeerReviewQuestionAnswer {
+                peer_review_question_id,
+                text_data: None,
+                number_data: None,
+                rubric_level_ids: Some(
+                    level_ids
+                        .iter()
+                        .map(|id| Uuid::parse_str(id).unwrap())
+                        .collect(),
+                ),
+            }
+        }
+
         fn create_peer_review_question(
             id: Uuid,
             peer_review_config_id: Uuid,
@@ -607,6 +1416,7 @@ mod tests {
                 question: "".to_string(),
                 question_type: PeerReviewQuestionType::Essay,
                 answer_required,
+                rubric: None,
             })
         }
 
@@ -617,6 +1427,71 @@ mod tests {
                 peer_review_question_id,
                 text_data: Some("".to_string()),
                 number_data: None,
+                rubric_level_ids: None,
+            }
+        }
+    }
+
+    mod build_peer_review_calibration_question_feedback {
+        use chrono::TimeZone;
+
+        use super::*;
+
+        #[test]
+        fn compares_given_answers_to_reference_answers() {
+            let question_id = Uuid::parse_str("68d5cda3-6ad8-464b-9af1-bd1692fcbee1").unwrap();
+            let reference_answers = vec![create_reference_answer(question_id, 4.0)];
+            let given_answers = vec![create_given_answer(question_id, Some(2.0))];
+            let feedback =
+                build_peer_review_calibration_question_feedback(reference_answers, &given_answers);
+            assert_eq!(feedback.len(), 1);
+            assert_eq!(feedback[0].given_number_data, Some(2.0));
+            assert_eq!(feedback[0].reference_number_data, 4.0);
+            assert_eq!(feedback[0].difference, Some(-2.0));
+        }
+
+        #[test]
+        fn unanswered_questions_have_no_difference() {
+            let question_id = Uuid::parse_str("68d5cda3-6ad8-464b-9af1-bd1692fcbee1").unwrap();
+            let other_question_id =
+                Uuid::parse_str("5f464818-1e68-4839-ae86-850b310f508c").unwrap();
+            let reference_answers = vec![create_reference_answer(question_id, 4.0)];
+            let given_answers = vec![create_given_answer(other_question_id, Some(2.0))];
+            let feedback =
+                build_peer_review_calibration_question_feedback(reference_answers, &given_answers);
+            assert_eq!(feedback.len(), 1);
+            assert_eq!(feedback[0].given_number_data, None);
+            assert_eq!(feedback[0].difference, None);
+        }
+
+        fn create_reference_answer(
+            peer_review_question_id: Uuid,
+            reference_number_data: f32,
+        ) -> PeerReviewCalibrationReferenceAnswer {
+            PeerReviewCalibrationReferenceAnswer {
+                id: Uuid::parse_str("bf923ea4-a637-4d97-b78b-6f843d76120a").unwrap(),
+                created_at: Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap(),
+                updated_at: Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap(),
+                deleted_at: None,
+                peer_review_calibration_sample_id: Uuid::parse_str(
+                    "9b69dc5e-0eca-4fcd-8fd2-031a3a65da82",
+                )
+                .unwrap(),
+                peer_review_question_id,
+                reference_number_data,
+                explanation: None,
+            }
+        }
+
+        fn create_given_answer(
+            peer_review_question_id: Uuid,
+            number_data: Option<f32>,
+        ) -> CourseMaterialPeerReviewQuestionAnswer {
+            CourseMaterialPeerReviewQuestionAnswer {
+                peer_review_question_id,
+                text_data: None,
+                number_data,
+                rubric_level_ids: None,
             }
         }
     }
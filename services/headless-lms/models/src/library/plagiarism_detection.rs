@@ -0,0 +1,206 @@
+use std::collections::{HashMap, HashSet};
+
+use headless_lms_utils::text_similarity::{self, Fingerprint};
+
+use crate::{exercise_task_submission_similarities, exercise_task_submissions, prelude::*};
+
+/// Submission pairs at least this similar are stored for the teachers to review.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.5;
+
+/// Compares the latest textual submissions of the students in the exercise and course instance to each other and stores the pairs that are at least as similar as the threshold. Replaces the pairs stored previously for the exercise and course instance. Returns the number of stored pairs.
+pub async fn detect_similar_submissions(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+    similarity_threshold: f32,
+) -> ModelResult<usize> {
+    let submissions = exercise_task_submissions::get_users_latest_exercise_task_submissions_by_exercise_and_course_instance_ids(
+        conn,
+        exercise_id,
+        course_instance_id,
+    )
+    .await?;
+    let mut fingerprints_by_exercise_task_id: HashMap<Uuid, Vec<(Uuid, Fingerprint)>> =
+        HashMap::new();
+    for submission in submissions {
+        let text = submission
+            .data_json
+            .as_ref()
+            .map(text_similarity::extract_text_from_json)
+            .unwrap_or_default();
+        let fingerprint = text_similarity::fingerprint(&text);
+        if fingerprint.is_empty() {
+            continue;
+        }
+        fingerprints_by_exercise_task_id
+            .entry(submission.exercise_task_id)
+            .or_default()
+            .push((submission.id, fingerprint));
+    }
+
+    let mut tx = conn.begin().await?;
+    exercise_task_submission_similarities::delete_by_exercise_and_course_instance_ids(
+        &mut tx,
+        exercise_id,
+        course_instance_id,
+    )
+    .await?;
+    let mut stored = 0;
+    for (exercise_task_id, fingerprints) in fingerprints_by_exercise_task_id {
+        for (first, second, similarity) in find_similar_pairs(&fingerprints, similarity_threshold) {
+            exercise_task_submission_similarities::insert(
+                &mut tx,
+                exercise_id,
+                course_instance_id,
+                exercise_task_id,
+                (fingerprints[first].0, fingerprints[second].0),
+                similarity,
+            )
+            .await?;
+            stored += 1;
+        }
+    }
+    tx.commit().await?;
+    Ok(stored)
+}
+
+/// Returns the indices of the fingerprint pairs that are at least as similar as the threshold, along with their similarity. Only the pairs that share at least one hash are compared so that we don't have to compare every submission to every other submission.
+fn find_similar_pairs(
+    fingerprints: &[(Uuid, Fingerprint)],
+    similarity_threshold: f32,
+) -> Vec<(usize, usize, f32)> {
+    let mut indices_by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, (_, fingerprint)) in fingerprints.iter().enumerate() {
+        for hash in fingerprint {
+            indices_by_hash.entry(*hash).or_default().push(i);
+        }
+    }
+    let mut candidates = HashSet::new();
+    for indices in indices_by_hash.values() {
+        for (n, first) in indices.iter().enumerate() {
+            for second in &indices[n + 1..] {
+                candidates.insert((*first, *second));
+            }
+        }
+    }
+    let mut res = candidates
+        .into_iter()
+        .filter_map(|(first, second)| {
+            let similarity =
+                text_similarity::similarity(&fingerprints[first].1, &fingerprints[second].1);
+            (similarity >= similarity_threshold).then_some((first, second, similarity))
+        })
+        .collect::<Vec<_>>();
+    res.sort_by_key(|(first, second, _)| (*first, *second));
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        exercise_slide_submissions::{self, NewExerciseSlideSubmission},
+        exercise_task_gradings::UserPointsUpdateStrategy,
+        exercise_task_submission_similarities,
+        test_helper::*,
+        users,
+    };
+
+    #[tokio::test]
+    async fn stores_similar_submissions() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide, :task);
+        let other_user = users::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            "other@example.com",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let third_user = users::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            "third@example.com",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let copied =
+            "Photosynthesis converts light energy into chemical energy that is stored in glucose.";
+        let answers = [
+            (user, copied.to_string()),
+            (other_user, format!("{} Plants need it.", copied)),
+            (
+                third_user,
+                "Plants make their own food from sunlight, water and carbon dioxide.".to_string(),
+            ),
+        ];
+        for (user_id, answer) in answers {
+            let exercise_slide_submission =
+                exercise_slide_submissions::insert_exercise_slide_submission(
+                    tx.as_mut(),
+                    NewExerciseSlideSubmission {
+                        exercise_slide_id: slide,
+                        course_id: Some(course),
+                        course_instance_id: Some(instance.id),
+                        exam_id: None,
+                        user_id,
+                        exercise_id: exercise,
+                        user_points_update_strategy:
+                            UserPointsUpdateStrategy::CanAddPointsAndCanRemovePoints,
+                    },
+                )
+                .await
+                .unwrap();
+            exercise_task_submissions::insert(
+                tx.as_mut(),
+                PKeyPolicy::Generate,
+                exercise_slide_submission.id,
+                slide,
+                task,
+                serde_json::json!({ "answer": answer }),
+            )
+            .await
+            .unwrap();
+        }
+
+        let stored = detect_similar_submissions(
+            tx.as_mut(),
+            exercise,
+            instance.id,
+            DEFAULT_SIMILARITY_THRESHOLD,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored, 1);
+        let similarities =
+            exercise_task_submission_similarities::get_by_exercise_id(tx.as_mut(), exercise)
+                .await
+                .unwrap();
+        assert_eq!(similarities.len(), 1);
+        let users = [
+            similarities[0].first.user_id,
+            similarities[0].second.user_id,
+        ];
+        assert!(users.contains(&user));
+        assert!(users.contains(&other_user));
+
+        // Running the detection again replaces the previous results
+        let stored = detect_similar_submissions(
+            tx.as_mut(),
+            exercise,
+            instance.id,
+            DEFAULT_SIMILARITY_THRESHOLD,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored, 1);
+        let similarities =
+            exercise_task_submission_similarities::get_by_exercise_id(tx.as_mut(), exercise)
+                .await
+                .unwrap();
+        assert_eq!(similarities.len(), 1);
+    }
+}
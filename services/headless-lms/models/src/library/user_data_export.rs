@@ -0,0 +1,514 @@
+/*!
+Functions for collecting all the data the system holds about a user, for example for fulfilling a GDPR data access request.
+*/
+
+use crate::prelude::*;
+
+/// One file in a user data export. The data is the user's rows in one table, or in a few related tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserDataFile {
+    pub file_name: &'static str,
+    pub data: serde_json::Value,
+}
+
+/// Collects the data related to the user. Includes the data that has been marked as deleted, because it is still stored.
+///
+/// Ids of other users, such as peer reviewers and teachers, are left out because they are not the user's data.
+pub async fn get_user_data(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> ModelResult<Vec<UserDataFile>> {
+    let mut files = vec![];
+    let data = sqlx::query!(
+        r#"
+SELECT to_jsonb(t) AS "data!"
+FROM users t
+WHERE t.id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "user.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM user_identities t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "user_identities.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM course_instance_enrollments t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "course_instance_enrollments.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM exam_enrollments t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "exam_enrollments.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM user_course_settings t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "user_course_settings.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM course_background_question_answers t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "course_background_question_answers.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM exercise_slide_submissions t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "exercise_slide_submissions.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM exercise_task_submissions t
+  JOIN exercise_slide_submissions s ON s.id = t.exercise_slide_submission_id
+WHERE s.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "exercise_task_submissions.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM exercise_task_gradings t
+  JOIN exercise_task_submissions ts ON ts.id = t.exercise_task_submission_id
+  JOIN exercise_slide_submissions s ON s.id = ts.exercise_slide_submission_id
+WHERE s.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "exercise_task_gradings.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM user_exercise_states t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "user_exercise_states.json",
+        data,
+    });
+
+    // the id of the teacher who made the decision is personal data of someone else
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t) - 'user_id'
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM teacher_grading_decisions t
+  JOIN user_exercise_states ues ON ues.id = t.user_exercise_state_id
+WHERE ues.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "teacher_grading_decisions.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM peer_review_submissions t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "peer_reviews_given.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM peer_review_question_submissions t
+  JOIN peer_review_submissions prs ON prs.id = t.peer_review_submission_id
+WHERE prs.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "peer_review_answers_given.json",
+        data,
+    });
+
+    // the id of the reviewer is personal data of someone else
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t) - 'user_id'
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM peer_review_submissions t
+  JOIN exercise_slide_submissions s ON s.id = t.exercise_slide_submission_id
+WHERE s.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "peer_reviews_received.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM peer_review_question_submissions t
+  JOIN peer_review_submissions prs ON prs.id = t.peer_review_submission_id
+  JOIN exercise_slide_submissions s ON s.id = prs.exercise_slide_submission_id
+WHERE s.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "peer_review_answers_received.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM feedback t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "feedback.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM proposed_page_edits t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "proposed_page_edits.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM proposed_block_edits t
+  JOIN proposed_page_edits ppe ON ppe.id = t.proposal_id
+WHERE ppe.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "proposed_block_edits.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM course_module_completions t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "course_module_completions.json",
+        data,
+    });
+
+    let data = sqlx::query!(
+        r#"
+SELECT COALESCE(
+    jsonb_agg(
+      to_jsonb(t)
+      ORDER BY t.created_at
+    ),
+    '[]'::jsonb
+  ) AS "data!"
+FROM user_course_instance_exercise_service_variables t
+WHERE t.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .data;
+    files.push(UserDataFile {
+        file_name: "exercise_service_variables.json",
+        data,
+    });
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        exercise_slide_submissions::{self, NewExerciseSlideSubmission},
+        exercise_task_gradings::UserPointsUpdateStrategy,
+        test_helper::*,
+    };
+
+    #[tokio::test]
+    async fn collects_user_data() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        exercise_slide_submissions::insert_exercise_slide_submission(
+            tx.as_mut(),
+            NewExerciseSlideSubmission {
+                exercise_slide_id: slide,
+                course_id: Some(course),
+                course_instance_id: Some(instance.id),
+                exam_id: None,
+                exercise_id: exercise,
+                user_id: user,
+                user_points_update_strategy:
+                    UserPointsUpdateStrategy::CanAddPointsButCannotRemovePoints,
+            },
+        )
+        .await
+        .unwrap();
+
+        let files = get_user_data(tx.as_mut(), user).await.unwrap();
+        let file = |name: &str| {
+            files
+                .iter()
+                .find(|file| file.file_name == name)
+                .map(|file| file.data.clone())
+                .unwrap()
+        };
+        assert_eq!(file("user.json")["id"], json!(user));
+        let submissions = file("exercise_slide_submissions.json");
+        assert_eq!(submissions.as_array().unwrap().len(), 1);
+        assert_eq!(submissions[0]["exercise_id"], json!(exercise));
+        assert_eq!(file("feedback.json"), json!([]));
+    }
+}
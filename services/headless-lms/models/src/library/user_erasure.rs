@@ -0,0 +1,461 @@
+/*!
+Functions for erasing the personal data of a user, for example for fulfilling a GDPR erasure request.
+
+The user row itself is kept, because submissions, peer reviews, points and completions refer to it. After the erasure the row no longer contains anything that identifies the person, so the data that refers to it is pseudonymous and the course statistics stay intact. Completions keep their email, because registered completions have to be retained for the study registry.
+*/
+
+use crate::prelude::*;
+
+/// Every column that references `users`, and how the erasure handles it. When adding a new reference to `users`, decide how it should be erased and add it here.
+pub const USER_REFERENCES: &[(&str, &str, ErasureHandling)] = &[
+    ("api_tokens", "user_id", ErasureHandling::Deleted),
+    ("audit_log_entries", "actor_user_id", ErasureHandling::Kept),
+    (
+        "course_background_question_answers",
+        "user_id",
+        ErasureHandling::Deleted,
+    ),
+    (
+        "course_instance_enrollments",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    (
+        "course_module_completion_registered_to_study_registries",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    (
+        "course_module_completions",
+        "completion_granter_user_id",
+        ErasureHandling::Kept,
+    ),
+    (
+        "course_module_completions",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    ("email_deliveries", "user_id", ErasureHandling::Deleted),
+    ("exam_enrollments", "user_id", ErasureHandling::Kept),
+    (
+        "exercise_slide_submissions",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    ("feedback", "user_id", ErasureHandling::Detached),
+    ("lti_scores", "user_id", ErasureHandling::Kept),
+    ("lti_users", "user_id", ErasureHandling::Deleted),
+    ("page_edit_locks", "user_id", ErasureHandling::Deleted),
+    ("page_edit_presences", "user_id", ErasureHandling::Deleted),
+    ("page_drafts", "author_user_id", ErasureHandling::Detached),
+    ("page_history", "author_user_id", ErasureHandling::Kept),
+    (
+        "peer_review_calibration_answers",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    (
+        "peer_review_queue_entries",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    (
+        "peer_review_submission_flags",
+        "flagged_by",
+        ErasureHandling::Kept,
+    ),
+    (
+        "peer_review_submission_flags",
+        "resolved_by",
+        ErasureHandling::Kept,
+    ),
+    (
+        "peer_review_submission_ratings",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    ("peer_review_submissions", "user_id", ErasureHandling::Kept),
+    ("proposed_page_edits", "user_id", ErasureHandling::Detached),
+    ("regradings", "user_id", ErasureHandling::Kept),
+    ("role_requests", "decided_by_user_id", ErasureHandling::Kept),
+    ("role_requests", "user_id", ErasureHandling::Deleted),
+    ("roles", "user_id", ErasureHandling::Deleted),
+    (
+        "teacher_grading_decisions",
+        "user_id",
+        ErasureHandling::Kept,
+    ),
+    (
+        "user_course_instance_exercise_service_variables",
+        "user_id",
+        ErasureHandling::Deleted,
+    ),
+    ("user_course_settings", "user_id", ErasureHandling::Kept),
+    ("user_data_exports", "user_id", ErasureHandling::Deleted),
+    ("user_exercise_states", "user_id", ErasureHandling::Kept),
+    ("user_identities", "user_id", ErasureHandling::Deleted),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErasureHandling {
+    /// The rows are kept and refer to the pseudonymous user.
+    Kept,
+    /// The rows are kept, but the reference to the user is removed.
+    Detached,
+    /// The rows are deleted, or marked as deleted with their personal content cleared.
+    Deleted,
+}
+
+/// The email the erased user gets. Emails are required and unique, and the domain is reserved so that nothing can be delivered to it.
+pub fn erased_email(user_id: Uuid) -> String {
+    format!("erased-{}@erased.invalid", user_id)
+}
+
+/**
+Erases the personal data of the user. Returns false if the user had already been erased, in which case nothing is changed.
+
+Everything is done in one transaction, so a failed erasure can simply be retried.
+*/
+pub async fn erase_user(conn: &mut PgConnection, user_id: Uuid) -> ModelResult<bool> {
+    let mut tx = conn.begin().await?;
+    let email = erased_email(user_id);
+    let old_email = sqlx::query!(
+        "
+SELECT email
+FROM users
+WHERE id = $1
+FOR UPDATE
+        ",
+        user_id
+    )
+    .fetch_one(&mut tx)
+    .await?
+    .email;
+    if old_email == email {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        "
+UPDATE users
+SET email = $2,
+  first_name = NULL,
+  last_name = NULL,
+  upstream_id = NULL,
+  deleted_at = COALESCE(deleted_at, now())
+WHERE id = $1
+        ",
+        user_id,
+        email
+    )
+    .execute(&mut tx)
+    .await?;
+    // roles that had not been accepted yet are only identified by the email
+    sqlx::query!(
+        "
+DELETE FROM pending_roles
+WHERE LOWER(user_email) = LOWER($1)
+        ",
+        old_email
+    )
+    .execute(&mut tx)
+    .await?;
+
+    // the user can no longer log in or use the API
+    sqlx::query!(
+        "
+DELETE FROM user_identities
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    // the platforms can no longer log in as the user
+    sqlx::query!(
+        "
+DELETE FROM lti_users
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+UPDATE api_tokens
+SET deleted_at = now()
+WHERE user_id = $1
+  AND deleted_at IS NULL
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+UPDATE roles
+SET deleted_at = now()
+WHERE user_id = $1
+  AND deleted_at IS NULL
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+DELETE FROM role_requests
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+UPDATE email_deliveries
+SET deleted_at = now()
+WHERE user_id = $1
+  AND deleted_at IS NULL
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    // the exporter deletes the files of expired exports
+    sqlx::query!(
+        "
+UPDATE user_data_exports
+SET expires_at = now()
+WHERE user_id = $1
+  AND expires_at > now()
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+UPDATE user_data_exports
+SET deleted_at = now()
+WHERE user_id = $1
+  AND completed_at IS NULL
+  AND deleted_at IS NULL
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+
+    // answers about the user's background are personal and not needed for the course statistics
+    sqlx::query!(
+        "
+DELETE FROM course_background_question_answers
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+DELETE FROM user_course_instance_exercise_service_variables
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    // the locks and presences only tell who is editing a page right now
+    sqlx::query!(
+        "
+DELETE FROM page_edit_locks
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+DELETE FROM page_edit_presences
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+
+    // free-form texts written by the user are kept, but no longer linked to them
+    sqlx::query!(
+        "
+UPDATE feedback
+SET user_id = NULL
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+UPDATE proposed_page_edits
+SET user_id = NULL
+WHERE user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    // a draft without an author can only be published manually, so that the page history is attributed to the publisher
+    sqlx::query!(
+        "
+UPDATE page_drafts
+SET author_user_id = NULL,
+  publish_at = NULL
+WHERE author_user_id = $1
+        ",
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{
+        feedback::{self, NewFeedback},
+        lti_registrations::{self, NewLtiRegistration},
+        lti_users,
+        page_drafts::{self, PageDraftUpdate},
+        pages::{self, CmsPageUpdate},
+        test_helper::*,
+        users,
+    };
+
+    #[tokio::test]
+    async fn handles_all_references_to_users() {
+        insert_data!(:tx);
+        let references: HashSet<(String, String)> = sqlx::query!(
+            r#"
+SELECT cl.relname::text AS "table_name!",
+  a.attname::text AS "column_name!"
+FROM pg_constraint c
+  JOIN pg_class cl ON cl.oid = c.conrelid
+  JOIN pg_attribute a ON a.attrelid = c.conrelid
+  AND a.attnum = ANY(c.conkey)
+WHERE c.contype = 'f'
+  AND c.confrelid = 'users'::regclass
+            "#
+        )
+        .fetch_all(tx.as_mut())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.table_name, r.column_name))
+        .collect();
+        let handled: HashSet<(String, String)> = USER_REFERENCES
+            .iter()
+            .map(|(table, column, _)| (table.to_string(), column.to_string()))
+            .collect();
+        assert_eq!(references, handled);
+    }
+
+    #[tokio::test]
+    async fn erases_user() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+        let feedback_id = feedback::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            Some(user),
+            course,
+            NewFeedback {
+                feedback_given: "Great course!".to_string(),
+                selected_text: None,
+                related_blocks: vec![],
+                page_id: page,
+            },
+        )
+        .await
+        .unwrap();
+        let published = pages::get_page(tx.as_mut(), page).await.unwrap();
+        page_drafts::upsert(
+            tx.as_mut(),
+            page,
+            user,
+            &PageDraftUpdate {
+                content: CmsPageUpdate {
+                    content: serde_json::json!([]),
+                    exercises: vec![],
+                    exercise_slides: vec![],
+                    exercise_tasks: vec![],
+                    url_path: published.url_path,
+                    title: "Draft title".to_string(),
+                    chapter_id: published.chapter_id,
+                    base_history_id: None,
+                },
+                publish_at: Some(Utc::now() + chrono::Duration::days(1)),
+            },
+        )
+        .await
+        .unwrap();
+
+        let registration = lti_registrations::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &NewLtiRegistration {
+                name: "Partner Moodle".to_string(),
+                issuer: "https://moodle.example.com".to_string(),
+                client_id: "project-331".to_string(),
+                auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+                auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+                jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+                link_existing_users_by_email: false,
+            },
+        )
+        .await
+        .unwrap();
+        lti_users::insert(tx.as_mut(), registration.id, "moodle-user-1", user)
+            .await
+            .unwrap();
+
+        assert!(erase_user(tx.as_mut(), user).await.unwrap());
+        let erased = users::get_by_id(tx.as_mut(), user).await.unwrap();
+        assert_eq!(erased.email, erased_email(user));
+        assert_eq!(erased.first_name, None);
+        assert_eq!(erased.last_name, None);
+        assert!(erased.deleted_at.is_some());
+        let unread_feedback =
+            feedback::get_feedback_for_course(tx.as_mut(), course, false, Pagination::default())
+                .await
+                .unwrap();
+        let feedback = unread_feedback
+            .iter()
+            .find(|f| f.id == feedback_id)
+            .unwrap();
+        assert_eq!(feedback.user_id, None);
+        let draft = page_drafts::get_by_page_id(tx.as_mut(), page)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(draft.author_user_id, None);
+        assert_eq!(draft.publish_at, None);
+        assert_eq!(
+            lti_users::try_get_user_id_by_subject(tx.as_mut(), registration.id, "moodle-user-1")
+                .await
+                .unwrap(),
+            None
+        );
+
+        // erasing again does nothing
+        assert!(!erase_user(tx.as_mut(), user).await.unwrap());
+    }
+}
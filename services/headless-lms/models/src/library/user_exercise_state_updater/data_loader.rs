@@ -3,6 +3,7 @@ use crate::{
     exercises::Exercise,
     peer_review_configs::{self, PeerReviewConfig},
     peer_review_question_submissions::PeerReviewQuestionSubmission,
+    peer_review_questions::{self, PeerReviewQuestion},
     peer_review_queue_entries::PeerReviewQueueEntry,
     peer_review_submissions::{self, PeerReviewSubmission},
     prelude::*,
@@ -142,6 +143,7 @@ async fn load_peer_review_information(
             latest_exercise_slide_submission_received_peer_review_question_submissions,
             peer_review_queue_entry,
             peer_review_config,
+            peer_review_questions,
         } = if let Some(already_loaded_peer_review_information) =
             already_loaded_peer_review_information
         {
@@ -157,6 +159,9 @@ async fn load_peer_review_information(
         )
         .await?;
 
+        let loaded_peer_review_config =
+            load_peer_review_config(conn, peer_review_config, loaded_exercise).await?;
+
         Ok(Some(
             UserExerciseStateUpdateRequiredDataPeerReviewInformation {
                 given_peer_review_submissions: load_given_peer_review_submissions(
@@ -179,12 +184,13 @@ async fn load_peer_review_information(
                     loaded_user_exercise_state,
                 )
                 .await?,
-                peer_review_config: load_peer_review_config(
+                peer_review_questions: load_peer_review_questions(
                     conn,
-                    peer_review_config,
-                    loaded_exercise,
+                    peer_review_questions,
+                    &loaded_peer_review_config,
                 )
                 .await?,
+                peer_review_config: loaded_peer_review_config,
             },
         ))
     } else {
@@ -220,6 +226,24 @@ async fn load_peer_review_config(
     }
 }
 
+async fn load_peer_review_questions(
+    conn: &mut PgConnection,
+    already_loaded_peer_review_questions: Option<Vec<PeerReviewQuestion>>,
+    loaded_peer_review_config: &PeerReviewConfig,
+) -> ModelResult<Vec<PeerReviewQuestion>> {
+    if let Some(prq) = already_loaded_peer_review_questions {
+        info!("Using already loaded peer review questions");
+        Ok(prq)
+    } else {
+        info!("Loading peer review questions");
+        Ok(peer_review_questions::get_all_by_peer_review_config_id(
+            conn,
+            loaded_peer_review_config.id,
+        )
+        .await?)
+    }
+}
+
 async fn load_peer_review_queue_entry(
     conn: &mut PgConnection,
     already_loaded_peer_review_queue_entry: Option<Option<PeerReviewQueueEntry>>,
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use headless_lms_utils::numbers::f32_to_two_decimals;
 
 use crate::{
@@ -5,6 +7,7 @@ use crate::{
     library::user_exercise_state_updater::validation::validate_input,
     peer_review_configs::PeerReviewAcceptingStrategy,
     peer_review_question_submissions::PeerReviewQuestionSubmission,
+    peer_review_questions::{PeerReviewQuestion, PeerReviewQuestionType},
     prelude::*,
     user_exercise_states::{ReviewingStage, UserExerciseStateUpdate},
 };
@@ -269,6 +272,19 @@ fn get_peer_review_opinion(
                     reviewing_stage: ReviewingStage::WaitingForManualGrading,
                 })
             }
+            PeerReviewAcceptingStrategy::AutomaticallyGradeByRubricAverage => {
+                let share_of_max_points = calculate_average_received_rubric_share_of_max_points(
+                    &info.peer_review_questions,
+                    &info
+                        .latest_exercise_slide_submission_received_peer_review_question_submissions,
+                );
+                let score_given = share_of_max_points * score_maximum as f32;
+                info!(share_of_max_points = ?share_of_max_points, score_given = ?score_given, peer_review_accepting_strategy = ?info.peer_review_config.accepting_strategy, "Automatically giving points based on the received rubric totals");
+                Some(PeerReviewOpinion {
+                    score_given: Some(score_given),
+                    reviewing_stage: ReviewingStage::ReviewedAndLocked,
+                })
+            }
         }
     } else {
         // Even though the exercise needs peer review, the peer review has not been configured. The safest thing to do here is to consider peer review as not complete
@@ -296,6 +312,47 @@ fn calculate_average_received_peer_review_score(
     answers_considered.iter().sum::<f32>() / answers_considered.len() as f32
 }
 
+/// Sums up the rubric points each received peer review gave, averages the sums and returns the average as a share (0.0 - 1.0) of the maximum rubric points.
+fn calculate_average_received_rubric_share_of_max_points(
+    peer_review_questions: &[PeerReviewQuestion],
+    peer_review_question_submissions: &[PeerReviewQuestionSubmission],
+) -> f32 {
+    let rubric_questions = peer_review_questions
+        .iter()
+        .filter(|prq| prq.question_type == PeerReviewQuestionType::Rubric)
+        .filter_map(|prq| prq.rubric.as_ref().map(|rubric| (prq.id, rubric)))
+        .collect::<HashMap<_, _>>();
+    let max_points: f32 = rubric_questions
+        .values()
+        .map(|rubric| rubric.max_points())
+        .sum();
+    if max_points <= 0.0 {
+        warn!("The peer review has no rubric questions with points. Assuming score is 0.");
+        return 0.0;
+    }
+    let mut points_by_peer_review_submission: HashMap<Uuid, f32> = HashMap::new();
+    for prqs in peer_review_question_submissions {
+        if prqs.deleted_at.is_some() {
+            continue;
+        }
+        if let (Some(rubric), Some(rubric_level_ids)) = (
+            rubric_questions.get(&prqs.peer_review_question_id),
+            &prqs.rubric_level_ids,
+        ) {
+            *points_by_peer_review_submission
+                .entry(prqs.peer_review_submission_id)
+                .or_default() += rubric.points_for_selected_levels(rubric_level_ids);
+        }
+    }
+    if points_by_peer_review_submission.is_empty() {
+        warn!("No rubric answers for this answer. Assuming score is 0.");
+        return 0.0;
+    }
+    let average_points = points_by_peer_review_submission.values().sum::<f32>()
+        / points_by_peer_review_submission.len() as f32;
+    (average_points / max_points).clamp(0.0, 1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,7 +363,8 @@ mod tests {
         use crate::{
             exercises::Exercise,
             library::user_exercise_state_updater::UserExerciseStateUpdateRequiredDataPeerReviewInformation,
-            peer_review_configs::PeerReviewConfig, peer_review_queue_entries::PeerReviewQueueEntry,
+            peer_review_configs::{PeerReviewConfig, PeerReviewTimeoutAction},
+            peer_review_queue_entries::PeerReviewQueueEntry,
             peer_review_submissions::PeerReviewSubmission,
             user_exercise_slide_states::UserExerciseSlideStateGradingSummary,
             user_exercise_states::UserExerciseState,
@@ -363,7 +421,8 @@ mod tests {
                     peer_review_information: Some(
                         UserExerciseStateUpdateRequiredDataPeerReviewInformation {
                             given_peer_review_submissions: Vec::new(), latest_exercise_slide_submission_received_peer_review_question_submissions: Vec::new(), peer_review_queue_entry: None,
-                            peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrRejectByAverage)
+                            peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrRejectByAverage),
+                            peer_review_questions: Vec::new(),
                         },
                     ),
                     latest_teacher_grading_decision: None,
@@ -404,7 +463,8 @@ mod tests {
                                 given_peer_review_submissions: vec![create_peer_review_submission(), create_peer_review_submission(), create_peer_review_submission()],
                                 latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(4.0), create_peer_review_question_submission(3.0), create_peer_review_question_submission(4.0)],
                                 peer_review_queue_entry: Some(create_peer_review_queue_entry()),
-                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrRejectByAverage)
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrRejectByAverage),
+                                peer_review_questions: Vec::new(),
                             },
                         ),
                         latest_teacher_grading_decision: None,
@@ -444,7 +504,8 @@ mod tests {
                                 // Average below 2.1
                                 latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(3.0), create_peer_review_question_submission(1.0), create_peer_review_question_submission(1.0)],
                                 peer_review_queue_entry: Some(create_peer_review_queue_entry()),
-                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrRejectByAverage)
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrRejectByAverage),
+                                peer_review_questions: Vec::new(),
                             },
                         ),
                         latest_teacher_grading_decision: None,
@@ -488,7 +549,8 @@ mod tests {
                                 given_peer_review_submissions: vec![create_peer_review_submission(), create_peer_review_submission(), create_peer_review_submission()],
                                 latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(4.0), create_peer_review_question_submission(3.0), create_peer_review_question_submission(4.0)],
                                 peer_review_queue_entry: Some(create_peer_review_queue_entry()),
-                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrManualReviewByAverage)
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrManualReviewByAverage),
+                                peer_review_questions: Vec::new(),
                             },
                         ),
                         latest_teacher_grading_decision: None,
@@ -529,7 +591,8 @@ mod tests {
                                 // Average below 2.1
                                 latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(3.0), create_peer_review_question_submission(1.0), create_peer_review_question_submission(1.0)],
                                 peer_review_queue_entry: Some(create_peer_review_queue_entry()),
-                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrManualReviewByAverage)
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyAcceptOrManualReviewByAverage),
+                                peer_review_questions: Vec::new(),
                             },
                         ),
                         latest_teacher_grading_decision: None,
@@ -573,7 +636,8 @@ mod tests {
                                 given_peer_review_submissions: vec![create_peer_review_submission(), create_peer_review_submission(), create_peer_review_submission()],
                                 latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(4.0), create_peer_review_question_submission(3.0), create_peer_review_question_submission(4.0)],
                                 peer_review_queue_entry: Some(create_peer_review_queue_entry()),
-                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::ManualReviewEverything)
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::ManualReviewEverything),
+                                peer_review_questions: Vec::new(),
                             },
                         ),
                         latest_teacher_grading_decision: None,
@@ -614,7 +678,8 @@ mod tests {
                                 // Average below 2.1
                                 latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(3.0), create_peer_review_question_submission(1.0), create_peer_review_question_submission(1.0)],
                                 peer_review_queue_entry: Some(create_peer_review_queue_entry()),
-                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::ManualReviewEverything)
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::ManualReviewEverything),
+                                peer_review_questions: Vec::new(),
                             },
                         ),
                         latest_teacher_grading_decision: None,
@@ -635,6 +700,173 @@ mod tests {
             }
         }
 
+        mod automatically_grade_by_rubric_average {
+            use crate::peer_review_questions::{
+                PeerReviewRubric, PeerReviewRubricCriterion, PeerReviewRubricLevel,
+            };
+
+            use super::*;
+
+            const CORRECT_LEVEL_ID: &str = "10c1d4f6-a3a4-4ed9-8cee-0a55d2dbd8b3";
+            const INCORRECT_LEVEL_ID: &str = "b6c5f1c4-4b8e-4bd4-9d55-8f31e5f1a1a0";
+            const CLEAR_LEVEL_ID: &str = "2cb9f3fd-0c1b-4f27-8bf8-7d7d1e2e8c55";
+
+            #[test]
+            fn gives_average_of_rubric_totals_scaled_to_score_maximum() {
+                let id = Uuid::parse_str("5f464818-1e68-4839-ae86-850b310f508c").unwrap();
+                let exercise = create_exercise(CourseOrExamId::Course(id), true, true);
+                let user_exercise_state = create_user_exercise_state(
+                    &exercise,
+                    None,
+                    ActivityProgress::Initialized,
+                    ReviewingStage::NotStarted,
+                );
+                let new_user_exercise_state =
+                    derive_new_user_exercise_state(UserExerciseStateUpdateRequiredData {
+                        exercise,
+                        current_user_exercise_state: user_exercise_state,
+                        peer_review_information: Some(
+                            UserExerciseStateUpdateRequiredDataPeerReviewInformation {
+                                given_peer_review_submissions: vec![create_peer_review_submission(), create_peer_review_submission(), create_peer_review_submission()],
+                                // Totals 3 and 1 out of 3, average is 2 out of 3
+                                latest_exercise_slide_submission_received_peer_review_question_submissions: vec![
+                                    create_rubric_question_submission("be4061b5-b468-4f50-93b0-cf3bf9de9a13", &[CORRECT_LEVEL_ID, CLEAR_LEVEL_ID]),
+                                    create_rubric_question_submission("4d2c6bd9-9b5e-4a39-8c1b-2f2f4a6e6c1d", &[INCORRECT_LEVEL_ID, CLEAR_LEVEL_ID]),
+                                    // Likert answers don't affect the rubric grading
+                                    create_peer_review_question_submission(1.0),
+                                ],
+                                peer_review_queue_entry: Some(create_peer_review_queue_entry()),
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyGradeByRubricAverage),
+                                peer_review_questions: vec![create_rubric_question()],
+                            },
+                        ),
+                        latest_teacher_grading_decision: None,
+                        user_exercise_slide_state_grading_summary:
+                            UserExerciseSlideStateGradingSummary {
+                                score_given: Some(1.0),
+                                grading_progress: GradingProgress::FullyGraded,
+                            },
+                    })
+                    .unwrap();
+                assert_results(
+                    &new_user_exercise_state,
+                    Some(6000.0),
+                    ActivityProgress::Completed,
+                    ReviewingStage::ReviewedAndLocked,
+                );
+            }
+
+            #[test]
+            fn gives_zero_points_without_rubric_answers() {
+                let id = Uuid::parse_str("5f464818-1e68-4839-ae86-850b310f508c").unwrap();
+                let exercise = create_exercise(CourseOrExamId::Course(id), true, true);
+                let user_exercise_state = create_user_exercise_state(
+                    &exercise,
+                    None,
+                    ActivityProgress::Initialized,
+                    ReviewingStage::NotStarted,
+                );
+                let new_user_exercise_state =
+                    derive_new_user_exercise_state(UserExerciseStateUpdateRequiredData {
+                        exercise,
+                        current_user_exercise_state: user_exercise_state,
+                        peer_review_information: Some(
+                            UserExerciseStateUpdateRequiredDataPeerReviewInformation {
+                                given_peer_review_submissions: vec![create_peer_review_submission(), create_peer_review_submission(), create_peer_review_submission()],
+                                latest_exercise_slide_submission_received_peer_review_question_submissions: vec![create_peer_review_question_submission(5.0)],
+                                peer_review_queue_entry: Some(create_peer_review_queue_entry()),
+                                peer_review_config: create_peer_review_config(PeerReviewAcceptingStrategy::AutomaticallyGradeByRubricAverage),
+                                peer_review_questions: vec![create_rubric_question()],
+                            },
+                        ),
+                        latest_teacher_grading_decision: None,
+                        user_exercise_slide_state_grading_summary:
+                            UserExerciseSlideStateGradingSummary {
+                                score_given: Some(1.0),
+                                grading_progress: GradingProgress::FullyGraded,
+                            },
+                    })
+                    .unwrap();
+                assert_results(
+                    &new_user_exercise_state,
+                    Some(0.0),
+                    ActivityProgress::Completed,
+                    ReviewingStage::ReviewedAndLocked,
+                );
+            }
+
+            fn create_rubric_question() -> PeerReviewQuestion {
+                let id = Uuid::parse_str("5f464818-1e68-4839-ae86-850b310f508c").unwrap();
+                PeerReviewQuestion {
+                    id: Uuid::parse_str("8e0e2f0a-3c6b-4a8e-a7a5-1b1c3f2d9e77").unwrap(),
+                    created_at: Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap(),
+                    updated_at: Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap(),
+                    deleted_at: None,
+                    peer_review_config_id: id,
+                    order_number: 0,
+                    question: "".to_string(),
+                    question_type: PeerReviewQuestionType::Rubric,
+                    answer_required: true,
+                    rubric: Some(PeerReviewRubric {
+                        criteria: vec![
+                            PeerReviewRubricCriterion {
+                                id: Uuid::parse_str("0d0f5a4e-3d7f-4f0b-9b0e-7f1c3c0b5f11")
+                                    .unwrap(),
+                                name: "Correctness".to_string(),
+                                levels: vec![
+                                    PeerReviewRubricLevel {
+                                        id: Uuid::parse_str(CORRECT_LEVEL_ID).unwrap(),
+                                        description: "Correct".to_string(),
+                                        points: 2.0,
+                                    },
+                                    PeerReviewRubricLevel {
+                                        id: Uuid::parse_str(INCORRECT_LEVEL_ID).unwrap(),
+                                        description: "Incorrect".to_string(),
+                                        points: 0.0,
+                                    },
+                                ],
+                            },
+                            PeerReviewRubricCriterion {
+                                id: Uuid::parse_str("8f3e2b9a-6a1f-4c5e-9d3a-2b7c4e1f0a22")
+                                    .unwrap(),
+                                name: "Clarity".to_string(),
+                                levels: vec![PeerReviewRubricLevel {
+                                    id: Uuid::parse_str(CLEAR_LEVEL_ID).unwrap(),
+                                    description: "Clear".to_string(),
+                                    points: 1.0,
+                                }],
+                            },
+                        ],
+                    }),
+                }
+            }
+
+            fn create_rubric_question_submission(
+                peer_review_submission_id: &str,
+                level_ids: &[&str],
+            ) -> PeerReviewQuestionSubmission {
+                PeerReviewQuestionSubmission {
+                    id: Uuid::parse_str("bf923ea4-a637-4d97-b78b-6f843d76120a").unwrap(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    deleted_at: None,
+                    peer_review_question_id: Uuid::parse_str(
+                        "8e0e2f0a-3c6b-4a8e-a7a5-1b1c3f2d9e77",
+                    )
+                    .unwrap(),
+                    peer_review_submission_id: Uuid::parse_str(peer_review_submission_id).unwrap(),
+                    text_data: None,
+                    number_data: None,
+                    rubric_level_ids: Some(
+                        level_ids
+                            .iter()
+                            .map(|id| Uuid::parse_str(id).unwrap())
+                            .collect(),
+                    ),
+                }
+            }
+        }
+
         fn assert_results(
             update: &UserExerciseStateUpdate,
             score_given: Option<f32>,
@@ -689,6 +921,10 @@ mod tests {
                 peer_reviews_to_receive: 2,
                 accepting_threshold: 2.1,
                 accepting_strategy,
+                calibration_samples_to_review: 0,
+                timeout_in_days: 21,
+                timeout_action: PeerReviewTimeoutAction::MoveToManualReview,
+                manual_review_timeout_in_days: Some(90),
             }
         }
 
@@ -706,6 +942,7 @@ mod tests {
                     .unwrap(),
                 text_data: None,
                 number_data: Some(number_data),
+                rubric_level_ids: None,
             }
         }
 
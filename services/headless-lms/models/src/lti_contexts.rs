@@ -0,0 +1,204 @@
+use crate::{
+    course_instances::{self, NewCourseInstance},
+    prelude::*,
+};
+
+/// Maps a context (usually a course) of an LTI platform to a course instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LtiContext {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub lti_registration_id: Uuid,
+    pub deployment_id: String,
+    pub context_id: String,
+    pub title: Option<String>,
+    pub course_id: Uuid,
+    pub course_instance_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewLtiContext<'a> {
+    pub lti_registration_id: Uuid,
+    pub deployment_id: &'a str,
+    pub context_id: &'a str,
+    pub title: Option<&'a str>,
+    pub course_id: Uuid,
+}
+
+/**
+Returns the context of the course launched from the platform context, creating a new course instance for it on the first launch.
+
+The new course instance is named after the platform context and it has the same teacher in charge as the default instance of the course.
+*/
+pub async fn get_or_insert(
+    conn: &mut PgConnection,
+    new_context: &NewLtiContext<'_>,
+) -> ModelResult<LtiContext> {
+    let mut tx = conn.begin().await?;
+    if let Some(mut context) = try_get(&mut tx, new_context).await? {
+        if context.title.as_deref() != new_context.title {
+            update_title(&mut tx, context.id, new_context.title).await?;
+            context.title = new_context.title.map(str::to_string);
+        }
+        tx.commit().await?;
+        return Ok(context);
+    }
+
+    let default_instance =
+        course_instances::get_default_by_course_id(&mut tx, new_context.course_id).await?;
+    let name: String = new_context
+        .title
+        .unwrap_or(new_context.context_id)
+        .chars()
+        .take(255)
+        .collect();
+    let course_instance = course_instances::insert(
+        &mut tx,
+        PKeyPolicy::Generate,
+        NewCourseInstance {
+            course_id: new_context.course_id,
+            name: Some(&name),
+            description: None,
+            teacher_in_charge_name: &default_instance.teacher_in_charge_name,
+            teacher_in_charge_email: &default_instance.teacher_in_charge_email,
+            support_email: default_instance.support_email.as_deref(),
+            opening_time: None,
+            closing_time: None,
+        },
+    )
+    .await?;
+    let context = sqlx::query_as!(
+        LtiContext,
+        "
+INSERT INTO lti_contexts (
+    lti_registration_id,
+    deployment_id,
+    context_id,
+    title,
+    course_id,
+    course_instance_id
+  )
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING *
+        ",
+        new_context.lti_registration_id,
+        new_context.deployment_id,
+        new_context.context_id,
+        new_context.title,
+        new_context.course_id,
+        course_instance.id,
+    )
+    .fetch_one(&mut tx)
+    .await?;
+    tx.commit().await?;
+    Ok(context)
+}
+
+async fn try_get(
+    conn: &mut PgConnection,
+    context: &NewLtiContext<'_>,
+) -> ModelResult<Option<LtiContext>> {
+    let res = sqlx::query_as!(
+        LtiContext,
+        "
+SELECT *
+FROM lti_contexts
+WHERE lti_registration_id = $1
+  AND deployment_id = $2
+  AND context_id = $3
+  AND course_id = $4
+  AND deleted_at IS NULL
+        ",
+        context.lti_registration_id,
+        context.deployment_id,
+        context.context_id,
+        context.course_id,
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res)
+}
+
+async fn update_title(conn: &mut PgConnection, id: Uuid, title: Option<&str>) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE lti_contexts
+SET title = $2
+WHERE id = $1
+        ",
+        id,
+        title
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        lti_registrations::{self, NewLtiRegistration},
+        test_helper::*,
+    };
+
+    #[tokio::test]
+    async fn creates_course_instance_for_each_context() {
+        insert_data!(:tx, user: _user, :org, :course);
+        let registration = lti_registrations::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &NewLtiRegistration {
+                name: "Partner Moodle".to_string(),
+                issuer: "https://moodle.example.com".to_string(),
+                client_id: "client".to_string(),
+                auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+                auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+                jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+                link_existing_users_by_email: false,
+            },
+        )
+        .await
+        .unwrap();
+        let new_context = |context_id| NewLtiContext {
+            lti_registration_id: registration.id,
+            deployment_id: "1",
+            context_id,
+            title: Some("Introduction to programming, spring"),
+            course_id: course,
+        };
+
+        let first = get_or_insert(tx.as_mut(), &new_context("course-1"))
+            .await
+            .unwrap();
+        let default_instance = course_instances::get_default_by_course_id(tx.as_mut(), course)
+            .await
+            .unwrap();
+        assert_ne!(first.course_instance_id, default_instance.id);
+        let course_instance =
+            course_instances::get_course_instance(tx.as_mut(), first.course_instance_id)
+                .await
+                .unwrap();
+        assert_eq!(
+            course_instance.name.as_deref(),
+            Some("Introduction to programming, spring")
+        );
+        assert_eq!(
+            course_instance.teacher_in_charge_email,
+            default_instance.teacher_in_charge_email
+        );
+
+        let again = get_or_insert(tx.as_mut(), &new_context("course-1"))
+            .await
+            .unwrap();
+        assert_eq!(again.course_instance_id, first.course_instance_id);
+
+        let other = get_or_insert(tx.as_mut(), &new_context("course-2"))
+            .await
+            .unwrap();
+        assert_ne!(other.course_instance_id, first.course_instance_id);
+    }
+}
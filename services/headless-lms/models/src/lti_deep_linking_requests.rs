@@ -0,0 +1,83 @@
+use crate::prelude::*;
+
+/// A request from an LTI platform to select the content a new link launches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LtiDeepLinkingRequest {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub lti_registration_id: Uuid,
+    pub deployment_id: String,
+    pub deep_link_return_url: String,
+    pub data: Option<String>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    lti_registration_id: Uuid,
+    deployment_id: &str,
+    deep_link_return_url: &str,
+    data: Option<&str>,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO lti_deep_linking_requests (
+    lti_registration_id,
+    deployment_id,
+    deep_link_return_url,
+    data
+  )
+VALUES ($1, $2, $3, $4)
+RETURNING id
+        ",
+        lti_registration_id,
+        deployment_id,
+        deep_link_return_url,
+        data
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+/// Returns the request if it has not been responded to yet and it was received less than an hour ago.
+pub async fn get_open_by_id(
+    conn: &mut PgConnection,
+    id: Uuid,
+) -> ModelResult<LtiDeepLinkingRequest> {
+    let res = sqlx::query_as!(
+        LtiDeepLinkingRequest,
+        "
+SELECT *
+FROM lti_deep_linking_requests
+WHERE id = $1
+  AND responded_at IS NULL
+  AND created_at > now() - INTERVAL '1 hour'
+  AND deleted_at IS NULL
+        ",
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Marks the request as responded to. Fails if the request has already been responded to, so that only one response is created for each request.
+pub async fn mark_responded(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE lti_deep_linking_requests
+SET responded_at = now()
+WHERE id = $1
+  AND responded_at IS NULL
+  AND deleted_at IS NULL
+RETURNING id
+        ",
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use chrono::Duration;
+
+use crate::prelude::*;
+
+/// How long the platform has to send the launch message after the login has been initiated.
+const LOGIN_STATE_VALID_MINUTES: i32 = 10;
+
+/// An initiated LTI launch, waiting for the launch message from the platform.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LtiLoginState {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub lti_registration_id: Uuid,
+    pub state: String,
+    pub nonce: String,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    lti_registration_id: Uuid,
+    state: &str,
+    nonce: &str,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO lti_login_states (lti_registration_id, state, nonce)
+VALUES ($1, $2, $3)
+RETURNING id
+        ",
+        lti_registration_id,
+        state,
+        nonce
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+/// Removes and returns the login state, so that each state can only be used once. Returns None if the state does not exist or has expired.
+pub async fn take(conn: &mut PgConnection, state: &str) -> ModelResult<Option<LtiLoginState>> {
+    let res = sqlx::query_as!(
+        LtiLoginState,
+        "
+DELETE FROM lti_login_states
+WHERE state = $1
+RETURNING *
+        ",
+        state
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res.filter(|login_state| {
+        login_state.created_at + Duration::minutes(LOGIN_STATE_VALID_MINUTES.into()) > Utc::now()
+    }))
+}
+
+/// Deletes the states of launches that were never finished.
+pub async fn delete_expired(conn: &mut PgConnection) -> ModelResult<u64> {
+    let res = sqlx::query!(
+        "
+DELETE FROM lti_login_states
+WHERE created_at < now() - make_interval(mins => $1)
+        ",
+        LOGIN_STATE_VALID_MINUTES
+    )
+    .execute(conn)
+    .await?;
+    Ok(res.rows_affected())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        lti_registrations::{self, NewLtiRegistration},
+        test_helper::*,
+    };
+
+    #[tokio::test]
+    async fn states_can_only_be_taken_once() {
+        insert_data!(:tx, user: _user, :org);
+        let registration = lti_registrations::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &NewLtiRegistration {
+                name: "Partner Moodle".to_string(),
+                issuer: "https://moodle.example.com".to_string(),
+                client_id: "client".to_string(),
+                auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+                auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+                jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+                link_existing_users_by_email: false,
+            },
+        )
+        .await
+        .unwrap();
+        insert(tx.as_mut(), registration.id, "state", "nonce")
+            .await
+            .unwrap();
+        let login_state = take(tx.as_mut(), "state").await.unwrap().unwrap();
+        assert_eq!(login_state.nonce, "nonce");
+        assert!(take(tx.as_mut(), "state").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::prelude::*;
+
+/// An LTI 1.3 platform, such as Moodle or Canvas, that can launch the courses of an organization.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct LtiRegistration {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub auth_login_url: String,
+    pub auth_token_url: String,
+    pub jwks_url: String,
+    /// If true, platform users are linked to existing users with the same email if the users have only been active in the organization. Only global admins can set this.
+    pub link_existing_users_by_email: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewLtiRegistration {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub auth_login_url: String,
+    pub auth_token_url: String,
+    pub jwks_url: String,
+    pub link_existing_users_by_email: bool,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    pkey_policy: PKeyPolicy<Uuid>,
+    organization_id: Uuid,
+    new_registration: &NewLtiRegistration,
+) -> ModelResult<LtiRegistration> {
+    validate(new_registration)?;
+    let res = sqlx::query_as!(
+        LtiRegistration,
+        "
+INSERT INTO lti_registrations (
+    id,
+    organization_id,
+    name,
+    issuer,
+    client_id,
+    auth_login_url,
+    auth_token_url,
+    jwks_url,
+    link_existing_users_by_email
+  )
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+RETURNING id,
+  created_at,
+  updated_at,
+  organization_id,
+  name,
+  issuer,
+  client_id,
+  auth_login_url,
+  auth_token_url,
+  jwks_url,
+  link_existing_users_by_email
+        ",
+        pkey_policy.into_uuid(),
+        organization_id,
+        new_registration.name,
+        new_registration.issuer,
+        new_registration.client_id,
+        new_registration.auth_login_url,
+        new_registration.auth_token_url,
+        new_registration.jwks_url,
+        new_registration.link_existing_users_by_email,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<LtiRegistration> {
+    let res = sqlx::query_as!(
+        LtiRegistration,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  name,
+  issuer,
+  client_id,
+  auth_login_url,
+  auth_token_url,
+  jwks_url,
+  link_existing_users_by_email
+FROM lti_registrations
+WHERE id = $1
+  AND deleted_at IS NULL
+        ",
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Finds the registration of the platform that initiates a launch. Platforms are not required to send the client id when initiating a launch, so it can only be left out if the issuer has only one registration.
+pub async fn get_by_issuer_and_client_id(
+    conn: &mut PgConnection,
+    issuer: &str,
+    client_id: Option<&str>,
+) -> ModelResult<LtiRegistration> {
+    let mut res = sqlx::query_as!(
+        LtiRegistration,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  name,
+  issuer,
+  client_id,
+  auth_login_url,
+  auth_token_url,
+  jwks_url,
+  link_existing_users_by_email
+FROM lti_registrations
+WHERE issuer = $1
+  AND (
+    $2::VARCHAR IS NULL
+    OR client_id = $2
+  )
+  AND deleted_at IS NULL
+        ",
+        issuer,
+        client_id
+    )
+    .fetch_all(conn)
+    .await?;
+    match res.len() {
+        1 => Ok(res.remove(0)),
+        0 => Err(ModelError::new(
+            ModelErrorType::NotFound,
+            "The platform has not been registered.".to_string(),
+            None,
+        )),
+        _ => Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The platform has been registered multiple times, so the client id is required."
+                .to_string(),
+            None,
+        )),
+    }
+}
+
+pub async fn get_by_organization_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+) -> ModelResult<Vec<LtiRegistration>> {
+    let res = sqlx::query_as!(
+        LtiRegistration,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  name,
+  issuer,
+  client_id,
+  auth_login_url,
+  auth_token_url,
+  jwks_url,
+  link_existing_users_by_email
+FROM lti_registrations
+WHERE organization_id = $1
+  AND deleted_at IS NULL
+ORDER BY name
+        ",
+        organization_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete(conn: &mut PgConnection, organization_id: Uuid, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE lti_registrations
+SET deleted_at = now()
+WHERE id = $1
+  AND organization_id = $2
+  AND deleted_at IS NULL
+RETURNING id
+        ",
+        id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+fn validate(registration: &NewLtiRegistration) -> ModelResult<()> {
+    let urls = [
+        &registration.issuer,
+        &registration.auth_login_url,
+        &registration.auth_token_url,
+        &registration.jwks_url,
+    ];
+    for url in urls {
+        let valid_url = url::Url::parse(url)
+            .map(|url| url.scheme() == "http" || url.scheme() == "https")
+            .unwrap_or(false);
+        if !valid_url {
+            return Err(ModelError::new(
+                ModelErrorType::InvalidRequest,
+                format!("{} is not an HTTP or HTTPS url.", url),
+                None,
+            ));
+        }
+    }
+    if registration.client_id.trim().is_empty() {
+        return Err(ModelError::new(
+            ModelErrorType::InvalidRequest,
+            "The client id is required.".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn new_registration(client_id: &str) -> NewLtiRegistration {
+        NewLtiRegistration {
+            name: "Partner Moodle".to_string(),
+            issuer: "https://moodle.example.com".to_string(),
+            client_id: client_id.to_string(),
+            auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+            auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+            jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+            link_existing_users_by_email: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn client_id_is_required_only_with_multiple_registrations() {
+        insert_data!(:tx, user: _user, :org);
+        let first = insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &new_registration("first"),
+        )
+        .await
+        .unwrap();
+        let found = get_by_issuer_and_client_id(tx.as_mut(), "https://moodle.example.com", None)
+            .await
+            .unwrap();
+        assert_eq!(found.id, first.id);
+
+        let second = insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &new_registration("second"),
+        )
+        .await
+        .unwrap();
+        assert!(
+            get_by_issuer_and_client_id(tx.as_mut(), "https://moodle.example.com", None)
+                .await
+                .is_err()
+        );
+        let found =
+            get_by_issuer_and_client_id(tx.as_mut(), "https://moodle.example.com", Some("second"))
+                .await
+                .unwrap();
+        assert_eq!(found.id, second.id);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_urls() {
+        insert_data!(:tx, user: _user, :org);
+        let mut registration = new_registration("client");
+        registration.jwks_url = "ftp://moodle.example.com/certs".to_string();
+        assert!(
+            insert(tx.as_mut(), PKeyPolicy::Generate, org, &registration)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+
+/// A link in an LTI platform that launches a page or a chapter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LtiResourceLink {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub lti_context_id: Uuid,
+    pub resource_link_id: String,
+    pub title: Option<String>,
+    pub page_id: Option<Uuid>,
+    pub chapter_id: Option<Uuid>,
+    pub line_item_url: Option<String>,
+}
+
+/// What an LTI resource link launches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LtiLinkTarget {
+    Page(Uuid),
+    Chapter(Uuid),
+}
+
+impl LtiLinkTarget {
+    pub fn page_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Page(page_id) => Some(*page_id),
+            Self::Chapter(_) => None,
+        }
+    }
+
+    pub fn chapter_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Page(_) => None,
+            Self::Chapter(chapter_id) => Some(*chapter_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewLtiResourceLink<'a> {
+    pub lti_context_id: Uuid,
+    pub resource_link_id: &'a str,
+    pub title: Option<&'a str>,
+    pub target: LtiLinkTarget,
+    pub line_item_url: Option<&'a str>,
+}
+
+/// Inserts the link on its first launch. On later launches, updates the link in case it has been changed in the platform.
+pub async fn upsert(
+    conn: &mut PgConnection,
+    new_link: &NewLtiResourceLink<'_>,
+) -> ModelResult<LtiResourceLink> {
+    let res = sqlx::query_as!(
+        LtiResourceLink,
+        "
+INSERT INTO lti_resource_links (
+    lti_context_id,
+    resource_link_id,
+    title,
+    page_id,
+    chapter_id,
+    line_item_url
+  )
+VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (lti_context_id, resource_link_id)
+WHERE deleted_at IS NULL DO
+UPDATE
+SET title = excluded.title,
+  page_id = excluded.page_id,
+  chapter_id = excluded.chapter_id,
+  line_item_url = excluded.line_item_url
+RETURNING *
+        ",
+        new_link.lti_context_id,
+        new_link.resource_link_id,
+        new_link.title,
+        new_link.target.page_id(),
+        new_link.target.chapter_id(),
+        new_link.line_item_url,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// The total maximum score of the exercises in the page or the chapter.
+pub async fn get_score_maximum(conn: &mut PgConnection, target: LtiLinkTarget) -> ModelResult<i32> {
+    let res = sqlx::query!(
+        r#"
+SELECT COALESCE(SUM(score_maximum), 0)::INTEGER AS "score_maximum!"
+FROM exercises
+WHERE (
+    page_id = $1
+    OR chapter_id = $2
+  )
+  AND deleted_at IS NULL
+        "#,
+        target.page_id(),
+        target.chapter_id(),
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.score_maximum)
+}
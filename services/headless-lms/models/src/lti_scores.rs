@@ -0,0 +1,263 @@
+use crate::prelude::*;
+
+/// A score that needs to be passed back to an LTI platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingLtiScore {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// The id of the user at the platform.
+    pub subject: String,
+    pub lti_registration_id: Uuid,
+    pub line_item_url: String,
+    pub course_instance_id: Uuid,
+    pub page_id: Option<Uuid>,
+    pub chapter_id: Option<Uuid>,
+}
+
+/// The current score of a user in the exercises of a linked page or chapter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrentLtiScore {
+    pub score_given: f32,
+    pub score_maximum: i32,
+    pub graded_exercises: i64,
+    pub total_exercises: i64,
+}
+
+/// Records that the user has launched the link, so that their score is passed back to the platform.
+pub async fn insert_if_not_exists(
+    conn: &mut PgConnection,
+    lti_resource_link_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+INSERT INTO lti_scores (lti_resource_link_id, user_id)
+VALUES ($1, $2) ON CONFLICT (lti_resource_link_id, user_id)
+WHERE deleted_at IS NULL DO NOTHING
+        ",
+        lti_resource_link_id,
+        user_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Returns the scores that have not been passed back yet, or whose exercise states have been updated after they were last passed back.
+pub async fn get_pending(conn: &mut PgConnection, limit: i64) -> ModelResult<Vec<PendingLtiScore>> {
+    let res = sqlx::query_as!(
+        PendingLtiScore,
+        r#"
+SELECT s.id,
+  s.user_id,
+  u.subject,
+  c.lti_registration_id,
+  l.line_item_url AS "line_item_url!",
+  c.course_instance_id,
+  l.page_id,
+  l.chapter_id
+FROM lti_scores s
+  JOIN lti_resource_links l ON l.id = s.lti_resource_link_id
+  JOIN lti_contexts c ON c.id = l.lti_context_id
+  JOIN lti_registrations r ON r.id = c.lti_registration_id
+  JOIN lti_users u ON u.lti_registration_id = c.lti_registration_id
+  AND u.user_id = s.user_id
+  AND u.deleted_at IS NULL
+WHERE l.line_item_url IS NOT NULL
+  AND s.deleted_at IS NULL
+  AND l.deleted_at IS NULL
+  AND c.deleted_at IS NULL
+  AND r.deleted_at IS NULL
+  AND (
+    s.synced_at IS NULL
+    OR EXISTS (
+      SELECT 1
+      FROM user_exercise_states ues
+        JOIN exercises e ON e.id = ues.exercise_id
+      WHERE ues.user_id = s.user_id
+        AND ues.course_instance_id = c.course_instance_id
+        AND (
+          e.page_id = l.page_id
+          OR e.chapter_id = l.chapter_id
+        )
+        AND ues.updated_at > s.synced_at
+    )
+  )
+ORDER BY s.synced_at NULLS FIRST
+LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Sums the scores of the user in the exercises of the linked page or chapter.
+pub async fn get_current_score(
+    conn: &mut PgConnection,
+    pending: &PendingLtiScore,
+) -> ModelResult<CurrentLtiScore> {
+    let res = sqlx::query_as!(
+        CurrentLtiScore,
+        r#"
+SELECT COALESCE(SUM(ues.score_given), 0)::REAL AS "score_given!",
+  COALESCE(SUM(e.score_maximum), 0)::INTEGER AS "score_maximum!",
+  COUNT(ues.score_given) AS "graded_exercises!",
+  COUNT(e.id) AS "total_exercises!"
+FROM exercises e
+  LEFT JOIN user_exercise_states ues ON ues.exercise_id = e.id
+  AND ues.user_id = $1
+  AND ues.course_instance_id = $2
+  AND ues.deleted_at IS NULL
+WHERE (
+    e.page_id = $3
+    OR e.chapter_id = $4
+  )
+  AND e.deleted_at IS NULL
+        "#,
+        pending.user_id,
+        pending.course_instance_id,
+        pending.page_id,
+        pending.chapter_id,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Records the score that was passed back. `synced_at` should be the time before the score was computed, so that updates made during the passback are not missed.
+pub async fn mark_synced(
+    conn: &mut PgConnection,
+    id: Uuid,
+    score: &CurrentLtiScore,
+    synced_at: DateTime<Utc>,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE lti_scores
+SET score_given = $2,
+  score_maximum = $3,
+  synced_at = $4
+WHERE id = $1
+        ",
+        id,
+        score.score_given,
+        score.score_maximum,
+        synced_at
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        exercises::{ActivityProgress, GradingProgress},
+        lti_contexts::{self, NewLtiContext},
+        lti_registrations::{self, NewLtiRegistration},
+        lti_resource_links::{self, LtiLinkTarget, NewLtiResourceLink},
+        lti_users,
+        test_helper::*,
+        user_exercise_states::{self, ReviewingStage, UserExerciseStateUpdate},
+    };
+
+    #[tokio::test]
+    async fn scores_are_pending_until_synced_and_after_updates() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page, :exercise);
+        let registration = lti_registrations::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &NewLtiRegistration {
+                name: "Partner Moodle".to_string(),
+                issuer: "https://moodle.example.com".to_string(),
+                client_id: "client".to_string(),
+                auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+                auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+                jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+                link_existing_users_by_email: false,
+            },
+        )
+        .await
+        .unwrap();
+        lti_users::insert(tx.as_mut(), registration.id, "moodle-user", user)
+            .await
+            .unwrap();
+        let context = lti_contexts::get_or_insert(
+            tx.as_mut(),
+            &NewLtiContext {
+                lti_registration_id: registration.id,
+                deployment_id: "1",
+                context_id: "course-1",
+                title: None,
+                course_id: course,
+            },
+        )
+        .await
+        .unwrap();
+        let link = lti_resource_links::upsert(
+            tx.as_mut(),
+            &NewLtiResourceLink {
+                lti_context_id: context.id,
+                resource_link_id: "link-1",
+                title: Some("Chapter 1"),
+                target: LtiLinkTarget::Page(page),
+                line_item_url: Some(
+                    "https://moodle.example.com/mod/lti/services.php/2/lineitems/1/lineitem",
+                ),
+            },
+        )
+        .await
+        .unwrap();
+        insert_if_not_exists(tx.as_mut(), link.id, user)
+            .await
+            .unwrap();
+
+        let pending = get_pending(tx.as_mut(), 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].subject, "moodle-user");
+        assert_eq!(pending[0].course_instance_id, context.course_instance_id);
+        let score = get_current_score(tx.as_mut(), &pending[0]).await.unwrap();
+        assert_eq!(score.score_given, 0.0);
+        assert_eq!(score.score_maximum, 1);
+        assert_eq!(score.graded_exercises, 0);
+        assert_eq!(score.total_exercises, 1);
+
+        // the timestamps of the updates are the start time of the transaction
+        let synced_at = Utc::now() - chrono::Duration::hours(1);
+        mark_synced(tx.as_mut(), pending[0].id, &score, synced_at)
+            .await
+            .unwrap();
+        assert!(get_pending(tx.as_mut(), 10).await.unwrap().is_empty());
+
+        let state = user_exercise_states::get_or_create_user_exercise_state(
+            tx.as_mut(),
+            user,
+            exercise,
+            Some(context.course_instance_id),
+            None,
+        )
+        .await
+        .unwrap();
+        user_exercise_states::update(
+            tx.as_mut(),
+            UserExerciseStateUpdate {
+                id: state.id,
+                score_given: Some(1.0),
+                activity_progress: ActivityProgress::Completed,
+                reviewing_stage: ReviewingStage::NotStarted,
+                grading_progress: GradingProgress::FullyGraded,
+            },
+        )
+        .await
+        .unwrap();
+        let pending = get_pending(tx.as_mut(), 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        let score = get_current_score(tx.as_mut(), &pending[0]).await.unwrap();
+        assert_eq!(score.score_given, 1.0);
+        assert_eq!(score.graded_exercises, 1);
+    }
+}
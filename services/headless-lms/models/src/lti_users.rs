@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+/// Links a user of an LTI platform to our user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LtiUser {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub lti_registration_id: Uuid,
+    pub subject: String,
+    pub user_id: Uuid,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    lti_registration_id: Uuid,
+    subject: &str,
+    user_id: Uuid,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO lti_users (lti_registration_id, subject, user_id)
+VALUES ($1, $2, $3)
+RETURNING id
+        ",
+        lti_registration_id,
+        subject,
+        user_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+/// Returns the id of the user that has been linked to the platform user, if any.
+pub async fn try_get_user_id_by_subject(
+    conn: &mut PgConnection,
+    lti_registration_id: Uuid,
+    subject: &str,
+) -> ModelResult<Option<Uuid>> {
+    let res = sqlx::query!(
+        "
+SELECT user_id
+FROM lti_users
+WHERE lti_registration_id = $1
+  AND subject = $2
+  AND deleted_at IS NULL
+        ",
+        lti_registration_id,
+        subject
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res.map(|r| r.user_id))
+}
@@ -0,0 +1,302 @@
+/*!
+Drafts of pages.
+
+A draft lets the teachers prepare changes to a page while the published version stays visible to the students. The draft is saved in the same format the CMS saves pages in, and publishing the draft updates the page just like saving it in the CMS would. A draft is published either manually or automatically at its `publish_at` by the page draft publisher.
+
+Users who can edit the page see the draft in the course material instead of the published version.
+*/
+
+use futures::future::BoxFuture;
+use url::Url;
+
+use crate::{
+    exercise_service_info::ExerciseServiceInfoApi,
+    page_history::HistoryChangeReason,
+    pages::{self, CmsPageUpdate, ContentManagementPage, PageUpdateArgs},
+    prelude::*,
+    CourseOrExamId,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageDraft {
+    pub id: Uuid,
+    pub page_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// None if the personal data of the author has been erased.
+    pub author_user_id: Option<Uuid>,
+    /// The changes to the page, in the format the CMS saves pages in.
+    pub content: CmsPageUpdate,
+    /// When the draft is published automatically. If null, the draft is only published manually.
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageDraftUpdate {
+    pub content: CmsPageUpdate,
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+struct DatabasePageDraft {
+    id: Uuid,
+    page_id: Uuid,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    author_user_id: Option<Uuid>,
+    content: serde_json::Value,
+    publish_at: Option<DateTime<Utc>>,
+}
+
+impl DatabasePageDraft {
+    fn into_page_draft(self) -> ModelResult<PageDraft> {
+        Ok(PageDraft {
+            id: self.id,
+            page_id: self.page_id,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            author_user_id: self.author_user_id,
+            content: serde_json::from_value(self.content)?,
+            publish_at: self.publish_at,
+        })
+    }
+}
+
+/// Saves the draft of the page, replacing the previous draft if there is one.
+pub async fn upsert(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    author_user_id: Uuid,
+    draft_update: &PageDraftUpdate,
+) -> ModelResult<PageDraft> {
+    draft_update.content.validate_exercise_data()?;
+    let mut tx = conn.begin().await?;
+    crate::page_edit_locks::check_can_save(&mut tx, page_id, author_user_id).await?;
+    let res = sqlx::query_as!(
+        DatabasePageDraft,
+        "
+INSERT INTO page_drafts (page_id, author_user_id, content, publish_at)
+VALUES ($1, $2, $3, $4) ON CONFLICT (page_id)
+WHERE deleted_at IS NULL DO
+UPDATE
+SET author_user_id = excluded.author_user_id,
+  content = excluded.content,
+  publish_at = excluded.publish_at
+RETURNING id,
+  page_id,
+  created_at,
+  updated_at,
+  author_user_id,
+  content,
+  publish_at
+",
+        page_id,
+        author_user_id,
+        serde_json::to_value(&draft_update.content)?,
+        draft_update.publish_at,
+    )
+    .fetch_one(&mut tx)
+    .await?;
+    tx.commit().await?;
+    res.into_page_draft()
+}
+
+pub async fn get_by_page_id(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+) -> ModelResult<Option<PageDraft>> {
+    let res = sqlx::query_as!(
+        DatabasePageDraft,
+        "
+SELECT id,
+  page_id,
+  created_at,
+  updated_at,
+  author_user_id,
+  content,
+  publish_at
+FROM page_drafts
+WHERE page_id = $1
+  AND deleted_at IS NULL
+",
+        page_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    res.map(DatabasePageDraft::into_page_draft).transpose()
+}
+
+/// Gets the ids of the pages whose drafts should have been published by now.
+pub async fn get_page_ids_of_drafts_to_publish(conn: &mut PgConnection) -> ModelResult<Vec<Uuid>> {
+    let res = sqlx::query!(
+        "
+SELECT page_id
+FROM page_drafts
+WHERE publish_at <= now()
+  AND deleted_at IS NULL
+ORDER BY publish_at
+"
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res.into_iter().map(|r| r.page_id).collect())
+}
+
+/// Discards the draft of the page.
+pub async fn delete(conn: &mut PgConnection, page_id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE page_drafts
+SET deleted_at = now()
+WHERE page_id = $1
+  AND deleted_at IS NULL
+",
+        page_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Updates the page with its draft and deletes the draft. The draft overwrites the page even if someone else is editing the page or the page has been saved after the draft.
+///
+/// The page history entry is attributed to the author of the draft, or to `published_by` if the author has been erased.
+pub async fn publish(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    published_by: Option<Uuid>,
+    spec_fetcher: impl Fn(
+        Url,
+        &str,
+        Option<&serde_json::Value>,
+    ) -> BoxFuture<'static, ModelResult<serde_json::Value>>,
+    fetch_service_info: impl Fn(Url) -> BoxFuture<'static, ModelResult<ExerciseServiceInfoApi>>,
+) -> ModelResult<ContentManagementPage> {
+    let mut tx = conn.begin().await?;
+    // Deleting the draft first so that the draft can't be published twice at the same time
+    let draft = sqlx::query_as!(
+        DatabasePageDraft,
+        "
+UPDATE page_drafts
+SET deleted_at = now()
+WHERE page_id = $1
+  AND deleted_at IS NULL
+RETURNING id,
+  page_id,
+  created_at,
+  updated_at,
+  author_user_id,
+  content,
+  publish_at
+",
+        page_id
+    )
+    .fetch_optional(&mut tx)
+    .await?
+    .ok_or_else(|| {
+        ModelError::new(
+            ModelErrorType::NotFound,
+            "The page does not have a draft.".to_string(),
+            None,
+        )
+    })?
+    .into_page_draft()?;
+    let author = draft.author_user_id.or(published_by).ok_or_else(|| {
+        ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The author of the draft has been erased, so the draft has to be published manually."
+                .to_string(),
+            None,
+        )
+    })?;
+    let course_or_exam_id = pages::get_course_and_exam_id(&mut tx, page_id).await?;
+    let page = pages::update_page(
+        &mut tx,
+        PageUpdateArgs {
+            page_id,
+            author,
+            cms_page_update: CmsPageUpdate {
+                base_history_id: None,
+                ..draft.content
+            },
+            retain_ids: false,
+            history_change_reason: HistoryChangeReason::DraftPublished,
+            is_exam_page: matches!(course_or_exam_id, CourseOrExamId::Exam(_)),
+        },
+        spec_fetcher,
+        fetch_service_info,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(page)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[tokio::test]
+    async fn publishing_a_draft_updates_the_page_and_deletes_the_draft() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+        let published = pages::get_page(tx.as_mut(), page).await.unwrap();
+
+        let draft = upsert(
+            tx.as_mut(),
+            page,
+            user,
+            &PageDraftUpdate {
+                content: CmsPageUpdate {
+                    content: serde_json::json!([]),
+                    exercises: vec![],
+                    exercise_slides: vec![],
+                    exercise_tasks: vec![],
+                    url_path: published.url_path.clone(),
+                    title: "Draft title".to_string(),
+                    chapter_id: published.chapter_id,
+                    base_history_id: None,
+                },
+                publish_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+            },
+        )
+        .await
+        .unwrap();
+        // the published version stays as it was until the draft is published
+        assert_eq!(
+            pages::get_page(tx.as_mut(), page).await.unwrap().title,
+            published.title
+        );
+        assert_eq!(
+            get_page_ids_of_drafts_to_publish(tx.as_mut())
+                .await
+                .unwrap(),
+            vec![page]
+        );
+
+        let cms_page = publish(
+            tx.as_mut(),
+            page,
+            None,
+            |_, _, _| unimplemented!(),
+            |_| unimplemented!(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cms_page.page.title, draft.content.title);
+        assert!(get_by_page_id(tx.as_mut(), page).await.unwrap().is_none());
+        assert!(get_page_ids_of_drafts_to_publish(tx.as_mut())
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(publish(
+            tx.as_mut(),
+            page,
+            None,
+            |_, _, _| unimplemented!(),
+            |_| unimplemented!(),
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,284 @@
+/*!
+Edit locks and presence for pages in the CMS.
+
+When a user opens a page in the CMS, the editor tries to acquire the edit lock of the page and then sends a heartbeat periodically. The heartbeat renews the lock of the user holding it and tells the others who have the page open. Only the user holding an unexpired lock can save the page, and the others have to take the lock over before saving.
+
+Locks and presence both expire a minute after the latest heartbeat.
+*/
+
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageEditLock {
+    pub page_id: Uuid,
+    pub user_id: Uuid,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A user who has the page open in the CMS.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageEditor {
+    pub user_id: Uuid,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: String,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageEditStatus {
+    pub page_id: Uuid,
+    /// The unexpired lock of the page, if any.
+    pub lock: Option<PageEditLock>,
+    /// Everyone who has the page open, including the user holding the lock.
+    pub editors: Vec<PageEditor>,
+}
+
+/// Acquires the lock for the user unless someone else holds an unexpired lock. The returned status tells who holds the lock.
+pub async fn acquire(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<PageEditStatus> {
+    let mut tx = conn.begin().await?;
+    record_presence(&mut tx, page_id, user_id).await?;
+    sqlx::query!(
+        "
+INSERT INTO page_edit_locks (page_id, user_id, expires_at)
+VALUES ($1, $2, now() + interval '1 minute') ON CONFLICT (page_id) DO
+UPDATE
+SET user_id = excluded.user_id,
+  expires_at = excluded.expires_at
+WHERE page_edit_locks.user_id = excluded.user_id
+  OR page_edit_locks.expires_at < now()
+",
+        page_id,
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    let status = get_status(&mut tx, page_id).await?;
+    tx.commit().await?;
+    Ok(status)
+}
+
+/// The heartbeat of the editor. Renews the lock if the user still holds it, e.g. it has not been taken over by someone else.
+pub async fn renew(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<PageEditStatus> {
+    let mut tx = conn.begin().await?;
+    record_presence(&mut tx, page_id, user_id).await?;
+    sqlx::query!(
+        "
+UPDATE page_edit_locks
+SET expires_at = now() + interval '1 minute'
+WHERE page_id = $1
+  AND user_id = $2
+",
+        page_id,
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    let status = get_status(&mut tx, page_id).await?;
+    tx.commit().await?;
+    Ok(status)
+}
+
+/// Takes the lock over from whoever holds it.
+pub async fn steal(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<PageEditStatus> {
+    let mut tx = conn.begin().await?;
+    record_presence(&mut tx, page_id, user_id).await?;
+    sqlx::query!(
+        "
+INSERT INTO page_edit_locks (page_id, user_id, expires_at)
+VALUES ($1, $2, now() + interval '1 minute') ON CONFLICT (page_id) DO
+UPDATE
+SET user_id = excluded.user_id,
+  expires_at = excluded.expires_at
+",
+        page_id,
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    let status = get_status(&mut tx, page_id).await?;
+    tx.commit().await?;
+    Ok(status)
+}
+
+/// Releases the lock if the user holds it and marks that the user no longer has the page open.
+pub async fn release(conn: &mut PgConnection, page_id: Uuid, user_id: Uuid) -> ModelResult<()> {
+    let mut tx = conn.begin().await?;
+    sqlx::query!(
+        "
+DELETE FROM page_edit_locks
+WHERE page_id = $1
+  AND user_id = $2
+",
+        page_id,
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    sqlx::query!(
+        "
+DELETE FROM page_edit_presences
+WHERE page_id = $1
+  AND user_id = $2
+",
+        page_id,
+        user_id
+    )
+    .execute(&mut tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn record_presence(conn: &mut PgConnection, page_id: Uuid, user_id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+INSERT INTO page_edit_presences (page_id, user_id)
+VALUES ($1, $2) ON CONFLICT (page_id, user_id) DO
+UPDATE
+SET last_seen_at = now()
+",
+        page_id,
+        user_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// The unexpired lock of the page, if any.
+pub async fn get_lock(conn: &mut PgConnection, page_id: Uuid) -> ModelResult<Option<PageEditLock>> {
+    let res = sqlx::query_as!(
+        PageEditLock,
+        "
+SELECT page_edit_locks.page_id,
+  page_edit_locks.user_id,
+  users.first_name,
+  users.last_name,
+  users.email,
+  page_edit_locks.expires_at
+FROM page_edit_locks
+  JOIN users ON users.id = page_edit_locks.user_id
+WHERE page_edit_locks.page_id = $1
+  AND page_edit_locks.expires_at > now()
+",
+        page_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_status(conn: &mut PgConnection, page_id: Uuid) -> ModelResult<PageEditStatus> {
+    let lock = get_lock(&mut *conn, page_id).await?;
+    let editors = sqlx::query_as!(
+        PageEditor,
+        "
+SELECT page_edit_presences.user_id,
+  users.first_name,
+  users.last_name,
+  users.email,
+  page_edit_presences.last_seen_at
+FROM page_edit_presences
+  JOIN users ON users.id = page_edit_presences.user_id
+WHERE page_edit_presences.page_id = $1
+  AND page_edit_presences.last_seen_at > now() - interval '1 minute'
+ORDER BY page_edit_presences.created_at,
+  page_edit_presences.user_id
+",
+        page_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(PageEditStatus {
+        page_id,
+        lock,
+        editors,
+    })
+}
+
+/// Fails with [ModelErrorType::PageEditLocked] if someone else than the user holds an unexpired lock on the page.
+pub async fn check_can_save(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<()> {
+    match get_lock(conn, page_id).await? {
+        Some(lock) if lock.user_id != user_id => Err(ModelError::new(
+            ModelErrorType::PageEditLocked(Box::new(lock)),
+            "Someone else is editing the page. Take over the editing to save your changes."
+                .to_string(),
+            None,
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[tokio::test]
+    async fn only_the_lock_holder_can_save() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+        let other_user = crate::users::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            "other@example.com",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let status = acquire(tx.as_mut(), page, user).await.unwrap();
+        assert_eq!(status.lock.unwrap().user_id, user);
+        check_can_save(tx.as_mut(), page, user).await.unwrap();
+
+        // the other user can't acquire the lock or save, but they are shown as editing the page
+        let status = acquire(tx.as_mut(), page, other_user).await.unwrap();
+        assert_eq!(status.lock.unwrap().user_id, user);
+        assert_eq!(status.editors.len(), 2);
+        let err = check_can_save(tx.as_mut(), page, other_user)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.error_type(),
+            ModelErrorType::PageEditLocked(_)
+        ));
+
+        // after taking the lock over, the other user can save and the original holder can't
+        let status = steal(tx.as_mut(), page, other_user).await.unwrap();
+        assert_eq!(status.lock.unwrap().user_id, other_user);
+        check_can_save(tx.as_mut(), page, other_user).await.unwrap();
+        assert!(check_can_save(tx.as_mut(), page, user).await.is_err());
+        let status = renew(tx.as_mut(), page, user).await.unwrap();
+        assert_eq!(status.lock.unwrap().user_id, other_user);
+
+        // anyone can save after the lock is released
+        release(tx.as_mut(), page, other_user).await.unwrap();
+        check_can_save(tx.as_mut(), page, user).await.unwrap();
+        let status = get_status(tx.as_mut(), page).await.unwrap();
+        assert!(status.lock.is_none());
+        assert_eq!(status.editors.len(), 1);
+    }
+}
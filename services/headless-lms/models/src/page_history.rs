@@ -1,3 +1,7 @@
+use headless_lms_utils::{
+    content_diff::{self, BlockChange, ItemChange, TextDiffPart},
+    document_schema_processor::GutenbergBlock,
+};
 use serde_json::Value;
 
 use crate::{
@@ -13,6 +17,7 @@ use crate::{
 pub enum HistoryChangeReason {
     PageSaved,
     HistoryRestored,
+    DraftPublished,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -38,6 +43,22 @@ pub struct PageHistoryContent {
     pub peer_review_questions: Vec<CmsPeerReviewQuestion>,
 }
 
+/// The changes between two versions of a page.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageHistoryDiff {
+    pub old_history_id: Uuid,
+    pub new_history_id: Uuid,
+    /// None if the title was not changed.
+    pub title_diff: Option<Vec<TextDiffPart>>,
+    pub blocks: Vec<BlockChange>,
+    pub exercises: Vec<ItemChange>,
+    pub exercise_slides: Vec<ItemChange>,
+    pub exercise_tasks: Vec<ItemChange>,
+    pub peer_review_configs: Vec<ItemChange>,
+    pub peer_review_questions: Vec<ItemChange>,
+}
+
 // Batch refactor pushed past the limit
 #[allow(clippy::too_many_arguments)]
 pub async fn insert(
@@ -106,6 +127,144 @@ WHERE page_history.id = $1
     })
 }
 
+/// Compares two versions of the page. Both history entries must belong to the page.
+pub async fn diff(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    old_history_id: Uuid,
+    new_history_id: Uuid,
+) -> ModelResult<PageHistoryDiff> {
+    let old = get_title_and_content(conn, page_id, old_history_id).await?;
+    let new = get_title_and_content(conn, page_id, new_history_id).await?;
+    diff_contents(
+        old_history_id,
+        &old.0,
+        &old.1,
+        new_history_id,
+        &new.0,
+        &new.1,
+    )
+}
+
+async fn get_title_and_content(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    history_id: Uuid,
+) -> ModelResult<(String, PageHistoryContent)> {
+    let res = sqlx::query!(
+        "
+SELECT title,
+  content
+FROM page_history
+WHERE id = $1
+  AND page_id = $2
+  AND deleted_at IS NULL
+",
+        history_id,
+        page_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok((res.title, serde_json::from_value(res.content)?))
+}
+
+fn diff_contents(
+    old_history_id: Uuid,
+    old_title: &str,
+    old: &PageHistoryContent,
+    new_history_id: Uuid,
+    new_title: &str,
+    new: &PageHistoryContent,
+) -> ModelResult<PageHistoryDiff> {
+    fn with_ids<T: Serialize>(
+        items: &[T],
+        get_id: impl Fn(&T) -> Uuid,
+    ) -> ModelResult<Vec<(Uuid, Value)>> {
+        items
+            .iter()
+            .map(|item| Ok((get_id(item), serde_json::to_value(item)?)))
+            .collect()
+    }
+    fn diff_items<T: Serialize>(
+        old: &[T],
+        new: &[T],
+        get_id: impl Fn(&T) -> Uuid,
+    ) -> ModelResult<Vec<ItemChange>> {
+        Ok(content_diff::diff_items(
+            &with_ids(old, &get_id)?,
+            &with_ids(new, &get_id)?,
+        ))
+    }
+
+    let old_blocks: Vec<GutenbergBlock> = serde_json::from_value(old.content.clone())?;
+    let new_blocks: Vec<GutenbergBlock> = serde_json::from_value(new.content.clone())?;
+    Ok(PageHistoryDiff {
+        old_history_id,
+        new_history_id,
+        title_diff: (old_title != new_title).then(|| content_diff::diff_text(old_title, new_title)),
+        blocks: content_diff::diff_blocks(&old_blocks, &new_blocks),
+        exercises: diff_items(&old.exercises, &new.exercises, |e| e.id)?,
+        exercise_slides: diff_items(&old.exercise_slides, &new.exercise_slides, |s| s.id)?,
+        exercise_tasks: diff_items(&old.exercise_tasks, &new.exercise_tasks, |t| t.id)?,
+        peer_review_configs: diff_items(&old.peer_review_configs, &new.peer_review_configs, |c| {
+            c.id
+        })?,
+        peer_review_questions: diff_items(
+            &old.peer_review_questions,
+            &new.peer_review_questions,
+            |q| q.id,
+        )?,
+    })
+}
+
+/// The id of the latest version of the page, or None if the page has no history.
+pub async fn get_latest_id(conn: &mut PgConnection, page_id: Uuid) -> ModelResult<Option<Uuid>> {
+    let res = sqlx::query!(
+        "
+SELECT id
+FROM page_history
+WHERE page_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at DESC,
+  id
+LIMIT 1
+",
+        page_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res.map(|r| r.id))
+}
+
+/// The version of the page with the given id, or None if it is not a version of the page.
+pub async fn get_by_id_and_page_id(
+    conn: &mut PgConnection,
+    id: Uuid,
+    page_id: Uuid,
+) -> ModelResult<Option<PageHistory>> {
+    let res = sqlx::query_as!(
+        PageHistory,
+        r#"
+SELECT id,
+  title,
+  content,
+  created_at,
+  history_change_reason as "history_change_reason: HistoryChangeReason",
+  restored_from_id,
+  author_user_id
+FROM page_history
+WHERE id = $1
+  AND page_id = $2
+  AND deleted_at IS NULL
+"#,
+        id,
+        page_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res)
+}
+
 pub async fn history(
     conn: &mut PgConnection,
     page_id: Uuid,
@@ -151,3 +310,47 @@ AND deleted_at IS NULL
     .await?;
     Ok(res.count.unwrap_or_default())
 }
+
+#[cfg(test)]
+mod test {
+    use headless_lms_utils::content_diff::ChangeKind;
+    use serde_json::json;
+
+    use super::*;
+
+    fn content(blocks: Value) -> PageHistoryContent {
+        PageHistoryContent {
+            content: blocks,
+            exercises: vec![],
+            exercise_slides: vec![],
+            exercise_tasks: vec![],
+            peer_review_configs: vec![],
+            peer_review_questions: vec![],
+        }
+    }
+
+    #[test]
+    fn diffs_title_and_blocks() {
+        let old_id = Uuid::new_v4();
+        let new_id = Uuid::new_v4();
+        let old = content(json!([
+            {"name": "core/paragraph", "isValid": true, "clientId": "c68f55ae-65c4-4e9b-aded-0b52e36e344a", "attributes": {"content": "Hello"}, "innerBlocks": []}
+        ]));
+        let new = content(json!([
+            {"name": "core/paragraph", "isValid": true, "clientId": "c68f55ae-65c4-4e9b-aded-0b52e36e344a", "attributes": {"content": "Hello world"}, "innerBlocks": []},
+            {"name": "core/heading", "isValid": true, "clientId": "415ecc4c-a5c6-410e-a43f-c14b8ee910ea", "attributes": {"content": "Title"}, "innerBlocks": []}
+        ]));
+
+        let diff = diff_contents(old_id, "Page", &old, new_id, "Page", &new).unwrap();
+        assert_eq!(diff.old_history_id, old_id);
+        assert_eq!(diff.new_history_id, new_id);
+        assert!(diff.title_diff.is_none());
+        assert_eq!(diff.blocks.len(), 2);
+        assert_eq!(diff.blocks[0].kind, ChangeKind::Modified);
+        assert_eq!(diff.blocks[1].kind, ChangeKind::Added);
+        assert!(diff.exercises.is_empty());
+
+        let diff = diff_contents(old_id, "Page", &old, new_id, "New page", &new).unwrap();
+        assert!(diff.title_diff.is_some());
+    }
+}
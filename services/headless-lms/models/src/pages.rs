@@ -1,8 +1,9 @@
 use std::collections::{hash_map, HashMap};
 
 use futures::future::{BoxFuture, OptionFuture};
-use headless_lms_utils::document_schema_processor::{
-    contains_blocks_not_allowed_in_top_level_pages, GutenbergBlock,
+use headless_lms_utils::{
+    document_schema_processor::{contains_blocks_not_allowed_in_top_level_pages, GutenbergBlock},
+    merge_edits,
 };
 use itertools::Itertools;
 use sqlx::{Postgres, QueryBuilder, Row};
@@ -19,10 +20,11 @@ use crate::{
     exercise_slides::ExerciseSlide,
     exercise_tasks::ExerciseTask,
     exercises::Exercise,
-    page_history::{self, HistoryChangeReason, PageHistoryContent},
+    page_history::{self, HistoryChangeReason, PageHistory, PageHistoryContent},
     peer_review_configs::CmsPeerReviewConfig,
     peer_review_questions::CmsPeerReviewQuestion,
     prelude::*,
+    url_redirections::{self, MAX_REDIRECTION_HOPS},
     user_course_settings::{self, UserCourseSettings},
     CourseOrExamId,
 };
@@ -44,6 +46,10 @@ pub struct Page {
     pub order_number: i32,
     pub copied_from: Option<Uuid>,
     pub hidden: bool,
+    /// When the page becomes visible to students. Users who can edit the page can view it before that.
+    pub publish_at: Option<DateTime<Utc>>,
+    /// When the page stops being visible to students.
+    pub unpublish_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -61,6 +67,21 @@ impl Page {
     pub fn blocks_cloned(&self) -> ModelResult<Vec<GutenbergBlock>> {
         serde_json::from_value(self.content.clone()).map_err(Into::into)
     }
+
+    /// Checks the publishing schedule of the page. Doesn't take the chapter of the page or whether the page is hidden into account.
+    pub fn is_published_at(&self, timestamp: DateTime<Utc>) -> bool {
+        is_within_publishing_schedule(self.publish_at, self.unpublish_at, timestamp)
+    }
+}
+
+/// Whether something with the given publishing schedule is visible to students at the given time.
+pub fn is_within_publishing_schedule(
+    publish_at: Option<DateTime<Utc>>,
+    unpublish_at: Option<DateTime<Utc>>,
+    timestamp: DateTime<Utc>,
+) -> bool {
+    publish_at.map(|p| p <= timestamp).unwrap_or(true)
+        && unpublish_at.map(|u| u > timestamp).unwrap_or(true)
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -71,7 +92,11 @@ pub struct CoursePageWithUserData {
     pub settings: Option<UserCourseSettings>,
     /// If true, the frontend needs to update the url in the browser to match the path in the page object without reloading the page.
     pub was_redirected: bool,
+    /// Set if the redirection led to another language version of the course. The frontend needs to navigate to the page in the course with this slug.
+    pub redirected_course_slug: Option<String>,
     pub is_test_mode: bool,
+    /// If true, the page contains the unpublished draft of the page instead of the version students see.
+    pub is_draft: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -165,6 +190,8 @@ pub struct ContentManagementPage {
     pub peer_review_configs: Vec<CmsPeerReviewConfig>,
     pub peer_review_questions: Vec<CmsPeerReviewQuestion>,
     pub organization_id: Uuid,
+    /// The page history entry of this version of the page. The editor sends it back as the `base_history_id` of the next update.
+    pub history_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, PartialEq, Eq, Clone)]
@@ -429,6 +456,11 @@ impl PageVisibility {
             PageVisibility::Hidden => Some(false),
         }
     }
+
+    /// Public pages are only the ones that are visible to students right now according to the publishing schedules of the page and its chapter.
+    fn only_published(&self) -> bool {
+        matches!(self, PageVisibility::Public)
+    }
 }
 
 /// Gets all pages that belong to the given course that match the visibility filter.
@@ -453,20 +485,58 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages
 WHERE course_id = $1
   AND hidden IS DISTINCT FROM $2
+  AND (
+    NOT $3
+    OR (
+      (pages.publish_at IS NULL OR pages.publish_at <= now())
+      AND (pages.unpublish_at IS NULL OR pages.unpublish_at > now())
+      AND NOT EXISTS (
+        SELECT 1
+        FROM chapters
+        WHERE chapters.id = pages.chapter_id
+          AND (chapters.publish_at > now() OR chapters.unpublish_at <= now())
+      )
+    )
+  )
   AND deleted_at IS NULL
     ",
         course_id,
         inverse_visibility_filter,
+        page_visibility.only_published(),
     )
     .fetch_all(conn)
     .await?;
     Ok(res)
 }
 
+/// Returns the url paths of the deleted pages of the course.
+pub async fn get_deleted_url_paths_by_course_id(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+) -> ModelResult<Vec<String>> {
+    let res = sqlx::query!(
+        "
+SELECT DISTINCT url_path
+FROM pages
+WHERE course_id = $1
+  AND deleted_at IS NOT NULL
+        ",
+        course_id
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|r| r.url_path)
+    .collect();
+    Ok(res)
+}
+
 /// Gets all pages that belong to the given course but not in any chapter.
 pub async fn get_course_top_level_pages_by_course_id_and_visibility(
     conn: &mut PgConnection,
@@ -489,15 +559,25 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages p
 WHERE course_id = $1
   AND hidden IS DISTINCT FROM $2
+  AND (
+    NOT $3
+    OR (
+      (p.publish_at IS NULL OR p.publish_at <= now())
+      AND (p.unpublish_at IS NULL OR p.unpublish_at > now())
+    )
+  )
   AND p.chapter_id IS NULL
   AND p.deleted_at IS NULL
         ",
         course_id,
         inverse_visibility_filter,
+        page_visibility.only_published(),
     )
     .fetch_all(conn)
     .await?;
@@ -526,14 +606,30 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages
 WHERE chapter_id = $1
   AND hidden IS DISTINCT FROM $2
+  AND (
+    NOT $3
+    OR (
+      (pages.publish_at IS NULL OR pages.publish_at <= now())
+      AND (pages.unpublish_at IS NULL OR pages.unpublish_at > now())
+      AND NOT EXISTS (
+        SELECT 1
+        FROM chapters
+        WHERE chapters.id = pages.chapter_id
+          AND (chapters.publish_at > now() OR chapters.unpublish_at <= now())
+      )
+    )
+  )
   AND deleted_at IS NULL
     ",
         chapter_id,
         inverse_visibility_filter,
+        page_visibility.only_published(),
     )
     .fetch_all(conn)
     .await?;
@@ -556,7 +652,9 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages
 WHERE id = $1;
 ",
@@ -613,7 +711,9 @@ SELECT pages.id,
   pages.content,
   pages.order_number,
   pages.copied_from,
-  pages.hidden
+  pages.hidden,
+  pages.publish_at,
+  pages.unpublish_at
 FROM pages
 WHERE pages.course_id = $1
   AND url_path = $2
@@ -627,11 +727,21 @@ WHERE pages.course_id = $1
     Ok(page)
 }
 
+/// Which version of a page is shown in the course material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageVersion {
+    /// The version students see. Pages that are not published are not found.
+    Published,
+    /// The draft of the page if it has one and the published version otherwise, whether the page is published or not. Only for users who can edit the page.
+    Draft,
+}
+
 pub async fn get_page_with_user_data_by_path(
     conn: &mut PgConnection,
     user_id: Option<Uuid>,
     course_slug: &str,
     url_path: &str,
+    page_version: PageVersion,
 ) -> ModelResult<CoursePageWithUserData> {
     let course_data = get_nondeleted_course_id_by_slug(conn, course_slug).await?;
     let page_option = get_page_by_path(conn, course_data.id, url_path).await?;
@@ -643,20 +753,40 @@ pub async fn get_page_with_user_data_by_path(
             page,
             false,
             course_data.is_test_mode,
+            page_version,
         )
         .await;
     } else {
         let potential_redirect =
             try_to_find_redirected_page(conn, course_data.id, url_path).await?;
         if let Some(redirected_page) = potential_redirect {
-            return get_course_page_with_user_data_from_selected_page(
+            let redirected_course = match redirected_page.course_id {
+                Some(id) if id != course_data.id => {
+                    Some(crate::courses::get_course(conn, id).await?)
+                }
+                _ => None,
+            };
+            let is_test_mode = redirected_course
+                .as_ref()
+                .map(|c| c.is_test_mode)
+                .unwrap_or(course_data.is_test_mode);
+            // the page version was decided based on the permissions on the original course, so drafts are not shown from other courses
+            let page_version = if redirected_course.is_some() {
+                PageVersion::Published
+            } else {
+                page_version
+            };
+            let mut res = get_course_page_with_user_data_from_selected_page(
                 conn,
                 user_id,
                 redirected_page,
                 true,
-                course_data.is_test_mode,
+                is_test_mode,
+                page_version,
             )
-            .await;
+            .await?;
+            res.redirected_course_slug = redirected_course.map(|c| c.slug);
+            return Ok(res);
         }
     }
 
@@ -667,40 +797,39 @@ pub async fn get_page_with_user_data_by_path(
     ))
 }
 
+/// Follows the redirections from the url path until a page is found. Redirections may lead to other language versions of the course. The hit counts of the followed redirections are updated if a page is found.
 pub async fn try_to_find_redirected_page(
     conn: &mut PgConnection,
     course_id: Uuid,
     url_path: &str,
 ) -> ModelResult<Option<Page>> {
-    let page = sqlx::query_as!(
-        Page,
-        "
-SELECT pages.id,
-  pages.created_at,
-  pages.updated_at,
-  pages.course_id,
-  pages.exam_id,
-  pages.chapter_id,
-  pages.url_path,
-  pages.title,
-  pages.deleted_at,
-  pages.content,
-  pages.order_number,
-  pages.copied_from,
-  pages.hidden
-FROM url_redirections
-  JOIN pages on pages.id = url_redirections.destination_page_id
-WHERE url_redirections.course_id = $1
-  AND old_url_path = $2
-  AND url_redirections.deleted_at IS NULL
-  AND pages.deleted_at IS NULL;
-    ",
-        course_id,
-        url_path
-    )
-    .fetch_optional(conn)
-    .await?;
-    Ok(page)
+    let mut course_id = course_id;
+    let mut url_path = url_path.to_string();
+    let mut followed = vec![];
+    while followed.len() < MAX_REDIRECTION_HOPS {
+        let redirection =
+            match url_redirections::get_by_old_url_path(conn, course_id, &url_path).await? {
+                Some(redirection) if !followed.contains(&redirection.id) => redirection,
+                _ => break,
+            };
+        followed.push(redirection.id);
+        let page = if let Some(page_id) = redirection.destination_page_id {
+            let page = get_page(conn, page_id).await?;
+            if page.deleted_at.is_some() {
+                break;
+            }
+            Some(page)
+        } else {
+            course_id = redirection.destination_course_id.unwrap_or(course_id);
+            url_path = redirection.destination_url_path.unwrap_or_default();
+            get_page_by_path(conn, course_id, &url_path).await?
+        };
+        if let Some(page) = page {
+            url_redirections::record_hits(conn, &followed).await?;
+            return Ok(Some(page));
+        }
+    }
+    Ok(None)
 }
 
 pub async fn get_course_page_with_user_data_from_selected_page(
@@ -709,7 +838,32 @@ pub async fn get_course_page_with_user_data_from_selected_page(
     page: Page,
     was_redirected: bool,
     is_test_mode: bool,
+    page_version: PageVersion,
 ) -> ModelResult<CoursePageWithUserData> {
+    let (page, is_draft) = match page_version {
+        PageVersion::Published => {
+            if !is_published(conn, &page, Utc::now()).await? {
+                return Err(ModelError::new(
+                    ModelErrorType::NotFound,
+                    "Page not found".to_string(),
+                    None,
+                ));
+            }
+            (page, false)
+        }
+        PageVersion::Draft => match crate::page_drafts::get_by_page_id(conn, page.id).await? {
+            Some(draft) => (
+                Page {
+                    title: draft.content.title,
+                    content: draft.content.content,
+                    ..page
+                },
+                true,
+            ),
+            None => (page, false),
+        },
+    };
+
     if let Some(chapter_id) = page.chapter_id {
         if !crate::chapters::is_open(conn, chapter_id).await? {
             return Err(ModelError::new(
@@ -733,7 +887,9 @@ pub async fn get_course_page_with_user_data_from_selected_page(
                 instance,
                 settings,
                 was_redirected,
+                redirected_course_slug: None,
                 is_test_mode,
+                is_draft,
             });
         }
     }
@@ -742,10 +898,81 @@ pub async fn get_course_page_with_user_data_from_selected_page(
         instance: None,
         settings: None,
         was_redirected,
+        redirected_course_slug: None,
         is_test_mode,
+        is_draft,
     })
 }
 
+/// Whether the page is visible to students at the given time based on the publishing schedules of the page and its chapter.
+pub async fn is_published(
+    conn: &mut PgConnection,
+    page: &Page,
+    timestamp: DateTime<Utc>,
+) -> ModelResult<bool> {
+    if !page.is_published_at(timestamp) {
+        return Ok(false);
+    }
+    if let Some(chapter_id) = page.chapter_id {
+        let chapter = get_chapter(conn, chapter_id).await?;
+        return Ok(chapter.is_published_at(timestamp));
+    }
+    Ok(true)
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PagePublishingSchedule {
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
+}
+
+pub async fn set_publishing_schedule(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    schedule: &PagePublishingSchedule,
+) -> ModelResult<Page> {
+    if let (Some(publish_at), Some(unpublish_at)) = (schedule.publish_at, schedule.unpublish_at) {
+        if publish_at >= unpublish_at {
+            return Err(ModelError::new(
+                ModelErrorType::PreconditionFailed,
+                "The page has to be published before it is unpublished.".to_string(),
+                None,
+            ));
+        }
+    }
+    let page = sqlx::query_as!(
+        Page,
+        "
+UPDATE pages
+SET publish_at = $2,
+  unpublish_at = $3
+WHERE id = $1
+RETURNING id,
+  created_at,
+  updated_at,
+  course_id,
+  exam_id,
+  chapter_id,
+  url_path,
+  title,
+  deleted_at,
+  content,
+  order_number,
+  copied_from,
+  hidden,
+  publish_at,
+  unpublish_at
+",
+        page_id,
+        schedule.publish_at,
+        schedule.unpublish_at,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(page)
+}
+
 pub async fn get_page_with_exercises(
     conn: &mut PgConnection,
     page_id: Uuid,
@@ -807,6 +1034,7 @@ pub async fn get_page_with_exercises(
         .collect();
 
     let organization_id = get_organization_id(&mut *conn, page_id).await?;
+    let history_id = page_history::get_latest_id(&mut *conn, page_id).await?;
     Ok(ContentManagementPage {
         page,
         exercises,
@@ -815,6 +1043,7 @@ pub async fn get_page_with_exercises(
         peer_review_configs: peer_review_configs.into_values().collect(),
         peer_review_questions: peer_review_questions.into_values().flatten().collect(),
         organization_id,
+        history_id,
     })
 }
 
@@ -835,7 +1064,9 @@ SELECT pages.id,
   pages.content,
   pages.order_number,
   pages.copied_from,
-  pages.hidden
+  pages.hidden,
+  pages.publish_at,
+  pages.unpublish_at
 FROM pages
 WHERE exam_id = $1
 AND pages.deleted_at IS NULL
@@ -937,6 +1168,8 @@ pub struct CmsPageUpdate {
     pub url_path: String,
     pub title: String,
     pub chapter_id: Option<Uuid>,
+    /// The `history_id` of the version of the page the editor started editing from. If the page has been saved after it, the update is rejected with a [PageSaveConflict] so that the other save doesn't get overwritten. Updates without it always overwrite the page.
+    pub base_history_id: Option<Uuid>,
 }
 
 impl CmsPageUpdate {
@@ -997,6 +1230,16 @@ impl CmsPageUpdate {
     }
 }
 
+/// Returned when a page update is based on an older version of the page than the current one.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageSaveConflict {
+    /// The page as it is now.
+    pub current: ContentManagementPage,
+    /// The rejected update merged with the current version of the page, if the changes could be merged automatically.
+    pub merged: Option<CmsPageUpdate>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PageUpdateArgs {
     pub page_id: Uuid,
@@ -1020,6 +1263,23 @@ pub async fn update_page(
     let cms_page_update = page_update.cms_page_update;
     cms_page_update.validate_exercise_data()?;
 
+    let mut tx = conn.begin().await?;
+
+    // A draft is published when it is due, whoever happens to be editing the page at the time
+    if page_update.history_change_reason != HistoryChangeReason::DraftPublished {
+        crate::page_edit_locks::check_can_save(&mut tx, page_update.page_id, page_update.author)
+            .await?;
+    }
+    if let Some(base_history_id) = cms_page_update.base_history_id {
+        check_for_save_conflict(
+            &mut tx,
+            page_update.page_id,
+            base_history_id,
+            &cms_page_update,
+        )
+        .await?;
+    }
+
     let parsed_content: Vec<GutenbergBlock> = serde_json::from_value(cms_page_update.content)?;
     if !page_update.is_exam_page
         && cms_page_update.chapter_id.is_none()
@@ -1030,8 +1290,6 @@ pub async fn update_page(
             ));
     }
 
-    let mut tx = conn.begin().await?;
-
     // Updating page
     let page = sqlx::query_as!(
         Page,
@@ -1054,7 +1312,9 @@ RETURNING id,
   content,
   order_number,
   copied_from,
-  pages.hidden
+  pages.hidden,
+  pages.publish_at,
+  pages.unpublish_at
         ",
         page_update.page_id,
         serde_json::to_value(parsed_content)?,
@@ -1193,7 +1453,9 @@ RETURNING id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
         ",
         new_content,
         page.id
@@ -1236,7 +1498,7 @@ RETURNING id,
         peer_review_configs: final_peer_reviews,
         peer_review_questions: final_peer_review_questions,
     };
-    crate::page_history::insert(
+    let history_id = crate::page_history::insert(
         &mut tx,
         PKeyPolicy::Generate,
         page_update.page_id,
@@ -1259,9 +1521,184 @@ RETURNING id,
         peer_review_configs: history_content.peer_review_configs,
         peer_review_questions: history_content.peer_review_questions,
         organization_id,
+        history_id: Some(history_id),
     })
 }
 
+/// Rejects the update if the page has been saved after the version the update is based on. Locks the page until the end of the transaction so that concurrent saves cannot both pass the check.
+async fn check_for_save_conflict(
+    conn: &mut PgConnection,
+    page_id: Uuid,
+    base_history_id: Uuid,
+    cms_page_update: &CmsPageUpdate,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+SELECT id
+FROM pages
+WHERE id = $1
+FOR UPDATE
+",
+        page_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    // each save adds a history entry, so changes to the page that are not saves from the editor,
+    // like reordering pages, don't cause conflicts
+    let latest_history_id = page_history::get_latest_id(&mut *conn, page_id).await?;
+    if latest_history_id.map_or(true, |id| id == base_history_id) {
+        return Ok(());
+    }
+
+    let current = get_page_with_exercises(&mut *conn, page_id).await?;
+    let merged =
+        match page_history::get_by_id_and_page_id(&mut *conn, base_history_id, page_id).await? {
+            Some(ancestor) => merge_page_update(&ancestor, cms_page_update, &current)?,
+            None => None,
+        };
+    Err(ModelError::new(
+        ModelErrorType::PageSaveConflict(Box::new(PageSaveConflict { current, merged })),
+        "The page has been saved by someone else after you started editing it.".to_string(),
+        None,
+    ))
+}
+
+/// Used to merge the exercise data together with the exercise blocks.
+const EXERCISE_DATA_ATTRIBUTE: &str = "exerciseDataForMerge";
+
+#[derive(Serialize, Deserialize)]
+struct ExerciseBlockData {
+    exercise: CmsPageExercise,
+    exercise_slides: Vec<CmsPageExerciseSlide>,
+    exercise_tasks: Vec<CmsPageExerciseTask>,
+}
+
+/// Merges an update that is based on the `ancestor` version of the page with the `current` version of the page.
+/// Returns None if the versions cannot be merged automatically.
+fn merge_page_update(
+    ancestor: &PageHistory,
+    cms_page_update: &CmsPageUpdate,
+    current: &ContentManagementPage,
+) -> ModelResult<Option<CmsPageUpdate>> {
+    // the history doesn't have these, so it's not possible to tell which version changed them
+    if cms_page_update.url_path.trim() != current.page.url_path
+        || cms_page_update.chapter_id != current.page.chapter_id
+    {
+        return Ok(None);
+    }
+    let title = match merge_edits::merge(
+        ancestor.title.trim(),
+        cms_page_update.title.trim(),
+        &current.page.title,
+    ) {
+        Some(title) => title,
+        None => return Ok(None),
+    };
+
+    let ancestor_content: PageHistoryContent = serde_json::from_value(ancestor.content.clone())?;
+    let ancestor_blocks = blocks_with_exercise_data(
+        &ancestor_content.content,
+        &ancestor_content.exercises,
+        &ancestor_content.exercise_slides,
+        &ancestor_content.exercise_tasks,
+    )?;
+    let incoming_blocks = blocks_with_exercise_data(
+        &cms_page_update.content,
+        &cms_page_update.exercises,
+        &cms_page_update.exercise_slides,
+        &cms_page_update.exercise_tasks,
+    )?;
+    let current_blocks = blocks_with_exercise_data(
+        &current.page.content,
+        &current.exercises,
+        &current.exercise_slides,
+        &current.exercise_tasks,
+    )?;
+    let mut merged_blocks =
+        match merge_edits::merge_blocks(&ancestor_blocks, &incoming_blocks, &current_blocks) {
+            Some(merged_blocks) => merged_blocks,
+            None => return Ok(None),
+        };
+
+    let mut exercises = vec![];
+    let mut exercise_slides = vec![];
+    let mut exercise_tasks = vec![];
+    for block in merged_blocks.iter_mut() {
+        if let Some(data) = block.attributes.remove(EXERCISE_DATA_ATTRIBUTE) {
+            let data: ExerciseBlockData = serde_json::from_value(data)?;
+            exercises.push(CmsPageExercise {
+                order_number: exercises.len() as i32,
+                ..data.exercise
+            });
+            exercise_slides.extend(data.exercise_slides);
+            exercise_tasks.extend(data.exercise_tasks);
+        }
+    }
+    Ok(Some(CmsPageUpdate {
+        content: serde_json::to_value(merged_blocks)?,
+        exercises,
+        exercise_slides,
+        exercise_tasks,
+        url_path: current.page.url_path.clone(),
+        title,
+        chapter_id: current.page.chapter_id,
+        base_history_id: current.history_id,
+    }))
+}
+
+/// Adds the data of each exercise to its block so that changes to the exercises are merged like changes to the blocks.
+fn blocks_with_exercise_data(
+    content: &serde_json::Value,
+    exercises: &[CmsPageExercise],
+    exercise_slides: &[CmsPageExerciseSlide],
+    exercise_tasks: &[CmsPageExerciseTask],
+) -> ModelResult<Vec<GutenbergBlock>> {
+    let mut blocks: Vec<GutenbergBlock> = serde_json::from_value(content.clone())?;
+    for block in blocks
+        .iter_mut()
+        .filter(|block| block.name == "moocfi/exercise")
+    {
+        let exercise = block
+            .attributes
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .and_then(|id| exercises.iter().find(|exercise| exercise.id == id));
+        if let Some(exercise) = exercise {
+            let slides = exercise_slides
+                .iter()
+                .filter(|slide| slide.exercise_id == exercise.id)
+                .sorted_by_key(|slide| slide.order_number)
+                .cloned()
+                .collect::<Vec<_>>();
+            let tasks = slides
+                .iter()
+                .flat_map(|slide| {
+                    exercise_tasks
+                        .iter()
+                        .filter(|task| task.exercise_slide_id == slide.id)
+                        .sorted_by_key(|task| task.order_number)
+                })
+                .cloned()
+                .collect();
+            let data = ExerciseBlockData {
+                // the order is determined by the order of the blocks
+                exercise: CmsPageExercise {
+                    order_number: 0,
+                    ..exercise.clone()
+                },
+                exercise_slides: slides,
+                exercise_tasks: tasks,
+            };
+            block.attributes.insert(
+                EXERCISE_DATA_ATTRIBUTE.to_string(),
+                serde_json::to_value(data)?,
+            );
+        }
+    }
+    Ok(blocks)
+}
+
 /// Remaps ids from updates to exercises that may have their ids regenerated.
 async fn upsert_exercises(
     conn: &mut PgConnection,
@@ -1559,6 +1996,10 @@ pub async fn upsert_peer_review_configs(
         peer_reviews_to_receive,
         accepting_strategy,
         accepting_threshold,
+        calibration_samples_to_review,
+        timeout_in_days,
+        timeout_action,
+        manual_review_timeout_in_days,
         deleted_at
       ) ",
         );
@@ -1591,6 +2032,10 @@ pub async fn upsert_peer_review_configs(
                 .push_bind(pr.peer_reviews_to_receive)
                 .push_bind(pr.accepting_strategy)
                 .push_bind(pr.accepting_threshold)
+                .push_bind(pr.calibration_samples_to_review)
+                .push_bind(pr.timeout_in_days)
+                .push_bind(pr.timeout_action)
+                .push_bind(pr.manual_review_timeout_in_days)
                 .push("NULL");
         });
 
@@ -1611,6 +2056,10 @@ SET course_id = excluded.course_id,
   peer_reviews_to_receive = excluded.peer_reviews_to_receive,
   accepting_strategy = excluded.accepting_strategy,
   accepting_threshold = excluded.accepting_threshold,
+  calibration_samples_to_review = excluded.calibration_samples_to_review,
+  timeout_in_days = excluded.timeout_in_days,
+  timeout_action = excluded.timeout_action,
+  manual_review_timeout_in_days = excluded.manual_review_timeout_in_days,
   deleted_at = NULL
 RETURNING id;
 ",
@@ -1633,7 +2082,11 @@ SELECT id as "id!",
   peer_reviews_to_give as "peer_reviews_to_give!",
   peer_reviews_to_receive as "peer_reviews_to_receive!",
   accepting_strategy AS "accepting_strategy!: _",
-  accepting_threshold "accepting_threshold!"
+  accepting_threshold "accepting_threshold!",
+  calibration_samples_to_review as "calibration_samples_to_review!",
+  timeout_in_days as "timeout_in_days!",
+  timeout_action AS "timeout_action!: _",
+  manual_review_timeout_in_days
 FROM peer_review_configs
 WHERE id IN (
     SELECT UNNEST($1::uuid [])
@@ -1684,6 +2137,7 @@ pub async fn upsert_peer_review_questions(
         question,
         question_type,
         answer_required,
+        rubric,
         deleted_at
       ) ",
         );
@@ -1692,22 +2146,23 @@ pub async fn upsert_peer_review_questions(
             .iter()
             .take(1000)
             .map(|prq| {
-                remapped_peer_review_config_ids
+                let peer_review_config_id = remapped_peer_review_config_ids
                     .get(&prq.peer_review_config_id)
-                    .map(|r| (prq, r.id))
+                    .map(|r| r.id)
                     .ok_or_else(|| {
                         ModelError::new(
                             ModelErrorType::Generic,
                             "No peer review found for peer review questions".to_string(),
                             None,
                         )
-                    })
+                    })?;
+                Ok((prq, peer_review_config_id, prq.rubric_to_save()?))
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<ModelResult<Vec<_>>>()?;
 
         sql.push_values(
             peer_review_questions,
-            |mut x, (prq, peer_review_config_id)| {
+            |mut x, (prq, peer_review_config_id, rubric)| {
                 let peer_review_question_exists = existing_peer_review_questions
                     .iter()
                     .any(|id| *id == prq.id);
@@ -1726,6 +2181,7 @@ pub async fn upsert_peer_review_questions(
                     .push_bind(prq.question.as_str())
                     .push_bind(prq.question_type)
                     .push_bind(prq.answer_required)
+                    .push_bind(rubric)
                     .push("NULL");
             },
         );
@@ -1738,6 +2194,7 @@ SET peer_review_config_id = excluded.peer_review_config_id,
     question = excluded.question,
     question_type = excluded.question_type,
     answer_required = excluded.answer_required,
+    rubric = excluded.rubric,
     deleted_at = NULL
 RETURNING id;
 ",
@@ -1759,7 +2216,8 @@ SELECT id AS "id!",
   order_number AS "order_number!",
   peer_review_config_id AS "peer_review_config_id!",
   question AS "question!",
-  question_type AS "question_type!: _"
+  question_type AS "question_type!: _",
+  rubric AS "rubric: _"
 FROM peer_review_questions
 WHERE id IN (
     SELECT UNNEST($1::uuid [])
@@ -1962,7 +2420,9 @@ RETURNING id,
   content,
   order_number,
   copied_from,
-  pages.hidden
+  pages.hidden,
+  pages.publish_at,
+  pages.unpublish_at
           "#,
         new_page.course_id,
         new_page.exam_id,
@@ -1989,6 +2449,7 @@ RETURNING id,
                 url_path: page.url_path,
                 title: page.title,
                 chapter_id: page.chapter_id,
+                base_history_id: None,
             },
             retain_ids: false,
             history_change_reason: HistoryChangeReason::PageSaved,
@@ -2031,6 +2492,8 @@ RETURNING *;
         chapter_id: page.chapter_id,
         copied_from: page.copied_from,
         hidden: page.hidden,
+        publish_at: page.publish_at,
+        unpublish_at: page.unpublish_at,
     })
 }
 
@@ -2057,7 +2520,9 @@ RETURNING id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
           "#,
         page_id,
     )
@@ -2132,7 +2597,9 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages
 WHERE chapter_id = $1
   AND deleted_at IS NULL
@@ -2513,7 +2980,9 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages p
 WHERE p.chapter_id = $1
   AND p.deleted_at IS NULL;
@@ -2545,7 +3014,9 @@ SELECT id,
   content,
   order_number,
   copied_from,
-  hidden
+  hidden,
+  publish_at,
+  unpublish_at
 FROM pages p
 WHERE p.chapter_id = $1
   AND p.deleted_at IS NULL
@@ -2628,6 +3099,14 @@ SELECT id,
 FROM pages
 WHERE course_id = $1
     AND deleted_at IS NULL
+    AND (publish_at IS NULL OR publish_at <= now())
+    AND (unpublish_at IS NULL OR unpublish_at > now())
+    AND NOT EXISTS (
+    SELECT 1
+    FROM chapters
+    WHERE chapters.id = pages.chapter_id
+        AND (chapters.publish_at > now() OR chapters.unpublish_at <= now())
+    )
     AND content_search @@ (
     SELECT query
     from cte
@@ -2712,6 +3191,14 @@ SELECT id,
 FROM pages
 WHERE course_id = $1
     AND deleted_at IS NULL
+    AND (publish_at IS NULL OR publish_at <= now())
+    AND (unpublish_at IS NULL OR unpublish_at > now())
+    AND NOT EXISTS (
+    SELECT 1
+    FROM chapters
+    WHERE chapters.id = pages.chapter_id
+        AND (chapters.publish_at > now() OR chapters.unpublish_at <= now())
+    )
     AND content_search @@ (
     SELECT query
     from cte
@@ -2778,6 +3265,7 @@ pub async fn restore(
                 url_path: page.url_path,
                 title: history_data.title,
                 chapter_id: page.chapter_id,
+                base_history_id: None,
             },
             retain_ids: true,
             history_change_reason: HistoryChangeReason::HistoryRestored,
@@ -3139,6 +3627,65 @@ mod test {
         assert_eq!(org, exam_page_org);
     }
 
+    #[tokio::test]
+    async fn redirections_to_other_courses_do_not_show_drafts() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, chapter: _chapter, :page);
+        insert_data!(tx: tx, user: user, org: org; course: other_course);
+        let published = get_page(tx.as_mut(), page).await.unwrap();
+        crate::url_redirections::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            page,
+            "/moved-page",
+            other_course,
+        )
+        .await
+        .unwrap();
+        crate::page_drafts::upsert(
+            tx.as_mut(),
+            page,
+            user,
+            &crate::page_drafts::PageDraftUpdate {
+                content: CmsPageUpdate {
+                    content: serde_json::json!([]),
+                    exercises: vec![],
+                    exercise_slides: vec![],
+                    exercise_tasks: vec![],
+                    url_path: published.url_path.clone(),
+                    title: "Draft title".to_string(),
+                    chapter_id: published.chapter_id,
+                    base_history_id: None,
+                },
+                publish_at: None,
+            },
+        )
+        .await
+        .unwrap();
+        let course_slug = crate::courses::get_course(tx.as_mut(), course)
+            .await
+            .unwrap()
+            .slug;
+        let other_course_slug = crate::courses::get_course(tx.as_mut(), other_course)
+            .await
+            .unwrap()
+            .slug;
+
+        // the draft version is asked for based on the permissions on the course of the redirection, not the course of the page
+        let res = get_page_with_user_data_by_path(
+            tx.as_mut(),
+            Some(user),
+            &other_course_slug,
+            "/moved-page",
+            PageVersion::Draft,
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.page.id, page);
+        assert_eq!(res.page.title, published.title);
+        assert!(!res.is_draft);
+        assert_eq!(res.redirected_course_slug, Some(course_slug));
+    }
+
     #[tokio::test]
     async fn page_update_validation_works() {
         let e1 = CmsPageExercise {
@@ -3206,7 +3753,110 @@ mod test {
             url_path: "".to_string(),
             title: "".to_string(),
             chapter_id: None,
+            base_history_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_stale_saves_and_merges_edits_to_different_blocks() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+        let first = GutenbergBlock {
+            client_id: Uuid::parse_str("1d0e5ab1-1cb4-4c52-a6a0-0b9e5dd2b4b5").unwrap(),
+            ..GutenbergBlock::paragraph("First.")
+        };
+        let second = GutenbergBlock {
+            client_id: Uuid::parse_str("a8b3fd35-6f5f-48f9-bb2d-7a0a0c2ab1a1").unwrap(),
+            ..GutenbergBlock::paragraph("Second.")
+        };
+        let page_update = |content: Vec<&GutenbergBlock>, base_history_id| PageUpdateArgs {
+            page_id: page,
+            author: user,
+            cms_page_update: CmsPageUpdate {
+                content: serde_json::to_value(content).unwrap(),
+                url_path: "/page".to_string(),
+                title: "Page".to_string(),
+                chapter_id: Some(chapter),
+                base_history_id,
+                ..create_update(vec![], vec![], vec![])
+            },
+            retain_ids: false,
+            history_change_reason: HistoryChangeReason::PageSaved,
+            is_exam_page: false,
+        };
+
+        // the whole test runs in one transaction where all the saves happen at the same time,
+        // so the earlier versions of the page are moved to the past to tell which one is the latest
+        async fn move_history_to_past(conn: &mut PgConnection, page_id: Uuid) {
+            sqlx::query(
+                "UPDATE page_history SET created_at = created_at - interval '1 hour' WHERE page_id = $1",
+            )
+            .bind(page_id)
+            .execute(conn)
+            .await
+            .unwrap();
         }
+        move_history_to_past(tx.as_mut(), page).await;
+        let base = update_page(
+            tx.as_mut(),
+            page_update(vec![&first, &second], None),
+            |_, _, _| unimplemented!(),
+            |_| unimplemented!(),
+        )
+        .await
+        .unwrap();
+        move_history_to_past(tx.as_mut(), page).await;
+
+        // someone else edits the second paragraph
+        let second_edited = GutenbergBlock {
+            client_id: second.client_id,
+            ..GutenbergBlock::paragraph("Second, edited.")
+        };
+        let current = update_page(
+            tx.as_mut(),
+            page_update(vec![&first, &second_edited], None),
+            |_, _, _| unimplemented!(),
+            |_| unimplemented!(),
+        )
+        .await
+        .unwrap();
+
+        // an editor that was opened before the other save edits the first paragraph
+        let first_edited = GutenbergBlock {
+            client_id: first.client_id,
+            ..GutenbergBlock::paragraph("First, edited.")
+        };
+        let err = update_page(
+            tx.as_mut(),
+            page_update(vec![&first_edited, &second], base.history_id),
+            |_, _, _| unimplemented!(),
+            |_| unimplemented!(),
+        )
+        .await
+        .unwrap_err();
+        let conflict = match err.error_type() {
+            ModelErrorType::PageSaveConflict(conflict) => conflict,
+            _ => panic!("Expected a conflict, got {:?}", err),
+        };
+        assert_eq!(conflict.current, current);
+        let merged = conflict.merged.clone().unwrap();
+        assert_eq!(
+            merged.content,
+            serde_json::to_value(vec![&first_edited, &second_edited]).unwrap()
+        );
+        assert_eq!(merged.base_history_id, current.history_id);
+
+        // the merged version can be saved
+        update_page(
+            tx.as_mut(),
+            PageUpdateArgs {
+                cms_page_update: merged,
+                ..page_update(vec![], None)
+            },
+            |_, _, _| unimplemented!(),
+            |_| unimplemented!(),
+        )
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -3225,7 +3875,11 @@ mod test {
             accepting_strategy: crate::peer_review_configs::PeerReviewAcceptingStrategy::AutomaticallyAcceptOrManualReviewByAverage,
             accepting_threshold:0.5,
             peer_reviews_to_give:2,
-            peer_reviews_to_receive:1
+            peer_reviews_to_receive:1,
+            calibration_samples_to_review:0,
+            timeout_in_days:21,
+            timeout_action: crate::peer_review_configs::PeerReviewTimeoutAction::MoveToManualReview,
+            manual_review_timeout_in_days:Some(90),
         };
         let prq = CmsPeerReviewQuestion {
             id: prq_id,
@@ -3234,6 +3888,7 @@ mod test {
             order_number: 0,
             question: "juu".to_string(),
             question_type: crate::peer_review_questions::PeerReviewQuestionType::Essay,
+            rubric: None,
         };
         let mut remapped_exercises = HashMap::new();
         remapped_exercises.insert(exercise_id, exercise);
@@ -3265,7 +3920,11 @@ mod test {
             accepting_strategy: crate::peer_review_configs::PeerReviewAcceptingStrategy::AutomaticallyAcceptOrManualReviewByAverage,
             accepting_threshold:0.5,
             peer_reviews_to_give:2,
-            peer_reviews_to_receive:1
+            peer_reviews_to_receive:1,
+            calibration_samples_to_review:0,
+            timeout_in_days:21,
+            timeout_action: crate::peer_review_configs::PeerReviewTimeoutAction::MoveToManualReview,
+            manual_review_timeout_in_days:Some(90),
         };
         let prq = CmsPeerReviewQuestion {
             id: prq_id,
@@ -3274,6 +3933,7 @@ mod test {
             order_number: 0,
             question: "juu".to_string(),
             question_type: crate::peer_review_questions::PeerReviewQuestionType::Essay,
+            rubric: None,
         };
         let mut remapped_exercises = HashMap::new();
         remapped_exercises.insert(exercise_id, exercise);
@@ -0,0 +1,137 @@
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationAnswer {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub user_id: Uuid,
+    pub exercise_id: Uuid,
+    pub course_instance_id: Uuid,
+    pub peer_review_calibration_sample_id: Uuid,
+    pub peer_review_question_id: Uuid,
+    pub text_data: Option<String>,
+    pub number_data: Option<f32>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn insert(
+    conn: &mut PgConnection,
+    pkey_policy: PKeyPolicy<Uuid>,
+    user_id: Uuid,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+    peer_review_calibration_sample_id: Uuid,
+    peer_review_question_id: Uuid,
+    text_data: Option<String>,
+    number_data: Option<f32>,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO peer_review_calibration_answers (
+    id,
+    user_id,
+    exercise_id,
+    course_instance_id,
+    peer_review_calibration_sample_id,
+    peer_review_question_id,
+    text_data,
+    number_data
+  )
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+RETURNING id
+        ",
+        pkey_policy.into_uuid(),
+        user_id,
+        exercise_id,
+        course_instance_id,
+        peer_review_calibration_sample_id,
+        peer_review_question_id,
+        text_data,
+        number_data,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+/// Returns the exercise slide submission ids of the calibration samples the user has reviewed for the exercise.
+pub async fn get_reviewed_exercise_slide_submission_ids(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+) -> ModelResult<Vec<Uuid>> {
+    let res = sqlx::query!(
+        "
+SELECT DISTINCT prcs.exercise_slide_submission_id
+FROM peer_review_calibration_answers prca
+  JOIN peer_review_calibration_samples prcs ON prcs.id = prca.peer_review_calibration_sample_id
+WHERE prca.user_id = $1
+  AND prca.exercise_id = $2
+  AND prca.course_instance_id = $3
+  AND prca.deleted_at IS NULL
+        ",
+        user_id,
+        exercise_id,
+        course_instance_id,
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|x| x.exercise_slide_submission_id)
+    .collect();
+    Ok(res)
+}
+
+pub async fn count_reviewed_samples_by_user_and_exercise_and_course_instance(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    exercise_id: Uuid,
+    course_instance_id: Uuid,
+) -> ModelResult<i64> {
+    let res = sqlx::query!(
+        "
+SELECT COUNT(DISTINCT peer_review_calibration_sample_id) AS count
+FROM peer_review_calibration_answers
+WHERE user_id = $1
+  AND exercise_id = $2
+  AND course_instance_id = $3
+  AND deleted_at IS NULL
+        ",
+        user_id,
+        exercise_id,
+        course_instance_id,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.count.unwrap_or(0))
+}
+
+pub async fn has_user_reviewed_sample(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    course_instance_id: Uuid,
+    peer_review_calibration_sample_id: Uuid,
+) -> ModelResult<bool> {
+    let res = sqlx::query!(
+        r#"
+SELECT EXISTS (
+    SELECT 1
+    FROM peer_review_calibration_answers
+    WHERE user_id = $1
+      AND course_instance_id = $2
+      AND peer_review_calibration_sample_id = $3
+      AND deleted_at IS NULL
+  ) AS "exists!"
+        "#,
+        user_id,
+        course_instance_id,
+        peer_review_calibration_sample_id,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.exists)
+}
@@ -0,0 +1,95 @@
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationReferenceAnswer {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub peer_review_calibration_sample_id: Uuid,
+    pub peer_review_question_id: Uuid,
+    pub reference_number_data: f32,
+    pub explanation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewPeerReviewCalibrationReferenceAnswer {
+    pub peer_review_question_id: Uuid,
+    pub reference_number_data: f32,
+    pub explanation: Option<String>,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    pkey_policy: PKeyPolicy<Uuid>,
+    peer_review_calibration_sample_id: Uuid,
+    new_reference_answer: &NewPeerReviewCalibrationReferenceAnswer,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO peer_review_calibration_reference_answers (
+    id,
+    peer_review_calibration_sample_id,
+    peer_review_question_id,
+    reference_number_data,
+    explanation
+  )
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id
+        ",
+        pkey_policy.into_uuid(),
+        peer_review_calibration_sample_id,
+        new_reference_answer.peer_review_question_id,
+        new_reference_answer.reference_number_data,
+        new_reference_answer.explanation,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn get_by_peer_review_calibration_sample_id(
+    conn: &mut PgConnection,
+    peer_review_calibration_sample_id: Uuid,
+) -> ModelResult<Vec<PeerReviewCalibrationReferenceAnswer>> {
+    let res = sqlx::query_as!(
+        PeerReviewCalibrationReferenceAnswer,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  deleted_at,
+  peer_review_calibration_sample_id,
+  peer_review_question_id,
+  reference_number_data,
+  explanation
+FROM peer_review_calibration_reference_answers
+WHERE peer_review_calibration_sample_id = $1
+  AND deleted_at IS NULL
+        ",
+        peer_review_calibration_sample_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete_by_peer_review_calibration_sample_id(
+    conn: &mut PgConnection,
+    peer_review_calibration_sample_id: Uuid,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE peer_review_calibration_reference_answers
+SET deleted_at = now()
+WHERE peer_review_calibration_sample_id = $1
+  AND deleted_at IS NULL
+        ",
+        peer_review_calibration_sample_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use crate::{
+    peer_review_calibration_reference_answers::PeerReviewCalibrationReferenceAnswer, prelude::*,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationSample {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub exercise_slide_submission_id: Uuid,
+    pub exercise_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewCalibrationSampleWithReferenceAnswers {
+    pub calibration_sample: PeerReviewCalibrationSample,
+    pub reference_answers: Vec<PeerReviewCalibrationReferenceAnswer>,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    pkey_policy: PKeyPolicy<Uuid>,
+    exercise_slide_submission_id: Uuid,
+    exercise_id: Uuid,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO peer_review_calibration_samples (
+    id,
+    exercise_slide_submission_id,
+    exercise_id
+  )
+VALUES ($1, $2, $3)
+RETURNING id
+        ",
+        pkey_policy.into_uuid(),
+        exercise_slide_submission_id,
+        exercise_id,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn get_by_id(
+    conn: &mut PgConnection,
+    id: Uuid,
+) -> ModelResult<PeerReviewCalibrationSample> {
+    let res = sqlx::query_as!(
+        PeerReviewCalibrationSample,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  deleted_at,
+  exercise_slide_submission_id,
+  exercise_id
+FROM peer_review_calibration_samples
+WHERE id = $1
+  AND deleted_at IS NULL
+        ",
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_exercise_slide_submission_id(
+    conn: &mut PgConnection,
+    exercise_slide_submission_id: Uuid,
+) -> ModelResult<PeerReviewCalibrationSample> {
+    let res = sqlx::query_as!(
+        PeerReviewCalibrationSample,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  deleted_at,
+  exercise_slide_submission_id,
+  exercise_id
+FROM peer_review_calibration_samples
+WHERE exercise_slide_submission_id = $1
+  AND deleted_at IS NULL
+        ",
+        exercise_slide_submission_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Selects a random calibration sample for the exercise that the user has not reviewed yet on the given course instance.
+///
+/// Samples made from the user's own submissions are never returned.
+pub async fn try_to_get_random_unreviewed_by_user(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+    user_id: Uuid,
+    course_instance_id: Uuid,
+) -> ModelResult<Option<PeerReviewCalibrationSample>> {
+    let res = sqlx::query_as!(
+        PeerReviewCalibrationSample,
+        "
+SELECT prcs.id,
+  prcs.created_at,
+  prcs.updated_at,
+  prcs.deleted_at,
+  prcs.exercise_slide_submission_id,
+  prcs.exercise_id
+FROM peer_review_calibration_samples prcs
+  JOIN exercise_slide_submissions ess ON ess.id = prcs.exercise_slide_submission_id
+WHERE prcs.exercise_id = $1
+  AND ess.user_id <> $2
+  AND prcs.deleted_at IS NULL
+  AND ess.deleted_at IS NULL
+  AND NOT EXISTS (
+    SELECT 1
+    FROM peer_review_calibration_answers prca
+    WHERE prca.peer_review_calibration_sample_id = prcs.id
+      AND prca.user_id = $2
+      AND prca.course_instance_id = $3
+      AND prca.deleted_at IS NULL
+  )
+ORDER BY random() ASC
+LIMIT 1
+        ",
+        exercise_id,
+        user_id,
+        course_instance_id,
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE peer_review_calibration_samples
+SET deleted_at = now()
+WHERE id = $1
+  AND deleted_at IS NULL
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -26,6 +26,10 @@ pub struct PeerReviewConfig {
     pub peer_reviews_to_receive: i32,
     pub accepting_threshold: f32,
     pub accepting_strategy: PeerReviewAcceptingStrategy,
+    pub calibration_samples_to_review: i32,
+    pub timeout_in_days: i32,
+    pub timeout_action: PeerReviewTimeoutAction,
+    pub manual_review_timeout_in_days: Option<i32>,
 }
 
 /// Like `PeerReviewConfig` but only the fields it's fine to show to all users.
@@ -49,6 +53,10 @@ pub struct CmsPeerReviewConfig {
     pub peer_reviews_to_receive: i32,
     pub accepting_threshold: f32,
     pub accepting_strategy: PeerReviewAcceptingStrategy,
+    pub calibration_samples_to_review: i32,
+    pub timeout_in_days: i32,
+    pub timeout_action: PeerReviewTimeoutAction,
+    pub manual_review_timeout_in_days: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -76,6 +84,21 @@ pub enum PeerReviewAcceptingStrategy {
     AutomaticallyAcceptOrManualReviewByAverage,
     /// All answers will be sent to be manually reviewed by the teacher once they have received and given enough peer reviews.
     ManualReviewEverything,
+    /// The points are given based on the rubric questions. The average of the received rubric totals is scaled to the maximum score of the exercise. The accepting threshold is not used.
+    AutomaticallyGradeByRubricAverage,
+}
+
+/// Determines what happens to an answer that has waited in the peer review queue for too long without receiving enough peer reviews.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "peer_review_timeout_action", rename_all = "snake_case")]
+pub enum PeerReviewTimeoutAction {
+    /// The answer is moved to be manually reviewed by the teacher. If the teacher has not reviewed the answer within `manual_review_timeout_in_days`, the answer is given full points.
+    MoveToManualReview,
+    /// The answer is given full points.
+    GiveFullPoints,
+    /// The average of the peer review likert answers received so far is compared to the accepting threshold. If the average is greater than the threshold, the answer is given full points, otherwise it is moved to manual review. Answers without any peer reviews are moved to manual review. Like with `MoveToManualReview`, answers in manual review are given full points after `manual_review_timeout_in_days`.
+    AcceptByCurrentAverage,
 }
 
 pub async fn insert(
@@ -113,30 +136,46 @@ pub async fn upsert_with_id(
     peer_reviews_to_give,
     peer_reviews_to_receive,
     accepting_threshold,
-    accepting_strategy
+    accepting_strategy,
+    calibration_samples_to_review,
+    timeout_in_days,
+    timeout_action,
+    manual_review_timeout_in_days
   )
-VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (id) DO
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) ON CONFLICT (id) DO
 UPDATE
 SET course_id = excluded.course_id,
   exercise_id = excluded.exercise_id,
   peer_reviews_to_give = excluded.peer_reviews_to_give,
   peer_reviews_to_receive = excluded.peer_reviews_to_receive,
   accepting_threshold = excluded.accepting_threshold,
-  accepting_strategy = excluded.accepting_strategy
+  accepting_strategy = excluded.accepting_strategy,
+  calibration_samples_to_review = excluded.calibration_samples_to_review,
+  timeout_in_days = excluded.timeout_in_days,
+  timeout_action = excluded.timeout_action,
+  manual_review_timeout_in_days = excluded.manual_review_timeout_in_days
 RETURNING id,
   course_id,
   exercise_id,
   peer_reviews_to_give,
   peer_reviews_to_receive,
   accepting_threshold,
-  accepting_strategy AS "accepting_strategy:_";"#,
+  accepting_strategy AS "accepting_strategy:_",
+  calibration_samples_to_review,
+  timeout_in_days,
+  timeout_action AS "timeout_action: _",
+  manual_review_timeout_in_days;"#,
         cms_peer_review.id,
         cms_peer_review.course_id,
         cms_peer_review.exercise_id,
         cms_peer_review.peer_reviews_to_give,
         cms_peer_review.peer_reviews_to_receive,
         cms_peer_review.accepting_threshold,
-        cms_peer_review.accepting_strategy as _
+        cms_peer_review.accepting_strategy as _,
+        cms_peer_review.calibration_samples_to_review,
+        cms_peer_review.timeout_in_days,
+        cms_peer_review.timeout_action as _,
+        cms_peer_review.manual_review_timeout_in_days,
     )
     .fetch_one(conn)
     .await?;
@@ -156,7 +195,11 @@ SELECT id,
   peer_reviews_to_give,
   peer_reviews_to_receive,
   accepting_threshold,
-  accepting_strategy AS "accepting_strategy: _"
+  accepting_strategy AS "accepting_strategy: _",
+  calibration_samples_to_review,
+  timeout_in_days,
+  timeout_action AS "timeout_action: _",
+  manual_review_timeout_in_days
 FROM peer_review_configs
 WHERE id = $1
   AND deleted_at IS NULL
@@ -185,7 +228,11 @@ SELECT id,
     peer_reviews_to_give,
     peer_reviews_to_receive,
     accepting_threshold,
-    accepting_strategy AS "accepting_strategy: _"
+    accepting_strategy AS "accepting_strategy: _",
+    calibration_samples_to_review,
+    timeout_in_days,
+    timeout_action AS "timeout_action: _",
+    manual_review_timeout_in_days
 FROM peer_review_configs
 WHERE exercise_id = $1
   AND deleted_at IS NULL
@@ -226,7 +273,11 @@ SELECT id,
   peer_reviews_to_give,
   peer_reviews_to_receive,
   accepting_threshold,
-  accepting_strategy AS "accepting_strategy: _"
+  accepting_strategy AS "accepting_strategy: _",
+  calibration_samples_to_review,
+  timeout_in_days,
+  timeout_action AS "timeout_action: _",
+  manual_review_timeout_in_days
 FROM peer_review_configs
 WHERE course_id = $1
   AND exercise_id IS NULL
@@ -324,7 +375,11 @@ SELECT pr.id as id,
   pr.peer_reviews_to_give as peer_reviews_to_give,
   pr.peer_reviews_to_receive as peer_reviews_to_receive,
   pr.accepting_threshold as accepting_threshold,
-  pr.accepting_strategy AS "accepting_strategy: _"
+  pr.accepting_strategy AS "accepting_strategy: _",
+  pr.calibration_samples_to_review,
+  pr.timeout_in_days,
+  pr.timeout_action AS "timeout_action: _",
+  pr.manual_review_timeout_in_days
 from pages p
   join exercises e on p.id = e.page_id
   join peer_review_configs pr on e.id = pr.exercise_id
@@ -376,7 +431,11 @@ SELECT id,
   peer_reviews_to_give,
   peer_reviews_to_receive,
   accepting_threshold,
-  accepting_strategy AS "accepting_strategy: _"
+  accepting_strategy AS "accepting_strategy: _",
+  calibration_samples_to_review,
+  timeout_in_days,
+  timeout_action AS "timeout_action: _",
+  manual_review_timeout_in_days
 FROM peer_review_configs
 where course_id = $1
   AND deleted_at IS NULL;
@@ -401,7 +460,11 @@ SELECT id,
   peer_reviews_to_give,
   peer_reviews_to_receive,
   accepting_threshold,
-  accepting_strategy AS "accepting_strategy:_"
+  accepting_strategy AS "accepting_strategy:_",
+  calibration_samples_to_review,
+  timeout_in_days,
+  timeout_action AS "timeout_action: _",
+  manual_review_timeout_in_days
 FROM peer_review_configs
 WHERE id = $1;
     "#,
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::peer_review_questions::PeerReviewQuestionType;
+use crate::peer_review_questions::{PeerReviewQuestionType, PeerReviewRubric};
 use crate::prelude::*;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -14,6 +14,8 @@ pub struct PeerReviewQuestionSubmission {
     pub peer_review_submission_id: Uuid,
     pub text_data: Option<String>,
     pub number_data: Option<f32>,
+    /// The selected levels if the question is a rubric question.
+    pub rubric_level_ids: Option<Vec<Uuid>>,
 }
 
 pub async fn insert(
@@ -23,6 +25,7 @@ pub async fn insert(
     peer_review_submission_id: Uuid,
     text_data: Option<String>,
     number_data: Option<f32>,
+    rubric_level_ids: Option<Vec<Uuid>>,
 ) -> ModelResult<Uuid> {
     let res = sqlx::query!(
         "
@@ -31,9 +34,10 @@ INSERT INTO peer_review_question_submissions (
     peer_review_question_id,
     peer_review_submission_id,
     text_data,
-    number_data
+    number_data,
+    rubric_level_ids
   )
-VALUES ($1, $2, $3, $4, $5)
+VALUES ($1, $2, $3, $4, $5, $6)
 RETURNING id
         ",
         pkey_policy.into_uuid(),
@@ -41,12 +45,31 @@ RETURNING id
         peer_review_submission_id,
         text_data,
         number_data,
+        rubric_level_ids.as_deref(),
     )
     .fetch_one(conn)
     .await?;
     Ok(res.id)
 }
 
+pub async fn delete_by_peer_review_submission_id(
+    conn: &mut PgConnection,
+    peer_review_submission_id: Uuid,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE peer_review_question_submissions
+SET deleted_at = now()
+WHERE peer_review_submission_id = $1
+  AND deleted_at IS NULL
+        ",
+        peer_review_submission_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
 pub async fn get_by_peer_reviews_question_ids(
     conn: &mut PgConnection,
     ids: &[Uuid],
@@ -63,7 +86,8 @@ pub async fn get_by_peer_reviews_question_ids(
         qs.peer_review_question_id,
         qs.peer_review_submission_id,
         qs.text_data,
-        qs.number_data
+        qs.number_data,
+        qs.rubric_level_ids
     FROM peer_review_question_submissions qs
         JOIN peer_review_submissions s ON (qs.peer_review_submission_id = s.id)
         JOIN exercise_slide_submissions es ON (s.exercise_slide_submission_id = es.id)
@@ -83,6 +107,7 @@ pub async fn get_by_peer_reviews_question_ids(
     Ok(res)
 }
 
+/// Returns the answers of the peer reviews received by the submission. Answers of peer reviews with unresolved flags are not included so that they don't affect the peer review average.
 pub async fn get_received_question_submissions_for_exercise_slide_submission(
     conn: &mut PgConnection,
     exercise_slide_submission_id: Uuid,
@@ -96,6 +121,13 @@ FROM peer_review_submissions prs
 WHERE prs.exercise_slide_submission_id = $1
   AND prs.deleted_at IS NULL
   AND prqs.deleted_at IS NULL
+  AND NOT EXISTS (
+    SELECT 1
+    FROM peer_review_submission_flags prsf
+    WHERE prsf.peer_review_submission_id = prs.id
+      AND prsf.resolved_at IS NULL
+      AND prsf.deleted_at IS NULL
+  )
     ",
         exercise_slide_submission_id
     )
@@ -109,8 +141,27 @@ WHERE prs.exercise_slide_submission_id = $1
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum PeerReviewAnswer {
     NoAnswer,
-    Essay { value: String },
-    Scale { value: f32 },
+    Essay {
+        value: String,
+    },
+    Scale {
+        value: f32,
+    },
+    Rubric {
+        selected_levels: Vec<PeerReviewRubricSelectedLevel>,
+        points: f32,
+        max_points: f32,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewRubricSelectedLevel {
+    pub criterion_id: Uuid,
+    pub criterion_name: String,
+    pub level_id: Uuid,
+    pub level_description: String,
+    pub points: f32,
 }
 
 impl PeerReviewAnswer {
@@ -118,13 +169,46 @@ impl PeerReviewAnswer {
         question_type: PeerReviewQuestionType,
         text_data: Option<String>,
         number_data: Option<f32>,
+        rubric: Option<PeerReviewRubric>,
+        rubric_level_ids: Option<Vec<Uuid>>,
     ) -> Self {
         match (question_type, text_data, number_data) {
             (PeerReviewQuestionType::Essay, Some(value), _) => Self::Essay { value },
             (PeerReviewQuestionType::Scale, _, Some(value)) => Self::Scale { value },
+            (PeerReviewQuestionType::Rubric, _, _) => match (rubric, rubric_level_ids) {
+                (Some(rubric), Some(rubric_level_ids)) => {
+                    Self::new_rubric(rubric, rubric_level_ids)
+                }
+                _ => Self::NoAnswer,
+            },
             _ => Self::NoAnswer,
         }
     }
+
+    fn new_rubric(rubric: PeerReviewRubric, rubric_level_ids: Vec<Uuid>) -> Self {
+        let selected_levels = rubric
+            .criteria
+            .iter()
+            .flat_map(|criterion| {
+                criterion
+                    .levels
+                    .iter()
+                    .filter(|level| rubric_level_ids.contains(&level.id))
+                    .map(|level| PeerReviewRubricSelectedLevel {
+                        criterion_id: criterion.id,
+                        criterion_name: criterion.name.clone(),
+                        level_id: level.id,
+                        level_description: level.description.clone(),
+                        points: level.points,
+                    })
+            })
+            .collect();
+        Self::Rubric {
+            selected_levels,
+            points: rubric.points_for_selected_levels(&rubric_level_ids),
+            max_points: rubric.max_points(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -156,12 +240,14 @@ pub async fn get_questions_and_answers_by_submission_id(
 SELECT answers.id AS peer_review_question_submission_id,
   answers.text_data,
   answers.number_data,
+  answers.rubric_level_ids,
   questions.peer_review_config_id,
   questions.id AS peer_review_question_id,
   questions.order_number,
   questions.question,
   questions.question_type AS "question_type: PeerReviewQuestionType",
   questions.answer_required,
+  questions.rubric AS "rubric: PeerReviewRubric",
   submissions.id AS peer_review_submission_id
 FROM peer_review_question_submissions answers
   JOIN peer_review_questions questions ON (
@@ -184,7 +270,13 @@ WHERE submissions.exercise_slide_submission_id = $1
         peer_review_submission_id: x.peer_review_submission_id,
         order_number: x.order_number,
         question: x.question,
-        answer: PeerReviewAnswer::new(x.question_type, x.text_data, x.number_data),
+        answer: PeerReviewAnswer::new(
+            x.question_type,
+            x.text_data,
+            x.number_data,
+            x.rubric,
+            x.rubric_level_ids,
+        ),
         answer_required: x.answer_required,
     })
     .fetch_all(conn)
@@ -203,12 +295,14 @@ pub async fn get_questions_and_answers_by_user_exercise_instance(
 SELECT answers.id AS peer_review_question_submission_id,
   answers.text_data,
   answers.number_data,
+  answers.rubric_level_ids,
   questions.peer_review_config_id,
   questions.id AS peer_review_question_id,
   questions.order_number,
   questions.question,
   questions.question_type AS "question_type: PeerReviewQuestionType",
   questions.answer_required,
+  questions.rubric AS "rubric: PeerReviewRubric",
   submissions.id AS peer_review_submission_id
 FROM peer_review_question_submissions answers
   JOIN peer_review_questions questions ON (
@@ -235,7 +329,13 @@ WHERE submissions.user_id = $1
         peer_review_submission_id: x.peer_review_submission_id,
         order_number: x.order_number,
         question: x.question,
-        answer: PeerReviewAnswer::new(x.question_type, x.text_data, x.number_data),
+        answer: PeerReviewAnswer::new(
+            x.question_type,
+            x.text_data,
+            x.number_data,
+            x.rubric,
+            x.rubric_level_ids,
+        ),
         answer_required: x.answer_required,
     })
     .fetch_all(conn)
@@ -1,6 +1,12 @@
 use std::collections::HashMap;
 
-use sqlx::{Postgres, QueryBuilder, Row};
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+    types::Json,
+    Decode, Encode, Postgres, QueryBuilder, Row,
+};
 
 use crate::prelude::*;
 
@@ -10,9 +16,90 @@ use crate::prelude::*;
 pub enum PeerReviewQuestionType {
     Essay,
     Scale,
+    /// The reviewer picks one descriptive level for each criterion of the question's rubric.
+    Rubric,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
+/// The criteria of a rubric question. Stored as JSON in `peer_review_questions.rubric`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewRubric {
+    pub criteria: Vec<PeerReviewRubricCriterion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewRubricCriterion {
+    pub id: Uuid,
+    pub name: String,
+    pub levels: Vec<PeerReviewRubricLevel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewRubricLevel {
+    pub id: Uuid,
+    pub description: String,
+    pub points: f32,
+}
+
+impl PeerReviewRubric {
+    /// The points a review gets if the best level is selected for every criterion.
+    pub fn max_points(&self) -> f32 {
+        self.criteria
+            .iter()
+            .map(|criterion| {
+                criterion
+                    .levels
+                    .iter()
+                    .map(|level| level.points)
+                    .fold(0.0, f32::max)
+            })
+            .sum()
+    }
+
+    /// Sums up the points of the selected levels. Ids that don't belong to this rubric are ignored.
+    pub fn points_for_selected_levels(&self, selected_level_ids: &[Uuid]) -> f32 {
+        self.criteria
+            .iter()
+            .flat_map(|criterion| criterion.levels.iter())
+            .filter(|level| selected_level_ids.contains(&level.id))
+            .map(|level| level.points)
+            .sum()
+    }
+
+    /// Returns the criterion the level belongs to.
+    pub fn find_criterion_by_level_id(&self, level_id: Uuid) -> Option<&PeerReviewRubricCriterion> {
+        self.criteria
+            .iter()
+            .find(|criterion| criterion.levels.iter().any(|level| level.id == level_id))
+    }
+}
+
+// The rubric is stored as JSONB, so it is encoded and decoded the same way as `Json<PeerReviewRubric>`.
+impl Type<Postgres> for PeerReviewRubric {
+    fn type_info() -> PgTypeInfo {
+        <Json<Self> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Json<Self> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PeerReviewRubric {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(<Json<Self> as Decode<Postgres>>::decode(value)?.0)
+    }
+}
+
+impl Encode<'_, Postgres> for PeerReviewRubric {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        Json(self).encode_by_ref(buf)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[cfg_attr(feature = "ts_rs", derive(TS))]
 pub struct CmsPeerReviewQuestion {
     pub id: Uuid,
@@ -21,6 +108,23 @@ pub struct CmsPeerReviewQuestion {
     pub question: String,
     pub question_type: PeerReviewQuestionType,
     pub answer_required: bool,
+    /// Only used with rubric questions.
+    pub rubric: Option<PeerReviewRubric>,
+}
+
+impl CmsPeerReviewQuestion {
+    /// The rubric that should be saved with the question. The rubric is dropped from other question types so that changing the question type in the editor does not leave stale data behind.
+    pub fn rubric_to_save(&self) -> ModelResult<Option<&PeerReviewRubric>> {
+        match (self.question_type, &self.rubric) {
+            (PeerReviewQuestionType::Rubric, Some(rubric)) => Ok(Some(rubric)),
+            (PeerReviewQuestionType::Rubric, None) => Err(ModelError::new(
+                ModelErrorType::PreconditionFailed,
+                "Rubric questions need to have a rubric.".to_string(),
+                None,
+            )),
+            _ => Ok(None),
+        }
+    }
 }
 
 impl From<PeerReviewQuestion> for CmsPeerReviewQuestion {
@@ -32,11 +136,12 @@ impl From<PeerReviewQuestion> for CmsPeerReviewQuestion {
             question: prq.question,
             question_type: prq.question_type,
             answer_required: prq.answer_required,
+            rubric: prq.rubric,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[cfg_attr(feature = "ts_rs", derive(TS))]
 pub struct PeerReviewQuestion {
     pub id: Uuid,
@@ -48,6 +153,8 @@ pub struct PeerReviewQuestion {
     pub question: String,
     pub question_type: PeerReviewQuestionType,
     pub answer_required: bool,
+    /// Only used with rubric questions.
+    pub rubric: Option<PeerReviewRubric>,
 }
 
 pub async fn insert(
@@ -62,9 +169,10 @@ INSERT INTO peer_review_questions (
     peer_review_config_id,
     order_number,
     question,
-    question_type
+    question_type,
+    rubric
   )
-VALUES ($1, $2, $3, $4, $5)
+VALUES ($1, $2, $3, $4, $5, $6)
 RETURNING id
         ",
         pkey_policy.into_uuid(),
@@ -72,6 +180,7 @@ RETURNING id
         new_peer_review_question.order_number,
         new_peer_review_question.question,
         new_peer_review_question.question_type as PeerReviewQuestionType,
+        new_peer_review_question.rubric.clone() as _,
     )
     .fetch_one(conn)
     .await?;
@@ -90,7 +199,8 @@ SELECT id,
   order_number,
   question,
   question_type AS "question_type: _",
-  answer_required
+  answer_required,
+  rubric AS "rubric: _"
 FROM peer_review_questions
 WHERE id = $1
   AND deleted_at IS NULL;
@@ -117,7 +227,8 @@ SELECT id,
     order_number,
     question,
     question_type AS "question_type: _",
-    answer_required
+    answer_required,
+    rubric AS "rubric: _"
 FROM peer_review_questions
 WHERE peer_review_config_id = $1
   AND deleted_at IS NULL;
@@ -144,7 +255,8 @@ SELECT id,
     order_number,
     question,
     question_type AS "question_type: _",
-    answer_required
+    answer_required,
+    rubric AS "rubric: _"
 FROM peer_review_questions
 WHERE peer_review_config_id = $1
     AND deleted_at IS NULL;
@@ -180,7 +292,8 @@ SELECT prq.id as id,
   prq.order_number as order_number,
   prq.question as question,
   prq.question_type AS "question_type: _",
-  prq.answer_required as answer_required
+  prq.answer_required as answer_required,
+  prq.rubric AS "rubric: _"
 from pages p
   join exercises e on p.id = e.page_id
   join peer_review_configs pr on e.id = pr.exercise_id
@@ -233,7 +346,8 @@ SELECT id,
   order_number,
   question_type AS "question_type: _",
   question,
-  answer_required
+  answer_required,
+  rubric AS "rubric: _"
 FROM peer_review_questions
 where peer_review_config_id = $1
   AND deleted_at IS NULL;
@@ -250,14 +364,20 @@ pub async fn upsert_multiple_peer_review_questions(
     conn: &mut PgConnection,
     peer_review_questions: &[CmsPeerReviewQuestion],
 ) -> ModelResult<Vec<CmsPeerReviewQuestion>> {
-    let mut sql:QueryBuilder<Postgres> = sqlx::QueryBuilder::new("INSERT INTO peer_review_questions (peer_review_config_id, order_number, question_type, question, answer_required) ");
+    let peer_review_questions = peer_review_questions
+        .iter()
+        .map(|prq| prq.rubric_to_save().map(|rubric| (prq, rubric)))
+        .collect::<ModelResult<Vec<_>>>()?;
+
+    let mut sql:QueryBuilder<Postgres> = sqlx::QueryBuilder::new("INSERT INTO peer_review_questions (peer_review_config_id, order_number, question_type, question, answer_required, rubric) ");
 
-    sql.push_values(peer_review_questions, |mut x, prq| {
+    sql.push_values(peer_review_questions, |mut x, (prq, rubric)| {
         x.push_bind(prq.peer_review_config_id)
             .push_bind(prq.order_number)
             .push_bind(prq.question_type)
             .push_bind(prq.question.as_str())
-            .push_bind(prq.answer_required);
+            .push_bind(prq.answer_required)
+            .push_bind(rubric);
     });
     sql.push(
         r#" ON CONFLICT (id) DO
@@ -266,7 +386,8 @@ SET peer_review_config_id = excluded.peer_review_config_id,
   order_number = excluded.order_number,
   question_type = excluded.question_type,
   question = excluded.question,
-  answer_required = excluded.answer_required
+  answer_required = excluded.answer_required,
+  rubric = excluded.rubric
 RETURNING id;
 "#,
     );
@@ -287,7 +408,8 @@ SELECT id,
   order_number,
   question,
   question_type AS "question_type: _",
-  answer_required
+  answer_required,
+  rubric AS "rubric: _"
 from peer_review_questions
 WHERE id IN (
     SELECT UNNEST($1::uuid [])
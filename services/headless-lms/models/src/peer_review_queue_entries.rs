@@ -1,6 +1,8 @@
 use crate::{
     exercises,
     library::user_exercise_state_updater,
+    peer_review_configs::PeerReviewConfig,
+    peer_review_question_submissions,
     prelude::*,
     teacher_grading_decisions,
     user_exercise_states::{self, ReviewingStage},
@@ -393,6 +395,22 @@ WHERE course_instance_id = $1
     Ok(res)
 }
 
+/// The number of answers in all the peer review queues that are still waiting for peer reviews.
+pub async fn get_count_of_entries_that_need_reviews(conn: &mut PgConnection) -> ModelResult<i64> {
+    let res = sqlx::query!(
+        "
+SELECT COUNT(*) AS count
+FROM peer_review_queue_entries
+WHERE received_enough_peer_reviews = FALSE
+  AND removed_from_queue_for_unusual_reason = FALSE
+  AND deleted_at IS NULL
+"
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.count.unwrap_or(0))
+}
+
 pub async fn remove_from_queue_and_add_to_manual_review(
     conn: &mut PgConnection,
     peer_review_queue_entry: &PeerReviewQueueEntry,
@@ -454,6 +472,66 @@ pub async fn remove_from_queue_and_give_full_points(
     Ok(res)
 }
 
+/// Used when an answer has waited in the peer review queue for too long. Compares the average of the peer review likert answers the answer has received so far to the accepting threshold. If the average is high enough, the answer is given full points, otherwise it is moved to manual review. Answers without any likert answers are moved to manual review.
+pub async fn remove_from_queue_and_accept_by_current_average(
+    conn: &mut PgConnection,
+    peer_review_queue_entry: &PeerReviewQueueEntry,
+    peer_review_config: &PeerReviewConfig,
+) -> ModelResult<PeerReviewQueueEntry> {
+    let received_peer_review_question_submissions =
+        peer_review_question_submissions::get_received_question_submissions_for_exercise_slide_submission(
+            conn,
+            peer_review_queue_entry.receiving_peer_reviews_exercise_slide_submission_id,
+        )
+        .await?;
+    let likert_answers = received_peer_review_question_submissions
+        .iter()
+        .filter_map(|prqs| prqs.number_data)
+        .collect::<Vec<_>>();
+    if likert_answers.is_empty() {
+        info!(peer_review_queue_entry_id = ?peer_review_queue_entry.id, "No likert answers received, moving the answer to manual review");
+        return remove_from_queue_and_add_to_manual_review(conn, peer_review_queue_entry).await;
+    }
+    let avg = likert_answers.iter().sum::<f32>() / likert_answers.len() as f32;
+    if avg < peer_review_config.accepting_threshold {
+        info!(peer_review_queue_entry_id = ?peer_review_queue_entry.id, avg = ?avg, threshold = ?peer_review_config.accepting_threshold, "Average is below the threshold, moving the answer to manual review");
+        remove_from_queue_and_add_to_manual_review(conn, peer_review_queue_entry).await
+    } else {
+        info!(peer_review_queue_entry_id = ?peer_review_queue_entry.id, avg = ?avg, threshold = ?peer_review_config.accepting_threshold, "Average is above the threshold, giving the answer full points");
+        remove_from_queue_and_give_full_points(conn, peer_review_queue_entry).await
+    }
+}
+
+/// Returns the entries that were removed from the queue and moved to manual review because they did not receive enough peer reviews, and that the teacher has not reviewed yet.
+pub async fn get_entries_waiting_for_manual_review_after_timeout_and_older_than(
+    conn: &mut PgConnection,
+    course_instance_id: Uuid,
+    timestamp: DateTime<Utc>,
+) -> ModelResult<Vec<PeerReviewQueueEntry>> {
+    let res = sqlx::query_as!(
+        PeerReviewQueueEntry,
+        "
+SELECT prqe.*
+FROM peer_review_queue_entries prqe
+  JOIN user_exercise_states ues ON ues.user_id = prqe.user_id
+  AND ues.exercise_id = prqe.exercise_id
+  AND ues.course_instance_id = prqe.course_instance_id
+WHERE prqe.course_instance_id = $1
+  AND prqe.received_enough_peer_reviews = FALSE
+  AND prqe.removed_from_queue_for_unusual_reason = TRUE
+  AND prqe.created_at < $2
+  AND prqe.deleted_at IS NULL
+  AND ues.reviewing_stage = 'waiting_for_manual_grading'
+  AND ues.deleted_at IS NULL
+    ",
+        course_instance_id,
+        timestamp
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(res)
+}
+
 async fn remove_from_queue(
     conn: &mut PgConnection,
     peer_review_queue_entry: &PeerReviewQueueEntry,
@@ -493,3 +571,87 @@ AND deleted_at is NULL
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exercise_slide_submissions::{self, NewExerciseSlideSubmission},
+        exercise_task_gradings::UserPointsUpdateStrategy,
+        test_helper::*,
+    };
+
+    #[tokio::test]
+    async fn finds_entries_waiting_for_manual_review_after_timeout() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        let exercise_slide_submission =
+            exercise_slide_submissions::insert_exercise_slide_submission(
+                tx.as_mut(),
+                NewExerciseSlideSubmission {
+                    exercise_slide_id: slide,
+                    course_id: Some(course),
+                    course_instance_id: Some(instance.id),
+                    exam_id: None,
+                    user_id: user,
+                    exercise_id: exercise,
+                    user_points_update_strategy:
+                        UserPointsUpdateStrategy::CanAddPointsAndCanRemovePoints,
+                },
+            )
+            .await
+            .unwrap();
+        user_exercise_states::get_or_create_user_exercise_state(
+            tx.as_mut(),
+            user,
+            exercise,
+            Some(instance.id),
+            None,
+        )
+        .await
+        .unwrap();
+        let id = insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            user,
+            exercise,
+            instance.id,
+            exercise_slide_submission.id,
+            0,
+        )
+        .await
+        .unwrap();
+        let entry = get_by_id(tx.as_mut(), id).await.unwrap();
+        let in_future = Utc::now() + chrono::Duration::days(1);
+
+        let waiting = get_entries_waiting_for_manual_review_after_timeout_and_older_than(
+            tx.as_mut(),
+            instance.id,
+            in_future,
+        )
+        .await
+        .unwrap();
+        assert!(waiting.is_empty());
+
+        remove_from_queue_and_add_to_manual_review(tx.as_mut(), &entry)
+            .await
+            .unwrap();
+        let waiting = get_entries_waiting_for_manual_review_after_timeout_and_older_than(
+            tx.as_mut(),
+            instance.id,
+            in_future,
+        )
+        .await
+        .unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].id, id);
+
+        let waiting = get_entries_waiting_for_manual_review_after_timeout_and_older_than(
+            tx.as_mut(),
+            instance.id,
+            entry.created_at,
+        )
+        .await
+        .unwrap();
+        assert!(waiting.is_empty());
+    }
+}
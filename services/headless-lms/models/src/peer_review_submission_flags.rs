@@ -0,0 +1,186 @@
+use crate::prelude::*;
+
+/// What a teacher decided to do with a flagged peer review.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(
+    type_name = "peer_review_submission_flag_resolution",
+    rename_all = "snake_case"
+)]
+pub enum PeerReviewSubmissionFlagResolution {
+    /// The flag was unfounded. The peer review is counted again.
+    ReviewKept,
+    /// The peer review was inappropriate. It is removed and will not be counted.
+    ReviewRemoved,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewSubmissionFlag {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub peer_review_submission_id: Uuid,
+    pub flagged_by: Uuid,
+    pub reason: String,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+    pub resolution: Option<PeerReviewSubmissionFlagResolution>,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    pkey_policy: PKeyPolicy<Uuid>,
+    peer_review_submission_id: Uuid,
+    flagged_by: Uuid,
+    reason: &str,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO peer_review_submission_flags (
+    id,
+    peer_review_submission_id,
+    flagged_by,
+    reason
+  )
+VALUES ($1, $2, $3, $4)
+RETURNING id
+        ",
+        pkey_policy.into_uuid(),
+        peer_review_submission_id,
+        flagged_by,
+        reason,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<PeerReviewSubmissionFlag> {
+    let res = sqlx::query_as!(
+        PeerReviewSubmissionFlag,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  deleted_at,
+  peer_review_submission_id,
+  flagged_by,
+  reason,
+  resolved_at,
+  resolved_by,
+  resolution AS "resolution: _"
+FROM peer_review_submission_flags
+WHERE id = $1
+  AND deleted_at IS NULL
+        "#,
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the flags the user has given to the peer reviews received for the exercise slide submission.
+pub async fn get_by_exercise_slide_submission_id_and_flagged_by(
+    conn: &mut PgConnection,
+    exercise_slide_submission_id: Uuid,
+    flagged_by: Uuid,
+) -> ModelResult<Vec<PeerReviewSubmissionFlag>> {
+    let res = sqlx::query_as!(
+        PeerReviewSubmissionFlag,
+        r#"
+SELECT prsf.id,
+  prsf.created_at,
+  prsf.updated_at,
+  prsf.deleted_at,
+  prsf.peer_review_submission_id,
+  prsf.flagged_by,
+  prsf.reason,
+  prsf.resolved_at,
+  prsf.resolved_by,
+  prsf.resolution AS "resolution: _"
+FROM peer_review_submission_flags prsf
+  JOIN peer_review_submissions prs ON prs.id = prsf.peer_review_submission_id
+WHERE prs.exercise_slide_submission_id = $1
+  AND prsf.flagged_by = $2
+  AND prs.deleted_at IS NULL
+  AND prsf.deleted_at IS NULL
+        "#,
+        exercise_slide_submission_id,
+        flagged_by,
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the flags on the exercise's peer reviews that are waiting for a teacher to resolve them.
+pub async fn get_unresolved_by_exercise_id(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+) -> ModelResult<Vec<PeerReviewSubmissionFlag>> {
+    let res = sqlx::query_as!(
+        PeerReviewSubmissionFlag,
+        r#"
+SELECT prsf.id,
+  prsf.created_at,
+  prsf.updated_at,
+  prsf.deleted_at,
+  prsf.peer_review_submission_id,
+  prsf.flagged_by,
+  prsf.reason,
+  prsf.resolved_at,
+  prsf.resolved_by,
+  prsf.resolution AS "resolution: _"
+FROM peer_review_submission_flags prsf
+  JOIN peer_review_submissions prs ON prs.id = prsf.peer_review_submission_id
+WHERE prs.exercise_id = $1
+  AND prsf.resolved_at IS NULL
+  AND prs.deleted_at IS NULL
+  AND prsf.deleted_at IS NULL
+ORDER BY prsf.created_at
+        "#,
+        exercise_id,
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn resolve(
+    conn: &mut PgConnection,
+    id: Uuid,
+    resolved_by: Uuid,
+    resolution: PeerReviewSubmissionFlagResolution,
+) -> ModelResult<PeerReviewSubmissionFlag> {
+    let res = sqlx::query_as!(
+        PeerReviewSubmissionFlag,
+        r#"
+UPDATE peer_review_submission_flags
+SET resolved_at = now(),
+  resolved_by = $2,
+  resolution = $3
+WHERE id = $1
+  AND resolved_at IS NULL
+  AND deleted_at IS NULL
+RETURNING id,
+  created_at,
+  updated_at,
+  deleted_at,
+  peer_review_submission_id,
+  flagged_by,
+  reason,
+  resolved_at,
+  resolved_by,
+  resolution AS "resolution: _"
+        "#,
+        id,
+        resolved_by,
+        resolution as PeerReviewSubmissionFlagResolution,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
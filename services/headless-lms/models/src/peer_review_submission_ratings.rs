@@ -0,0 +1,69 @@
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewSubmissionRating {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub peer_review_submission_id: Uuid,
+    pub user_id: Uuid,
+    /// From 1 (not helpful at all) to 5 (very helpful).
+    pub helpfulness: i32,
+}
+
+/// Inserts a rating or updates the existing one if the user has already rated the peer review.
+pub async fn upsert(
+    conn: &mut PgConnection,
+    peer_review_submission_id: Uuid,
+    user_id: Uuid,
+    helpfulness: i32,
+) -> ModelResult<PeerReviewSubmissionRating> {
+    let res = sqlx::query_as!(
+        PeerReviewSubmissionRating,
+        "
+INSERT INTO peer_review_submission_ratings (
+    peer_review_submission_id,
+    user_id,
+    helpfulness
+  )
+VALUES ($1, $2, $3) ON CONFLICT (peer_review_submission_id, user_id)
+WHERE deleted_at IS NULL DO
+UPDATE
+SET helpfulness = excluded.helpfulness
+RETURNING *
+        ",
+        peer_review_submission_id,
+        user_id,
+        helpfulness,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the ratings the user has given to the peer reviews received for the exercise slide submission.
+pub async fn get_by_exercise_slide_submission_id_and_user_id(
+    conn: &mut PgConnection,
+    exercise_slide_submission_id: Uuid,
+    user_id: Uuid,
+) -> ModelResult<Vec<PeerReviewSubmissionRating>> {
+    let res = sqlx::query_as!(
+        PeerReviewSubmissionRating,
+        "
+SELECT prsr.*
+FROM peer_review_submission_ratings prsr
+  JOIN peer_review_submissions prs ON prs.id = prsr.peer_review_submission_id
+WHERE prs.exercise_slide_submission_id = $1
+  AND prsr.user_id = $2
+  AND prs.deleted_at IS NULL
+  AND prsr.deleted_at IS NULL
+        ",
+        exercise_slide_submission_id,
+        user_id,
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
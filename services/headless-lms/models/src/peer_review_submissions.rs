@@ -164,6 +164,7 @@ WHERE user_id = $1
     Ok(res.count.unwrap_or(0).try_into()?)
 }
 
+/// Counts the peer reviews received by the submission. Peer reviews with unresolved flags are not counted.
 pub async fn count_peer_review_submissions_for_exercise_slide_submission(
     conn: &mut PgConnection,
     exercise_slide_submission_id: Uuid,
@@ -171,9 +172,16 @@ pub async fn count_peer_review_submissions_for_exercise_slide_submission(
     let res = sqlx::query!(
         "
 SELECT COUNT(*) AS count
-FROM peer_review_submissions
-WHERE exercise_slide_submission_id = $1
-  AND deleted_at IS NULL
+FROM peer_review_submissions prs
+WHERE prs.exercise_slide_submission_id = $1
+  AND prs.deleted_at IS NULL
+  AND NOT EXISTS (
+    SELECT 1
+    FROM peer_review_submission_flags prsf
+    WHERE prsf.peer_review_submission_id = prs.id
+      AND prsf.resolved_at IS NULL
+      AND prsf.deleted_at IS NULL
+  )
         ",
         exercise_slide_submission_id
     )
@@ -181,3 +189,300 @@ WHERE exercise_slide_submission_id = $1
     .await?;
     Ok(res.count.unwrap_or(0).try_into()?)
 }
+
+pub async fn delete(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE peer_review_submissions
+SET deleted_at = now()
+WHERE id = $1
+  AND deleted_at IS NULL
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PeerReviewReviewerStatistics {
+    pub user_id: Uuid,
+    #[cfg_attr(feature = "ts_rs", ts(type = "number"))]
+    pub peer_reviews_given: i64,
+    #[cfg_attr(feature = "ts_rs", ts(type = "number"))]
+    pub helpfulness_ratings_received: i64,
+    /// Average of the helpfulness ratings the reviewees have given, from 1 to 5. None if no ratings have been given.
+    pub average_helpfulness: Option<f32>,
+    #[cfg_attr(feature = "ts_rs", ts(type = "number"))]
+    pub flags_received: i64,
+    /// How many of the reviewer's peer reviews a teacher has removed after they were flagged.
+    #[cfg_attr(feature = "ts_rs", ts(type = "number"))]
+    pub reviews_removed: i64,
+}
+
+/// Returns statistics about the peer reviews each reviewer has given for the exercise, based on the feedback the reviewees have given.
+pub async fn get_reviewer_statistics_by_exercise_id(
+    conn: &mut PgConnection,
+    exercise_id: Uuid,
+) -> ModelResult<Vec<PeerReviewReviewerStatistics>> {
+    // A peer review can only be rated and flagged once by the student who received it, so the joins don't multiply rows.
+    // Removing a flagged review deletes it, so the flags of deleted reviews are still counted if they led to the removal.
+    let res = sqlx::query_as!(
+        PeerReviewReviewerStatistics,
+        r#"
+SELECT prs.user_id,
+  COUNT(prs.id) FILTER (
+    WHERE prs.deleted_at IS NULL
+  ) AS "peer_reviews_given!",
+  COUNT(prsr.id) AS "helpfulness_ratings_received!",
+  AVG(prsr.helpfulness)::REAL AS average_helpfulness,
+  COUNT(prsf.id) AS "flags_received!",
+  COUNT(prsf.id) FILTER (
+    WHERE prsf.resolution = 'review_removed'
+  ) AS "reviews_removed!"
+FROM peer_review_submissions prs
+  LEFT JOIN peer_review_submission_ratings prsr ON (
+    prsr.peer_review_submission_id = prs.id
+    AND prsr.deleted_at IS NULL
+    AND prs.deleted_at IS NULL
+  )
+  LEFT JOIN peer_review_submission_flags prsf ON (
+    prsf.peer_review_submission_id = prs.id
+    AND prsf.deleted_at IS NULL
+    AND (
+      prs.deleted_at IS NULL
+      OR prsf.resolution = 'review_removed'
+    )
+  )
+WHERE prs.exercise_id = $1
+GROUP BY prs.user_id
+ORDER BY average_helpfulness ASC NULLS LAST,
+  prs.user_id
+        "#,
+        exercise_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exercise_slide_submissions::{self, NewExerciseSlideSubmission},
+        exercise_task_gradings::UserPointsUpdateStrategy,
+        peer_review_configs, peer_review_question_submissions, peer_review_questions,
+        peer_review_submission_flags::{self, PeerReviewSubmissionFlagResolution},
+        peer_review_submission_ratings,
+        test_helper::*,
+        users,
+    };
+
+    /// Inserts a peer review given by a new reviewer to a new submission of the user. Returns the ids of the reviewer, the received submission and the peer review.
+    async fn insert_peer_review(
+        tx: &mut PgConnection,
+        user: Uuid,
+        course: Uuid,
+        instance: Uuid,
+        exercise: Uuid,
+        slide: Uuid,
+    ) -> (Uuid, Uuid, Uuid) {
+        let reviewer = users::insert(
+            &mut *tx,
+            PKeyPolicy::Generate,
+            &format!("{}@example.com", Uuid::new_v4()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let exercise_slide_submission =
+            exercise_slide_submissions::insert_exercise_slide_submission(
+                &mut *tx,
+                NewExerciseSlideSubmission {
+                    exercise_slide_id: slide,
+                    course_id: Some(course),
+                    course_instance_id: Some(instance),
+                    exam_id: None,
+                    user_id: user,
+                    exercise_id: exercise,
+                    user_points_update_strategy:
+                        UserPointsUpdateStrategy::CanAddPointsAndCanRemovePoints,
+                },
+            )
+            .await
+            .unwrap();
+        let peer_review_config =
+            peer_review_configs::get_default_for_course_by_course_id(&mut *tx, course)
+                .await
+                .unwrap();
+        let questions = peer_review_questions::get_all_by_peer_review_config_id(
+            &mut *tx,
+            peer_review_config.id,
+        )
+        .await
+        .unwrap();
+        let peer_review_submission_id = insert(
+            &mut *tx,
+            PKeyPolicy::Generate,
+            reviewer,
+            exercise,
+            instance,
+            peer_review_config.id,
+            exercise_slide_submission.id,
+        )
+        .await
+        .unwrap();
+        peer_review_question_submissions::insert(
+            &mut *tx,
+            PKeyPolicy::Generate,
+            questions[0].id,
+            peer_review_submission_id,
+            Some("Good answer".to_string()),
+            Some(4.0),
+            None,
+        )
+        .await
+        .unwrap();
+        (
+            reviewer,
+            exercise_slide_submission.id,
+            peer_review_submission_id,
+        )
+    }
+
+    /// Returns the number of received peer reviews and received peer review answers that are counted for the submission.
+    async fn count_received(
+        tx: &mut PgConnection,
+        exercise_slide_submission_id: Uuid,
+    ) -> (u32, usize) {
+        let count = count_peer_review_submissions_for_exercise_slide_submission(
+            &mut *tx,
+            exercise_slide_submission_id,
+        )
+        .await
+        .unwrap();
+        let answers = peer_review_question_submissions::get_received_question_submissions_for_exercise_slide_submission(
+            &mut *tx,
+            exercise_slide_submission_id,
+        )
+        .await
+        .unwrap();
+        (count, answers.len())
+    }
+
+    #[tokio::test]
+    async fn excludes_reviews_with_unresolved_flags() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        let (_reviewer, exercise_slide_submission_id, peer_review_submission_id) =
+            insert_peer_review(tx.as_mut(), user, course, instance.id, exercise, slide).await;
+        assert_eq!(
+            count_received(tx.as_mut(), exercise_slide_submission_id).await,
+            (1, 1)
+        );
+
+        let flag_id = peer_review_submission_flags::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            peer_review_submission_id,
+            user,
+            "Not about my answer",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            count_received(tx.as_mut(), exercise_slide_submission_id).await,
+            (0, 0)
+        );
+
+        peer_review_submission_flags::resolve(
+            tx.as_mut(),
+            flag_id,
+            user,
+            PeerReviewSubmissionFlagResolution::ReviewKept,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            count_received(tx.as_mut(), exercise_slide_submission_id).await,
+            (1, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn reviewer_statistics_ignore_feedback_on_deleted_reviews() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        let (reviewer, _exercise_slide_submission_id, peer_review_submission_id) =
+            insert_peer_review(tx.as_mut(), user, course, instance.id, exercise, slide).await;
+        peer_review_submission_ratings::upsert(tx.as_mut(), peer_review_submission_id, user, 2)
+            .await
+            .unwrap();
+        peer_review_submission_flags::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            peer_review_submission_id,
+            user,
+            "Not about my answer",
+        )
+        .await
+        .unwrap();
+
+        let statistics = get_reviewer_statistics_by_exercise_id(tx.as_mut(), exercise)
+            .await
+            .unwrap();
+        assert_eq!(statistics.len(), 1);
+        assert_eq!(statistics[0].user_id, reviewer);
+        assert_eq!(statistics[0].peer_reviews_given, 1);
+        assert_eq!(statistics[0].helpfulness_ratings_received, 1);
+        assert_eq!(statistics[0].flags_received, 1);
+
+        delete(tx.as_mut(), peer_review_submission_id)
+            .await
+            .unwrap();
+        let statistics = get_reviewer_statistics_by_exercise_id(tx.as_mut(), exercise)
+            .await
+            .unwrap();
+        assert_eq!(statistics.len(), 1);
+        assert_eq!(statistics[0].peer_reviews_given, 0);
+        assert_eq!(statistics[0].helpfulness_ratings_received, 0);
+        assert_eq!(statistics[0].average_helpfulness, None);
+        assert_eq!(statistics[0].flags_received, 0);
+
+        // the flags of reviews that a teacher removed are still counted
+        let (reviewer, _exercise_slide_submission_id, peer_review_submission_id) =
+            insert_peer_review(tx.as_mut(), user, course, instance.id, exercise, slide).await;
+        let flag_id = peer_review_submission_flags::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            peer_review_submission_id,
+            user,
+            "Not about my answer",
+        )
+        .await
+        .unwrap();
+        peer_review_submission_flags::resolve(
+            tx.as_mut(),
+            flag_id,
+            user,
+            PeerReviewSubmissionFlagResolution::ReviewRemoved,
+        )
+        .await
+        .unwrap();
+        delete(tx.as_mut(), peer_review_submission_id)
+            .await
+            .unwrap();
+        let statistics = get_reviewer_statistics_by_exercise_id(tx.as_mut(), exercise)
+            .await
+            .unwrap();
+        let statistics = statistics
+            .iter()
+            .find(|statistics| statistics.user_id == reviewer)
+            .unwrap();
+        assert_eq!(statistics.peer_reviews_given, 0);
+        assert_eq!(statistics.flags_received, 1);
+        assert_eq!(statistics.reviews_removed, 1);
+    }
+}
@@ -364,6 +364,7 @@ WHERE id = $1
         url_path: page_with_exercises.page.url_path,
         title: page_with_exercises.page.title,
         chapter_id: page_with_exercises.page.chapter_id,
+        base_history_id: None,
     };
     crate::pages::update_page(
         &mut tx,
@@ -447,6 +448,7 @@ mod test {
             exercises: vec![],
             exercise_slides: vec![],
             exercise_tasks: vec![],
+            base_history_id: None,
         };
         crate::pages::update_page(
             conn,
@@ -0,0 +1,158 @@
+/*!
+Rate limits that are shared by all the server instances.
+
+The policies of a route group are stored in `rate_limit_policies`, and the requests of each user or IP address are counted in fixed time windows in `rate_limit_counters`.
+*/
+
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct RateLimitPolicy {
+    pub id: Uuid,
+    pub route_group: String,
+    pub interval_seconds: i32,
+    pub max_requests: i32,
+}
+
+/// The number of requests made in the current time window of a policy, including the request that was just counted.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct RateLimitCount {
+    pub policy_id: Uuid,
+    pub interval_seconds: i32,
+    pub max_requests: i32,
+    pub request_count: i32,
+    pub window_end: DateTime<Utc>,
+}
+
+impl RateLimitCount {
+    pub fn is_exceeded(&self) -> bool {
+        self.request_count > self.max_requests
+    }
+
+    pub fn remaining(&self) -> i32 {
+        (self.max_requests - self.request_count).max(0)
+    }
+}
+
+pub async fn insert_policy(
+    conn: &mut PgConnection,
+    route_group: &str,
+    interval_seconds: i32,
+    max_requests: i32,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO rate_limit_policies (route_group, interval_seconds, max_requests)
+VALUES ($1, $2, $3)
+RETURNING id
+",
+        route_group,
+        interval_seconds,
+        max_requests
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn get_policies_by_route_group(
+    conn: &mut PgConnection,
+    route_group: &str,
+) -> ModelResult<Vec<RateLimitPolicy>> {
+    let res = sqlx::query_as!(
+        RateLimitPolicy,
+        "
+SELECT id,
+  route_group,
+  interval_seconds,
+  max_requests
+FROM rate_limit_policies
+WHERE route_group = $1
+  AND deleted_at IS NULL
+ORDER BY interval_seconds
+",
+        route_group
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/**
+Counts a request made by `key` to the route group in every policy of the group and returns the updated counts.
+
+The counting is a single atomic upsert, so concurrent requests to different server instances are all counted. The windows are aligned to the interval, and the count is reset when a request is made in a new window.
+*/
+pub async fn count_request(
+    conn: &mut PgConnection,
+    route_group: &str,
+    key: &str,
+) -> ModelResult<Vec<RateLimitCount>> {
+    let res = sqlx::query_as!(
+        RateLimitCount,
+        r#"
+WITH current_windows AS (
+  SELECT id,
+    interval_seconds,
+    to_timestamp(
+      floor(extract(epoch FROM now()) / interval_seconds) * interval_seconds
+    ) AS window_start
+  FROM rate_limit_policies
+  WHERE route_group = $1
+    AND deleted_at IS NULL
+  ORDER BY id
+),
+counters AS (
+  INSERT INTO rate_limit_counters (
+      policy_id,
+      key,
+      window_start,
+      window_end,
+      request_count
+    )
+  SELECT id,
+    $2,
+    window_start,
+    window_start + make_interval(secs => interval_seconds),
+    1
+  FROM current_windows ON CONFLICT (policy_id, key) DO
+  UPDATE
+  SET request_count = CASE
+      WHEN rate_limit_counters.window_start = EXCLUDED.window_start THEN rate_limit_counters.request_count + 1
+      ELSE 1
+    END,
+    window_start = EXCLUDED.window_start,
+    window_end = EXCLUDED.window_end
+  RETURNING policy_id,
+    request_count,
+    window_end
+)
+SELECT counters.policy_id AS "policy_id!",
+  rate_limit_policies.interval_seconds AS "interval_seconds!",
+  rate_limit_policies.max_requests AS "max_requests!",
+  counters.request_count AS "request_count!",
+  counters.window_end AS "window_end!"
+FROM counters
+  JOIN rate_limit_policies ON rate_limit_policies.id = counters.policy_id
+ORDER BY rate_limit_policies.interval_seconds
+"#,
+        route_group,
+        key
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Deletes the counters whose time window has ended. Returns the number of deleted counters.
+pub async fn delete_ended_counters(conn: &mut PgConnection) -> ModelResult<u64> {
+    let res = sqlx::query!(
+        "
+DELETE FROM rate_limit_counters
+WHERE window_end < now()
+"
+    )
+    .execute(conn)
+    .await?;
+    Ok(res.rows_affected())
+}
@@ -185,6 +185,20 @@ WHERE deleted_at IS NULL;
     Ok(res.count.unwrap_or(0))
 }
 
+pub async fn get_uncompleted_count(conn: &mut PgConnection) -> ModelResult<i64> {
+    let res = sqlx::query!(
+        "
+SELECT COUNT(*) as count
+from regradings
+WHERE regrading_completed_at IS NULL
+  AND deleted_at IS NULL;
+"
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.count.unwrap_or(0))
+}
+
 pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<Regrading> {
     let res = sqlx::query_as!(
         Regrading,
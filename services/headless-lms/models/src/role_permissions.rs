@@ -0,0 +1,203 @@
+/*!
+The permissions of the built-in and custom roles.
+
+The server maps its authorization actions and resources to `PermissionAction` and `PermissionResourceType` and checks the permissions of the user's roles against them.
+*/
+
+use crate::{prelude::*, roles::UserRole};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "permission_action", rename_all = "snake_case")]
+pub enum PermissionAction {
+    ViewMaterial,
+    View,
+    Edit,
+    Grade,
+    Teach,
+    Download,
+    Duplicate,
+    DeleteAnswer,
+    EditRole,
+    CreateCoursesOrExams,
+    UsuallyUnacceptableDeletion,
+    UploadFile,
+    Impersonate,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "permission_resource_type", rename_all = "snake_case")]
+pub enum PermissionResourceType {
+    GlobalPermissions,
+    Chapter,
+    Course,
+    CourseInstance,
+    Exam,
+    Exercise,
+    ExerciseSlideSubmission,
+    ExerciseTask,
+    ExerciseTaskGrading,
+    ExerciseTaskSubmission,
+    Organization,
+    Page,
+    StudyRegistry,
+    AnyCourse,
+    Role,
+    User,
+    PlaygroundExample,
+    ExerciseService,
+    MaterialReference,
+}
+
+/// A permission of either a built-in role or a custom role.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct RolePermission {
+    pub id: Uuid,
+    pub built_in_role: Option<UserRole>,
+    pub custom_role_id: Option<Uuid>,
+    pub action: PermissionAction,
+    /// The role that can be given and removed with the `EditRole` action.
+    pub action_role: Option<UserRole>,
+    /// If set, the action is only permitted on resources of this type.
+    pub resource_type: Option<PermissionResourceType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewRolePermission {
+    pub action: PermissionAction,
+    pub action_role: Option<UserRole>,
+    pub resource_type: Option<PermissionResourceType>,
+}
+
+impl NewRolePermission {
+    /// Whether the existing permission allows everything this permission would allow.
+    pub fn is_covered_by(&self, permission: &RolePermission) -> bool {
+        self.action == permission.action
+            && self.action_role == permission.action_role
+            && (permission.resource_type.is_none()
+                || self.resource_type == permission.resource_type)
+    }
+}
+
+/// Returns the permissions of all the built-in roles and the given custom roles.
+pub async fn get_for_authorization(
+    conn: &mut PgConnection,
+    custom_role_ids: &[Uuid],
+) -> ModelResult<Vec<RolePermission>> {
+    let res = sqlx::query_as!(
+        RolePermission,
+        r#"
+SELECT id,
+  built_in_role AS "built_in_role: UserRole",
+  custom_role_id,
+  action AS "action: PermissionAction",
+  action_role AS "action_role: UserRole",
+  resource_type AS "resource_type: PermissionResourceType"
+FROM role_permissions
+WHERE (
+    built_in_role IS NOT NULL
+    OR custom_role_id = ANY($1)
+  )
+  AND deleted_at IS NULL
+"#,
+        custom_role_ids
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_built_in_role(
+    conn: &mut PgConnection,
+    built_in_role: UserRole,
+) -> ModelResult<Vec<RolePermission>> {
+    let res = sqlx::query_as!(
+        RolePermission,
+        r#"
+SELECT id,
+  built_in_role AS "built_in_role: UserRole",
+  custom_role_id,
+  action AS "action: PermissionAction",
+  action_role AS "action_role: UserRole",
+  resource_type AS "resource_type: PermissionResourceType"
+FROM role_permissions
+WHERE built_in_role = $1
+  AND deleted_at IS NULL
+"#,
+        built_in_role as UserRole
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_custom_role_id(
+    conn: &mut PgConnection,
+    custom_role_id: Uuid,
+) -> ModelResult<Vec<RolePermission>> {
+    let res = sqlx::query_as!(
+        RolePermission,
+        r#"
+SELECT id,
+  built_in_role AS "built_in_role: UserRole",
+  custom_role_id,
+  action AS "action: PermissionAction",
+  action_role AS "action_role: UserRole",
+  resource_type AS "resource_type: PermissionResourceType"
+FROM role_permissions
+WHERE custom_role_id = $1
+  AND deleted_at IS NULL
+"#,
+        custom_role_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn insert_for_custom_role(
+    conn: &mut PgConnection,
+    custom_role_id: Uuid,
+    new_role_permission: &NewRolePermission,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO role_permissions (
+    custom_role_id,
+    action,
+    action_role,
+    resource_type
+  )
+VALUES ($1, $2, $3, $4)
+RETURNING id
+",
+        custom_role_id,
+        new_role_permission.action as PermissionAction,
+        new_role_permission.action_role as Option<UserRole>,
+        new_role_permission.resource_type as Option<PermissionResourceType>
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn delete_by_custom_role_id(
+    conn: &mut PgConnection,
+    custom_role_id: Uuid,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE role_permissions
+SET deleted_at = now()
+WHERE custom_role_id = $1
+  AND deleted_at IS NULL
+",
+        custom_role_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
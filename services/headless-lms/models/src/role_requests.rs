@@ -0,0 +1,292 @@
+use crate::{prelude::*, roles::UserRole};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "role_request_status", rename_all = "snake_case")]
+pub enum RoleRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// A user's request for a role on a course.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct RoleRequest {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    pub user_email: String,
+    pub course_id: Uuid,
+    pub course_name: String,
+    pub role: UserRole,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub reason: String,
+    pub status: RoleRequestStatus,
+    pub decided_by_user_id: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewRoleRequest {
+    pub course_id: Uuid,
+    pub role: UserRole,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub reason: String,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    new_role_request: &NewRoleRequest,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO role_requests (
+    user_id,
+    course_id,
+    role,
+    valid_from,
+    valid_until,
+    reason
+  )
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING id
+        ",
+        user_id,
+        new_role_request.course_id,
+        new_role_request.role as UserRole,
+        new_role_request.valid_from,
+        new_role_request.valid_until,
+        new_role_request.reason
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<RoleRequest> {
+    let res = sqlx::query_as!(
+        RoleRequest,
+        r#"
+SELECT role_requests.id,
+  role_requests.created_at,
+  role_requests.user_id,
+  users.email AS user_email,
+  role_requests.course_id,
+  courses.name AS course_name,
+  role_requests.role AS "role: UserRole",
+  role_requests.valid_from,
+  role_requests.valid_until,
+  role_requests.reason,
+  role_requests.status AS "status: RoleRequestStatus",
+  role_requests.decided_by_user_id,
+  role_requests.decided_at
+FROM role_requests
+  JOIN users ON users.id = role_requests.user_id
+  JOIN courses ON courses.id = role_requests.course_id
+WHERE role_requests.id = $1
+  AND role_requests.deleted_at IS NULL
+        "#,
+        id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the user's requests, newest first.
+pub async fn get_by_user_id(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> ModelResult<Vec<RoleRequest>> {
+    let res = sqlx::query_as!(
+        RoleRequest,
+        r#"
+SELECT role_requests.id,
+  role_requests.created_at,
+  role_requests.user_id,
+  users.email AS user_email,
+  role_requests.course_id,
+  courses.name AS course_name,
+  role_requests.role AS "role: UserRole",
+  role_requests.valid_from,
+  role_requests.valid_until,
+  role_requests.reason,
+  role_requests.status AS "status: RoleRequestStatus",
+  role_requests.decided_by_user_id,
+  role_requests.decided_at
+FROM role_requests
+  JOIN users ON users.id = role_requests.user_id
+  JOIN courses ON courses.id = role_requests.course_id
+WHERE role_requests.user_id = $1
+  AND role_requests.deleted_at IS NULL
+ORDER BY role_requests.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the pending requests for the courses of the organization, oldest first.
+pub async fn get_pending_by_organization_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+) -> ModelResult<Vec<RoleRequest>> {
+    let res = sqlx::query_as!(
+        RoleRequest,
+        r#"
+SELECT role_requests.id,
+  role_requests.created_at,
+  role_requests.user_id,
+  users.email AS user_email,
+  role_requests.course_id,
+  courses.name AS course_name,
+  role_requests.role AS "role: UserRole",
+  role_requests.valid_from,
+  role_requests.valid_until,
+  role_requests.reason,
+  role_requests.status AS "status: RoleRequestStatus",
+  role_requests.decided_by_user_id,
+  role_requests.decided_at
+FROM role_requests
+  JOIN users ON users.id = role_requests.user_id
+  JOIN courses ON courses.id = role_requests.course_id
+WHERE courses.organization_id = $1
+  AND role_requests.status = 'pending'
+  AND role_requests.deleted_at IS NULL
+ORDER BY role_requests.created_at
+        "#,
+        organization_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Marks a pending request as approved. The role that was given for the request has to be inserted beforehand.
+pub async fn approve(
+    conn: &mut PgConnection,
+    id: Uuid,
+    decided_by_user_id: Uuid,
+    role_id: Uuid,
+) -> ModelResult<()> {
+    let res = sqlx::query!(
+        "
+UPDATE role_requests
+SET status = 'approved',
+  decided_by_user_id = $2,
+  decided_at = now(),
+  role_id = $3
+WHERE id = $1
+  AND status = 'pending'
+  AND deleted_at IS NULL
+        ",
+        id,
+        decided_by_user_id,
+        role_id
+    )
+    .execute(conn)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The role request has already been decided on.".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+pub async fn reject(
+    conn: &mut PgConnection,
+    id: Uuid,
+    decided_by_user_id: Uuid,
+) -> ModelResult<()> {
+    let res = sqlx::query!(
+        "
+UPDATE role_requests
+SET status = 'rejected',
+  decided_by_user_id = $2,
+  decided_at = now()
+WHERE id = $1
+  AND status = 'pending'
+  AND deleted_at IS NULL
+        ",
+        id,
+        decided_by_user_id
+    )
+    .execute(conn)
+    .await?;
+    if res.rows_affected() == 0 {
+        return Err(ModelError::new(
+            ModelErrorType::PreconditionFailed,
+            "The role request has already been decided on.".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        roles::{self, RoleDomain},
+        test_helper::*,
+    };
+
+    #[tokio::test]
+    async fn approving_gives_a_time_bounded_role() {
+        insert_data!(:tx, :user, :org, :course);
+        let now = Utc::now();
+        let new_role_request = NewRoleRequest {
+            course_id: course,
+            role: UserRole::Assistant,
+            valid_from: None,
+            valid_until: Some(now + Duration::days(120)),
+            reason: "Teaching assistant for the spring semester".to_string(),
+        };
+        let id = insert(tx.as_mut(), user, &new_role_request).await.unwrap();
+        let pending = get_pending_by_organization_id(tx.as_mut(), org)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, id);
+
+        let role_id = roles::insert_with_validity(
+            tx.as_mut(),
+            user,
+            new_role_request.role,
+            RoleDomain::Course(course),
+            new_role_request.valid_from,
+            new_role_request.valid_until,
+        )
+        .await
+        .unwrap();
+        approve(tx.as_mut(), id, user, role_id).await.unwrap();
+        let request = get_by_id(tx.as_mut(), id).await.unwrap();
+        assert_eq!(request.status, RoleRequestStatus::Approved);
+        assert!(get_pending_by_organization_id(tx.as_mut(), org)
+            .await
+            .unwrap()
+            .is_empty());
+        // a request can only be decided on once
+        reject(tx.as_mut(), id, user).await.unwrap_err();
+
+        let user_roles = roles::get_roles(tx.as_mut(), user).await.unwrap();
+        let role = user_roles
+            .iter()
+            .find(|role| role.is_role_for_course(course))
+            .unwrap();
+        assert!(role.is_valid_at(now));
+        assert!(!role.is_valid_at(now + Duration::days(121)));
+    }
+}
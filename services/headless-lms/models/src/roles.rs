@@ -20,9 +20,19 @@ pub struct Role {
     pub course_instance_id: Option<Uuid>,
     pub exam_id: Option<Uuid>,
     pub role: UserRole,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    /// If set, the role has the permissions of the custom role instead of the permissions of `role`.
+    pub custom_role_id: Option<Uuid>,
 }
 
 impl Role {
+    /// Roles with a validity window only grant permissions during the window.
+    pub fn is_valid_at(&self, time: DateTime<Utc>) -> bool {
+        self.valid_from.map(|from| from <= time).unwrap_or(true)
+            && self.valid_until.map(|until| time < until).unwrap_or(true)
+    }
+
     pub fn is_global(&self) -> bool {
         self.is_global
     }
@@ -48,7 +58,7 @@ impl Role {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts_rs", derive(TS))]
 #[serde(tag = "tag", content = "id")]
 pub enum RoleDomain {
@@ -65,6 +75,15 @@ pub struct RoleInfo {
     pub email: String,
     pub role: UserRole,
     pub domain: RoleDomain,
+    /// Only used when adding a role.
+    #[serde(default)]
+    pub valid_from: Option<DateTime<Utc>>,
+    /// Only used when adding a role.
+    #[serde(default)]
+    pub valid_until: Option<DateTime<Utc>>,
+    /// Only used when adding a role. The role is given with the permissions of the custom role, and `role` has to be its base role.
+    #[serde(default)]
+    pub custom_role_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize)]
@@ -75,6 +94,10 @@ pub struct RoleUser {
     pub last_name: Option<String>,
     pub email: String,
     pub role: UserRole,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub custom_role_id: Option<Uuid>,
+    pub custom_role_name: Option<String>,
 }
 
 pub async fn get(conn: &mut PgConnection, domain: RoleDomain) -> ModelResult<Vec<RoleUser>> {
@@ -87,9 +110,14 @@ SELECT users.id AS "id!",
   users.first_name,
   users.last_name,
   email AS "email!",
-  role AS "role!: UserRole"
+  role AS "role!: UserRole",
+  roles.valid_from,
+  roles.valid_until,
+  roles.custom_role_id,
+  custom_roles.name AS "custom_role_name?"
 FROM users
   JOIN roles ON users.id = roles.user_id
+  LEFT JOIN custom_roles ON custom_roles.id = roles.custom_role_id
 WHERE is_global = TRUE
 AND roles.deleted_at IS NULL
 "#,
@@ -105,9 +133,14 @@ SELECT users.id,
   users.first_name,
   users.last_name,
   email,
-  role AS "role: UserRole"
+  role AS "role: UserRole",
+  roles.valid_from,
+  roles.valid_until,
+  roles.custom_role_id,
+  custom_roles.name AS "custom_role_name?"
 FROM users
   JOIN roles ON users.id = roles.user_id
+  LEFT JOIN custom_roles ON custom_roles.id = roles.custom_role_id
 WHERE roles.organization_id = $1
 AND roles.deleted_at IS NULL
 "#,
@@ -124,9 +157,14 @@ SELECT users.id,
   users.first_name,
   users.last_name,
   email,
-  role AS "role: UserRole"
+  role AS "role: UserRole",
+  roles.valid_from,
+  roles.valid_until,
+  roles.custom_role_id,
+  custom_roles.name AS "custom_role_name?"
 FROM users
   JOIN roles ON users.id = roles.user_id
+  LEFT JOIN custom_roles ON custom_roles.id = roles.custom_role_id
 WHERE roles.course_id = $1
 AND roles.deleted_at IS NULL
 "#,
@@ -143,9 +181,14 @@ SELECT users.id,
   users.first_name,
   users.last_name,
   email,
-  role AS "role: UserRole"
+  role AS "role: UserRole",
+  roles.valid_from,
+  roles.valid_until,
+  roles.custom_role_id,
+  custom_roles.name AS "custom_role_name?"
 FROM users
   JOIN roles ON users.id = roles.user_id
+  LEFT JOIN custom_roles ON custom_roles.id = roles.custom_role_id
 WHERE roles.course_instance_id = $1
 AND roles.deleted_at IS NULL
 "#,
@@ -162,9 +205,14 @@ SELECT users.id,
   users.first_name,
   users.last_name,
   email,
-  role AS "role: UserRole"
+  role AS "role: UserRole",
+  roles.valid_from,
+  roles.valid_until,
+  roles.custom_role_id,
+  custom_roles.name AS "custom_role_name?"
 FROM users
   JOIN roles ON users.id = roles.user_id
+  LEFT JOIN custom_roles ON custom_roles.id = roles.custom_role_id
 WHERE roles.exam_id = $1
 AND roles.deleted_at IS NULL
 "#,
@@ -182,17 +230,31 @@ pub async fn insert(
     user_id: Uuid,
     role: UserRole,
     domain: RoleDomain,
+) -> ModelResult<Uuid> {
+    insert_with_validity(conn, user_id, role, domain, None, None).await
+}
+
+/// Inserts a role that is only valid between the given times. A missing bound means that the role is not limited in that direction.
+pub async fn insert_with_validity(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    role: UserRole,
+    domain: RoleDomain,
+    valid_from: Option<DateTime<Utc>>,
+    valid_until: Option<DateTime<Utc>>,
 ) -> ModelResult<Uuid> {
     let id = match domain {
         RoleDomain::Global => {
             sqlx::query!(
                 "
-INSERT INTO roles (user_id, role, is_global)
-VALUES ($1, $2, True)
+INSERT INTO roles (user_id, role, is_global, valid_from, valid_until)
+VALUES ($1, $2, True, $3, $4)
 RETURNING id
 ",
                 user_id,
-                role as UserRole
+                role as UserRole,
+                valid_from,
+                valid_until
             )
             .fetch_one(conn)
             .await?
@@ -201,13 +263,15 @@ RETURNING id
         RoleDomain::Organization(id) => {
             sqlx::query!(
                 "
-INSERT INTO roles (user_id, role, organization_id)
-VALUES ($1, $2, $3)
+INSERT INTO roles (user_id, role, organization_id, valid_from, valid_until)
+VALUES ($1, $2, $3, $4, $5)
 RETURNING id
 ",
                 user_id,
                 role as UserRole,
-                id
+                id,
+                valid_from,
+                valid_until
             )
             .fetch_one(conn)
             .await?
@@ -216,13 +280,15 @@ RETURNING id
         RoleDomain::Course(id) => {
             sqlx::query!(
                 "
-INSERT INTO roles (user_id, role, course_id)
-VALUES ($1, $2, $3)
+INSERT INTO roles (user_id, role, course_id, valid_from, valid_until)
+VALUES ($1, $2, $3, $4, $5)
 RETURNING id
 ",
                 user_id,
                 role as UserRole,
-                id
+                id,
+                valid_from,
+                valid_until
             )
             .fetch_one(conn)
             .await?
@@ -231,13 +297,15 @@ RETURNING id
         RoleDomain::CourseInstance(id) => {
             sqlx::query!(
                 "
-INSERT INTO roles (user_id, role, course_instance_id)
-VALUES ($1, $2, $3)
+INSERT INTO roles (user_id, role, course_instance_id, valid_from, valid_until)
+VALUES ($1, $2, $3, $4, $5)
 RETURNING id
 ",
                 user_id,
                 role as UserRole,
-                id
+                id,
+                valid_from,
+                valid_until
             )
             .fetch_one(conn)
             .await?
@@ -246,13 +314,15 @@ RETURNING id
         RoleDomain::Exam(id) => {
             sqlx::query!(
                 "
-INSERT INTO roles (user_id, role, exam_id)
-VALUES ($1, $2, $3)
+INSERT INTO roles (user_id, role, exam_id, valid_from, valid_until)
+VALUES ($1, $2, $3, $4, $5)
 RETURNING id
 ",
                 user_id,
                 role as UserRole,
-                id
+                id,
+                valid_from,
+                valid_until
             )
             .fetch_one(conn)
             .await?
@@ -262,6 +332,26 @@ RETURNING id
     Ok(id)
 }
 
+/// Gives the role the permissions of the custom role, or the permissions of the built-in role if `custom_role_id` is `None`.
+pub async fn set_custom_role(
+    conn: &mut PgConnection,
+    id: Uuid,
+    custom_role_id: Option<Uuid>,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE roles
+SET custom_role_id = $2
+WHERE id = $1
+",
+        id,
+        custom_role_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
 pub async fn remove(
     conn: &mut PgConnection,
     user_id: Uuid,
@@ -365,7 +455,10 @@ SELECT is_global,
   course_id,
   course_instance_id,
   exam_id,
-  role AS "role: UserRole"
+  role AS "role: UserRole",
+  valid_from,
+  valid_until,
+  custom_role_id
 FROM roles
 WHERE user_id = $1
 AND roles.deleted_at IS NULL
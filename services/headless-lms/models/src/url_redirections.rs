@@ -1,4 +1,83 @@
-use crate::prelude::*;
+/*!
+Redirections from old url paths of a course to the current locations of the pages.
+
+Redirections are created automatically when pages are moved, and teachers can also manage them manually. A redirection leads either to a page, in which case it follows the page when the page is moved again, or to a url path. If there is no page at the url path, the redirections from that path are followed, which forms a chain of redirections. Chains can be flattened so that each redirection leads directly to the page at the end of the chain.
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{courses, prelude::*};
+
+/// Following a redirection gives up after this many redirections, so that cycles do not cause infinite loops.
+pub const MAX_REDIRECTION_HOPS: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct UrlRedirection {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// The course of the old url path.
+    pub course_id: Uuid,
+    pub old_url_path: String,
+    /// The page the redirection leads to. None if the redirection leads to a url path.
+    pub destination_page_id: Option<Uuid>,
+    /// The course of the destination url path if it is another language version of the course.
+    pub destination_course_id: Option<Uuid>,
+    /// The url path the redirection leads to. None if the redirection leads to a page.
+    pub destination_url_path: Option<String>,
+    pub hit_count: i32,
+    pub last_hit_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewUrlRedirection {
+    pub old_url_path: String,
+    /// Either the destination page or the destination url path needs to be given.
+    pub destination_page_id: Option<Uuid>,
+    /// If given, the destination url path is in this course. Needs to be a language version of the course.
+    pub destination_course_id: Option<Uuid>,
+    pub destination_url_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub enum UrlRedirectionProblem {
+    /// The redirection leads to a page only through other redirections.
+    Chain,
+    /// Following the redirection leads back to a redirection that has already been followed.
+    Cycle,
+    /// The redirection leads to a deleted page or to a url path without a page or a redirection.
+    BrokenDestination,
+}
+
+/// Where a redirection leads after following all the redirections after it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct UrlRedirectionResolution {
+    pub page_id: Option<Uuid>,
+    pub course_id: Option<Uuid>,
+    pub url_path: Option<String>,
+    /// How many redirections were followed, including the redirection itself.
+    pub hops: i32,
+    pub problem: Option<UrlRedirectionProblem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct UrlRedirectionWithResolution {
+    pub redirection: UrlRedirection,
+    pub resolution: UrlRedirectionResolution,
+}
+
+/// The location of a page that has not been deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PageLocation {
+    id: Uuid,
+    course_id: Uuid,
+    url_path: String,
+}
 
 pub async fn insert(
     conn: &mut PgConnection,
@@ -22,3 +101,542 @@ RETURNING id
     .await?;
     Ok(res.id)
 }
+
+/// Creates a redirection in the course after checking that the destination is valid.
+pub async fn insert_url_redirection(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    new_redirection: &NewUrlRedirection,
+) -> ModelResult<UrlRedirection> {
+    validate(conn, course_id, new_redirection).await?;
+    let res = sqlx::query_as!(
+        UrlRedirection,
+        "
+INSERT INTO url_redirections (
+    course_id,
+    old_url_path,
+    destination_page_id,
+    destination_course_id,
+    destination_url_path
+  )
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id,
+  created_at,
+  updated_at,
+  course_id,
+  old_url_path,
+  destination_page_id,
+  destination_course_id,
+  destination_url_path,
+  hit_count,
+  last_hit_at
+        ",
+        course_id,
+        new_redirection.old_url_path,
+        new_redirection.destination_page_id,
+        new_redirection.destination_course_id,
+        new_redirection.destination_url_path,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Changes the old url path and the destination of a redirection in the course. The hit count is kept.
+pub async fn update_url_redirection(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    id: Uuid,
+    update: &NewUrlRedirection,
+) -> ModelResult<UrlRedirection> {
+    validate(conn, course_id, update).await?;
+    let res = sqlx::query_as!(
+        UrlRedirection,
+        "
+UPDATE url_redirections
+SET old_url_path = $3,
+  destination_page_id = $4,
+  destination_course_id = $5,
+  destination_url_path = $6
+WHERE id = $1
+  AND course_id = $2
+  AND deleted_at IS NULL
+RETURNING id,
+  created_at,
+  updated_at,
+  course_id,
+  old_url_path,
+  destination_page_id,
+  destination_course_id,
+  destination_url_path,
+  hit_count,
+  last_hit_at
+        ",
+        id,
+        course_id,
+        update.old_url_path,
+        update.destination_page_id,
+        update.destination_course_id,
+        update.destination_url_path,
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete_url_redirection(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    id: Uuid,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE url_redirections
+SET deleted_at = now()
+WHERE id = $1
+  AND course_id = $2
+  AND deleted_at IS NULL
+RETURNING id
+        ",
+        id,
+        course_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+/// Returns the redirections of the course, oldest first.
+pub async fn get_by_course_id(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+) -> ModelResult<Vec<UrlRedirection>> {
+    let res = sqlx::query_as!(
+        UrlRedirection,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  course_id,
+  old_url_path,
+  destination_page_id,
+  destination_course_id,
+  destination_url_path,
+  hit_count,
+  last_hit_at
+FROM url_redirections
+WHERE course_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at
+        ",
+        course_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_old_url_path(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    old_url_path: &str,
+) -> ModelResult<Option<UrlRedirection>> {
+    let res = sqlx::query_as!(
+        UrlRedirection,
+        "
+SELECT id,
+  created_at,
+  updated_at,
+  course_id,
+  old_url_path,
+  destination_page_id,
+  destination_course_id,
+  destination_url_path,
+  hit_count,
+  last_hit_at
+FROM url_redirections
+WHERE course_id = $1
+  AND old_url_path = $2
+  AND deleted_at IS NULL
+        ",
+        course_id,
+        old_url_path
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Increments the hit counts of the redirections that were followed to find a page.
+pub async fn record_hits(conn: &mut PgConnection, ids: &[Uuid]) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE url_redirections
+SET hit_count = hit_count + 1,
+  last_hit_at = now()
+WHERE id = ANY($1)
+        ",
+        ids
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Returns the redirections of the course together with where they lead, so that chains, cycles and broken redirections can be found.
+pub async fn get_by_course_id_with_resolutions(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+) -> ModelResult<Vec<UrlRedirectionWithResolution>> {
+    let course = courses::get_course(conn, course_id).await?;
+    let course_ids = courses::get_all_language_versions_of_course(conn, &course)
+        .await?
+        .into_iter()
+        .map(|c| c.id)
+        .collect::<Vec<_>>();
+    // chains may continue through the other language versions of the course
+    let mut redirections = vec![];
+    for id in course_ids.iter() {
+        redirections.extend(get_by_course_id(conn, *id).await?);
+    }
+    let pages = get_page_locations(conn, &course_ids).await?;
+    let res = resolve_all(&redirections, &pages)
+        .into_iter()
+        .filter(|r| r.redirection.course_id == course_id)
+        .collect();
+    Ok(res)
+}
+
+/// Makes the redirections of the course that lead to a page through other redirections lead directly to the page. Returns the number of changed redirections.
+pub async fn flatten_chains(conn: &mut PgConnection, course_id: Uuid) -> ModelResult<i64> {
+    let resolved = get_by_course_id_with_resolutions(conn, course_id).await?;
+    let mut tx = conn.begin().await?;
+    let mut flattened = 0;
+    for r in resolved {
+        if let (Some(UrlRedirectionProblem::Chain), Some(page_id)) =
+            (r.resolution.problem, r.resolution.page_id)
+        {
+            sqlx::query!(
+                "
+UPDATE url_redirections
+SET destination_page_id = $2,
+  destination_course_id = NULL,
+  destination_url_path = NULL
+WHERE id = $1
+                ",
+                r.redirection.id,
+                page_id
+            )
+            .execute(&mut tx)
+            .await?;
+            flattened += 1;
+        }
+    }
+    tx.commit().await?;
+    Ok(flattened)
+}
+
+async fn get_page_locations(
+    conn: &mut PgConnection,
+    course_ids: &[Uuid],
+) -> ModelResult<Vec<PageLocation>> {
+    let res = sqlx::query!(
+        r#"
+SELECT id,
+  course_id AS "course_id!",
+  url_path
+FROM pages
+WHERE course_id = ANY($1)
+  AND deleted_at IS NULL
+        "#,
+        course_ids
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|r| PageLocation {
+        id: r.id,
+        course_id: r.course_id,
+        url_path: r.url_path,
+    })
+    .collect();
+    Ok(res)
+}
+
+async fn validate(
+    conn: &mut PgConnection,
+    course_id: Uuid,
+    redirection: &NewUrlRedirection,
+) -> ModelResult<()> {
+    let invalid = |message: &str| {
+        Err(ModelError::new(
+            ModelErrorType::InvalidRequest,
+            message.to_string(),
+            None,
+        ))
+    };
+    if !redirection.old_url_path.starts_with('/') {
+        return invalid("The old url path must start with a slash.");
+    }
+    let course = courses::get_course(conn, course_id).await?;
+    let language_versions = courses::get_all_language_versions_of_course(conn, &course).await?;
+    let destination_course_id = match (
+        redirection.destination_page_id,
+        &redirection.destination_url_path,
+    ) {
+        (Some(page_id), None) => {
+            if redirection.destination_course_id.is_some() {
+                return invalid("The destination course is only used with a destination url path.");
+            }
+            let page = crate::pages::get_page(conn, page_id).await?;
+            if page.deleted_at.is_some() {
+                return invalid("The destination page has been deleted.");
+            }
+            page.course_id
+        }
+        (None, Some(url_path)) => {
+            if !url_path.starts_with('/') {
+                return invalid("The destination url path must start with a slash.");
+            }
+            let destination_course_id = redirection.destination_course_id.unwrap_or(course_id);
+            if destination_course_id == course_id && *url_path == redirection.old_url_path {
+                return invalid("A redirection cannot lead to itself.");
+            }
+            Some(destination_course_id)
+        }
+        _ => return invalid("Either a destination page or a destination url path is required."),
+    };
+    let destination_course = language_versions
+        .iter()
+        .find(|c| Some(c.id) == destination_course_id);
+    match destination_course {
+        Some(c) if c.organization_id == course.organization_id => {}
+        _ => {
+            return invalid(
+                "The destination must be in the course or in another language version of the course in the same organization.",
+            )
+        }
+    }
+    let pages = get_page_locations(conn, &[course_id]).await?;
+    if pages.iter().any(|p| p.url_path == redirection.old_url_path) {
+        return invalid(
+            "There is a page at the old url path, so the redirection would never be used.",
+        );
+    }
+    Ok(())
+}
+
+/// Resolves where each of the redirections leads.
+fn resolve_all(
+    redirections: &[UrlRedirection],
+    pages: &[PageLocation],
+) -> Vec<UrlRedirectionWithResolution> {
+    let redirections_by_path = redirections
+        .iter()
+        .map(|r| ((r.course_id, r.old_url_path.as_str()), r))
+        .collect::<HashMap<_, _>>();
+    let pages_by_path = pages
+        .iter()
+        .map(|p| ((p.course_id, p.url_path.as_str()), p))
+        .collect::<HashMap<_, _>>();
+    let pages_by_id = pages.iter().map(|p| (p.id, p)).collect::<HashMap<_, _>>();
+    redirections
+        .iter()
+        .map(|redirection| {
+            let mut visited = HashSet::new();
+            let mut current = redirection;
+            let mut hops = 1;
+            let (page, problem) = loop {
+                visited.insert(current.id);
+                if let Some(page_id) = current.destination_page_id {
+                    break match pages_by_id.get(&page_id) {
+                        Some(page) => (Some(*page), None),
+                        None => (None, Some(UrlRedirectionProblem::BrokenDestination)),
+                    };
+                }
+                let course_id = current.destination_course_id.unwrap_or(current.course_id);
+                let url_path = current.destination_url_path.as_deref().unwrap_or_default();
+                if let Some(page) = pages_by_path.get(&(course_id, url_path)) {
+                    break (Some(*page), None);
+                }
+                match redirections_by_path.get(&(course_id, url_path)) {
+                    Some(next) if visited.contains(&next.id) => {
+                        break (None, Some(UrlRedirectionProblem::Cycle))
+                    }
+                    Some(next) if hops < MAX_REDIRECTION_HOPS as i32 => {
+                        current = *next;
+                        hops += 1;
+                    }
+                    _ => break (None, Some(UrlRedirectionProblem::BrokenDestination)),
+                }
+            };
+            let problem = match (page, problem) {
+                (Some(_), None) if hops > 1 => Some(UrlRedirectionProblem::Chain),
+                (_, problem) => problem,
+            };
+            UrlRedirectionWithResolution {
+                redirection: redirection.clone(),
+                resolution: UrlRedirectionResolution {
+                    page_id: page.map(|p| p.id),
+                    course_id: page.map(|p| p.course_id),
+                    url_path: page.map(|p| p.url_path.clone()),
+                    hops,
+                    problem,
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn redirection(
+        course_id: Uuid,
+        old_url_path: &str,
+        destination_page_id: Option<Uuid>,
+        destination_url_path: Option<&str>,
+    ) -> UrlRedirection {
+        UrlRedirection {
+            id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            course_id,
+            old_url_path: old_url_path.to_string(),
+            destination_page_id,
+            destination_course_id: None,
+            destination_url_path: destination_url_path.map(|p| p.to_string()),
+            hit_count: 0,
+            last_hit_at: None,
+        }
+    }
+
+    #[test]
+    fn resolves_chains_cycles_and_broken_redirections() {
+        let course_id = Uuid::new_v4();
+        let page = PageLocation {
+            id: Uuid::new_v4(),
+            course_id,
+            url_path: "/chapter-1/page".to_string(),
+        };
+        let redirections = vec![
+            redirection(course_id, "/direct", Some(page.id), None),
+            redirection(course_id, "/to-path", None, Some("/chapter-1/page")),
+            redirection(course_id, "/chain", None, Some("/direct")),
+            redirection(course_id, "/cycle-1", None, Some("/cycle-2")),
+            redirection(course_id, "/cycle-2", None, Some("/cycle-1")),
+            redirection(course_id, "/broken", None, Some("/nowhere")),
+            redirection(course_id, "/deleted", Some(Uuid::new_v4()), None),
+        ];
+        let resolved = resolve_all(&redirections, &[page.clone()]);
+        let summary = resolved
+            .iter()
+            .map(|r| {
+                (
+                    r.redirection.old_url_path.as_str(),
+                    r.resolution.page_id,
+                    r.resolution.hops,
+                    r.resolution.problem,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("/direct", Some(page.id), 1, None),
+                ("/to-path", Some(page.id), 1, None),
+                (
+                    "/chain",
+                    Some(page.id),
+                    2,
+                    Some(UrlRedirectionProblem::Chain)
+                ),
+                ("/cycle-1", None, 2, Some(UrlRedirectionProblem::Cycle)),
+                ("/cycle-2", None, 2, Some(UrlRedirectionProblem::Cycle)),
+                (
+                    "/broken",
+                    None,
+                    1,
+                    Some(UrlRedirectionProblem::BrokenDestination)
+                ),
+                (
+                    "/deleted",
+                    None,
+                    1,
+                    Some(UrlRedirectionProblem::BrokenDestination)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn flattens_chains() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+        let page_url_path = crate::pages::get_page(tx.as_mut(), page)
+            .await
+            .unwrap()
+            .url_path;
+
+        insert_url_redirection(
+            tx.as_mut(),
+            course,
+            &NewUrlRedirection {
+                old_url_path: "/old".to_string(),
+                destination_page_id: None,
+                destination_course_id: None,
+                destination_url_path: Some(page_url_path),
+            },
+        )
+        .await
+        .unwrap();
+        insert_url_redirection(
+            tx.as_mut(),
+            course,
+            &NewUrlRedirection {
+                old_url_path: "/older".to_string(),
+                destination_page_id: None,
+                destination_course_id: None,
+                destination_url_path: Some("/old".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        let resolved = get_by_course_id_with_resolutions(tx.as_mut(), course)
+            .await
+            .unwrap();
+        let older = resolved
+            .iter()
+            .find(|r| r.redirection.old_url_path == "/older")
+            .unwrap();
+        assert_eq!(older.resolution.problem, Some(UrlRedirectionProblem::Chain));
+        assert_eq!(older.resolution.page_id, Some(page));
+
+        assert_eq!(flatten_chains(tx.as_mut(), course).await.unwrap(), 1);
+        let older = get_by_old_url_path(tx.as_mut(), course, "/older")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(older.destination_page_id, Some(page));
+        assert_eq!(older.destination_url_path, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_redirections_to_themselves() {
+        insert_data!(:tx, :user, :org, :course);
+
+        let res = insert_url_redirection(
+            tx.as_mut(),
+            course,
+            &NewUrlRedirection {
+                old_url_path: "/old".to_string(),
+                destination_page_id: None,
+                destination_course_id: None,
+                destination_url_path: Some("/old".to_string()),
+            },
+        )
+        .await;
+        assert!(res.is_err());
+    }
+}
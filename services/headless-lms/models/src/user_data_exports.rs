@@ -0,0 +1,162 @@
+use crate::prelude::*;
+
+/// A user's request for a copy of their data. The file can be downloaded after the export has been completed and before it expires.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct UserDataExport {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A completed export whose file should be deleted.
+#[derive(Debug, Clone)]
+pub struct ExpiredUserDataExport {
+    pub id: Uuid,
+    pub file_path: String,
+}
+
+pub async fn insert(conn: &mut PgConnection, user_id: Uuid) -> ModelResult<UserDataExport> {
+    let res = sqlx::query_as!(
+        UserDataExport,
+        "
+INSERT INTO user_data_exports (user_id)
+VALUES ($1)
+RETURNING id,
+  created_at,
+  user_id,
+  completed_at,
+  expires_at
+        ",
+        user_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the user's exports that have not been deleted, newest first.
+pub async fn get_by_user_id(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> ModelResult<Vec<UserDataExport>> {
+    let res = sqlx::query_as!(
+        UserDataExport,
+        "
+SELECT id,
+  created_at,
+  user_id,
+  completed_at,
+  expires_at
+FROM user_data_exports
+WHERE user_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at DESC
+        ",
+        user_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Returns the exports that have not been generated yet, oldest first.
+pub async fn get_pending(conn: &mut PgConnection) -> ModelResult<Vec<UserDataExport>> {
+    let res = sqlx::query_as!(
+        UserDataExport,
+        "
+SELECT id,
+  created_at,
+  user_id,
+  completed_at,
+  expires_at
+FROM user_data_exports
+WHERE completed_at IS NULL
+  AND deleted_at IS NULL
+ORDER BY created_at
+        "
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn mark_completed(
+    conn: &mut PgConnection,
+    id: Uuid,
+    file_path: &str,
+    expires_at: DateTime<Utc>,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE user_data_exports
+SET file_path = $2,
+  completed_at = now(),
+  expires_at = $3
+WHERE id = $1
+        ",
+        id,
+        file_path,
+        expires_at
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Returns the path of the export's file if the export belongs to the user, has been completed and has not expired.
+pub async fn get_downloadable_file_path(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    id: Uuid,
+) -> ModelResult<String> {
+    let res = sqlx::query!(
+        r#"
+SELECT file_path AS "file_path!"
+FROM user_data_exports
+WHERE id = $1
+  AND user_id = $2
+  AND file_path IS NOT NULL
+  AND expires_at > now()
+  AND deleted_at IS NULL
+        "#,
+        id,
+        user_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.file_path)
+}
+
+pub async fn get_expired(conn: &mut PgConnection) -> ModelResult<Vec<ExpiredUserDataExport>> {
+    let res = sqlx::query_as!(
+        ExpiredUserDataExport,
+        r#"
+SELECT id,
+  file_path AS "file_path!"
+FROM user_data_exports
+WHERE file_path IS NOT NULL
+  AND expires_at <= now()
+  AND deleted_at IS NULL
+        "#
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE user_data_exports
+SET deleted_at = now()
+WHERE id = $1
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -941,6 +941,18 @@ pub struct CourseInstanceUserPointsInner {
     pub points_for_chapter: f32,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CourseInstanceUserExercisePoints {
+    pub user_id: Uuid,
+    pub points_for_exercise: Vec<CourseInstanceUserExercisePointsInner>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CourseInstanceUserExercisePointsInner {
+    pub exercise_id: Uuid,
+    pub score_given: f32,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct ExamUserPoints {
     pub user_id: Uuid,
@@ -998,6 +1010,55 @@ GROUP BY user_id
     .fetch(conn)
 }
 
+/// Streams the points of each user enrolled on the course instance for each exercise they have attempted, sorted by name.
+pub fn stream_course_instance_exercise_points(
+    conn: &mut PgConnection,
+    course_instance_id: Uuid,
+) -> impl Stream<Item = sqlx::Result<CourseInstanceUserExercisePoints>> + '_ {
+    sqlx::query!(
+        r#"
+SELECT cie.user_id,
+  COALESCE(
+    (
+      SELECT jsonb_agg(
+          jsonb_build_object(
+            'exercise_id',
+            ues.exercise_id,
+            'score_given',
+            COALESCE(ues.score_given, 0)
+          )
+        )
+      FROM user_exercise_states ues
+        JOIN exercises e ON e.id = ues.exercise_id
+      WHERE ues.user_id = cie.user_id
+        AND ues.course_instance_id = cie.course_instance_id
+        AND ues.deleted_at IS NULL
+        AND e.deleted_at IS NULL
+    ),
+    '[]'::jsonb
+  ) AS "points_for_exercises!"
+FROM course_instance_enrollments cie
+  JOIN users u ON u.id = cie.user_id
+WHERE cie.course_instance_id = $1
+  AND cie.deleted_at IS NULL
+ORDER BY u.last_name NULLS FIRST,
+  u.first_name NULLS FIRST,
+  u.email
+"#,
+        course_instance_id
+    )
+    .try_map(|i| {
+        let user_id = i.user_id;
+        serde_json::from_value(i.points_for_exercises)
+            .map(|points_for_exercise| CourseInstanceUserExercisePoints {
+                user_id,
+                points_for_exercise,
+            })
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+    })
+    .fetch(conn)
+}
+
 pub fn stream_exam_points(
     conn: &mut PgConnection,
     exam_id: Uuid,
@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+/// Links a user to their account in an identity provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserIdentity {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub user_id: Uuid,
+    pub identity_provider_id: Uuid,
+    pub subject: String,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    identity_provider_id: Uuid,
+    subject: &str,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO user_identities (user_id, identity_provider_id, subject)
+VALUES ($1, $2, $3)
+RETURNING id
+        ",
+        user_id,
+        identity_provider_id,
+        subject
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+/// Returns the id of the user that has been linked to the subject of the identity provider, if any.
+pub async fn try_get_user_id_by_subject(
+    conn: &mut PgConnection,
+    identity_provider_id: Uuid,
+    subject: &str,
+) -> ModelResult<Option<Uuid>> {
+    let res = sqlx::query!(
+        "
+SELECT user_id
+FROM user_identities
+WHERE identity_provider_id = $1
+  AND subject = $2
+  AND deleted_at IS NULL
+        ",
+        identity_provider_id,
+        subject
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(res.map(|r| r.user_id))
+}
+
+pub async fn get_by_user_id(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> ModelResult<Vec<UserIdentity>> {
+    let res = sqlx::query_as!(
+        UserIdentity,
+        "
+SELECT *
+FROM user_identities
+WHERE user_id = $1
+  AND deleted_at IS NULL
+        ",
+        user_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
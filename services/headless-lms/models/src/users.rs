@@ -95,6 +95,54 @@ WHERE email = $1
     Ok(user)
 }
 
+/// Whether the user has enrollments or roles in the organization, and none outside it. Global roles count as being outside the organization.
+pub async fn is_only_in_organization(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    organization_id: Uuid,
+) -> ModelResult<bool> {
+    let res = sqlx::query!(
+        r#"
+WITH user_organizations AS (
+  SELECT c.organization_id
+  FROM course_instance_enrollments e
+    JOIN courses c ON c.id = e.course_id
+  WHERE e.user_id = $1
+    AND e.deleted_at IS NULL
+  UNION ALL
+  SELECT COALESCE(
+      r.organization_id,
+      c.organization_id,
+      ic.organization_id,
+      ex.organization_id
+    ) AS organization_id
+  FROM roles r
+    LEFT JOIN courses c ON c.id = r.course_id
+    LEFT JOIN course_instances ci ON ci.id = r.course_instance_id
+    LEFT JOIN courses ic ON ic.id = ci.course_id
+    LEFT JOIN exams ex ON ex.id = r.exam_id
+  WHERE r.user_id = $1
+    AND r.deleted_at IS NULL
+)
+SELECT EXISTS (
+    SELECT 1
+    FROM user_organizations
+    WHERE organization_id = $2
+  )
+  AND NOT EXISTS (
+    SELECT 1
+    FROM user_organizations
+    WHERE organization_id IS DISTINCT FROM $2
+  ) AS "only_in_organization!"
+        "#,
+        user_id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.only_in_organization)
+}
+
 pub async fn get_by_id(conn: &mut PgConnection, id: Uuid) -> ModelResult<User> {
     let user = sqlx::query_as!(
         User,
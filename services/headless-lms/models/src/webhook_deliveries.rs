@@ -0,0 +1,433 @@
+use chrono::Duration;
+
+use crate::{prelude::*, webhook_subscriptions::WebhookEventType};
+
+/// How many times the delivery of an event is attempted before it is marked as failed.
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub webhook_subscription_id: Uuid,
+    pub event_type: WebhookEventType,
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// The data sent to the subscribers of an event. Serialized as the `data` of the webhook request.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum WebhookEvent {
+    EnrollmentCreated {
+        user_id: Uuid,
+        course_id: Uuid,
+        course_instance_id: Uuid,
+    },
+    ModuleCompletionGranted {
+        user_id: Uuid,
+        course_id: Uuid,
+        course_module_id: Uuid,
+        course_module_completion_id: Uuid,
+        passed: bool,
+        grade: Option<i32>,
+    },
+    ExamEnded {
+        exam_id: Uuid,
+    },
+    SubmissionGraded {
+        user_id: Uuid,
+        course_id: Option<Uuid>,
+        exam_id: Option<Uuid>,
+        exercise_id: Uuid,
+        exercise_task_submission_id: Uuid,
+        score_given: Option<f32>,
+    },
+    PeerReviewReceived {
+        user_id: Uuid,
+        course_id: Uuid,
+        exercise_id: Uuid,
+        exercise_slide_submission_id: Uuid,
+        peer_review_submission_id: Uuid,
+    },
+}
+
+impl WebhookEvent {
+    pub fn event_type(&self) -> WebhookEventType {
+        match self {
+            Self::EnrollmentCreated { .. } => WebhookEventType::EnrollmentCreated,
+            Self::ModuleCompletionGranted { .. } => WebhookEventType::ModuleCompletionGranted,
+            Self::ExamEnded { .. } => WebhookEventType::ExamEnded,
+            Self::SubmissionGraded { .. } => WebhookEventType::SubmissionGraded,
+            Self::PeerReviewReceived { .. } => WebhookEventType::PeerReviewReceived,
+        }
+    }
+}
+
+/// Queues the event for delivery to all enabled subscriptions of the organization that owns the course or exam.
+///
+/// Should be called in the same transaction as the change that caused the event so that events are not sent for changes that were rolled back.
+pub async fn insert_for_event(
+    conn: &mut PgConnection,
+    course_or_exam_id: CourseOrExamId,
+    event: &WebhookEvent,
+) -> ModelResult<u64> {
+    let (course_id, exam_id) = course_or_exam_id.to_course_and_exam_ids();
+    let payload = serde_json::to_value(event)?;
+    let res = sqlx::query!(
+        "
+INSERT INTO webhook_deliveries (webhook_subscription_id, event_type, payload)
+SELECT ws.id,
+  $3,
+  $4
+FROM webhook_subscriptions ws
+WHERE ws.organization_id IN (
+    SELECT organization_id
+    FROM courses
+    WHERE id = $1
+    UNION
+    SELECT organization_id
+    FROM exams
+    WHERE id = $2
+  )
+  AND $3 = ANY(ws.event_types)
+  AND ws.enabled
+  AND ws.deleted_at IS NULL
+        ",
+        course_id,
+        exam_id,
+        event.event_type() as WebhookEventType,
+        payload
+    )
+    .execute(conn)
+    .await?;
+    Ok(res.rows_affected())
+}
+
+/// A delivery that is due, with the subscription details needed to send it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingWebhookDelivery {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub event_type: WebhookEventType,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+pub async fn get_pending(
+    conn: &mut PgConnection,
+    limit: i64,
+) -> ModelResult<Vec<PendingWebhookDelivery>> {
+    let res = sqlx::query_as!(
+        PendingWebhookDelivery,
+        r#"
+SELECT wd.id,
+  wd.created_at,
+  wd.event_type AS "event_type: WebhookEventType",
+  wd.payload,
+  wd.attempts,
+  ws.url,
+  ws.secret
+FROM webhook_deliveries wd
+  JOIN webhook_subscriptions ws ON ws.id = wd.webhook_subscription_id
+WHERE wd.status = 'pending'
+  AND wd.next_attempt_at <= now()
+  AND wd.deleted_at IS NULL
+  AND ws.enabled
+  AND ws.deleted_at IS NULL
+ORDER BY wd.next_attempt_at
+LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn mark_delivered(conn: &mut PgConnection, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE webhook_deliveries
+SET status = 'delivered',
+  attempts = attempts + 1,
+  delivered_at = now()
+WHERE id = $1
+        ",
+        id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Records a failed attempt. The delivery is retried at `next_attempt_at`, or marked as failed if there are no attempts left.
+pub async fn mark_attempt_failed(
+    conn: &mut PgConnection,
+    id: Uuid,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE webhook_deliveries
+SET attempts = attempts + 1,
+  status = CASE
+    WHEN $2::timestamptz IS NULL THEN 'failed'::webhook_delivery_status
+    ELSE 'pending'::webhook_delivery_status
+  END,
+  next_attempt_at = COALESCE($2, next_attempt_at)
+WHERE id = $1
+        ",
+        id,
+        next_attempt_at
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// When to retry a delivery after `attempts_made` failed attempts. The wait doubles after each attempt, starting from one minute.
+/// Returns None when there are no attempts left.
+pub fn next_attempt_at(attempts_made: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if attempts_made >= MAX_DELIVERY_ATTEMPTS {
+        return None;
+    }
+    let exponent = (attempts_made - 1).max(0) as u32;
+    Some(now + Duration::minutes(2_i64.pow(exponent)))
+}
+
+/// Queues a delivery to be sent again as soon as possible, regardless of its earlier attempts.
+pub async fn redeliver(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    id: Uuid,
+) -> ModelResult<WebhookDelivery> {
+    let res = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+UPDATE webhook_deliveries wd
+SET status = 'pending',
+  attempts = 0,
+  next_attempt_at = now(),
+  delivered_at = NULL
+FROM webhook_subscriptions ws
+WHERE wd.id = $1
+  AND ws.id = wd.webhook_subscription_id
+  AND ws.organization_id = $2
+  AND wd.deleted_at IS NULL
+  AND ws.deleted_at IS NULL
+RETURNING wd.id,
+  wd.created_at,
+  wd.updated_at,
+  wd.webhook_subscription_id,
+  wd.event_type AS "event_type: WebhookEventType",
+  wd.payload,
+  wd.status AS "status: WebhookDeliveryStatus",
+  wd.attempts,
+  wd.next_attempt_at,
+  wd.delivered_at
+        "#,
+        id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    id: Uuid,
+) -> ModelResult<WebhookDelivery> {
+    let res = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+SELECT wd.id,
+  wd.created_at,
+  wd.updated_at,
+  wd.webhook_subscription_id,
+  wd.event_type AS "event_type: WebhookEventType",
+  wd.payload,
+  wd.status AS "status: WebhookDeliveryStatus",
+  wd.attempts,
+  wd.next_attempt_at,
+  wd.delivered_at
+FROM webhook_deliveries wd
+  JOIN webhook_subscriptions ws ON ws.id = wd.webhook_subscription_id
+WHERE wd.id = $1
+  AND ws.organization_id = $2
+  AND wd.deleted_at IS NULL
+  AND ws.deleted_at IS NULL
+        "#,
+        id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Gets the deliveries of a subscription, newest first.
+pub async fn get_by_subscription_id_paginated(
+    conn: &mut PgConnection,
+    webhook_subscription_id: Uuid,
+    pagination: Pagination,
+) -> ModelResult<Vec<WebhookDelivery>> {
+    let res = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  webhook_subscription_id,
+  event_type AS "event_type: WebhookEventType",
+  payload,
+  status AS "status: WebhookDeliveryStatus",
+  attempts,
+  next_attempt_at,
+  delivered_at
+FROM webhook_deliveries
+WHERE webhook_subscription_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at DESC
+LIMIT $2 OFFSET $3
+        "#,
+        webhook_subscription_id,
+        pagination.limit(),
+        pagination.offset()
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        test_helper::*,
+        webhook_subscriptions::{self, NewWebhookSubscription},
+    };
+
+    #[test]
+    fn backoff_doubles_until_attempts_run_out() {
+        let now = Utc::now();
+        assert_eq!(next_attempt_at(1, now), Some(now + Duration::minutes(1)));
+        assert_eq!(next_attempt_at(2, now), Some(now + Duration::minutes(2)));
+        assert_eq!(next_attempt_at(4, now), Some(now + Duration::minutes(8)));
+        assert_eq!(next_attempt_at(MAX_DELIVERY_ATTEMPTS, now), None);
+    }
+
+    #[tokio::test]
+    async fn events_are_queued_for_matching_subscriptions() {
+        insert_data!(:tx, :user, :org, :course);
+        let enrollments = webhook_subscriptions::insert(
+            tx.as_mut(),
+            org,
+            &NewWebhookSubscription {
+                url: "https://example.com/enrollments".to_string(),
+                event_types: vec![WebhookEventType::EnrollmentCreated],
+                enabled: true,
+            },
+            "secret",
+        )
+        .await
+        .unwrap();
+        webhook_subscriptions::insert(
+            tx.as_mut(),
+            org,
+            &NewWebhookSubscription {
+                url: "https://example.com/completions".to_string(),
+                event_types: vec![WebhookEventType::ModuleCompletionGranted],
+                enabled: true,
+            },
+            "secret",
+        )
+        .await
+        .unwrap();
+        webhook_subscriptions::insert(
+            tx.as_mut(),
+            org,
+            &NewWebhookSubscription {
+                url: "https://example.com/disabled".to_string(),
+                event_types: vec![WebhookEventType::EnrollmentCreated],
+                enabled: false,
+            },
+            "secret",
+        )
+        .await
+        .unwrap();
+
+        let event = WebhookEvent::EnrollmentCreated {
+            user_id: user,
+            course_id: course,
+            course_instance_id: Uuid::new_v4(),
+        };
+        let queued = insert_for_event(tx.as_mut(), CourseOrExamId::Course(course), &event)
+            .await
+            .unwrap();
+        assert_eq!(queued, 1);
+
+        let pending = get_pending(tx.as_mut(), 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].url, enrollments.url);
+        assert_eq!(pending[0].event_type, WebhookEventType::EnrollmentCreated);
+        assert_eq!(pending[0].payload, serde_json::to_value(&event).unwrap());
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_can_be_redelivered() {
+        insert_data!(:tx, :user, :org, :course);
+        webhook_subscriptions::insert(
+            tx.as_mut(),
+            org,
+            &NewWebhookSubscription {
+                url: "https://example.com/enrollments".to_string(),
+                event_types: vec![WebhookEventType::EnrollmentCreated],
+                enabled: true,
+            },
+            "secret",
+        )
+        .await
+        .unwrap();
+        let event = WebhookEvent::EnrollmentCreated {
+            user_id: user,
+            course_id: course,
+            course_instance_id: Uuid::new_v4(),
+        };
+        insert_for_event(tx.as_mut(), CourseOrExamId::Course(course), &event)
+            .await
+            .unwrap();
+        let delivery_id = get_pending(tx.as_mut(), 10).await.unwrap()[0].id;
+
+        mark_attempt_failed(tx.as_mut(), delivery_id, None)
+            .await
+            .unwrap();
+        assert!(get_pending(tx.as_mut(), 10).await.unwrap().is_empty());
+        let failed = get_by_id(tx.as_mut(), org, delivery_id).await.unwrap();
+        assert_eq!(failed.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(failed.attempts, 1);
+
+        let redelivered = redeliver(tx.as_mut(), org, delivery_id).await.unwrap();
+        assert_eq!(redelivered.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(redelivered.attempts, 0);
+        assert_eq!(get_pending(tx.as_mut(), 10).await.unwrap().len(), 1);
+    }
+}
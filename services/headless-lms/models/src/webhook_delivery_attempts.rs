@@ -0,0 +1,71 @@
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct WebhookDeliveryAttempt {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub webhook_delivery_id: Uuid,
+    /// None if no response was received.
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewWebhookDeliveryAttempt {
+    pub webhook_delivery_id: Uuid,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    attempt: &NewWebhookDeliveryAttempt,
+) -> ModelResult<Uuid> {
+    let res = sqlx::query!(
+        "
+INSERT INTO webhook_delivery_attempts (
+    webhook_delivery_id,
+    response_status,
+    error,
+    duration_ms
+  )
+VALUES ($1, $2, $3, $4)
+RETURNING id
+        ",
+        attempt.webhook_delivery_id,
+        attempt.response_status,
+        attempt.error,
+        attempt.duration_ms
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res.id)
+}
+
+pub async fn get_by_webhook_delivery_id(
+    conn: &mut PgConnection,
+    webhook_delivery_id: Uuid,
+) -> ModelResult<Vec<WebhookDeliveryAttempt>> {
+    let res = sqlx::query_as!(
+        WebhookDeliveryAttempt,
+        "
+SELECT id,
+  created_at,
+  webhook_delivery_id,
+  response_status,
+  error,
+  duration_ms
+FROM webhook_delivery_attempts
+WHERE webhook_delivery_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at DESC
+        ",
+        webhook_delivery_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
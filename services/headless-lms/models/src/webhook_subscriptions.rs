@@ -0,0 +1,208 @@
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
+
+use crate::prelude::*;
+
+/// Events in the courses and exams of an organization that external systems can subscribe to.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Type)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "webhook_event_type", rename_all = "snake_case")]
+pub enum WebhookEventType {
+    EnrollmentCreated,
+    ModuleCompletionGranted,
+    ExamEnded,
+    SubmissionGraded,
+    PeerReviewReceived,
+}
+
+impl PgHasArrayType for WebhookEventType {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_webhook_event_type")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub organization_id: Uuid,
+    pub url: String,
+    /// Used for signing the requests so that the receiver can verify them.
+    pub secret: String,
+    pub event_types: Vec<WebhookEventType>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewWebhookSubscription {
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    pub enabled: bool,
+}
+
+pub async fn insert(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    new_subscription: &NewWebhookSubscription,
+    secret: &str,
+) -> ModelResult<WebhookSubscription> {
+    validate(new_subscription)?;
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+INSERT INTO webhook_subscriptions (
+    organization_id,
+    url,
+    secret,
+    event_types,
+    enabled
+  )
+VALUES ($1, $2, $3, $4, $5)
+RETURNING id,
+  created_at,
+  updated_at,
+  organization_id,
+  url,
+  secret,
+  event_types AS "event_types: Vec<WebhookEventType>",
+  enabled
+        "#,
+        organization_id,
+        new_subscription.url,
+        secret,
+        &new_subscription.event_types as &[WebhookEventType],
+        new_subscription.enabled
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+/// Changes the url, the events and whether the subscription is enabled. The secret is kept.
+pub async fn update(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    id: Uuid,
+    update: &NewWebhookSubscription,
+) -> ModelResult<WebhookSubscription> {
+    validate(update)?;
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+UPDATE webhook_subscriptions
+SET url = $3,
+  event_types = $4,
+  enabled = $5
+WHERE id = $1
+  AND organization_id = $2
+  AND deleted_at IS NULL
+RETURNING id,
+  created_at,
+  updated_at,
+  organization_id,
+  url,
+  secret,
+  event_types AS "event_types: Vec<WebhookEventType>",
+  enabled
+        "#,
+        id,
+        organization_id,
+        update.url,
+        &update.event_types as &[WebhookEventType],
+        update.enabled
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn delete(conn: &mut PgConnection, organization_id: Uuid, id: Uuid) -> ModelResult<()> {
+    sqlx::query!(
+        "
+UPDATE webhook_subscriptions
+SET deleted_at = now()
+WHERE id = $1
+  AND organization_id = $2
+  AND deleted_at IS NULL
+RETURNING id
+        ",
+        id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_by_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    id: Uuid,
+) -> ModelResult<WebhookSubscription> {
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  url,
+  secret,
+  event_types AS "event_types: Vec<WebhookEventType>",
+  enabled
+FROM webhook_subscriptions
+WHERE id = $1
+  AND organization_id = $2
+  AND deleted_at IS NULL
+        "#,
+        id,
+        organization_id
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(res)
+}
+
+pub async fn get_by_organization_id(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+) -> ModelResult<Vec<WebhookSubscription>> {
+    let res = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+SELECT id,
+  created_at,
+  updated_at,
+  organization_id,
+  url,
+  secret,
+  event_types AS "event_types: Vec<WebhookEventType>",
+  enabled
+FROM webhook_subscriptions
+WHERE organization_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at
+        "#,
+        organization_id
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(res)
+}
+
+fn validate(subscription: &NewWebhookSubscription) -> ModelResult<()> {
+    // the deliverer also checks the addresses the host resolves to when sending
+    headless_lms_utils::public_addresses::validate_public_https_url(&subscription.url)
+        .map_err(|message| ModelError::new(ModelErrorType::InvalidRequest, message, None))?;
+    if subscription.event_types.is_empty() {
+        return Err(ModelError::new(
+            ModelErrorType::InvalidRequest,
+            "A webhook needs to subscribe to at least one event.".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
@@ -13,4 +13,6 @@ type Chapter = {
   deadline: Date | null
   copied_from: string | null
   course_module_id: string
+  publish_at: Date | null
+  unpublish_at: Date | null
 }
@@ -6,4 +6,5 @@ type ContentManagementPage = {
   peer_review_configs: Array<CmsPeerReviewConfig>
   peer_review_questions: Array<CmsPeerReviewQuestion>
   organization_id: string
+  history_id: string | null
 }
@@ -0,0 +1,4 @@
+type CourseContentLintReport = {
+  run: ContentLintRun | null
+  issues: Array<ContentLintIssue>
+}
@@ -3,4 +3,5 @@ type CourseMaterialPeerReviewData = {
   peer_review_config: PeerReviewConfig
   peer_review_questions: Array<PeerReviewQuestion>
   num_peer_reviews_given: number
+  is_calibration_sample: boolean
 }
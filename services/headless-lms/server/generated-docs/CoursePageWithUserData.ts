@@ -3,5 +3,7 @@ type CoursePageWithUserData = {
   instance: CourseInstance | null
   settings: UserCourseSettings | null
   was_redirected: boolean
+  redirected_course_slug: string | null
   is_test_mode: boolean
+  is_draft: boolean
 }
@@ -0,0 +1 @@
+type CreatedApiToken = { api_token: ApiToken; token: string }
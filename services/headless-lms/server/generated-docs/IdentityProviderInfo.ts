@@ -0,0 +1,6 @@
+type IdentityProviderInfo = {
+  id: string
+  organization_id: string
+  name: string
+  issuer_url: string
+}
@@ -0,0 +1,6 @@
+type Impersonation = {
+  impersonated_user_id: string
+  course_id: string
+  started_at: Date
+  expires_at: Date
+}
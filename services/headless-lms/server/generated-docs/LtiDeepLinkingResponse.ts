@@ -0,0 +1 @@
+type LtiDeepLinkingResponse = { deep_link_return_url: string; jwt: string }
@@ -0,0 +1,6 @@
+type NewUrlRedirection = {
+  old_url_path: string
+  destination_page_id: string | null
+  destination_course_id: string | null
+  destination_url_path: string | null
+}
@@ -12,4 +12,6 @@ type Option<Page> = {
   order_number: number
   copied_from: string | null
   hidden: boolean
+  publish_at: Date | null
+  unpublish_at: Date | null
 } | null
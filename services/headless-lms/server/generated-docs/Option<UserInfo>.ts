@@ -1 +1 @@
-type Option<UserInfo> = { user_id: string } | null
+type Option<UserInfo> = { user_id: string; impersonation: Impersonation | null } | null
@@ -0,0 +1,9 @@
+type PageDraft = {
+  id: string
+  page_id: string
+  created_at: Date
+  updated_at: Date
+  author_user_id: string | null
+  content: CmsPageUpdate
+  publish_at: Date | null
+}
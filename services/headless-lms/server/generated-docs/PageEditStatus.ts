@@ -0,0 +1,5 @@
+type PageEditStatus = {
+  page_id: string
+  lock: PageEditLock | null
+  editors: Array<PageEditor>
+}
@@ -0,0 +1,11 @@
+type PageHistoryDiff = {
+  old_history_id: string
+  new_history_id: string
+  title_diff: Array<TextDiffPart> | null
+  blocks: Array<BlockChange>
+  exercises: Array<ItemChange>
+  exercise_slides: Array<ItemChange>
+  exercise_tasks: Array<ItemChange>
+  peer_review_configs: Array<ItemChange>
+  peer_review_questions: Array<ItemChange>
+}
@@ -0,0 +1,5 @@
+type PeerReviewCalibrationFeedback = {
+  question_feedback: Array<PeerReviewCalibrationQuestionFeedback>
+  calibration_samples_reviewed: number
+  calibration_samples_to_review: number
+}
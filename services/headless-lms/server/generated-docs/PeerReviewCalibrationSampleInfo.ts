@@ -0,0 +1,4 @@
+type PeerReviewCalibrationSampleInfo = {
+  calibration_sample: PeerReviewCalibrationSampleWithReferenceAnswers | null
+  peer_review_questions: Array<PeerReviewQuestion>
+}
@@ -0,0 +1,4 @@
+type PeerReviewCalibrationSampleWithReferenceAnswers = {
+  calibration_sample: PeerReviewCalibrationSample
+  reference_answers: Array<PeerReviewCalibrationReferenceAnswer>
+}
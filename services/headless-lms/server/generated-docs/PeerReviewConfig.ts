@@ -9,4 +9,8 @@ type PeerReviewConfig = {
   peer_reviews_to_receive: number
   accepting_threshold: number
   accepting_strategy: PeerReviewAcceptingStrategy
+  calibration_samples_to_review: number
+  timeout_in_days: number
+  timeout_action: PeerReviewTimeoutAction
+  manual_review_timeout_in_days: number | null
 }
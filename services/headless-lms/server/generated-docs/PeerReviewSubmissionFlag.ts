@@ -0,0 +1,12 @@
+type PeerReviewSubmissionFlag = {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  peer_review_submission_id: string
+  flagged_by: string
+  reason: string
+  resolved_at: Date | null
+  resolved_by: string | null
+  resolution: PeerReviewSubmissionFlagResolution | null
+}
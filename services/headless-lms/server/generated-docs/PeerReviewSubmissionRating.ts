@@ -0,0 +1,9 @@
+type PeerReviewSubmissionRating = {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  peer_review_submission_id: string
+  user_id: string
+  helpfulness: number
+}
@@ -1,4 +1,6 @@
 type PeerReviewsRecieved = {
   peer_review_questions: Array<PeerReviewQuestion>
   peer_review_question_submissions: Array<PeerReviewQuestionSubmission>
+  peer_review_submission_ratings: Array<PeerReviewSubmissionRating>
+  peer_review_submission_flags: Array<PeerReviewSubmissionFlag>
 }
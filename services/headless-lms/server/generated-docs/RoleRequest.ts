@@ -0,0 +1,15 @@
+type RoleRequest = {
+  id: string
+  created_at: Date
+  user_id: string
+  user_email: string
+  course_id: string
+  course_name: string
+  role: UserRole
+  valid_from: Date | null
+  valid_until: Date | null
+  reason: string
+  status: RoleRequestStatus
+  decided_by_user_id: string | null
+  decided_at: Date | null
+}
@@ -0,0 +1,12 @@
+type UrlRedirection = {
+  id: string
+  created_at: Date
+  updated_at: Date
+  course_id: string
+  old_url_path: string
+  destination_page_id: string | null
+  destination_course_id: string | null
+  destination_url_path: string | null
+  hit_count: number
+  last_hit_at: Date | null
+}
@@ -0,0 +1,7 @@
+type UserDataExport = {
+  id: string
+  created_at: Date
+  user_id: string
+  completed_at: Date | null
+  expires_at: Date | null
+}
@@ -0,0 +1,10 @@
+type Vec<ApiToken> = Array<{
+  id: string
+  created_at: Date
+  updated_at: Date
+  user_id: string
+  name: string
+  scopes: Array<ApiTokenScope>
+  expires_at: Date
+  last_used_at: Date | null
+}>
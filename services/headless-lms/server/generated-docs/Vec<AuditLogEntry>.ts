@@ -0,0 +1,13 @@
+type Vec<AuditLogEntry> = Array<{
+  id: string
+  created_at: Date
+  actor_user_id: string | null
+  actor_email: string | null
+  api_token_id: string | null
+  request_id: string | null
+  action: AuditLogAction
+  resource_id: string
+  course_id: string | null
+  before_summary: unknown | null
+  after_summary: unknown | null
+}>
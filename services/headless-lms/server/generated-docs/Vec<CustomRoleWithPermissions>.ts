@@ -0,0 +1,4 @@
+type Vec<CustomRoleWithPermissions> = Array<{
+  custom_role: CustomRole
+  permissions: Array<RolePermission>
+}>
@@ -0,0 +1,8 @@
+type Vec<ExerciseTaskSubmissionSimilarityInfo> = Array<{
+  id: string
+  course_instance_id: string
+  exercise_task_id: string
+  similarity: number
+  first: SimilarSubmission
+  second: SimilarSubmission
+}>
@@ -0,0 +1,6 @@
+type Vec<FlaggedPeerReview> = Array<{
+  flag: PeerReviewSubmissionFlag
+  reviewer_user_id: string
+  exercise_slide_submission_id: string
+  peer_review: PeerReviewWithQuestionsAndAnswers | null
+}>
@@ -0,0 +1,6 @@
+type Vec<IdentityProviderInfo> = Array<{
+  id: string
+  organization_id: string
+  name: string
+  issuer_url: string
+}>
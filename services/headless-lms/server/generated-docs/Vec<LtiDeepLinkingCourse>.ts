@@ -0,0 +1,6 @@
+type Vec<LtiDeepLinkingCourse> = Array<{
+  id: string
+  name: string
+  pages: Array<LtiDeepLinkingPage>
+  chapters: Array<LtiDeepLinkingChapter>
+}>
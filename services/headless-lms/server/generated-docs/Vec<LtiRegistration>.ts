@@ -0,0 +1,13 @@
+type Vec<LtiRegistration> = Array<{
+  id: string
+  created_at: Date
+  updated_at: Date
+  organization_id: string
+  name: string
+  issuer: string
+  client_id: string
+  auth_login_url: string
+  auth_token_url: string
+  jwks_url: string
+  link_existing_users_by_email: boolean
+}>
@@ -12,4 +12,6 @@ type Vec<Page> = Array<{
   order_number: number
   copied_from: string | null
   hidden: boolean
+  publish_at: Date | null
+  unpublish_at: Date | null
 }>
@@ -12,5 +12,7 @@ type Vec<PageWithExercises> = Array<{
   order_number: number
   copied_from: string | null
   hidden: boolean
+  publish_at: Date | null
+  unpublish_at: Date | null
   exercises: Array<Exercise>
 }>
@@ -0,0 +1,8 @@
+type Vec<PeerReviewReviewerStatistics> = Array<{
+  user_id: string
+  peer_reviews_given: number
+  helpfulness_ratings_received: number
+  average_helpfulness: number | null
+  flags_received: number
+  reviews_removed: number
+}>
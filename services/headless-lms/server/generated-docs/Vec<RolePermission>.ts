@@ -0,0 +1,8 @@
+type Vec<RolePermission> = Array<{
+  id: string
+  built_in_role: UserRole | null
+  custom_role_id: string | null
+  action: PermissionAction
+  action_role: UserRole | null
+  resource_type: PermissionResourceType | null
+}>
@@ -4,4 +4,8 @@ type Vec<RoleUser> = Array<{
   last_name: string | null
   email: string
   role: UserRole
+  valid_from: Date | null
+  valid_until: Date | null
+  custom_role_id: string | null
+  custom_role_name: string | null
 }>
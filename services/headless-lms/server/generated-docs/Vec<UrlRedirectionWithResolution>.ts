@@ -0,0 +1,4 @@
+type Vec<UrlRedirectionWithResolution> = Array<{
+  redirection: UrlRedirection
+  resolution: UrlRedirectionResolution
+}>
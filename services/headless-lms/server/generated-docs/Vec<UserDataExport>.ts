@@ -0,0 +1,7 @@
+type Vec<UserDataExport> = Array<{
+  id: string
+  created_at: Date
+  user_id: string
+  completed_at: Date | null
+  expires_at: Date | null
+}>
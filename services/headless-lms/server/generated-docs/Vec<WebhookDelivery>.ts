@@ -0,0 +1,12 @@
+type Vec<WebhookDelivery> = Array<{
+  id: string
+  created_at: Date
+  updated_at: Date
+  webhook_subscription_id: string
+  event_type: WebhookEventType
+  payload: unknown
+  status: WebhookDeliveryStatus
+  attempts: number
+  next_attempt_at: Date
+  delivered_at: Date | null
+}>
@@ -0,0 +1,8 @@
+type Vec<WebhookDeliveryAttempt> = Array<{
+  id: string
+  created_at: Date
+  webhook_delivery_id: string
+  response_status: number | null
+  error: string | null
+  duration_ms: number
+}>
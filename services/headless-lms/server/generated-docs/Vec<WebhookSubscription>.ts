@@ -0,0 +1,10 @@
+type Vec<WebhookSubscription> = Array<{
+  id: string
+  created_at: Date
+  updated_at: Date
+  organization_id: string
+  url: string
+  secret: string
+  event_types: Array<WebhookEventType>
+  enabled: boolean
+}>
@@ -2,10 +2,11 @@
 Handlers for HTTP requests to `/api/v0/auth`.
 */
 
-use std::{env, time::Duration};
+use std::env;
 
 use actix_session::Session;
-use models::users::User;
+use chrono::Utc;
+use models::{identity_providers::IdentityProviderInfo, users::User};
 use oauth2::{
     basic::BasicTokenType, reqwest::AsyncHttpClientError, EmptyExtraTokenFields,
     ResourceOwnerPassword, ResourceOwnerUsername, StandardTokenResponse, TokenResponse,
@@ -16,8 +17,11 @@ use crate::{
     domain::{
         authorization::{
             self, authorize_with_fetched_list_of_roles, skip_authorize, ActionOnResource,
+            Impersonation,
         },
-        rate_limit_middleware_builder::build_rate_limiting_middleware,
+        impersonation::{self, ImpersonationEndReason},
+        oidc,
+        rate_limits::RateLimit,
     },
     prelude::*,
     OAuthClient,
@@ -297,13 +301,26 @@ async fn async_http_client_with_headers(
 }
 
 /**
-POST `/api/v0/auth/logout` Logs out.
+POST `/api/v0/auth/logout` Logs out. Ends the impersonation if the user is impersonating someone.
 **/
-#[instrument(skip(session))]
+#[instrument(skip(session, pool))]
 #[allow(clippy::async_yields_async)]
-pub async fn logout(session: Session) -> HttpResponse {
+pub async fn logout(
+    session: Session,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, ControllerError> {
+    if let Some(user) = authorization::get_session_user(&session) {
+        let mut conn = pool.acquire().await?;
+        impersonation::end(
+            &mut conn,
+            &session,
+            &user,
+            ImpersonationEndReason::LoggedOut,
+        )
+        .await?;
+    }
     authorization::forget(&session);
-    HttpResponse::Ok().finish()
+    Ok(HttpResponse::Ok().finish())
 }
 
 /**
@@ -322,21 +339,176 @@ pub async fn logged_in(session: Session) -> web::Json<bool> {
 #[cfg_attr(feature = "ts_rs", derive(TS))]
 pub struct UserInfo {
     pub user_id: Uuid,
+    /// Set if the user is viewing the course material as another user.
+    pub impersonation: Option<Impersonation>,
 }
 
 /**
 GET `/api/v0/auth/user-info` Returns the current user's info.
 **/
 #[generated_doc]
-#[instrument(skip(user))]
-pub async fn user_info(user: Option<AuthUser>) -> web::Json<Option<UserInfo>> {
+#[instrument(skip(user, session))]
+pub async fn user_info(user: Option<AuthUser>, session: Session) -> web::Json<Option<UserInfo>> {
     if let Some(user) = user {
-        web::Json(Some(UserInfo { user_id: user.id }))
+        let impersonation = authorization::get_impersonation(&session)
+            .filter(|impersonation| !impersonation.has_expired(Utc::now()));
+        web::Json(Some(UserInfo {
+            user_id: user.id,
+            impersonation,
+        }))
     } else {
         web::Json(None)
     }
 }
 
+/**
+GET `/api/v0/auth/identity-providers` Returns the identity providers that can be used for logging in in addition to the mooc.fi account.
+**/
+#[generated_doc]
+#[instrument(skip(pool))]
+pub async fn get_identity_providers(
+    pool: web::Data<PgPool>,
+) -> ControllerResult<web::Json<Vec<IdentityProviderInfo>>> {
+    let mut conn = pool.acquire().await?;
+    let identity_providers = models::identity_providers::get_all(&mut conn).await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(identity_providers))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OidcLoginQuery {
+    return_to: Option<String>,
+    #[serde(default)]
+    link: bool,
+}
+
+/**
+GET `/api/v0/auth/oidc/:identity_provider_id/login` Redirects the user to log in with the identity provider.
+
+The optional `return_to` query parameter is the path the user is redirected to after logging in.
+With `link=true`, a logged in user links the identity to their account. Identities are never linked to existing accounts otherwise.
+**/
+#[instrument(skip(session, pool, app_conf))]
+pub async fn oidc_login(
+    session: Session,
+    pool: web::Data<PgPool>,
+    app_conf: web::Data<ApplicationConfiguration>,
+    identity_provider_id: web::Path<Uuid>,
+    query: web::Query<OidcLoginQuery>,
+    user: Option<AuthUser>,
+) -> ControllerResult<HttpResponse> {
+    let link_to_user_id = match (query.link, user) {
+        (true, Some(user)) => Some(user.id),
+        (true, None) => {
+            return Err(ControllerError::new(
+                ControllerErrorType::Unauthorized,
+                "Log in to link your account to the identity provider.".to_string(),
+                None,
+            ))
+        }
+        (false, _) => None,
+    };
+    let mut conn = pool.acquire().await?;
+    let identity_provider =
+        models::identity_providers::get_by_id(&mut conn, *identity_provider_id).await?;
+    let redirect_url = oidc::redirect_url(&app_conf.base_url, identity_provider.id)?;
+    let authorization_url = oidc::begin_login(
+        &session,
+        &Client::new(),
+        &identity_provider,
+        redirect_url,
+        query.return_to.as_deref(),
+        link_to_user_id,
+    )
+    .await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(
+        HttpResponse::Found()
+            .append_header(("location", authorization_url.to_string()))
+            .finish(),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OidcCallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+/**
+GET `/api/v0/auth/oidc/:identity_provider_id/callback` The identity provider redirects the user here after logging in. Logs the user in and redirects them to the path given when starting the login. Shares the rate limit of the login endpoint.
+**/
+#[instrument(skip(session, pool, app_conf, query))]
+pub async fn oidc_callback(
+    session: Session,
+    pool: web::Data<PgPool>,
+    app_conf: web::Data<ApplicationConfiguration>,
+    identity_provider_id: web::Path<Uuid>,
+    query: web::Query<OidcCallbackQuery>,
+    user: Option<AuthUser>,
+) -> ControllerResult<HttpResponse> {
+    let mut conn = pool.acquire().await?;
+    let OidcCallbackQuery { code, state, error } = query.into_inner();
+    let (code, state) = match (code, state, error) {
+        (Some(code), Some(state), None) => (code, state),
+        (_, _, error) => {
+            return Err(ControllerError::new(
+                ControllerErrorType::Unauthorized,
+                format!(
+                    "The identity provider did not accept the login: {}",
+                    error.as_deref().unwrap_or("missing authorization code")
+                ),
+                None,
+            ));
+        }
+    };
+    let identity_provider =
+        models::identity_providers::get_by_id(&mut conn, *identity_provider_id).await?;
+    let redirect_url = oidc::redirect_url(&app_conf.base_url, identity_provider.id)?;
+    let completed_login = oidc::finish_login(
+        &session,
+        &Client::new(),
+        &identity_provider,
+        redirect_url,
+        &code,
+        &state,
+    )
+    .await
+    .map_err(|err| {
+        info!(error = ?err, "Failed to log in with the identity provider");
+        ControllerError::new(
+            ControllerErrorType::Unauthorized,
+            "Logging in with the identity provider failed.".to_string(),
+            Some(err),
+        )
+    })?;
+    // the user who started linking must still be the one logged in
+    if let Some(link_to_user_id) = completed_login.link_to_user_id {
+        if user.map(|user| user.id) != Some(link_to_user_id) {
+            return Err(ControllerError::new(
+                ControllerErrorType::Unauthorized,
+                "The account to link is no longer logged in.".to_string(),
+                None,
+            ));
+        }
+    }
+    let user = oidc::get_or_create_user(
+        &mut conn,
+        &identity_provider,
+        &completed_login.user_info,
+        completed_login.link_to_user_id,
+    )
+    .await?;
+    let token = skip_authorize()?;
+    authorization::remember(&session, user)?;
+    token.authorized_ok(
+        HttpResponse::Found()
+            .append_header(("location", completed_login.return_to))
+            .finish(),
+    )
+}
+
 pub type LoginToken = StandardTokenResponse<EmptyExtraTokenFields, BasicTokenType>;
 
 /// Posts new user account to tmc.mooc.fi.
@@ -454,26 +626,25 @@ pub async fn get_user_from_moocfi(
 pub fn _add_routes(cfg: &mut ServiceConfig) {
     cfg.service(
         web::resource("/signup")
-            .wrap(build_rate_limiting_middleware(Duration::from_secs(60), 15))
-            .wrap(build_rate_limiting_middleware(
-                Duration::from_secs(60 * 60 * 24),
-                1000,
-            ))
+            .wrap(RateLimit::new("signup"))
             .to(signup),
     )
     .service(
         web::resource("/login")
-            .wrap(build_rate_limiting_middleware(Duration::from_secs(60), 20))
-            .wrap(build_rate_limiting_middleware(
-                Duration::from_secs(60 * 60),
-                100,
-            ))
-            .wrap(build_rate_limiting_middleware(
-                Duration::from_secs(60 * 60 * 24),
-                500,
-            ))
+            .wrap(RateLimit::new("login"))
             .to(login),
     )
+    .service(
+        web::resource("/oidc/{identity_provider_id}/login")
+            .wrap(RateLimit::new("oidc_login"))
+            .to(oidc_login),
+    )
+    .service(
+        web::resource("/oidc/{identity_provider_id}/callback")
+            .wrap(RateLimit::new("oidc_login"))
+            .route(web::get().to(oidc_callback)),
+    )
+    .route("/identity-providers", web::get().to(get_identity_providers))
     .route("/logout", web::post().to(logout))
     .route("/logged-in", web::get().to(logged_in))
     .route("/authorize", web::post().to(authorize_action_on_resource))
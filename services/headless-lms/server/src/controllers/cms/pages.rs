@@ -1,9 +1,12 @@
 //! Controllers for requests starting with `/api/v0/cms/pages`.
 
 use models::{
+    page_drafts::{self, PageDraft, PageDraftUpdate},
+    page_edit_locks::{self, PageEditStatus},
     page_history::HistoryChangeReason,
     pages::{
-        CmsPageUpdate, ContentManagementPage, PageInfo, PageNavigationInformation, PageUpdateArgs,
+        CmsPageUpdate, ContentManagementPage, Page, PageInfo, PageNavigationInformation,
+        PagePublishingSchedule, PageUpdateArgs,
     },
     CourseOrExamId,
 };
@@ -57,6 +60,8 @@ Please note that this endpoint will change all the exercise and exercise task id
 
 If optional property front_page_of_chapter_id is set, this page will become the front page of the specified course part.
 
+Fails with 409 Conflict if someone else holds the edit lock of the page, or if `base_history_id` is set and the page has been saved after it.
+
 # Example: OUTDATED
 
 Request:
@@ -105,6 +110,175 @@ async fn update_page(
     token.authorized_ok(web::Json(saved))
 }
 
+/**
+POST `/api/v0/cms/pages/:page_id/edit-lock/acquire` - Acquires the edit lock of the page unless someone else holds it.
+
+Called when the page is opened in the CMS. The response tells who holds the lock and who else has the page open.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn acquire_edit_lock(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PageEditStatus>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let status = page_edit_locks::acquire(&mut conn, *page_id, user.id).await?;
+    token.authorized_ok(web::Json(status))
+}
+
+/**
+POST `/api/v0/cms/pages/:page_id/edit-lock/renew` - The heartbeat of the editor. Renews the edit lock if the user still holds it.
+
+The lock and the presence of the user expire a minute after the latest heartbeat.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn renew_edit_lock(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PageEditStatus>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let status = page_edit_locks::renew(&mut conn, *page_id, user.id).await?;
+    token.authorized_ok(web::Json(status))
+}
+
+/**
+POST `/api/v0/cms/pages/:page_id/edit-lock/steal` - Takes the edit lock of the page over from whoever holds it.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn steal_edit_lock(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PageEditStatus>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let status = page_edit_locks::steal(&mut conn, *page_id, user.id).await?;
+    token.authorized_ok(web::Json(status))
+}
+
+/**
+POST `/api/v0/cms/pages/:page_id/edit-lock/release` - Releases the edit lock if the user holds it. Called when the page is closed in the CMS.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn release_edit_lock(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    page_edit_locks::release(&mut conn, *page_id, user.id).await?;
+    token.authorized_ok(web::Json(()))
+}
+
+/**
+GET `/api/v0/cms/pages/:page_id/draft` - Returns the draft of the page, if it has one.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_draft(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Option<PageDraft>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let draft = page_drafts::get_by_page_id(&mut conn, *page_id).await?;
+    token.authorized_ok(web::Json(draft))
+}
+
+/**
+PUT `/api/v0/cms/pages/:page_id/draft` - Saves the draft of the page. The published version of the page stays as it is until the draft is published.
+
+If `publish_at` is set, the draft is published automatically at that time. Fails with 409 Conflict if someone else holds the edit lock of the page.
+*/
+#[generated_doc]
+#[instrument(skip(pool, payload))]
+async fn save_draft(
+    payload: web::Json<PageDraftUpdate>,
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PageDraft>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let draft = page_drafts::upsert(&mut conn, *page_id, user.id, &payload).await?;
+    token.authorized_ok(web::Json(draft))
+}
+
+/**
+DELETE `/api/v0/cms/pages/:page_id/draft` - Discards the draft of the page.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_draft(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    page_drafts::delete(&mut conn, *page_id).await?;
+    token.authorized_ok(web::Json(()))
+}
+
+/**
+POST `/api/v0/cms/pages/:page_id/draft/publish` - Publishes the draft of the page right away, overwriting the published version.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn publish_draft(
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    jwt_key: web::Data<JwtKey>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<ContentManagementPage>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let page = page_drafts::publish(
+        &mut conn,
+        *page_id,
+        Some(user.id),
+        models_requests::make_spec_fetcher(jwt_key.into_inner()),
+        models_requests::fetch_service_info,
+    )
+    .await?;
+    token.authorized_ok(web::Json(page))
+}
+
+/**
+PUT `/api/v0/cms/pages/:page_id/publishing-schedule` - Sets when the page becomes visible to students and when it stops being visible.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn set_publishing_schedule(
+    payload: web::Json<PagePublishingSchedule>,
+    page_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Page>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+
+    let page = models::pages::set_publishing_schedule(&mut conn, *page_id, &payload).await?;
+    token.authorized_ok(web::Json(page))
+}
+
 /**
 GET /api/v0/cms/pages/:page_id/page-navigation - tells what's the next page, previous page, and the chapter front page given a page id.
 */
@@ -134,5 +308,29 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
             "/{page_id}/page-navigation",
             web::get().to(get_page_navigation),
         )
-        .route("/{page_id}", web::put().to(update_page));
+        .route("/{page_id}", web::put().to(update_page))
+        .route(
+            "/{page_id}/edit-lock/acquire",
+            web::post().to(acquire_edit_lock),
+        )
+        .route(
+            "/{page_id}/edit-lock/renew",
+            web::post().to(renew_edit_lock),
+        )
+        .route(
+            "/{page_id}/edit-lock/steal",
+            web::post().to(steal_edit_lock),
+        )
+        .route(
+            "/{page_id}/edit-lock/release",
+            web::post().to(release_edit_lock),
+        )
+        .route("/{page_id}/draft", web::get().to(get_draft))
+        .route("/{page_id}/draft", web::put().to(save_draft))
+        .route("/{page_id}/draft", web::delete().to(delete_draft))
+        .route("/{page_id}/draft/publish", web::post().to(publish_draft))
+        .route(
+            "/{page_id}/publishing-schedule",
+            web::put().to(set_publishing_schedule),
+        );
 }
@@ -21,7 +21,10 @@ use models::{
     page_visit_datum_daily_visit_hashing_keys::{
         generate_anonymous_identifier, GenerateAnonymousIdentifierInput,
     },
-    pages::{CoursePageWithUserData, Page, PageSearchRequest, PageSearchResult, PageVisibility},
+    pages::{
+        CoursePageWithUserData, Page, PageSearchRequest, PageSearchResult, PageVersion,
+        PageVisibility,
+    },
     proposed_page_edits::{self, NewProposedPageEdits},
     user_course_settings::UserCourseSettings,
 };
@@ -52,6 +55,10 @@ GET `/:course_slug/page-by-path/...` - Returns a course page by path
 
 If the page has moved and there's a redirection, this will still return the moved page but the field `was_redirected` will indicate that the redirection happened. The new path can be found in the page object. The frontend is supposed to update the url of the page to the new location without reloading the page.
 
+Redirections are followed until a page is found, and they may lead to another language version of the course. In that case the field `redirected_course_slug` contains the slug of that course, and the frontend is supposed to navigate to the page in that course.
+
+Pages that have not been published yet or have been unpublished are not found, except for users who can edit the course. They are shown the draft of the page instead of the published version if the page has one, which is indicated by the field `is_draft`.
+
 # Example
 GET /api/v0/course-material/courses/introduction-to-everything/page-by-path//part-2/hello-world
 */
@@ -73,11 +80,30 @@ async fn get_course_page_by_path(
         format!("/{}", raw_page_path)
     };
 
+    let course_data =
+        models::courses::get_nondeleted_course_id_by_slug(&mut conn, &course_slug).await?;
+    let can_edit = match &user {
+        Some(user) => authorize(
+            &mut conn,
+            Act::Edit,
+            Some(user.id),
+            Res::Course(course_data.id),
+        )
+        .await
+        .is_ok(),
+        None => false,
+    };
+    let page_version = if can_edit {
+        PageVersion::Draft
+    } else {
+        PageVersion::Published
+    };
     let page_with_user_data = models::pages::get_page_with_user_data_by_path(
         &mut conn,
         user.as_ref().map(|u| u.id),
         &course_slug,
         &path,
+        page_version,
     )
     .await?;
 
@@ -333,6 +359,12 @@ async fn get_chapters(
     app_conf: web::Data<ApplicationConfiguration>,
 ) -> ControllerResult<web::Json<ChaptersWithStatus>> {
     let mut conn = pool.acquire().await?;
+    let can_edit = match &user {
+        Some(user) => authorize(&mut conn, Act::Edit, Some(user.id), Res::Course(*course_id))
+            .await
+            .is_ok(),
+        None => false,
+    };
     let is_previewable = OptionFuture::from(user.map(|u| {
         authorize(&mut conn, Act::Teach, Some(u.id), Res::Course(*course_id)).map(|r| r.ok())
     }))
@@ -340,9 +372,12 @@ async fn get_chapters(
     .is_some();
     let token = skip_authorize()?;
     let course_modules = models::course_modules::get_by_course_id(&mut conn, *course_id).await?;
+    let now = Utc::now();
     let chapters = models::chapters::course_chapters(&mut conn, *course_id)
         .await?
         .into_iter()
+        // the users who can edit the course can view the chapters before they are published
+        .filter(|chapter| can_edit || chapter.is_published_at(now))
         .map(|chapter| {
             let chapter_image_url = chapter
                 .chapter_image_path
@@ -350,7 +385,7 @@ async fn get_chapters(
                 .map(|path| file_store.get_download_url(Path::new(&path), &app_conf));
             ChapterWithStatus::from_database_chapter_timestamp_and_image_url(
                 chapter,
-                Utc::now(),
+                now,
                 chapter_image_url,
             )
         })
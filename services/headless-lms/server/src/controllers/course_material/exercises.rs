@@ -9,8 +9,14 @@ use models::{
         grading::{
             GradingPolicy, StudentExerciseSlideSubmission, StudentExerciseSlideSubmissionResult,
         },
-        peer_reviewing::{CourseMaterialPeerReviewData, CourseMaterialPeerReviewSubmission},
+        peer_reviewing::{
+            CourseMaterialPeerReviewData, CourseMaterialPeerReviewSubmission,
+            NewPeerReviewSubmissionFlag, NewPeerReviewSubmissionRating,
+            PeerReviewCalibrationFeedback,
+        },
     },
+    peer_review_submission_flags::PeerReviewSubmissionFlag,
+    peer_review_submission_ratings::PeerReviewSubmissionRating,
     user_exercise_states::{self, CourseInstanceOrExamId, ExerciseWithUserState},
 };
 
@@ -134,6 +140,68 @@ async fn get_peer_reviews_received(
     token.authorized_ok(web::Json(peer_review_data))
 }
 
+/**
+PUT `/api/v0/course-material/exercises/:exercise_id/peer-reviews-received/:peer_review_submission_id/rating` - Rate how helpful a received peer review was. Rating the same review again replaces the previous rating.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn rate_received_peer_review(
+    pool: web::Data<PgPool>,
+    params: web::Path<(Uuid, Uuid)>,
+    payload: web::Json<NewPeerReviewSubmissionRating>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PeerReviewSubmissionRating>> {
+    let mut conn = pool.acquire().await?;
+    let (exercise_id, peer_review_submission_id) = params.into_inner();
+    let token = authorize(
+        &mut conn,
+        Act::View,
+        Some(user.id),
+        Res::Exercise(exercise_id),
+    )
+    .await?;
+    let rating = models::library::peer_reviewing::rate_received_peer_review(
+        &mut conn,
+        exercise_id,
+        peer_review_submission_id,
+        user.id,
+        payload.0,
+    )
+    .await?;
+    token.authorized_ok(web::Json(rating))
+}
+
+/**
+POST `/api/v0/course-material/exercises/:exercise_id/peer-reviews-received/:peer_review_submission_id/flag` - Flag a received peer review as inappropriate. The review is not counted until a teacher has resolved the flag.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn flag_received_peer_review(
+    pool: web::Data<PgPool>,
+    params: web::Path<(Uuid, Uuid)>,
+    payload: web::Json<NewPeerReviewSubmissionFlag>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PeerReviewSubmissionFlag>> {
+    let mut conn = pool.acquire().await?;
+    let (exercise_id, peer_review_submission_id) = params.into_inner();
+    let token = authorize(
+        &mut conn,
+        Act::View,
+        Some(user.id),
+        Res::Exercise(exercise_id),
+    )
+    .await?;
+    let flag = models::library::peer_reviewing::flag_received_peer_review(
+        &mut conn,
+        exercise_id,
+        peer_review_submission_id,
+        user.id,
+        payload.0,
+    )
+    .await?;
+    token.authorized_ok(web::Json(flag))
+}
+
 /**
 POST `/api/v0/course-material/exercises/:exercise_id/submissions` - Post new submission for an
 exercise.
@@ -277,6 +345,41 @@ async fn start_peer_review(
     token.authorized_ok(web::Json(true))
 }
 
+/**
+ * POST `/api/v0/course-material/exercises/:exercise_id/peer-reviews/calibration` - Post a peer review
+ * for a calibration sample. Returns feedback on how close the review was to the reference answers
+ * given by the teacher.
+ */
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn submit_peer_review_calibration(
+    pool: web::Data<PgPool>,
+    exercise_id: web::Path<Uuid>,
+    payload: web::Json<CourseMaterialPeerReviewSubmission>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PeerReviewCalibrationFeedback>> {
+    let mut conn = pool.acquire().await?;
+    let exercise = models::exercises::get_by_id(&mut conn, *exercise_id).await?;
+    let token = authorize(
+        &mut conn,
+        Act::View,
+        Some(user.id),
+        Res::Exercise(exercise.id),
+    )
+    .await?;
+    let user_exercise_state =
+        user_exercise_states::get_users_current_by_exercise(&mut conn, user.id, &exercise).await?;
+    let feedback =
+        models::library::peer_reviewing::create_peer_review_calibration_submission_for_user(
+            &mut conn,
+            &exercise,
+            user_exercise_state,
+            payload.0,
+        )
+        .await?;
+    token.authorized_ok(web::Json(feedback))
+}
+
 /**
  * POST `/api/v0/course-material/exercises/:exercise_id/peer-reviews - Post a peer review for an
  * exercise submission.
@@ -409,6 +512,10 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
             "/{exercise_id}/peer-reviews/start",
             web::post().to(start_peer_review),
         )
+        .route(
+            "/{exercise_id}/peer-reviews/calibration",
+            web::post().to(submit_peer_review_calibration),
+        )
         .route(
             "/{exercise_id}/peer-review",
             web::get().to(get_peer_review_for_exercise),
@@ -417,6 +524,14 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
             "/{exercise_id}/exercise-slide-submission/{exercise_slide_submission_id}/peer-reviews-received",
             web::get().to(get_peer_reviews_received),
         )
+        .route(
+            "/{exercise_id}/peer-reviews-received/{peer_review_submission_id}/rating",
+            web::put().to(rate_received_peer_review),
+        )
+        .route(
+            "/{exercise_id}/peer-reviews-received/{peer_review_submission_id}/flag",
+            web::post().to(flag_received_peer_review),
+        )
         .route(
             "/{exercise_id}/submissions",
             web::post().to(post_submission),
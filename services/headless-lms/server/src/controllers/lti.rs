@@ -0,0 +1,516 @@
+/*!
+Handlers for HTTP requests to `/api/v0/lti`.
+
+These are the endpoints LTI 1.3 platforms use for launching courses, see [crate::domain::lti].
+*/
+
+use std::collections::HashMap;
+
+use actix_session::Session;
+use models::{
+    chapters, courses, lti_deep_linking_requests, lti_registrations,
+    lti_resource_links::{self, LtiLinkTarget},
+    pages::{self, PageVisibility},
+};
+use reqwest::Client;
+
+use crate::{
+    domain::{
+        authorization,
+        lti::{self, LtiMessage, LtiToolKey},
+        oidc::JsonWebKeySet,
+        rate_limits::RateLimit,
+    },
+    prelude::*,
+};
+
+const DEEP_LINKING_SESSION_KEY: &str = "lti_deep_linking_request_id";
+
+#[derive(Debug, Deserialize)]
+pub struct LtiLoginInitiation {
+    iss: String,
+    login_hint: String,
+    lti_message_hint: Option<String>,
+    client_id: Option<String>,
+}
+
+/**
+GET `/api/v0/lti/login` - Initiates a launch from an LTI platform. Redirects the user to the authorization endpoint of the platform, which then posts the launch message to `/api/v0/lti/launch`.
+
+The session cookie is `SameSite=Strict`, so browsers do not send it to a course embedded in an iframe on the platform's site. If the platform opens the tool in an iframe, the response is a page with a link that restarts the launch in a new window instead.
+*/
+#[instrument(skip(req, pool, app_conf))]
+async fn login_get(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    app_conf: web::Data<ApplicationConfiguration>,
+    query: web::Query<LtiLoginInitiation>,
+) -> ControllerResult<HttpResponse> {
+    login(&req, &pool, &app_conf, &query).await
+}
+
+/**
+POST `/api/v0/lti/login` - Same as `GET /api/v0/lti/login`, platforms may send the parameters either way.
+*/
+#[instrument(skip(req, pool, app_conf))]
+async fn login_post(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    app_conf: web::Data<ApplicationConfiguration>,
+    form: web::Form<LtiLoginInitiation>,
+) -> ControllerResult<HttpResponse> {
+    login(&req, &pool, &app_conf, &form).await
+}
+
+async fn login(
+    req: &HttpRequest,
+    pool: &PgPool,
+    app_conf: &ApplicationConfiguration,
+    initiation: &LtiLoginInitiation,
+) -> ControllerResult<HttpResponse> {
+    let in_iframe = req
+        .headers()
+        .get("sec-fetch-dest")
+        .map_or(false, |dest| dest == "iframe");
+    if in_iframe {
+        let token = skip_authorize()?;
+        return token.authorized_ok(
+            HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(open_in_new_window_page(initiation)),
+        );
+    }
+    let mut conn = pool.acquire().await?;
+    let registration = lti_registrations::get_by_issuer_and_client_id(
+        &mut conn,
+        &initiation.iss,
+        initiation.client_id.as_deref(),
+    )
+    .await?;
+    let authorization_url = lti::begin_login(
+        &mut conn,
+        &registration,
+        &lti::launch_url(&app_conf.base_url),
+        &initiation.login_hint,
+        initiation.lti_message_hint.as_deref(),
+    )
+    .await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(
+        HttpResponse::Found()
+            .append_header(("location", authorization_url.to_string()))
+            .finish(),
+    )
+}
+
+/// A page that restarts the launch in a new window, where the session cookie can be set.
+fn open_in_new_window_page(initiation: &LtiLoginInitiation) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    query
+        .append_pair("iss", &initiation.iss)
+        .append_pair("login_hint", &initiation.login_hint);
+    if let Some(lti_message_hint) = &initiation.lti_message_hint {
+        query.append_pair("lti_message_hint", lti_message_hint);
+    }
+    if let Some(client_id) = &initiation.client_id {
+        query.append_pair("client_id", client_id);
+    }
+    // the encoded query only contains characters that are safe in HTML except for the separators
+    let login_url = format!("/api/v0/lti/login?{}", query.finish()).replace('&', "&amp;");
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Open the course</title></head>
+<body>
+<p>The course cannot be shown inside this page.</p>
+<p><a href="{}" target="_blank" rel="noopener">Open the course in a new window</a></p>
+</body>
+</html>
+"#,
+        login_url
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LtiLaunchForm {
+    id_token: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+/**
+POST `/api/v0/lti/launch` - The platform posts the launch message here.
+
+A resource link launch logs the user in and redirects them to the linked page. A deep linking launch redirects the teacher to select the page or the chapter to link to.
+*/
+#[instrument(skip(session, pool, form))]
+async fn launch(
+    session: Session,
+    pool: web::Data<PgPool>,
+    form: web::Form<LtiLaunchForm>,
+) -> ControllerResult<HttpResponse> {
+    let LtiLaunchForm {
+        id_token,
+        state,
+        error,
+    } = form.into_inner();
+    let (id_token, state) = match (id_token, state, error) {
+        (Some(id_token), Some(state), None) => (id_token, state),
+        (_, _, error) => {
+            return Err(ControllerError::new(
+                ControllerErrorType::Unauthorized,
+                format!(
+                    "The platform did not accept the launch: {}",
+                    error.as_deref().unwrap_or("missing launch message")
+                ),
+                None,
+            ));
+        }
+    };
+    let mut conn = pool.acquire().await?;
+    let login_state = models::lti_login_states::take(&mut conn, &state)
+        .await?
+        .ok_or_else(|| {
+            ControllerError::new(
+                ControllerErrorType::Unauthorized,
+                "The launch has expired or it has already been used.".to_string(),
+                None,
+            )
+        })?;
+    let registration =
+        lti_registrations::get_by_id(&mut conn, login_state.lti_registration_id).await?;
+    let launch = async {
+        let jwks = lti::fetch_platform_keys(&Client::new(), &registration).await?;
+        lti::validate_launch(&id_token, &registration, &jwks, &login_state.nonce)
+    }
+    .await
+    .map_err(|err| {
+        info!(error = ?err, "Invalid LTI launch");
+        ControllerError::new(
+            ControllerErrorType::Unauthorized,
+            "The launch from the platform could not be validated.".to_string(),
+            Some(err),
+        )
+    })?;
+
+    let token = skip_authorize()?;
+    match &launch.message {
+        LtiMessage::ResourceLink(resource_link) => {
+            let (user, path) =
+                lti::launch_resource_link(&mut conn, &registration, &launch, resource_link).await?;
+            authorization::remember(&session, user)?;
+            token.authorized_ok(
+                HttpResponse::SeeOther()
+                    .append_header(("location", path))
+                    .finish(),
+            )
+        }
+        LtiMessage::DeepLinking {
+            deep_link_return_url,
+            data,
+        } => {
+            let request_id = lti_deep_linking_requests::insert(
+                &mut conn,
+                registration.id,
+                &launch.deployment_id,
+                deep_link_return_url,
+                data.as_deref(),
+            )
+            .await?;
+            session
+                .insert(DEEP_LINKING_SESSION_KEY, request_id)
+                .map_err(|_| anyhow::anyhow!("Failed to insert to session"))?;
+            token.authorized_ok(
+                HttpResponse::SeeOther()
+                    .append_header(("location", format!("/lti/deep-linking/{}", request_id)))
+                    .finish(),
+            )
+        }
+    }
+}
+
+/**
+GET `/api/v0/lti/jwks` - Returns the public key the platforms validate the messages of the tool with.
+*/
+#[instrument(skip(lti_key))]
+async fn get_jwks(
+    lti_key: web::Data<Option<LtiToolKey>>,
+) -> ControllerResult<web::Json<JsonWebKeySet>> {
+    let lti_key = configured_key(&lti_key)?;
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(lti_key.jwks()))
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct LtiDeepLinkingPage {
+    pub id: Uuid,
+    pub title: String,
+    pub url_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct LtiDeepLinkingChapter {
+    pub id: Uuid,
+    pub name: String,
+    pub chapter_number: i32,
+    pub pages: Vec<LtiDeepLinkingPage>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct LtiDeepLinkingCourse {
+    pub id: Uuid,
+    pub name: String,
+    /// The pages that are not in any chapter.
+    pub pages: Vec<LtiDeepLinkingPage>,
+    pub chapters: Vec<LtiDeepLinkingChapter>,
+}
+
+/**
+GET `/api/v0/lti/deep-linking/{request_id}` - Returns the published pages and chapters of the courses the platform can link to.
+
+Only available in the session of the teacher the platform launched to select the content.
+*/
+#[generated_doc]
+#[instrument(skip(session, pool))]
+async fn get_deep_linking_content(
+    session: Session,
+    pool: web::Data<PgPool>,
+    request_id: web::Path<Uuid>,
+) -> ControllerResult<web::Json<Vec<LtiDeepLinkingCourse>>> {
+    check_deep_linking_session(&session, *request_id)?;
+    let mut conn = pool.acquire().await?;
+    let request = lti_deep_linking_requests::get_open_by_id(&mut conn, *request_id).await?;
+    let registration = lti_registrations::get_by_id(&mut conn, request.lti_registration_id).await?;
+    let courses = courses::organization_courses_visible_to_user_paginated(
+        &mut conn,
+        registration.organization_id,
+        None,
+        Pagination::new(1, 10_000)?,
+    )
+    .await?;
+    let mut res = vec![];
+    for course in courses {
+        let mut pages_by_chapter: HashMap<Option<Uuid>, Vec<pages::Page>> = HashMap::new();
+        for page in
+            pages::get_all_by_course_id_and_visibility(&mut conn, course.id, PageVisibility::Public)
+                .await?
+        {
+            pages_by_chapter
+                .entry(page.chapter_id)
+                .or_default()
+                .push(page);
+        }
+        let mut take_pages = |chapter_id: Option<Uuid>| {
+            let mut pages = pages_by_chapter.remove(&chapter_id).unwrap_or_default();
+            pages.sort_by_key(|page| page.order_number);
+            pages
+                .into_iter()
+                .map(|page| LtiDeepLinkingPage {
+                    id: page.id,
+                    title: page.title,
+                    url_path: page.url_path,
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut chapters = chapters::course_chapters(&mut conn, course.id).await?;
+        chapters.sort_by_key(|chapter| chapter.chapter_number);
+        let chapters = chapters
+            .into_iter()
+            .filter_map(|chapter| {
+                let pages = take_pages(Some(chapter.id));
+                // chapters without published pages are not published yet
+                (!pages.is_empty()).then(|| LtiDeepLinkingChapter {
+                    id: chapter.id,
+                    name: chapter.name,
+                    chapter_number: chapter.chapter_number,
+                    pages,
+                })
+            })
+            .collect();
+        res.push(LtiDeepLinkingCourse {
+            id: course.id,
+            name: course.name,
+            pages: take_pages(None),
+            chapters,
+        });
+    }
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(res))
+}
+
+/// Either a page or a chapter.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct LtiDeepLinkingSelection {
+    pub page_id: Option<Uuid>,
+    pub chapter_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct LtiDeepLinkingResponse {
+    /// Where the JWT is posted to in the `JWT` form field.
+    pub deep_link_return_url: String,
+    pub jwt: String,
+}
+
+/**
+POST `/api/v0/lti/deep-linking/{request_id}` - Creates the signed deep linking response that links the selected page or chapter to the platform. The frontend posts the response to the platform.
+
+Only published pages and chapters can be linked. Only available in the session of the teacher the platform launched to select the content.
+*/
+#[generated_doc]
+#[instrument(skip(session, pool, app_conf, lti_key))]
+async fn create_deep_linking_response(
+    session: Session,
+    pool: web::Data<PgPool>,
+    app_conf: web::Data<ApplicationConfiguration>,
+    lti_key: web::Data<Option<LtiToolKey>>,
+    request_id: web::Path<Uuid>,
+    payload: web::Json<LtiDeepLinkingSelection>,
+) -> ControllerResult<web::Json<LtiDeepLinkingResponse>> {
+    let lti_key = configured_key(&lti_key)?;
+    check_deep_linking_session(&session, *request_id)?;
+    let mut conn = pool.acquire().await?;
+    let mut tx = conn.begin().await?;
+    let request = lti_deep_linking_requests::get_open_by_id(&mut tx, *request_id).await?;
+    let registration = lti_registrations::get_by_id(&mut tx, request.lti_registration_id).await?;
+    let (target, title, course_id) = match (payload.page_id, payload.chapter_id) {
+        (Some(page_id), None) => {
+            let page = pages::get_page(&mut tx, page_id).await?;
+            (LtiLinkTarget::Page(page.id), page.title, page.course_id)
+        }
+        (None, Some(chapter_id)) => {
+            let chapter = chapters::get_chapter(&mut tx, chapter_id).await?;
+            if chapter.deleted_at.is_some() {
+                return Err(not_published());
+            }
+            (
+                LtiLinkTarget::Chapter(chapter.id),
+                chapter.name,
+                Some(chapter.course_id),
+            )
+        }
+        _ => {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "Select either a page or a chapter.".to_string(),
+                None,
+            ))
+        }
+    };
+    let course = match course_id {
+        Some(course_id) => courses::get_course(&mut tx, course_id).await?,
+        None => {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "Only course pages can be linked.".to_string(),
+                None,
+            ))
+        }
+    };
+    if course.organization_id != registration.organization_id {
+        return Err(ControllerError::new(
+            ControllerErrorType::Forbidden,
+            "The course is not available to this platform.".to_string(),
+            None,
+        ));
+    }
+    if course.is_draft || course.deleted_at.is_some() {
+        return Err(not_published());
+    }
+    // same rules as in the content listing: chapters without published pages are not published yet
+    let published_pages =
+        pages::get_all_by_course_id_and_visibility(&mut tx, course.id, PageVisibility::Public)
+            .await?;
+    let published = match target {
+        LtiLinkTarget::Page(page_id) => published_pages.iter().any(|page| page.id == page_id),
+        LtiLinkTarget::Chapter(chapter_id) => published_pages
+            .iter()
+            .any(|page| page.chapter_id == Some(chapter_id)),
+    };
+    if !published {
+        return Err(not_published());
+    }
+    let score_maximum = lti_resource_links::get_score_maximum(&mut tx, target).await?;
+    let jwt = lti::deep_linking_response(
+        lti_key,
+        &registration,
+        &request,
+        &lti::launch_url(&app_conf.base_url),
+        &title,
+        target,
+        score_maximum,
+    )?;
+    lti_deep_linking_requests::mark_responded(&mut tx, request.id).await?;
+    tx.commit().await?;
+
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(LtiDeepLinkingResponse {
+        deep_link_return_url: request.deep_link_return_url,
+        jwt,
+    }))
+}
+
+/// Checks that the deep linking request was started in this session.
+fn check_deep_linking_session(session: &Session, request_id: Uuid) -> ControllerResult<()> {
+    match session.get::<Uuid>(DEEP_LINKING_SESSION_KEY) {
+        Ok(Some(session_request_id)) if session_request_id == request_id => Ok(()),
+        _ => Err(ControllerError::new(
+            ControllerErrorType::Unauthorized,
+            "The content selection was not started in this browser session.".to_string(),
+            None,
+        )),
+    }
+}
+
+fn not_published() -> ControllerError {
+    ControllerError::new(
+        ControllerErrorType::BadRequest,
+        "Only published pages and chapters can be linked.".to_string(),
+        None,
+    )
+}
+
+fn configured_key(lti_key: &Option<LtiToolKey>) -> ControllerResult<&LtiToolKey> {
+    lti_key.as_ref().ok_or_else(|| {
+        ControllerError::new(
+            ControllerErrorType::NotFound,
+            "LTI has not been configured.".to_string(),
+            None,
+        )
+    })
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.service(
+        web::resource("/login")
+            .wrap(RateLimit::new("lti_login"))
+            .route(web::get().to(login_get))
+            .route(web::post().to(login_post)),
+    )
+    .service(
+        web::resource("/launch")
+            .wrap(RateLimit::new("lti_launch"))
+            .route(web::post().to(launch)),
+    )
+    .route("/jwks", web::get().to(get_jwks))
+    .route(
+        "/deep-linking/{request_id}",
+        web::get().to(get_deep_linking_content),
+    )
+    .route(
+        "/deep-linking/{request_id}",
+        web::post().to(create_deep_linking_response),
+    );
+}
@@ -0,0 +1,122 @@
+//! Controllers for requests starting with `/api/v0/main-frontend/api-tokens`.
+
+use chrono::{Duration, Utc};
+use models::api_tokens::{ApiToken, NewApiToken};
+
+use crate::{domain::api_tokens, prelude::*};
+
+/// Tokens have to expire so that forgotten tokens don't stay usable forever.
+const MAX_API_TOKEN_VALIDITY_DAYS: i64 = 365;
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct CreatedApiToken {
+    pub api_token: ApiToken,
+    /// The token to use in the `Authorization: Bearer` header. It is only shown once and cannot be retrieved later.
+    pub token: String,
+}
+
+/**
+GET `/api/v0/main-frontend/api-tokens` - Returns the current user's API tokens that have not been revoked.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_api_tokens(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<ApiToken>>> {
+    let mut conn = pool.acquire().await?;
+    let api_tokens = models::api_tokens::get_by_user_id(&mut conn, user.id).await?;
+    // users can only see their own tokens
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(api_tokens))
+}
+
+/**
+POST `/api/v0/main-frontend/api-tokens` - Creates a new API token for the current user.
+
+The response contains the token, which is not stored and cannot be retrieved later.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn create_api_token(
+    pool: web::Data<PgPool>,
+    payload: web::Json<NewApiToken>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<CreatedApiToken>> {
+    let mut conn = pool.acquire().await?;
+    forbid_api_token_authentication(&user)?;
+    let new_api_token = payload.into_inner();
+    if new_api_token.scopes.is_empty() {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "The API token needs at least one scope.".to_string(),
+            None,
+        ));
+    }
+    let now = Utc::now();
+    if new_api_token.expires_at <= now
+        || new_api_token.expires_at > now + Duration::days(MAX_API_TOKEN_VALIDITY_DAYS)
+    {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            format!(
+                "The API token must expire within {} days.",
+                MAX_API_TOKEN_VALIDITY_DAYS
+            ),
+            None,
+        ));
+    }
+
+    let (secret, secret_hash) = api_tokens::generate_token();
+    let api_token =
+        models::api_tokens::insert(&mut conn, user.id, &new_api_token, &secret_hash).await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(CreatedApiToken {
+        api_token,
+        token: secret,
+    }))
+}
+
+/**
+DELETE `/api/v0/main-frontend/api-tokens/:id` - Revokes the current user's API token.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn revoke_api_token(
+    pool: web::Data<PgPool>,
+    api_token_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let mut conn = pool.acquire().await?;
+    forbid_api_token_authentication(&user)?;
+    // the query only matches the user's own tokens
+    models::api_tokens::revoke(&mut conn, user.id, *api_token_id).await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(()))
+}
+
+/// A leaked token should not be usable for creating new tokens or revoking the other tokens.
+fn forbid_api_token_authentication(user: &AuthUser) -> Result<(), ControllerError> {
+    if user.api_token_id().is_some() {
+        return Err(ControllerError::new(
+            ControllerErrorType::Forbidden,
+            "API tokens cannot be managed using an API token.".to_string(),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.route("", web::get().to(get_api_tokens))
+        .route("", web::post().to(create_api_token))
+        .route("/{api_token_id}", web::delete().to(revoke_api_token));
+}
@@ -0,0 +1,103 @@
+//! Controllers for requests starting with `/api/v0/main-frontend/audit-log`.
+
+use bytes::Bytes;
+use chrono::Utc;
+use models::audit_log_entries::{AuditLogEntry, AuditLogFilter};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::{
+    domain::csv_export::{self, make_authorized_streamable, CSVExportAdapter},
+    prelude::*,
+};
+
+/**
+GET `/api/v0/main-frontend/audit-log` - Returns a paginated list of the audit log entries that match the filters, newest first.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_audit_log_entries(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+    filter: web::Query<AuditLogFilter>,
+    pagination: web::Query<Pagination>,
+) -> ControllerResult<web::Json<Vec<AuditLogEntry>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+    let res =
+        models::audit_log_entries::get_filtered_paginated(&mut conn, &filter, *pagination).await?;
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+GET `/api/v0/main-frontend/audit-log/count` - Counts the audit log entries that match the filters.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_audit_log_entry_count(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+    filter: web::Query<AuditLogFilter>,
+) -> ControllerResult<web::Json<i64>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+    let res = models::audit_log_entries::get_filtered_count(&mut conn, &filter).await?;
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+GET `/api/v0/main-frontend/audit-log/export` - Exports the audit log entries that match the filters as CSV, oldest first.
+*/
+#[instrument(skip(pool))]
+async fn export_audit_log(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+    filter: web::Query<AuditLogFilter>,
+) -> ControllerResult<HttpResponse> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+    let filter = filter.into_inner();
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<ControllerResult<Bytes>>();
+    // spawn handle that writes the csv row by row into the sender
+    let _handle = tokio::spawn(async move {
+        let res = csv_export::export_audit_log(
+            &mut conn,
+            &filter,
+            CSVExportAdapter {
+                sender,
+                authorization_token: token,
+            },
+        )
+        .await;
+        if let Err(err) = res {
+            tracing::error!("Failed to export audit log: {}", err);
+        }
+    });
+
+    // return response that streams data from the receiver
+    token.authorized_ok(
+        HttpResponse::Ok()
+            .append_header((
+                "Content-Disposition",
+                format!(
+                    "attachment; filename=\"Audit log {}.csv\"",
+                    Utc::now().format("%Y-%m-%d")
+                ),
+            ))
+            .streaming(make_authorized_streamable(UnboundedReceiverStream::new(
+                receiver,
+            ))),
+    )
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.route("", web::get().to(get_audit_log_entries))
+        .route("/count", web::get().to(get_audit_log_entry_count))
+        .route("/export", web::get().to(export_audit_log));
+}
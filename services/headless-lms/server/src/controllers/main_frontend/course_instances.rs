@@ -3,6 +3,7 @@
 use bytes::Bytes;
 use chrono::Utc;
 use models::{
+    audit_log_entries::AuditLogAction,
     course_instances::{self, CourseInstance, CourseInstanceForm, Points},
     courses,
     email_templates::{EmailTemplate, EmailTemplateNew},
@@ -17,7 +18,11 @@ use models::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
-    domain::csv_export::{self, make_authorized_streamable, CSVExportAdapter},
+    domain::{
+        audit_log,
+        csv_export::{self, make_authorized_streamable, CSVExportAdapter},
+        grade_export::{self, GradeExportFormat},
+    },
     prelude::*,
 };
 
@@ -237,8 +242,27 @@ async fn post_completions(
     let data = payload.0;
     let course_instance =
         course_instances::get_course_instance(&mut conn, *course_instance_id).await?;
-    library::progressing::add_manual_completions(&mut conn, user.id, &course_instance, &data)
-        .await?;
+    let mut tx = conn.begin().await?;
+    library::progressing::add_manual_completions(&mut tx, user.id, &course_instance, &data).await?;
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::ManualCompletionsAdded,
+        course_instance.id,
+        Some(course_instance.course_id),
+        None,
+        Some(serde_json::json!({
+            "course_module_id": data.course_module_id,
+            "user_ids": data
+                .new_completions
+                .iter()
+                .map(|completion| completion.user_id)
+                .collect::<Vec<_>>(),
+            "skip_duplicate_completions": data.skip_duplicate_completions,
+        })),
+    )
+    .await?;
+    tx.commit().await?;
     token.authorized_ok(web::Json(()))
 }
 
@@ -364,6 +388,72 @@ pub async fn completions_export(
     );
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GradeExportQuery {
+    pub format: GradeExportFormat,
+}
+
+/**
+GET `/api/v0/main-frontend/course-instances/{course_instance_id}/grades/export?format=Csv` - Exports the points, completions and grades of the students for importing them into an external gradebook. The format is one of `Csv`, `Xlsx` or `MoodleCsv`.
+*/
+#[instrument(skip(pool))]
+pub async fn grades_export(
+    course_instance_id: web::Path<Uuid>,
+    query: web::Query<GradeExportQuery>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<HttpResponse> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::CourseInstance(*course_instance_id),
+    )
+    .await?;
+    let course_instance_id = *course_instance_id;
+    let format = query.format;
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<ControllerResult<Bytes>>();
+    let mut handle_conn = pool.acquire().await?;
+    let _handle = tokio::spawn(async move {
+        let res = grade_export::export_course_instance_grades(
+            &mut handle_conn,
+            course_instance_id,
+            format,
+            CSVExportAdapter {
+                sender,
+                authorization_token: token,
+            },
+        )
+        .await;
+        if let Err(err) = res {
+            tracing::error!("Failed to export course instance grades: {}", err);
+        }
+    });
+
+    let course_instance =
+        course_instances::get_course_instance(&mut conn, course_instance_id).await?;
+    let course = courses::get_course(&mut conn, course_instance.course_id).await?;
+
+    token.authorized_ok(
+        HttpResponse::Ok()
+            .content_type(format.content_type())
+            .append_header((
+                "Content-Disposition",
+                format!(
+                    "attachment; filename=\"{} - {} - Grades export {}.{}\"",
+                    course.name,
+                    course_instance.name.as_deref().unwrap_or("unnamed"),
+                    Utc::now().format("%Y-%m-%d"),
+                    format.file_extension()
+                ),
+            ))
+            .streaming(make_authorized_streamable(UnboundedReceiverStream::new(
+                receiver,
+            ))),
+    )
+}
+
 /**
 Add a route for each controller in this module.
 
@@ -385,6 +475,10 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
             "/{course_instance_id}/points/export",
             web::get().to(point_export),
         )
+        .route(
+            "/{course_instance_id}/grades/export",
+            web::get().to(grades_export),
+        )
         .route("/{course_instance_id}/edit", web::post().to(edit))
         .route("/{course_instance_id}/delete", web::post().to(delete))
         .route(
@@ -4,7 +4,9 @@ use std::sync::Arc;
 
 use headless_lms_utils::strings::is_ietf_language_code_like;
 use models::{
+    audit_log_entries::AuditLogAction,
     chapters::Chapter,
+    content_lint_issues::CourseContentLintReport,
     course_instances::{CourseInstance, CourseInstanceForm, NewCourseInstance},
     course_modules::ModuleUpdates,
     courses::{Course, CourseStructure, CourseUpdate, NewCourse},
@@ -20,11 +22,15 @@ use models::{
     pages::Page,
     peer_review_configs::PeerReviewConfig,
     peer_review_questions::PeerReviewQuestion,
+    url_redirections::{NewUrlRedirection, UrlRedirection, UrlRedirectionWithResolution},
     user_exercise_states::ExerciseUserCounts,
 };
 
 use crate::{
-    domain::models_requests::{self, JwtKey},
+    domain::{
+        audit_log,
+        models_requests::{self, JwtKey},
+    },
     prelude::*,
 };
 
@@ -155,7 +161,23 @@ async fn delete_course(
         Res::Course(*course_id),
     )
     .await?;
-    let course = models::courses::delete_course(&mut conn, *course_id).await?;
+    let mut tx = conn.begin().await?;
+    let course = models::courses::delete_course(&mut tx, *course_id).await?;
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::CourseDeleted,
+        course.id,
+        Some(course.id),
+        Some(serde_json::json!({
+            "name": course.name,
+            "slug": course.slug,
+            "organization_id": course.organization_id,
+        })),
+        None,
+    )
+    .await?;
+    tx.commit().await?;
 
     token.authorized_ok(web::Json(course))
 }
@@ -863,6 +885,157 @@ async fn post_update_peer_review_queue_reviews_received(
     token.authorized_ok(web::Json(true))
 }
 
+/**
+GET `/api/v0/main-frontend/courses/{course_id}/content-lint-report` - Returns the problems found in the content of the course pages the last time the content linter checked the course.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_content_lint_report(
+    course_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<CourseContentLintReport>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Course(*course_id),
+    )
+    .await?;
+    let report = models::content_lint_issues::get_report_for_course(&mut conn, *course_id).await?;
+    token.authorized_ok(web::Json(report))
+}
+
+/**
+POST `/api/v0/main-frontend/courses/{course_id}/content-lint-report` - Checks the content of the course pages right away instead of waiting for the content linter and returns the new results.
+*/
+#[generated_doc]
+#[instrument(skip(pool, file_store, app_conf))]
+async fn post_content_lint_check(
+    course_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+    file_store: web::Data<dyn FileStore>,
+    app_conf: web::Data<ApplicationConfiguration>,
+) -> ControllerResult<web::Json<CourseContentLintReport>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Course(*course_id),
+    )
+    .await?;
+    library::content_linting::lint_course(
+        &mut conn,
+        *course_id,
+        file_store.as_ref(),
+        app_conf.as_ref(),
+    )
+    .await?;
+    let report = models::content_lint_issues::get_report_for_course(&mut conn, *course_id).await?;
+    token.authorized_ok(web::Json(report))
+}
+
+/**
+GET `/api/v0/main-frontend/courses/{course_id}/url-redirections` - Returns the url redirections of the course with where they finally lead, so that chains, cycles and broken redirections can be spotted.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_url_redirections(
+    course_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<UrlRedirectionWithResolution>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Course(*course_id),
+    )
+    .await?;
+    let redirections =
+        models::url_redirections::get_by_course_id_with_resolutions(&mut conn, *course_id).await?;
+    token.authorized_ok(web::Json(redirections))
+}
+
+/**
+POST `/api/v0/main-frontend/courses/{course_id}/url-redirections` - Creates a url redirection in the course. The destination can be a page or a url path in the course or in another language version of the course.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn post_url_redirection(
+    course_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+    payload: web::Json<NewUrlRedirection>,
+) -> ControllerResult<web::Json<UrlRedirection>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Course(*course_id)).await?;
+    let redirection =
+        models::url_redirections::insert_url_redirection(&mut conn, *course_id, &payload).await?;
+    token.authorized_ok(web::Json(redirection))
+}
+
+/**
+PUT `/api/v0/main-frontend/courses/{course_id}/url-redirections/{redirection_id}` - Changes the old url path and the destination of a url redirection.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn put_url_redirection(
+    path: web::Path<(Uuid, Uuid)>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+    payload: web::Json<NewUrlRedirection>,
+) -> ControllerResult<web::Json<UrlRedirection>> {
+    let (course_id, redirection_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Course(course_id)).await?;
+    let redirection = models::url_redirections::update_url_redirection(
+        &mut conn,
+        course_id,
+        redirection_id,
+        &payload,
+    )
+    .await?;
+    token.authorized_ok(web::Json(redirection))
+}
+
+/**
+DELETE `/api/v0/main-frontend/courses/{course_id}/url-redirections/{redirection_id}` - Deletes a url redirection.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_url_redirection(
+    path: web::Path<(Uuid, Uuid)>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let (course_id, redirection_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Course(course_id)).await?;
+    models::url_redirections::delete_url_redirection(&mut conn, course_id, redirection_id).await?;
+    token.authorized_ok(web::Json(()))
+}
+
+/**
+POST `/api/v0/main-frontend/courses/{course_id}/url-redirections/flatten` - Makes the redirections that lead to a page through other redirections lead directly to the page. Returns the number of changed redirections.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn post_flatten_url_redirections(
+    course_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<i64>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Course(*course_id)).await?;
+    let flattened = models::url_redirections::flatten_chains(&mut conn, *course_id).await?;
+    token.authorized_ok(web::Json(flattened))
+}
+
 /**
 Add a route for each controller in this module.
 
@@ -967,5 +1140,33 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
         .route(
             "/{course_id}/update-peer-review-queue-reviews-received",
             web::post().to(post_update_peer_review_queue_reviews_received),
+        )
+        .route(
+            "/{course_id}/content-lint-report",
+            web::get().to(get_content_lint_report),
+        )
+        .route(
+            "/{course_id}/content-lint-report",
+            web::post().to(post_content_lint_check),
+        )
+        .route(
+            "/{course_id}/url-redirections",
+            web::get().to(get_url_redirections),
+        )
+        .route(
+            "/{course_id}/url-redirections",
+            web::post().to(post_url_redirection),
+        )
+        .route(
+            "/{course_id}/url-redirections/flatten",
+            web::post().to(post_flatten_url_redirections),
+        )
+        .route(
+            "/{course_id}/url-redirections/{redirection_id}",
+            web::put().to(put_url_redirection),
+        )
+        .route(
+            "/{course_id}/url-redirections/{redirection_id}",
+            web::delete().to(delete_url_redirection),
         );
 }
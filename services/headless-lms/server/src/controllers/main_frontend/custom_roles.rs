@@ -0,0 +1,203 @@
+//! Controllers for requests starting with `/api/v0/main-frontend/custom-roles`.
+
+use models::{
+    custom_roles::{self, CustomRoleUpdate, CustomRoleWithPermissions, NewCustomRole},
+    role_permissions::{self, NewRolePermission, PermissionAction, RolePermission},
+    roles::UserRole,
+};
+
+use crate::prelude::*;
+
+/// Checks that the permissions are valid and only restrict the permissions of the base role, so that a custom role never allows more than its base role.
+async fn validate_custom_role(
+    conn: &mut PgConnection,
+    name: &str,
+    base_role: UserRole,
+    permissions: &[NewRolePermission],
+) -> Result<(), ControllerError> {
+    if name.trim().is_empty() {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "The custom role needs a name.".to_string(),
+            None,
+        ));
+    }
+    let base_role_permissions = role_permissions::get_by_built_in_role(conn, base_role).await?;
+    for permission in permissions {
+        if (permission.action == PermissionAction::EditRole) != permission.action_role.is_some() {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "The role to edit has to be given for the edit role action, and only for it."
+                    .to_string(),
+                None,
+            ));
+        }
+        if !base_role_permissions
+            .iter()
+            .any(|base_role_permission| permission.is_covered_by(base_role_permission))
+        {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "A custom role can only have permissions that its base role has.".to_string(),
+                None,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/**
+GET `/api/v0/main-frontend/custom-roles/organization/{organization_id}` - Returns the custom roles of the organization with their permissions.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_organization_custom_roles(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<CustomRoleWithPermissions>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    let custom_roles =
+        custom_roles::get_with_permissions_by_organization_id(&mut conn, *organization_id).await?;
+    token.authorized_ok(web::Json(custom_roles))
+}
+
+/**
+POST `/api/v0/main-frontend/custom-roles/organization/{organization_id}` - Defines a new custom role for the organization.
+
+The permissions have to be a subset of the permissions of the base role, and only users who can give the base role in the organization can define custom roles based on it.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn create_custom_role(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    payload: web::Json<NewCustomRole>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Uuid>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::EditRole(payload.base_role),
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    validate_custom_role(
+        &mut conn,
+        &payload.name,
+        payload.base_role,
+        &payload.permissions,
+    )
+    .await?;
+    let id = custom_roles::insert(&mut conn, *organization_id, &payload).await?;
+    token.authorized_ok(web::Json(id))
+}
+
+/**
+PUT `/api/v0/main-frontend/custom-roles/{id}` - Renames the custom role and replaces its permissions. The new permissions apply to everyone who has the role.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn update_custom_role(
+    pool: web::Data<PgPool>,
+    id: web::Path<Uuid>,
+    payload: web::Json<CustomRoleUpdate>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<bool>> {
+    let mut conn = pool.acquire().await?;
+    let custom_role = custom_roles::get_by_id(&mut conn, *id).await?;
+    let token = authorize(
+        &mut conn,
+        Act::EditRole(custom_role.base_role),
+        Some(user.id),
+        Res::Organization(custom_role.organization_id),
+    )
+    .await?;
+    validate_custom_role(
+        &mut conn,
+        &payload.name,
+        custom_role.base_role,
+        &payload.permissions,
+    )
+    .await?;
+    custom_roles::update(&mut conn, custom_role.id, &payload).await?;
+    token.authorized_ok(web::Json(true))
+}
+
+/**
+DELETE `/api/v0/main-frontend/custom-roles/{id}` - Deletes the custom role. The roles that have been given with it are removed.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_custom_role(
+    pool: web::Data<PgPool>,
+    id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<bool>> {
+    let mut conn = pool.acquire().await?;
+    let custom_role = custom_roles::get_by_id(&mut conn, *id).await?;
+    let token = authorize(
+        &mut conn,
+        Act::EditRole(custom_role.base_role),
+        Some(user.id),
+        Res::Organization(custom_role.organization_id),
+    )
+    .await?;
+    custom_roles::delete(&mut conn, custom_role.id).await?;
+    token.authorized_ok(web::Json(true))
+}
+
+/**
+GET `/api/v0/main-frontend/custom-roles/organization/{organization_id}/built-in-permissions/{role}` - Returns the permissions of a built-in role, i.e. the permissions a custom role based on it can have.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_built_in_role_permissions(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, UserRole)>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<RolePermission>>> {
+    let (organization_id, role) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    let permissions = role_permissions::get_by_built_in_role(&mut conn, role).await?;
+    token.authorized_ok(web::Json(permissions))
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.route(
+        "/organization/{organization_id}",
+        web::get().to(get_organization_custom_roles),
+    )
+    .route(
+        "/organization/{organization_id}",
+        web::post().to(create_custom_role),
+    )
+    .route(
+        "/organization/{organization_id}/built-in-permissions/{role}",
+        web::get().to(get_built_in_role_permissions),
+    )
+    .route("/{id}", web::put().to(update_custom_role))
+    .route("/{id}", web::delete().to(delete_custom_role));
+}
@@ -1,8 +1,17 @@
-use crate::{domain::models_requests, prelude::*};
+use crate::{
+    domain::{audit_log, models_requests},
+    prelude::*,
+};
 use headless_lms_models::exercise_slide_submissions::ExerciseSlideSubmissionInfo;
 use models::{
+    audit_log_entries::AuditLogAction,
     exercises::get_exercise_by_id,
-    library::user_exercise_state_updater,
+    library::{
+        peer_reviewing::{self, PeerReviewCalibrationSampleInfo},
+        user_exercise_state_updater,
+    },
+    peer_review_calibration_reference_answers::NewPeerReviewCalibrationReferenceAnswer,
+    peer_review_calibration_samples::PeerReviewCalibrationSampleWithReferenceAnswers,
     teacher_grading_decisions::{NewTeacherGradingDecision, TeacherDecisionType},
     user_exercise_states::UserExerciseState,
 };
@@ -59,9 +68,9 @@ async fn update_answer_requiring_attention(
         Res::Exercise(exercise_id),
     )
     .await?;
+    let exercise = get_exercise_by_id(&mut conn, exercise_id).await?;
     let points_given;
     if *action == TeacherDecisionType::FullPoints {
-        let exercise = get_exercise_by_id(&mut conn, exercise_id).await?;
         points_given = exercise.score_maximum as f32;
     } else if *action == TeacherDecisionType::ZeroPoints {
         points_given = 0.0;
@@ -82,6 +91,9 @@ async fn update_answer_requiring_attention(
         &action, points_given
     );
 
+    let old_user_exercise_state =
+        models::user_exercise_states::get_by_id(&mut conn, user_exercise_state_id).await?;
+
     let mut tx = conn.begin().await?;
 
     let _res = models::teacher_grading_decisions::add_teacher_grading_decision(
@@ -108,13 +120,125 @@ async fn update_answer_requiring_attention(
         .await?;
     }
 
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::TeacherGradingDecisionAdded,
+        user_exercise_state_id,
+        exercise.course_id,
+        Some(serde_json::json!({
+            "score_given": old_user_exercise_state.score_given,
+            "reviewing_stage": old_user_exercise_state.reviewing_stage,
+        })),
+        Some(serde_json::json!({
+            "action": action,
+            "points_given": points_given,
+            "score_given": new_user_exercise_state.score_given,
+            "reviewing_stage": new_user_exercise_state.reviewing_stage,
+        })),
+    )
+    .await?;
+
     tx.commit().await?;
 
     token.authorized_ok(web::Json(new_user_exercise_state))
 }
 
+/**
+GET `/api/v0/main-frontend/exercise-slide-submissions/{submission_id}/calibration-sample` - Returns the calibration sample and its reference answers if the submission has been marked as a calibration sample, along with the peer review questions that can be given reference answers.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_calibration_sample(
+    submission_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PeerReviewCalibrationSampleInfo>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::ExerciseSlideSubmission(*submission_id),
+    )
+    .await?;
+
+    let res =
+        peer_reviewing::get_peer_review_calibration_sample_info(&mut conn, *submission_id).await?;
+
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+PUT `/api/v0/main-frontend/exercise-slide-submissions/{submission_id}/calibration-sample` - Marks the submission as a calibration sample that students review before reviewing answers from other students.
+
+The payload contains the reference scores for the scale questions of the exercise's peer review. Any previous reference scores are replaced.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn put_calibration_sample(
+    submission_id: web::Path<Uuid>,
+    payload: web::Json<Vec<NewPeerReviewCalibrationReferenceAnswer>>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PeerReviewCalibrationSampleWithReferenceAnswers>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::ExerciseSlideSubmission(*submission_id),
+    )
+    .await?;
+
+    let res = peer_reviewing::upsert_peer_review_calibration_sample(
+        &mut conn,
+        *submission_id,
+        payload.into_inner(),
+    )
+    .await?;
+
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+DELETE `/api/v0/main-frontend/exercise-slide-submissions/{submission_id}/calibration-sample` - Stops using the submission as a calibration sample.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_calibration_sample(
+    submission_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::ExerciseSlideSubmission(*submission_id),
+    )
+    .await?;
+
+    peer_reviewing::remove_peer_review_calibration_sample(&mut conn, *submission_id).await?;
+
+    token.authorized_ok(web::Json(()))
+}
+
 pub fn _add_routes(cfg: &mut ServiceConfig) {
     cfg.route("/{submission_id}/info", web::get().to(get_submission_info))
+        .route(
+            "/{submission_id}/calibration-sample",
+            web::get().to(get_calibration_sample),
+        )
+        .route(
+            "/{submission_id}/calibration-sample",
+            web::put().to(put_calibration_sample),
+        )
+        .route(
+            "/{submission_id}/calibration-sample",
+            web::delete().to(delete_calibration_sample),
+        )
         .route(
             "/update-answer-requiring-attention",
             web::put().to(update_answer_requiring_attention),
@@ -4,7 +4,14 @@ use futures::future;
 
 use models::{
     exercise_slide_submissions::ExerciseSlideSubmission,
-    library::grading::AnswersRequiringAttention, CourseOrExamId,
+    exercise_task_submission_similarities::ExerciseTaskSubmissionSimilarityInfo,
+    library::{
+        grading::AnswersRequiringAttention,
+        peer_reviewing::{FlaggedPeerReview, ResolvePeerReviewSubmissionFlag},
+    },
+    peer_review_submission_flags::PeerReviewSubmissionFlag,
+    peer_review_submissions::PeerReviewReviewerStatistics,
+    CourseOrExamId,
 };
 
 use crate::{domain::models_requests, prelude::*};
@@ -89,6 +96,125 @@ async fn get_exercise_answers_requiring_attention(
     token.authorized_ok(web::Json(res))
 }
 
+/**
+GET `/api/v0/main-frontend/exercises/:exercise_id/flagged-peer-reviews` - Returns the peer reviews of the exercise that students have flagged and that are waiting for a teacher to resolve them.
+ */
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_flagged_peer_reviews(
+    pool: web::Data<PgPool>,
+    exercise_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<FlaggedPeerReview>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Exercise(*exercise_id),
+    )
+    .await?;
+    let res = models::library::peer_reviewing::get_unresolved_flagged_peer_reviews(
+        &mut conn,
+        *exercise_id,
+    )
+    .await?;
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+POST `/api/v0/main-frontend/exercises/:exercise_id/flagged-peer-reviews/:flag_id/resolve` - Resolves a flag on a peer review. The teacher decides whether the review is kept or removed.
+
+# Example
+
+Request:
+```http
+POST /api/v0/main-frontend/exercises/1c7a4b4a-d6f1-4b47-9b2e-7d9ad0a2a8f4/flagged-peer-reviews/5f8b3a2e-4bb5-4a1c-b8a6-3e41b5f2ad3a/resolve HTTP/1.1
+Content-Type: application/json
+
+{
+  "resolution": "ReviewRemoved"
+}
+```
+ */
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn resolve_peer_review_flag(
+    pool: web::Data<PgPool>,
+    params: web::Path<(Uuid, Uuid)>,
+    payload: web::Json<ResolvePeerReviewSubmissionFlag>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PeerReviewSubmissionFlag>> {
+    let mut conn = pool.acquire().await?;
+    let (exercise_id, flag_id) = params.into_inner();
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Exercise(exercise_id),
+    )
+    .await?;
+    let res = models::library::peer_reviewing::resolve_peer_review_submission_flag(
+        &mut conn,
+        exercise_id,
+        flag_id,
+        user.id,
+        payload.0,
+    )
+    .await?;
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+GET `/api/v0/main-frontend/exercises/:exercise_id/peer-review-reviewer-statistics` - Returns statistics for each student who has given peer reviews in the exercise, including the average helpfulness rating their reviews received.
+ */
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_peer_review_reviewer_statistics(
+    pool: web::Data<PgPool>,
+    exercise_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<PeerReviewReviewerStatistics>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Exercise(*exercise_id),
+    )
+    .await?;
+    let res = models::peer_review_submissions::get_reviewer_statistics_by_exercise_id(
+        &mut conn,
+        *exercise_id,
+    )
+    .await?;
+    token.authorized_ok(web::Json(res))
+}
+
+/**
+GET `/api/v0/main-frontend/exercises/:exercise_id/submission-similarities` - Returns the pairs of submissions the plagiarism detector has found to be similar, the most similar first.
+ */
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_submission_similarities(
+    pool: web::Data<PgPool>,
+    exercise_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<ExerciseTaskSubmissionSimilarityInfo>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Teach,
+        Some(user.id),
+        Res::Exercise(*exercise_id),
+    )
+    .await?;
+    let res =
+        models::exercise_task_submission_similarities::get_by_exercise_id(&mut conn, *exercise_id)
+            .await?;
+    token.authorized_ok(web::Json(res))
+}
+
 /**
 Add a route for each controller in this module.
 
@@ -104,5 +230,21 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
     .route(
         "/{exercise_id}/answers-requiring-attention",
         web::get().to(get_exercise_answers_requiring_attention),
+    )
+    .route(
+        "/{exercise_id}/flagged-peer-reviews",
+        web::get().to(get_flagged_peer_reviews),
+    )
+    .route(
+        "/{exercise_id}/flagged-peer-reviews/{flag_id}/resolve",
+        web::post().to(resolve_peer_review_flag),
+    )
+    .route(
+        "/{exercise_id}/peer-review-reviewer-statistics",
+        web::get().to(get_peer_review_reviewer_statistics),
+    )
+    .route(
+        "/{exercise_id}/submission-similarities",
+        web::get().to(get_submission_similarities),
     );
 }
@@ -0,0 +1,99 @@
+//! Controllers for requests starting with `/api/v0/main-frontend/impersonation`.
+
+use actix_session::Session;
+use chrono::Utc;
+use models::audit_log_entries::AuditLogAction;
+
+use crate::{
+    domain::{
+        audit_log,
+        authorization::{self, Impersonation},
+        impersonation::{self, ImpersonationEndReason},
+    },
+    prelude::*,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct NewImpersonation {
+    pub course_id: Uuid,
+    pub email: String,
+}
+
+/**
+POST `/api/v0/main-frontend/impersonation/start` - Starts viewing the course material as the given student of the course.
+
+While impersonating, the course material endpoints of the course are handled as the student and nothing can be modified. Only students who are enrolled on the course and have no roles can be impersonated.
+*/
+#[generated_doc]
+#[instrument(skip(pool, session))]
+async fn start_impersonation(
+    pool: web::Data<PgPool>,
+    session: Session,
+    payload: web::Json<NewImpersonation>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Impersonation>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Impersonate,
+        Some(user.id),
+        Res::Course(payload.course_id),
+    )
+    .await?;
+
+    let student = impersonation::get_impersonatable_student(
+        &mut conn,
+        user.id,
+        &payload.email,
+        payload.course_id,
+    )
+    .await?;
+
+    // recorded before starting so that there is never an impersonation that is missing from the log
+    audit_log::record(
+        &mut conn,
+        &user,
+        AuditLogAction::ImpersonationStarted,
+        student.id,
+        Some(payload.course_id),
+        None,
+        Some(serde_json::json!({
+            "max_duration_minutes": authorization::IMPERSONATION_MAX_DURATION_MINUTES
+        })),
+    )
+    .await?;
+    let impersonation =
+        authorization::start_impersonation(&session, student, payload.course_id, Utc::now())?;
+
+    token.authorized_ok(web::Json(impersonation))
+}
+
+/**
+POST `/api/v0/main-frontend/impersonation/stop` - Stops impersonating. Returns whether there was an impersonation to stop.
+*/
+#[generated_doc]
+#[instrument(skip(pool, session))]
+async fn stop_impersonation(
+    pool: web::Data<PgPool>,
+    session: Session,
+    user: AuthUser,
+) -> ControllerResult<web::Json<bool>> {
+    let mut conn = pool.acquire().await?;
+    let ended =
+        impersonation::end(&mut conn, &session, &user, ImpersonationEndReason::Stopped).await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(ended.is_some()))
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.route("/start", web::post().to(start_impersonation))
+        .route("/stop", web::post().to(stop_impersonation));
+}
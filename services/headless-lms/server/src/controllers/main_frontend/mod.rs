@@ -5,10 +5,13 @@ This documents all endpoints. Select a module below for a category.
 
 */
 
+pub mod api_tokens;
+pub mod audit_log;
 pub mod chapters;
 pub mod course_instances;
 pub mod course_modules;
 pub mod courses;
+pub mod custom_roles;
 pub mod email_templates;
 pub mod exams;
 pub mod exercise_repositories;
@@ -17,23 +20,29 @@ pub mod exercise_slide_submissions;
 pub mod exercises;
 pub mod feedback;
 pub mod glossary;
+pub mod impersonation;
 pub mod org;
 pub mod organizations;
 pub mod pages;
 pub mod playground_examples;
 pub mod proposed_edits;
 pub mod regradings;
+pub mod role_requests;
 pub mod roles;
+pub mod user_data_exports;
 pub mod users;
 
 use actix_web::web::{self, ServiceConfig};
 
 /// Add controllers from all the submodules.
 pub fn _add_routes(cfg: &mut ServiceConfig) {
-    cfg.service(web::scope("/chapters").configure(chapters::_add_routes))
+    cfg.service(web::scope("/api-tokens").configure(api_tokens::_add_routes))
+        .service(web::scope("/audit-log").configure(audit_log::_add_routes))
+        .service(web::scope("/chapters").configure(chapters::_add_routes))
         .service(web::scope("/course-instances").configure(course_instances::_add_routes))
         .service(web::scope("/course-modules").configure(course_modules::_add_routes))
         .service(web::scope("/courses").configure(courses::_add_routes))
+        .service(web::scope("/custom-roles").configure(custom_roles::_add_routes))
         .service(web::scope("/email-templates").configure(email_templates::_add_routes))
         .service(web::scope("/exercises").configure(exercises::_add_routes))
         .service(web::scope("/feedback").configure(feedback::_add_routes))
@@ -48,9 +57,12 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
         .service(web::scope("/exercise-services").configure(exercise_services::_add_routes))
         .service(web::scope("/playground_examples").configure(playground_examples::_add_routes))
         .service(web::scope("/users").configure(users::_add_routes))
+        .service(web::scope("/user-data-exports").configure(user_data_exports::_add_routes))
         .service(web::scope("/exams").configure(exams::_add_routes))
         .service(web::scope("/glossary").configure(glossary::_add_routes))
+        .service(web::scope("/impersonation").configure(impersonation::_add_routes))
         .service(web::scope("/roles").configure(roles::_add_routes))
+        .service(web::scope("/role-requests").configure(role_requests::_add_routes))
         .service(web::scope("/exercise-repositories").configure(exercise_repositories::_add_routes))
         .service(web::scope("/regradings").configure(regradings::_add_routes));
 }
@@ -5,13 +5,19 @@ use std::{path::PathBuf, str::FromStr};
 use models::{
     courses::{Course, CourseCount},
     exams::{CourseExam, NewExam, OrgExam},
+    identity_providers::{IdentityProviderInfo, NewIdentityProvider},
+    lti_registrations::{LtiRegistration, NewLtiRegistration},
     organizations::Organization,
     pages::{self, NewPage},
+    webhook_deliveries::WebhookDelivery,
+    webhook_delivery_attempts::WebhookDeliveryAttempt,
+    webhook_subscriptions::{NewWebhookSubscription, WebhookSubscription},
 };
 
 use crate::{
     controllers::helpers::media::upload_image_for_organization,
-    domain::authorization::skip_authorize, prelude::*,
+    domain::{authorization::skip_authorize, webhooks},
+    prelude::*,
 };
 use actix_web::web::{self, Json};
 
@@ -348,6 +354,332 @@ async fn create_exam(
     token.authorized_ok(web::Json(()))
 }
 
+/**
+GET `/api/v0/main-frontend/organizations/{organization_id}/identity-providers` - Returns the identity providers users can log in with.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_identity_providers(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<IdentityProviderInfo>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    let identity_providers =
+        models::identity_providers::get_by_organization_id(&mut conn, *organization_id).await?;
+    token.authorized_ok(web::Json(identity_providers))
+}
+
+/**
+POST `/api/v0/main-frontend/organizations/{organization_id}/identity-providers` - Adds an OpenID Connect identity provider users can log in with.
+
+Only global admins can add identity providers because the server fetches the discovery document from the issuer URL, and because a provider that links users by email can log in as the users whose emails it verifies.
+*/
+#[generated_doc]
+#[instrument(skip(pool, payload))]
+async fn create_identity_provider(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    payload: web::Json<NewIdentityProvider>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<IdentityProviderInfo>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+    let identity_provider = models::identity_providers::insert(
+        &mut conn,
+        PKeyPolicy::Generate,
+        *organization_id,
+        &payload,
+    )
+    .await?;
+    token.authorized_ok(web::Json(identity_provider))
+}
+
+/**
+DELETE `/api/v0/main-frontend/organizations/{organization_id}/identity-providers/{identity_provider_id}` - Removes an identity provider. Users who have logged in with it keep their accounts. Requires global admin permissions.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_identity_provider(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let (organization_id, identity_provider_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+    models::identity_providers::delete(&mut conn, organization_id, identity_provider_id).await?;
+    token.authorized_ok(web::Json(()))
+}
+
+/**
+GET `/api/v0/main-frontend/organizations/{organization_id}/lti-registrations` - Returns the LTI platforms that can launch the courses of the organization.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_lti_registrations(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<LtiRegistration>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    let registrations =
+        models::lti_registrations::get_by_organization_id(&mut conn, *organization_id).await?;
+    token.authorized_ok(web::Json(registrations))
+}
+
+/**
+POST `/api/v0/main-frontend/organizations/{organization_id}/lti-registrations` - Registers an LTI 1.3 platform, such as Moodle or Canvas, that can launch the courses of the organization.
+
+Only global admins can allow the platform to link its users to existing users with the same email, since the platform could then log in as those users.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn create_lti_registration(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    payload: web::Json<NewLtiRegistration>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<LtiRegistration>> {
+    let mut conn = pool.acquire().await?;
+    let resource = if payload.link_existing_users_by_email {
+        Res::GlobalPermissions
+    } else {
+        Res::Organization(*organization_id)
+    };
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), resource).await?;
+    let registration = models::lti_registrations::insert(
+        &mut conn,
+        PKeyPolicy::Generate,
+        *organization_id,
+        &payload,
+    )
+    .await?;
+    token.authorized_ok(web::Json(registration))
+}
+
+/**
+DELETE `/api/v0/main-frontend/organizations/{organization_id}/lti-registrations/{lti_registration_id}` - Removes an LTI platform. The platform can no longer launch courses, and scores are no longer passed back to it.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_lti_registration(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let (organization_id, lti_registration_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    models::lti_registrations::delete(&mut conn, organization_id, lti_registration_id).await?;
+    token.authorized_ok(web::Json(()))
+}
+
+/**
+GET `/api/v0/main-frontend/organizations/{organization_id}/webhooks` - Returns the webhooks that are sent events from the courses and exams of the organization.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_webhook_subscriptions(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<WebhookSubscription>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    let subscriptions =
+        models::webhook_subscriptions::get_by_organization_id(&mut conn, *organization_id).await?;
+    token.authorized_ok(web::Json(subscriptions))
+}
+
+/**
+POST `/api/v0/main-frontend/organizations/{organization_id}/webhooks` - Adds a webhook. The secret used for signing the requests is generated by the server.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn create_webhook_subscription(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    payload: web::Json<NewWebhookSubscription>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<WebhookSubscription>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    let subscription = models::webhook_subscriptions::insert(
+        &mut conn,
+        *organization_id,
+        &payload,
+        &webhooks::generate_secret(),
+    )
+    .await?;
+    token.authorized_ok(web::Json(subscription))
+}
+
+/**
+PUT `/api/v0/main-frontend/organizations/{organization_id}/webhooks/{webhook_id}` - Updates a webhook.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn update_webhook_subscription(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    payload: web::Json<NewWebhookSubscription>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<WebhookSubscription>> {
+    let (organization_id, webhook_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    let subscription =
+        models::webhook_subscriptions::update(&mut conn, organization_id, webhook_id, &payload)
+            .await?;
+    token.authorized_ok(web::Json(subscription))
+}
+
+/**
+DELETE `/api/v0/main-frontend/organizations/{organization_id}/webhooks/{webhook_id}` - Removes a webhook. Events that have not been delivered yet are not sent.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn delete_webhook_subscription(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<()>> {
+    let (organization_id, webhook_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    models::webhook_subscriptions::delete(&mut conn, organization_id, webhook_id).await?;
+    token.authorized_ok(web::Json(()))
+}
+
+/**
+GET `/api/v0/main-frontend/organizations/{organization_id}/webhooks/{webhook_id}/deliveries` - Returns the events sent or to be sent to a webhook, newest first.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_webhook_deliveries(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    pagination: web::Query<Pagination>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<WebhookDelivery>>> {
+    let (organization_id, webhook_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    // makes sure the webhook belongs to the organization
+    let subscription =
+        models::webhook_subscriptions::get_by_id(&mut conn, organization_id, webhook_id).await?;
+    let deliveries = models::webhook_deliveries::get_by_subscription_id_paginated(
+        &mut conn,
+        subscription.id,
+        *pagination,
+    )
+    .await?;
+    token.authorized_ok(web::Json(deliveries))
+}
+
+/**
+GET `/api/v0/main-frontend/organizations/{organization_id}/webhooks/deliveries/{delivery_id}/attempts` - Returns the attempts to deliver an event, newest first.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_webhook_delivery_attempts(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<WebhookDeliveryAttempt>>> {
+    let (organization_id, delivery_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    // makes sure the delivery belongs to the organization
+    let delivery =
+        models::webhook_deliveries::get_by_id(&mut conn, organization_id, delivery_id).await?;
+    let attempts =
+        models::webhook_delivery_attempts::get_by_webhook_delivery_id(&mut conn, delivery.id)
+            .await?;
+    token.authorized_ok(web::Json(attempts))
+}
+
+/**
+POST `/api/v0/main-frontend/organizations/{organization_id}/webhooks/deliveries/{delivery_id}/redeliver` - Sends an event again, for example after the receiver has been fixed. The event keeps its id so that the receiver can recognize duplicates.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn redeliver_webhook_delivery(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<WebhookDelivery>> {
+    let (organization_id, delivery_id) = path.into_inner();
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    let delivery =
+        models::webhook_deliveries::redeliver(&mut conn, organization_id, delivery_id).await?;
+    token.authorized_ok(web::Json(delivery))
+}
+
 /**
 Add a route for each controller in this module.
 
@@ -387,5 +719,57 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
             web::get().to(get_course_exams),
         )
         .route("/{organization_id}/org_exams", web::get().to(get_org_exams))
-        .route("/{organization_id}/exams", web::post().to(create_exam));
+        .route("/{organization_id}/exams", web::post().to(create_exam))
+        .route(
+            "/{organization_id}/identity-providers",
+            web::get().to(get_identity_providers),
+        )
+        .route(
+            "/{organization_id}/identity-providers",
+            web::post().to(create_identity_provider),
+        )
+        .route(
+            "/{organization_id}/identity-providers/{identity_provider_id}",
+            web::delete().to(delete_identity_provider),
+        )
+        .route(
+            "/{organization_id}/lti-registrations",
+            web::get().to(get_lti_registrations),
+        )
+        .route(
+            "/{organization_id}/lti-registrations",
+            web::post().to(create_lti_registration),
+        )
+        .route(
+            "/{organization_id}/lti-registrations/{lti_registration_id}",
+            web::delete().to(delete_lti_registration),
+        )
+        .route(
+            "/{organization_id}/webhooks",
+            web::get().to(get_webhook_subscriptions),
+        )
+        .route(
+            "/{organization_id}/webhooks",
+            web::post().to(create_webhook_subscription),
+        )
+        .route(
+            "/{organization_id}/webhooks/{webhook_id}",
+            web::put().to(update_webhook_subscription),
+        )
+        .route(
+            "/{organization_id}/webhooks/{webhook_id}",
+            web::delete().to(delete_webhook_subscription),
+        )
+        .route(
+            "/{organization_id}/webhooks/{webhook_id}/deliveries",
+            web::get().to(get_webhook_deliveries),
+        )
+        .route(
+            "/{organization_id}/webhooks/deliveries/{delivery_id}/attempts",
+            web::get().to(get_webhook_delivery_attempts),
+        )
+        .route(
+            "/{organization_id}/webhooks/deliveries/{delivery_id}/redeliver",
+            web::post().to(redeliver_webhook_delivery),
+        );
 }
@@ -3,12 +3,16 @@
 use std::sync::Arc;
 
 use models::{
-    page_history::PageHistory,
+    audit_log_entries::AuditLogAction,
+    page_history::{PageHistory, PageHistoryDiff},
     pages::{HistoryRestoreData, NewPage, Page, PageInfo},
 };
 
 use crate::{
-    domain::models_requests::{self, JwtKey},
+    domain::{
+        audit_log,
+        models_requests::{self, JwtKey},
+    },
     prelude::*,
 };
 
@@ -128,6 +132,37 @@ async fn history_count(
     token.authorized_ok(web::Json(res))
 }
 
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct PageHistoryDiffQuery {
+    old_history_id: Uuid,
+    new_history_id: Uuid,
+}
+
+/**
+GET `/api/v0/main-frontend/pages/:page_id/history-diff?old_history_id=...&new_history_id=...` - Returns the blocks, exercises, slides, tasks and peer review configs that were added, removed, moved or modified between the two versions of the page.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn history_diff(
+    pool: web::Data<PgPool>,
+    page_id: web::Path<Uuid>,
+    query: web::Query<PageHistoryDiffQuery>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<PageHistoryDiff>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Teach, Some(user.id), Res::Page(*page_id)).await?;
+    let res = models::page_history::diff(
+        &mut conn,
+        *page_id,
+        query.old_history_id,
+        query.new_history_id,
+    )
+    .await?;
+
+    token.authorized_ok(web::Json(res))
+}
+
 /**
 POST /api/v0/main-frontend/pages/:page_id/restore
 */
@@ -142,8 +177,10 @@ async fn restore(
 ) -> ControllerResult<web::Json<Uuid>> {
     let mut conn = pool.acquire().await?;
     let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::Page(*page_id)).await?;
+    let page = models::pages::get_page(&mut conn, *page_id).await?;
+    let mut tx = conn.begin().await?;
     let res = models::pages::restore(
-        &mut conn,
+        &mut tx,
         *page_id,
         restore_data.history_id,
         user.id,
@@ -151,6 +188,17 @@ async fn restore(
         models_requests::fetch_service_info,
     )
     .await?;
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::PageRestored,
+        page.id,
+        page.course_id,
+        Some(serde_json::json!({ "title": page.title })),
+        Some(serde_json::json!({ "restored_history_id": restore_data.history_id })),
+    )
+    .await?;
+    tx.commit().await?;
 
     token.authorized_ok(web::Json(res))
 }
@@ -187,5 +235,6 @@ pub fn _add_routes(cfg: &mut ServiceConfig) {
         .route("/{page_id}/info", web::get().to(get_page_info))
         .route("/{page_id}/history", web::get().to(history))
         .route("/{page_id}/history_count", web::get().to(history_count))
+        .route("/{page_id}/history-diff", web::get().to(history_diff))
         .route("/{history_id}/restore", web::post().to(restore));
 }
@@ -1,8 +1,11 @@
 //! Controllers for requests starting with `/api/v0/main-frontend/regradings/`.
 
-use models::regradings::{NewRegrading, Regrading, RegradingInfo};
+use models::{
+    audit_log_entries::AuditLogAction,
+    regradings::{NewRegrading, Regrading, RegradingInfo},
+};
 
-use crate::prelude::*;
+use crate::{domain::audit_log, prelude::*};
 
 /**
 GET `/api/v0/main-frontend/regradings` - Returns a paginated list of all the regradings.
@@ -47,12 +50,25 @@ async fn create_by_exercise_task_submission_ids(
 ) -> ControllerResult<web::Json<Uuid>> {
     let mut conn = pool.acquire().await?;
     let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+    let summary = serde_json::json!(new_regrading.0);
+    let mut tx = conn.begin().await?;
     let res = models::regradings::insert_and_create_exercise_task_regradings(
-        &mut conn,
+        &mut tx,
         new_regrading.0,
         user.id,
     )
     .await?;
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::RegradingCreated,
+        res,
+        None,
+        None,
+        Some(summary),
+    )
+    .await?;
+    tx.commit().await?;
     token.authorized_ok(web::Json(res))
 }
 
@@ -0,0 +1,201 @@
+//! Controllers for requests starting with `/api/v0/main-frontend/role-requests`.
+
+use models::{
+    audit_log_entries::AuditLogAction,
+    role_requests::{self, NewRoleRequest, RoleRequest},
+    roles::{self, RoleDomain, UserRole},
+};
+
+use crate::{domain::audit_log, prelude::*};
+
+/// Checks that the user can decide on the request, i.e. that they could give the requested role on the course's organization.
+async fn authorize_decision(
+    conn: &mut PgConnection,
+    role_request: &RoleRequest,
+    user_id: Uuid,
+) -> ControllerResult<()> {
+    let organization_id =
+        models::courses::get_organization_id(conn, role_request.course_id).await?;
+    let token = authorize(
+        conn,
+        Act::EditRole(role_request.role),
+        Some(user_id),
+        Res::Organization(organization_id),
+    )
+    .await?;
+    token.authorized_ok(())
+}
+
+/**
+GET `/api/v0/main-frontend/role-requests` - Returns the current user's role requests, newest first.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_own_role_requests(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<RoleRequest>>> {
+    let mut conn = pool.acquire().await?;
+    let role_requests = role_requests::get_by_user_id(&mut conn, user.id).await?;
+    // users can only see their own requests
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(role_requests))
+}
+
+/**
+POST `/api/v0/main-frontend/role-requests` - Requests a role on a course. The request is approved or rejected by an admin of the course's organization.
+
+Only users who teach on some course can request roles.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn create_role_request(
+    pool: web::Data<PgPool>,
+    payload: web::Json<NewRoleRequest>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<RoleRequest>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Teach, Some(user.id), Res::AnyCourse).await?;
+
+    let new_role_request = payload.into_inner();
+    if !matches!(
+        new_role_request.role,
+        UserRole::Teacher | UserRole::Assistant | UserRole::Reviewer | UserRole::MaterialViewer
+    ) {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "This role cannot be requested for a course.".to_string(),
+            None,
+        ));
+    }
+    if new_role_request.reason.trim().is_empty() {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "The reason for the request is required.".to_string(),
+            None,
+        ));
+    }
+    if let (Some(valid_from), Some(valid_until)) =
+        (new_role_request.valid_from, new_role_request.valid_until)
+    {
+        if valid_from >= valid_until {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "The role has to be valid from before it is valid until.".to_string(),
+                None,
+            ));
+        }
+    }
+
+    let id = role_requests::insert(&mut conn, user.id, &new_role_request).await?;
+    let role_request = role_requests::get_by_id(&mut conn, id).await?;
+    token.authorized_ok(web::Json(role_request))
+}
+
+/**
+GET `/api/v0/main-frontend/role-requests/organization/{organization_id}` - Returns the pending role requests for the courses of the organization, oldest first.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_pending_role_requests_for_organization(
+    pool: web::Data<PgPool>,
+    organization_id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<RoleRequest>>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(
+        &mut conn,
+        Act::Edit,
+        Some(user.id),
+        Res::Organization(*organization_id),
+    )
+    .await?;
+    let role_requests =
+        role_requests::get_pending_by_organization_id(&mut conn, *organization_id).await?;
+    token.authorized_ok(web::Json(role_requests))
+}
+
+/**
+POST `/api/v0/main-frontend/role-requests/{id}/approve` - Approves the role request and gives the requested role on the course to the user for the requested period.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn approve_role_request(
+    pool: web::Data<PgPool>,
+    id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<bool>> {
+    let mut conn = pool.acquire().await?;
+    let role_request = role_requests::get_by_id(&mut conn, *id).await?;
+    authorize_decision(&mut conn, &role_request, user.id).await?;
+
+    let domain = RoleDomain::Course(role_request.course_id);
+    let mut tx = conn.begin().await?;
+    let role_id = roles::insert_with_validity(
+        &mut tx,
+        role_request.user_id,
+        role_request.role,
+        domain,
+        role_request.valid_from,
+        role_request.valid_until,
+    )
+    .await?;
+    role_requests::approve(&mut tx, role_request.id, user.id, role_id).await?;
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::RoleAdded,
+        role_request.user_id,
+        Some(role_request.course_id),
+        None,
+        Some(serde_json::json!({
+            "role": role_request.role,
+            "domain": domain,
+            "valid_from": role_request.valid_from,
+            "valid_until": role_request.valid_until,
+            "role_request_id": role_request.id,
+        })),
+    )
+    .await?;
+    tx.commit().await?;
+
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(true))
+}
+
+/**
+POST `/api/v0/main-frontend/role-requests/{id}/reject` - Rejects the role request.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn reject_role_request(
+    pool: web::Data<PgPool>,
+    id: web::Path<Uuid>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<bool>> {
+    let mut conn = pool.acquire().await?;
+    let role_request = role_requests::get_by_id(&mut conn, *id).await?;
+    authorize_decision(&mut conn, &role_request, user.id).await?;
+    role_requests::reject(&mut conn, role_request.id, user.id).await?;
+
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(true))
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.route("", web::get().to(get_own_role_requests))
+        .route("", web::post().to(create_role_request))
+        .route(
+            "/organization/{organization_id}",
+            web::get().to(get_pending_role_requests_for_organization),
+        )
+        .route("/{id}/approve", web::post().to(approve_role_request))
+        .route("/{id}/reject", web::post().to(reject_role_request));
+}
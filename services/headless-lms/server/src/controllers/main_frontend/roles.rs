@@ -1,5 +1,7 @@
-use crate::prelude::*;
+use crate::{domain::audit_log, prelude::*};
 use models::{
+    audit_log_entries::AuditLogAction,
+    course_instances, courses, custom_roles, exams,
     pending_roles::{self, PendingRole},
     roles::{self, RoleDomain, RoleInfo, RoleUser},
     users,
@@ -30,6 +32,43 @@ async fn authorize_role_management(
     token.authorized_ok(())
 }
 
+/// The course the role is related to, for filtering the audit log by course.
+async fn role_domain_course_id(
+    conn: &mut PgConnection,
+    domain: RoleDomain,
+) -> Result<Option<Uuid>, ControllerError> {
+    let course_id = match domain {
+        RoleDomain::Course(id) => Some(id),
+        RoleDomain::CourseInstance(id) => Some(
+            course_instances::get_course_instance(conn, id)
+                .await?
+                .course_id,
+        ),
+        RoleDomain::Global | RoleDomain::Organization(_) | RoleDomain::Exam(_) => None,
+    };
+    Ok(course_id)
+}
+
+/// The organization the role is given in. Custom roles can only be given in the organization that defined them.
+async fn role_domain_organization_id(
+    conn: &mut PgConnection,
+    domain: RoleDomain,
+) -> Result<Option<Uuid>, ControllerError> {
+    let organization_id = match domain {
+        RoleDomain::Global => None,
+        RoleDomain::Organization(id) => Some(id),
+        RoleDomain::Course(id) => Some(courses::get_organization_id(conn, id).await?),
+        RoleDomain::CourseInstance(id) => {
+            let course_id = course_instances::get_course_instance(conn, id)
+                .await?
+                .course_id;
+            Some(courses::get_organization_id(conn, course_id).await?)
+        }
+        RoleDomain::Exam(id) => Some(exams::get_organization_id(conn, id).await?),
+    };
+    Ok(organization_id)
+}
+
 /**
  * POST /api/v0/main-frontend/roles/set - Give a role to a user.
  */
@@ -48,8 +87,68 @@ pub async fn set(
     )
     .await?;
 
+    if let (Some(valid_from), Some(valid_until)) = (role_info.valid_from, role_info.valid_until) {
+        if valid_from >= valid_until {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "The role has to be valid from before it is valid until.".to_string(),
+                None,
+            ));
+        }
+    }
+
+    if let Some(custom_role_id) = role_info.custom_role_id {
+        let custom_role = custom_roles::get_by_id(&mut conn, custom_role_id).await?;
+        if custom_role.base_role != role_info.role {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "The role has to be the base role of the custom role.".to_string(),
+                None,
+            ));
+        }
+        let organization_id = role_domain_organization_id(&mut conn, role_info.domain).await?;
+        if organization_id != Some(custom_role.organization_id) {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "The custom role can only be given in the organization that defined it."
+                    .to_string(),
+                None,
+            ));
+        }
+    }
+
     let target_user = users::get_by_email(&mut conn, &role_info.email).await?;
-    roles::insert(&mut conn, target_user.id, role_info.role, role_info.domain).await?;
+    let course_id = role_domain_course_id(&mut conn, role_info.domain).await?;
+    let mut tx = conn.begin().await?;
+    let role_id = roles::insert_with_validity(
+        &mut tx,
+        target_user.id,
+        role_info.role,
+        role_info.domain,
+        role_info.valid_from,
+        role_info.valid_until,
+    )
+    .await?;
+    if role_info.custom_role_id.is_some() {
+        roles::set_custom_role(&mut tx, role_id, role_info.custom_role_id).await?;
+    }
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::RoleAdded,
+        target_user.id,
+        course_id,
+        None,
+        Some(serde_json::json!({
+            "role": role_info.role,
+            "domain": role_info.domain,
+            "valid_from": role_info.valid_from,
+            "valid_until": role_info.valid_until,
+            "custom_role_id": role_info.custom_role_id,
+        })),
+    )
+    .await?;
+    tx.commit().await?;
 
     let token = skip_authorize()?;
     token.authorized_ok(HttpResponse::Ok().finish())
@@ -73,7 +172,23 @@ pub async fn unset(
     )
     .await?;
     let target_user = users::get_by_email(&mut conn, &role_info.email).await?;
-    roles::remove(&mut conn, target_user.id, role_info.role, role_info.domain).await?;
+    let course_id = role_domain_course_id(&mut conn, role_info.domain).await?;
+    let mut tx = conn.begin().await?;
+    roles::remove(&mut tx, target_user.id, role_info.role, role_info.domain).await?;
+    audit_log::record(
+        &mut tx,
+        &user,
+        AuditLogAction::RoleRemoved,
+        target_user.id,
+        course_id,
+        Some(serde_json::json!({
+            "role": role_info.role,
+            "domain": role_info.domain,
+        })),
+        None,
+    )
+    .await?;
+    tx.commit().await?;
 
     let token = skip_authorize()?;
     token.authorized_ok(HttpResponse::Ok().finish())
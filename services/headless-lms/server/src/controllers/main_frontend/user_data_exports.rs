@@ -0,0 +1,87 @@
+//! Controllers for requests starting with `/api/v0/main-frontend/user-data-exports`.
+
+use std::path::PathBuf;
+
+use models::user_data_exports::UserDataExport;
+
+use crate::prelude::*;
+
+/**
+GET `/api/v0/main-frontend/user-data-exports` - Returns the current user's data exports, newest first.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn get_user_data_exports(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<Vec<UserDataExport>>> {
+    let mut conn = pool.acquire().await?;
+    let exports = models::user_data_exports::get_by_user_id(&mut conn, user.id).await?;
+    // users can only see their own exports
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(exports))
+}
+
+/**
+POST `/api/v0/main-frontend/user-data-exports` - Requests an export of all the data the system holds about the current user.
+
+The export is generated in the background. It can be downloaded once it has been completed.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+async fn create_user_data_export(
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<UserDataExport>> {
+    let mut conn = pool.acquire().await?;
+    let exports = models::user_data_exports::get_by_user_id(&mut conn, user.id).await?;
+    if exports.iter().any(|export| export.completed_at.is_none()) {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "A data export is already being generated.".to_string(),
+            None,
+        ));
+    }
+    let export = models::user_data_exports::insert(&mut conn, user.id).await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(web::Json(export))
+}
+
+/**
+GET `/api/v0/main-frontend/user-data-exports/{id}/download` - Redirects to a short-lived download link of the completed export.
+*/
+#[instrument(skip(pool, file_store))]
+async fn download_user_data_export(
+    pool: web::Data<PgPool>,
+    id: web::Path<Uuid>,
+    user: AuthUser,
+    file_store: web::Data<dyn FileStore>,
+) -> ControllerResult<HttpResponse> {
+    let mut conn = pool.acquire().await?;
+    // only returns the user's own exports
+    let file_path =
+        models::user_data_exports::get_downloadable_file_path(&mut conn, user.id, *id).await?;
+    let url = file_store
+        .get_direct_download_url(&PathBuf::from(file_path))
+        .await?;
+    let token = skip_authorize()?;
+    token.authorized_ok(
+        HttpResponse::Found()
+            .append_header(("location", url))
+            .append_header(("cache-control", "no-store"))
+            .finish(),
+    )
+}
+
+/**
+Add a route for each controller in this module.
+
+The name starts with an underline in order to appear before other functions in the module documentation.
+
+We add the routes by calling the route method instead of using the route annotations because this method preserves the function signatures for documentation.
+*/
+pub fn _add_routes(cfg: &mut ServiceConfig) {
+    cfg.route("", web::get().to(get_user_data_exports))
+        .route("", web::post().to(create_user_data_export))
+        .route("/{id}/download", web::get().to(download_user_data_export));
+}
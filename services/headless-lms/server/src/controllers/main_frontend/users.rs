@@ -1,6 +1,6 @@
-use models::users::User;
+use models::{audit_log_entries::AuditLogAction, library::user_erasure, users::User};
 
-use crate::prelude::*;
+use crate::{domain::audit_log, prelude::*};
 
 /**
 GET `/api/v0/main-frontend/users/:id`
@@ -18,6 +18,41 @@ pub async fn get_user(
     token.authorized_ok(web::Json(user))
 }
 
+/**
+POST `/api/v0/main-frontend/users/:id/erase` - Erases the personal data of the user.
+
+The user's submissions, points and completions are kept, but they can no longer be linked to the person. Erasing an already erased user does nothing. Returns whether the user was erased by this request.
+*/
+#[generated_doc]
+#[instrument(skip(pool))]
+pub async fn erase_user(
+    user_id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    user: AuthUser,
+) -> ControllerResult<web::Json<bool>> {
+    let mut conn = pool.acquire().await?;
+    let token = authorize(&mut conn, Act::Edit, Some(user.id), Res::GlobalPermissions).await?;
+
+    let mut tx = conn.begin().await?;
+    let erased = user_erasure::erase_user(&mut tx, *user_id).await?;
+    if erased {
+        audit_log::record(
+            &mut tx,
+            &user,
+            AuditLogAction::UserErased,
+            *user_id,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+
+    token.authorized_ok(web::Json(erased))
+}
+
 pub fn _add_routes(cfg: &mut ServiceConfig) {
-    cfg.route("/{user_id}", web::get().to(get_user));
+    cfg.route("/{user_id}", web::get().to(get_user))
+        .route("/{user_id}/erase", web::post().to(erase_user));
 }
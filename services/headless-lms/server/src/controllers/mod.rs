@@ -13,6 +13,7 @@ pub mod cms;
 pub mod course_material;
 pub mod files;
 pub mod helpers;
+pub mod lti;
 pub mod main_frontend;
 pub mod study_registry;
 
@@ -37,5 +38,6 @@ pub fn configure_controllers(cfg: &mut ServiceConfig) {
         .service(web::scope("/files").configure(files::_add_routes))
         .service(web::scope("/main-frontend").configure(main_frontend::_add_routes))
         .service(web::scope("/auth").configure(auth::_add_routes))
+        .service(web::scope("/lti").configure(lti::_add_routes))
         .service(web::scope("/study-registry").configure(study_registry::_add_routes));
 }
@@ -0,0 +1,199 @@
+/*!
+Personal API tokens for accessing the API from scripts.
+
+A request with the header `Authorization: Bearer <token>` is authenticated by the [ApiTokenAuthentication] middleware as the user who created the token, so the [AuthUser] extractor works the same way as with a session. While the request is handled, the scopes of the token are available through [current_api_token] and `authorization::authorize` only allows the actions the scopes permit.
+
+Endpoints that don't call `authorize` can't check the scopes, so `authorization::skip_authorize` rejects requests made with an API token. Requests that modify something are also rejected outright if the token only has the `read` scope.
+*/
+
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_http::{header::AUTHORIZATION, HttpMessage};
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+use futures_util::future::LocalBoxFuture;
+use headless_lms_models::api_tokens::{self, ApiTokenScope};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+
+use crate::{domain::authorization, prelude::*};
+
+/// All tokens start with this prefix so that they are easy to recognize, for example by secret scanners.
+pub const TOKEN_PREFIX: &str = "mooc_pat_";
+const TOKEN_RANDOM_PART_LENGTH: usize = 40;
+
+tokio::task_local! {
+    static CURRENT_API_TOKEN: CurrentApiToken;
+}
+
+/// The API token the current request was authenticated with.
+#[derive(Debug, Clone)]
+pub struct CurrentApiToken {
+    pub scopes: Vec<ApiTokenScope>,
+    /// True if the request method does not modify anything, e.g. GET.
+    pub read_only_request: bool,
+}
+
+/// Generates a new token. Returns the token, which should be shown to the user only once, and its hash, which should be stored.
+pub fn generate_token() -> (String, String) {
+    let random_part: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_RANDOM_PART_LENGTH)
+        .map(char::from)
+        .collect();
+    let token = format!("{}{}", TOKEN_PREFIX, random_part);
+    let token_hash = hash_token(&token);
+    (token, token_hash)
+}
+
+/// The tokens have enough entropy that a plain hash is enough, there's no need for a slow password hash.
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// The API token the current request was authenticated with. None if the request was not authenticated with an API token.
+pub fn current_api_token() -> Option<CurrentApiToken> {
+    CURRENT_API_TOKEN.try_with(Clone::clone).ok()
+}
+
+pub struct ApiTokenAuthentication;
+
+impl<S, B> Transform<S, ServiceRequest> for ApiTokenAuthentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ApiTokenAuthenticationMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiTokenAuthenticationMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Authenticates requests that have an API token as the bearer token. Other requests are passed through as is.
+pub struct ApiTokenAuthenticationMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiTokenAuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let token = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| token.starts_with(TOKEN_PREFIX))
+            .map(str::to_string);
+        let token = match token {
+            Some(token) => token,
+            // for example the study registry uses its own authorization header
+            None => return Box::pin(self.service.call(req)),
+        };
+
+        let service = Rc::clone(&self.service);
+        Box::pin(async move {
+            let pool = req
+                .app_data::<web::Data<PgPool>>()
+                .expect("Missing database pool in app data")
+                .clone();
+            let mut conn = pool.acquire().await.map_err(ControllerError::from)?;
+            let api_token = api_tokens::try_get_valid_by_token_hash(&mut conn, &hash_token(&token))
+                .await
+                .map_err(ControllerError::from)?
+                .ok_or_else(|| {
+                    ControllerError::new(
+                        ControllerErrorType::Unauthorized,
+                        "Invalid, expired or revoked API token.".to_string(),
+                        None,
+                    )
+                })?;
+            let user = models::users::get_by_id(&mut conn, api_token.user_id)
+                .await
+                .map_err(ControllerError::from)?;
+            api_tokens::update_last_used_at(&mut conn, api_token.id)
+                .await
+                .map_err(ControllerError::from)?;
+            drop(conn);
+
+            let read_only_request = req.method().is_safe();
+            if !read_only_request
+                && api_token
+                    .scopes
+                    .iter()
+                    .all(|scope| *scope == ApiTokenScope::Read)
+            {
+                return Err(ControllerError::new(
+                    ControllerErrorType::Forbidden,
+                    "The API token can only be used for requests that do not modify anything."
+                        .to_string(),
+                    None,
+                )
+                .into());
+            }
+
+            authorization::remember_for_request(&mut req.extensions_mut(), user, api_token.id);
+            let current_api_token = CurrentApiToken {
+                scopes: api_token.scopes,
+                read_only_request,
+            };
+            CURRENT_API_TOKEN
+                .scope(current_api_token, service.call(req))
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generates_prefixed_tokens_with_matching_hashes() {
+        let (token, token_hash) = generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + TOKEN_RANDOM_PART_LENGTH);
+        assert_eq!(token_hash, hash_token(&token));
+        assert_eq!(token_hash.len(), 64);
+        let (other_token, _) = generate_token();
+        assert_ne!(token, other_token);
+    }
+
+    #[tokio::test]
+    async fn endpoints_without_scope_checks_reject_api_tokens() {
+        assert!(authorization::skip_authorize().is_ok());
+        let api_token = CurrentApiToken {
+            scopes: vec![ApiTokenScope::Grade, ApiTokenScope::EditContent],
+            read_only_request: false,
+        };
+        let res = CURRENT_API_TOKEN
+            .scope(api_token, async { authorization::skip_authorize() })
+            .await;
+        assert!(
+            matches!(res, Err(err) if matches!(err.error_type(), ControllerErrorType::Forbidden))
+        );
+    }
+}
@@ -0,0 +1,39 @@
+/*!
+Recording privileged actions in the audit log.
+
+The controllers that perform privileged actions call [record] after the action, preferably in the same transaction, so that the log entry is written only if the action succeeds.
+*/
+
+use headless_lms_models::{
+    audit_log_entries::{self, AuditLogAction, NewAuditLogEntry},
+    ModelResult,
+};
+
+use crate::{domain::request_span_middleware, prelude::*};
+
+/// Records an action performed by the user in the current request. The request id and the API token the request was authenticated with are recorded automatically.
+pub async fn record(
+    conn: &mut PgConnection,
+    actor: &AuthUser,
+    action: AuditLogAction,
+    resource_id: Uuid,
+    course_id: Option<Uuid>,
+    before_summary: Option<serde_json::Value>,
+    after_summary: Option<serde_json::Value>,
+) -> ModelResult<()> {
+    audit_log_entries::insert(
+        conn,
+        &NewAuditLogEntry {
+            actor_user_id: Some(actor.id),
+            api_token_id: actor.api_token_id(),
+            request_id: request_span_middleware::current_request_id(),
+            action,
+            resource_id,
+            course_id,
+            before_summary,
+            after_summary,
+        },
+    )
+    .await?;
+    Ok(())
+}
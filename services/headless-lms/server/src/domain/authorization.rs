@@ -1,13 +1,18 @@
-use crate::prelude::*;
+use crate::{domain::api_tokens, prelude::*};
 
-use actix_http::Payload;
+use actix_http::{Extensions, HttpMessage, Payload};
 use actix_session::Session;
 use actix_session::SessionExt;
 use actix_web::{FromRequest, HttpRequest, Responder};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures::future::{err, ok, Ready};
-use headless_lms_models::{self as models, roles::UserRole};
+use headless_lms_models::{
+    self as models,
+    api_tokens::ApiTokenScope,
+    role_permissions::{PermissionAction, PermissionResourceType, RolePermission},
+    roles::UserRole,
+};
 use models::{roles::Role, CourseOrExamId};
 use serde::{Deserialize, Serialize};
 use sqlx::PgConnection;
@@ -16,6 +21,9 @@ pub use ts_rs::TS;
 use uuid::Uuid;
 
 const SESSION_KEY: &str = "user";
+const IMPERSONATION_SESSION_KEY: &str = "impersonation";
+/// Impersonation ends automatically after this many minutes so that it is not left on by accident.
+pub const IMPERSONATION_MAX_DURATION_MINUTES: i64 = 60;
 
 // at least one field should be kept private to prevent initializing the struct outside of this module;
 // this way FromRequest is the only way to create an AuthUser
@@ -27,6 +35,9 @@ pub struct AuthUser {
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
     upstream_id: Option<i32>,
+    /// Set if the request was authenticated with an API token instead of a session.
+    #[serde(skip)]
+    api_token_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -42,6 +53,22 @@ impl AuthUser {
     pub fn upstream_id(&self) -> Option<i32> {
         self.upstream_id
     }
+
+    /// The API token the request was authenticated with, if any.
+    pub fn api_token_id(&self) -> Option<Uuid> {
+        self.api_token_id
+    }
+
+    fn from_user(user: models::users::User, api_token_id: Option<Uuid>) -> Self {
+        Self {
+            id: user.id,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            deleted_at: user.deleted_at,
+            upstream_id: user.upstream_id,
+            api_token_id,
+        }
+    }
 }
 
 impl FromRequest for AuthUser {
@@ -49,6 +76,10 @@ impl FromRequest for AuthUser {
     type Future = Ready<Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        // set by the API token and impersonation middlewares
+        if let Some(user) = req.extensions().get::<AuthUser>() {
+            return ok(user.clone());
+        }
         let session = req.get_session();
         match session.get::<AuthUser>(SESSION_KEY) {
             Ok(Some(user)) => ok(user),
@@ -73,18 +104,21 @@ impl FromRequest for AuthUser {
 
 /// Stores the user as authenticated in the given session.
 pub fn remember(session: &Session, user: models::users::User) -> Result<()> {
-    let auth_user = AuthUser {
-        id: user.id,
-        created_at: user.created_at,
-        updated_at: user.updated_at,
-        deleted_at: user.deleted_at,
-        upstream_id: user.upstream_id,
-    };
+    let auth_user = AuthUser::from_user(user, None);
     session
         .insert(SESSION_KEY, auth_user)
         .map_err(|_| anyhow::anyhow!("Failed to insert to session"))
 }
 
+/// Stores the user as authenticated for a single request that was authenticated with an API token.
+pub fn remember_for_request(
+    extensions: &mut Extensions,
+    user: models::users::User,
+    api_token_id: Uuid,
+) {
+    extensions.insert(AuthUser::from_user(user, Some(api_token_id)));
+}
+
 /// Checks if the user is authenticated in the given session.
 pub fn has_auth_user_session(session: &Session) -> bool {
     session.entries().get(SESSION_KEY).is_some()
@@ -93,6 +127,84 @@ pub fn has_auth_user_session(session: &Session) -> bool {
 /// Forgets authentication from the current session, if any.
 pub fn forget(session: &Session) {
     session.remove(SESSION_KEY);
+    session.remove(IMPERSONATION_SESSION_KEY);
+}
+
+/// The user authenticated in the given session, ignoring any impersonation.
+pub fn get_session_user(session: &Session) -> Option<AuthUser> {
+    session.get::<AuthUser>(SESSION_KEY).ok().flatten()
+}
+
+/// A teacher viewing the course material as one of the students of a course.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct Impersonation {
+    pub impersonated_user_id: Uuid,
+    pub course_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Impersonation {
+    pub fn has_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImpersonationSession {
+    impersonation: Impersonation,
+    impersonated_user: AuthUser,
+}
+
+/// Starts impersonating the given user in the session. The caller is responsible for checking that the impersonation is allowed.
+pub fn start_impersonation(
+    session: &Session,
+    impersonated_user: models::users::User,
+    course_id: Uuid,
+    started_at: DateTime<Utc>,
+) -> Result<Impersonation> {
+    let impersonation = Impersonation {
+        impersonated_user_id: impersonated_user.id,
+        course_id,
+        started_at,
+        expires_at: started_at + chrono::Duration::minutes(IMPERSONATION_MAX_DURATION_MINUTES),
+    };
+    session
+        .insert(
+            IMPERSONATION_SESSION_KEY,
+            ImpersonationSession {
+                impersonation: impersonation.clone(),
+                impersonated_user: AuthUser::from_user(impersonated_user, None),
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to insert to session"))?;
+    Ok(impersonation)
+}
+
+/// The impersonation in the given session, if any. The impersonation may have expired.
+pub fn get_impersonation(session: &Session) -> Option<Impersonation> {
+    session
+        .get::<ImpersonationSession>(IMPERSONATION_SESSION_KEY)
+        .ok()
+        .flatten()
+        .map(|impersonation_session| impersonation_session.impersonation)
+}
+
+/// Handles the rest of the request as the impersonated user, if there is an impersonation in the given session. The caller is responsible for checking that the request is related to the impersonated course.
+pub fn remember_impersonated_user_for_request(session: &Session, extensions: &mut Extensions) {
+    if let Ok(Some(impersonation_session)) =
+        session.get::<ImpersonationSession>(IMPERSONATION_SESSION_KEY)
+    {
+        extensions.insert(impersonation_session.impersonated_user);
+    }
+}
+
+/// Ends the impersonation in the given session. Returns the impersonation that was ended, if any.
+pub fn end_impersonation(session: &Session) -> Option<Impersonation> {
+    let impersonation = get_impersonation(session);
+    session.remove(IMPERSONATION_SESSION_KEY);
+    impersonation
 }
 
 /// Describes an action that a user can take on some resource.
@@ -113,6 +225,8 @@ pub enum Action {
     /// Deletion that we usually don't want to allow.
     UsuallyUnacceptableDeletion,
     UploadFile,
+    /// Viewing the course material as one of the students of the course.
+    Impersonate,
 }
 
 /// The target of an action.
@@ -196,6 +310,14 @@ async fn example_function(
 ```
 */
 pub fn skip_authorize() -> Result<AuthorizationToken, ControllerError> {
+    // the scopes of API tokens are only checked in authorize, so API tokens are only allowed on endpoints that use it
+    if api_tokens::current_api_token().is_some() {
+        return Err(ControllerError::new(
+            ControllerErrorType::Forbidden,
+            "This endpoint cannot be used with an API token.".to_string(),
+            None,
+        ));
+    }
     Ok(AuthorizationToken(()))
 }
 
@@ -217,7 +339,8 @@ pub async fn authorize_access_to_course_material(
         }
         authorize(conn, Act::ViewMaterial, user_id, Res::Course(course_id)).await?
     } else {
-        skip_authorize()?
+        check_api_token_scope(Act::ViewMaterial, &Res::Course(course_id))?;
+        AuthorizationToken(())
     };
     Ok(token)
 }
@@ -263,9 +386,22 @@ pub async fn authorize_with_fetched_list_of_roles(
     resource: Resource,
     user_roles: &[Role],
 ) -> Result<AuthorizationToken, ControllerError> {
+    check_api_token_scope(action, &resource)?;
+
+    // roles outside of their validity window grant nothing
+    let now = Utc::now();
+    let user_roles: Vec<Role> = user_roles
+        .iter()
+        .filter(|role| role.is_valid_at(now))
+        .copied()
+        .collect();
+    let user_roles = user_roles.as_slice();
+    let permissions = RolePermissions::fetch(conn, user_roles, &resource).await?;
+    let permissions = &permissions;
+
     // check global role
     for role in user_roles {
-        if role.is_global() && has_permission(role.role, action) {
+        if role.is_global() && permissions.allows(role, action) {
             return Ok(AuthorizationToken(()));
         }
     }
@@ -273,7 +409,7 @@ pub async fn authorize_with_fetched_list_of_roles(
     // for this resource, the domain of the role does not matter (e.g. organization role, course role, etc.)
     if resource == Resource::AnyCourse {
         for role in user_roles {
-            if has_permission(role.role, action) {
+            if permissions.allows(role, action) {
                 return Ok(AuthorizationToken(()));
             }
         }
@@ -291,50 +427,98 @@ pub async fn authorize_with_fetched_list_of_roles(
                 };
             // there are no chapter roles so we check the course instead
             let course_id = models::chapters::get_course_id(conn, id).await?;
-            check_course_permission(conn, user_roles, action, course_id).await
+            check_course_permission(conn, user_roles, permissions, action, course_id).await
+        }
+        Resource::Course(id) => {
+            check_course_permission(conn, user_roles, permissions, action, id).await
         }
-        Resource::Course(id) => check_course_permission(conn, user_roles, action, id).await,
         Resource::CourseInstance(id) => {
-            check_course_instance_permission(conn, user_roles, action, id).await
+            check_course_instance_permission(conn, user_roles, permissions, action, id).await
         }
         Resource::Exercise(id) => {
             // an exercise can be part of a course or an exam
             let course_or_exam_id = models::exercises::get_course_or_exam_id(conn, id).await?;
-            check_course_or_exam_permission(conn, user_roles, action, course_or_exam_id).await
+            check_course_or_exam_permission(
+                conn,
+                user_roles,
+                permissions,
+                action,
+                course_or_exam_id,
+            )
+            .await
         }
         Resource::ExerciseSlideSubmission(id) => {
             //an exercise slide submissions can be part of a course or an exam
             let course_or_exam_id =
                 models::exercise_slide_submissions::get_course_and_exam_id(conn, id).await?;
-            check_course_or_exam_permission(conn, user_roles, action, course_or_exam_id).await
+            check_course_or_exam_permission(
+                conn,
+                user_roles,
+                permissions,
+                action,
+                course_or_exam_id,
+            )
+            .await
         }
         Resource::ExerciseTask(id) => {
             // an exercise task can be part of a course or an exam
             let course_or_exam_id = models::exercise_tasks::get_course_or_exam_id(conn, id).await?;
-            check_course_or_exam_permission(conn, user_roles, action, course_or_exam_id).await
+            check_course_or_exam_permission(
+                conn,
+                user_roles,
+                permissions,
+                action,
+                course_or_exam_id,
+            )
+            .await
         }
         Resource::ExerciseTaskSubmission(id) => {
             // an exercise task submission can be part of a course or an exam
             let course_or_exam_id =
                 models::exercise_task_submissions::get_course_and_exam_id(conn, id).await?;
-            check_course_or_exam_permission(conn, user_roles, action, course_or_exam_id).await
+            check_course_or_exam_permission(
+                conn,
+                user_roles,
+                permissions,
+                action,
+                course_or_exam_id,
+            )
+            .await
         }
         Resource::ExerciseTaskGrading(id) => {
             // a grading can be part of a course or an exam
             let course_or_exam_id =
                 models::exercise_task_gradings::get_course_or_exam_id(conn, id).await?;
-            check_course_or_exam_permission(conn, user_roles, action, course_or_exam_id).await
+            check_course_or_exam_permission(
+                conn,
+                user_roles,
+                permissions,
+                action,
+                course_or_exam_id,
+            )
+            .await
+        }
+        Resource::Organization(id) => {
+            check_organization_permission(user_roles, permissions, action, id).await
         }
-        Resource::Organization(id) => check_organization_permission(user_roles, action, id).await,
         Resource::Page(id) => {
             // a page can be part of a course or an exam
             let course_or_exam_id = models::pages::get_course_and_exam_id(conn, id).await?;
-            check_course_or_exam_permission(conn, user_roles, action, course_or_exam_id).await
+            check_course_or_exam_permission(
+                conn,
+                user_roles,
+                permissions,
+                action,
+                course_or_exam_id,
+            )
+            .await
         }
         Resource::StudyRegistry(secret_key) => {
             check_study_registry_permission(conn, secret_key, action).await
         }
-        Resource::Exam(exam_id) => check_exam_permission(conn, user_roles, action, exam_id).await,
+        Resource::Exam(exam_id) => {
+            check_exam_permission(conn, user_roles, permissions, action, exam_id).await
+        }
         Resource::Role
         | Resource::User
         | Resource::AnyCourse
@@ -349,13 +533,14 @@ pub async fn authorize_with_fetched_list_of_roles(
             ))
         }
         Resource::MaterialReference => {
-            check_material_reference_permissions(user_roles, action).await
+            check_material_reference_permissions(user_roles, permissions, action).await
         }
     }
 }
 
 async fn check_organization_permission(
     roles: &[Role],
+    permissions: &RolePermissions,
     action: Action,
     organization_id: Uuid,
 ) -> Result<AuthorizationToken, ControllerError> {
@@ -366,7 +551,7 @@ async fn check_organization_permission(
 
     // check organization role
     for role in roles {
-        if role.is_role_for_organization(organization_id) && has_permission(role.role, action) {
+        if role.is_role_for_organization(organization_id) && permissions.allows(role, action) {
             return Ok(AuthorizationToken(()));
         }
     }
@@ -381,23 +566,25 @@ async fn check_organization_permission(
 async fn check_course_permission(
     conn: &mut PgConnection,
     roles: &[Role],
+    permissions: &RolePermissions,
     action: Action,
     course_id: Uuid,
 ) -> Result<AuthorizationToken, ControllerError> {
     // check course role
     for role in roles {
-        if role.is_role_for_course(course_id) && has_permission(role.role, action) {
+        if role.is_role_for_course(course_id) && permissions.allows(role, action) {
             return Ok(AuthorizationToken(()));
         }
     }
     let organization_id = models::courses::get_organization_id(conn, course_id).await?;
-    check_organization_permission(roles, action, organization_id).await
+    check_organization_permission(roles, permissions, action, organization_id).await
 }
 
 /// Also checks organization and course roles which are valid for course instances.
 async fn check_course_instance_permission(
     conn: &mut PgConnection,
     roles: &[Role],
+    permissions: &RolePermissions,
     mut action: Action,
     course_instance_id: Uuid,
 ) -> Result<AuthorizationToken, ControllerError> {
@@ -410,52 +597,57 @@ async fn check_course_instance_permission(
 
     // check course instance role
     for role in roles {
-        if role.is_role_for_course_instance(course_instance_id) && has_permission(role.role, action)
+        if role.is_role_for_course_instance(course_instance_id) && permissions.allows(role, action)
         {
             return Ok(AuthorizationToken(()));
         }
     }
     let course_id = models::course_instances::get_course_id(conn, course_instance_id).await?;
-    check_course_permission(conn, roles, action, course_id).await
+    check_course_permission(conn, roles, permissions, action, course_id).await
 }
 
 /// Also checks organization role which is valid for exams.
 async fn check_exam_permission(
     conn: &mut PgConnection,
     roles: &[Role],
+    permissions: &RolePermissions,
     action: Action,
     exam_id: Uuid,
 ) -> Result<AuthorizationToken, ControllerError> {
     // check exam role
     for role in roles {
-        if role.is_role_for_exam(exam_id) && has_permission(role.role, action) {
+        if role.is_role_for_exam(exam_id) && permissions.allows(role, action) {
             return Ok(AuthorizationToken(()));
         }
     }
     let organization_id = models::exams::get_organization_id(conn, exam_id).await?;
-    check_organization_permission(roles, action, organization_id).await
+    check_organization_permission(roles, permissions, action, organization_id).await
 }
 
 async fn check_course_or_exam_permission(
     conn: &mut PgConnection,
     roles: &[Role],
+    permissions: &RolePermissions,
     action: Action,
     course_or_exam_id: CourseOrExamId,
 ) -> Result<AuthorizationToken, ControllerError> {
     match course_or_exam_id {
         CourseOrExamId::Course(course_id) => {
-            check_course_permission(conn, roles, action, course_id).await
+            check_course_permission(conn, roles, permissions, action, course_id).await
+        }
+        CourseOrExamId::Exam(exam_id) => {
+            check_exam_permission(conn, roles, permissions, action, exam_id).await
         }
-        CourseOrExamId::Exam(exam_id) => check_exam_permission(conn, roles, action, exam_id).await,
     }
 }
 
 async fn check_material_reference_permissions(
     roles: &[Role],
+    permissions: &RolePermissions,
     action: Action,
 ) -> Result<AuthorizationToken, ControllerError> {
     for role in roles {
-        if has_permission(role.role, action) {
+        if permissions.allows(role, action) {
             return Ok(AuthorizationToken(()));
         }
     }
@@ -476,37 +668,159 @@ async fn check_study_registry_permission(
     Ok(AuthorizationToken(()))
 }
 
-// checks whether the role is allowed to perform the action
-fn has_permission(user_role: UserRole, action: Action) -> bool {
-    use Action::*;
-    use UserRole::*;
-
-    match user_role {
-        Admin => true,
-        Teacher => matches!(
-            action,
-            View | Teach
-                | Edit
-                | Grade
-                | Duplicate
-                | DeleteAnswer
-                | EditRole(Teacher | Assistant | Reviewer)
-                | CreateCoursesOrExams
-                | ViewMaterial
-                | UploadFile
-        ),
-        Assistant => matches!(
-            action,
-            View | Edit
-                | Grade
-                | DeleteAnswer
-                | EditRole(Assistant | Reviewer)
-                | Teach
-                | ViewMaterial
-        ),
-        Reviewer => matches!(action, View | Grade | ViewMaterial),
-        CourseOrExamCreator => matches!(action, CreateCoursesOrExams),
-        MaterialViewer => matches!(action, ViewMaterial),
+/// The permissions of the user's roles, used for checking an action on a single resource.
+///
+/// The permissions of both the built-in and the custom roles are stored in the database.
+struct RolePermissions {
+    permissions: Vec<RolePermission>,
+    resource_type: PermissionResourceType,
+}
+
+impl RolePermissions {
+    async fn fetch(
+        conn: &mut PgConnection,
+        roles: &[Role],
+        resource: &Resource,
+    ) -> Result<Self, ControllerError> {
+        let permissions = if roles.is_empty() {
+            // anonymous users and users without roles have no permissions to check
+            Vec::new()
+        } else {
+            let custom_role_ids: Vec<Uuid> = roles
+                .iter()
+                .filter_map(|role| role.custom_role_id)
+                .collect();
+            models::role_permissions::get_for_authorization(conn, &custom_role_ids).await?
+        };
+        Ok(Self {
+            permissions,
+            resource_type: permission_resource_type(resource),
+        })
+    }
+
+    // checks whether the role is allowed to perform the action
+    fn allows(&self, role: &Role, action: Action) -> bool {
+        let (permission_action, action_role) = permission_action(action);
+        self.permissions.iter().any(|permission| {
+            let is_permission_of_role = match role.custom_role_id {
+                Some(custom_role_id) => permission.custom_role_id == Some(custom_role_id),
+                None => permission.built_in_role == Some(role.role),
+            };
+            is_permission_of_role
+                && permission.action == permission_action
+                && permission.action_role == action_role
+                && permission
+                    .resource_type
+                    .map(|resource_type| resource_type == self.resource_type)
+                    .unwrap_or(true)
+        })
+    }
+}
+
+/// Maps the action to how it is stored in the permissions.
+fn permission_action(action: Action) -> (PermissionAction, Option<UserRole>) {
+    match action {
+        Action::ViewMaterial => (PermissionAction::ViewMaterial, None),
+        Action::View => (PermissionAction::View, None),
+        Action::Edit => (PermissionAction::Edit, None),
+        Action::Grade => (PermissionAction::Grade, None),
+        Action::Teach => (PermissionAction::Teach, None),
+        Action::Download => (PermissionAction::Download, None),
+        Action::Duplicate => (PermissionAction::Duplicate, None),
+        Action::DeleteAnswer => (PermissionAction::DeleteAnswer, None),
+        Action::EditRole(role) => (PermissionAction::EditRole, Some(role)),
+        Action::CreateCoursesOrExams => (PermissionAction::CreateCoursesOrExams, None),
+        Action::UsuallyUnacceptableDeletion => {
+            (PermissionAction::UsuallyUnacceptableDeletion, None)
+        }
+        Action::UploadFile => (PermissionAction::UploadFile, None),
+        Action::Impersonate => (PermissionAction::Impersonate, None),
+    }
+}
+
+/// Maps the resource to the resource type that permissions can be limited to.
+fn permission_resource_type(resource: &Resource) -> PermissionResourceType {
+    match resource {
+        Resource::GlobalPermissions => PermissionResourceType::GlobalPermissions,
+        Resource::Chapter(_) => PermissionResourceType::Chapter,
+        Resource::Course(_) => PermissionResourceType::Course,
+        Resource::CourseInstance(_) => PermissionResourceType::CourseInstance,
+        Resource::Exam(_) => PermissionResourceType::Exam,
+        Resource::Exercise(_) => PermissionResourceType::Exercise,
+        Resource::ExerciseSlideSubmission(_) => PermissionResourceType::ExerciseSlideSubmission,
+        Resource::ExerciseTask(_) => PermissionResourceType::ExerciseTask,
+        Resource::ExerciseTaskGrading(_) => PermissionResourceType::ExerciseTaskGrading,
+        Resource::ExerciseTaskSubmission(_) => PermissionResourceType::ExerciseTaskSubmission,
+        Resource::Organization(_) => PermissionResourceType::Organization,
+        Resource::Page(_) => PermissionResourceType::Page,
+        Resource::StudyRegistry(_) => PermissionResourceType::StudyRegistry,
+        Resource::AnyCourse => PermissionResourceType::AnyCourse,
+        Resource::Role => PermissionResourceType::Role,
+        Resource::User => PermissionResourceType::User,
+        Resource::PlaygroundExample => PermissionResourceType::PlaygroundExample,
+        Resource::ExerciseService => PermissionResourceType::ExerciseService,
+        Resource::MaterialReference => PermissionResourceType::MaterialReference,
+    }
+}
+
+/// Requests made with an API token are limited by the scopes of the token. Does nothing for other requests.
+fn check_api_token_scope(action: Action, resource: &Resource) -> Result<(), ControllerError> {
+    if let Some(api_token) = api_tokens::current_api_token() {
+        if !api_token.scopes.iter().any(|scope| {
+            api_token_scope_allows(*scope, api_token.read_only_request, action, resource)
+        }) {
+            return Err(ControllerError::new(
+                ControllerErrorType::Forbidden,
+                "The API token does not have a scope that allows this action.".to_string(),
+                None,
+            ));
+        }
+    }
+    Ok(())
+}
+
+// checks whether the scope of the API token used for the request allows the action
+fn api_token_scope_allows(
+    scope: ApiTokenScope,
+    read_only_request: bool,
+    action: Action,
+    resource: &Resource,
+) -> bool {
+    match scope {
+        // the user's permissions are still checked, the scope only prevents modifying anything
+        ApiTokenScope::Read => read_only_request,
+        ApiTokenScope::Grade => {
+            matches!(action, Action::View | Action::ViewMaterial | Action::Grade)
+                && matches!(
+                    resource,
+                    Resource::AnyCourse
+                        | Resource::Course(_)
+                        | Resource::CourseInstance(_)
+                        | Resource::Exam(_)
+                        | Resource::Exercise(_)
+                        | Resource::ExerciseSlideSubmission(_)
+                        | Resource::ExerciseTask(_)
+                        | Resource::ExerciseTaskGrading(_)
+                        | Resource::ExerciseTaskSubmission(_)
+                )
+        }
+        ApiTokenScope::EditContent => {
+            matches!(
+                action,
+                Action::View | Action::ViewMaterial | Action::Edit | Action::UploadFile
+            ) && matches!(
+                resource,
+                Resource::Chapter(_)
+                    | Resource::Course(_)
+                    | Resource::CourseInstance(_)
+                    | Resource::Exam(_)
+                    | Resource::Exercise(_)
+                    | Resource::ExerciseTask(_)
+                    | Resource::Page(_)
+                    | Resource::MaterialReference
+                    | Resource::PlaygroundExample
+            )
+        }
     }
 }
 
@@ -618,6 +932,196 @@ mod test {
         .unwrap();
     }
 
+    #[actix_web::test]
+    async fn role_outside_validity_window_grants_nothing() {
+        insert_data!(:tx, :user, :org, :course);
+        let now = Utc::now();
+
+        roles::insert_with_validity(
+            tx.as_mut(),
+            user,
+            UserRole::Teacher,
+            RoleDomain::Course(course),
+            Some(now - chrono::Duration::days(30)),
+            Some(now - chrono::Duration::days(1)),
+        )
+        .await
+        .unwrap();
+        authorize(
+            tx.as_mut(),
+            Action::Edit,
+            Some(user),
+            Resource::Course(course),
+        )
+        .await
+        .unwrap_err();
+
+        roles::insert_with_validity(
+            tx.as_mut(),
+            user,
+            UserRole::Teacher,
+            RoleDomain::Course(course),
+            Some(now - chrono::Duration::days(1)),
+            Some(now + chrono::Duration::days(30)),
+        )
+        .await
+        .unwrap();
+        authorize(
+            tx.as_mut(),
+            Action::Edit,
+            Some(user),
+            Resource::Course(course),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[actix_web::test]
+    async fn built_in_roles_have_their_original_permissions() {
+        insert_data!(:tx);
+        let permissions = RolePermissions {
+            permissions: role_permissions::get_for_authorization(tx.as_mut(), &[])
+                .await
+                .unwrap(),
+            resource_type: PermissionResourceType::Course,
+        };
+        let user_roles = [
+            UserRole::Reviewer,
+            UserRole::Assistant,
+            UserRole::Teacher,
+            UserRole::Admin,
+            UserRole::CourseOrExamCreator,
+            UserRole::MaterialViewer,
+        ];
+        let mut actions = vec![
+            Action::ViewMaterial,
+            Action::View,
+            Action::Edit,
+            Action::Grade,
+            Action::Teach,
+            Action::Download,
+            Action::Duplicate,
+            Action::DeleteAnswer,
+            Action::CreateCoursesOrExams,
+            Action::UsuallyUnacceptableDeletion,
+            Action::UploadFile,
+            Action::Impersonate,
+        ];
+        actions.extend(user_roles.iter().map(|role| Action::EditRole(*role)));
+
+        // the permissions that were hard-coded before they were moved to the database
+        fn original_permission(user_role: UserRole, action: Action) -> bool {
+            use Action::*;
+            use UserRole::*;
+
+            match user_role {
+                Admin => true,
+                Teacher => matches!(
+                    action,
+                    View | Teach
+                        | Edit
+                        | Grade
+                        | Duplicate
+                        | DeleteAnswer
+                        | EditRole(Teacher | Assistant | Reviewer)
+                        | CreateCoursesOrExams
+                        | ViewMaterial
+                        | UploadFile
+                        // added after the permissions were moved to the database
+                        | Impersonate
+                ),
+                Assistant => matches!(
+                    action,
+                    View | Edit
+                        | Grade
+                        | DeleteAnswer
+                        | EditRole(Assistant | Reviewer)
+                        | Teach
+                        | ViewMaterial
+                ),
+                Reviewer => matches!(action, View | Grade | ViewMaterial),
+                CourseOrExamCreator => matches!(action, CreateCoursesOrExams),
+                MaterialViewer => matches!(action, ViewMaterial),
+            }
+        }
+
+        for user_role in user_roles {
+            let role = Role {
+                is_global: true,
+                organization_id: None,
+                course_id: None,
+                course_instance_id: None,
+                exam_id: None,
+                role: user_role,
+                valid_from: None,
+                valid_until: None,
+                custom_role_id: None,
+            };
+            for action in &actions {
+                assert_eq!(
+                    permissions.allows(&role, *action),
+                    original_permission(user_role, *action),
+                    "{:?} {:?}",
+                    user_role,
+                    action
+                );
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn custom_role_restricts_base_role() {
+        insert_data!(:tx, :user, :org, :course, instance: _instance, :course_module, :chapter, :page);
+        let custom_role_id = custom_roles::insert(
+            tx.as_mut(),
+            org,
+            &custom_roles::NewCustomRole {
+                name: "Course grader".to_string(),
+                base_role: UserRole::Assistant,
+                permissions: vec![role_permissions::NewRolePermission {
+                    action: PermissionAction::Grade,
+                    action_role: None,
+                    resource_type: Some(PermissionResourceType::Course),
+                }],
+            },
+        )
+        .await
+        .unwrap();
+        let role_id = roles::insert(
+            tx.as_mut(),
+            user,
+            UserRole::Assistant,
+            RoleDomain::Course(course),
+        )
+        .await
+        .unwrap();
+        roles::set_custom_role(tx.as_mut(), role_id, Some(custom_role_id))
+            .await
+            .unwrap();
+
+        authorize(
+            tx.as_mut(),
+            Action::Grade,
+            Some(user),
+            Resource::Course(course),
+        )
+        .await
+        .unwrap();
+        // the base role could edit the course
+        authorize(
+            tx.as_mut(),
+            Action::Edit,
+            Some(user),
+            Resource::Course(course),
+        )
+        .await
+        .unwrap_err();
+        // the permission is limited to courses
+        authorize(tx.as_mut(), Action::Grade, Some(user), Resource::Page(page))
+            .await
+            .unwrap_err();
+    }
+
     #[actix_web::test]
     async fn anonymous_user_can_view_open_course() {
         insert_data!(:tx, :user, :org, :course);
@@ -626,4 +1130,51 @@ mod test {
             .await
             .unwrap();
     }
+
+    #[test]
+    fn api_token_scopes_limit_actions() {
+        let id = Uuid::new_v4();
+        assert!(api_token_scope_allows(
+            ApiTokenScope::Read,
+            true,
+            Action::Teach,
+            &Resource::Course(id)
+        ));
+        assert!(!api_token_scope_allows(
+            ApiTokenScope::Read,
+            false,
+            Action::View,
+            &Resource::Course(id)
+        ));
+        assert!(api_token_scope_allows(
+            ApiTokenScope::Grade,
+            false,
+            Action::Grade,
+            &Resource::ExerciseSlideSubmission(id)
+        ));
+        assert!(!api_token_scope_allows(
+            ApiTokenScope::Grade,
+            false,
+            Action::Edit,
+            &Resource::Page(id)
+        ));
+        assert!(api_token_scope_allows(
+            ApiTokenScope::EditContent,
+            false,
+            Action::Edit,
+            &Resource::Page(id)
+        ));
+        assert!(!api_token_scope_allows(
+            ApiTokenScope::EditContent,
+            false,
+            Action::EditRole(UserRole::Teacher),
+            &Resource::Course(id)
+        ));
+        assert!(!api_token_scope_allows(
+            ApiTokenScope::EditContent,
+            false,
+            Action::Edit,
+            &Resource::Organization(id)
+        ));
+    }
 }
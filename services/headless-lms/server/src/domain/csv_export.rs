@@ -3,6 +3,7 @@ use bytes::Bytes;
 use csv::Writer;
 use futures::{stream::FuturesUnordered, Stream, StreamExt, TryStreamExt};
 use headless_lms_models::{
+    audit_log_entries::{self, AuditLogFilter},
     chapters, course_instances, exercise_task_submissions, exercises, user_exercise_states,
 };
 
@@ -25,14 +26,14 @@ use crate::prelude::*;
 
 use super::authorization::{AuthorizationToken, AuthorizedResponse};
 /// Convenience struct for creating CSV data.
-struct CsvWriter<W: Write> {
+pub(crate) struct CsvWriter<W: Write> {
     csv_writer: Arc<Mutex<Writer<W>>>,
     handles: FuturesUnordered<JoinHandle<Result<()>>>,
 }
 
 impl<W: Write + Send + 'static> CsvWriter<W> {
     /// Creates a new CsvWriter, and also writes the given headers before returning.
-    async fn new_with_initialized_headers<I, T>(writer: W, headers: I) -> Result<Self>
+    pub(crate) async fn new_with_initialized_headers<I, T>(writer: W, headers: I) -> Result<Self>
     where
         I: IntoIterator<Item = T> + Send + 'static,
         T: AsRef<[u8]>,
@@ -57,7 +58,7 @@ impl<W: Write + Send + 'static> CsvWriter<W> {
     }
 
     /// Spawns a task that writes a single CSV record
-    fn write_record<I, T>(&self, csv_row: I)
+    pub(crate) fn write_record<I, T>(&self, csv_row: I)
     where
         I: IntoIterator<Item = T> + Send + 'static,
         T: AsRef<[u8]>,
@@ -75,7 +76,7 @@ impl<W: Write + Send + 'static> CsvWriter<W> {
 
     /// Waits for handles to finish, flushes the writer and extracts the inner writer.
     /// Should always be called before dropping the writer to make sure writing the CSV finishes properly.
-    async fn finish(mut self) -> Result<W> {
+    pub(crate) async fn finish(mut self) -> Result<W> {
         // ensure every task is finished before the writer is extracted
         while let Some(handle) = self.handles.next().await {
             handle??;
@@ -304,11 +305,63 @@ where
     Ok(writer)
 }
 
+/// Writes the audit log entries that match the filter as csv into the writer
+pub async fn export_audit_log<W>(
+    conn: &mut PgConnection,
+    filter: &AuditLogFilter,
+    writer: W,
+) -> Result<W>
+where
+    W: Write + Send + 'static,
+{
+    let headers = IntoIterator::into_iter([
+        "id".to_string(),
+        "created_at".to_string(),
+        "actor_user_id".to_string(),
+        "actor_email".to_string(),
+        "api_token_id".to_string(),
+        "request_id".to_string(),
+        "action".to_string(),
+        "resource_id".to_string(),
+        "course_id".to_string(),
+        "before_summary".to_string(),
+        "after_summary".to_string(),
+    ]);
+
+    let mut stream = audit_log_entries::stream_filtered(conn, filter);
+
+    let writer = CsvWriter::new_with_initialized_headers(writer, headers).await?;
+    while let Some(next) = stream.try_next().await? {
+        let csv_row = vec![
+            next.id.to_string(),
+            next.created_at.to_rfc3339(),
+            next.actor_user_id
+                .map(|o| o.to_string())
+                .unwrap_or_default(),
+            next.actor_email.unwrap_or_default(),
+            next.api_token_id.map(|o| o.to_string()).unwrap_or_default(),
+            next.request_id.map(|o| o.to_string()).unwrap_or_default(),
+            format!("{:?}", next.action),
+            next.resource_id.to_string(),
+            next.course_id.map(|o| o.to_string()).unwrap_or_default(),
+            next.before_summary
+                .map(|o| o.to_string())
+                .unwrap_or_default(),
+            next.after_summary
+                .map(|o| o.to_string())
+                .unwrap_or_default(),
+        ];
+        writer.write_record(csv_row);
+    }
+    let writer = writer.finish().await?;
+    Ok(writer)
+}
+
 /**
  * For csv export. Return the grade as a number if there is a numeric grade. If the grade is not numeric, returns pass/fail/
  * If course module has not been completed yet, returns "-".
  */
-fn course_module_completion_info_to_grade_string(
+pub(crate) fn course_module_completion_info_to_grade_string(
     input: Option<&CourseModuleCompletionWithRegistrationInfo>,
 ) -> String {
     let grade_string = input.map(|info| {
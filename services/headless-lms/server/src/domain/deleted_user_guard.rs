@@ -0,0 +1,153 @@
+/*!
+Logs out the sessions of deleted users.
+
+The sessions are stored in cookies, so they cannot be invalidated when a user is deleted, for example when their personal data is erased. Instead, the [DeletedUserGuard] middleware checks the user of each request that is authenticated with a session, and forgets the user from the session if they have been deleted. The [AuthUser] extractor then rejects the request as unauthenticated.
+*/
+
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_session::SessionExt;
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+use futures_util::future::LocalBoxFuture;
+use headless_lms_models::users;
+
+use crate::{domain::authorization, prelude::*};
+
+pub struct DeletedUserGuard;
+
+impl<S, B> Transform<S, ServiceRequest> for DeletedUserGuard
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = DeletedUserGuardMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(DeletedUserGuardMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Forgets the user from the session if the user has been deleted.
+pub struct DeletedUserGuardMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for DeletedUserGuardMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let session = req.get_session();
+        let session_user = match authorization::get_session_user(&session) {
+            Some(session_user) => session_user,
+            None => return Box::pin(self.service.call(req)),
+        };
+
+        let service = Rc::clone(&self.service);
+        Box::pin(async move {
+            let pool = req
+                .app_data::<web::Data<PgPool>>()
+                .expect("Missing database pool in app data")
+                .clone();
+            let mut conn = pool.acquire().await.map_err(ControllerError::from)?;
+            let deleted = users::get_by_id(&mut conn, session_user.id)
+                .await
+                .optional()
+                .map_err(ControllerError::from)?
+                .map_or(true, |user| user.deleted_at.is_some());
+            drop(conn);
+            if deleted {
+                info!(user_id = %session_user.id, "Logging out the session of a deleted user");
+                authorization::forget(&session);
+            }
+            service.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use actix_session::{storage::CookieSessionStore, Session, SessionMiddleware};
+    use actix_web::{cookie::Key, http::StatusCode, test, App, HttpResponse};
+    use headless_lms_models::library::user_erasure;
+
+    use super::*;
+    use crate::test_helper::*;
+
+    #[actix_web::test]
+    async fn erased_users_are_logged_out() {
+        let pool = init_pool().await;
+        let mut conn = pool.acquire().await.unwrap();
+        let user_id = users::insert(
+            &mut conn,
+            PKeyPolicy::Generate,
+            &format!("{}@example.com", Uuid::new_v4()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .route(
+                    "/login",
+                    web::post().to(
+                        move |session: Session, pool: web::Data<PgPool>| async move {
+                            let mut conn = pool.acquire().await.unwrap();
+                            let user = users::get_by_id(&mut conn, user_id).await.unwrap();
+                            authorization::remember(&session, user).unwrap();
+                            HttpResponse::Ok().finish()
+                        },
+                    ),
+                )
+                .default_service(web::to(|user: AuthUser| async move {
+                    HttpResponse::Ok().body(user.id.to_string())
+                }))
+                .wrap(DeletedUserGuard)
+                .wrap(
+                    SessionMiddleware::builder(CookieSessionStore::default(), Key::from(&[0; 64]))
+                        .cookie_secure(false)
+                        .build(),
+                ),
+        )
+        .await;
+        let res =
+            test::call_service(&app, test::TestRequest::post().uri("/login").to_request()).await;
+        let session = res.response().cookies().next().unwrap().into_owned();
+        let call = || {
+            test::TestRequest::get()
+                .uri("/user")
+                .cookie(session.clone())
+                .to_request()
+        };
+
+        let res = test::call_service(&app, call()).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        user_erasure::erase_user(&mut conn, user_id).await.unwrap();
+        let res = test::call_service(&app, call()).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+}
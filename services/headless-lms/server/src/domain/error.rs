@@ -12,7 +12,9 @@ use actix_web::{
 };
 use backtrace::Backtrace;
 use derive_more::Display;
-use headless_lms_models::{ModelError, ModelErrorType};
+use headless_lms_models::{
+    page_edit_locks::PageEditLock, pages::PageSaveConflict, ModelError, ModelErrorType,
+};
 use headless_lms_utils::error::{
     backend_error::BackendError, backtrace_formatter::format_backtrace, util_error::UtilError,
 };
@@ -56,6 +58,14 @@ pub enum ControllerErrorType {
     /// HTTP status code 403. Is logged in but is not allowed to access the resource.
     #[display(fmt = "Forbidden")]
     Forbidden,
+
+    /// HTTP status code 409. The request conflicts with the current state of the resource, e.g. it is based on an outdated version of it.
+    #[display(fmt = "Conflict")]
+    ConflictWithData(ErrorData),
+
+    /// HTTP status code 429. Has made too many requests in a short time.
+    #[display(fmt = "Too many requests")]
+    TooManyRequests,
 }
 
 /**
@@ -226,6 +236,8 @@ impl BackendError for ControllerError {
 #[serde(rename_all = "snake_case")]
 pub enum ErrorData {
     BlockId(Uuid),
+    PageSaveConflict(Box<PageSaveConflict>),
+    PageEditLock(Box<PageEditLock>),
 }
 
 /// The format all error messages from the API is in
@@ -257,10 +269,10 @@ impl error::ResponseError for ControllerError {
         }
 
         let status = self.status_code();
-        let error_data = if let ControllerErrorType::BadRequestWithData(data) = &self.error_type {
-            Some(data.clone())
-        } else {
-            None
+        let error_data = match &self.error_type {
+            ControllerErrorType::BadRequestWithData(data)
+            | ControllerErrorType::ConflictWithData(data) => Some(data.clone()),
+            _ => None,
         };
 
         let source = self.source();
@@ -289,6 +301,8 @@ impl error::ResponseError for ControllerError {
             ControllerErrorType::NotFound => StatusCode::NOT_FOUND,
             ControllerErrorType::Unauthorized => StatusCode::UNAUTHORIZED,
             ControllerErrorType::Forbidden => StatusCode::FORBIDDEN,
+            ControllerErrorType::ConflictWithData(_) => StatusCode::CONFLICT,
+            ControllerErrorType::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
@@ -378,6 +392,22 @@ impl From<ModelError> for ControllerError {
                     span_trace,
                 )
             }
+            ModelErrorType::PageSaveConflict(conflict) => Self::new_with_traces(
+                ControllerErrorType::ConflictWithData(ErrorData::PageSaveConflict(
+                    conflict.clone(),
+                )),
+                err.message().to_string(),
+                Some(err.into()),
+                backtrace,
+                span_trace,
+            ),
+            ModelErrorType::PageEditLocked(lock) => Self::new_with_traces(
+                ControllerErrorType::ConflictWithData(ErrorData::PageEditLock(lock.clone())),
+                err.message().to_string(),
+                Some(err.into()),
+                backtrace,
+                span_trace,
+            ),
             ModelErrorType::DatabaseConstraint { description, .. } => Self::new_with_traces(
                 ControllerErrorType::BadRequest,
                 description.to_string(),
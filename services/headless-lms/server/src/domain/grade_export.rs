@@ -0,0 +1,463 @@
+/*!
+Exporting the grades of a course instance in formats that external gradebooks can import.
+
+Every format contains the same students: everyone enrolled on the course instance, sorted by name.
+*/
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Write},
+};
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use headless_lms_models::{
+    chapters, course_instances, exercises,
+    library::progressing::{self, UserWithModuleCompletions},
+    pages::{self, PageVisibility},
+    user_exercise_states::{self, CourseInstanceUserExercisePoints},
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::csv_export::{course_module_completion_info_to_grade_string, CsvWriter};
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub enum GradeExportFormat {
+    /// A CSV with the user details, and the points, completion status and grade of each module, followed by the points of each exercise.
+    Csv,
+    /// The same contents as `Csv` as an Excel workbook.
+    Xlsx,
+    /// A CSV in the layout of the Moodle gradebook export, which can be imported to Moodle by matching the users with their email addresses.
+    MoodleCsv,
+}
+
+impl GradeExportFormat {
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::Csv | Self::MoodleCsv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv | Self::MoodleCsv => "text/csv",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Text(String),
+    Number(f32),
+    Empty,
+}
+
+impl Cell {
+    fn into_csv_field(self) -> String {
+        match self {
+            Cell::Text(text) => text,
+            Cell::Number(number) => number.to_string(),
+            Cell::Empty => "".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+/// Writes the grades of the course instance in the given format into the writer.
+///
+/// The CSV rows are written as the points of the students are streamed from the database. An XLSX workbook is a zip archive, so it is built in memory.
+pub async fn export_course_instance_grades<W>(
+    conn: &mut PgConnection,
+    course_instance_id: Uuid,
+    format: GradeExportFormat,
+    writer: W,
+) -> Result<W>
+where
+    W: Write + Send + 'static,
+{
+    let mut grades = GradeRows::load(conn, course_instance_id, format).await?;
+    let headers = grades.headers();
+    let mut stream =
+        user_exercise_states::stream_course_instance_exercise_points(conn, course_instance_id);
+    match format {
+        GradeExportFormat::Csv | GradeExportFormat::MoodleCsv => {
+            let writer = CsvWriter::new_with_initialized_headers(writer, headers).await?;
+            while let Some(next) = stream.try_next().await? {
+                if let Some(row) = grades.row(next) {
+                    writer.write_record(
+                        row.into_iter()
+                            .map(Cell::into_csv_field)
+                            .collect::<Vec<_>>(),
+                    );
+                }
+            }
+            writer.finish().await
+        }
+        GradeExportFormat::Xlsx => {
+            let mut rows = vec![];
+            while let Some(next) = stream.try_next().await? {
+                rows.extend(grades.row(next));
+            }
+            let table = Table { headers, rows };
+            tokio::task::spawn_blocking(move || {
+                let mut writer = writer;
+                writer.write_all(&write_xlsx(&table)?)?;
+                Result::<_, anyhow::Error>::Ok(writer)
+            })
+            .await?
+        }
+    }
+}
+
+/// An exercise of the course with the chapter it is in.
+struct CourseExercise {
+    chapter_number: i32,
+    course_module_id: Uuid,
+    exercise: exercises::Exercise,
+}
+
+/// Builds the rows of the export from the points of the students.
+struct GradeRows {
+    format: GradeExportFormat,
+    modules: Vec<models::course_modules::CourseModule>,
+    /// In the order they appear in the course.
+    course_exercises: Vec<CourseExercise>,
+    users: HashMap<Uuid, UserWithModuleCompletions>,
+}
+
+impl GradeRows {
+    async fn load(
+        conn: &mut PgConnection,
+        course_instance_id: Uuid,
+        format: GradeExportFormat,
+    ) -> Result<Self> {
+        let course_instance =
+            course_instances::get_course_instance(conn, course_instance_id).await?;
+        let summary =
+            progressing::get_course_instance_completion_summary(conn, &course_instance).await?;
+        let mut modules = summary.course_modules;
+        modules.sort_by_key(|m| m.order_number);
+        let users = summary
+            .users_with_course_module_completions
+            .into_iter()
+            .map(|u| (u.user_id, u))
+            .collect();
+
+        let chapters_by_id: HashMap<Uuid, chapters::DatabaseChapter> =
+            chapters::course_chapters(conn, course_instance.course_id)
+                .await?
+                .into_iter()
+                .map(|c| (c.id, c))
+                .collect();
+        let page_order_numbers: HashMap<Uuid, i32> = pages::get_all_by_course_id_and_visibility(
+            conn,
+            course_instance.course_id,
+            PageVisibility::Any,
+        )
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.order_number))
+        .collect();
+        let mut course_exercises: Vec<CourseExercise> =
+            exercises::get_exercises_by_course_id(conn, course_instance.course_id)
+                .await?
+                .into_iter()
+                .filter_map(|e| {
+                    let chapter = chapters_by_id.get(&e.chapter_id?)?;
+                    Some(CourseExercise {
+                        chapter_number: chapter.chapter_number,
+                        course_module_id: chapter.course_module_id,
+                        exercise: e,
+                    })
+                })
+                .collect();
+        course_exercises.sort_by_key(|ce| {
+            (
+                ce.chapter_number,
+                page_order_numbers.get(&ce.exercise.page_id).copied(),
+                ce.exercise.order_number,
+            )
+        });
+        Ok(Self {
+            format,
+            modules,
+            course_exercises,
+            users,
+        })
+    }
+
+    fn headers(&self) -> Vec<String> {
+        let module_name = |module: &models::course_modules::CourseModule| {
+            module
+                .name
+                .clone()
+                .unwrap_or_else(|| "default_module".to_string())
+        };
+        let mut headers = vec![];
+        match self.format {
+            GradeExportFormat::Csv | GradeExportFormat::Xlsx => {
+                headers.extend(["user_id", "first_name", "last_name", "email"].map(str::to_string));
+                for module in &self.modules {
+                    let module_name = module_name(module);
+                    headers.push(format!("{module_name}_points"));
+                    headers.push(format!("{module_name}_completed"));
+                    headers.push(format!("{module_name}_grade"));
+                }
+                headers.push("total_points".to_string());
+                let mut previous_chapter_number = None;
+                let mut number_in_chapter = 0;
+                for ce in &self.course_exercises {
+                    if previous_chapter_number != Some(ce.chapter_number) {
+                        previous_chapter_number = Some(ce.chapter_number);
+                        number_in_chapter = 0;
+                    }
+                    number_in_chapter += 1;
+                    headers.push(format!(
+                        "{}.{}: {}",
+                        ce.chapter_number, number_in_chapter, ce.exercise.name
+                    ));
+                }
+            }
+            GradeExportFormat::MoodleCsv => {
+                headers.extend(["First name", "Surname", "Email address"].map(str::to_string));
+                for module in &self.modules {
+                    let module_name = module_name(module);
+                    headers.push(format!("{module_name} points (Real)"));
+                    headers.push(format!("{module_name} grade (Real)"));
+                }
+                headers.push("Course total (Real)".to_string());
+            }
+        }
+        headers
+    }
+
+    /// The row of the student, or None if they are not enrolled on the course instance.
+    fn row(&mut self, points: CourseInstanceUserExercisePoints) -> Option<Vec<Cell>> {
+        let user = self.users.remove(&points.user_id)?;
+        let user_points: HashMap<Uuid, f32> = points
+            .points_for_exercise
+            .into_iter()
+            .map(|p| (p.exercise_id, p.score_given))
+            .collect();
+        let exercise_points: Vec<f32> = self
+            .course_exercises
+            .iter()
+            .map(|ce| user_points.get(&ce.exercise.id).copied().unwrap_or(0.0))
+            .collect();
+        let module_points = |module_id: Uuid| -> f32 {
+            self.course_exercises
+                .iter()
+                .zip(&exercise_points)
+                .filter(|(ce, _)| ce.course_module_id == module_id)
+                .map(|(_, points)| points)
+                .sum()
+        };
+        let total_points: f32 = exercise_points.iter().sum();
+
+        let mut row = vec![];
+        match self.format {
+            GradeExportFormat::Csv | GradeExportFormat::Xlsx => {
+                row.push(Cell::Text(user.user_id.to_string()));
+                row.push(Cell::Text(user.first_name.clone().unwrap_or_default()));
+                row.push(Cell::Text(user.last_name.clone().unwrap_or_default()));
+                row.push(Cell::Text(user.email.clone()));
+                for module in &self.modules {
+                    let completion = user
+                        .completed_modules
+                        .iter()
+                        .find(|cm| cm.course_module_id == module.id);
+                    row.push(Cell::Number(module_points(module.id)));
+                    row.push(Cell::Text(completion.is_some().to_string()));
+                    row.push(Cell::Text(course_module_completion_info_to_grade_string(
+                        completion,
+                    )));
+                }
+                row.push(Cell::Number(total_points));
+                row.extend(exercise_points.iter().map(|points| Cell::Number(*points)));
+            }
+            GradeExportFormat::MoodleCsv => {
+                row.push(Cell::Text(user.first_name.clone().unwrap_or_default()));
+                row.push(Cell::Text(user.last_name.clone().unwrap_or_default()));
+                row.push(Cell::Text(user.email.clone()));
+                for module in &self.modules {
+                    let grade = user
+                        .completed_modules
+                        .iter()
+                        .find(|cm| cm.course_module_id == module.id)
+                        .and_then(|cm| cm.grade);
+                    row.push(Cell::Number(module_points(module.id)));
+                    // Moodle grade items are numeric, so pass/fail grades are left out
+                    row.push(
+                        grade
+                            .map(|grade| Cell::Number(grade as f32))
+                            .unwrap_or(Cell::Empty),
+                    );
+                }
+                row.push(Cell::Number(total_points));
+            }
+        }
+        Some(row)
+    }
+}
+
+/// Creates an Excel workbook with a single sheet that contains the table.
+fn write_xlsx(table: &Table) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("[Content_Types].xml", options)?;
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#,
+    )?;
+    zip.start_file("_rels/.rels", options)?;
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#,
+    )?;
+    zip.start_file("xl/workbook.xml", options)?;
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Grades" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+    )?;
+    zip.start_file("xl/_rels/workbook.xml.rels", options)?;
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+    )?;
+
+    zip.start_file("xl/worksheets/sheet1.xml", options)?;
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    )?;
+    let header_row = table
+        .headers
+        .iter()
+        .map(|header| Cell::Text(header.clone()))
+        .collect::<Vec<_>>();
+    for (row_idx, row) in std::iter::once(&header_row)
+        .chain(table.rows.iter())
+        .enumerate()
+    {
+        let row_number = row_idx + 1;
+        write!(zip, r#"<row r="{}">"#, row_number)?;
+        for (column_idx, cell) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_name(column_idx), row_number);
+            match cell {
+                Cell::Text(text) => write!(
+                    zip,
+                    r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                    reference,
+                    escape_xml(text)
+                )?,
+                Cell::Number(number) => {
+                    write!(zip, r#"<c r="{}"><v>{}</v></c>"#, reference, number)?
+                }
+                Cell::Empty => (),
+            }
+        }
+        zip.write_all(b"</row>")?;
+    }
+    zip.write_all(b"</sheetData></worksheet>")?;
+
+    let res = zip.finish()?.into_inner();
+    Ok(res)
+}
+
+/// The spreadsheet column name for the zero-based index: A, B, ..., Z, AA, AB, ...
+fn column_name(idx: usize) -> String {
+    let mut name = vec![];
+    let mut remaining = idx + 1;
+    while remaining > 0 {
+        let letter = (remaining - 1) % 26;
+        name.push(b'A' + letter as u8);
+        remaining = (remaining - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("column names are ascii")
+}
+
+/// Escapes the text for XML, dropping the control characters XML does not allow.
+fn escape_xml(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\t' | '\n' | '\r' => res.push(c),
+            c if c.is_control() => (),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use zip::ZipArchive;
+
+    use super::*;
+
+    #[test]
+    fn names_columns() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!(
+            escape_xml("<Tom & \"Jerry\">\u{0}"),
+            "&lt;Tom &amp; &quot;Jerry&quot;&gt;"
+        );
+    }
+
+    #[test]
+    fn writes_xlsx() {
+        let table = Table {
+            headers: vec!["email".to_string(), "total_points".to_string()],
+            rows: vec![vec![
+                Cell::Text("student@example.com".to_string()),
+                Cell::Number(1.5),
+            ]],
+        };
+        let xlsx = write_xlsx(&table).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(xlsx)).unwrap();
+        for name in [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "xl/workbook.xml",
+            "xl/_rels/workbook.xml.rels",
+        ] {
+            assert!(archive.by_name(name).is_ok(), "missing {}", name);
+        }
+        let mut sheet = String::new();
+        archive
+            .by_name("xl/worksheets/sheet1.xml")
+            .unwrap()
+            .read_to_string(&mut sheet)
+            .unwrap();
+        assert!(sheet.contains(
+            r#"<row r="1"><c r="A1" t="inlineStr"><is><t xml:space="preserve">email</t></is></c>"#
+        ));
+        assert!(sheet.contains(r#"<c r="A2" t="inlineStr"><is><t xml:space="preserve">student@example.com</t></is></c><c r="B2"><v>1.5</v></c></row>"#));
+    }
+}
@@ -0,0 +1,522 @@
+/*!
+Impersonation lets teachers view the course material as one of the students of their course, for example when debugging a problem the student has reported.
+
+The impersonation is stored in the session with [authorization::start_impersonation]. While it is active:
+- the [ImpersonationGuard] middleware makes the [AuthUser] extractor return the impersonated user for the course material endpoints of the impersonated course, and the teacher for all other endpoints,
+- the [ImpersonationGuard] middleware rejects all requests that could modify something, apart from ending the impersonation and logging out,
+- the impersonation ends automatically after [authorization::IMPERSONATION_MAX_DURATION_MINUTES] minutes.
+
+Starting and ending the impersonation are recorded in the audit log.
+*/
+
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_http::{HttpMessage, Method};
+use actix_session::{Session, SessionExt};
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+use chrono::{DateTime, Utc};
+use futures_util::future::LocalBoxFuture;
+use headless_lms_models::{
+    audit_log_entries::AuditLogAction,
+    chapters, course_instances, exercises, pages, roles, user_course_settings,
+    users::{self, User},
+};
+
+use crate::{
+    domain::{
+        audit_log,
+        authorization::{self, Impersonation},
+    },
+    prelude::*,
+};
+
+/// Requests that modify something are allowed to these paths while impersonating.
+const ALLOWED_MODIFYING_PATHS: &[&str] = &[
+    "/api/v0/main-frontend/impersonation/stop",
+    "/api/v0/auth/logout",
+];
+/// Requests to these endpoints are handled as the impersonated user if they are related to the impersonated course.
+const IMPERSONATED_PATH_PREFIX: &str = "/api/v0/course-material/";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpersonationEndReason {
+    Stopped,
+    LoggedOut,
+    Expired,
+}
+
+/// Ends the impersonation in the session, if any, and records it in the audit log. Returns the impersonation that was ended.
+pub async fn end(
+    conn: &mut PgConnection,
+    session: &Session,
+    actor: &AuthUser,
+    reason: ImpersonationEndReason,
+) -> Result<Option<Impersonation>, ControllerError> {
+    let impersonation = match authorization::end_impersonation(session) {
+        Some(impersonation) => impersonation,
+        None => return Ok(None),
+    };
+    audit_log::record(
+        conn,
+        actor,
+        AuditLogAction::ImpersonationEnded,
+        impersonation.impersonated_user_id,
+        Some(impersonation.course_id),
+        None,
+        Some(serde_json::json!({ "reason": reason })),
+    )
+    .await?;
+    Ok(Some(impersonation))
+}
+
+/// Returns the student the impersonator may view the course material as. Only students who are enrolled on the course and have no roles can be impersonated.
+pub async fn get_impersonatable_student(
+    conn: &mut PgConnection,
+    impersonator_id: Uuid,
+    email: &str,
+    course_id: Uuid,
+) -> Result<User, ControllerError> {
+    let not_enrolled = || {
+        ControllerError::new(
+            ControllerErrorType::NotFound,
+            "No student with the given email is enrolled on the course.".to_string(),
+            None,
+        )
+    };
+    let student = users::try_get_by_email(conn, email.trim())
+        .await?
+        .ok_or_else(not_enrolled)?;
+    user_course_settings::get_user_course_settings_by_course_id(conn, student.id, course_id)
+        .await?
+        .ok_or_else(not_enrolled)?;
+    if student.id == impersonator_id {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "You cannot impersonate yourself.".to_string(),
+            None,
+        ));
+    }
+    // the roles would let the impersonator see more than they are allowed to
+    if !roles::get_roles(conn, student.id).await?.is_empty() {
+        return Err(ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "Only users who have no roles can be impersonated.".to_string(),
+            None,
+        ));
+    }
+    Ok(student)
+}
+
+/// Ends the impersonation in the session if it has expired. Returns whether an impersonation was ended.
+pub async fn end_if_expired(
+    conn: &mut PgConnection,
+    session: &Session,
+    now: DateTime<Utc>,
+) -> Result<bool, ControllerError> {
+    match authorization::get_impersonation(session) {
+        Some(impersonation) if impersonation.has_expired(now) => {}
+        _ => return Ok(false),
+    }
+    match authorization::get_session_user(session) {
+        Some(actor) => {
+            end(conn, session, &actor, ImpersonationEndReason::Expired).await?;
+        }
+        None => {
+            authorization::end_impersonation(session);
+        }
+    }
+    Ok(true)
+}
+
+/// Whether a request with the given method can be made to the given path while impersonating.
+pub fn is_allowed_while_impersonating(method: &Method, path: &str) -> bool {
+    method.is_safe() || ALLOWED_MODIFYING_PATHS.contains(&path)
+}
+
+/// The course a course material request is related to, based on the resource in its path. None if the request is not a course material request or the resource is not part of a course.
+pub async fn get_course_id_of_course_material_request(
+    conn: &mut PgConnection,
+    path: &str,
+) -> Result<Option<Uuid>, ControllerError> {
+    let mut segments = match path.strip_prefix(IMPERSONATED_PATH_PREFIX) {
+        Some(rest) => rest.split('/'),
+        None => return Ok(None),
+    };
+    let (resource, id) = match (
+        segments.next(),
+        segments.next().and_then(|id| Uuid::parse_str(id).ok()),
+    ) {
+        (Some(resource), Some(id)) => (resource, id),
+        _ => return Ok(None),
+    };
+    let course_id = match resource {
+        "courses" | "proposed-edits" => Some(id),
+        "course-instances" => Some(course_instances::get_course_id(conn, id).await?),
+        "chapters" => Some(chapters::get_course_id(conn, id).await?),
+        "pages" => pages::get_page(conn, id).await?.course_id,
+        "exercises" => exercises::get_by_id(conn, id).await?.course_id,
+        _ => None,
+    };
+    Ok(course_id)
+}
+
+pub struct ImpersonationGuard;
+
+impl<S, B> Transform<S, ServiceRequest> for ImpersonationGuard
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ImpersonationGuardMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ImpersonationGuardMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Rejects requests that could modify something while impersonating and ends expired impersonations. The course material requests related to the impersonated course are handled as the impersonated user, other requests are passed through as is.
+pub struct ImpersonationGuardMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ImpersonationGuardMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let session = req.get_session();
+        let impersonation = match authorization::get_impersonation(&session) {
+            Some(impersonation) => impersonation,
+            None => return Box::pin(self.service.call(req)),
+        };
+
+        let service = Rc::clone(&self.service);
+        Box::pin(async move {
+            let now = Utc::now();
+            if impersonation.has_expired(now) || req.path().starts_with(IMPERSONATED_PATH_PREFIX) {
+                let pool = req
+                    .app_data::<web::Data<PgPool>>()
+                    .expect("Missing database pool in app data")
+                    .clone();
+                let mut conn = pool.acquire().await.map_err(ControllerError::from)?;
+                if end_if_expired(&mut conn, &session, now).await? {
+                    drop(conn);
+                    return service.call(req).await;
+                }
+                // the impersonated user is only used for the course the impersonation was started for
+                let course_id =
+                    get_course_id_of_course_material_request(&mut conn, req.path()).await?;
+                drop(conn);
+                if course_id == Some(impersonation.course_id) {
+                    authorization::remember_impersonated_user_for_request(
+                        &session,
+                        &mut req.extensions_mut(),
+                    );
+                }
+            }
+
+            if !is_allowed_while_impersonating(req.method(), req.path()) {
+                return Err(ControllerError::new(
+                    ControllerErrorType::Forbidden,
+                    "Nothing can be modified while viewing the course material as another user."
+                        .to_string(),
+                    None,
+                )
+                .into());
+            }
+            service.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use actix_session::{storage::CookieSessionStore, SessionMiddleware};
+    use actix_web::{cookie::Key, http::StatusCode, test, App, HttpRequest, HttpResponse};
+    use chrono::Duration;
+    use headless_lms_models::{
+        audit_log_entries::{self, AuditLogFilter},
+        course_instance_enrollments::{self, NewCourseInstanceEnrollment},
+        roles::{RoleDomain, UserRole},
+        PKeyPolicy,
+    };
+    use headless_lms_utils::pagination::Pagination;
+
+    use super::*;
+    use crate::test_helper::*;
+
+    fn user(id: Uuid) -> User {
+        User {
+            id,
+            first_name: None,
+            last_name: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            upstream_id: None,
+            email: format!("{}@example.com", id),
+        }
+    }
+
+    #[actix_web::test]
+    async fn only_enrolled_students_without_roles_can_be_impersonated() {
+        insert_data!(:tx, :user, :org, :course, :instance);
+        let student = users::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            "impersonated-student@example.com",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let err = get_impersonatable_student(
+            tx.as_mut(),
+            user,
+            "impersonated-student@example.com",
+            course,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err.error_type(), ControllerErrorType::NotFound));
+
+        course_instance_enrollments::insert_enrollment_and_set_as_current(
+            tx.as_mut(),
+            NewCourseInstanceEnrollment {
+                user_id: student,
+                course_id: course,
+                course_instance_id: instance.id,
+            },
+        )
+        .await
+        .unwrap();
+        let impersonated = get_impersonatable_student(
+            tx.as_mut(),
+            user,
+            " impersonated-student@example.com ",
+            course,
+        )
+        .await
+        .unwrap();
+        assert_eq!(impersonated.id, student);
+
+        roles::insert(
+            tx.as_mut(),
+            student,
+            UserRole::Assistant,
+            RoleDomain::Course(course),
+        )
+        .await
+        .unwrap();
+        let err = get_impersonatable_student(
+            tx.as_mut(),
+            user,
+            "impersonated-student@example.com",
+            course,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err.error_type(), ControllerErrorType::BadRequest));
+    }
+
+    #[actix_web::test]
+    async fn expired_impersonation_is_ended_and_recorded() {
+        insert_data!(:tx, :user, :org, :course);
+        let student = users::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            "expired-impersonation@example.com",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let session = test::TestRequest::default().to_http_request().get_session();
+        authorization::remember(&session, users::get_by_id(tx.as_mut(), user).await.unwrap())
+            .unwrap();
+        let started_at = Utc::now() - Duration::minutes(30);
+        authorization::start_impersonation(
+            &session,
+            users::get_by_id(tx.as_mut(), student).await.unwrap(),
+            course,
+            started_at,
+        )
+        .unwrap();
+
+        // not expired yet
+        assert!(!end_if_expired(tx.as_mut(), &session, Utc::now())
+            .await
+            .unwrap());
+        assert!(authorization::get_impersonation(&session).is_some());
+
+        let expired_at =
+            started_at + Duration::minutes(authorization::IMPERSONATION_MAX_DURATION_MINUTES);
+        assert!(end_if_expired(tx.as_mut(), &session, expired_at)
+            .await
+            .unwrap());
+        assert!(authorization::get_impersonation(&session).is_none());
+        assert_eq!(
+            authorization::get_session_user(&session).map(|user| user.id),
+            Some(user)
+        );
+        let entries = audit_log_entries::get_filtered_paginated(
+            tx.as_mut(),
+            &AuditLogFilter {
+                action: Some(AuditLogAction::ImpersonationEnded),
+                resource_id: Some(student),
+                ..Default::default()
+            },
+            Pagination::new(1, 10).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].actor_user_id, Some(user));
+        assert_eq!(entries[0].course_id, Some(course));
+        assert_eq!(
+            entries[0].after_summary,
+            Some(serde_json::json!({ "reason": "expired" }))
+        );
+    }
+
+    #[test]
+    fn only_ending_the_impersonation_can_modify_while_impersonating() {
+        assert!(is_allowed_while_impersonating(
+            &Method::GET,
+            "/api/v0/course-material/courses"
+        ));
+        assert!(is_allowed_while_impersonating(
+            &Method::POST,
+            "/api/v0/main-frontend/impersonation/stop"
+        ));
+        assert!(is_allowed_while_impersonating(
+            &Method::POST,
+            "/api/v0/auth/logout"
+        ));
+        assert!(!is_allowed_while_impersonating(
+            &Method::POST,
+            "/api/v0/main-frontend/impersonation/start"
+        ));
+        assert!(!is_allowed_while_impersonating(
+            &Method::PUT,
+            "/api/v0/course-material/courses/user-settings"
+        ));
+        assert!(!is_allowed_while_impersonating(
+            &Method::DELETE,
+            "/api/v0/main-frontend/courses"
+        ));
+    }
+
+    #[actix_web::test]
+    async fn impersonated_user_is_only_used_for_the_impersonated_course() {
+        let teacher_id = Uuid::new_v4();
+        let student_id = Uuid::new_v4();
+        let course_id = Uuid::new_v4();
+        let other_course_id = Uuid::new_v4();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(init_pool().await))
+                .route(
+                    "/start",
+                    web::post().to(move |session: Session| async move {
+                        authorization::remember(&session, user(teacher_id)).unwrap();
+                        authorization::start_impersonation(
+                            &session,
+                            user(student_id),
+                            course_id,
+                            Utc::now(),
+                        )
+                        .unwrap();
+                        HttpResponse::Ok().finish()
+                    }),
+                )
+                .default_service(web::to(|req: HttpRequest, user: AuthUser| async move {
+                    HttpResponse::Ok().body(format!("{} {}", req.method(), user.id))
+                }))
+                .wrap(ImpersonationGuard)
+                .wrap(
+                    SessionMiddleware::builder(CookieSessionStore::default(), Key::from(&[0; 64]))
+                        .cookie_secure(false)
+                        .build(),
+                ),
+        )
+        .await;
+        let res =
+            test::call_service(&app, test::TestRequest::post().uri("/start").to_request()).await;
+        let session = res.response().cookies().next().unwrap().into_owned();
+        let call = |method: Method, path: String| {
+            test::TestRequest::default()
+                .method(method)
+                .uri(&path)
+                .cookie(session.clone())
+                .to_request()
+        };
+
+        let body = test::call_and_read_body(
+            &app,
+            call(
+                Method::GET,
+                format!(
+                    "/api/v0/course-material/courses/{}/user-settings",
+                    course_id
+                ),
+            ),
+        )
+        .await;
+        assert_eq!(body, format!("GET {}", student_id).as_bytes());
+        let body = test::call_and_read_body(
+            &app,
+            call(
+                Method::GET,
+                format!(
+                    "/api/v0/course-material/courses/{}/user-settings",
+                    other_course_id
+                ),
+            ),
+        )
+        .await;
+        assert_eq!(body, format!("GET {}", teacher_id).as_bytes());
+        let body = test::call_and_read_body(
+            &app,
+            call(Method::GET, "/api/v0/main-frontend/courses".to_string()),
+        )
+        .await;
+        assert_eq!(body, format!("GET {}", teacher_id).as_bytes());
+
+        let res = test::call_service(
+            &app,
+            call(
+                Method::POST,
+                format!("/api/v0/course-material/courses/{}/feedback", course_id),
+            ),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res =
+            test::call_service(&app, call(Method::POST, "/api/v0/auth/logout".to_string())).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
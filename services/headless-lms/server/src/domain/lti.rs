@@ -0,0 +1,994 @@
+/*!
+Embedding courses in other learning platforms, such as Moodle or Canvas, as an LTI 1.3 tool.
+
+The platforms are registered per organization in the `lti_registrations` table. A launch from a platform goes as follows:
+
+1. The platform redirects the user to the login initiation endpoint. [begin_login] stores a state and a nonce and returns the URL of the platform's authorization endpoint the user should be redirected to.
+2. The platform posts the launch message, a signed ID token, to the launch endpoint, where [validate_launch] validates it against the platform's keys.
+3. In a resource link launch, [launch_resource_link] links the platform user to a user, enrolls them to the course instance of the platform context and returns the page the user should be redirected to. In a deep linking launch, a teacher selects the page or the chapter a new link launches, and [deep_linking_response] creates the signed response that is posted back to the platform.
+
+The scores of the users in the exercises of the linked pages and chapters are passed back to the platforms with the Assignment and Grade Services by the `lti-score-passback` program, see [fetch_access_token] and [send_score].
+
+The session cookie is `SameSite=Strict`, so the launches must happen in a top-level window. Browsers would not send the cookie to an iframe on the platform's site, and a launch that is started in an iframe is restarted in a new window.
+
+The tool signs its messages with the RSA key in the `LTI_PRIVATE_KEY` environment variable. The platforms get the public key from the JWKS endpoint.
+*/
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, Duration, Utc};
+use headless_lms_models::{
+    chapters,
+    course_instance_enrollments::{self, NewCourseInstanceEnrollment},
+    courses,
+    lti_contexts::{self, NewLtiContext},
+    lti_deep_linking_requests::LtiDeepLinkingRequest,
+    lti_login_states,
+    lti_registrations::LtiRegistration,
+    lti_resource_links::{self, LtiLinkTarget, NewLtiResourceLink},
+    lti_scores::{self, CurrentLtiScore, PendingLtiScore},
+    lti_users, organizations, pages,
+    users::{self, User},
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header, Validation};
+use oauth2::CsrfToken;
+use openssl::pkey::PKey;
+use reqwest::Client;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::{
+    domain::oidc::{self, JsonWebKey, JsonWebKeySet},
+    prelude::*,
+};
+
+const LTI_VERSION: &str = "1.3.0";
+/// Allowed clock skew between us and the platform when validating the launch message.
+const LAUNCH_LEEWAY_SECONDS: u64 = 60;
+/// How long the messages signed by the tool are valid.
+const SIGNED_MESSAGE_VALID_MINUTES: i64 = 5;
+const SCORE_SCOPE: &str = "https://purl.imsglobal.org/spec/lti-ags/scope/score";
+const SCORE_CONTENT_TYPE: &str = "application/vnd.ims.lis.v1.score+json";
+
+/// The key the tool signs its messages with.
+#[derive(Clone)]
+pub struct LtiToolKey {
+    encoding_key: EncodingKey,
+    public_key: JsonWebKey,
+}
+
+impl std::fmt::Debug for LtiToolKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the private key is left out on purpose
+        f.debug_struct("LtiToolKey")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LtiToolKey {
+    /// Reads the PEM encoded RSA private key from `LTI_PRIVATE_KEY`. Returns None if the variable is not set.
+    pub fn try_from_env() -> anyhow::Result<Option<Self>> {
+        match std::env::var("LTI_PRIVATE_KEY") {
+            Ok(private_key) => Ok(Some(Self::new(private_key.as_bytes())?)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn new(private_key_pem: &[u8]) -> anyhow::Result<Self> {
+        let rsa = PKey::private_key_from_pem(private_key_pem)
+            .context("Invalid LTI private key")?
+            .rsa()
+            .context("The LTI private key is not an RSA key")?;
+        let n = base64::encode_config(rsa.n().to_vec(), base64::URL_SAFE_NO_PAD);
+        let e = base64::encode_config(rsa.e().to_vec(), base64::URL_SAFE_NO_PAD);
+        // the key id only needs to change when the key changes
+        let key_id = format!("{:x}", Sha256::digest(n.as_bytes()))[..16].to_string();
+        Ok(Self {
+            encoding_key: EncodingKey::from_rsa_pem(private_key_pem)?,
+            public_key: JsonWebKey {
+                kty: "RSA".to_string(),
+                kid: Some(key_id),
+                key_use: Some("sig".to_string()),
+                alg: Some("RS256".to_string()),
+                n: Some(n),
+                e: Some(e),
+            },
+        })
+    }
+
+    /// The public key set the platforms validate the tool's messages with.
+    pub fn jwks(&self) -> JsonWebKeySet {
+        JsonWebKeySet {
+            keys: vec![self.public_key.clone()],
+        }
+    }
+
+    fn sign<T: Serialize>(&self, claims: &T) -> anyhow::Result<String> {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = self.public_key.kid.clone();
+        Ok(jsonwebtoken::encode(&header, claims, &self.encoding_key)?)
+    }
+}
+
+/// The URL the platforms post the launch messages to.
+pub fn launch_url(base_url: &str) -> String {
+    format!("{}/api/v0/lti/launch", base_url.trim_end_matches('/'))
+}
+
+/// Starts a launch initiated by the platform. Returns the URL of the platform's authorization endpoint the user should be redirected to.
+pub async fn begin_login(
+    conn: &mut PgConnection,
+    registration: &LtiRegistration,
+    launch_url: &str,
+    login_hint: &str,
+    lti_message_hint: Option<&str>,
+) -> anyhow::Result<Url> {
+    let state = CsrfToken::new_random();
+    let nonce = CsrfToken::new_random();
+    lti_login_states::insert(conn, registration.id, state.secret(), nonce.secret()).await?;
+
+    let mut url = Url::parse(&registration.auth_login_url)?;
+    url.query_pairs_mut()
+        .append_pair("scope", "openid")
+        .append_pair("response_type", "id_token")
+        .append_pair("response_mode", "form_post")
+        .append_pair("prompt", "none")
+        .append_pair("client_id", &registration.client_id)
+        .append_pair("redirect_uri", launch_url)
+        .append_pair("login_hint", login_hint)
+        .append_pair("state", state.secret())
+        .append_pair("nonce", nonce.secret());
+    if let Some(lti_message_hint) = lti_message_hint {
+        url.query_pairs_mut()
+            .append_pair("lti_message_hint", lti_message_hint);
+    }
+    Ok(url)
+}
+
+/// Fetches the public keys of the platform.
+pub async fn fetch_platform_keys(
+    http_client: &Client,
+    registration: &LtiRegistration,
+) -> anyhow::Result<JsonWebKeySet> {
+    let jwks = http_client
+        .get(&registration.jwks_url)
+        .send()
+        .await
+        .context("Failed to fetch the platform keys")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Invalid platform keys")?;
+    Ok(jwks)
+}
+
+/// A validated launch message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LtiLaunch {
+    /// The id of the user at the platform.
+    pub subject: String,
+    pub email: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub deployment_id: String,
+    pub message: LtiMessage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LtiMessage {
+    /// The user opened a link in the platform.
+    ResourceLink(ResourceLinkLaunch),
+    /// A teacher is creating a new link in the platform.
+    DeepLinking {
+        deep_link_return_url: String,
+        data: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceLinkLaunch {
+    pub resource_link_id: String,
+    pub resource_link_title: Option<String>,
+    pub context_id: String,
+    pub context_title: Option<String>,
+    pub target: LtiLinkTarget,
+    /// Where the score of the user is sent to. None if the platform does not accept scores for the link.
+    pub line_item_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LaunchClaims {
+    sub: String,
+    nonce: String,
+    email: Option<String>,
+    given_name: Option<String>,
+    family_name: Option<String>,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/message_type")]
+    message_type: String,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/version")]
+    version: String,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/deployment_id")]
+    deployment_id: String,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/resource_link")]
+    resource_link: Option<ResourceLinkClaim>,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/context")]
+    context: Option<ContextClaim>,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/custom", default)]
+    custom: Map<String, Value>,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti-ags/claim/endpoint")]
+    ags_endpoint: Option<AgsEndpointClaim>,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti-dl/claim/deep_linking_settings")]
+    deep_linking_settings: Option<DeepLinkingSettingsClaim>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResourceLinkClaim {
+    id: String,
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContextClaim {
+    id: String,
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AgsEndpointClaim {
+    #[serde(default)]
+    scope: Vec<String>,
+    lineitem: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepLinkingSettingsClaim {
+    deep_link_return_url: String,
+    #[serde(default)]
+    accept_types: Vec<String>,
+    data: Option<String>,
+}
+
+/// Validates the signature, issuer, audience, expiration and nonce of the launch message and parses the LTI claims.
+pub fn validate_launch(
+    id_token: &str,
+    registration: &LtiRegistration,
+    jwks: &JsonWebKeySet,
+    nonce: &str,
+) -> anyhow::Result<LtiLaunch> {
+    let header = jsonwebtoken::decode_header(id_token)?;
+    if !matches!(
+        header.alg,
+        Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512
+    ) {
+        bail!("Unsupported launch message algorithm {:?}", header.alg);
+    }
+    let key = oidc::find_decoding_key(jwks, &header)?;
+    let mut validation = Validation::new(header.alg);
+    validation.leeway = LAUNCH_LEEWAY_SECONDS;
+    validation.iss = Some(registration.issuer.clone());
+    validation.set_audience(&[&registration.client_id]);
+    let claims = jsonwebtoken::decode::<LaunchClaims>(id_token, &key, &validation)?.claims;
+
+    if claims.nonce != nonce {
+        bail!("Invalid nonce");
+    }
+    if claims.version != LTI_VERSION {
+        bail!("Unsupported LTI version {}", claims.version);
+    }
+    let message = match claims.message_type.as_str() {
+        "LtiResourceLinkRequest" => {
+            let resource_link = claims
+                .resource_link
+                .ok_or_else(|| anyhow!("Missing resource link"))?;
+            let context = claims.context.ok_or_else(|| anyhow!("Missing context"))?;
+            let line_item_url = claims
+                .ags_endpoint
+                .filter(|endpoint| endpoint.scope.iter().any(|scope| scope == SCORE_SCOPE))
+                .and_then(|endpoint| endpoint.lineitem);
+            LtiMessage::ResourceLink(ResourceLinkLaunch {
+                resource_link_id: resource_link.id,
+                resource_link_title: resource_link.title,
+                context_id: context.id,
+                context_title: context.title,
+                target: link_target(&claims.custom)?,
+                line_item_url,
+            })
+        }
+        "LtiDeepLinkingRequest" => {
+            let settings = claims
+                .deep_linking_settings
+                .ok_or_else(|| anyhow!("Missing deep linking settings"))?;
+            if !settings
+                .accept_types
+                .iter()
+                .any(|accept_type| accept_type == "ltiResourceLink")
+            {
+                bail!("The platform does not accept resource links");
+            }
+            LtiMessage::DeepLinking {
+                deep_link_return_url: settings.deep_link_return_url,
+                data: settings.data,
+            }
+        }
+        other => bail!("Unsupported message type {}", other),
+    };
+    Ok(LtiLaunch {
+        subject: claims.sub,
+        email: claims.email,
+        first_name: claims.given_name,
+        last_name: claims.family_name,
+        deployment_id: claims.deployment_id,
+        message,
+    })
+}
+
+/// The linked page or chapter is in the custom parameters set in the deep linking response.
+fn link_target(custom: &Map<String, Value>) -> anyhow::Result<LtiLinkTarget> {
+    let custom_id = |name: &str| {
+        custom
+            .get(name)
+            .and_then(Value::as_str)
+            .map(Uuid::parse_str)
+            .transpose()
+    };
+    match (custom_id("page_id")?, custom_id("chapter_id")?) {
+        (Some(page_id), None) => Ok(LtiLinkTarget::Page(page_id)),
+        (None, Some(chapter_id)) => Ok(LtiLinkTarget::Chapter(chapter_id)),
+        _ => bail!("The link needs either a page_id or a chapter_id custom parameter"),
+    }
+}
+
+/**
+Handles a resource link launch. Returns the user and the path of the page the user should be redirected to.
+
+The launch enrolls the user to the course instance of the platform context, and the user's score in the linked page or chapter will be passed back to the platform.
+*/
+pub async fn launch_resource_link(
+    conn: &mut PgConnection,
+    registration: &LtiRegistration,
+    launch: &LtiLaunch,
+    resource_link: &ResourceLinkLaunch,
+) -> ControllerResult<(User, String)> {
+    let page_id = match resource_link.target {
+        LtiLinkTarget::Page(page_id) => page_id,
+        LtiLinkTarget::Chapter(chapter_id) => chapters::get_chapter(conn, chapter_id)
+            .await?
+            .front_page_id
+            .ok_or_else(|| {
+                ControllerError::new(
+                    ControllerErrorType::NotFound,
+                    "The linked chapter has no front page.".to_string(),
+                    None,
+                )
+            })?,
+    };
+    let page = pages::get_page(conn, page_id).await?;
+    let course = match page.course_id {
+        Some(course_id) => courses::get_course(conn, course_id).await?,
+        None => {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "Only course pages can be linked.".to_string(),
+                None,
+            ))
+        }
+    };
+    // the platform can only launch the courses of the organization it is registered to
+    if course.organization_id != registration.organization_id {
+        return Err(ControllerError::new(
+            ControllerErrorType::Forbidden,
+            "The linked course is not available to this platform.".to_string(),
+            None,
+        ));
+    }
+    let organization = organizations::get_organization(conn, course.organization_id).await?;
+
+    let mut tx = conn.begin().await?;
+    let user = get_or_create_user(&mut tx, registration, launch).await?;
+    let context = lti_contexts::get_or_insert(
+        &mut tx,
+        &NewLtiContext {
+            lti_registration_id: registration.id,
+            deployment_id: &launch.deployment_id,
+            context_id: &resource_link.context_id,
+            title: resource_link.context_title.as_deref(),
+            course_id: course.id,
+        },
+    )
+    .await?;
+    course_instance_enrollments::insert_enrollment_and_set_as_current(
+        &mut tx,
+        NewCourseInstanceEnrollment {
+            user_id: user.id,
+            course_id: course.id,
+            course_instance_id: context.course_instance_id,
+        },
+    )
+    .await?;
+    let link = lti_resource_links::upsert(
+        &mut tx,
+        &NewLtiResourceLink {
+            lti_context_id: context.id,
+            resource_link_id: &resource_link.resource_link_id,
+            title: resource_link.resource_link_title.as_deref(),
+            target: resource_link.target,
+            line_item_url: resource_link.line_item_url.as_deref(),
+        },
+    )
+    .await?;
+    lti_scores::insert_if_not_exists(&mut tx, link.id, user.id).await?;
+    tx.commit().await?;
+
+    let path = format!(
+        "/org/{}/courses/{}{}",
+        organization.slug, course.slug, page.url_path
+    );
+    Ok((user, path))
+}
+
+/// Returns the user linked to the platform user. If there is no linked user yet, the platform user is linked to a new user, or to the user with the same email if the registration allows it and the user has only been active in the organization of the platform.
+pub async fn get_or_create_user(
+    conn: &mut PgConnection,
+    registration: &LtiRegistration,
+    launch: &LtiLaunch,
+) -> ControllerResult<User> {
+    if let Some(user_id) =
+        lti_users::try_get_user_id_by_subject(conn, registration.id, &launch.subject).await?
+    {
+        let user = users::get_by_id(conn, user_id).await?;
+        return Ok(user);
+    }
+
+    let email = launch.email.as_deref().ok_or_else(|| {
+        ControllerError::new(
+            ControllerErrorType::BadRequest,
+            "The platform did not send the email of the user. The platform needs to be configured to share the email with the tool.".to_string(),
+            None,
+        )
+    })?;
+    let mut tx = conn.begin().await?;
+    let user = match users::try_get_by_email(&mut tx, email).await? {
+        Some(existing_user) => {
+            if !registration.link_existing_users_by_email {
+                return Err(ControllerError::new(
+                    ControllerErrorType::Forbidden,
+                    "An account with the same email already exists, but the platform is not trusted to link accounts by email.".to_string(),
+                    None,
+                ));
+            }
+            // the platform is only trusted with the users of its own organization
+            if !users::is_only_in_organization(
+                &mut tx,
+                existing_user.id,
+                registration.organization_id,
+            )
+            .await?
+            {
+                return Err(ControllerError::new(
+                    ControllerErrorType::Forbidden,
+                    "An account with the same email already exists, and it is used outside the organization of the platform. It cannot be linked to the platform.".to_string(),
+                    None,
+                ));
+            }
+            info!(user_id = ?existing_user.id, lti_registration_id = ?registration.id, "Linking LTI user to an existing user");
+            existing_user
+        }
+        None => {
+            let user_id = users::insert(
+                &mut tx,
+                PKeyPolicy::Generate,
+                email,
+                launch.first_name.as_deref(),
+                launch.last_name.as_deref(),
+            )
+            .await?;
+            users::get_by_id(&mut tx, user_id).await?
+        }
+    };
+    lti_users::insert(&mut tx, registration.id, &launch.subject, user.id).await?;
+    tx.commit().await?;
+    Ok(user)
+}
+
+#[derive(Debug, Serialize)]
+struct DeepLinkingResponseClaims<'a> {
+    iss: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+    nonce: String,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/message_type")]
+    message_type: &'static str,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/version")]
+    version: &'static str,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti/claim/deployment_id")]
+    deployment_id: &'a str,
+    #[serde(
+        rename = "https://purl.imsglobal.org/spec/lti-dl/claim/data",
+        skip_serializing_if = "Option::is_none"
+    )]
+    data: Option<&'a str>,
+    #[serde(rename = "https://purl.imsglobal.org/spec/lti-dl/claim/content_items")]
+    content_items: Vec<ResourceLinkContentItem>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceLinkContentItem {
+    #[serde(rename = "type")]
+    item_type: &'static str,
+    title: String,
+    url: String,
+    custom: HashMap<&'static str, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_item: Option<LineItem>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineItem {
+    score_maximum: i32,
+    label: String,
+}
+
+/**
+Creates the signed deep linking response that adds a link to the page or the chapter to the platform.
+
+The link gets a line item in the platform's gradebook if the page or the chapter has exercises, i.e. if `score_maximum` is greater than zero.
+*/
+pub fn deep_linking_response(
+    key: &LtiToolKey,
+    registration: &LtiRegistration,
+    request: &LtiDeepLinkingRequest,
+    launch_url: &str,
+    title: &str,
+    target: LtiLinkTarget,
+    score_maximum: i32,
+) -> anyhow::Result<String> {
+    let custom = match target {
+        LtiLinkTarget::Page(page_id) => HashMap::from([("page_id", page_id.to_string())]),
+        LtiLinkTarget::Chapter(chapter_id) => {
+            HashMap::from([("chapter_id", chapter_id.to_string())])
+        }
+    };
+    let now = Utc::now();
+    let claims = DeepLinkingResponseClaims {
+        iss: &registration.client_id,
+        aud: &registration.issuer,
+        iat: now.timestamp(),
+        exp: (now + Duration::minutes(SIGNED_MESSAGE_VALID_MINUTES)).timestamp(),
+        nonce: CsrfToken::new_random().secret().clone(),
+        message_type: "LtiDeepLinkingResponse",
+        version: LTI_VERSION,
+        deployment_id: &request.deployment_id,
+        data: request.data.as_deref(),
+        content_items: vec![ResourceLinkContentItem {
+            item_type: "ltiResourceLink",
+            title: title.to_string(),
+            url: launch_url.to_string(),
+            custom,
+            line_item: (score_maximum > 0).then(|| LineItem {
+                score_maximum,
+                label: title.to_string(),
+            }),
+        }],
+    };
+    key.sign(&claims)
+}
+
+#[derive(Debug, Serialize)]
+struct ClientAssertionClaims<'a> {
+    iss: &'a str,
+    sub: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+    jti: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+}
+
+/// Fetches an access token for sending scores to the platform with the client credentials grant, authenticating with a JWT signed by the tool.
+pub async fn fetch_access_token(
+    http_client: &Client,
+    key: &LtiToolKey,
+    registration: &LtiRegistration,
+) -> anyhow::Result<String> {
+    let now = Utc::now();
+    let client_assertion = key.sign(&ClientAssertionClaims {
+        iss: &registration.client_id,
+        sub: &registration.client_id,
+        aud: &registration.auth_token_url,
+        iat: now.timestamp(),
+        exp: (now + Duration::minutes(SIGNED_MESSAGE_VALID_MINUTES)).timestamp(),
+        jti: Uuid::new_v4().to_string(),
+    })?;
+    let response: AccessTokenResponse = http_client
+        .post(&registration.auth_token_url)
+        .form(&[
+            ("grant_type", "client_credentials"),
+            (
+                "client_assertion_type",
+                "urn:ietf:params:oauth:client-assertion-type:jwt-bearer",
+            ),
+            ("client_assertion", &client_assertion),
+            ("scope", SCORE_SCOPE),
+        ])
+        .send()
+        .await
+        .context("Failed to request an access token from the platform")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Invalid access token response")?;
+    Ok(response.access_token)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Score<'a> {
+    user_id: &'a str,
+    score_given: f32,
+    score_maximum: i32,
+    activity_progress: &'static str,
+    grading_progress: &'static str,
+    timestamp: DateTime<Utc>,
+}
+
+/// Sends the score of the user to the line item of the link in the platform's gradebook.
+pub async fn send_score(
+    http_client: &Client,
+    access_token: &str,
+    pending: &PendingLtiScore,
+    score: &CurrentLtiScore,
+    timestamp: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let activity_progress = if score.graded_exercises == 0 {
+        "Initialized"
+    } else if score.graded_exercises < score.total_exercises {
+        "InProgress"
+    } else {
+        "Completed"
+    };
+    let grading_progress = if score.graded_exercises == 0 {
+        "NotReady"
+    } else {
+        "FullyGraded"
+    };
+    http_client
+        .post(scores_url(&pending.line_item_url)?)
+        .bearer_auth(access_token)
+        .header(reqwest::header::CONTENT_TYPE, SCORE_CONTENT_TYPE)
+        .body(serde_json::to_vec(&Score {
+            user_id: &pending.subject,
+            score_given: score.score_given,
+            score_maximum: score.score_maximum,
+            activity_progress,
+            grading_progress,
+            timestamp,
+        })?)
+        .send()
+        .await
+        .context("Failed to send the score to the platform")?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// The scores service is at the path of the line item followed by `/scores`, keeping the query of the line item URL.
+fn scores_url(line_item_url: &str) -> anyhow::Result<Url> {
+    let mut url = Url::parse(line_item_url)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Invalid line item URL {}", line_item_url))?
+        .pop_if_empty()
+        .push("scores");
+    Ok(url)
+}
+
+#[cfg(test)]
+mod test {
+    use headless_lms_models::{
+        lti_registrations::{self, NewLtiRegistration},
+        roles::{self, RoleDomain, UserRole},
+        PKeyPolicy,
+    };
+    use jsonwebtoken::DecodingKey;
+
+    use super::*;
+    use crate::test_helper::*;
+
+    // A key pair used only in tests, the private key is intentionally public.
+    const TEST_PRIVATE_KEY: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/oidc-test-private-key.pem"
+    ));
+    const TEST_KEY_MODULUS: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/oidc-test-public-key-modulus.txt"
+    ));
+
+    fn registration() -> LtiRegistration {
+        LtiRegistration {
+            id: Uuid::parse_str("0d0b6a5c-3f3e-4f5a-9c43-6f7a1b9f6c1e").unwrap(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            organization_id: Uuid::parse_str("8bb12295-53ac-4099-9644-ac0ff5e34d92").unwrap(),
+            name: "Partner Moodle".to_string(),
+            issuer: "https://moodle.example.com".to_string(),
+            client_id: "project-331".to_string(),
+            auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+            auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+            jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+            link_existing_users_by_email: false,
+        }
+    }
+
+    fn key() -> LtiToolKey {
+        LtiToolKey::new(TEST_PRIVATE_KEY).unwrap()
+    }
+
+    fn claims() -> Value {
+        serde_json::json!({
+            "iss": "https://moodle.example.com",
+            "aud": "project-331",
+            "sub": "moodle-user-1",
+            "exp": (Utc::now() + Duration::minutes(5)).timestamp(),
+            "nonce": "nonce",
+            "email": "student@moodle.example.com",
+            "given_name": "Example",
+            "family_name": "Student",
+            "https://purl.imsglobal.org/spec/lti/claim/message_type": "LtiResourceLinkRequest",
+            "https://purl.imsglobal.org/spec/lti/claim/version": "1.3.0",
+            "https://purl.imsglobal.org/spec/lti/claim/deployment_id": "1",
+            "https://purl.imsglobal.org/spec/lti/claim/resource_link": {
+                "id": "link-1",
+                "title": "Chapter 1",
+            },
+            "https://purl.imsglobal.org/spec/lti/claim/context": {
+                "id": "course-1",
+                "title": "Introduction to programming",
+            },
+            "https://purl.imsglobal.org/spec/lti/claim/custom": {
+                "page_id": "307fa56f-9853-4f5c-afb9-a6736c232f32",
+            },
+            "https://purl.imsglobal.org/spec/lti-ags/claim/endpoint": {
+                "scope": [SCORE_SCOPE],
+                "lineitem": "https://moodle.example.com/mod/lti/services.php/2/lineitems/1/lineitem?type_id=1",
+            },
+        })
+    }
+
+    fn sign(claims: Value) -> String {
+        key().sign(&claims).unwrap()
+    }
+
+    #[test]
+    fn publishes_the_public_key() {
+        let jwks = key().jwks();
+        assert_eq!(jwks.keys.len(), 1);
+        assert_eq!(jwks.keys[0].n.as_deref(), Some(TEST_KEY_MODULUS.trim()));
+        assert_eq!(jwks.keys[0].e.as_deref(), Some("AQAB"));
+        assert!(jwks.keys[0].kid.is_some());
+    }
+
+    #[test]
+    fn validates_resource_link_launch() {
+        let launch =
+            validate_launch(&sign(claims()), &registration(), &key().jwks(), "nonce").unwrap();
+        assert_eq!(
+            launch,
+            LtiLaunch {
+                subject: "moodle-user-1".to_string(),
+                email: Some("student@moodle.example.com".to_string()),
+                first_name: Some("Example".to_string()),
+                last_name: Some("Student".to_string()),
+                deployment_id: "1".to_string(),
+                message: LtiMessage::ResourceLink(ResourceLinkLaunch {
+                    resource_link_id: "link-1".to_string(),
+                    resource_link_title: Some("Chapter 1".to_string()),
+                    context_id: "course-1".to_string(),
+                    context_title: Some("Introduction to programming".to_string()),
+                    target: LtiLinkTarget::Page(
+                        Uuid::parse_str("307fa56f-9853-4f5c-afb9-a6736c232f32").unwrap()
+                    ),
+                    line_item_url: Some("https://moodle.example.com/mod/lti/services.php/2/lineitems/1/lineitem?type_id=1".to_string()),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn validates_deep_linking_launch() {
+        let mut claims = claims();
+        claims["https://purl.imsglobal.org/spec/lti/claim/message_type"] =
+            Value::from("LtiDeepLinkingRequest");
+        claims["https://purl.imsglobal.org/spec/lti-dl/claim/deep_linking_settings"] = serde_json::json!({
+            "deep_link_return_url": "https://moodle.example.com/mod/lti/contentitem_return.php",
+            "accept_types": ["ltiResourceLink"],
+            "data": "opaque",
+        });
+        let launch =
+            validate_launch(&sign(claims), &registration(), &key().jwks(), "nonce").unwrap();
+        assert_eq!(
+            launch.message,
+            LtiMessage::DeepLinking {
+                deep_link_return_url: "https://moodle.example.com/mod/lti/contentitem_return.php"
+                    .to_string(),
+                data: Some("opaque".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_launches() {
+        let validate = |claims: Value, nonce: &str| {
+            validate_launch(&sign(claims), &registration(), &key().jwks(), nonce)
+        };
+        assert!(validate(claims(), "other nonce").is_err());
+
+        let mut wrong_audience = claims();
+        wrong_audience["aud"] = Value::from("other-client");
+        assert!(validate(wrong_audience, "nonce").is_err());
+
+        let mut wrong_issuer = claims();
+        wrong_issuer["iss"] = Value::from("https://evil.example.com");
+        assert!(validate(wrong_issuer, "nonce").is_err());
+
+        let mut wrong_version = claims();
+        wrong_version["https://purl.imsglobal.org/spec/lti/claim/version"] = Value::from("1.1");
+        assert!(validate(wrong_version, "nonce").is_err());
+
+        let mut no_target = claims();
+        no_target["https://purl.imsglobal.org/spec/lti/claim/custom"] = serde_json::json!({});
+        assert!(validate(no_target, "nonce").is_err());
+    }
+
+    #[test]
+    fn creates_deep_linking_response() {
+        let jwt = deep_linking_response(
+            &key(),
+            &registration(),
+            &LtiDeepLinkingRequest {
+                id: Uuid::parse_str("5f9d2f3c-7a51-4d1b-8f0e-2c6a4b1d9e7a").unwrap(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+                lti_registration_id: registration().id,
+                deployment_id: "1".to_string(),
+                deep_link_return_url: "https://moodle.example.com/mod/lti/contentitem_return.php"
+                    .to_string(),
+                data: Some("opaque".to_string()),
+                responded_at: None,
+            },
+            "https://courses.mooc.fi/api/v0/lti/launch",
+            "Chapter 1",
+            LtiLinkTarget::Chapter(
+                Uuid::parse_str("307fa56f-9853-4f5c-afb9-a6736c232f32").unwrap(),
+            ),
+            10,
+        )
+        .unwrap();
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&["https://moodle.example.com"]);
+        let claims = jsonwebtoken::decode::<Value>(
+            &jwt,
+            &DecodingKey::from_rsa_components(TEST_KEY_MODULUS.trim(), "AQAB"),
+            &validation,
+        )
+        .unwrap()
+        .claims;
+        assert_eq!(claims["iss"], "project-331");
+        assert_eq!(
+            claims["https://purl.imsglobal.org/spec/lti-dl/claim/data"],
+            "opaque"
+        );
+        let content_item = &claims["https://purl.imsglobal.org/spec/lti-dl/claim/content_items"][0];
+        assert_eq!(content_item["type"], "ltiResourceLink");
+        assert_eq!(
+            content_item["custom"]["chapter_id"],
+            "307fa56f-9853-4f5c-afb9-a6736c232f32"
+        );
+        assert_eq!(content_item["lineItem"]["scoreMaximum"], 10);
+    }
+
+    #[actix_web::test]
+    async fn links_existing_users_only_within_the_organization() {
+        insert_data!(:tx, :user, :org, :course, :instance);
+        insert_data!(tx: tx, user: user; org: other_org);
+        let registration = lti_registrations::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &NewLtiRegistration {
+                name: "Partner Moodle".to_string(),
+                issuer: "https://moodle.example.com".to_string(),
+                client_id: "project-331".to_string(),
+                auth_login_url: "https://moodle.example.com/mod/lti/auth.php".to_string(),
+                auth_token_url: "https://moodle.example.com/mod/lti/token.php".to_string(),
+                jwks_url: "https://moodle.example.com/mod/lti/certs.php".to_string(),
+                link_existing_users_by_email: true,
+            },
+        )
+        .await
+        .unwrap();
+        let launch_as = |subject: &str, email: String| LtiLaunch {
+            subject: subject.to_string(),
+            email: Some(email),
+            first_name: None,
+            last_name: None,
+            deployment_id: "1".to_string(),
+            message: LtiMessage::DeepLinking {
+                deep_link_return_url: "https://moodle.example.com/mod/lti/contentitem_return.php"
+                    .to_string(),
+                data: None,
+            },
+        };
+
+        // a user who has not used any course is not linked
+        let email = users::get_by_id(tx.as_mut(), user).await.unwrap().email;
+        let err = get_or_create_user(
+            tx.as_mut(),
+            &registration,
+            &launch_as("user-1", email.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err.error_type(), ControllerErrorType::Forbidden));
+
+        // a user enrolled in the organization's course is linked
+        course_instance_enrollments::insert(tx.as_mut(), user, course, instance.id)
+            .await
+            .unwrap();
+        let linked = get_or_create_user(tx.as_mut(), &registration, &launch_as("user-1", email))
+            .await
+            .unwrap();
+        assert_eq!(linked.id, user);
+
+        // a user who also has a role in another organization is not linked
+        insert_data!(tx: tx; user: other_user);
+        course_instance_enrollments::insert(tx.as_mut(), other_user, course, instance.id)
+            .await
+            .unwrap();
+        roles::insert(
+            tx.as_mut(),
+            other_user,
+            UserRole::Teacher,
+            RoleDomain::Organization(other_org),
+        )
+        .await
+        .unwrap();
+        let other_email = users::get_by_id(tx.as_mut(), other_user)
+            .await
+            .unwrap()
+            .email;
+        let err = get_or_create_user(
+            tx.as_mut(),
+            &registration,
+            &launch_as("user-2", other_email),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err.error_type(), ControllerErrorType::Forbidden));
+        assert_eq!(
+            lti_users::try_get_user_id_by_subject(tx.as_mut(), registration.id, "user-2")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn scores_url_keeps_query() {
+        assert_eq!(
+            scores_url("https://moodle.example.com/mod/lti/services.php/2/lineitems/1/lineitem?type_id=1")
+                .unwrap()
+                .as_str(),
+            "https://moodle.example.com/mod/lti/services.php/2/lineitems/1/lineitem/scores?type_id=1"
+        );
+    }
+}
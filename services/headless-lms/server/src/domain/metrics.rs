@@ -0,0 +1,277 @@
+/*!
+Prometheus metrics for the server and the background programs.
+
+All the metrics are registered to the default registry of the `prometheus` crate when they are used for the first time, and they are exposed in the Prometheus text format:
+- the server serves them at `/metrics`, which is not routed through the ingress,
+- long running programs, like the regrader, serve them with [serve] on the port in `METRICS_PORT`,
+- programs that run as cronjobs push them to the Pushgateway in `PROMETHEUS_PUSHGATEWAY_URL` with [push] before exiting.
+*/
+
+use std::{
+    env,
+    future::{ready, Ready},
+    rc::Rc,
+    time::Instant,
+};
+
+use actix_http::header::CONTENT_TYPE;
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    App, Error, HttpServer,
+};
+use futures_util::future::LocalBoxFuture;
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, Encoder, HistogramVec,
+    IntCounterVec, IntGauge, TextEncoder,
+};
+
+use crate::prelude::*;
+
+const DEFAULT_METRICS_PORT: &str = "3002";
+
+pub static HTTP_REQUEST_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "http_request_duration_seconds",
+        "How long it took to handle HTTP requests, labelled by the route pattern instead of the path so that ids don't create new series.",
+        &["method", "route", "status"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static GRADING_REQUEST_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "grading_request_duration_seconds",
+        "How long the exercise services took to grade submissions.",
+        &["exercise_service"],
+        vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static GRADING_REQUEST_FAILURES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "grading_request_failures_total",
+        "Grading requests to the exercise services that failed.",
+        &["exercise_service"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static REGRADER_UNCOMPLETED_REGRADINGS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "regrader_uncompleted_regradings",
+        "Regradings that the regrader has not completed yet."
+    )
+    .expect("Failed to register metric")
+});
+
+pub static EMAIL_QUEUE_LENGTH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!("email_queue_length", "Emails that are waiting to be sent.")
+        .expect("Failed to register metric")
+});
+
+pub static EMAILS_SENT_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "emails_sent_total",
+        "Emails the email deliverer has tried to send.",
+        &["result"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static PEER_REVIEW_QUEUE_LENGTH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "peer_review_queue_length",
+        "Answers in the peer review queues that are still waiting for peer reviews."
+    )
+    .expect("Failed to register metric")
+});
+
+pub static PEER_REVIEW_TIMEOUTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "peer_review_timeouts_total",
+        "Answers the peer review updater has processed because they waited too long, labelled by what was done to them.",
+        &["action"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static ENDED_EXAMS_PROCESSED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "ended_exams_processed_total",
+        "Ended exams whose completions the ended exams processor has processed.",
+        &["result"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static PAGE_DRAFTS_PUBLISHED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "page_drafts_published_total",
+        "Scheduled page drafts the page draft publisher has tried to publish.",
+        &["result"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static CONTENT_LINT_RUNS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "content_lint_runs_total",
+        "Courses the content linter has tried to check.",
+        &["result"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static WEBHOOK_DELIVERIES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "webhook_deliveries_total",
+        "Attempts the webhook deliverer has made to deliver events to webhook subscribers.",
+        &["result"]
+    )
+    .expect("Failed to register metric")
+});
+
+pub static LTI_SCORE_PASSBACKS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "lti_score_passbacks_total",
+        "Scores the LTI score passback has sent to LTI platforms.",
+        &["result"]
+    )
+    .expect("Failed to register metric")
+});
+
+/// Encodes all the metrics in the Prometheus text format.
+pub fn encode() -> anyhow::Result<String> {
+    let mut buffer = vec![];
+    TextEncoder::new().encode(&prometheus::gather(), &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/**
+GET `/metrics` - Returns the metrics in the Prometheus text format.
+*/
+pub async fn metrics_endpoint() -> ControllerResult<HttpResponse> {
+    let metrics = encode()?;
+    let token = skip_authorize()?;
+    token.authorized_ok(
+        HttpResponse::Ok()
+            .insert_header((CONTENT_TYPE, TextEncoder::new().format_type()))
+            .body(metrics),
+    )
+}
+
+/// Serves the metrics of a long running program in the background.
+pub fn serve() -> anyhow::Result<()> {
+    let port = env::var("METRICS_PORT").unwrap_or_else(|_| DEFAULT_METRICS_PORT.to_string());
+    let server = HttpServer::new(|| App::new().route("/metrics", web::get().to(metrics_endpoint)))
+        .workers(1)
+        .bind(format!("0.0.0.0:{}", port))?
+        .run();
+    info!("Serving metrics on port {}", port);
+    actix_web::rt::spawn(server);
+    Ok(())
+}
+
+/// Pushes the metrics of a program that is about to exit to the Pushgateway. Does nothing if `PROMETHEUS_PUSHGATEWAY_URL` is not set.
+pub async fn push(job: &str) -> anyhow::Result<()> {
+    let pushgateway_url = match env::var("PROMETHEUS_PUSHGATEWAY_URL") {
+        Ok(url) => url,
+        Err(_) => {
+            debug!("PROMETHEUS_PUSHGATEWAY_URL not set, not pushing metrics");
+            return Ok(());
+        }
+    };
+    reqwest::Client::new()
+        .put(format!(
+            "{}/metrics/job/{}",
+            pushgateway_url.trim_end_matches('/'),
+            job
+        ))
+        .header(CONTENT_TYPE, TextEncoder::new().format_type())
+        .body(encode()?)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+pub struct HttpMetrics;
+
+impl<S, B> Transform<S, ServiceRequest> for HttpMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = HttpMetricsMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(HttpMetricsMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Records how long it took to handle each request.
+pub struct HttpMetricsMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for HttpMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let method = req.method().to_string();
+        // the pattern of the resource that handles the request, e.g. /api/v0/main-frontend/courses/{course_id}
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+        Box::pin(async move {
+            let timer = Instant::now();
+            let res = service.call(req).await;
+            // errors from other middlewares are turned into responses only after this middleware
+            let status = match &res {
+                Ok(res) => res.status(),
+                Err(err) => err.as_response_error().status_code(),
+            };
+            HTTP_REQUEST_DURATION_SECONDS
+                .with_label_values(&[&method, &route, status.as_str()])
+                .observe(timer.elapsed().as_secs_f64());
+            res
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_metrics_in_text_format() {
+        EMAIL_QUEUE_LENGTH.set(3);
+        GRADING_REQUEST_FAILURES_TOTAL
+            .with_label_values(&["example-exercise"])
+            .inc();
+        let metrics = encode().unwrap();
+        assert!(metrics.contains("# TYPE email_queue_length gauge"));
+        assert!(metrics.contains("email_queue_length 3"));
+        assert!(metrics
+            .contains(r#"grading_request_failures_total{exercise_service="example-exercise"} 1"#));
+    }
+}
@@ -2,11 +2,21 @@
 Helpful structs and functions that are not related to database tables.
 */
 
+pub mod api_tokens;
+pub mod audit_log;
 pub mod authorization;
 pub mod csv_export;
+pub mod deleted_user_guard;
 pub mod error;
 pub mod exercise_repositories;
 pub mod file_uploading;
+pub mod grade_export;
+pub mod impersonation;
+pub mod lti;
+pub mod metrics;
 pub mod models_requests;
-pub mod rate_limit_middleware_builder;
+pub mod oidc;
+pub mod rate_limits;
 pub mod request_span_middleware;
+pub mod user_data_export;
+pub mod webhooks;
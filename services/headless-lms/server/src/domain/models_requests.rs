@@ -22,7 +22,10 @@ use sha2::Sha256;
 use std::{borrow::Cow, fmt::Debug, sync::Arc};
 use url::Url;
 
-use super::error::{ControllerError, ControllerErrorType};
+use super::{
+    error::{ControllerError, ControllerErrorType},
+    metrics,
+};
 
 const EXERCISE_SERVICE_UPLOAD_CLAIM_HEADER: &str = "exercise-service-upload-claim";
 
@@ -145,12 +148,21 @@ pub struct SpecRequest<'a> {
 pub fn make_spec_fetcher(
     jwt_key: Arc<JwtKey>,
 ) -> impl Fn(Url, &str, Option<&serde_json::Value>) -> BoxFuture<'static, ModelResult<serde_json::Value>>
+{
+    make_spec_fetcher_with_upload_base_url(jwt_key, "http://project-331.local".to_string())
+}
+
+/// Like `make_spec_fetcher`, but the exercise service is told to upload the files to the given server instead.
+pub fn make_spec_fetcher_with_upload_base_url(
+    jwt_key: Arc<JwtKey>,
+    upload_base_url: String,
+) -> impl Fn(Url, &str, Option<&serde_json::Value>) -> BoxFuture<'static, ModelResult<serde_json::Value>>
 {
     move |url, exercise_service_slug, private_spec| {
         let client = reqwest::Client::new();
         let upload_claim = UploadClaim::expiring_in_1_day(exercise_service_slug.into());
         let upload_url = Some(format!(
-            "http://project-331.local/api/v0/files/{exercise_service_slug}"
+            "{upload_base_url}/api/v0/files/{exercise_service_slug}"
         ));
         let req = client
             .post(url)
@@ -225,10 +237,22 @@ pub fn send_grading_request(
             exercise_spec: &exercise_task.private_spec,
             submission_data: &submission.data_json,
         });
-    async {
-        let res = req.send().await.map_err(reqwest_err)?;
+    let exercise_service = exercise_task.exercise_type.clone();
+    async move {
+        let _timer = metrics::GRADING_REQUEST_DURATION_SECONDS
+            .with_label_values(&[&exercise_service])
+            .start_timer();
+        let res = req.send().await.map_err(|err| {
+            metrics::GRADING_REQUEST_FAILURES_TOTAL
+                .with_label_values(&[&exercise_service])
+                .inc();
+            reqwest_err(err)
+        })?;
         let status = res.status();
         if !status.is_success() {
+            metrics::GRADING_REQUEST_FAILURES_TOTAL
+                .with_label_values(&[&exercise_service])
+                .inc();
             let response_body = res.text().await;
             error!(
                 ?response_body,
@@ -0,0 +1,634 @@
+/*!
+Login with OpenID Connect identity providers, in addition to the mooc.fi password login.
+
+The identity providers are configured per organization in the `identity_providers` table. The login uses the authorization code flow with PKCE:
+
+1. [begin_login] discovers the provider configuration, stores the CSRF token, nonce and PKCE verifier to the session and returns the URL the user should be redirected to.
+2. The provider redirects the user back to the callback endpoint, where [finish_login] exchanges the authorization code for an ID token, validates the ID token and maps its claims to [OidcUserInfo].
+3. [get_or_create_user] finds the user linked to the identity, links the identity to the logged in user who started the login for linking their account, or creates a new user.
+
+The identity providers are created by global admins only. An identity is only linked to an existing user with the same email if the identity provider has been configured to link users by email and it says that it has verified the email.
+*/
+
+use actix_session::Session;
+use anyhow::{anyhow, bail};
+use headless_lms_models::{
+    identity_providers::IdentityProvider,
+    user_identities,
+    users::{self, User},
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Header, Validation};
+use oauth2::{
+    basic::{
+        BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+        BasicTokenType,
+    },
+    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, ExtraTokenFields,
+    PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, Scope, StandardRevocableToken,
+    StandardTokenResponse, TokenUrl,
+};
+use reqwest::Client;
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::prelude::*;
+
+const SESSION_KEY: &str = "oidc_login";
+/// Allowed clock skew between us and the identity provider when validating the ID token.
+const ID_TOKEN_LEEWAY_SECONDS: u64 = 60;
+
+/// The parts of the identity provider's configuration that are needed for logging in.
+///
+/// See <https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata>.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonWebKeySet {
+    pub keys: Vec<JsonWebKey>,
+}
+
+/// A public key of the identity provider. Only RSA keys are supported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonWebKey {
+    pub kty: String,
+    pub kid: Option<String>,
+    #[serde(rename = "use")]
+    pub key_use: Option<String>,
+    pub alg: Option<String>,
+    pub n: Option<String>,
+    pub e: Option<String>,
+}
+
+/// The information about the user that was received from the identity provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcUserInfo {
+    pub subject: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+/// Stored in the session between redirecting the user to the identity provider and the callback.
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingLogin {
+    identity_provider_id: Uuid,
+    csrf_token: String,
+    nonce: String,
+    pkce_verifier: String,
+    return_to: String,
+    /// Set if a logged in user started the login for linking the identity to their account.
+    link_to_user_id: Option<Uuid>,
+}
+
+/// The result of a successful login with the identity provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedLogin {
+    pub user_info: OidcUserInfo,
+    /// The path the user should be redirected to.
+    pub return_to: String,
+    /// Set if the login was started for linking the identity to the account of this user.
+    pub link_to_user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdTokenFields {
+    pub id_token: String,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+type OidcClient = oauth2::Client<
+    BasicErrorResponse,
+    StandardTokenResponse<IdTokenFields, BasicTokenType>,
+    BasicTokenType,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
+>;
+
+/// Fetches the configuration of the identity provider from `{issuer_url}/.well-known/openid-configuration`.
+pub async fn discover(http_client: &Client, issuer_url: &str) -> anyhow::Result<ProviderMetadata> {
+    let issuer_url = issuer_url.trim_end_matches('/');
+    let metadata: ProviderMetadata = http_client
+        .get(format!("{}/.well-known/openid-configuration", issuer_url))
+        .send()
+        .await
+        .context("Failed to fetch the identity provider configuration")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Invalid identity provider configuration")?;
+    if metadata.issuer.trim_end_matches('/') != issuer_url {
+        bail!(
+            "The issuer of the identity provider configuration ({}) does not match the configured issuer ({})",
+            metadata.issuer,
+            issuer_url
+        );
+    }
+    Ok(metadata)
+}
+
+/// The URL the identity provider redirects the user to after logging in. Must be registered at the identity provider.
+pub fn redirect_url(base_url: &str, identity_provider_id: Uuid) -> anyhow::Result<RedirectUrl> {
+    let redirect_url = RedirectUrl::new(format!(
+        "{}/api/v0/auth/oidc/{}/callback",
+        base_url.trim_end_matches('/'),
+        identity_provider_id
+    ))?;
+    Ok(redirect_url)
+}
+
+/// Starts logging in with the identity provider. Returns the URL of the identity provider that the user should be redirected to.
+///
+/// Only relative paths are accepted for `return_to` so that the login cannot be used for redirecting users to other sites.
+/// If `link_to_user_id` is given, the identity is linked to that user's account when the login finishes.
+pub async fn begin_login(
+    session: &Session,
+    http_client: &Client,
+    identity_provider: &IdentityProvider,
+    redirect_url: RedirectUrl,
+    return_to: Option<&str>,
+    link_to_user_id: Option<Uuid>,
+) -> anyhow::Result<Url> {
+    let metadata = discover(http_client, &identity_provider.issuer_url).await?;
+    let client = build_client(identity_provider, &metadata, redirect_url)?;
+    let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+    let nonce = CsrfToken::new_random();
+    let (authorization_url, csrf_token) = client
+        .authorize_url(CsrfToken::new_random)
+        .add_scopes(
+            identity_provider
+                .scopes
+                .split_whitespace()
+                .map(|scope| Scope::new(scope.to_string())),
+        )
+        .add_extra_param("nonce", nonce.secret())
+        .set_pkce_challenge(pkce_challenge)
+        .url();
+    let return_to = match return_to {
+        Some(path) if path.starts_with('/') && !path.starts_with("//") => path.to_string(),
+        _ => "/".to_string(),
+    };
+    session
+        .insert(
+            SESSION_KEY,
+            PendingLogin {
+                identity_provider_id: identity_provider.id,
+                csrf_token: csrf_token.secret().clone(),
+                nonce: nonce.secret().clone(),
+                pkce_verifier: pkce_verifier.secret().clone(),
+                return_to,
+                link_to_user_id,
+            },
+        )
+        .map_err(|_| anyhow!("Failed to insert to session"))?;
+    Ok(authorization_url)
+}
+
+/// Finishes logging in with the identity provider after it has redirected the user back to us.
+pub async fn finish_login(
+    session: &Session,
+    http_client: &Client,
+    identity_provider: &IdentityProvider,
+    redirect_url: RedirectUrl,
+    code: &str,
+    state: &str,
+) -> anyhow::Result<CompletedLogin> {
+    // the pending login can only be used once
+    let pending_login = session
+        .remove_as::<PendingLogin>(SESSION_KEY)
+        .and_then(Result::ok)
+        .ok_or_else(|| anyhow!("No login in progress"))?;
+    if pending_login.identity_provider_id != identity_provider.id {
+        bail!("The login was started with a different identity provider");
+    }
+    if pending_login.csrf_token != state {
+        bail!("Invalid state");
+    }
+
+    let metadata = discover(http_client, &identity_provider.issuer_url).await?;
+    let client = build_client(identity_provider, &metadata, redirect_url)?;
+    let token = client
+        .exchange_code(AuthorizationCode::new(code.to_string()))
+        .set_pkce_verifier(PkceCodeVerifier::new(pending_login.pkce_verifier))
+        .request_async(oauth2::reqwest::async_http_client)
+        .await
+        .map_err(|err| anyhow!("Failed to exchange the authorization code: {}", err))?;
+    let jwks: JsonWebKeySet = http_client
+        .get(&metadata.jwks_uri)
+        .send()
+        .await
+        .context("Failed to fetch the identity provider keys")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Invalid identity provider keys")?;
+    let user_info = validate_id_token(
+        &token.extra_fields().id_token,
+        identity_provider,
+        &metadata,
+        &jwks,
+        &pending_login.nonce,
+    )?;
+    Ok(CompletedLogin {
+        user_info,
+        return_to: pending_login.return_to,
+        link_to_user_id: pending_login.link_to_user_id,
+    })
+}
+
+fn build_client(
+    identity_provider: &IdentityProvider,
+    metadata: &ProviderMetadata,
+    redirect_url: RedirectUrl,
+) -> anyhow::Result<OidcClient> {
+    let client = OidcClient::new(
+        ClientId::new(identity_provider.client_id.clone()),
+        identity_provider
+            .client_secret
+            .clone()
+            .map(ClientSecret::new),
+        AuthUrl::new(metadata.authorization_endpoint.clone())?,
+        Some(TokenUrl::new(metadata.token_endpoint.clone())?),
+    )
+    .set_redirect_uri(redirect_url);
+    Ok(client)
+}
+
+/// Validates the signature, issuer, audience, expiration and nonce of the ID token and maps its claims to the user's information using the claim names configured for the identity provider.
+pub fn validate_id_token(
+    id_token: &str,
+    identity_provider: &IdentityProvider,
+    metadata: &ProviderMetadata,
+    jwks: &JsonWebKeySet,
+    nonce: &str,
+) -> anyhow::Result<OidcUserInfo> {
+    let header = jsonwebtoken::decode_header(id_token)?;
+    if !matches!(
+        header.alg,
+        Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512
+    ) {
+        bail!("Unsupported ID token algorithm {:?}", header.alg);
+    }
+    let key = find_decoding_key(jwks, &header)?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.leeway = ID_TOKEN_LEEWAY_SECONDS;
+    validation.iss = Some(metadata.issuer.clone());
+    validation.set_audience(&[&identity_provider.client_id]);
+    let claims = jsonwebtoken::decode::<Map<String, Value>>(id_token, &key, &validation)?.claims;
+
+    if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+        bail!("Invalid nonce");
+    }
+    let subject = string_claim(&claims, "sub").ok_or_else(|| anyhow!("Missing subject"))?;
+    let email = string_claim(&claims, &identity_provider.email_claim)
+        .ok_or_else(|| anyhow!("Missing email claim {}", identity_provider.email_claim))?;
+    // some identity providers send the boolean as a string
+    let email_verified = match claims.get("email_verified") {
+        Some(Value::Bool(verified)) => *verified,
+        Some(Value::String(verified)) => verified == "true",
+        _ => false,
+    };
+    Ok(OidcUserInfo {
+        subject,
+        email,
+        email_verified,
+        first_name: string_claim(&claims, &identity_provider.first_name_claim),
+        last_name: string_claim(&claims, &identity_provider.last_name_claim),
+    })
+}
+
+/// Chooses the RSA signing key the token header refers to from the key set.
+pub fn find_decoding_key<'a>(
+    jwks: &'a JsonWebKeySet,
+    header: &Header,
+) -> anyhow::Result<DecodingKey<'a>> {
+    let rsa_keys = jwks
+        .keys
+        .iter()
+        .filter(|key| key.kty == "RSA" && key.key_use.as_deref().unwrap_or("sig") == "sig")
+        .collect::<Vec<_>>();
+    let key = match &header.kid {
+        Some(kid) => rsa_keys
+            .into_iter()
+            .find(|key| key.kid.as_ref() == Some(kid)),
+        // without a key id, the key can only be chosen if there's just one
+        None if rsa_keys.len() == 1 => rsa_keys.into_iter().next(),
+        None => None,
+    }
+    .ok_or_else(|| anyhow!("No key found for validating the token"))?;
+    let (n, e) = key
+        .n
+        .as_deref()
+        .zip(key.e.as_deref())
+        .ok_or_else(|| anyhow!("Invalid RSA key"))?;
+    Ok(DecodingKey::from_rsa_components(n, e))
+}
+
+fn string_claim(claims: &Map<String, Value>, name: &str) -> Option<String> {
+    claims
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Returns the user linked to the identity. If there is no linked user yet, the identity is linked to the user given in `link_to_user_id`, to the existing user with the same email, or to a new user.
+///
+/// Linking by email is only allowed if the identity provider is trusted with it and has verified the email, because whoever controls the identity provider could otherwise log in as any user.
+pub async fn get_or_create_user(
+    conn: &mut PgConnection,
+    identity_provider: &IdentityProvider,
+    user_info: &OidcUserInfo,
+    link_to_user_id: Option<Uuid>,
+) -> ControllerResult<User> {
+    if let Some(user_id) =
+        user_identities::try_get_user_id_by_subject(conn, identity_provider.id, &user_info.subject)
+            .await?
+    {
+        if link_to_user_id.map_or(false, |link_to_user_id| link_to_user_id != user_id) {
+            return Err(ControllerError::new(
+                ControllerErrorType::BadRequest,
+                "The identity is already linked to another account.".to_string(),
+                None,
+            ));
+        }
+        let user = users::get_by_id(conn, user_id).await?;
+        return Ok(user);
+    }
+
+    let mut tx = conn.begin().await?;
+    let user = match link_to_user_id {
+        Some(link_to_user_id) => {
+            info!(user_id = ?link_to_user_id, identity_provider_id = ?identity_provider.id, "Linking identity to the logged in user");
+            users::get_by_id(&mut tx, link_to_user_id).await?
+        }
+        None => match users::try_get_by_email(&mut tx, &user_info.email).await? {
+            Some(existing_user) => {
+                if !identity_provider.link_existing_users_by_email || !user_info.email_verified {
+                    return Err(ControllerError::new(
+                        ControllerErrorType::Forbidden,
+                        "An account with the same email already exists. Log in to the account first and then link it to the identity provider.".to_string(),
+                        None,
+                    ));
+                }
+                info!(user_id = ?existing_user.id, identity_provider_id = ?identity_provider.id, "Linking identity to an existing user with the same verified email");
+                existing_user
+            }
+            None => {
+                let user_id = users::insert(
+                    &mut tx,
+                    PKeyPolicy::Generate,
+                    &user_info.email,
+                    user_info.first_name.as_deref(),
+                    user_info.last_name.as_deref(),
+                )
+                .await?;
+                users::get_by_id(&mut tx, user_id).await?
+            }
+        },
+    };
+    user_identities::insert(&mut tx, user.id, identity_provider.id, &user_info.subject).await?;
+    tx.commit().await?;
+    Ok(user)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+    use headless_lms_models::identity_providers::{self, NewIdentityProvider};
+    use jsonwebtoken::EncodingKey;
+
+    use super::*;
+    use crate::test_helper::*;
+
+    // A key pair used only in tests, the private key is intentionally public.
+    const TEST_PRIVATE_KEY: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/oidc-test-private-key.pem"
+    ));
+    const TEST_KEY_MODULUS: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/oidc-test-public-key-modulus.txt"
+    ));
+
+    fn identity_provider() -> IdentityProvider {
+        IdentityProvider {
+            id: Uuid::parse_str("4dbe6af0-8e55-4a3e-9d3b-7a2d0d3b1e5c").unwrap(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            organization_id: Uuid::parse_str("8bb12295-53ac-4099-9644-ac0ff5e34d92").unwrap(),
+            name: "Partner".to_string(),
+            issuer_url: "https://idp.example.com".to_string(),
+            client_id: "project-331".to_string(),
+            client_secret: None,
+            scopes: "openid email profile".to_string(),
+            email_claim: "email".to_string(),
+            first_name_claim: "given_name".to_string(),
+            last_name_claim: "family_name".to_string(),
+            link_existing_users_by_email: false,
+        }
+    }
+
+    fn metadata() -> ProviderMetadata {
+        ProviderMetadata {
+            issuer: "https://idp.example.com".to_string(),
+            authorization_endpoint: "https://idp.example.com/authorize".to_string(),
+            token_endpoint: "https://idp.example.com/token".to_string(),
+            jwks_uri: "https://idp.example.com/jwks".to_string(),
+        }
+    }
+
+    fn jwks() -> JsonWebKeySet {
+        JsonWebKeySet {
+            keys: vec![JsonWebKey {
+                kty: "RSA".to_string(),
+                kid: Some("test-key".to_string()),
+                key_use: Some("sig".to_string()),
+                alg: Some("RS256".to_string()),
+                n: Some(TEST_KEY_MODULUS.trim().to_string()),
+                e: Some("AQAB".to_string()),
+            }],
+        }
+    }
+
+    fn sign(claims: Value) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("test-key".to_string());
+        jsonwebtoken::encode(
+            &header,
+            &claims,
+            &EncodingKey::from_rsa_pem(TEST_PRIVATE_KEY).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn claims() -> Value {
+        serde_json::json!({
+            "iss": "https://idp.example.com",
+            "aud": "project-331",
+            "sub": "partner-user-1",
+            "exp": (Utc::now() + Duration::minutes(5)).timestamp(),
+            "nonce": "nonce",
+            "email": "student@partner.example.com",
+            "email_verified": true,
+            "given_name": "Example",
+            "family_name": "Student",
+        })
+    }
+
+    #[test]
+    fn validates_id_token_and_maps_claims() {
+        let id_token = sign(claims());
+        let user_info = validate_id_token(
+            &id_token,
+            &identity_provider(),
+            &metadata(),
+            &jwks(),
+            "nonce",
+        )
+        .unwrap();
+        assert_eq!(
+            user_info,
+            OidcUserInfo {
+                subject: "partner-user-1".to_string(),
+                email: "student@partner.example.com".to_string(),
+                email_verified: true,
+                first_name: Some("Example".to_string()),
+                last_name: Some("Student".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn uses_configured_claim_names() {
+        let mut claims = claims();
+        claims["mail"] = Value::String("other@partner.example.com".to_string());
+        let id_token = sign(claims);
+        let identity_provider = IdentityProvider {
+            email_claim: "mail".to_string(),
+            ..identity_provider()
+        };
+        let user_info =
+            validate_id_token(&id_token, &identity_provider, &metadata(), &jwks(), "nonce")
+                .unwrap();
+        assert_eq!(user_info.email, "other@partner.example.com");
+    }
+
+    #[test]
+    fn rejects_invalid_id_tokens() {
+        let validate = |claims: Value, nonce: &str| {
+            validate_id_token(
+                &sign(claims),
+                &identity_provider(),
+                &metadata(),
+                &jwks(),
+                nonce,
+            )
+        };
+        assert!(validate(claims(), "other nonce").is_err());
+
+        let mut wrong_audience = claims();
+        wrong_audience["aud"] = Value::String("other-client".to_string());
+        assert!(validate(wrong_audience, "nonce").is_err());
+
+        let mut wrong_issuer = claims();
+        wrong_issuer["iss"] = Value::String("https://evil.example.com".to_string());
+        assert!(validate(wrong_issuer, "nonce").is_err());
+
+        let mut expired = claims();
+        expired["exp"] = Value::from((Utc::now() - Duration::hours(1)).timestamp());
+        assert!(validate(expired, "nonce").is_err());
+
+        let hmac_signed = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims(),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        assert!(validate_id_token(
+            &hmac_signed,
+            &identity_provider(),
+            &metadata(),
+            &jwks(),
+            "nonce"
+        )
+        .is_err());
+    }
+
+    #[actix_web::test]
+    async fn links_existing_users_by_verified_email_only_if_allowed() {
+        insert_data!(:tx, :user, :org);
+        let email = users::get_by_id(tx.as_mut(), user).await.unwrap().email;
+        let new_identity_provider = |link_existing_users_by_email| NewIdentityProvider {
+            name: "Partner".to_string(),
+            issuer_url: "https://idp.example.com".to_string(),
+            client_id: "project-331".to_string(),
+            client_secret: None,
+            scopes: "openid email profile".to_string(),
+            email_claim: "email".to_string(),
+            first_name_claim: "given_name".to_string(),
+            last_name_claim: "family_name".to_string(),
+            link_existing_users_by_email,
+        };
+        let user_info = |subject: &str, email_verified| OidcUserInfo {
+            subject: subject.to_string(),
+            email: email.clone(),
+            email_verified,
+            first_name: None,
+            last_name: None,
+        };
+
+        let untrusted = identity_providers::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &new_identity_provider(false),
+        )
+        .await
+        .unwrap();
+        let untrusted = identity_providers::get_by_id(tx.as_mut(), untrusted.id)
+            .await
+            .unwrap();
+        let err = get_or_create_user(tx.as_mut(), &untrusted, &user_info("user-1", true), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.error_type(), ControllerErrorType::Forbidden));
+
+        let trusted = identity_providers::insert(
+            tx.as_mut(),
+            PKeyPolicy::Generate,
+            org,
+            &new_identity_provider(true),
+        )
+        .await
+        .unwrap();
+        let trusted = identity_providers::get_by_id(tx.as_mut(), trusted.id)
+            .await
+            .unwrap();
+        let err = get_or_create_user(tx.as_mut(), &trusted, &user_info("user-2", false), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.error_type(), ControllerErrorType::Forbidden));
+        let linked = get_or_create_user(tx.as_mut(), &trusted, &user_info("user-3", true), None)
+            .await
+            .unwrap();
+        assert_eq!(linked.id, user);
+        assert_eq!(
+            user_identities::try_get_user_id_by_subject(tx.as_mut(), trusted.id, "user-3")
+                .await
+                .unwrap(),
+            Some(user)
+        );
+    }
+}
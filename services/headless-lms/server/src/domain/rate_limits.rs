@@ -0,0 +1,160 @@
+/*!
+Rate limiting that holds across all the server instances.
+
+The policies are stored in the database per route group, and a route is limited by wrapping it with [RateLimit], e.g. `web::resource("/login").wrap(RateLimit::new("login"))`. The requests are counted per user if the request is authenticated and per IP address otherwise. The counters are in the database too, so the limits hold no matter how many server instances there are.
+
+Every response to a rate limited route tells how much of the most restrictive policy is left with the `X-RateLimit-Limit`, `X-RateLimit-Remaining` and `X-RateLimit-Reset` headers. When a limit has been exceeded, the request is rejected with status 429 and a `Retry-After` header.
+*/
+
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_http::{
+    body::{BoxBody, EitherBody},
+    header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
+    HttpMessage,
+};
+use actix_session::SessionExt;
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, ResponseError,
+};
+use chrono::Utc;
+use futures_util::future::LocalBoxFuture;
+use headless_lms_models::rate_limits::{self, RateLimitCount};
+
+use crate::{domain::authorization, prelude::*};
+
+pub const X_RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+pub const X_RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+/// The number of seconds until the limit is reset.
+pub const X_RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// Limits the requests to the wrapped routes with the policies of the route group.
+pub struct RateLimit {
+    route_group: &'static str,
+}
+
+impl RateLimit {
+    pub fn new(route_group: &'static str) -> Self {
+        Self { route_group }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            route_group: self.route_group,
+        }))
+    }
+}
+
+/// Counts the request and rejects it if it exceeds any of the policies of the route group.
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    route_group: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let route_group = self.route_group;
+        Box::pin(async move {
+            let key = rate_limit_key(&req);
+            let pool = req
+                .app_data::<web::Data<PgPool>>()
+                .expect("Missing database pool in app data")
+                .clone();
+            let mut conn = pool.acquire().await.map_err(ControllerError::from)?;
+            let counts = rate_limits::count_request(&mut conn, route_group, &key)
+                .await
+                .map_err(ControllerError::from)?;
+            drop(conn);
+
+            // when several limits have been exceeded, the client has to wait until all of them have been reset
+            let exceeded = counts
+                .iter()
+                .filter(|count| count.is_exceeded())
+                .max_by_key(|count| count.window_end);
+            if let Some(exceeded) = exceeded {
+                info!(route_group, key = %key, "Rate limit exceeded");
+                let error = ControllerError::new(
+                    ControllerErrorType::TooManyRequests,
+                    "Too many requests. Please try again later.".to_string(),
+                    None,
+                );
+                let mut res = req
+                    .into_response(error.error_response())
+                    .map_into_right_body();
+                insert_headers(res.headers_mut(), exceeded);
+                res.headers_mut().insert(
+                    RETRY_AFTER,
+                    HeaderValue::from(seconds_until_reset(exceeded)),
+                );
+                return Ok(res);
+            }
+
+            let mut res = service.call(req).await?.map_into_left_body();
+            if let Some(most_restrictive) = counts.iter().min_by_key(|count| count.remaining()) {
+                insert_headers(res.headers_mut(), most_restrictive);
+            }
+            Ok(res)
+        })
+    }
+}
+
+/// The requests of authenticated users are counted per user so that users behind the same IP address, e.g. on a university network, don't limit each other.
+fn rate_limit_key(req: &ServiceRequest) -> String {
+    // authenticated with an API token
+    let api_token_user_id = req.extensions().get::<AuthUser>().map(|user| user.id);
+    let user_id = api_token_user_id
+        .or_else(|| authorization::get_session_user(&req.get_session()).map(|user| user.id));
+    match user_id {
+        Some(user_id) => format!("user:{}", user_id),
+        // client ip address, not the reverse proxy ip address
+        None => format!(
+            "ip:{}",
+            req.connection_info()
+                .realip_remote_addr()
+                .unwrap_or("unknown")
+        ),
+    }
+}
+
+fn seconds_until_reset(count: &RateLimitCount) -> i64 {
+    (count.window_end - Utc::now()).num_seconds().max(0)
+}
+
+fn insert_headers(headers: &mut HeaderMap, count: &RateLimitCount) {
+    headers.insert(X_RATE_LIMIT_LIMIT, HeaderValue::from(count.max_requests));
+    headers.insert(X_RATE_LIMIT_REMAINING, HeaderValue::from(count.remaining()));
+    headers.insert(
+        X_RATE_LIMIT_RESET,
+        HeaderValue::from(seconds_until_reset(count)),
+    );
+}
@@ -1,5 +1,7 @@
 /*!
 Middleware that wraps HTTP requests to tokio tracing spans for debugging and attaches a request id to all log messages.
+
+The request id is also available through [current_request_id] while the request is handled, for example for recording it in the audit log.
 */
 
 use actix_http::header::{HeaderName, HeaderValue};
@@ -12,6 +14,15 @@ use std::future::{ready, Ready};
 use tracing::Instrument;
 use uuid::Uuid;
 
+tokio::task_local! {
+    static REQUEST_ID: Uuid;
+}
+
+/// The id of the HTTP request that is currently being handled. None if called outside of a request.
+pub fn current_request_id() -> Option<Uuid> {
+    REQUEST_ID.try_with(|request_id| *request_id).ok()
+}
+
 pub struct RequestSpan;
 
 impl<S, B> Transform<S, ServiceRequest> for RequestSpan
@@ -62,7 +73,9 @@ where
             );
         });
 
-        let fut = self.service.call(req).instrument(request_span);
+        let fut = REQUEST_ID
+            .scope(request_id, self.service.call(req))
+            .instrument(request_span);
 
         Box::pin(async move {
             let mut res = fut.await?;
@@ -0,0 +1,26 @@
+/*!
+Packaging the data the system holds about a user into a ZIP file.
+*/
+
+use std::io::{Cursor, Write};
+
+use headless_lms_models::library::user_data_export;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::prelude::*;
+
+/// How long a generated export can be downloaded.
+pub const EXPORT_VALID_DAYS: i64 = 7;
+
+/// Creates a ZIP file that contains a JSON file for each kind of data related to the user.
+pub async fn create_zip(conn: &mut PgConnection, user_id: Uuid) -> anyhow::Result<Vec<u8>> {
+    let files = user_data_export::get_user_data(conn, user_id).await?;
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for file in files {
+        zip.start_file(file.file_name, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&file.data)?)?;
+    }
+    let res = zip.finish()?.into_inner();
+    Ok(res)
+}
@@ -0,0 +1,314 @@
+/*!
+Delivering course events to the webhooks organizations have subscribed to.
+
+Each event is sent as a JSON POST request. The request is signed with the secret of the subscription: the `X-Webhook-Signature` header is `sha256=<hex encoded HMAC-SHA256 of "<timestamp>.<body>">`, where the timestamp is the value of the `X-Webhook-Timestamp` header. Receivers should verify the signature and reject requests with old timestamps.
+
+Any 2xx response counts as a successful delivery. Other responses and connection errors are retried with an exponential backoff, see [models::webhook_deliveries::next_attempt_at]. Redirects are not followed, and the response body is not read, since the receiver is chosen by organization users.
+
+The requests are only sent to public addresses so that webhooks cannot be used for reaching the internal services of the deployment, see [headless_lms_utils::public_addresses]. The host is resolved before sending, and the request is sent to the checked address.
+*/
+
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use headless_lms_models::{
+    webhook_deliveries::{self, PendingWebhookDelivery},
+    webhook_delivery_attempts::{self, NewWebhookDeliveryAttempt},
+    webhook_subscriptions::WebhookEventType,
+};
+use headless_lms_utils::public_addresses;
+use hmac::{Hmac, Mac};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::Sha256;
+
+use crate::prelude::*;
+
+pub const ID_HEADER: &str = "X-Webhook-Id";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+const SECRET_LENGTH: usize = 40;
+
+/// The body of the requests sent to the webhooks.
+#[derive(Debug, Serialize)]
+pub struct WebhookRequestBody<'a> {
+    /// The id of the delivery. Stays the same when the delivery is retried, so it can be used to ignore duplicates.
+    pub id: Uuid,
+    pub event_type: WebhookEventType,
+    pub created_at: DateTime<Utc>,
+    pub data: &'a serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryOutcome {
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl DeliveryOutcome {
+    pub fn is_success(&self) -> bool {
+        self.response_status
+            .map(|status| (200..300).contains(&status))
+            .unwrap_or(false)
+    }
+}
+
+/// Sends the requests to the webhooks.
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookSender {
+    timeout: Duration,
+    /// Only for tests that send the requests to a local mock server.
+    allow_private_destinations: bool,
+}
+
+impl WebhookSender {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            allow_private_destinations: false,
+        }
+    }
+
+    /// Resolves the host of the url and checks that it only resolves to public addresses. Returns the address the request should be sent to.
+    async fn resolve_destination(&self, url: &str) -> Result<(String, SocketAddr), String> {
+        let url = url::Url::parse(url).map_err(|_| "The url is not valid.".to_string())?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| "The url has no host.".to_string())?
+            .to_string();
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| "The url has no port.".to_string())?;
+        // IPv6 hosts are in brackets in urls
+        let addresses =
+            tokio::net::lookup_host((host.trim_matches(|c| c == '[' || c == ']'), port))
+                .await
+                .map_err(|err| format!("Failed to resolve the host: {}", err))?
+                .collect::<Vec<_>>();
+        if !self.allow_private_destinations
+            && addresses
+                .iter()
+                .any(|address| !public_addresses::is_public_ip_address(address.ip()))
+        {
+            return Err("The host resolves to an address that is not public.".to_string());
+        }
+        let address = addresses
+            .into_iter()
+            .next()
+            .ok_or_else(|| "The host did not resolve to any address.".to_string())?;
+        Ok((host, address))
+    }
+}
+
+/// Generates a new secret for signing the requests sent to a webhook.
+pub fn generate_secret() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Computes the value of the signature header for a request.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+/// Sends the delivery to the webhook once. Does not touch the database.
+pub async fn send(
+    sender: &WebhookSender,
+    delivery: &PendingWebhookDelivery,
+) -> anyhow::Result<DeliveryOutcome> {
+    let (host, address) = match sender.resolve_destination(&delivery.url).await {
+        Ok(destination) => destination,
+        Err(error) => {
+            return Ok(DeliveryOutcome {
+                response_status: None,
+                error: Some(error),
+            })
+        }
+    };
+    // the connection is made to the checked address so that the host can't resolve to another address in between
+    let client = reqwest::Client::builder()
+        .timeout(sender.timeout)
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve(&host, address)
+        .build()?;
+    let body = serde_json::to_string(&WebhookRequestBody {
+        id: delivery.id,
+        event_type: delivery.event_type,
+        created_at: delivery.created_at,
+        data: &delivery.payload,
+    })?;
+    let timestamp = Utc::now().timestamp();
+    let signature = sign(&delivery.secret, timestamp, &body);
+    let event = serde_json::to_value(delivery.event_type)?;
+    let res = client
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(ID_HEADER, delivery.id.to_string())
+        .header(EVENT_HEADER, event.as_str().unwrap_or_default())
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, signature)
+        .body(body)
+        .send()
+        .await;
+    let outcome = match res {
+        Ok(res) => DeliveryOutcome {
+            response_status: Some(res.status().as_u16()),
+            error: None,
+        },
+        Err(err) => DeliveryOutcome {
+            response_status: None,
+            error: Some(err.to_string()),
+        },
+    };
+    Ok(outcome)
+}
+
+/// Sends the delivery, logs the attempt and schedules a retry if it failed. Returns true if the delivery succeeded.
+pub async fn deliver(
+    conn: &mut PgConnection,
+    sender: &WebhookSender,
+    delivery: &PendingWebhookDelivery,
+) -> anyhow::Result<bool> {
+    let start = Instant::now();
+    let outcome = send(sender, delivery).await?;
+    let duration_ms = start.elapsed().as_millis().try_into().unwrap_or(i32::MAX);
+
+    let mut tx = conn.begin().await?;
+    webhook_delivery_attempts::insert(
+        &mut tx,
+        &NewWebhookDeliveryAttempt {
+            webhook_delivery_id: delivery.id,
+            response_status: outcome.response_status.map(i32::from),
+            error: outcome.error.clone(),
+            duration_ms,
+        },
+    )
+    .await?;
+    let success = outcome.is_success();
+    if success {
+        webhook_deliveries::mark_delivered(&mut tx, delivery.id).await?;
+    } else {
+        let next_attempt_at =
+            webhook_deliveries::next_attempt_at(delivery.attempts + 1, Utc::now());
+        webhook_deliveries::mark_attempt_failed(&mut tx, delivery.id, next_attempt_at).await?;
+    }
+    tx.commit().await?;
+    Ok(success)
+}
+
+#[cfg(test)]
+mod test {
+    use mockito::Matcher;
+
+    use super::*;
+
+    fn test_sender() -> WebhookSender {
+        WebhookSender {
+            allow_private_destinations: true,
+            ..WebhookSender::new(Duration::from_secs(10))
+        }
+    }
+
+    fn pending_delivery(url: String) -> PendingWebhookDelivery {
+        PendingWebhookDelivery {
+            id: Uuid::parse_str("307fa56f-9853-4f5c-afb9-a6736c232f32").unwrap(),
+            created_at: Utc::now(),
+            event_type: WebhookEventType::ExamEnded,
+            payload: serde_json::json!({ "exam_id": "8e14e9b4-3b1a-4b5d-9d4e-2b7f9a8c6d5e" }),
+            attempts: 0,
+            url,
+            secret: "secret".to_string(),
+        }
+    }
+
+    #[test]
+    fn signs_timestamp_and_body() {
+        assert_eq!(
+            sign("secret", 1675846800, r#"{"hello":"world"}"#),
+            "sha256=a632ef072a4d6a65669d21e1ce18af24edf7820949fe2caf6505615e43e9b649"
+        );
+    }
+
+    #[test]
+    fn generates_random_secrets() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), SECRET_LENGTH);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[tokio::test]
+    async fn sends_signed_request() {
+        let _mock = mockito::mock("POST", "/webhook")
+            .match_header(EVENT_HEADER, "exam_ended")
+            .match_header(ID_HEADER, "307fa56f-9853-4f5c-afb9-a6736c232f32")
+            .match_header(
+                SIGNATURE_HEADER,
+                Matcher::Regex("^sha256=[0-9a-f]{64}$".to_string()),
+            )
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "event_type": "exam_ended",
+                "data": { "exam_id": "8e14e9b4-3b1a-4b5d-9d4e-2b7f9a8c6d5e" }
+            })))
+            .with_status(204)
+            .create();
+        let delivery = pending_delivery(format!("{}/webhook", mockito::server_url()));
+        let outcome = send(&test_sender(), &delivery).await.unwrap();
+        assert_eq!(outcome.response_status, Some(204));
+        assert!(outcome.is_success());
+    }
+
+    #[tokio::test]
+    async fn error_responses_are_not_successful() {
+        let _mock = mockito::mock("POST", "/failing-webhook")
+            .with_status(500)
+            .with_body("internal error")
+            .create();
+        let delivery = pending_delivery(format!("{}/failing-webhook", mockito::server_url()));
+        let outcome = send(&test_sender(), &delivery).await.unwrap();
+        assert_eq!(outcome.response_status, Some(500));
+        assert!(!outcome.is_success());
+    }
+
+    #[tokio::test]
+    async fn does_not_follow_redirects() {
+        let _mock = mockito::mock("POST", "/redirecting-webhook")
+            .with_status(307)
+            .with_header("location", "http://169.254.169.254/latest/meta-data")
+            .create();
+        let delivery = pending_delivery(format!("{}/redirecting-webhook", mockito::server_url()));
+        let outcome = send(&test_sender(), &delivery).await.unwrap();
+        assert_eq!(outcome.response_status, Some(307));
+        assert!(!outcome.is_success());
+    }
+
+    #[tokio::test]
+    async fn does_not_send_to_private_addresses() {
+        let mock = mockito::mock("POST", "/internal-webhook")
+            .with_status(204)
+            .expect(0)
+            .create();
+        let sender = WebhookSender::new(Duration::from_secs(10));
+        for url in [
+            format!("{}/internal-webhook", mockito::server_url()),
+            "http://localhost/internal-webhook".to_string(),
+            "http://[::1]/internal-webhook".to_string(),
+        ] {
+            let outcome = send(&sender, &pending_delivery(url)).await.unwrap();
+            assert_eq!(outcome.response_status, None);
+            assert_eq!(
+                outcome.error.as_deref(),
+                Some("The host resolves to an address that is not public.")
+            );
+        }
+        mock.assert();
+    }
+}
@@ -32,7 +32,11 @@ use actix_web::{
     HttpResponse,
 };
 use anyhow::Result;
-use domain::{models_requests::JwtKey, request_span_middleware::RequestSpan};
+use domain::{
+    api_tokens::ApiTokenAuthentication, deleted_user_guard::DeletedUserGuard,
+    impersonation::ImpersonationGuard, lti::LtiToolKey, metrics::HttpMetrics,
+    models_requests::JwtKey, request_span_middleware::RequestSpan,
+};
 use headless_lms_utils::{
     file_store::FileStore, ip_to_country::IpToCountryMapper, ApplicationConfiguration,
 };
@@ -48,6 +52,7 @@ pub fn configure(
     file_store: Arc<dyn FileStore>,
     app_conf: ApplicationConfiguration,
     jwt_key: JwtKey,
+    lti_key: Option<LtiToolKey>,
 ) {
     let ip_to_country_mapper =
         IpToCountryMapper::new().expect("Could not load ip to country mapper");
@@ -65,14 +70,21 @@ pub fn configure(
         .app_data(json_config)
         .service(
             web::scope("/api/v0")
+                .wrap(ImpersonationGuard)
+                .wrap(DeletedUserGuard)
+                .wrap(ApiTokenAuthentication)
                 .wrap(RequestSpan)
+                .wrap(HttpMetrics)
                 .configure(controllers::configure_controllers),
         )
+        // not under /api so that it is not exposed through the ingress
+        .route("/metrics", web::get().to(domain::metrics::metrics_endpoint))
         // Not using Data::new for file_store to avoid double wrapping it in a arc
         .app_data(Data::from(file_store))
         .app_data(Data::new(app_conf))
         .app_data(Data::new(ip_to_country_mapper))
-        .app_data(Data::new(jwt_key));
+        .app_data(Data::new(jwt_key))
+        .app_data(Data::new(lti_key));
 }
 
 /**
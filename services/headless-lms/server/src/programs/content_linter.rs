@@ -0,0 +1,59 @@
+use std::env;
+
+use crate::{domain::metrics, programs::start_server::setup_file_store, setup_tracing};
+use dotenv::dotenv;
+use headless_lms_models as models;
+use headless_lms_utils::ApplicationConfiguration;
+use sqlx::{Connection, PgConnection};
+
+/// Checks the content of the pages of all courses for broken links, missing media files and other problems, and stores the results for the teachers.
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv().ok();
+    setup_tracing()?;
+    let database_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let app_conf = ApplicationConfiguration {
+        base_url: env::var("BASE_URL").expect("BASE_URL must be defined"),
+        test_mode: env::var("TEST_MODE").is_ok(),
+        development_uuid_login: env::var("DEVELOPMENT_UUID_LOGIN").is_ok(),
+    };
+    let file_store = setup_file_store();
+    let mut conn = PgConnection::connect(&database_url).await?;
+
+    let courses = models::courses::all_courses(&mut conn).await?;
+    info!("Checking the content of {} courses.", courses.len());
+    let mut success = 0;
+    for course in courses.iter() {
+        match models::library::content_linting::lint_course(
+            &mut conn,
+            course.id,
+            file_store.as_ref(),
+            &app_conf,
+        )
+        .await
+        {
+            Ok(_) => {
+                metrics::CONTENT_LINT_RUNS_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+                success += 1;
+            }
+            Err(err) => {
+                metrics::CONTENT_LINT_RUNS_TOTAL
+                    .with_label_values(&["failure"])
+                    .inc();
+                error!(
+                    "Failed to check the content of course {}: {:#?}",
+                    course.id, err
+                );
+            }
+        }
+    }
+    info!(
+        "Course contents checked. Succeeded: {}, failed: {}.",
+        success,
+        courses.len() - success
+    );
+    metrics::push("content-linter").await
+}
@@ -2,7 +2,9 @@ use std::{env, time::Duration};
 
 use anyhow::{Context, Result};
 use futures::{FutureExt, StreamExt};
-use headless_lms_models::email_deliveries::{fetch_emails, mark_as_sent, save_err_to_email, Email};
+use headless_lms_models::email_deliveries::{
+    fetch_emails, get_unsent_count, mark_as_sent, save_err_to_email, Email,
+};
 use headless_lms_utils::email_processor::{self, EmailGutenbergBlock};
 use lettre::{
     message::{header, MultiPart, SinglePart},
@@ -11,6 +13,8 @@ use lettre::{
 use once_cell::sync::Lazy;
 use sqlx::PgPool;
 
+use crate::domain::metrics;
+
 const BATCH_SIZE: usize = 100;
 
 static MOOCFI_EMAIL: Lazy<String> =
@@ -28,6 +32,7 @@ pub async fn mail_sender() -> Result<()> {
 
     let mut conn = pool.acquire().await?;
 
+    metrics::EMAIL_QUEUE_LENGTH.set(get_unsent_count(&mut conn).await?);
     let emails = fetch_emails(&mut conn).await?;
     let mailer = SmtpTransport::relay(&EMAIL_RELAY)?.build();
 
@@ -81,18 +86,29 @@ pub async fn send_message(email: Email, mailer: &SmtpTransport, pool: PgPool) ->
         .expect("Failed to build email");
 
     match mailer.send(&msg) {
-        Ok(_) => mark_as_sent(email.id, &mut conn)
-            .await
-            .context("Couldn't mark as sent")?,
-        Err(err) => save_err_to_email(email.id, err, &mut conn)
-            .await
-            .context("Couldn't save sent err to db")?,
+        Ok(_) => {
+            metrics::EMAILS_SENT_TOTAL
+                .with_label_values(&["sent"])
+                .inc();
+            mark_as_sent(email.id, &mut conn)
+                .await
+                .context("Couldn't mark as sent")?
+        }
+        Err(err) => {
+            metrics::EMAILS_SENT_TOTAL
+                .with_label_values(&["failed"])
+                .inc();
+            save_err_to_email(email.id, err, &mut conn)
+                .await
+                .context("Couldn't save sent err to db")?
+        }
     };
 
     Ok(())
 }
 
 pub async fn main() -> anyhow::Result<()> {
+    metrics::serve()?;
     let mut interval = tokio::time::interval(Duration::from_secs(10));
     loop {
         interval.tick().await;
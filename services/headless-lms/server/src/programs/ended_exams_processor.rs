@@ -1,6 +1,6 @@
 use std::{collections::HashSet, env};
 
-use crate::setup_tracing;
+use crate::{domain::metrics, setup_tracing};
 use chrono::Utc;
 use dotenv::dotenv;
 use headless_lms_models as models;
@@ -15,7 +15,8 @@ pub async fn main() -> anyhow::Result<()> {
         .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
     let db_pool = PgPool::connect(&database_url).await?;
     let mut conn = db_pool.acquire().await?;
-    process_ended_exams(&mut conn).await
+    process_ended_exams(&mut conn).await?;
+    metrics::push("ended-exams-processor").await
 }
 
 /// Fetches ended exams that haven't yet been processed and updates completions for them.
@@ -28,8 +29,16 @@ async fn process_ended_exams(conn: &mut sqlx::PgConnection) -> anyhow::Result<()
     let mut success = 0;
     for exam_id in exam_ids.iter() {
         match process_ended_exam(conn, *exam_id, &mut processed_courses_cache).await {
-            Ok(_) => success += 1,
+            Ok(_) => {
+                metrics::ENDED_EXAMS_PROCESSED_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+                success += 1;
+            }
             Err(err) => {
+                metrics::ENDED_EXAMS_PROCESSED_TOTAL
+                    .with_label_values(&["failure"])
+                    .inc();
                 tracing::error!("Failed to process exam {}: {:#?}", exam_id, err);
             }
         }
@@ -63,6 +72,12 @@ async fn process_ended_exam(
         }
     }
     models::ended_processed_exams::upsert(&mut tx, exam_id).await?;
+    models::webhook_deliveries::insert_for_event(
+        &mut tx,
+        models::CourseOrExamId::Exam(exam_id),
+        &models::webhook_deliveries::WebhookEvent::ExamEnded { exam_id },
+    )
+    .await?;
     tx.commit().await?;
     Ok(())
 }
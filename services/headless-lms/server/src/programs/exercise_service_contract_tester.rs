@@ -0,0 +1,698 @@
+/*!
+Checks that an exercise service implements the protocol headless-lms uses for communicating with exercise services.
+
+Usage: `exercise-service-contract-tester <service-info-url> <private-spec.json> [submission.json]`, for example
+`cargo run -- exercise-service-contract-tester http://localhost:3002/example-exercise/api/service-info private-spec.json submission.json`.
+
+The requests are made with the same functions the server uses, so a service that passes the checks works with the server.
+The public spec and model solution requests include an upload URL that points to a local server started by this program,
+which accepts uploads the same way as `POST /api/v0/files/:exercise_service_slug`. The slug is the first segment of the service info URL path.
+The local server listens on `CONTRACT_TESTER_UPLOAD_ADDRESS`, `127.0.0.1` with a random port by default.
+
+Prints a report of the checks and exits with an error if any of them failed.
+*/
+
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    sync::{Arc, Mutex},
+};
+
+use actix_multipart::Multipart;
+use actix_web::{web, App, HttpResponse, HttpServer};
+use anyhow::{bail, Context};
+use chrono::Utc;
+use dotenv::dotenv;
+use futures::StreamExt;
+use headless_lms_models::{
+    exercise_service_info::ExerciseServiceInfoApi,
+    exercise_task_gradings::ExerciseTaskGradingResult,
+    exercise_task_submissions::ExerciseTaskSubmission, exercise_tasks::ExerciseTask,
+    exercises::GradingProgress,
+};
+use rand::{distributions::Alphanumeric, Rng};
+use serde_json::Value;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        error::ControllerError,
+        models_requests::{self, JwtKey, UploadClaim},
+    },
+    setup_tracing,
+};
+
+const USAGE: &str =
+    "Usage: exercise-service-contract-tester <service-info-url> <private-spec.json> [submission.json]";
+
+#[derive(Debug)]
+enum CheckResult {
+    Passed(String),
+    Failed(String),
+    Skipped(String),
+}
+
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    result: CheckResult,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (status, details) = match &self.result {
+            CheckResult::Passed(details) => ("PASS", details),
+            CheckResult::Failed(details) => ("FAIL", details),
+            CheckResult::Skipped(details) => ("SKIP", details),
+        };
+        write!(f, "{} {:<22} {}", status, self.name, details)
+    }
+}
+
+/// The uploads the exercise service has made to the local upload server.
+#[derive(Debug, Default)]
+struct ReceivedUploads {
+    files: Vec<String>,
+    errors: Vec<String>,
+}
+
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=warn,actix_server=warn");
+    dotenv().ok();
+    setup_tracing()?;
+
+    let mut args = env::args().skip(2);
+    let (service_info_url, private_spec_path) = match (args.next(), args.next()) {
+        (Some(service_info_url), Some(private_spec_path)) => (service_info_url, private_spec_path),
+        _ => bail!(USAGE),
+    };
+    let service_info_url = Url::parse(&service_info_url).context("Invalid service info URL")?;
+    let private_spec = read_json(&private_spec_path)?;
+    let submission = args.next().map(|path| read_json(&path)).transpose()?;
+
+    let checks = run_checks(service_info_url, private_spec, submission).await?;
+    println!("Exercise service contract test report");
+    for check in &checks {
+        println!("{}", check);
+    }
+    let failed = checks
+        .iter()
+        .filter(|check| matches!(check.result, CheckResult::Failed(_)))
+        .count();
+    if failed > 0 {
+        bail!("{} of {} checks failed", failed, checks.len());
+    }
+    println!("All required checks passed.");
+    Ok(())
+}
+
+fn read_json(path: &str) -> anyhow::Result<Value> {
+    let contents = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+    let json = serde_json::from_str(&contents)
+        .with_context(|| format!("{} does not contain valid JSON", path))?;
+    Ok(json)
+}
+
+async fn run_checks(
+    service_info_url: Url,
+    private_spec: Value,
+    submission: Option<Value>,
+) -> anyhow::Result<Vec<Check>> {
+    let mut checks = vec![];
+
+    let service_info = match models_requests::fetch_service_info(service_info_url.clone()).await {
+        Ok(service_info) => {
+            checks.push(Check {
+                name: "service-info",
+                result: CheckResult::Passed(format!(
+                    "service name \"{}\"",
+                    service_info.service_name
+                )),
+            });
+            service_info
+        }
+        Err(err) => {
+            checks.push(Check {
+                name: "service-info",
+                result: CheckResult::Failed(err.to_string()),
+            });
+            for name in [
+                "user-interface",
+                "public-spec",
+                "model-solution-spec",
+                "upload-claim",
+                "grade",
+            ] {
+                checks.push(Check {
+                    name,
+                    result: CheckResult::Skipped("requires a valid service info".to_string()),
+                });
+            }
+            return Ok(checks);
+        }
+    };
+    checks.push(Check {
+        name: "service-info-paths",
+        result: check_paths(&service_info),
+    });
+    let endpoint_url = |path: &str| {
+        let mut url = service_info_url.clone();
+        url.set_path(path);
+        url
+    };
+
+    let user_interface_url = endpoint_url(&service_info.user_interface_iframe_path);
+    let result = match reqwest::get(user_interface_url.clone()).await {
+        Ok(res) if res.status().is_success() => {
+            CheckResult::Passed(format!("{} responded {}", user_interface_url, res.status()))
+        }
+        Ok(res) => {
+            CheckResult::Failed(format!("{} responded {}", user_interface_url, res.status()))
+        }
+        Err(err) => CheckResult::Failed(err.to_string()),
+    };
+    checks.push(Check {
+        name: "user-interface",
+        result,
+    });
+
+    // a local server that accepts the uploads with the upload claims the spec fetcher signs
+    let exercise_service_slug = service_info_url
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .filter(|segment| !segment.is_empty())
+        .unwrap_or("exercise-service")
+        .to_string();
+    let jwt_secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    let jwt_key = Arc::new(JwtKey::new(&jwt_secret)?);
+    let uploads = web::Data::new(Mutex::new(ReceivedUploads::default()));
+    let server_jwt_key = web::Data::from(Arc::clone(&jwt_key));
+    let server_uploads = uploads.clone();
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(server_jwt_key.clone())
+            .app_data(server_uploads.clone())
+            .route(
+                "/api/v0/files/{exercise_service_slug}",
+                web::post().to(receive_upload),
+            )
+    })
+    .workers(1)
+    .bind(
+        env::var("CONTRACT_TESTER_UPLOAD_ADDRESS").unwrap_or_else(|_| "127.0.0.1:0".to_string()),
+    )?;
+    let upload_address = *server
+        .addrs()
+        .first()
+        .context("The upload server is not listening on any address")?;
+    let server = server.run();
+    let server_handle = server.handle();
+    actix_web::rt::spawn(server);
+    let spec_fetcher = models_requests::make_spec_fetcher_with_upload_base_url(
+        jwt_key,
+        format!("http://{}", upload_address),
+    );
+
+    let public_spec = spec_fetcher(
+        endpoint_url(&service_info.public_spec_endpoint_path),
+        &exercise_service_slug,
+        Some(&private_spec),
+    )
+    .await;
+    checks.push(Check {
+        name: "public-spec",
+        result: match &public_spec {
+            Ok(_) => CheckResult::Passed("received a public spec".to_string()),
+            Err(err) => CheckResult::Failed(err.to_string()),
+        },
+    });
+    let model_solution_spec = spec_fetcher(
+        endpoint_url(&service_info.model_solution_spec_endpoint_path),
+        &exercise_service_slug,
+        Some(&private_spec),
+    )
+    .await;
+    checks.push(Check {
+        name: "model-solution-spec",
+        result: match &model_solution_spec {
+            Ok(_) => CheckResult::Passed("received a model solution spec".to_string()),
+            Err(err) => CheckResult::Failed(err.to_string()),
+        },
+    });
+
+    server_handle.stop(true).await;
+    let result = {
+        let uploads = uploads
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Failed to lock mutex"))?;
+        if !uploads.errors.is_empty() {
+            CheckResult::Failed(uploads.errors.join("; "))
+        } else if !uploads.files.is_empty() {
+            CheckResult::Passed(format!(
+                "uploaded {} with a valid upload claim",
+                uploads.files.join(", ")
+            ))
+        } else {
+            CheckResult::Skipped("the service did not upload any files".to_string())
+        }
+    };
+    checks.push(Check {
+        name: "upload-claim",
+        result,
+    });
+
+    let result = match submission {
+        Some(submission) => {
+            let exercise_task = ExerciseTask {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                exercise_slide_id: Uuid::new_v4(),
+                exercise_type: exercise_service_slug,
+                assignment: Value::Array(vec![]),
+                deleted_at: None,
+                public_spec: public_spec.ok(),
+                private_spec: Some(private_spec),
+                model_solution_spec: model_solution_spec.ok(),
+                copied_from: None,
+                order_number: 0,
+            };
+            let submission = ExerciseTaskSubmission {
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+                exercise_slide_submission_id: Uuid::new_v4(),
+                exercise_task_id: exercise_task.id,
+                exercise_slide_id: exercise_task.exercise_slide_id,
+                data_json: Some(submission),
+                exercise_task_grading_id: None,
+                metadata: None,
+            };
+            match models_requests::send_grading_request(
+                endpoint_url(&service_info.grade_endpoint_path),
+                &exercise_task,
+                &submission,
+            )
+            .await
+            {
+                Ok(grading_result) => {
+                    let problems = grading_result_problems(&grading_result);
+                    if problems.is_empty() {
+                        CheckResult::Passed(format!(
+                            "{:?} with {}/{} points",
+                            grading_result.grading_progress,
+                            grading_result.score_given,
+                            grading_result.score_maximum
+                        ))
+                    } else {
+                        CheckResult::Failed(problems.join("; "))
+                    }
+                }
+                Err(err) => CheckResult::Failed(err.to_string()),
+            }
+        }
+        None => CheckResult::Skipped("no sample submission given".to_string()),
+    };
+    checks.push(Check {
+        name: "grade",
+        result,
+    });
+
+    Ok(checks)
+}
+
+/// The server sets the paths of the service info to the service URL, so they need to be absolute.
+fn check_paths(service_info: &ExerciseServiceInfoApi) -> CheckResult {
+    let paths = [
+        (
+            "user_interface_iframe_path",
+            &service_info.user_interface_iframe_path,
+        ),
+        ("grade_endpoint_path", &service_info.grade_endpoint_path),
+        (
+            "public_spec_endpoint_path",
+            &service_info.public_spec_endpoint_path,
+        ),
+        (
+            "model_solution_spec_endpoint_path",
+            &service_info.model_solution_spec_endpoint_path,
+        ),
+    ];
+    let invalid = paths
+        .iter()
+        .filter(|(_, path)| !path.starts_with('/'))
+        .map(|(name, path)| format!("{} \"{}\" is not an absolute path", name, path))
+        .collect::<Vec<_>>();
+    if invalid.is_empty() {
+        CheckResult::Passed("all endpoint paths are absolute".to_string())
+    } else {
+        CheckResult::Failed(invalid.join("; "))
+    }
+}
+
+/// Checks the parts of the grading result the type system does not.
+fn grading_result_problems(grading_result: &ExerciseTaskGradingResult) -> Vec<String> {
+    let mut problems = vec![];
+    if grading_result.score_maximum < 0 {
+        problems.push(format!(
+            "score_maximum {} is negative",
+            grading_result.score_maximum
+        ));
+    }
+    if !grading_result.score_given.is_finite()
+        || grading_result.score_given < 0.0
+        || grading_result.score_given > grading_result.score_maximum as f32
+    {
+        problems.push(format!(
+            "score_given {} is not between 0 and score_maximum {}",
+            grading_result.score_given, grading_result.score_maximum
+        ));
+    }
+    if grading_result.grading_progress == GradingProgress::Failed {
+        problems.push("the grading failed".to_string());
+    }
+    problems
+}
+
+/// Accepts uploads like `POST /api/v0/files/:exercise_service_slug`, but only records them.
+async fn receive_upload(
+    exercise_service_slug: web::Path<String>,
+    mut payload: Multipart,
+    uploads: web::Data<Mutex<ReceivedUploads>>,
+    upload_claim: Result<UploadClaim<'static>, ControllerError>,
+) -> HttpResponse {
+    let error = match upload_claim {
+        Ok(upload_claim)
+            if upload_claim.exercise_service_slug() == exercise_service_slug.as_str() =>
+        {
+            None
+        }
+        Ok(_) => Some("Exercise service slug did not match upload claim".to_string()),
+        Err(err) => Some(err.to_string()),
+    };
+    let mut paths = HashMap::new();
+    while let Some(field) = payload.next().await {
+        let mut field = match field {
+            Ok(field) => field,
+            Err(err) => {
+                if let Ok(mut uploads) = uploads.lock() {
+                    uploads.errors.push(format!("Invalid upload: {}", err));
+                }
+                return HttpResponse::BadRequest().finish();
+            }
+        };
+        // the contents are not needed, only that the upload succeeds
+        while let Some(chunk) = field.next().await {
+            if chunk.is_err() {
+                break;
+            }
+        }
+        paths.insert(
+            field.name().to_string(),
+            format!("{}/{}", exercise_service_slug, Uuid::new_v4()),
+        );
+    }
+
+    let mut uploads = match uploads.lock() {
+        Ok(uploads) => uploads,
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
+    match error {
+        Some(error) => {
+            uploads.errors.push(format!("Upload rejected: {}", error));
+            HttpResponse::BadRequest().body(error)
+        }
+        None => {
+            uploads.files.extend(paths.keys().cloned());
+            HttpResponse::Ok().json(paths)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mockito::Mock;
+    use serde_json::json;
+
+    use super::*;
+
+    fn service_info(prefix: &str) -> ExerciseServiceInfoApi {
+        ExerciseServiceInfoApi {
+            service_name: "Example exercise".to_string(),
+            user_interface_iframe_path: format!("{}/iframe", prefix),
+            grade_endpoint_path: format!("{}/api/grade", prefix),
+            public_spec_endpoint_path: format!("{}/api/public-spec", prefix),
+            model_solution_spec_endpoint_path: format!("{}/api/model-solution", prefix),
+        }
+    }
+
+    fn grading_result(
+        grading_progress: GradingProgress,
+        score_given: f32,
+        score_maximum: i32,
+    ) -> ExerciseTaskGradingResult {
+        ExerciseTaskGradingResult {
+            grading_progress,
+            score_given,
+            score_maximum,
+            feedback_text: None,
+            feedback_json: None,
+            set_user_variables: None,
+        }
+    }
+
+    fn mock_json(method: &str, path: &str, status: usize, body: Value) -> Mock {
+        mockito::mock(method, path)
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create()
+    }
+
+    /// The status of each check by name, in the order the checks were made.
+    fn statuses(checks: &[Check]) -> Vec<(&'static str, &'static str)> {
+        checks
+            .iter()
+            .map(|check| {
+                let status = match check.result {
+                    CheckResult::Passed(_) => "PASS",
+                    CheckResult::Failed(_) => "FAIL",
+                    CheckResult::Skipped(_) => "SKIP",
+                };
+                (check.name, status)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn accepts_absolute_paths() {
+        assert!(matches!(
+            check_paths(&service_info("/example-exercise")),
+            CheckResult::Passed(_)
+        ));
+    }
+
+    #[test]
+    fn rejects_relative_paths() {
+        let mut service_info = service_info("/example-exercise");
+        service_info.grade_endpoint_path = "api/grade".to_string();
+        match check_paths(&service_info) {
+            CheckResult::Failed(details) => {
+                assert_eq!(
+                    details,
+                    "grade_endpoint_path \"api/grade\" is not an absolute path"
+                )
+            }
+            other => panic!("expected a failed check, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_valid_grading_results() {
+        for grading_result in [
+            grading_result(GradingProgress::FullyGraded, 1.0, 1),
+            grading_result(GradingProgress::FullyGraded, 0.0, 0),
+            grading_result(GradingProgress::PendingManual, 0.5, 2),
+        ] {
+            assert!(
+                grading_result_problems(&grading_result).is_empty(),
+                "{:?}",
+                grading_result
+            );
+        }
+    }
+
+    #[test]
+    fn finds_problems_in_grading_results() {
+        assert_eq!(
+            grading_result_problems(&grading_result(GradingProgress::FullyGraded, 2.0, 1)),
+            vec!["score_given 2 is not between 0 and score_maximum 1"]
+        );
+        assert_eq!(
+            grading_result_problems(&grading_result(GradingProgress::FullyGraded, -1.0, 1)),
+            vec!["score_given -1 is not between 0 and score_maximum 1"]
+        );
+        assert_eq!(
+            grading_result_problems(&grading_result(GradingProgress::FullyGraded, f32::NAN, 1))
+                .len(),
+            1
+        );
+        assert_eq!(
+            grading_result_problems(&grading_result(GradingProgress::FullyGraded, 0.0, -1)),
+            vec![
+                "score_maximum -1 is negative",
+                "score_given 0 is not between 0 and score_maximum -1"
+            ]
+        );
+        assert_eq!(
+            grading_result_problems(&grading_result(GradingProgress::Failed, 0.0, 1)),
+            vec!["the grading failed"]
+        );
+    }
+
+    #[actix_web::test]
+    async fn passes_a_service_that_follows_the_protocol() {
+        let prefix = "/passing-exercise";
+        let _service_info = mock_json(
+            "GET",
+            &format!("{}/api/service-info", prefix),
+            200,
+            json!(service_info(prefix)),
+        );
+        let _iframe = mockito::mock("GET", format!("{}/iframe", prefix).as_str())
+            .with_status(200)
+            .with_body("<html></html>")
+            .create();
+        let _public_spec = mock_json(
+            "POST",
+            &format!("{}/api/public-spec", prefix),
+            200,
+            json!({ "options": ["a", "b"] }),
+        );
+        let _model_solution = mock_json(
+            "POST",
+            &format!("{}/api/model-solution", prefix),
+            200,
+            json!({ "correct": "a" }),
+        );
+        let _grade = mock_json(
+            "POST",
+            &format!("{}/api/grade", prefix),
+            200,
+            json!(grading_result(GradingProgress::FullyGraded, 1.0, 1)),
+        );
+
+        let checks = run_checks(
+            Url::parse(&format!(
+                "{}{}/api/service-info",
+                mockito::server_url(),
+                prefix
+            ))
+            .unwrap(),
+            json!({ "correct": "a" }),
+            Some(json!({ "answer": "a" })),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            statuses(&checks),
+            vec![
+                ("service-info", "PASS"),
+                ("service-info-paths", "PASS"),
+                ("user-interface", "PASS"),
+                ("public-spec", "PASS"),
+                ("model-solution-spec", "PASS"),
+                ("upload-claim", "SKIP"),
+                ("grade", "PASS"),
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn fails_a_service_that_breaks_the_protocol() {
+        let prefix = "/failing-exercise";
+        let mut info = service_info(prefix);
+        info.model_solution_spec_endpoint_path = "api/model-solution".to_string();
+        let _service_info = mock_json(
+            "GET",
+            &format!("{}/api/service-info", prefix),
+            200,
+            json!(info),
+        );
+        let _iframe = mockito::mock("GET", format!("{}/iframe", prefix).as_str())
+            .with_status(404)
+            .create();
+        let _public_spec = mockito::mock("POST", format!("{}/api/public-spec", prefix).as_str())
+            .with_status(500)
+            .create();
+        // the relative path ends up at the root of the service URL
+        let _model_solution = mock_json("POST", "/api/model-solution", 200, json!({}));
+        let _grade = mock_json(
+            "POST",
+            &format!("{}/api/grade", prefix),
+            200,
+            json!(grading_result(GradingProgress::FullyGraded, 2.0, 1)),
+        );
+
+        let checks = run_checks(
+            Url::parse(&format!(
+                "{}{}/api/service-info",
+                mockito::server_url(),
+                prefix
+            ))
+            .unwrap(),
+            json!({}),
+            Some(json!({})),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            statuses(&checks),
+            vec![
+                ("service-info", "PASS"),
+                ("service-info-paths", "FAIL"),
+                ("user-interface", "FAIL"),
+                ("public-spec", "FAIL"),
+                ("model-solution-spec", "PASS"),
+                ("upload-claim", "SKIP"),
+                ("grade", "FAIL"),
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn skips_the_rest_without_service_info() {
+        let _service_info = mockito::mock("GET", "/missing-exercise/api/service-info")
+            .with_status(404)
+            .create();
+
+        let checks = run_checks(
+            Url::parse(&format!(
+                "{}/missing-exercise/api/service-info",
+                mockito::server_url()
+            ))
+            .unwrap(),
+            json!({}),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            statuses(&checks),
+            vec![
+                ("service-info", "FAIL"),
+                ("user-interface", "SKIP"),
+                ("public-spec", "SKIP"),
+                ("model-solution-spec", "SKIP"),
+                ("upload-claim", "SKIP"),
+                ("grade", "SKIP"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::{collections::HashMap, env, time::Duration};
+
+use anyhow::Context;
+use chrono::Utc;
+use headless_lms_models as models;
+use sqlx::{Connection, PgConnection};
+use uuid::Uuid;
+
+use crate::domain::{
+    lti::{self, LtiToolKey},
+    metrics,
+};
+
+const BATCH_SIZE: i64 = 500;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/**
+Starts a thread that will periodically send the scores of users who have launched courses from LTI platforms back to the platforms' gradebooks.
+*/
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv::dotenv().ok();
+    crate::setup_tracing()?;
+    let db_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let lti_key = LtiToolKey::try_from_env()?
+        .context("LTI_PRIVATE_KEY must be defined for passing scores back to LTI platforms")?;
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()?;
+
+    metrics::serve()?;
+
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        let mut conn = PgConnection::connect(&db_url).await?;
+        // do not stop the thread on error, report it and try again next tick
+        if let Err(err) = pass_back_pending(&mut conn, &client, &lti_key).await {
+            tracing::error!("Error in LTI score passback: {}", err);
+        }
+    }
+}
+
+async fn pass_back_pending(
+    conn: &mut PgConnection,
+    client: &reqwest::Client,
+    lti_key: &LtiToolKey,
+) -> anyhow::Result<()> {
+    models::lti_login_states::delete_expired(conn).await?;
+    let pending_scores = models::lti_scores::get_pending(conn, BATCH_SIZE).await?;
+    if !pending_scores.is_empty() {
+        tracing::info!("Passing back {} LTI scores.", pending_scores.len());
+    }
+    // one access token per platform is enough for the whole batch
+    let mut access_tokens: HashMap<Uuid, Option<String>> = HashMap::new();
+    for pending in pending_scores {
+        if !access_tokens.contains_key(&pending.lti_registration_id) {
+            let registration =
+                models::lti_registrations::get_by_id(conn, pending.lti_registration_id).await?;
+            let access_token = match lti::fetch_access_token(client, lti_key, &registration).await {
+                Ok(access_token) => Some(access_token),
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to get an access token from LTI platform {}: {:#?}",
+                        registration.id,
+                        err
+                    );
+                    None
+                }
+            };
+            access_tokens.insert(registration.id, access_token);
+        }
+        let access_token = match &access_tokens[&pending.lti_registration_id] {
+            Some(access_token) => access_token,
+            None => {
+                metrics::LTI_SCORE_PASSBACKS_TOTAL
+                    .with_label_values(&["error"])
+                    .inc();
+                continue;
+            }
+        };
+
+        // taken before computing the score, so that the updates made in the meantime are passed back on the next round
+        let synced_at = Utc::now();
+        let score = models::lti_scores::get_current_score(conn, &pending).await?;
+        if score.score_maximum == 0 {
+            // the platforms do not accept scores without a maximum
+            models::lti_scores::mark_synced(conn, pending.id, &score, synced_at).await?;
+            continue;
+        }
+        match lti::send_score(client, access_token, &pending, &score, synced_at).await {
+            Ok(()) => {
+                models::lti_scores::mark_synced(conn, pending.id, &score, synced_at).await?;
+                metrics::LTI_SCORE_PASSBACKS_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+            }
+            Err(err) => {
+                metrics::LTI_SCORE_PASSBACKS_TOTAL
+                    .with_label_values(&["failure"])
+                    .inc();
+                tracing::warn!(
+                    "Failed to pass back LTI score {}, it will be retried: {:#?}",
+                    pending.id,
+                    err
+                );
+            }
+        }
+    }
+    Ok(())
+}
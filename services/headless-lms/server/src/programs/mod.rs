@@ -1,13 +1,21 @@
 /*!
 Executable programs that can be started. Contains for example the server program, background services, and utility programs.
 */
+pub mod content_linter;
 pub mod doc_file_generator;
 pub mod email_deliver;
 pub mod ended_exams_processor;
+pub mod exercise_service_contract_tester;
+pub mod lti_score_passback;
 pub mod open_university_registration_link_fetcher;
+pub mod page_draft_publisher;
 pub mod peer_review_updater;
+pub mod plagiarism_detector;
+pub mod rate_limit_counter_cleaner;
 pub mod regrader;
 pub mod seed;
 pub mod service_info_fetcher;
 pub mod sorter;
 pub mod start_server;
+pub mod user_data_exporter;
+pub mod webhook_deliverer;
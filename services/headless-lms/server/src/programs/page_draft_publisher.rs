@@ -0,0 +1,69 @@
+use std::{env, sync::Arc};
+
+use crate::{
+    domain::{
+        metrics,
+        models_requests::{self, JwtKey},
+    },
+    setup_tracing,
+};
+use dotenv::dotenv;
+use headless_lms_models as models;
+use sqlx::{Connection, PgConnection};
+
+/// Publishes the page drafts whose scheduled publishing time has passed.
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv().ok();
+    setup_tracing()?;
+    let database_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let jwt_key = Arc::new(JwtKey::try_from_env().expect("Failed to create JwtKey"));
+    let mut conn = PgConnection::connect(&database_url).await?;
+    publish_scheduled_drafts(&mut conn, jwt_key).await?;
+    metrics::push("page-draft-publisher").await
+}
+
+async fn publish_scheduled_drafts(
+    conn: &mut PgConnection,
+    jwt_key: Arc<JwtKey>,
+) -> anyhow::Result<()> {
+    let spec_fetcher = models_requests::make_spec_fetcher(jwt_key);
+    let page_ids = models::page_drafts::get_page_ids_of_drafts_to_publish(conn).await?;
+    tracing::info!("Publishing {} scheduled page drafts.", page_ids.len());
+    let mut success = 0;
+    for page_id in page_ids.iter() {
+        match models::page_drafts::publish(
+            conn,
+            *page_id,
+            None,
+            &spec_fetcher,
+            models_requests::fetch_service_info,
+        )
+        .await
+        {
+            Ok(_) => {
+                metrics::PAGE_DRAFTS_PUBLISHED_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+                success += 1;
+            }
+            Err(err) => {
+                metrics::PAGE_DRAFTS_PUBLISHED_TOTAL
+                    .with_label_values(&["failure"])
+                    .inc();
+                tracing::error!(
+                    "Failed to publish the draft of page {}: {:#?}",
+                    page_id,
+                    err
+                );
+            }
+        }
+    }
+    tracing::info!(
+        "Page drafts published. Succeeded: {}, failed: {}.",
+        success,
+        page_ids.len() - success
+    );
+    Ok(())
+}
@@ -1,8 +1,15 @@
-use crate::setup_tracing;
+use crate::{domain::metrics, setup_tracing};
+use chrono::{DateTime, Utc};
 use dotenv::dotenv;
-use headless_lms_models::peer_review_queue_entries;
+use headless_lms_models::{
+    course_instances::CourseInstance,
+    exercises,
+    peer_review_configs::{self, PeerReviewConfig, PeerReviewTimeoutAction},
+    peer_review_queue_entries,
+};
 use sqlx::{Connection, PgConnection};
-use std::env;
+use std::{collections::HashMap, env};
+use uuid::Uuid;
 
 pub async fn main() -> anyhow::Result<()> {
     env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
@@ -10,12 +17,11 @@ pub async fn main() -> anyhow::Result<()> {
     setup_tracing()?;
     let db_url = env::var("DATABASE_URL")
         .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    // In dry-run mode we only report what would be changed.
+    let dry_run = env::var("DRY_RUN").is_ok();
     let mut conn = PgConnection::connect(&db_url).await?;
     let now = chrono::offset::Utc::now();
-    let manual_review_cutoff = now - chrono::Duration::days(7 * 3);
-    // After this date, we will assume that the teacher has given up on reviewing answers, so we will consider queue entries older than this to have passed the peer review.
-    let pass_automatically_cutoff = manual_review_cutoff - chrono::Duration::days(90);
-    info!("Peer review updater started");
+    info!(dry_run, "Peer review updater started");
     // Doing the update in small parts so that we don't end up constructing too heavy queries and so that we can get more frequeent log messages about the progress
     let all_course_instances =
         headless_lms_models::course_instances::get_all_course_instances(&mut conn).await?;
@@ -23,59 +29,445 @@ pub async fn main() -> anyhow::Result<()> {
         "Processing {:?} course instances",
         all_course_instances.len()
     );
-
+    let summary =
+        process_peer_review_timeouts(&mut conn, &all_course_instances, now, dry_run).await?;
+    info!(
+        dry_run,
+        "Total answers moved to manual review: {:?}", summary.moved_to_manual_review
+    );
+    info!(
+        dry_run,
+        "Total answers given full points: {:?}", summary.given_full_points
+    );
+    info!(
+        dry_run,
+        "Total answers accepted or moved to manual review by current average: {:?}",
+        summary.accepted_by_current_average
+    );
     info!(
-        ?manual_review_cutoff,
-        "Finding answers to move to manual review"
+        dry_run,
+        "Total answers given full points after waiting for manual review: {:?}",
+        summary.given_full_points_after_manual_review_timeout
     );
+    let queue_length =
+        peer_review_queue_entries::get_count_of_entries_that_need_reviews(&mut conn).await?;
+    metrics::PEER_REVIEW_QUEUE_LENGTH.set(queue_length);
+    if !dry_run {
+        metrics::push("peer-review-updater").await?;
+    }
+    info!("All done!");
+    Ok(())
+}
 
-    let mut moved_to_manual_review = 0;
+/// How many answers the timeouts were applied to.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PeerReviewTimeoutSummary {
+    moved_to_manual_review: u32,
+    given_full_points: u32,
+    accepted_by_current_average: u32,
+    given_full_points_after_manual_review_timeout: u32,
+}
 
-    for course_instance in all_course_instances.iter() {
-        let should_be_added_to_manual_review = headless_lms_models::peer_review_queue_entries::get_entries_that_need_reviews_and_are_older_than(&mut conn, course_instance.id, manual_review_cutoff).await?;
-        if should_be_added_to_manual_review.is_empty() {
-            continue;
+/// Applies the timeout actions of the peer review configs to the answers of the given course instances. In dry-run mode the answers are only counted.
+async fn process_peer_review_timeouts(
+    conn: &mut PgConnection,
+    course_instances: &[CourseInstance],
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> anyhow::Result<PeerReviewTimeoutSummary> {
+    // The timeouts are configured in the peer review configs. Caching them by exercise id so that we don't have to fetch them for every queue entry.
+    let mut peer_review_configs_by_exercise_id: HashMap<Uuid, PeerReviewConfig> = HashMap::new();
+    let mut summary = PeerReviewTimeoutSummary::default();
+
+    info!("Finding answers that have waited too long for peer reviews");
+
+    for course_instance in course_instances.iter() {
+        let waiting_for_peer_reviews =
+            peer_review_queue_entries::get_entries_that_need_reviews_and_are_older_than(
+                &mut *conn,
+                course_instance.id,
+                now,
+            )
+            .await?;
+        for peer_review_queue_entry in waiting_for_peer_reviews {
+            let peer_review_config = get_peer_review_config(
+                &mut *conn,
+                &mut peer_review_configs_by_exercise_id,
+                course_instance,
+                peer_review_queue_entry.exercise_id,
+            )
+            .await?;
+            let timeout = peer_review_queue_entry.created_at
+                + chrono::Duration::days(peer_review_config.timeout_in_days.into());
+            if timeout > now {
+                continue;
+            }
+            info!(course_instance_id = ?course_instance.id, peer_review_queue_entry_id = ?peer_review_queue_entry.id, timeout_action = ?peer_review_config.timeout_action, dry_run, "Answer has been in the peer review queue since {:?} and has not received enough peer reviews or has not been reviewed manually.", peer_review_queue_entry.created_at);
+            match peer_review_config.timeout_action {
+                PeerReviewTimeoutAction::MoveToManualReview => {
+                    if !dry_run {
+                        peer_review_queue_entries::remove_from_queue_and_add_to_manual_review(
+                            &mut *conn,
+                            &peer_review_queue_entry,
+                        )
+                        .await?;
+                    }
+                    summary.moved_to_manual_review += 1;
+                    metrics::PEER_REVIEW_TIMEOUTS_TOTAL
+                        .with_label_values(&["move_to_manual_review"])
+                        .inc();
+                }
+                PeerReviewTimeoutAction::GiveFullPoints => {
+                    if !dry_run {
+                        peer_review_queue_entries::remove_from_queue_and_give_full_points(
+                            &mut *conn,
+                            &peer_review_queue_entry,
+                        )
+                        .await?;
+                    }
+                    summary.given_full_points += 1;
+                    metrics::PEER_REVIEW_TIMEOUTS_TOTAL
+                        .with_label_values(&["give_full_points"])
+                        .inc();
+                }
+                PeerReviewTimeoutAction::AcceptByCurrentAverage => {
+                    if !dry_run {
+                        peer_review_queue_entries::remove_from_queue_and_accept_by_current_average(
+                            &mut *conn,
+                            &peer_review_queue_entry,
+                            &peer_review_config,
+                        )
+                        .await?;
+                    }
+                    summary.accepted_by_current_average += 1;
+                    metrics::PEER_REVIEW_TIMEOUTS_TOTAL
+                        .with_label_values(&["accept_by_current_average"])
+                        .inc();
+                }
+            }
         }
-        info!(course_instance_id = ?course_instance.id, "Found {:?} answers that have been added to the peer review queue before {:?} and have not received enough peer reviews or have not been reviewed manually. Adding them to be manually reviewed by the teachers.", should_be_added_to_manual_review.len(), manual_review_cutoff);
-        for peer_review_queue_entry in should_be_added_to_manual_review {
-            peer_review_queue_entries::remove_from_queue_and_add_to_manual_review(
-                &mut conn,
-                &peer_review_queue_entry,
+    }
+
+    info!("Finding answers in manual review that the teacher has not reviewed in time");
+
+    for course_instance in course_instances.iter() {
+        let waiting_for_manual_review = peer_review_queue_entries::get_entries_waiting_for_manual_review_after_timeout_and_older_than(&mut *conn, course_instance.id, now).await?;
+        for peer_review_queue_entry in waiting_for_manual_review {
+            let peer_review_config = get_peer_review_config(
+                &mut *conn,
+                &mut peer_review_configs_by_exercise_id,
+                course_instance,
+                peer_review_queue_entry.exercise_id,
             )
             .await?;
-            moved_to_manual_review += 1
+            // Both `MoveToManualReview` and `AcceptByCurrentAverage` move answers to manual review, so the manual review timeout applies regardless of the timeout action.
+            let manual_review_timeout_in_days =
+                match peer_review_config.manual_review_timeout_in_days {
+                    Some(days) => days,
+                    // The answer waits for the teacher indefinitely
+                    None => continue,
+                };
+            // After this date, we will assume that the teacher has given up on reviewing answers, so we will consider the answer to have passed the peer review.
+            let pass_automatically_timeout = peer_review_queue_entry.created_at
+                + chrono::Duration::days(peer_review_config.timeout_in_days.into())
+                + chrono::Duration::days(manual_review_timeout_in_days.into());
+            if pass_automatically_timeout > now {
+                continue;
+            }
+            info!(course_instance_id = ?course_instance.id, peer_review_queue_entry_id = ?peer_review_queue_entry.id, dry_run, "Answer has been waiting for manual review since {:?} without the teacher reviewing it. Giving it full points.", peer_review_queue_entry.created_at);
+            if !dry_run {
+                peer_review_queue_entries::remove_from_queue_and_give_full_points(
+                    &mut *conn,
+                    &peer_review_queue_entry,
+                )
+                .await?;
+            }
+            summary.given_full_points_after_manual_review_timeout += 1;
+            metrics::PEER_REVIEW_TIMEOUTS_TOTAL
+                .with_label_values(&["give_full_points_after_manual_review_timeout"])
+                .inc();
         }
     }
 
-    info!(
-        "Total answers moved to manual review: {:?}",
-        moved_to_manual_review
-    );
+    Ok(summary)
+}
 
-    info!(
-        ?pass_automatically_cutoff,
-        "Finding answers to give full points"
-    );
+async fn get_peer_review_config(
+    conn: &mut PgConnection,
+    peer_review_configs_by_exercise_id: &mut HashMap<Uuid, PeerReviewConfig>,
+    course_instance: &CourseInstance,
+    exercise_id: Uuid,
+) -> anyhow::Result<PeerReviewConfig> {
+    if let Some(peer_review_config) = peer_review_configs_by_exercise_id.get(&exercise_id) {
+        return Ok(peer_review_config.clone());
+    }
+    let exercise = exercises::get_by_id(&mut *conn, exercise_id).await?;
+    let peer_review_config = peer_review_configs::get_by_exercise_or_course_id(
+        &mut *conn,
+        &exercise,
+        course_instance.course_id,
+    )
+    .await?;
+    peer_review_configs_by_exercise_id.insert(exercise_id, peer_review_config.clone());
+    Ok(peer_review_config)
+}
 
-    let mut given_full_points = 0;
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helper::*;
+    use chrono::Duration;
+    use headless_lms_models::{
+        exercise_slide_submissions::{self, NewExerciseSlideSubmission},
+        exercise_task_gradings::UserPointsUpdateStrategy,
+        peer_review_configs::CmsPeerReviewConfig,
+        user_exercise_states::{self, CourseInstanceOrExamId, ReviewingStage},
+        PKeyPolicy,
+    };
 
-    for course_instance in all_course_instances.iter() {
-        let should_pass = headless_lms_models::peer_review_queue_entries::get_entries_that_need_reviews_and_are_older_than(&mut conn, course_instance.id, pass_automatically_cutoff).await?;
-        if should_pass.is_empty() {
-            continue;
-        }
-        info!(course_instance_id = ?course_instance.id, "Found {:?} answers that have been added to the peer review queue before {:?} and have not received enough peer reviews or have not been reviewed manually. Giving them full points.", should_pass.len(), pass_automatically_cutoff);
-        for peer_review_queue_entry in should_pass {
-            let _res = peer_review_queue_entries::remove_from_queue_and_give_full_points(
-                &mut conn,
-                &peer_review_queue_entry,
+    /// Sets the timeouts of the course's default peer review config and adds an answer to the peer review queue.
+    async fn insert_answer_waiting_for_peer_reviews(
+        tx: &mut PgConnection,
+        user: Uuid,
+        course: Uuid,
+        instance: Uuid,
+        exercise: Uuid,
+        slide: Uuid,
+        timeout_action: PeerReviewTimeoutAction,
+    ) {
+        let peer_review_config =
+            peer_review_configs::get_default_for_course_by_course_id(&mut *tx, course)
+                .await
+                .unwrap();
+        peer_review_configs::upsert_with_id(
+            &mut *tx,
+            &CmsPeerReviewConfig {
+                id: peer_review_config.id,
+                course_id: peer_review_config.course_id,
+                exercise_id: peer_review_config.exercise_id,
+                peer_reviews_to_give: peer_review_config.peer_reviews_to_give,
+                peer_reviews_to_receive: peer_review_config.peer_reviews_to_receive,
+                accepting_threshold: peer_review_config.accepting_threshold,
+                accepting_strategy: peer_review_config.accepting_strategy,
+                calibration_samples_to_review: peer_review_config.calibration_samples_to_review,
+                timeout_in_days: 1,
+                timeout_action,
+                manual_review_timeout_in_days: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+        let exercise_slide_submission =
+            exercise_slide_submissions::insert_exercise_slide_submission(
+                &mut *tx,
+                NewExerciseSlideSubmission {
+                    exercise_slide_id: slide,
+                    course_id: Some(course),
+                    course_instance_id: Some(instance),
+                    exam_id: None,
+                    user_id: user,
+                    exercise_id: exercise,
+                    user_points_update_strategy:
+                        UserPointsUpdateStrategy::CanAddPointsAndCanRemovePoints,
+                },
             )
-            .await?;
-            given_full_points += 1;
-        }
+            .await
+            .unwrap();
+        user_exercise_states::get_or_create_user_exercise_state(
+            &mut *tx,
+            user,
+            exercise,
+            Some(instance),
+            None,
+        )
+        .await
+        .unwrap();
+        peer_review_queue_entries::insert(
+            &mut *tx,
+            PKeyPolicy::Generate,
+            user,
+            exercise,
+            instance,
+            exercise_slide_submission.id,
+            0,
+        )
+        .await
+        .unwrap();
     }
 
-    info!("Total answers given full points: {:?}", given_full_points);
-    info!("All done!");
-    Ok(())
+    async fn reviewing_stage(
+        tx: &mut PgConnection,
+        user: Uuid,
+        exercise: Uuid,
+        instance: Uuid,
+    ) -> ReviewingStage {
+        user_exercise_states::get_user_exercise_state_if_exists(
+            tx,
+            user,
+            exercise,
+            CourseInstanceOrExamId::Instance(instance),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .reviewing_stage
+    }
+
+    #[tokio::test]
+    async fn dry_run_only_counts_the_answers() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        insert_answer_waiting_for_peer_reviews(
+            tx.as_mut(),
+            user,
+            course,
+            instance.id,
+            exercise,
+            slide,
+            PeerReviewTimeoutAction::MoveToManualReview,
+        )
+        .await;
+        let stage_before = reviewing_stage(tx.as_mut(), user, exercise, instance.id).await;
+        let after_timeout = Utc::now() + Duration::hours(36);
+
+        let summary = process_peer_review_timeouts(
+            tx.as_mut(),
+            std::slice::from_ref(&instance),
+            after_timeout,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            PeerReviewTimeoutSummary {
+                moved_to_manual_review: 1,
+                ..Default::default()
+            }
+        );
+        let waiting = peer_review_queue_entries::get_entries_that_need_reviews_and_are_older_than(
+            tx.as_mut(),
+            instance.id,
+            after_timeout,
+        )
+        .await
+        .unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(
+            reviewing_stage(tx.as_mut(), user, exercise, instance.id).await,
+            stage_before
+        );
+    }
+
+    #[tokio::test]
+    async fn moves_to_manual_review_and_gives_full_points_after_manual_review_timeout() {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        insert_answer_waiting_for_peer_reviews(
+            tx.as_mut(),
+            user,
+            course,
+            instance.id,
+            exercise,
+            slide,
+            PeerReviewTimeoutAction::MoveToManualReview,
+        )
+        .await;
+
+        // the timeout has passed but the manual review timeout has not
+        let summary = process_peer_review_timeouts(
+            tx.as_mut(),
+            std::slice::from_ref(&instance),
+            Utc::now() + Duration::hours(36),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            PeerReviewTimeoutSummary {
+                moved_to_manual_review: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            reviewing_stage(tx.as_mut(), user, exercise, instance.id).await,
+            ReviewingStage::WaitingForManualGrading
+        );
+
+        let summary = process_peer_review_timeouts(
+            tx.as_mut(),
+            std::slice::from_ref(&instance),
+            Utc::now() + Duration::hours(60),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            PeerReviewTimeoutSummary {
+                given_full_points_after_manual_review_timeout: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            reviewing_stage(tx.as_mut(), user, exercise, instance.id).await,
+            ReviewingStage::ReviewedAndLocked
+        );
+    }
+
+    #[tokio::test]
+    async fn answers_not_accepted_by_current_average_are_given_full_points_after_manual_review_timeout(
+    ) {
+        insert_data!(:tx, :user, :org, :course, :instance, :course_module, :chapter, :page, :exercise, :slide);
+        insert_answer_waiting_for_peer_reviews(
+            tx.as_mut(),
+            user,
+            course,
+            instance.id,
+            exercise,
+            slide,
+            PeerReviewTimeoutAction::AcceptByCurrentAverage,
+        )
+        .await;
+
+        // without any peer reviews the answer is moved to manual review
+        let summary = process_peer_review_timeouts(
+            tx.as_mut(),
+            std::slice::from_ref(&instance),
+            Utc::now() + Duration::hours(36),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            PeerReviewTimeoutSummary {
+                accepted_by_current_average: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            reviewing_stage(tx.as_mut(), user, exercise, instance.id).await,
+            ReviewingStage::WaitingForManualGrading
+        );
+
+        let summary = process_peer_review_timeouts(
+            tx.as_mut(),
+            std::slice::from_ref(&instance),
+            Utc::now() + Duration::hours(60),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            PeerReviewTimeoutSummary {
+                given_full_points_after_manual_review_timeout: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            reviewing_stage(tx.as_mut(), user, exercise, instance.id).await,
+            ReviewingStage::ReviewedAndLocked
+        );
+    }
 }
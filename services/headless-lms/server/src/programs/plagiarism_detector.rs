@@ -0,0 +1,67 @@
+use std::env;
+
+use crate::setup_tracing;
+use chrono::{Duration, Utc};
+use dotenv::dotenv;
+use headless_lms_models as models;
+use models::library::plagiarism_detection;
+use sqlx::{Connection, PgConnection};
+
+/// How far back we look for new submissions. The job is run daily, the extra day makes sure that we don't miss submissions if a run fails.
+const NEW_SUBMISSIONS_LOOKBACK_DAYS: i64 = 2;
+
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv().ok();
+    setup_tracing()?;
+    let database_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let similarity_threshold = match env::var("PLAGIARISM_SIMILARITY_THRESHOLD") {
+        Ok(threshold) => threshold.parse()?,
+        Err(_) => plagiarism_detection::DEFAULT_SIMILARITY_THRESHOLD,
+    };
+    let mut conn = PgConnection::connect(&database_url).await?;
+    let since = Utc::now() - Duration::days(NEW_SUBMISSIONS_LOOKBACK_DAYS);
+    let exercises_and_course_instances =
+        models::exercise_slide_submissions::get_exercise_and_course_instance_ids_with_submissions_since(
+            &mut conn, since,
+        )
+        .await?;
+    info!(
+        ?similarity_threshold,
+        "Detecting similar submissions in {} exercises that have received submissions since {:?}",
+        exercises_and_course_instances.len(),
+        since
+    );
+    let mut success = 0;
+    let mut similar_pairs = 0;
+    for ids in exercises_and_course_instances.iter() {
+        // Each exercise is compared separately so that one failure doesn't stop the whole job
+        match plagiarism_detection::detect_similar_submissions(
+            &mut conn,
+            ids.exercise_id,
+            ids.course_instance_id,
+            similarity_threshold,
+        )
+        .await
+        {
+            Ok(stored) => {
+                if stored > 0 {
+                    info!(exercise_id = ?ids.exercise_id, course_instance_id = ?ids.course_instance_id, "Found {} similar submission pairs", stored);
+                }
+                similar_pairs += stored;
+                success += 1;
+            }
+            Err(err) => {
+                error!(exercise_id = ?ids.exercise_id, course_instance_id = ?ids.course_instance_id, "Failed to detect similar submissions: {:#?}", err);
+            }
+        }
+    }
+    info!(
+        "Exercises processed. Succeeded: {}, failed: {}. Similar submission pairs found: {}.",
+        success,
+        exercises_and_course_instances.len() - success,
+        similar_pairs
+    );
+    Ok(())
+}
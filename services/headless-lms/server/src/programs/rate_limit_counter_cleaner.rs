@@ -0,0 +1,19 @@
+use std::env;
+
+use crate::setup_tracing;
+use dotenv::dotenv;
+use headless_lms_models as models;
+use sqlx::{Connection, PgConnection};
+
+/// Deletes the rate limit counters whose time window has ended so that the table does not keep growing with every new IP address.
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv().ok();
+    setup_tracing()?;
+    let database_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let mut conn = PgConnection::connect(&database_url).await?;
+    let deleted = models::rate_limits::delete_ended_counters(&mut conn).await?;
+    info!("Deleted {} ended rate limit counters", deleted);
+    Ok(())
+}
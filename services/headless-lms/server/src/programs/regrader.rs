@@ -4,7 +4,7 @@ use headless_lms_models as models;
 use models::library::regrading;
 use sqlx::{Connection, PgConnection};
 
-use crate::domain::models_requests;
+use crate::domain::{metrics, models_requests};
 
 /**
 Starts a thread that will periodically send regrading submissions to the corresponding exercise services for regrading.
@@ -16,10 +16,16 @@ pub async fn main() -> anyhow::Result<()> {
     let db_url = env::var("DATABASE_URL")
         .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
 
+    metrics::serve()?;
+
     let mut interval = tokio::time::interval(Duration::from_secs(10));
     loop {
         interval.tick().await;
         let mut conn = PgConnection::connect(&db_url).await?;
+        match models::regradings::get_uncompleted_count(&mut conn).await {
+            Ok(count) => metrics::REGRADER_UNCOMPLETED_REGRADINGS.set(count),
+            Err(err) => tracing::error!("Failed to count uncompleted regradings: {}", err),
+        }
         let exercise_services_by_type =
             models::exercise_service_info::get_all_exercise_services_by_type(
                 &mut conn,
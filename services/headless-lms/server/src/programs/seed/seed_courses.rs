@@ -372,6 +372,7 @@ pub async fn seed_sample_course(
                 paragraph("Like this.", block_id_5),
                 paragraph(&"At vero eos et accusamus et iusto odio dignissimos ducimus qui blanditiis praesentium voluptatum deleniti atque corrupti quos dolores et quas molestias excepturi sint occaecati cupiditate non provident, similique sunt in culpa qui officia deserunt mollitia animi, id est laborum et dolorum fuga. Et harum quidem rerum facilis est et expedita distinctio. Nam libero tempore, cum soluta nobis est eligendi optio cumque nihil impedit quo minus id quod maxime placeat facere possimus, omnis voluptas assumenda est, omnis dolor repellendus. Temporibus autem quibusdam et aut officiis debitis aut rerum necessitatibus saepe eveniet ut et voluptates repudiandae sint et molestiae non recusandae. Itaque earum rerum hic tenetur a sapiente delectus, ut aut reiciendis voluptatibus maiores alias consequatur aut perferendis doloribus asperiores repellat. ".repeat(4), block_id_6),
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -459,6 +460,7 @@ pub async fn seed_sample_course(
                 exercise_block_3,
                 exercise_block_4,
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -936,6 +938,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_1,
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -960,6 +963,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_2
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -984,6 +988,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_3
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1008,6 +1013,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_4
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1032,6 +1038,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_5,
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1056,6 +1063,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_7
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1080,6 +1088,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_6
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1104,6 +1113,7 @@ pub async fn seed_sample_course(
                 ),
                 quizzes_exercise_block_8
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1208,6 +1218,7 @@ pub async fn seed_sample_course(
                 ),
                 multi_exercise_block_1
             ]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1246,6 +1257,7 @@ pub async fn seed_sample_course(
             exercise_slides: vec![exercise_slide_5],
             exercise_tasks: vec![exercise_task_5],
             content: serde_json::json!([exercise_block_5]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1269,6 +1281,7 @@ pub async fn seed_sample_course(
                 attributes: attributes! {},
                 inner_blocks: vec![]
             }]),
+            base_history_id: None,
         },
         jwt_key,
     )
@@ -1621,6 +1634,7 @@ pub async fn create_glossary_course(
                 "Use the KB to write sentences for your CS-courses.",
                 Uuid::new_v5(&course.id, b"6903cf16-4f79-4985-a354-4257be1193a2")
             ),]),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -1948,6 +1962,7 @@ pub async fn seed_cs_course_material(
                 exercises: vec![],
                 exercise_slides: vec![],
                 exercise_tasks: vec![],
+                base_history_id: None,
             },
             retain_ids: true,
             history_change_reason: HistoryChangeReason::PageSaved,
@@ -2013,6 +2028,7 @@ pub async fn seed_cs_course_material(
                 exercises: vec![],
                 exercise_slides: vec![],
                 exercise_tasks: vec![],
+                base_history_id: None,
             },
             retain_ids: true,
             history_change_reason: HistoryChangeReason::PageSaved,
@@ -2078,6 +2094,7 @@ pub async fn seed_cs_course_material(
             )
             .with_id(Uuid::parse_str("0d47c02a-194e-42a4-927e-fb29a4fda39c")?),
         ]),
+        base_history_id: None,
     };
     create_page(
         &mut conn,
@@ -2125,6 +2142,7 @@ pub async fn seed_cs_course_material(
             )
             .with_id(Uuid::parse_str("c96f56d5-ea35-4aae-918a-72a36847a49c")?),
         ]),
+        base_history_id: None,
     };
     create_page(
         &mut conn,
@@ -2184,6 +2202,7 @@ pub async fn seed_cs_course_material(
                 exercises: vec![],
                 exercise_slides: vec![],
                 exercise_tasks: vec![],
+                base_history_id: None,
             },
             retain_ids: true,
             history_change_reason: HistoryChangeReason::PageSaved,
@@ -2229,6 +2248,7 @@ pub async fn seed_cs_course_material(
         exercise_tasks: vec![],
         url_path: "/chapter-2/user-research".to_string(),
         title: "User research".to_string(),
+        base_history_id: None,
     };
     create_page(
         &mut conn,
@@ -2255,6 +2275,7 @@ pub async fn seed_cs_course_material(
             url_path: "/chapter-2/content-rendering".to_string(),
             title: "Content rendering".to_string(),
             chapter_id: Some(chapter_2.id),
+            base_history_id: None,
         },
         Arc::clone(&jwt_key),
     )
@@ -2281,6 +2302,7 @@ pub async fn seed_cs_course_material(
                 ),
                 quizzes_exercise_block_5,
             ]),
+            base_history_id: None,
         },
         jwt_key.clone(),
     )
@@ -2305,6 +2327,7 @@ pub async fn seed_cs_course_material(
                 ),
                 quizzes_exercise_block_6,
             ]),
+            base_history_id: None,
         },
         jwt_key.clone(),
     )
@@ -2330,6 +2353,7 @@ pub async fn seed_cs_course_material(
                 ),
                 quizzes_exercise_block_7,
             ]),
+            base_history_id: None,
         },
         jwt_key.clone(),
     )
@@ -2354,6 +2378,7 @@ pub async fn seed_cs_course_material(
                 ),
                 quizzes_exercise_block_8,
             ]),
+            base_history_id: None,
         },
         jwt_key,
     )
@@ -66,6 +66,7 @@ pub async fn create_page(
                 url_path: page_data.url_path,
                 title: page_data.title,
                 chapter_id,
+                base_history_id: None,
             },
             retain_ids: true,
             history_change_reason: HistoryChangeReason::PageSaved,
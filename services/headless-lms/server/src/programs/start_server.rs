@@ -1,6 +1,9 @@
 use std::{env, sync::Arc};
 
-use crate::{domain::models_requests::JwtKey, setup_tracing, OAuthClient};
+use crate::{
+    domain::{lti::LtiToolKey, models_requests::JwtKey},
+    setup_tracing, OAuthClient,
+};
 use actix_session::{
     config::{CookieContentSecurity, PersistentSession, SessionLifecycle, TtlExtensionPolicy},
     storage::CookieSessionStore,
@@ -41,6 +44,7 @@ pub async fn main() -> anyhow::Result<()> {
     let test_mode = env::var("TEST_MODE").is_ok();
     let allow_no_https_for_development = env::var("ALLOW_NO_HTTPS_FOR_DEVELOPMENT").is_ok();
     let jwt_key = JwtKey::try_from_env().expect("Failed to create JwtKey");
+    let lti_key = LtiToolKey::try_from_env().expect("Failed to create LtiToolKey");
 
     if test_mode {
         info!("***********************************");
@@ -79,9 +83,12 @@ pub async fn main() -> anyhow::Result<()> {
         };
         let file_store = setup_file_store();
         let jwt_key_clone = jwt_key.clone();
+        let lti_key_clone = lti_key.clone();
 
         App::new()
-            .configure(move |config| crate::configure(config, file_store, app_conf, jwt_key_clone))
+            .configure(move |config| {
+                crate::configure(config, file_store, app_conf, jwt_key_clone, lti_key_clone)
+            })
             .wrap(
                 SessionMiddleware::builder(
                     CookieSessionStore::default(),
@@ -89,7 +96,7 @@ pub async fn main() -> anyhow::Result<()> {
                 )
                 .cookie_name("session".to_string())
                 .cookie_secure(!allow_no_https_for_development)
-                .cookie_same_site(SameSite::Strict) // Default api can only be accessed from the main website. Public api will be less strict on this.
+                .cookie_same_site(SameSite::Strict) // Default api can only be accessed from the main website. Public api will be less strict on this. LTI launches are opened in a new window because of this, see domain::lti.
                 .cookie_http_only(true) // Cookie is inaccessible from javascript for security
                 .cookie_path("/api".to_string()) // browser won't send the cookie unless this path exists in the request url
                 .cookie_content_security(CookieContentSecurity::Private)
@@ -129,7 +136,7 @@ pub async fn main() -> anyhow::Result<()> {
 Setups file store so that it can be passed to actix web as data.
 Using Arc here so that this can be accessed from all the different worker threads.
 */
-fn setup_file_store() -> Arc<dyn FileStore> {
+pub fn setup_file_store() -> Arc<dyn FileStore> {
     if env::var("FILE_STORE_USE_GOOGLE_CLOUD_STORAGE").is_ok() {
         info!("Using Google Cloud Storage as the file store");
         let bucket_name = env::var("GOOGLE_CLOUD_STORAGE_BUCKET_NAME").expect("env FILE_STORE_USE_GOOGLE_CLOUD_STORAGE was defined but GOOGLE_CLOUD_STORAGE_BUCKET_NAME was not.");
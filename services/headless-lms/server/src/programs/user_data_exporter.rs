@@ -0,0 +1,81 @@
+use std::env;
+
+use crate::{domain::user_data_export, programs::start_server::setup_file_store, setup_tracing};
+use chrono::{Duration, Utc};
+use dotenv::dotenv;
+use headless_lms_models as models;
+use headless_lms_utils::file_store::{file_utils, user_data_export_path};
+use sqlx::{Connection, PgConnection};
+use uuid::Uuid;
+
+/**
+Generates the user data exports that users have requested and deletes the expired ones.
+
+Can also be used for exporting the data of a single user to a local file: `user-data-exporter <user_id> <output.zip>`.
+*/
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv().ok();
+    setup_tracing()?;
+    let database_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let mut conn = PgConnection::connect(&database_url).await?;
+
+    if let Some(user_id) = env::args().nth(2) {
+        let user_id: Uuid = user_id.parse()?;
+        let output = env::args()
+            .nth(3)
+            .unwrap_or_else(|| format!("{}.zip", user_id));
+        let zip = user_data_export::create_zip(&mut conn, user_id).await?;
+        std::fs::write(&output, zip)?;
+        info!("Exported the data of user {} to {}", user_id, output);
+        return Ok(());
+    }
+
+    let file_store = setup_file_store();
+
+    let expired = models::user_data_exports::get_expired(&mut conn).await?;
+    for export in expired {
+        let path = std::path::PathBuf::from(&export.file_path);
+        // a missing file should not prevent deleting the row
+        if let Err(err) = file_store.delete(&path).await {
+            warn!(id = ?export.id, "Failed to delete expired user data export file: {:#?}", err);
+        }
+        models::user_data_exports::delete(&mut conn, export.id).await?;
+    }
+
+    let pending = models::user_data_exports::get_pending(&mut conn).await?;
+    info!("Generating {} user data exports", pending.len());
+    let mut success = 0;
+    for export in pending.iter() {
+        let path = user_data_export_path(
+            export.user_id,
+            &format!("{}.zip", file_utils::random_filename()),
+        );
+        let res = async {
+            let zip = user_data_export::create_zip(&mut conn, export.user_id).await?;
+            file_store.upload(&path, zip, "application/zip").await?;
+            models::user_data_exports::mark_completed(
+                &mut conn,
+                export.id,
+                &path.to_string_lossy(),
+                Utc::now() + Duration::days(user_data_export::EXPORT_VALID_DAYS),
+            )
+            .await?;
+            anyhow::Ok(())
+        }
+        .await;
+        match res {
+            Ok(()) => success += 1,
+            Err(err) => {
+                error!(id = ?export.id, user_id = ?export.user_id, "Failed to generate user data export: {:#?}", err);
+            }
+        }
+    }
+    info!(
+        "User data exports generated. Succeeded: {}, failed: {}.",
+        success,
+        pending.len() - success
+    );
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::{env, time::Duration};
+
+use headless_lms_models as models;
+use sqlx::{Connection, PgConnection};
+
+use crate::domain::{metrics, webhooks};
+
+const BATCH_SIZE: i64 = 100;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/**
+Starts a thread that will periodically send pending course events to the webhooks subscribed to them.
+*/
+pub async fn main() -> anyhow::Result<()> {
+    env::set_var("RUST_LOG", "info,actix_web=info,sqlx=warn");
+    dotenv::dotenv().ok();
+    crate::setup_tracing()?;
+    let db_url = env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "postgres://localhost/headless_lms_dev".to_string());
+    let sender = webhooks::WebhookSender::new(REQUEST_TIMEOUT);
+
+    metrics::serve()?;
+
+    let mut interval = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        interval.tick().await;
+        let mut conn = PgConnection::connect(&db_url).await?;
+        // do not stop the thread on error, report it and try again next tick
+        if let Err(err) = deliver_pending(&mut conn, &sender).await {
+            tracing::error!("Error in webhook deliverer: {}", err);
+        }
+    }
+}
+
+async fn deliver_pending(
+    conn: &mut PgConnection,
+    sender: &webhooks::WebhookSender,
+) -> anyhow::Result<()> {
+    let deliveries = models::webhook_deliveries::get_pending(conn, BATCH_SIZE).await?;
+    if !deliveries.is_empty() {
+        tracing::info!("Delivering {} webhook events.", deliveries.len());
+    }
+    for delivery in deliveries {
+        match webhooks::deliver(conn, sender, &delivery).await {
+            Ok(true) => {
+                metrics::WEBHOOK_DELIVERIES_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+            }
+            Ok(false) => {
+                metrics::WEBHOOK_DELIVERIES_TOTAL
+                    .with_label_values(&["failure"])
+                    .inc();
+                tracing::warn!(
+                    "Webhook delivery {} to {} failed, it will be retried if it has attempts left.",
+                    delivery.id,
+                    delivery.url
+                );
+            }
+            Err(err) => {
+                metrics::WEBHOOK_DELIVERIES_TOTAL
+                    .with_label_values(&["error"])
+                    .inc();
+                tracing::error!("Failed to deliver webhook {}: {:#?}", delivery.id, err);
+            }
+        }
+    }
+    Ok(())
+}
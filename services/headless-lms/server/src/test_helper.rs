@@ -1,5 +1,5 @@
 use crate::setup_tracing;
-use sqlx::{Connection, PgConnection, Postgres, Transaction};
+use sqlx::{Connection, PgConnection, PgPool, Postgres, Transaction};
 use std::env;
 use tokio::sync::Mutex;
 
@@ -24,6 +24,12 @@ async fn get_or_init_db() -> String {
     db
 }
 
+/// A connection pool to the test database for tests that run requests through the middlewares, which acquire their own connections. The connections are not in a transaction, so the pool should only be used for reading.
+pub async fn init_pool() -> PgPool {
+    let db = get_or_init_db().await;
+    PgPool::connect(&db).await.expect("failed to connect to db")
+}
+
 /// Wrapper to ensure the test database isn't used without a transaction
 pub struct Conn(PgConnection);
 
@@ -29,9 +29,11 @@ fn domain(target: &mut File) {
 
         authorization::Action,
         authorization::ActionOnResource,
+        authorization::Impersonation,
         authorization::Resource,
         error::ErrorData,
         error::ErrorResponse,
+        grade_export::GradeExportFormat,
         models_requests::SpecRequest,
     };
 }
@@ -42,6 +44,14 @@ fn models(target: &mut File) {
     export! {
         target,
 
+        api_tokens::ApiToken,
+        api_tokens::ApiTokenScope,
+        api_tokens::NewApiToken,
+
+        audit_log_entries::AuditLogAction,
+        audit_log_entries::AuditLogEntry,
+        audit_log_entries::AuditLogFilter,
+
         chapters::Chapter,
         chapters::ChapterStatus,
         chapters::ChapterUpdate,
@@ -49,6 +59,12 @@ fn models(target: &mut File) {
         chapters::DatabaseChapter,
         chapters::NewChapter,
         chapters::UserCourseInstanceChapterProgress,
+
+        content_lint_issues::ContentLintIssue,
+        content_lint_issues::ContentLintIssueType,
+        content_lint_issues::ContentLintRun,
+        content_lint_issues::CourseContentLintReport,
+
         course_instance_enrollments::CourseInstanceEnrollment,
         course_instances::ChapterScore,
         course_instances::CourseInstance,
@@ -78,6 +94,10 @@ fn models(target: &mut File) {
         courses::CourseStructure,
         courses::CourseUpdate,
         courses::NewCourse,
+        custom_roles::CustomRole,
+        custom_roles::CustomRoleUpdate,
+        custom_roles::CustomRoleWithPermissions,
+        custom_roles::NewCustomRole,
 
         email_templates::EmailTemplate,
         email_templates::EmailTemplateNew,
@@ -108,6 +128,9 @@ fn models(target: &mut File) {
         exercise_task_gradings::ExerciseTaskGrading,
         exercise_task_gradings::ExerciseTaskGradingResult,
         exercise_task_gradings::UserPointsUpdateStrategy,
+        exercise_task_submission_similarities::ExerciseTaskSubmissionSimilarity,
+        exercise_task_submission_similarities::ExerciseTaskSubmissionSimilarityInfo,
+        exercise_task_submission_similarities::SimilarSubmission,
         exercise_task_submissions::ExerciseTaskSubmission,
         exercise_task_submissions::PeerReviewsRecieved,
         exercise_tasks::CourseMaterialExerciseTask,
@@ -126,6 +149,9 @@ fn models(target: &mut File) {
         glossary::Term,
         glossary::TermUpdate,
 
+        identity_providers::IdentityProviderInfo,
+        identity_providers::NewIdentityProvider,
+
         library::grading::AnswerRequiringAttentionWithTasks,
         library::grading::AnswersRequiringAttention,
         library::grading::StudentExerciseSlideSubmission,
@@ -136,6 +162,13 @@ fn models(target: &mut File) {
         library::peer_reviewing::CourseMaterialPeerReviewDataAnswerToReview,
         library::peer_reviewing::CourseMaterialPeerReviewQuestionAnswer,
         library::peer_reviewing::CourseMaterialPeerReviewSubmission,
+        library::peer_reviewing::FlaggedPeerReview,
+        library::peer_reviewing::NewPeerReviewSubmissionFlag,
+        library::peer_reviewing::NewPeerReviewSubmissionRating,
+        library::peer_reviewing::PeerReviewCalibrationFeedback,
+        library::peer_reviewing::PeerReviewCalibrationQuestionFeedback,
+        library::peer_reviewing::PeerReviewCalibrationSampleInfo,
+        library::peer_reviewing::ResolvePeerReviewSubmissionFlag,
         library::progressing::CompletionRegistrationLink,
         library::progressing::CourseInstanceCompletionSummary,
         library::progressing::ManualCompletionPreview,
@@ -147,13 +180,24 @@ fn models(target: &mut File) {
         library::progressing::UserModuleCompletionStatus,
         library::progressing::UserWithModuleCompletions,
 
+        lti_registrations::LtiRegistration,
+        lti_registrations::NewLtiRegistration,
+
         material_references::MaterialReference,
         material_references::NewMaterialReference,
 
         organizations::Organization,
 
+        page_drafts::PageDraft,
+        page_drafts::PageDraftUpdate,
+
+        page_edit_locks::PageEditLock,
+        page_edit_locks::PageEditStatus,
+        page_edit_locks::PageEditor,
+
         page_history::HistoryChangeReason,
         page_history::PageHistory,
+        page_history::PageHistoryDiff,
         pages::CmsPageExercise,
         pages::CmsPageExerciseSlide,
         pages::CmsPageExerciseTask,
@@ -168,22 +212,37 @@ fn models(target: &mut File) {
         pages::PageChapterAndCourseInformation,
         pages::PageInfo,
         pages::PageNavigationInformation,
+        pages::PagePublishingSchedule,
         pages::PageRoutingData,
+        pages::PageSaveConflict,
         pages::PageSearchRequest,
         pages::PageSearchResult,
         pages::PageWithExercises,
+        peer_review_calibration_reference_answers::NewPeerReviewCalibrationReferenceAnswer,
+        peer_review_calibration_reference_answers::PeerReviewCalibrationReferenceAnswer,
+        peer_review_calibration_samples::PeerReviewCalibrationSample,
+        peer_review_calibration_samples::PeerReviewCalibrationSampleWithReferenceAnswers,
         peer_review_configs::CourseMaterialPeerReviewConfig,
         peer_review_configs::CmsPeerReviewConfig,
         peer_review_configs::CmsPeerReviewConfiguration,
         peer_review_configs::PeerReviewAcceptingStrategy,
         peer_review_configs::PeerReviewConfig,
+        peer_review_configs::PeerReviewTimeoutAction,
         peer_review_questions::CmsPeerReviewQuestion,
         peer_review_questions::PeerReviewQuestion,
         peer_review_questions::PeerReviewQuestionType,
+        peer_review_questions::PeerReviewRubric,
+        peer_review_questions::PeerReviewRubricCriterion,
+        peer_review_questions::PeerReviewRubricLevel,
         peer_review_question_submissions::PeerReviewAnswer,
         peer_review_question_submissions::PeerReviewQuestionAndAnswer,
         peer_review_question_submissions::PeerReviewQuestionSubmission,
+        peer_review_question_submissions::PeerReviewRubricSelectedLevel,
         peer_review_question_submissions::PeerReviewWithQuestionsAndAnswers,
+        peer_review_submission_flags::PeerReviewSubmissionFlag,
+        peer_review_submission_flags::PeerReviewSubmissionFlagResolution,
+        peer_review_submission_ratings::PeerReviewSubmissionRating,
+        peer_review_submissions::PeerReviewReviewerStatistics,
         pending_roles::PendingRole,
         playground_examples::PlaygroundExample,
         playground_examples::PlaygroundExampleData,
@@ -202,6 +261,13 @@ fn models(target: &mut File) {
         regradings::RegradingInfo,
         regradings::RegradingSubmissionInfo,
         repository_exercises::RepositoryExercise,
+        role_permissions::NewRolePermission,
+        role_permissions::PermissionAction,
+        role_permissions::PermissionResourceType,
+        role_permissions::RolePermission,
+        role_requests::NewRoleRequest,
+        role_requests::RoleRequest,
+        role_requests::RoleRequestStatus,
         roles::RoleDomain,
         roles::RoleInfo,
         roles::RoleUser,
@@ -211,13 +277,26 @@ fn models(target: &mut File) {
         teacher_grading_decisions::TeacherDecisionType,
         teacher_grading_decisions::TeacherGradingDecision,
 
+        url_redirections::NewUrlRedirection,
+        url_redirections::UrlRedirection,
+        url_redirections::UrlRedirectionProblem,
+        url_redirections::UrlRedirectionResolution,
+        url_redirections::UrlRedirectionWithResolution,
+
         user_course_settings::UserCourseSettings,
+        user_data_exports::UserDataExport,
         user_exercise_states::ExerciseUserCounts,
         user_exercise_states::ReviewingStage,
         user_exercise_states::UserCourseInstanceChapterExerciseProgress,
         user_exercise_states::UserCourseInstanceProgress,
         user_exercise_states::UserExerciseState,
         users::User,
+        webhook_deliveries::WebhookDelivery,
+        webhook_deliveries::WebhookDeliveryStatus,
+        webhook_delivery_attempts::WebhookDeliveryAttempt,
+        webhook_subscriptions::NewWebhookSubscription,
+        webhook_subscriptions::WebhookEventType,
+        webhook_subscriptions::WebhookSubscription,
 
         user_course_instance_exercise_service_variables::UserCourseInstanceExerciseServiceVariable,
     };
@@ -259,17 +338,34 @@ fn controllers(target: &mut File) {
         };
     }
 
+    // lti
+    {
+        use lti::*;
+        export! {
+            target,
+
+            LtiDeepLinkingChapter,
+            LtiDeepLinkingCourse,
+            LtiDeepLinkingPage,
+            LtiDeepLinkingResponse,
+            LtiDeepLinkingSelection,
+        };
+    }
+
     // main_frontend
     {
         use main_frontend::*;
         export! {
             target,
 
+            api_tokens::CreatedApiToken,
             courses::GetFeedbackQuery,
             exams::ExamCourseInfo,
             exercise_repositories::NewExerciseRepository,
             exercises::ExerciseSubmissions,
             feedback::MarkAsRead,
+            impersonation::NewImpersonation,
+            pages::PageHistoryDiffQuery,
             proposed_edits::GetEditProposalsQuery,
             roles::RoleQuery,
         };
@@ -282,6 +378,12 @@ fn utils(target: &mut File) {
     export! {
         target,
 
+        content_diff::BlockChange,
+        content_diff::ChangeKind,
+        content_diff::ItemChange,
+        content_diff::TextDiffPart,
+        content_diff::TextDiffPartKind,
+        content_diff::ValueChange,
         pagination::Pagination,
         url_to_oembed_endpoint::OEmbedResponse,
     };
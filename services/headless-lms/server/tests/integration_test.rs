@@ -7,7 +7,10 @@ use headless_lms_models::{
     organizations::{self, Organization},
     PKeyPolicy,
 };
-use headless_lms_server::{domain::models_requests::JwtKey, setup_tracing};
+use headless_lms_server::{
+    domain::{lti::LtiToolKey, models_requests::JwtKey},
+    setup_tracing,
+};
 use headless_lms_utils::{file_store::local_file_store::LocalFileStore, ApplicationConfiguration};
 use sqlx::{migrate::MigrateDatabase, Connection, PgConnection, PgPool, Postgres};
 use tokio::sync::Mutex;
@@ -56,6 +59,11 @@ pub fn make_jwt_key() -> JwtKey {
     JwtKey::new(test_jwt_key).unwrap()
 }
 
+pub fn make_lti_key() -> LtiToolKey {
+    // the private key is intentionally public, it is only used in tests
+    LtiToolKey::new(include_bytes!("data/oidc-test-private-key.pem")).unwrap()
+}
+
 /// Initialises the actix server for testing
 pub async fn init_actix() -> (
     impl actix_web::dev::Service<Request, Response = ServiceResponse<BoxBody>, Error = actix_web::Error>,
@@ -77,9 +85,10 @@ pub async fn init_actix() -> (
         development_uuid_login: false,
     };
     let jwt_key = make_jwt_key();
+    let lti_key = make_lti_key();
     let app = App::new()
         .configure(move |config| {
-            headless_lms_server::configure(config, file_store, app_conf, jwt_key)
+            headless_lms_server::configure(config, file_store, app_conf, jwt_key, Some(lti_key))
         })
         .wrap(
             SessionMiddleware::builder(
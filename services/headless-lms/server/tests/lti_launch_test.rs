@@ -0,0 +1,379 @@
+use std::sync::Arc;
+
+use actix_http::{body::BoxBody, Request, StatusCode};
+use actix_web::{cookie::Cookie, dev::ServiceResponse, test};
+use chrono::{Duration, Utc};
+use headless_lms_models::{
+    courses::NewCourse,
+    library::content_management::{self, CreateNewCourseFixedIds},
+    lti_registrations::{self, LtiRegistration, NewLtiRegistration},
+    lti_users, organizations, users, PKeyPolicy,
+};
+use headless_lms_server::domain::models_requests;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{json, Value};
+use url::Url;
+use uuid::Uuid;
+
+mod integration_test;
+
+// A key pair used only in tests, the private key is intentionally public.
+const TEST_PRIVATE_KEY: &[u8] = include_bytes!("data/oidc-test-private-key.pem");
+const TEST_KEY_MODULUS: &str = include_str!("data/oidc-test-public-key-modulus.txt");
+const CLIENT_ID: &str = "project-331-integration-test";
+
+#[actix_web::test]
+async fn launches_course_from_lti_platform() {
+    let (actix, pool) = integration_test::init_actix().await;
+    let mut conn = pool.acquire().await.unwrap();
+    let issuer = mockito::server_url();
+    let _jwks_mock = mockito::mock("GET", "/lti/certs")
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "keys": [{
+                    "kty": "RSA",
+                    "kid": "test-key",
+                    "use": "sig",
+                    "n": TEST_KEY_MODULUS.trim(),
+                    "e": "AQAB",
+                }]
+            })
+            .to_string(),
+        )
+        .create();
+
+    let teacher_id = users::insert(
+        &mut conn,
+        PKeyPolicy::Generate,
+        "teacher@example.com",
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let organization_id = organizations::insert(
+        &mut conn,
+        PKeyPolicy::Fixed(Uuid::parse_str("5a3b8f8e-5f8a-4c34-9d8e-2f4b6fd0b6a1").unwrap()),
+        "Partner organization",
+        "lti-organization",
+        "Embeds its courses in a learning platform",
+    )
+    .await
+    .unwrap();
+    let jwt_key = Arc::new(integration_test::make_jwt_key());
+    let (_course, front_page, _instance, _module) = content_management::create_new_course(
+        &mut conn,
+        PKeyPolicy::Fixed(CreateNewCourseFixedIds {
+            course_id: Uuid::parse_str("b8d2f4a5-3c6e-4f1b-8a7d-9e0c1b2a3d4e").unwrap(),
+            default_course_instance_id: Uuid::parse_str("c9e3a5b6-4d7f-4a2c-9b8e-0f1d2c3b4e5f")
+                .unwrap(),
+        }),
+        NewCourse {
+            name: "LTI course".to_string(),
+            slug: "lti-course".to_string(),
+            organization_id,
+            language_code: "en-US".to_string(),
+            teacher_in_charge_name: "Teacher".to_string(),
+            teacher_in_charge_email: "teacher@example.com".to_string(),
+            description: "".to_string(),
+            is_draft: false,
+            is_test_mode: false,
+        },
+        teacher_id,
+        models_requests::make_spec_fetcher(Arc::clone(&jwt_key)),
+        models_requests::fetch_service_info,
+    )
+    .await
+    .unwrap();
+    let registration = lti_registrations::insert(
+        &mut conn,
+        PKeyPolicy::Generate,
+        organization_id,
+        &NewLtiRegistration {
+            name: "Partner Moodle".to_string(),
+            issuer: issuer.clone(),
+            client_id: CLIENT_ID.to_string(),
+            auth_login_url: format!("{}/lti/auth", issuer),
+            auth_token_url: format!("{}/lti/token", issuer),
+            jwks_url: format!("{}/lti/certs", issuer),
+            link_existing_users_by_email: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    // A launch in an iframe is restarted in a new window, because the session cookie is not sent to iframes on other sites
+    let req = test::TestRequest::with_uri(&format!(
+        "/api/v0/lti/login?iss={}&login_hint=moodle-user-1&client_id={}",
+        issuer, CLIENT_ID
+    ))
+    .insert_header(("sec-fetch-dest", "iframe"))
+    .peer_addr("192.0.2.1:12345".parse().unwrap())
+    .to_request();
+    let res = test::call_service(&actix, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    assert!(body.contains(r#"target="_blank""#));
+    assert!(body.contains("/api/v0/lti/login?iss="));
+
+    // A new user is created, enrolled and redirected to the linked page
+    let (state, nonce) = initiate_login(&actix, &registration).await;
+    let res = launch(
+        &actix,
+        &registration,
+        &state,
+        &nonce,
+        &front_page.id.to_string(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        res.headers().get("location").unwrap(),
+        "/org/lti-organization/courses/lti-course/"
+    );
+    let session = session_cookie(&res).unwrap();
+    let user = users::try_get_by_email(&mut conn, "student@moodle.example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.first_name.as_deref(), Some("Example"));
+    assert_eq!(logged_in_user_id(&actix, session).await, Some(user.id));
+    assert_eq!(
+        lti_users::try_get_user_id_by_subject(&mut conn, registration.id, "moodle-user-1")
+            .await
+            .unwrap(),
+        Some(user.id)
+    );
+
+    // The same launch cannot be replayed
+    let res = launch(
+        &actix,
+        &registration,
+        &state,
+        &nonce,
+        &front_page.id.to_string(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // A launch with a different nonce is rejected
+    let (state, _nonce) = initiate_login(&actix, &registration).await;
+    let res = launch(
+        &actix,
+        &registration,
+        &state,
+        "wrong-nonce",
+        &front_page.id.to_string(),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // A deep linking launch can only be continued in the session of the teacher
+    let (state, nonce) = initiate_login(&actix, &registration).await;
+    let res = deep_linking_launch(&actix, &registration, &state, &nonce).await;
+    assert_eq!(res.status(), StatusCode::SEE_OTHER);
+    let location = res.headers().get("location").unwrap().to_str().unwrap();
+    let request_id = location.strip_prefix("/lti/deep-linking/").unwrap();
+    let session = session_cookie(&res).unwrap();
+    let content_uri = format!("/api/v0/lti/deep-linking/{}", request_id);
+    let req = test::TestRequest::with_uri(&content_uri).to_request();
+    let res = test::call_service(&actix, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    let req = test::TestRequest::with_uri(&content_uri)
+        .cookie(session.clone())
+        .to_request();
+    let res = test::call_service(&actix, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // Hidden pages cannot be linked
+    sqlx::query("UPDATE pages SET hidden = TRUE WHERE id = $1")
+        .bind(front_page.id)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    let selection = json!({ "page_id": front_page.id, "chapter_id": null });
+    let req = test::TestRequest::post()
+        .uri(&content_uri)
+        .cookie(session.clone())
+        .set_json(&selection)
+        .to_request();
+    let res = test::call_service(&actix, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    sqlx::query("UPDATE pages SET hidden = FALSE WHERE id = $1")
+        .bind(front_page.id)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    let req = test::TestRequest::post()
+        .uri(&content_uri)
+        .set_json(&selection)
+        .to_request();
+    let res = test::call_service(&actix, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    let req = test::TestRequest::post()
+        .uri(&content_uri)
+        .cookie(session)
+        .set_json(&selection)
+        .to_request();
+    let res: Value = test::call_and_read_body_json(&actix, req).await;
+    assert_eq!(
+        res["deep_link_return_url"],
+        "https://moodle.example.com/deep-link"
+    );
+}
+
+/// Starts the launch like the platform would. Returns the state and the nonce the platform would include in the launch.
+async fn initiate_login(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    registration: &LtiRegistration,
+) -> (String, String) {
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("iss", &registration.issuer)
+        .append_pair("login_hint", "moodle-user-1")
+        .append_pair("client_id", &registration.client_id)
+        .finish();
+    let req = test::TestRequest::with_uri(&format!("/api/v0/lti/login?{}", query))
+        .peer_addr("192.0.2.1:12345".parse().unwrap())
+        .to_request();
+    let res = test::call_service(actix, req).await;
+    assert_eq!(res.status(), StatusCode::FOUND);
+    let authorization_url =
+        Url::parse(res.headers().get("location").unwrap().to_str().unwrap()).unwrap();
+    assert!(authorization_url
+        .as_str()
+        .starts_with(&registration.auth_login_url));
+    let query_parameter = |name: &str| {
+        authorization_url
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.to_string())
+            .unwrap()
+    };
+    assert_eq!(
+        query_parameter("redirect_uri"),
+        "http://project-331.local/api/v0/lti/launch"
+    );
+    assert_eq!(query_parameter("login_hint"), "moodle-user-1");
+    (query_parameter("state"), query_parameter("nonce"))
+}
+
+/// Posts a resource link launch message linking to the given page, signed with the test key of the platform.
+async fn launch(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    registration: &LtiRegistration,
+    state: &str,
+    nonce: &str,
+    page_id: &str,
+) -> ServiceResponse<BoxBody> {
+    let claims = json!({
+        "iss": registration.issuer,
+        "aud": registration.client_id,
+        "sub": "moodle-user-1",
+        "exp": (Utc::now() + Duration::minutes(5)).timestamp(),
+        "nonce": nonce,
+        "email": "student@moodle.example.com",
+        "given_name": "Example",
+        "family_name": "Student",
+        "https://purl.imsglobal.org/spec/lti/claim/message_type": "LtiResourceLinkRequest",
+        "https://purl.imsglobal.org/spec/lti/claim/version": "1.3.0",
+        "https://purl.imsglobal.org/spec/lti/claim/deployment_id": "1",
+        "https://purl.imsglobal.org/spec/lti/claim/resource_link": {
+            "id": "link-1",
+            "title": "LTI course",
+        },
+        "https://purl.imsglobal.org/spec/lti/claim/context": {
+            "id": "moodle-course-1",
+            "title": "Introduction to programming",
+        },
+        "https://purl.imsglobal.org/spec/lti/claim/custom": {
+            "page_id": page_id,
+        },
+    });
+    post_launch(actix, &claims, state).await
+}
+
+/// Posts a deep linking launch message, signed with the test key of the platform.
+async fn deep_linking_launch(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    registration: &LtiRegistration,
+    state: &str,
+    nonce: &str,
+) -> ServiceResponse<BoxBody> {
+    let claims = json!({
+        "iss": registration.issuer,
+        "aud": registration.client_id,
+        "sub": "moodle-teacher-1",
+        "exp": (Utc::now() + Duration::minutes(5)).timestamp(),
+        "nonce": nonce,
+        "https://purl.imsglobal.org/spec/lti/claim/message_type": "LtiDeepLinkingRequest",
+        "https://purl.imsglobal.org/spec/lti/claim/version": "1.3.0",
+        "https://purl.imsglobal.org/spec/lti/claim/deployment_id": "1",
+        "https://purl.imsglobal.org/spec/lti-dl/claim/deep_linking_settings": {
+            "deep_link_return_url": "https://moodle.example.com/deep-link",
+            "accept_types": ["ltiResourceLink"],
+        },
+    });
+    post_launch(actix, &claims, state).await
+}
+
+async fn post_launch(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    claims: &Value,
+    state: &str,
+) -> ServiceResponse<BoxBody> {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some("test-key".to_string());
+    let id_token = jsonwebtoken::encode(
+        &header,
+        claims,
+        &EncodingKey::from_rsa_pem(TEST_PRIVATE_KEY).unwrap(),
+    )
+    .unwrap();
+    let req = test::TestRequest::post()
+        .uri("/api/v0/lti/launch")
+        .peer_addr("192.0.2.1:12345".parse().unwrap())
+        .set_form(&[("id_token", id_token.as_str()), ("state", state)])
+        .to_request();
+    test::call_service(actix, req).await
+}
+
+fn session_cookie(res: &ServiceResponse<BoxBody>) -> Option<Cookie<'static>> {
+    res.response()
+        .cookies()
+        .find(|cookie| cookie.name() == "session")
+        .map(|cookie| cookie.into_owned())
+}
+
+async fn logged_in_user_id(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    session: Cookie<'static>,
+) -> Option<Uuid> {
+    let req = test::TestRequest::with_uri("/api/v0/auth/user-info")
+        .cookie(session)
+        .to_request();
+    let user_info: Value = test::call_and_read_body_json(actix, req).await;
+    user_info["user_id"]
+        .as_str()
+        .map(|user_id| Uuid::parse_str(user_id).unwrap())
+}
@@ -0,0 +1,293 @@
+use actix_http::{body::BoxBody, Request, StatusCode};
+use actix_web::{cookie::Cookie, dev::ServiceResponse, test};
+use chrono::{Duration, Utc};
+use headless_lms_models::{
+    identity_providers::{self, NewIdentityProvider},
+    organizations, user_identities, users, PKeyPolicy,
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use mockito::Mock;
+use serde_json::{json, Value};
+use url::Url;
+use uuid::Uuid;
+
+mod integration_test;
+
+// A key pair used only in tests, the private key is intentionally public.
+const TEST_PRIVATE_KEY: &[u8] = include_bytes!("data/oidc-test-private-key.pem");
+const TEST_KEY_MODULUS: &str = include_str!("data/oidc-test-public-key-modulus.txt");
+const CLIENT_ID: &str = "project-331-integration-test";
+
+#[actix_web::test]
+async fn logs_in_with_oidc_identity_provider() {
+    let (actix, pool) = integration_test::init_actix().await;
+    let mut conn = pool.acquire().await.unwrap();
+    let issuer = mockito::server_url();
+    let _discovery_mock = mockito::mock("GET", "/.well-known/openid-configuration")
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{}/authorize", issuer),
+                "token_endpoint": format!("{}/token", issuer),
+                "jwks_uri": format!("{}/jwks", issuer),
+            })
+            .to_string(),
+        )
+        .create();
+    let _jwks_mock = mockito::mock("GET", "/jwks")
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "keys": [{
+                    "kty": "RSA",
+                    "kid": "test-key",
+                    "use": "sig",
+                    "n": TEST_KEY_MODULUS.trim(),
+                    "e": "AQAB",
+                }]
+            })
+            .to_string(),
+        )
+        .create();
+
+    let organization_id = organizations::insert(
+        &mut conn,
+        PKeyPolicy::Fixed(Uuid::parse_str("7c3b3a3e-2f0e-4a53-9a39-3d0fbd2b3e41").unwrap()),
+        "Partner organization",
+        "partner-organization",
+        "Uses its own identity provider",
+    )
+    .await
+    .unwrap();
+    let identity_provider = identity_providers::insert(
+        &mut conn,
+        PKeyPolicy::Generate,
+        organization_id,
+        &NewIdentityProvider {
+            name: "Partner".to_string(),
+            issuer_url: issuer.clone(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: Some("integration-test-intentionally-public".to_string()),
+            scopes: "openid email profile".to_string(),
+            email_claim: "email".to_string(),
+            first_name_claim: "given_name".to_string(),
+            last_name_claim: "family_name".to_string(),
+            link_existing_users_by_email: false,
+        },
+    )
+    .await
+    .unwrap();
+    users::insert(
+        &mut conn,
+        PKeyPolicy::Generate,
+        "existing@partner.example.com",
+        Some("Existing"),
+        Some("User"),
+    )
+    .await
+    .unwrap();
+
+    // An identity is not linked to an existing account by email if the identity provider does not allow it, even when the email is verified
+    let (status, _session) = log_in(
+        &actix,
+        identity_provider.id,
+        &issuer,
+        json!({
+            "sub": "partner-user-1",
+            "email": "existing@partner.example.com",
+            "email_verified": true,
+        }),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        user_identities::try_get_user_id_by_subject(
+            &mut conn,
+            identity_provider.id,
+            "partner-user-1"
+        )
+        .await
+        .unwrap(),
+        None
+    );
+
+    // A new user is created when there's no user with the same email
+    let (status, session) = log_in(
+        &actix,
+        identity_provider.id,
+        &issuer,
+        json!({
+            "sub": "partner-user-2",
+            "email": "new@partner.example.com",
+            "email_verified": "true",
+            "given_name": "New",
+            "family_name": "User",
+        }),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FOUND);
+    let new_user = users::try_get_by_email(&mut conn, "new@partner.example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_user.first_name.as_deref(), Some("New"));
+    assert_eq!(new_user.last_name.as_deref(), Some("User"));
+    let session = session.unwrap();
+    assert_eq!(
+        logged_in_user_id(&actix, session.clone()).await,
+        Some(new_user.id)
+    );
+
+    // Linking an account requires logging in first
+    let req = test::TestRequest::with_uri(&format!(
+        "/api/v0/auth/oidc/{}/login?link=true",
+        identity_provider.id
+    ))
+    .to_request();
+    let res = test::call_service(&actix, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // A logged in user can explicitly link another identity to their account
+    let (status, session) = log_in(
+        &actix,
+        identity_provider.id,
+        &issuer,
+        json!({
+            "sub": "partner-user-3",
+            "email": "existing@partner.example.com",
+            "email_verified": true,
+        }),
+        Some(session),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FOUND);
+    assert_eq!(
+        logged_in_user_id(&actix, session.unwrap()).await,
+        Some(new_user.id)
+    );
+    assert_eq!(
+        user_identities::try_get_user_id_by_subject(
+            &mut conn,
+            identity_provider.id,
+            "partner-user-3"
+        )
+        .await
+        .unwrap(),
+        Some(new_user.id)
+    );
+}
+
+/// Goes through the login flow with the mock identity provider that returns an ID token with the given claims.
+///
+/// If a session of a logged in user is given, the identity is linked to the user.
+async fn log_in(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    identity_provider_id: Uuid,
+    issuer: &str,
+    claims: Value,
+    logged_in_session: Option<Cookie<'static>>,
+) -> (StatusCode, Option<Cookie<'static>>) {
+    let mut req = test::TestRequest::with_uri(&format!(
+        "/api/v0/auth/oidc/{}/login?return_to=%2Fcourses&link={}",
+        identity_provider_id,
+        logged_in_session.is_some()
+    ));
+    if let Some(logged_in_session) = logged_in_session {
+        req = req.cookie(logged_in_session);
+    }
+    let req = req.to_request();
+    let res = test::call_service(actix, req).await;
+    assert_eq!(res.status(), StatusCode::FOUND);
+    let session = session_cookie(&res).unwrap();
+    let authorization_url =
+        Url::parse(res.headers().get("location").unwrap().to_str().unwrap()).unwrap();
+    assert!(authorization_url.as_str().starts_with(issuer));
+    let query_parameter = |name: &str| {
+        authorization_url
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.to_string())
+            .unwrap()
+    };
+    assert_eq!(query_parameter("code_challenge_method"), "S256");
+
+    let mut claims = claims;
+    claims["iss"] = json!(issuer);
+    claims["aud"] = json!(CLIENT_ID);
+    claims["exp"] = json!((Utc::now() + Duration::minutes(5)).timestamp());
+    claims["nonce"] = json!(query_parameter("nonce"));
+    let token_mock = token_endpoint_mock(&claims);
+
+    let req = test::TestRequest::with_uri(&format!(
+        "/api/v0/auth/oidc/{}/callback?code=authorization-code&state={}",
+        identity_provider_id,
+        query_parameter("state")
+    ))
+    .cookie(session)
+    .to_request();
+    let res = test::call_service(actix, req).await;
+    token_mock.assert();
+    if res.status() == StatusCode::FOUND {
+        assert_eq!(res.headers().get("location").unwrap(), "/courses");
+    }
+    (res.status(), session_cookie(&res))
+}
+
+fn token_endpoint_mock(claims: &Value) -> Mock {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some("test-key".to_string());
+    let id_token = jsonwebtoken::encode(
+        &header,
+        claims,
+        &EncodingKey::from_rsa_pem(TEST_PRIVATE_KEY).unwrap(),
+    )
+    .unwrap();
+    mockito::mock("POST", "/token")
+        .match_body(mockito::Matcher::Regex(
+            "code=authorization-code.*code_verifier=|code_verifier=.*code=authorization-code"
+                .to_string(),
+        ))
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "access_token": "access-token",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+                "id_token": id_token,
+            })
+            .to_string(),
+        )
+        .expect(1)
+        .create()
+}
+
+fn session_cookie(res: &ServiceResponse<BoxBody>) -> Option<Cookie<'static>> {
+    res.response()
+        .cookies()
+        .find(|cookie| cookie.name() == "session")
+        .map(|cookie| cookie.into_owned())
+}
+
+async fn logged_in_user_id(
+    actix: &impl actix_web::dev::Service<
+        Request,
+        Response = ServiceResponse<BoxBody>,
+        Error = actix_web::Error,
+    >,
+    session: Cookie<'static>,
+) -> Option<Uuid> {
+    let req = test::TestRequest::with_uri("/api/v0/auth/user-info")
+        .cookie(session)
+        .to_request();
+    let user_info: Value = test::call_and_read_body_json(actix, req).await;
+    user_info["user_id"]
+        .as_str()
+        .map(|user_id| Uuid::parse_str(user_id).unwrap())
+}
@@ -0,0 +1,72 @@
+use actix_http::{body::BoxBody, Request, StatusCode};
+use actix_web::{dev::ServiceResponse, test};
+use headless_lms_models::rate_limits;
+use serde_json::json;
+
+mod integration_test;
+
+fn login_request(ip: &str) -> Request {
+    test::TestRequest::post()
+        .uri("/api/v0/auth/login")
+        .peer_addr(format!("{}:12345", ip).parse().unwrap())
+        .set_json(json!({ "email": "nobody@example.com", "password": "wrong" }))
+        .to_request()
+}
+
+fn header(res: &ServiceResponse<BoxBody>, name: &str) -> Option<String> {
+    res.headers()
+        .get(name)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[actix_web::test]
+async fn rate_limits_hold_across_server_instances() {
+    // two instances with their own connection pools that share the database, like two pods
+    let (first_instance, pool) = integration_test::init_actix().await;
+    let (second_instance, _) = integration_test::init_actix().await;
+    let mut conn = pool.acquire().await.unwrap();
+    sqlx::query("UPDATE rate_limit_policies SET deleted_at = now() WHERE route_group = 'login'")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    // a long window so that the test doesn't start and end in different windows
+    rate_limits::insert_policy(&mut conn, "login", 365 * 24 * 60 * 60, 3)
+        .await
+        .unwrap();
+
+    let res = test::call_service(&first_instance, login_request("192.0.2.1")).await;
+    assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&res, "x-ratelimit-limit").as_deref(), Some("3"));
+    assert_eq!(header(&res, "x-ratelimit-remaining").as_deref(), Some("2"));
+    assert!(header(&res, "x-ratelimit-reset").is_some());
+
+    let res = test::call_service(&second_instance, login_request("192.0.2.1")).await;
+    assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&res, "x-ratelimit-remaining").as_deref(), Some("1"));
+
+    let res = test::call_service(&first_instance, login_request("192.0.2.1")).await;
+    assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&res, "x-ratelimit-remaining").as_deref(), Some("0"));
+
+    // the limit has been used up on the first instance and the second instance knows it
+    let res = test::call_service(&second_instance, login_request("192.0.2.1")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&res, "x-ratelimit-remaining").as_deref(), Some("0"));
+    assert!(header(&res, "retry-after").is_some());
+    let res = test::call_service(&first_instance, login_request("192.0.2.1")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // other clients are limited separately
+    let res = test::call_service(&second_instance, login_request("192.0.2.2")).await;
+    assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&res, "x-ratelimit-remaining").as_deref(), Some("2"));
+
+    // routes without policies are not limited
+    let req = test::TestRequest::get()
+        .uri("/api/v0/auth/identity-providers")
+        .peer_addr("192.0.2.1:12345".parse().unwrap())
+        .to_request();
+    let res = test::call_service(&first_instance, req).await;
+    assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(header(&res, "x-ratelimit-limit").is_none());
+}
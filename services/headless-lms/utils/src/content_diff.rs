@@ -0,0 +1,444 @@
+/*!
+Structured diffs between two versions of content, e.g. two revisions of a page.
+
+Blocks and other items are matched with their ids, so a diff tells which blocks were added, removed, moved or modified. Modified string values are diffed word by word so that small edits in long texts are easy to spot.
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+#[cfg(feature = "ts_rs")]
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::document_schema_processor::GutenbergBlock;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub enum TextDiffPartKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct TextDiffPart {
+    pub kind: TextDiffPartKind,
+    pub text: String,
+}
+
+/// A value that was added, removed or changed in an object, e.g. an attribute of a block or a field of an exercise.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ValueChange {
+    pub key: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+    /// The word level changes if both the old and the new value are strings.
+    pub text_diff: Option<Vec<TextDiffPart>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct BlockChange {
+    pub client_id: Uuid,
+    pub name: String,
+    pub kind: ChangeKind,
+    /// The block the block is an inner block of, if any. For removed blocks, the parent in the old version.
+    pub parent_client_id: Option<Uuid>,
+    /// True if the block is in a different place relative to the blocks that are in both versions.
+    pub moved: bool,
+    /// For added and removed blocks, all the attributes of the block.
+    pub attribute_changes: Vec<ValueChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(feature = "ts_rs", derive(TS))]
+pub struct ItemChange {
+    pub id: Uuid,
+    pub kind: ChangeKind,
+    /// For added and removed items, all the fields of the item.
+    pub field_changes: Vec<ValueChange>,
+}
+
+/// Diffs two texts word by word. Consecutive words with the same kind of change are combined into one part.
+pub fn diff_text(old: &str, new: &str) -> Vec<TextDiffPart> {
+    let old_words = split_words(old);
+    let new_words = split_words(new);
+    let mut parts: Vec<TextDiffPart> = vec![];
+    for result in diff::slice(&old_words, &new_words) {
+        let (kind, word) = match result {
+            diff::Result::Both(word, _) => (TextDiffPartKind::Unchanged, word),
+            diff::Result::Left(word) => (TextDiffPartKind::Removed, word),
+            diff::Result::Right(word) => (TextDiffPartKind::Added, word),
+        };
+        match parts.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(word),
+            _ => parts.push(TextDiffPart {
+                kind,
+                text: word.to_string(),
+            }),
+        }
+    }
+    parts
+}
+
+/// Splits the text into words and the whitespace between them so that joining the parts gives back the text.
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut start = 0;
+    let mut previous_was_whitespace = None;
+    for (i, c) in text.char_indices() {
+        let is_whitespace = c.is_whitespace();
+        if previous_was_whitespace.is_some() && previous_was_whitespace != Some(is_whitespace) {
+            words.push(&text[start..i]);
+            start = i;
+        }
+        previous_was_whitespace = Some(is_whitespace);
+    }
+    if start < text.len() {
+        words.push(&text[start..]);
+    }
+    words
+}
+
+/// Lists the values that differ between the objects, in the order of the keys in the old object followed by the keys only in the new object.
+pub fn diff_values(old: &Map<String, Value>, new: &Map<String, Value>) -> Vec<ValueChange> {
+    old.keys()
+        .chain(new.keys().filter(|key| !old.contains_key(*key)))
+        .filter_map(|key| {
+            let old_value = old.get(key);
+            let new_value = new.get(key);
+            if old_value == new_value {
+                return None;
+            }
+            let text_diff = match (old_value, new_value) {
+                (Some(Value::String(old)), Some(Value::String(new))) => Some(diff_text(old, new)),
+                _ => None,
+            };
+            Some(ValueChange {
+                key: key.clone(),
+                old_value: old_value.cloned(),
+                new_value: new_value.cloned(),
+                text_diff,
+            })
+        })
+        .collect()
+}
+
+struct FlatBlock<'a> {
+    block: &'a GutenbergBlock,
+    parent_client_id: Option<Uuid>,
+}
+
+fn flatten_blocks<'a>(
+    blocks: &'a [GutenbergBlock],
+    parent_client_id: Option<Uuid>,
+    flattened: &mut Vec<FlatBlock<'a>>,
+) {
+    for block in blocks {
+        flattened.push(FlatBlock {
+            block,
+            parent_client_id,
+        });
+        flatten_blocks(&block.inner_blocks, Some(block.client_id), flattened);
+    }
+}
+
+/**
+Diffs two versions of a list of blocks, including their inner blocks. Blocks are matched with their client ids.
+
+The changes are listed in document order: removed blocks where they were in the old version, and the other blocks where they are in the new version. Blocks that were neither changed nor moved are left out.
+*/
+pub fn diff_blocks(old: &[GutenbergBlock], new: &[GutenbergBlock]) -> Vec<BlockChange> {
+    let mut old_flat = vec![];
+    flatten_blocks(old, None, &mut old_flat);
+    let mut new_flat = vec![];
+    flatten_blocks(new, None, &mut new_flat);
+    let old_by_id = old_flat
+        .iter()
+        .map(|b| (b.block.client_id, b))
+        .collect::<HashMap<_, _>>();
+    let new_by_id = new_flat
+        .iter()
+        .map(|b| (b.block.client_id, b))
+        .collect::<HashMap<_, _>>();
+
+    let old_ids = old_flat
+        .iter()
+        .map(|b| b.block.client_id)
+        .collect::<Vec<_>>();
+    let new_ids = new_flat
+        .iter()
+        .map(|b| b.block.client_id)
+        .collect::<Vec<_>>();
+    // the blocks that are in both versions but not in their longest common order were moved
+    let mut in_common_order = HashSet::new();
+    let mut changes = vec![];
+    let results = diff::slice(&old_ids, &new_ids);
+    for result in results.iter() {
+        if let diff::Result::Both(id, _) = result {
+            in_common_order.insert(**id);
+        }
+    }
+    for result in results {
+        let id = match result {
+            diff::Result::Both(id, _) | diff::Result::Right(id) => *id,
+            diff::Result::Left(id) => {
+                if !new_by_id.contains_key(id) {
+                    let removed = old_by_id[id];
+                    changes.push(BlockChange {
+                        client_id: *id,
+                        name: removed.block.name.clone(),
+                        kind: ChangeKind::Removed,
+                        parent_client_id: removed.parent_client_id,
+                        moved: false,
+                        attribute_changes: diff_values(&Map::new(), &removed.block.attributes),
+                    });
+                }
+                // blocks that are also in the new version are handled where they are in the new version
+                continue;
+            }
+        };
+        let new_block = new_by_id[&id];
+        match old_by_id.get(&id) {
+            None => changes.push(BlockChange {
+                client_id: id,
+                name: new_block.block.name.clone(),
+                kind: ChangeKind::Added,
+                parent_client_id: new_block.parent_client_id,
+                moved: false,
+                attribute_changes: diff_values(&Map::new(), &new_block.block.attributes),
+            }),
+            Some(old_block) => {
+                let moved = !in_common_order.contains(&id)
+                    || old_block.parent_client_id != new_block.parent_client_id;
+                let attribute_changes =
+                    diff_values(&old_block.block.attributes, &new_block.block.attributes);
+                if moved || !attribute_changes.is_empty() {
+                    changes.push(BlockChange {
+                        client_id: id,
+                        name: new_block.block.name.clone(),
+                        kind: ChangeKind::Modified,
+                        parent_client_id: new_block.parent_client_id,
+                        moved,
+                        attribute_changes,
+                    });
+                }
+            }
+        }
+    }
+    changes
+}
+
+/**
+Diffs two versions of a list of items that have ids, e.g. the exercises of a page. The items are compared field by field, so they should serialize into JSON objects.
+
+Removed items are listed first in their old order, followed by the added and modified items in their new order.
+*/
+pub fn diff_items(old: &[(Uuid, Value)], new: &[(Uuid, Value)]) -> Vec<ItemChange> {
+    let empty = Map::new();
+    let fields = |value: &Value| value.as_object().cloned().unwrap_or_default();
+    let old_by_id = old
+        .iter()
+        .map(|(id, v)| (*id, v))
+        .collect::<HashMap<_, _>>();
+    let new_ids = new.iter().map(|(id, _)| *id).collect::<HashSet<_>>();
+
+    let removed = old
+        .iter()
+        .filter(|(id, _)| !new_ids.contains(id))
+        .map(|(id, value)| ItemChange {
+            id: *id,
+            kind: ChangeKind::Removed,
+            field_changes: diff_values(&empty, &fields(value)),
+        });
+    let added_or_modified = new
+        .iter()
+        .filter_map(|(id, value)| match old_by_id.get(id) {
+            None => Some(ItemChange {
+                id: *id,
+                kind: ChangeKind::Added,
+                field_changes: diff_values(&empty, &fields(value)),
+            }),
+            Some(old_value) => {
+                let field_changes = diff_values(&fields(old_value), &fields(value));
+                if field_changes.is_empty() {
+                    None
+                } else {
+                    Some(ItemChange {
+                        id: *id,
+                        kind: ChangeKind::Modified,
+                        field_changes,
+                    })
+                }
+            }
+        });
+    removed.chain(added_or_modified).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn block(
+        id: u128,
+        name: &str,
+        content: &str,
+        inner_blocks: Vec<GutenbergBlock>,
+    ) -> GutenbergBlock {
+        GutenbergBlock {
+            client_id: Uuid::from_u128(id),
+            name: name.to_string(),
+            is_valid: true,
+            attributes: json!({ "content": content }).as_object().unwrap().clone(),
+            inner_blocks,
+        }
+    }
+
+    #[test]
+    fn diffs_text_word_by_word() {
+        let parts = diff_text("The quick brown fox", "The slow brown fox jumps");
+        assert_eq!(
+            parts,
+            vec![
+                TextDiffPart {
+                    kind: TextDiffPartKind::Unchanged,
+                    text: "The ".to_string()
+                },
+                TextDiffPart {
+                    kind: TextDiffPartKind::Removed,
+                    text: "quick".to_string()
+                },
+                TextDiffPart {
+                    kind: TextDiffPartKind::Added,
+                    text: "slow".to_string()
+                },
+                TextDiffPart {
+                    kind: TextDiffPartKind::Unchanged,
+                    text: " brown fox".to_string()
+                },
+                TextDiffPart {
+                    kind: TextDiffPartKind::Added,
+                    text: " jumps".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_words_keeps_all_characters() {
+        let text = "  Hello,  wörld!\nNew line ";
+        assert_eq!(split_words(text).concat(), text);
+        assert_eq!(split_words(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn diffs_added_removed_and_modified_blocks() {
+        let old = vec![
+            block(1, "core/paragraph", "First", vec![]),
+            block(2, "core/paragraph", "Second", vec![]),
+            block(3, "core/heading", "Title", vec![]),
+        ];
+        let new = vec![
+            block(1, "core/paragraph", "First", vec![]),
+            block(4, "core/paragraph", "New", vec![]),
+            block(3, "core/heading", "New title", vec![]),
+        ];
+        let changes = diff_blocks(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].client_id, Uuid::from_u128(2));
+        assert_eq!(changes[0].kind, ChangeKind::Removed);
+        assert_eq!(changes[1].client_id, Uuid::from_u128(4));
+        assert_eq!(changes[1].kind, ChangeKind::Added);
+        assert_eq!(changes[2].client_id, Uuid::from_u128(3));
+        assert_eq!(changes[2].kind, ChangeKind::Modified);
+        assert!(!changes[2].moved);
+        assert_eq!(changes[2].attribute_changes[0].key, "content");
+        assert_eq!(
+            changes[2].attribute_changes[0].new_value,
+            Some(json!("New title"))
+        );
+    }
+
+    #[test]
+    fn detects_moved_blocks() {
+        let old = vec![
+            block(1, "core/paragraph", "First", vec![]),
+            block(2, "core/paragraph", "Second", vec![]),
+            block(3, "core/paragraph", "Third", vec![]),
+        ];
+        let new = vec![
+            block(2, "core/paragraph", "Second", vec![]),
+            block(3, "core/paragraph", "Third", vec![]),
+            block(1, "core/paragraph", "First", vec![]),
+        ];
+        let changes = diff_blocks(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].client_id, Uuid::from_u128(1));
+        assert_eq!(changes[0].kind, ChangeKind::Modified);
+        assert!(changes[0].moved);
+        assert!(changes[0].attribute_changes.is_empty());
+    }
+
+    #[test]
+    fn detects_blocks_moved_into_other_blocks() {
+        let old = vec![
+            block(1, "core/group", "", vec![]),
+            block(2, "core/paragraph", "Text", vec![]),
+        ];
+        let new = vec![block(
+            1,
+            "core/group",
+            "",
+            vec![block(2, "core/paragraph", "Text", vec![])],
+        )];
+        let changes = diff_blocks(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].client_id, Uuid::from_u128(2));
+        assert!(changes[0].moved);
+        assert_eq!(changes[0].parent_client_id, Some(Uuid::from_u128(1)));
+    }
+
+    #[test]
+    fn diffs_items() {
+        let old = vec![
+            (Uuid::from_u128(1), json!({"name": "a", "score_maximum": 1})),
+            (Uuid::from_u128(2), json!({"name": "b", "score_maximum": 1})),
+        ];
+        let new = vec![
+            (Uuid::from_u128(1), json!({"name": "a", "score_maximum": 2})),
+            (Uuid::from_u128(3), json!({"name": "c", "score_maximum": 1})),
+        ];
+        let changes = diff_items(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].id, Uuid::from_u128(2));
+        assert_eq!(changes[0].kind, ChangeKind::Removed);
+        assert_eq!(changes[1].id, Uuid::from_u128(1));
+        assert_eq!(changes[1].kind, ChangeKind::Modified);
+        assert_eq!(
+            changes[1].field_changes,
+            vec![ValueChange {
+                key: "score_maximum".to_string(),
+                old_value: Some(json!(1)),
+                new_value: Some(json!(2)),
+                text_diff: None,
+            }]
+        );
+        assert_eq!(changes[2].id, Uuid::from_u128(3));
+        assert_eq!(changes[2].kind, ChangeKind::Added);
+        assert_eq!(changes[2].field_changes.len(), 2);
+    }
+}
@@ -89,6 +89,11 @@ pub fn organization_file_path(organization_id: Uuid, file_name: &str) -> UtilRes
     Ok(path)
 }
 
+/// The file name should be random, because the files can be downloaded by anyone who knows the path.
+pub fn user_data_export_path(user_id: Uuid, file_name: &str) -> PathBuf {
+    PathBuf::from(format!("user_data_exports/{user_id}/{file_name}"))
+}
+
 pub fn repository_exercise_path(repository_id: Uuid, repository_exercise_id: Uuid) -> PathBuf {
     PathBuf::from(format!(
         "repository_exercises/{repository_id}/{repository_exercise_id}",
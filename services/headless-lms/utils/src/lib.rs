@@ -1,5 +1,6 @@
 //! Commonly used utils.
 
+pub mod content_diff;
 pub mod document_schema_processor;
 pub mod email_processor;
 pub mod error;
@@ -13,7 +14,9 @@ pub mod numbers;
 pub mod page_visit_hasher;
 pub mod pagination;
 pub mod prelude;
+pub mod public_addresses;
 pub mod strings;
+pub mod text_similarity;
 pub mod url_to_oembed_endpoint;
 
 #[macro_use]
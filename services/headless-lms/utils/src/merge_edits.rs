@@ -1,3 +1,10 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::document_schema_processor::GutenbergBlock;
+
 pub fn merge(ancestor: &str, incoming_edit: &str, current: &str) -> Option<String> {
     if ancestor == current {
         // if there have been no changes between the proposal and now, no need to merge
@@ -67,6 +74,181 @@ pub fn merge(ancestor: &str, incoming_edit: &str, current: &str) -> Option<Strin
     Some(result)
 }
 
+/**
+Merges two edits of the same list of blocks block by block. Blocks are matched with their client ids.
+
+A block that was changed in only one of the edits is taken from that edit. If both edits changed the same block, its attributes are merged one by one, and attributes that were changed in both edits are merged with [merge] if they are strings. Blocks that were added in one edit are kept, and blocks that were removed in one edit and left unchanged in the other are removed.
+
+Returns None if the edits conflict, e.g. if both edits changed the same non-string attribute, one edit removed a block the other one changed, or both edits reordered the blocks differently.
+*/
+pub fn merge_blocks(
+    ancestor: &[GutenbergBlock],
+    incoming_edit: &[GutenbergBlock],
+    current: &[GutenbergBlock],
+) -> Option<Vec<GutenbergBlock>> {
+    if ancestor == current {
+        return Some(incoming_edit.to_vec());
+    }
+    if ancestor == incoming_edit {
+        return Some(current.to_vec());
+    }
+
+    let ancestor_blocks = blocks_by_id(ancestor);
+    let incoming_blocks = blocks_by_id(incoming_edit);
+    let current_blocks = blocks_by_id(current);
+
+    let mut merged_blocks = HashMap::new();
+    for id in incoming_blocks.keys().chain(current_blocks.keys()) {
+        if merged_blocks.contains_key(id) {
+            continue;
+        }
+        let merged = match (
+            ancestor_blocks.get(id),
+            incoming_blocks.get(id),
+            current_blocks.get(id),
+        ) {
+            (Some(ancestor), Some(incoming), Some(current)) => {
+                merge_block(ancestor, incoming, current)?
+            }
+            // removed in the current version, can only be removed if the incoming edit left it unchanged
+            (Some(ancestor), Some(incoming), None) if ancestor == incoming => continue,
+            // removed in the incoming edit, can only be removed if it is unchanged in the current version
+            (Some(ancestor), None, Some(current)) if ancestor == current => continue,
+            // added in one of the versions
+            (None, Some(block), None) | (None, None, Some(block)) => (*block).clone(),
+            // added in both versions with the same id
+            (None, Some(incoming), Some(current)) if incoming == current => (*incoming).clone(),
+            _ => return None,
+        };
+        merged_blocks.insert(*id, merged);
+    }
+
+    // the order of the blocks that are in all the versions determines whose order is used
+    let common_order = |blocks: &[GutenbergBlock]| {
+        blocks
+            .iter()
+            .map(|b| b.client_id)
+            .filter(|id| {
+                ancestor_blocks.contains_key(id)
+                    && incoming_blocks.contains_key(id)
+                    && current_blocks.contains_key(id)
+            })
+            .collect::<Vec<_>>()
+    };
+    let ancestor_order = common_order(ancestor);
+    let incoming_order = common_order(incoming_edit);
+    let current_order = common_order(current);
+    let (base, other) = if incoming_order == ancestor_order || incoming_order == current_order {
+        (current, incoming_edit)
+    } else if current_order == ancestor_order {
+        (incoming_edit, current)
+    } else {
+        // both versions reordered the blocks differently
+        return None;
+    };
+
+    let mut order = base
+        .iter()
+        .map(|b| b.client_id)
+        .filter(|id| merged_blocks.contains_key(id))
+        .collect::<Vec<_>>();
+    // the blocks that were added in the other version are placed after the block that precedes them in the other version
+    let mut ordered = order.iter().copied().collect::<HashSet<_>>();
+    let mut previous: Option<Uuid> = None;
+    for id in other.iter().map(|b| b.client_id) {
+        if !merged_blocks.contains_key(&id) {
+            continue;
+        }
+        if !ordered.contains(&id) {
+            let position = previous
+                .and_then(|previous| order.iter().position(|o| *o == previous))
+                .map(|p| p + 1)
+                .unwrap_or(0);
+            order.insert(position, id);
+            ordered.insert(id);
+        }
+        previous = Some(id);
+    }
+
+    Some(
+        order
+            .into_iter()
+            .filter_map(|id| merged_blocks.remove(&id))
+            .collect(),
+    )
+}
+
+fn blocks_by_id(blocks: &[GutenbergBlock]) -> HashMap<Uuid, &GutenbergBlock> {
+    blocks.iter().map(|b| (b.client_id, b)).collect()
+}
+
+fn merge_block(
+    ancestor: &GutenbergBlock,
+    incoming_edit: &GutenbergBlock,
+    current: &GutenbergBlock,
+) -> Option<GutenbergBlock> {
+    if incoming_edit == current || ancestor == current {
+        return Some(incoming_edit.clone());
+    }
+    if ancestor == incoming_edit {
+        return Some(current.clone());
+    }
+    // changed in both versions
+    if incoming_edit.name != current.name {
+        return None;
+    }
+    Some(GutenbergBlock {
+        client_id: current.client_id,
+        name: current.name.clone(),
+        is_valid: incoming_edit.is_valid && current.is_valid,
+        attributes: merge_attributes(
+            &ancestor.attributes,
+            &incoming_edit.attributes,
+            &current.attributes,
+        )?,
+        inner_blocks: merge_blocks(
+            &ancestor.inner_blocks,
+            &incoming_edit.inner_blocks,
+            &current.inner_blocks,
+        )?,
+    })
+}
+
+fn merge_attributes(
+    ancestor: &Map<String, Value>,
+    incoming_edit: &Map<String, Value>,
+    current: &Map<String, Value>,
+) -> Option<Map<String, Value>> {
+    let mut merged = Map::new();
+    for key in incoming_edit.keys().chain(current.keys()) {
+        if merged.contains_key(key) {
+            continue;
+        }
+        let value = match (ancestor.get(key), incoming_edit.get(key), current.get(key)) {
+            (_, incoming, current) if incoming == current => incoming,
+            (ancestor, incoming, current) if ancestor == current => incoming,
+            (ancestor, incoming, current) if ancestor == incoming => current,
+            (
+                Some(Value::String(ancestor)),
+                Some(Value::String(incoming)),
+                Some(Value::String(current)),
+            ) => {
+                merged.insert(
+                    key.clone(),
+                    Value::String(merge(ancestor, incoming, current)?),
+                );
+                continue;
+            }
+            _ => return None,
+        };
+        // the attribute was removed if there is no value
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    Some(merged)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -134,4 +316,121 @@ mod test {
         let current = "paragraphs!";
         assert_eq!(merge(ancestor, incoming, current).unwrap(), "pgraphs!");
     }
+
+    fn paragraph(id: u128, content: &str) -> GutenbergBlock {
+        GutenbergBlock {
+            client_id: Uuid::from_u128(id),
+            ..GutenbergBlock::paragraph(content)
+        }
+    }
+
+    #[test]
+    fn merges_edits_to_different_blocks() {
+        let ancestor = vec![paragraph(1, "First."), paragraph(2, "Second.")];
+        let incoming = vec![paragraph(1, "First, edited."), paragraph(2, "Second.")];
+        let current = vec![
+            paragraph(1, "First."),
+            paragraph(2, "Second, edited."),
+            paragraph(3, "Third."),
+        ];
+        assert_eq!(
+            merge_blocks(&ancestor, &incoming, &current).unwrap(),
+            vec![
+                paragraph(1, "First, edited."),
+                paragraph(2, "Second, edited."),
+                paragraph(3, "Third.")
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_text_edits_to_the_same_block() {
+        let ancestor = vec![paragraph(1, "This is the original, uneditd text.")];
+        let incoming = vec![paragraph(
+            1,
+            "This is the original, completely uneditd text.",
+        )];
+        let current = vec![paragraph(1, "This is the original, unedited text.")];
+        assert_eq!(
+            merge_blocks(&ancestor, &incoming, &current).unwrap(),
+            vec![paragraph(
+                1,
+                "This is the original, completely unedited text."
+            )]
+        );
+    }
+
+    #[test]
+    fn places_added_blocks_after_their_predecessors() {
+        let ancestor = vec![paragraph(1, "First."), paragraph(2, "Second.")];
+        let incoming = vec![
+            paragraph(1, "First."),
+            paragraph(3, "Added after first."),
+            paragraph(2, "Second."),
+        ];
+        let current = vec![
+            paragraph(4, "Added to the start."),
+            paragraph(2, "Second."),
+            paragraph(1, "First."),
+        ];
+        assert_eq!(
+            merge_blocks(&ancestor, &incoming, &current).unwrap(),
+            vec![
+                paragraph(4, "Added to the start."),
+                paragraph(2, "Second."),
+                paragraph(1, "First."),
+                paragraph(3, "Added after first."),
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_blocks_removed_in_either_version() {
+        let ancestor = vec![paragraph(1, "First."), paragraph(2, "Second.")];
+        let incoming = vec![paragraph(2, "Second, edited.")];
+        let current = vec![paragraph(1, "First.")];
+        assert_eq!(merge_blocks(&ancestor, &incoming, &current), None);
+
+        let incoming = vec![paragraph(2, "Second.")];
+        assert_eq!(
+            merge_blocks(&ancestor, &incoming, &current).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn does_not_merge_conflicting_edits() {
+        let mut ancestor = paragraph(1, "Text.");
+        ancestor
+            .attributes
+            .insert("dropCap".to_string(), Value::Bool(false));
+        let mut incoming = ancestor.clone();
+        incoming
+            .attributes
+            .insert("align".to_string(), Value::String("left".to_string()));
+        let mut current = ancestor.clone();
+        current
+            .attributes
+            .insert("align".to_string(), Value::String("right".to_string()));
+        // both versions added the same attribute with different values
+        assert_eq!(merge_blocks(&[ancestor], &[incoming], &[current]), None);
+
+        let ancestor = vec![
+            paragraph(1, "First."),
+            paragraph(2, "Second."),
+            paragraph(3, "Third."),
+        ];
+        let incoming = vec![
+            paragraph(2, "Second."),
+            paragraph(1, "First."),
+            paragraph(3, "Third."),
+        ];
+        let current = vec![
+            paragraph(1, "First."),
+            paragraph(3, "Third."),
+            paragraph(2, "Second."),
+        ];
+        // both versions reordered the blocks differently
+        assert_eq!(merge_blocks(&ancestor, &incoming, &current), None);
+    }
 }
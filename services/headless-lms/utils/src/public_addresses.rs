@@ -0,0 +1,130 @@
+/*!
+Checks for the addresses the server sends requests to on behalf of users, such as webhooks. The requests must not reach the internal services of the deployment, so only public addresses are allowed.
+
+Checking the url is not enough on its own, because a public domain name can resolve to an internal address. The addresses the host resolves to have to be checked with [is_public_ip_address] when the request is sent, and the request has to be sent to the checked address.
+*/
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use url::{Host, Url};
+
+/// Whether the address is reachable from the public internet, i.e. not a loopback, private, link-local or otherwise special address.
+pub fn is_public_ip_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4_address(ip),
+        IpAddr::V6(ip) => is_public_ipv6_address(ip),
+    }
+}
+
+fn is_public_ipv4_address(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "this network"
+        || a == 0
+        // shared address space used by carrier-grade NAT
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // benchmarking
+        || (a == 198 && (18..20).contains(&b))
+        // reserved
+        || a >= 240)
+}
+
+fn is_public_ipv6_address(ip: Ipv6Addr) -> bool {
+    // IPv4-mapped and NAT64 addresses reach the embedded IPv4 address
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4_address(ipv4);
+    }
+    let segments = ip.segments();
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [_, _, _, _, _, _, high, low] = segments;
+        return is_public_ipv4_address(Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // unique local
+        || (segments[0] & 0xfe00) == 0xfc00
+        // link-local
+        || (segments[0] & 0xffc0) == 0xfe80
+        // documentation
+        || (segments[0] == 0x2001 && segments[1] == 0xdb8))
+}
+
+/// Checks that the url is an HTTPS url whose host can be a public address. Returns an error message suitable for showing to the user if it's not.
+pub fn validate_public_https_url(url: &str) -> Result<Url, String> {
+    let url = Url::parse(url).map_err(|_| "The url is not valid.".to_string())?;
+    if url.scheme() != "https" {
+        return Err("The url needs to be an HTTPS url.".to_string());
+    }
+    let is_public_host = match url.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_lowercase();
+            domain.contains('.') && domain != "localhost" && !domain.ends_with(".localhost")
+        }
+        Some(Host::Ipv4(ip)) => is_public_ipv4_address(ip),
+        Some(Host::Ipv6(ip)) => is_public_ipv6_address(ip),
+        None => false,
+    };
+    if !is_public_host {
+        return Err("The url needs to point to a public address.".to_string());
+    }
+    Ok(url)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recognizes_public_addresses() {
+        for public in ["8.8.8.8", "93.184.216.34", "2606:2800:220:1::1"] {
+            assert!(is_public_ip_address(public.parse().unwrap()), "{}", public);
+        }
+        for internal in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(
+                !is_public_ip_address(internal.parse().unwrap()),
+                "{}",
+                internal
+            );
+        }
+    }
+
+    #[test]
+    fn validates_public_https_urls() {
+        assert!(validate_public_https_url("https://example.com/webhook").is_ok());
+        assert!(validate_public_https_url("https://93.184.216.34/webhook").is_ok());
+        for invalid in [
+            "not a url",
+            "http://example.com/webhook",
+            "https://localhost/webhook",
+            "https://internal/webhook",
+            "https://127.0.0.1/webhook",
+            "https://[::1]/webhook",
+            "https://169.254.169.254/latest/meta-data",
+        ] {
+            assert!(validate_public_https_url(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Fingerprinting of texts for finding similar submissions. Uses winnowing: the text is normalized, split into overlapping k-grams, and from each window of consecutive k-gram hashes the smallest one is selected to the fingerprint.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+/// The length of the k-grams in characters. Matches shorter than this are not detected.
+const K_GRAM_LENGTH: usize = 12;
+/// The number of consecutive k-gram hashes one hash is selected from. Matches at least `K_GRAM_LENGTH + WINDOW_SIZE - 1` characters long are always detected.
+const WINDOW_SIZE: usize = 8;
+
+pub type Fingerprint = HashSet<u64>;
+
+/// Collects the texts from a submission's data so that they can be fingerprinted. Object keys, numbers, booleans and uuids are ignored since they tend to be the same for all submissions, for example ids of the selected options.
+pub fn extract_text_from_json(value: &Value) -> String {
+    let mut texts = Vec::new();
+    collect_texts(value, &mut texts);
+    texts.join(" ")
+}
+
+fn collect_texts<'a>(value: &'a Value, texts: &mut Vec<&'a str>) {
+    match value {
+        Value::String(s) => {
+            if Uuid::parse_str(s).is_err() {
+                texts.push(s);
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_texts(v, texts)),
+        Value::Object(map) => map.values().for_each(|v| collect_texts(v, texts)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// Creates a fingerprint from the text. Case, whitespace and punctuation are ignored. Texts shorter than the k-gram length have an empty fingerprint.
+pub fn fingerprint(text: &str) -> Fingerprint {
+    let normalized = text
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    if normalized.len() < K_GRAM_LENGTH {
+        return Fingerprint::new();
+    }
+    let hashes = normalized
+        .windows(K_GRAM_LENGTH)
+        .map(|k_gram| hash_k_gram(&k_gram.iter().collect::<String>()))
+        .collect::<Vec<_>>();
+    if hashes.len() <= WINDOW_SIZE {
+        return hashes.iter().min().into_iter().copied().collect();
+    }
+    hashes
+        .windows(WINDOW_SIZE)
+        .filter_map(|window| window.iter().min().copied())
+        .collect()
+}
+
+fn hash_k_gram(k_gram: &str) -> u64 {
+    let hash = blake3::hash(k_gram.as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Returns the share (0.0 - 1.0) of the fingerprint hashes the two fingerprints have in common (Jaccard index).
+pub fn similarity(first: &Fingerprint, second: &Fingerprint) -> f32 {
+    if first.is_empty() || second.is_empty() {
+        return 0.0;
+    }
+    let common = first.intersection(second).count();
+    let total = first.union(second).count();
+    common as f32 / total as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_texts_without_uuids() {
+        let value = serde_json::json!({
+            "answer": "This is my answer.",
+            "selectedOptionIds": ["5f464818-1e68-4839-ae86-850b310f508c"],
+            "details": [{ "text": "More text" }, 3, true, null],
+        });
+        let text = extract_text_from_json(&value);
+        assert!(text.contains("This is my answer."));
+        assert!(text.contains("More text"));
+        assert!(!text.contains("5f464818"));
+    }
+
+    #[test]
+    fn identical_texts_are_fully_similar_regardless_of_formatting() {
+        let first = fingerprint("The quick brown fox jumps over the lazy dog and runs away.");
+        let second = fingerprint("the QUICK brown fox   jumps over the lazy dog, and runs away");
+        assert_eq!(similarity(&first, &second), 1.0);
+    }
+
+    #[test]
+    fn different_texts_are_not_similar() {
+        let first = fingerprint("The quick brown fox jumps over the lazy dog and runs away.");
+        let second = fingerprint("Lorem ipsum dolor sit amet, consectetur adipiscing elit.");
+        assert!(similarity(&first, &second) < 0.1);
+    }
+
+    #[test]
+    fn partially_copied_texts_are_partially_similar() {
+        let copied = "Photosynthesis converts light energy into chemical energy stored in glucose molecules.";
+        let first = fingerprint(&format!(
+            "{} Plants need sunlight and water for this to happen.",
+            copied
+        ));
+        let second = fingerprint(&format!(
+            "{} Without it there would be no oxygen to breathe.",
+            copied
+        ));
+        let similarity = similarity(&first, &second);
+        assert!(similarity > 0.3);
+        assert!(similarity < 1.0);
+    }
+
+    #[test]
+    fn short_texts_have_no_fingerprint() {
+        assert!(fingerprint("yes").is_empty());
+        assert_eq!(similarity(&fingerprint("yes"), &fingerprint("yes")), 0.0);
+    }
+}
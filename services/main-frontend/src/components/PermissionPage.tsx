@@ -6,11 +6,13 @@ import { useRouter } from "next/router"
 import React, { useState } from "react"
 import { useTranslation } from "react-i18next"
 
+import { fetchCustomRolesForOrganization } from "../services/backend/custom-roles"
 import { fetchPendingRoles } from "../services/backend/pendingRoles"
 import { fetchRoles, giveRole, removeRole } from "../services/backend/roles"
 import { RoleDomain, RoleQuery, RoleUser, UserRole } from "../shared-module/bindings"
 import Button from "../shared-module/components/Button"
 import ErrorBanner from "../shared-module/components/ErrorBanner"
+import DateTimeLocal from "../shared-module/components/InputFields/DateTimeLocal"
 import SelectField from "../shared-module/components/InputFields/SelectField"
 import TextField from "../shared-module/components/InputFields/TextField"
 import useToastMutation from "../shared-module/hooks/useToastMutation"
@@ -50,9 +52,14 @@ interface EditingRole {
 
 interface Props {
   domain: RoleDomain
+  /** When given, the custom roles of the organization can be given on this page. */
+  organizationId?: string
 }
 
-export const PermissionPage: React.FC<React.PropsWithChildren<Props>> = ({ domain }) => {
+export const PermissionPage: React.FC<React.PropsWithChildren<Props>> = ({
+  domain,
+  organizationId,
+}) => {
   const { t } = useTranslation()
   const router = useRouter()
   let { sort: sort_key } = router.query
@@ -91,26 +98,59 @@ export const PermissionPage: React.FC<React.PropsWithChildren<Props>> = ({ domai
   const [newEmail, setNewEmail] = useState("")
   // eslint-disable-next-line i18next/no-literal-string
   const [newRole, setNewRole] = useState<UserRole>("Assistant")
+  const [newValidUntil, setNewValidUntil] = useState("")
+  const [newCustomRoleId, setNewCustomRoleId] = useState("")
   const [editingRole, setEditingRole] = useState<EditingRole | null>(null)
   const [mutationError, setMutationError] = useState<unknown | null>(null)
   const roleQuery = useQuery([`roles`, domain], () => fetchRoles(query))
   const pendingRolesQuery = useQuery([`pending-roles`, domain], () => fetchPendingRoles(query))
+  const customRolesQuery = useQuery(
+    [`custom-roles`, organizationId],
+    () => fetchCustomRolesForOrganization(organizationId as string),
+    { enabled: organizationId !== undefined },
+  )
+  const customRoleOptions = [
+    { value: "", label: t("no-custom-role") },
+    ...(customRolesQuery.data ?? [])
+      .filter((customRole) => customRole.custom_role.base_role === newRole)
+      .map((customRole) => ({
+        value: customRole.custom_role.id,
+        label: customRole.custom_role.name,
+      })),
+  ]
   const addMutation = useToastMutation(
     () => {
-      return giveRole(newEmail, newRole, domain)
+      return giveRole(
+        newEmail,
+        newRole,
+        domain,
+        newValidUntil ? new Date(newValidUntil) : null,
+        newCustomRoleId === "" ? null : newCustomRoleId,
+      )
     },
     { notify: true, method: "POST" },
     {
       onSuccess: () => {
         setNewEmail("")
+        setNewValidUntil("")
         roleQuery.refetch()
       },
       onError: setMutationError,
     },
   )
   const editMutation = useToastMutation(
-    ({ email, oldRole, newRole }: { email: string; oldRole: UserRole; newRole: UserRole }) =>
-      removeRole(email, oldRole, domain).then(() => giveRole(email, newRole, domain)),
+    ({
+      email,
+      oldRole,
+      newRole,
+      validUntil,
+    }: {
+      email: string
+      oldRole: UserRole
+      newRole: UserRole
+      validUntil: Date | null
+    }) =>
+      removeRole(email, oldRole, domain).then(() => giveRole(email, newRole, domain, validUntil)),
     { notify: true, method: "POST" },
     {
       onSuccess: () => roleQuery.refetch(),
@@ -265,7 +305,19 @@ export const PermissionPage: React.FC<React.PropsWithChildren<Props>> = ({ domai
                 <td>{ur.email}</td>
                 {editingRole?.userId !== ur.id && (
                   <>
-                    <td>{ur.role}</td>
+                    <td>
+                      {ur.custom_role_name ? `${ur.custom_role_name} (${ur.role})` : ur.role}
+                      {ur.valid_until && (
+                        <div
+                          className={css`
+                            font-size: 14px;
+                            margin-top: 8px;
+                          `}
+                        >
+                          {t("label-valid-until")}: {ur.valid_until.toLocaleString()}
+                        </div>
+                      )}
+                    </td>
                     <td>
                       <button
                         aria-label={t("edit-role")}
@@ -322,6 +374,7 @@ export const PermissionPage: React.FC<React.PropsWithChildren<Props>> = ({ domai
                             email: ur.email,
                             oldRole: ur.role,
                             newRole: editingRole.newRole,
+                            validUntil: ur.valid_until,
                           })
                           setEditingRole(null)
                         }}
@@ -393,11 +446,43 @@ export const PermissionPage: React.FC<React.PropsWithChildren<Props>> = ({ domai
             label={t("label-role")}
             onChange={(role) => {
               setNewRole(role)
+              setNewCustomRoleId("")
             }}
             options={options(t)}
             defaultValue={ASSISTANT}
           />
         </div>
+        {customRoleOptions.length > 1 && (
+          <div
+            className={css`
+              display: flex;
+              flex-direction: column;
+              width: 200px;
+              padding-right: 16px;
+            `}
+          >
+            <SelectField
+              id={"adding-custom-role"}
+              label={t("label-custom-role")}
+              onChange={(customRoleId) => setNewCustomRoleId(customRoleId)}
+              options={customRoleOptions}
+              value={newCustomRoleId}
+            />
+          </div>
+        )}
+        <div
+          className={css`
+            display: flex;
+            flex-direction: column;
+            width: 240px;
+            padding-right: 16px;
+          `}
+        >
+          <DateTimeLocal
+            label={t("label-valid-until")}
+            onChange={(value) => setNewValidUntil(value)}
+          />
+        </div>
 
         <Button
           className={css`
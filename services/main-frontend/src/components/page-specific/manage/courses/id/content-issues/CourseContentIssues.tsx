@@ -0,0 +1,141 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import { groupBy } from "lodash"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import { CourseManagementPagesProps } from "../../../../../../pages/manage/courses/[id]/[...path]"
+import {
+  fetchContentLintReport,
+  postContentLintCheck,
+} from "../../../../../../services/backend/courses"
+import { ContentLintIssue, ContentLintIssueType } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme, headingFont } from "../../../../../../shared-module/styles"
+
+const IssueTypeLabel: React.FC<{ issueType: ContentLintIssueType }> = ({ issueType }) => {
+  const { t } = useTranslation()
+  switch (issueType) {
+    case "LinkToMissingPage":
+      return <>{t("content-issue-link-to-missing-page")}</>
+    case "LinkToDeletedPage":
+      return <>{t("content-issue-link-to-deleted-page")}</>
+    case "LinkToHiddenPage":
+      return <>{t("content-issue-link-to-hidden-page")}</>
+    case "LinkThroughRedirection":
+      return <>{t("content-issue-link-through-redirection")}</>
+    case "MissingMediaFile":
+      return <>{t("content-issue-missing-media-file")}</>
+    case "UnsupportedEmbed":
+      return <>{t("content-issue-unsupported-embed")}</>
+    case "ImageWithoutAltText":
+      return <>{t("content-issue-image-without-alt-text")}</>
+    case "MissingExercise":
+      return <>{t("content-issue-missing-exercise")}</>
+  }
+}
+
+const Issue: React.FC<{ issue: ContentLintIssue }> = ({ issue }) => {
+  const { t } = useTranslation()
+  return (
+    <li>
+      <strong>
+        <IssueTypeLabel issueType={issue.issue_type} />
+      </strong>
+      {issue.target && (
+        <>
+          {": "}
+          <code>{issue.target}</code>
+        </>
+      )}
+      {issue.suggestion && (
+        <div>
+          {t("content-issue-suggestion")}: <code>{issue.suggestion}</code>
+        </div>
+      )}
+    </li>
+  )
+}
+
+const CourseContentIssues: React.FC<React.PropsWithChildren<CourseManagementPagesProps>> = ({
+  courseId,
+}) => {
+  const { t } = useTranslation()
+  const getReport = useQuery([`course-${courseId}-content-lint-report`], () =>
+    fetchContentLintReport(courseId),
+  )
+  const checkMutation = useToastMutation(
+    () => postContentLintCheck(courseId),
+    { notify: true, method: "POST" },
+    { onSuccess: () => getReport.refetch() },
+  )
+
+  return (
+    <div>
+      <h2
+        className={css`
+          font-size: clamp(2rem, 3.6vh, 36px);
+          color: ${baseTheme.colors.gray[700]};
+          font-family: ${headingFont};
+          font-weight: bold;
+        `}
+      >
+        {t("title-content-issues")}
+      </h2>
+      <p>{t("content-issues-explanation")}</p>
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={checkMutation.isLoading}
+        onClick={() => checkMutation.mutate()}
+      >
+        {t("button-check-now")}
+      </Button>
+      {checkMutation.isLoading && <Spinner variant="medium" />}
+      {getReport.isError && <ErrorBanner variant="readOnly" error={getReport.error} />}
+      {getReport.isLoading && <Spinner variant="medium" />}
+      {getReport.isSuccess && (
+        <div>
+          {getReport.data.run === null ? (
+            <p>{t("content-not-checked-yet")}</p>
+          ) : (
+            <p>
+              {t("content-checked-at", {
+                time: getReport.data.run.created_at.toLocaleString(),
+                pages: getReport.data.run.pages_checked,
+                issues: getReport.data.issues.length,
+              })}
+            </p>
+          )}
+          {Object.values(groupBy(getReport.data.issues, (issue) => issue.page_id)).map(
+            (issues) => (
+              <div key={issues[0].page_id}>
+                <h3>
+                  <a href={`/cms/pages/${issues[0].page_id}`}>{issues[0].page_title}</a>{" "}
+                  <span
+                    className={css`
+                      color: ${baseTheme.colors.gray[500]};
+                      font-size: 1rem;
+                    `}
+                  >
+                    {issues[0].page_url_path}
+                  </span>
+                </h3>
+                <ul>
+                  {issues.map((issue) => (
+                    <Issue key={issue.id} issue={issue} />
+                  ))}
+                </ul>
+              </div>
+            ),
+          )}
+        </div>
+      )}
+    </div>
+  )
+}
+
+export default CourseContentIssues
@@ -19,6 +19,7 @@ import {
   viewCourseInstancePointsPageRoute,
 } from "../../../../../../utils/routing"
 
+import GradeExportLinks from "./GradeExportLinks"
 import ModuleCompletionReprocessButton from "./ModuleCompletionReprocessButton"
 import NewCourseInstanceDialog from "./NewCourseInstanceDialog"
 import PointExportButton from "./PointExportButton"
@@ -94,7 +95,8 @@ const CourseCourseInstances: React.FC<React.PropsWithChildren<CourseManagementPa
                     >
                       {t("link-view-points")}
                     </Link>{" "}
-                    <PointExportButton courseInstanceId={instance.id} courseInstanceName={name} />
+                    <PointExportButton courseInstanceId={instance.id} courseInstanceName={name} />{" "}
+                    <GradeExportLinks courseInstanceId={instance.id} courseInstanceName={name} />
                     <ModuleCompletionReprocessButton courseInstanceId={instance.id} />
                   </li>
                 )
@@ -0,0 +1,39 @@
+import { useTranslation } from "react-i18next"
+
+import { GradeExportFormat } from "../../../../../../shared-module/bindings"
+
+const FORMATS: GradeExportFormat[] = ["Csv", "Xlsx", "MoodleCsv"]
+
+const GradeExportLinks: React.FC<
+  React.PropsWithChildren<{ courseInstanceId: string; courseInstanceName: string }>
+> = ({ courseInstanceId, courseInstanceName }) => {
+  const { t } = useTranslation()
+
+  const formatLabel = (format: GradeExportFormat) => {
+    switch (format) {
+      case "Csv":
+        return t("link-export-grades-csv")
+      case "Xlsx":
+        return t("link-export-grades-xlsx")
+      case "MoodleCsv":
+        return t("link-export-grades-moodle-csv")
+    }
+  }
+
+  return (
+    <>
+      {FORMATS.map((format) => (
+        <span key={format}>
+          <a
+            href={`/api/v0/main-frontend/course-instances/${courseInstanceId}/grades/export?format=${format}`}
+            aria-label={`${formatLabel(format)} (${courseInstanceName})`}
+          >
+            {formatLabel(format)}
+          </a>{" "}
+        </span>
+      ))}
+    </>
+  )
+}
+
+export default GradeExportLinks
@@ -23,6 +23,7 @@ import CourseLanguageVersionsList, {
 
 import UpdateCourseForm from "./UpdateCourseForm"
 import UpdatePeerReviewQueueReviewsReceivedButton from "./UpdatePeerReviewQueueReviewsReceivedButton"
+import ViewAsStudent from "./ViewAsStudent"
 
 interface Props {
   course: Course
@@ -151,6 +152,7 @@ const ManageCourse: React.FC<React.PropsWithChildren<Props>> = ({ course, refetc
       <CourseCourseInstances courseId={course.id} />
 
       <UpdatePeerReviewQueueReviewsReceivedButton courseId={course.id} />
+      <ViewAsStudent course={course} />
     </>
   )
 }
@@ -0,0 +1,63 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import { startImpersonation } from "../../../../../../services/backend/impersonation"
+import { fetchOrganization } from "../../../../../../services/backend/organizations"
+import { Course } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import TextField from "../../../../../../shared-module/components/InputFields/TextField"
+import OnlyRenderIfPermissions from "../../../../../../shared-module/components/OnlyRenderIfPermissions"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+
+interface Props {
+  course: Course
+}
+
+const ViewAsStudent: React.FC<React.PropsWithChildren<Props>> = ({ course }) => {
+  const { t } = useTranslation()
+  const [email, setEmail] = useState("")
+  const getOrganization = useQuery([`organization-${course.organization_id}`], () =>
+    fetchOrganization(course.organization_id),
+  )
+  const mutation = useToastMutation(
+    () => startImpersonation({ course_id: course.id, email }),
+    { notify: true, method: "POST" },
+    {
+      onSuccess: () => {
+        if (getOrganization.isSuccess) {
+          // the course material is served by another application
+          window.location.assign(`/org/${getOrganization.data.slug}/courses/${course.slug}`)
+        }
+      },
+    },
+  )
+
+  return (
+    <OnlyRenderIfPermissions
+      action={{ type: "impersonate" }}
+      resource={{ type: "course", id: course.id }}
+    >
+      <div
+        className={css`
+          margin: 1rem 0;
+        `}
+      >
+        <h2>{t("title-view-as-student")}</h2>
+        <p>{t("view-as-student-description")}</p>
+        <TextField label={t("label-email")} value={email} onChange={(value) => setEmail(value)} />
+        <Button
+          variant="secondary"
+          size="medium"
+          disabled={email === "" || !getOrganization.isSuccess || mutation.isLoading}
+          onClick={() => mutation.mutate()}
+        >
+          {t("button-text-view-as-student")}
+        </Button>
+      </div>
+    </OnlyRenderIfPermissions>
+  )
+}
+
+export default ViewAsStudent
@@ -4,7 +4,7 @@ import { useForm } from "react-hook-form"
 import { useTranslation } from "react-i18next"
 
 import { postNewChapter, updateChapter } from "../../../../../../services/backend/chapters"
-import { Chapter, NewChapter } from "../../../../../../shared-module/bindings"
+import { Chapter, ChapterUpdate, NewChapter } from "../../../../../../shared-module/bindings"
 import Button from "../../../../../../shared-module/components/Button"
 import CheckboxFieldWrapper from "../../../../../../shared-module/components/InputFields/CheckboxFieldWrapper"
 import DateTimeLocal from "../../../../../../shared-module/components/InputFields/DateTimeLocal"
@@ -26,8 +26,12 @@ interface Fields {
   opens_at: Date | null
   deadline: Date | null
   chapter_number: number
+  publish_at: Date | null
+  unpublish_at: Date | null
 }
 
+type ChapterFormData = NewChapter & Pick<ChapterUpdate, "publish_at" | "unpublish_at">
+
 const NewChapterForm: React.FC<React.PropsWithChildren<NewChapterFormProps>> = ({
   courseId,
   onSubmitForm,
@@ -51,12 +55,14 @@ const NewChapterForm: React.FC<React.PropsWithChildren<NewChapterFormProps>> = (
       chapter_number: chapterNumber,
       opens_at: null,
       deadline: null,
+      publish_at: null,
+      unpublish_at: null,
       ...initialData,
     },
   })
 
   const submitMutation = useToastMutation(
-    (data: NewChapter) => {
+    ({ publish_at, unpublish_at, ...data }: ChapterFormData) => {
       // Temp solution to retain module information without having a way to edit modules in frontend yet.
       data.course_module_id = initialData?.course_module_id ?? null
       if (newRecord) {
@@ -69,6 +75,8 @@ const NewChapterForm: React.FC<React.PropsWithChildren<NewChapterFormProps>> = (
       return updateChapter(initialData?.id, {
         ...data,
         course_module_id: initialData.course_module_id,
+        publish_at,
+        unpublish_at,
       })
     },
     { notify: true, method: "POST" },
@@ -77,6 +85,8 @@ const NewChapterForm: React.FC<React.PropsWithChildren<NewChapterFormProps>> = (
 
   const deadlineRegister = register("deadline", { valueAsDate: true, required: false })
   const opensAtRegister = register("opens_at", { valueAsDate: true, required: false })
+  const publishAtRegister = register("publish_at", { valueAsDate: true, required: false })
+  const unpublishAtRegister = register("unpublish_at", { valueAsDate: true, required: false })
   const chapterColorRegister = register("color", { required: false })
   return (
     <form
@@ -90,6 +100,8 @@ const NewChapterForm: React.FC<React.PropsWithChildren<NewChapterFormProps>> = (
           opens_at: data.opens_at,
           deadline: data.deadline,
           course_module_id: null,
+          publish_at: data.publish_at,
+          unpublish_at: data.unpublish_at,
         })
       })}
       className={css`
@@ -160,6 +172,44 @@ const NewChapterForm: React.FC<React.PropsWithChildren<NewChapterFormProps>> = (
           register={deadlineRegister}
         />
       </CheckboxFieldWrapper>
+      {!newRecord && (
+        <>
+          <CheckboxFieldWrapper
+            initialChecked={!!getValues("publish_at")}
+            fieldName={t("label-publish-at")}
+            onUncheck={() => setValue("publish_at", null)}
+          >
+            <DateTimeLocal
+              error={errors["publish_at"]?.message}
+              defaultValue={
+                initialData?.publish_at
+                  ? dateToDateTimeLocalString(initialData?.publish_at)
+                  : undefined
+              }
+              placeholder={t("label-publish-at")}
+              label={t("label-publish-at")}
+              register={publishAtRegister}
+            />
+          </CheckboxFieldWrapper>
+          <CheckboxFieldWrapper
+            initialChecked={!!getValues("unpublish_at")}
+            fieldName={t("label-unpublish-at")}
+            onUncheck={() => setValue("unpublish_at", null)}
+          >
+            <DateTimeLocal
+              error={errors["unpublish_at"]?.message}
+              defaultValue={
+                initialData?.unpublish_at
+                  ? dateToDateTimeLocalString(initialData?.unpublish_at)
+                  : undefined
+              }
+              placeholder={t("label-unpublish-at")}
+              label={t("label-unpublish-at")}
+              register={unpublishAtRegister}
+            />
+          </CheckboxFieldWrapper>
+        </>
+      )}
       <div>
         <Button variant="primary" size="medium" disabled={!isValid || isSubmitting}>
           {newRecord ? t("button-text-create") : t("button-text-update")}
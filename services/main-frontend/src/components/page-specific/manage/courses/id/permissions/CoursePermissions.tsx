@@ -61,6 +61,7 @@ const CoursePermissions: React.FC<React.PropsWithChildren<CourseManagementPagesP
               tag: "Course",
               id: course.data.id,
             }}
+            organizationId={course.data.organization_id}
           />
         </>
       )}
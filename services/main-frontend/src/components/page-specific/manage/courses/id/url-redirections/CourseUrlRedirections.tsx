@@ -0,0 +1,261 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import { CourseManagementPagesProps } from "../../../../../../pages/manage/courses/[id]/[...path]"
+import {
+  deleteUrlRedirection,
+  fetchCourseLanguageVersions,
+  fetchUrlRedirections,
+  postFlattenUrlRedirections,
+  postUrlRedirection,
+  putUrlRedirection,
+} from "../../../../../../services/backend/courses"
+import {
+  NewUrlRedirection,
+  UrlRedirectionProblem,
+  UrlRedirectionWithResolution,
+} from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import SelectField from "../../../../../../shared-module/components/InputFields/SelectField"
+import TextField from "../../../../../../shared-module/components/InputFields/TextField"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme, headingFont } from "../../../../../../shared-module/styles"
+
+const ProblemLabel: React.FC<{ problem: UrlRedirectionProblem }> = ({ problem }) => {
+  const { t } = useTranslation()
+  switch (problem) {
+    case "Chain":
+      return <>{t("url-redirection-problem-chain")}</>
+    case "Cycle":
+      return <>{t("url-redirection-problem-cycle")}</>
+    case "BrokenDestination":
+      return <>{t("url-redirection-problem-broken-destination")}</>
+  }
+}
+
+const CourseUrlRedirections: React.FC<React.PropsWithChildren<CourseManagementPagesProps>> = ({
+  courseId,
+}) => {
+  const { t } = useTranslation()
+  const [editingId, setEditingId] = useState<string | null>(null)
+  const [oldUrlPath, setOldUrlPath] = useState("")
+  const [destinationUrlPath, setDestinationUrlPath] = useState("")
+  const [destinationCourseId, setDestinationCourseId] = useState(courseId)
+  // redirections created when pages are moved follow the page until the destination is changed
+  const [destinationPageId, setDestinationPageId] = useState<string | null>(null)
+
+  const getRedirections = useQuery([`course-${courseId}-url-redirections`], () =>
+    fetchUrlRedirections(courseId),
+  )
+  const getLanguageVersions = useQuery([`course-${courseId}-language-versions`], () =>
+    fetchCourseLanguageVersions(courseId),
+  )
+
+  const resetForm = () => {
+    setEditingId(null)
+    setOldUrlPath("")
+    setDestinationUrlPath("")
+    setDestinationCourseId(courseId)
+    setDestinationPageId(null)
+  }
+  const startEditing = ({ redirection, resolution }: UrlRedirectionWithResolution) => {
+    setEditingId(redirection.id)
+    setOldUrlPath(redirection.old_url_path)
+    setDestinationUrlPath(redirection.destination_url_path ?? resolution.url_path ?? "")
+    setDestinationCourseId(redirection.destination_course_id ?? resolution.course_id ?? courseId)
+    setDestinationPageId(redirection.destination_page_id)
+  }
+  const form = (): NewUrlRedirection => {
+    if (destinationPageId) {
+      return {
+        old_url_path: oldUrlPath,
+        destination_page_id: destinationPageId,
+        destination_course_id: null,
+        destination_url_path: null,
+      }
+    }
+    return {
+      old_url_path: oldUrlPath,
+      destination_page_id: null,
+      destination_course_id: destinationCourseId === courseId ? null : destinationCourseId,
+      destination_url_path: destinationUrlPath,
+    }
+  }
+
+  const saveMutation = useToastMutation(
+    () =>
+      editingId === null
+        ? postUrlRedirection(courseId, form())
+        : putUrlRedirection(courseId, editingId, form()),
+    { notify: true, method: editingId === null ? "POST" : "PUT" },
+    {
+      onSuccess: () => {
+        resetForm()
+        getRedirections.refetch()
+      },
+    },
+  )
+  const deleteMutation = useToastMutation(
+    (redirectionId: string) => deleteUrlRedirection(courseId, redirectionId),
+    { notify: true, method: "DELETE" },
+    { onSuccess: () => getRedirections.refetch() },
+  )
+  const flattenMutation = useToastMutation(
+    () => postFlattenUrlRedirections(courseId),
+    { notify: true, method: "POST" },
+    { onSuccess: () => getRedirections.refetch() },
+  )
+
+  const courseSlug = (id: string | null) =>
+    getLanguageVersions.data?.find((course) => course.id === id)?.slug ?? ""
+  const chainCount =
+    getRedirections.data?.filter((r) => r.resolution.problem === "Chain").length ?? 0
+
+  return (
+    <div>
+      <h2
+        className={css`
+          font-size: clamp(2rem, 3.6vh, 36px);
+          color: ${baseTheme.colors.gray[700]};
+          font-family: ${headingFont};
+          font-weight: bold;
+        `}
+      >
+        {t("title-url-redirections")}
+      </h2>
+      <p>{t("url-redirections-explanation")}</p>
+      <div>
+        <TextField
+          label={t("label-old-url-path")}
+          placeholder={"/chapter-1/old-page"}
+          value={oldUrlPath}
+          onChange={setOldUrlPath}
+        />
+        {getLanguageVersions.isSuccess && getLanguageVersions.data.length > 1 && (
+          <SelectField
+            id="url-redirection-destination-course"
+            label={t("label-destination-course")}
+            value={destinationCourseId}
+            options={getLanguageVersions.data.map((course) => ({
+              value: course.id,
+              label: `${course.name} (${course.language_code})`,
+            }))}
+            onChange={(value) => {
+              setDestinationCourseId(value)
+              setDestinationPageId(null)
+            }}
+          />
+        )}
+        <TextField
+          label={t("label-destination-url-path")}
+          placeholder={"/chapter-1/new-page"}
+          value={destinationUrlPath}
+          onChange={(value) => {
+            setDestinationUrlPath(value)
+            setDestinationPageId(null)
+          }}
+        />
+        <Button
+          variant="primary"
+          size="medium"
+          disabled={saveMutation.isLoading}
+          onClick={() => saveMutation.mutate()}
+        >
+          {t("button-text-save")}
+        </Button>
+        {editingId !== null && (
+          <Button variant="tertiary" size="medium" onClick={resetForm}>
+            {t("button-text-cancel")}
+          </Button>
+        )}
+      </div>
+      {getRedirections.isError && <ErrorBanner variant="readOnly" error={getRedirections.error} />}
+      {getRedirections.isLoading && <Spinner variant="medium" />}
+      {getRedirections.isSuccess && (
+        <>
+          {chainCount > 0 && (
+            <p>
+              {t("url-redirection-chains-found", { count: chainCount })}{" "}
+              <Button
+                variant="secondary"
+                size="medium"
+                disabled={flattenMutation.isLoading}
+                onClick={() => flattenMutation.mutate()}
+              >
+                {t("button-flatten-redirection-chains")}
+              </Button>
+            </p>
+          )}
+          {getRedirections.data.length === 0 ? (
+            <p>{t("no-url-redirections")}</p>
+          ) : (
+            <table
+              className={css`
+                width: 100%;
+                border-collapse: collapse;
+                td,
+                th {
+                  padding: 0.5rem;
+                  text-align: left;
+                  border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+                }
+              `}
+            >
+              <thead>
+                <tr>
+                  <th>{t("label-old-url-path")}</th>
+                  <th>{t("label-destination")}</th>
+                  <th>{t("label-hits")}</th>
+                  <th>{t("label-last-hit")}</th>
+                  <th>{t("label-problem")}</th>
+                  <th />
+                </tr>
+              </thead>
+              <tbody>
+                {getRedirections.data.map((r) => (
+                  <tr key={r.redirection.id}>
+                    <td>
+                      <code>{r.redirection.old_url_path}</code>
+                    </td>
+                    <td>
+                      {r.resolution.url_path !== null && (
+                        <code>
+                          {r.resolution.course_id !== courseId &&
+                            `${courseSlug(r.resolution.course_id)}: `}
+                          {r.resolution.url_path}
+                        </code>
+                      )}
+                    </td>
+                    <td>{r.redirection.hit_count}</td>
+                    <td>{r.redirection.last_hit_at?.toLocaleString()}</td>
+                    <td>
+                      {r.resolution.problem && <ProblemLabel problem={r.resolution.problem} />}
+                    </td>
+                    <td>
+                      <Button variant="tertiary" size="small" onClick={() => startEditing(r)}>
+                        {t("edit")}
+                      </Button>
+                      <Button
+                        variant="tertiary"
+                        size="small"
+                        onClick={() => deleteMutation.mutate(r.redirection.id)}
+                      >
+                        {t("button-text-delete")}
+                      </Button>
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+        </>
+      )}
+    </div>
+  )
+}
+
+export default CourseUrlRedirections
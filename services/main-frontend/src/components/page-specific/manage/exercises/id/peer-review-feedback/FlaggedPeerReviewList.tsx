@@ -0,0 +1,99 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  fetchFlaggedPeerReviews,
+  postResolvePeerReviewFlag,
+} from "../../../../../../services/backend/exercises"
+import { PeerReviewSubmissionFlagResolution } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+import PeerReviewAccordion from "../submissions/PeerReviewAccordion"
+
+interface FlaggedPeerReviewListProps {
+  exerciseId: string
+}
+
+const FlaggedPeerReviewList: React.FC<FlaggedPeerReviewListProps> = ({ exerciseId }) => {
+  const { t } = useTranslation()
+  const getFlaggedPeerReviews = useQuery([`exercises-${exerciseId}-flagged-peer-reviews`], () =>
+    fetchFlaggedPeerReviews(exerciseId),
+  )
+  const resolveMutation = useToastMutation(
+    ({ flagId, resolution }: { flagId: string; resolution: PeerReviewSubmissionFlagResolution }) =>
+      postResolvePeerReviewFlag(exerciseId, flagId, { resolution }),
+    { notify: true, method: "POST" },
+    { onSuccess: () => getFlaggedPeerReviews.refetch() },
+  )
+
+  if (getFlaggedPeerReviews.isError) {
+    return <ErrorBanner variant={"readOnly"} error={getFlaggedPeerReviews.error} />
+  }
+  if (getFlaggedPeerReviews.isLoading) {
+    return <Spinner variant={"medium"} />
+  }
+  if (getFlaggedPeerReviews.data.length === 0) {
+    return <p>{t("no-flagged-peer-reviews")}</p>
+  }
+
+  return (
+    <>
+      {getFlaggedPeerReviews.data.map((flaggedPeerReview) => (
+        <div
+          key={flaggedPeerReview.flag.id}
+          className={css`
+            border-left: 4px solid ${baseTheme.colors.red[300]};
+            padding: 1rem;
+            margin-bottom: 2rem;
+          `}
+        >
+          <p>
+            {t("label-reviewer")}: {flaggedPeerReview.reviewer_user_id}
+          </p>
+          <p>
+            {t("label-report-reason")}: {flaggedPeerReview.flag.reason}
+          </p>
+          {flaggedPeerReview.peer_review && (
+            <PeerReviewAccordion
+              peerReviews={[flaggedPeerReview.peer_review]}
+              title={t("reported-peer-review")}
+            />
+          )}
+          <Button
+            variant="primary"
+            size="medium"
+            disabled={resolveMutation.isLoading}
+            onClick={() =>
+              resolveMutation.mutate({
+                flagId: flaggedPeerReview.flag.id,
+                resolution: "ReviewKept",
+              })
+            }
+          >
+            {t("button-text-keep-peer-review")}
+          </Button>
+          <Button
+            variant="reject"
+            size="medium"
+            disabled={resolveMutation.isLoading}
+            onClick={() =>
+              resolveMutation.mutate({
+                flagId: flaggedPeerReview.flag.id,
+                resolution: "ReviewRemoved",
+              })
+            }
+          >
+            {t("button-text-remove-peer-review")}
+          </Button>
+        </div>
+      ))}
+    </>
+  )
+}
+
+export default FlaggedPeerReviewList
@@ -0,0 +1,59 @@
+import { useQuery } from "@tanstack/react-query"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import { fetchPeerReviewReviewerStatistics } from "../../../../../../services/backend/exercises"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import FullWidthTable, { FullWidthTableRow } from "../../../../../tables/FullWidthTable"
+
+interface ReviewerStatisticsTableProps {
+  exerciseId: string
+}
+
+const ReviewerStatisticsTable: React.FC<ReviewerStatisticsTableProps> = ({ exerciseId }) => {
+  const { t } = useTranslation()
+  const getReviewerStatistics = useQuery(
+    [`exercises-${exerciseId}-peer-review-reviewer-statistics`],
+    () => fetchPeerReviewReviewerStatistics(exerciseId),
+  )
+
+  if (getReviewerStatistics.isError) {
+    return <ErrorBanner variant={"readOnly"} error={getReviewerStatistics.error} />
+  }
+  if (getReviewerStatistics.isLoading) {
+    return <Spinner variant={"medium"} />
+  }
+  if (getReviewerStatistics.data.length === 0) {
+    return <p>{t("no-data")}</p>
+  }
+
+  return (
+    <FullWidthTable>
+      <thead>
+        <tr>
+          <th>{t("label-user-id")}</th>
+          <th>{t("label-peer-reviews-given")}</th>
+          <th>{t("label-helpfulness-ratings-received")}</th>
+          <th>{t("label-average-helpfulness")}</th>
+          <th>{t("label-reports-received")}</th>
+          <th>{t("label-peer-reviews-removed")}</th>
+        </tr>
+      </thead>
+      <tbody>
+        {getReviewerStatistics.data.map((statistics) => (
+          <FullWidthTableRow key={statistics.user_id}>
+            <td>{statistics.user_id}</td>
+            <td>{statistics.peer_reviews_given}</td>
+            <td>{statistics.helpfulness_ratings_received}</td>
+            <td>{statistics.average_helpfulness?.toFixed(2) ?? "-"}</td>
+            <td>{statistics.flags_received}</td>
+            <td>{statistics.reviews_removed}</td>
+          </FullWidthTableRow>
+        ))}
+      </tbody>
+    </FullWidthTable>
+  )
+}
+
+export default ReviewerStatisticsTable
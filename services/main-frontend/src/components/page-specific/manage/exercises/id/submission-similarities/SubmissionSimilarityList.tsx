@@ -0,0 +1,128 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import Link from "next/link"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import { updateAnswerRequiringAttention } from "../../../../../../services/backend/answers-requiring-attention"
+import { fetchSubmissionSimilarities } from "../../../../../../services/backend/exercises"
+import { SimilarSubmission } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+interface SubmissionSimilarityListProps {
+  exerciseId: string
+}
+
+const SubmissionSimilarityList: React.FC<SubmissionSimilarityListProps> = ({ exerciseId }) => {
+  const { t } = useTranslation()
+  const getSubmissionSimilarities = useQuery(
+    [`exercises-${exerciseId}-submission-similarities`],
+    () => fetchSubmissionSimilarities(exerciseId),
+  )
+  const flagMutation = useToastMutation(
+    (userExerciseStateId: string) =>
+      updateAnswerRequiringAttention({
+        user_exercise_state_id: userExerciseStateId,
+        exercise_id: exerciseId,
+        // eslint-disable-next-line i18next/no-literal-string
+        action: "SuspectedPlagiarism",
+        manual_points: null,
+      }),
+    { notify: true, method: "PUT" },
+    { onSuccess: () => getSubmissionSimilarities.refetch() },
+  )
+
+  if (getSubmissionSimilarities.isError) {
+    return <ErrorBanner variant={"readOnly"} error={getSubmissionSimilarities.error} />
+  }
+  if (getSubmissionSimilarities.isLoading) {
+    return <Spinner variant={"medium"} />
+  }
+  if (getSubmissionSimilarities.data.length === 0) {
+    return <p>{t("no-similar-submissions")}</p>
+  }
+
+  const renderSubmission = (submission: SimilarSubmission) => (
+    <div
+      className={css`
+        flex: 1;
+        min-width: 0;
+      `}
+    >
+      <p>
+        {t("label-student")}: {submission.user_id}
+      </p>
+      <p>
+        {t("label-submission-time")}: {submission.created_at.toLocaleString()}
+      </p>
+      <Link
+        href={{
+          pathname: "/submissions/[id]",
+          query: { id: submission.exercise_slide_submission_id },
+        }}
+      >
+        {t("link")}
+      </Link>
+      <pre
+        className={css`
+          white-space: pre-wrap;
+          word-break: break-word;
+          background: ${baseTheme.colors.clear[100]};
+          padding: 0.5rem;
+        `}
+      >
+        {JSON.stringify(submission.data_json, null, 2)}
+      </pre>
+      {submission.latest_teacher_decision === "SuspectedPlagiarism" ? (
+        <p>{t("flagged-as-plagiarism")}</p>
+      ) : (
+        <Button
+          variant="reject"
+          size="medium"
+          disabled={flagMutation.isLoading || submission.user_exercise_state_id === null}
+          onClick={() => {
+            if (submission.user_exercise_state_id !== null) {
+              flagMutation.mutate(submission.user_exercise_state_id)
+            }
+          }}
+        >
+          {t("button-text-flag-as-plagiarism")}
+        </Button>
+      )}
+    </div>
+  )
+
+  return (
+    <>
+      {getSubmissionSimilarities.data.map((similarity) => (
+        <div
+          key={similarity.id}
+          className={css`
+            border-left: 4px solid ${baseTheme.colors.red[300]};
+            padding: 1rem;
+            margin-bottom: 2rem;
+          `}
+        >
+          <p>
+            {t("label-similarity")}: {Math.round(similarity.similarity * 100)} %
+          </p>
+          <div
+            className={css`
+              display: flex;
+              gap: 1rem;
+            `}
+          >
+            {renderSubmission(similarity.first)}
+            {renderSubmission(similarity.second)}
+          </div>
+        </div>
+      ))}
+    </>
+  )
+}
+
+export default SubmissionSimilarityList
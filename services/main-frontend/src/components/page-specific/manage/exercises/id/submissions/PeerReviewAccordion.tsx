@@ -50,6 +50,24 @@ const PeerReviewAccordion: React.FC<PeerReviewAccordionProps> = ({ peerReviews,
             />
           </div>
         )
+      case "rubric":
+        return (
+          <div>
+            <Question>{question}</Question>
+            <ul>
+              {answer.selected_levels.map((level) => (
+                <li key={level.level_id}>
+                  {`${level.criterion_name}: ${level.level_description} (${t("n-points", {
+                    n: level.points,
+                  })})`}
+                </li>
+              ))}
+            </ul>
+            <p>
+              {t("rubric-points-total", { points: answer.points, maxPoints: answer.max_points })}
+            </p>
+          </div>
+        )
       default:
         return (
           <div>
@@ -0,0 +1,181 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  createLtiRegistration,
+  deleteLtiRegistration,
+  fetchLtiRegistrations,
+} from "../../../../../../services/backend/lti"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import CheckBox from "../../../../../../shared-module/components/InputFields/CheckBox"
+import TextField from "../../../../../../shared-module/components/InputFields/TextField"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+interface Props {
+  organizationId: string
+}
+
+const LtiRegistrations: React.FC<React.PropsWithChildren<Props>> = ({ organizationId }) => {
+  const { t } = useTranslation()
+  const [name, setName] = useState("")
+  const [issuer, setIssuer] = useState("")
+  const [clientId, setClientId] = useState("")
+  const [authLoginUrl, setAuthLoginUrl] = useState("")
+  const [authTokenUrl, setAuthTokenUrl] = useState("")
+  const [jwksUrl, setJwksUrl] = useState("")
+  const [linkExistingUsersByEmail, setLinkExistingUsersByEmail] = useState(false)
+
+  const getRegistrations = useQuery([`lti-registrations`, organizationId], () =>
+    fetchLtiRegistrations(organizationId),
+  )
+
+  const resetForm = () => {
+    setName("")
+    setIssuer("")
+    setClientId("")
+    setAuthLoginUrl("")
+    setAuthTokenUrl("")
+    setJwksUrl("")
+    setLinkExistingUsersByEmail(false)
+  }
+
+  const createMutation = useToastMutation(
+    () =>
+      createLtiRegistration(organizationId, {
+        name,
+        issuer,
+        client_id: clientId,
+        auth_login_url: authLoginUrl,
+        auth_token_url: authTokenUrl,
+        jwks_url: jwksUrl,
+        link_existing_users_by_email: linkExistingUsersByEmail,
+      }),
+    { notify: true, method: "POST" },
+    {
+      onSuccess: () => {
+        resetForm()
+        getRegistrations.refetch()
+      },
+    },
+  )
+  const deleteMutation = useToastMutation(
+    (ltiRegistrationId: string) => deleteLtiRegistration(organizationId, ltiRegistrationId),
+    { notify: true, method: "DELETE" },
+    { onSuccess: () => getRegistrations.refetch() },
+  )
+
+  const toolUrl = (path: string) =>
+    typeof window === "undefined" ? path : `${window.location.origin}${path}`
+
+  return (
+    <div>
+      <p>{t("lti-platforms-explanation")}</p>
+      <dl>
+        <dt>{t("label-lti-login-url")}</dt>
+        <dd>
+          <code>{toolUrl("/api/v0/lti/login")}</code>
+        </dd>
+        <dt>{t("label-lti-launch-url")}</dt>
+        <dd>
+          <code>{toolUrl("/api/v0/lti/launch")}</code>
+        </dd>
+        <dt>{t("label-lti-jwks-url")}</dt>
+        <dd>
+          <code>{toolUrl("/api/v0/lti/jwks")}</code>
+        </dd>
+      </dl>
+      {getRegistrations.isError && (
+        <ErrorBanner variant="readOnly" error={getRegistrations.error} />
+      )}
+      {getRegistrations.isLoading && <Spinner variant="medium" />}
+      {getRegistrations.isSuccess && getRegistrations.data.length === 0 && (
+        <p>{t("no-lti-platforms")}</p>
+      )}
+      {getRegistrations.isSuccess &&
+        getRegistrations.data.map((registration) => (
+          <div
+            key={registration.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <h3>{registration.name}</h3>
+            <p>
+              {t("label-lti-issuer")}: <code>{registration.issuer}</code>
+            </p>
+            <p>
+              {t("label-lti-client-id")}: <code>{registration.client_id}</code>
+            </p>
+            {registration.link_existing_users_by_email && (
+              <p>{t("label-lti-link-existing-users-by-email")}</p>
+            )}
+            <Button
+              variant="secondary"
+              size="medium"
+              disabled={deleteMutation.isLoading}
+              onClick={() => deleteMutation.mutate(registration.id)}
+            >
+              {t("button-text-delete")}
+            </Button>
+          </div>
+        ))}
+      <h3>{t("title-new-lti-platform")}</h3>
+      <TextField label={t("label-name")} value={name} onChange={setName} />
+      <TextField
+        label={t("label-lti-issuer")}
+        placeholder={"https://moodle.example.com"}
+        value={issuer}
+        onChange={setIssuer}
+      />
+      <TextField label={t("label-lti-client-id")} value={clientId} onChange={setClientId} />
+      <TextField
+        label={t("label-lti-auth-login-url")}
+        placeholder={"https://moodle.example.com/mod/lti/auth.php"}
+        value={authLoginUrl}
+        onChange={setAuthLoginUrl}
+      />
+      <TextField
+        label={t("label-lti-auth-token-url")}
+        placeholder={"https://moodle.example.com/mod/lti/token.php"}
+        value={authTokenUrl}
+        onChange={setAuthTokenUrl}
+      />
+      <TextField
+        label={t("label-lti-platform-jwks-url")}
+        placeholder={"https://moodle.example.com/mod/lti/certs.php"}
+        value={jwksUrl}
+        onChange={setJwksUrl}
+      />
+      <CheckBox
+        label={t("label-lti-link-existing-users-by-email")}
+        checked={linkExistingUsersByEmail}
+        onChange={(checked) => setLinkExistingUsersByEmail(checked)}
+      />
+      <p>{t("lti-link-existing-users-by-email-explanation")}</p>
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={
+          name === "" ||
+          issuer === "" ||
+          clientId === "" ||
+          authLoginUrl === "" ||
+          authTokenUrl === "" ||
+          jwksUrl === "" ||
+          createMutation.isLoading
+        }
+        onClick={() => createMutation.mutate()}
+      >
+        {t("button-text-create")}
+      </Button>
+    </div>
+  )
+}
+
+export default LtiRegistrations
@@ -0,0 +1,179 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import { TFunction } from "i18next"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  createCustomRole,
+  deleteCustomRole,
+  fetchBuiltInRolePermissions,
+  fetchCustomRolesForOrganization,
+} from "../../../../../../services/backend/custom-roles"
+import { NewRolePermission, UserRole } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import CheckBox from "../../../../../../shared-module/components/InputFields/CheckBox"
+import SelectField from "../../../../../../shared-module/components/InputFields/SelectField"
+import TextField from "../../../../../../shared-module/components/InputFields/TextField"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+const ASSISTANT: UserRole = "Assistant"
+const REVIEWER: UserRole = "Reviewer"
+const TEACHER: UserRole = "Teacher"
+const MATERIAL_VIEWER: UserRole = "MaterialViewer"
+
+const baseRoleOptions = (t: TFunction) => {
+  return [
+    { value: ASSISTANT, label: t("role-assistant") },
+    { value: REVIEWER, label: t("role-reviewer") },
+    { value: TEACHER, label: t("role-teacher") },
+    { value: MATERIAL_VIEWER, label: t("role-material-viewer") },
+  ]
+}
+
+const permissionLabel = (permission: NewRolePermission): string => {
+  let label: string = permission.action
+  if (permission.action_role) {
+    label += ` (${permission.action_role})`
+  }
+  if (permission.resource_type) {
+    label += `: ${permission.resource_type}`
+  }
+  return label
+}
+
+interface Props {
+  organizationId: string
+}
+
+const CustomRoles: React.FC<React.PropsWithChildren<Props>> = ({ organizationId }) => {
+  const { t } = useTranslation()
+  const [name, setName] = useState("")
+  const [baseRole, setBaseRole] = useState<UserRole>(ASSISTANT)
+  const [selectedPermissionIds, setSelectedPermissionIds] = useState<string[]>([])
+
+  const getCustomRoles = useQuery([`custom-roles`, organizationId], () =>
+    fetchCustomRolesForOrganization(organizationId),
+  )
+  const getBuiltInPermissions = useQuery(
+    [`built-in-role-permissions`, organizationId, baseRole],
+    () => fetchBuiltInRolePermissions(organizationId, baseRole),
+  )
+  const createMutation = useToastMutation(
+    () =>
+      createCustomRole(organizationId, {
+        name,
+        base_role: baseRole,
+        permissions: (getBuiltInPermissions.data ?? [])
+          .filter((permission) => selectedPermissionIds.includes(permission.id))
+          .map((permission) => ({
+            action: permission.action,
+            action_role: permission.action_role,
+            resource_type: permission.resource_type,
+          })),
+      }),
+    { notify: true, method: "POST" },
+    {
+      onSuccess: () => {
+        setName("")
+        setSelectedPermissionIds([])
+        getCustomRoles.refetch()
+      },
+    },
+  )
+  const deleteMutation = useToastMutation(
+    deleteCustomRole,
+    { notify: true, method: "DELETE" },
+    { onSuccess: () => getCustomRoles.refetch() },
+  )
+
+  return (
+    <div
+      className={css`
+        margin-top: 2rem;
+      `}
+    >
+      <h3>{t("title-custom-roles")}</h3>
+      <p>{t("custom-roles-description")}</p>
+      {getCustomRoles.isError && <ErrorBanner variant={"readOnly"} error={getCustomRoles.error} />}
+      {getCustomRoles.isLoading && <Spinner variant={"medium"} />}
+      {getCustomRoles.isSuccess && getCustomRoles.data.length === 0 && (
+        <p>{t("no-custom-roles")}</p>
+      )}
+      {getCustomRoles.isSuccess &&
+        getCustomRoles.data.map(({ custom_role, permissions }) => (
+          <div
+            key={custom_role.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <h4>
+              {custom_role.name} ({custom_role.base_role})
+            </h4>
+            <ul>
+              {permissions.map((permission) => (
+                <li key={permission.id}>{permissionLabel(permission)}</li>
+              ))}
+            </ul>
+            <Button
+              variant="secondary"
+              size="medium"
+              disabled={deleteMutation.isLoading}
+              onClick={() => deleteMutation.mutate(custom_role.id)}
+            >
+              {t("button-text-delete")}
+            </Button>
+          </div>
+        ))}
+      <h4>{t("title-new-custom-role")}</h4>
+      <TextField
+        label={t("text-field-label-name")}
+        value={name}
+        onChange={(value) => setName(value)}
+      />
+      <SelectField
+        id={"custom-role-base-role"}
+        label={t("label-base-role")}
+        onChange={(value) => {
+          setBaseRole(value)
+          setSelectedPermissionIds([])
+        }}
+        options={baseRoleOptions(t)}
+        defaultValue={ASSISTANT}
+      />
+      {getBuiltInPermissions.isError && (
+        <ErrorBanner variant={"readOnly"} error={getBuiltInPermissions.error} />
+      )}
+      {getBuiltInPermissions.isSuccess &&
+        getBuiltInPermissions.data.map((permission) => (
+          <CheckBox
+            key={permission.id}
+            label={permissionLabel(permission)}
+            checked={selectedPermissionIds.includes(permission.id)}
+            onChange={(checked) =>
+              setSelectedPermissionIds((previous) =>
+                checked
+                  ? [...previous, permission.id]
+                  : previous.filter((id) => id !== permission.id),
+              )
+            }
+          />
+        ))}
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={name === "" || createMutation.isLoading}
+        onClick={() => createMutation.mutate()}
+      >
+        {t("button-text-create")}
+      </Button>
+    </div>
+  )
+}
+
+export default CustomRoles
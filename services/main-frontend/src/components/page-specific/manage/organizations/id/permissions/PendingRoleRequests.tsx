@@ -0,0 +1,93 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  approveRoleRequest,
+  fetchPendingRoleRequestsForOrganization,
+  rejectRoleRequest,
+} from "../../../../../../services/backend/role-requests"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+interface Props {
+  organizationId: string
+}
+
+const PendingRoleRequests: React.FC<React.PropsWithChildren<Props>> = ({ organizationId }) => {
+  const { t } = useTranslation()
+  const getPendingRoleRequests = useQuery([`pending-role-requests`, organizationId], () =>
+    fetchPendingRoleRequestsForOrganization(organizationId),
+  )
+  const approveMutation = useToastMutation(
+    approveRoleRequest,
+    { notify: true, method: "POST" },
+    { onSuccess: () => getPendingRoleRequests.refetch() },
+  )
+  const rejectMutation = useToastMutation(
+    rejectRoleRequest,
+    { notify: true, method: "POST" },
+    { onSuccess: () => getPendingRoleRequests.refetch() },
+  )
+
+  return (
+    <div
+      className={css`
+        margin-top: 2rem;
+      `}
+    >
+      <h3>{t("title-pending-role-requests")}</h3>
+      {getPendingRoleRequests.isError && (
+        <ErrorBanner variant={"readOnly"} error={getPendingRoleRequests.error} />
+      )}
+      {getPendingRoleRequests.isLoading && <Spinner variant={"medium"} />}
+      {getPendingRoleRequests.isSuccess && getPendingRoleRequests.data.length === 0 && (
+        <p>{t("no-pending-role-requests")}</p>
+      )}
+      {getPendingRoleRequests.isSuccess &&
+        getPendingRoleRequests.data.map((roleRequest) => (
+          <div
+            key={roleRequest.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <p>
+              {roleRequest.user_email}: {roleRequest.role} ({roleRequest.course_name})
+            </p>
+            {roleRequest.valid_until && (
+              <p>
+                {t("label-valid-until")}: {roleRequest.valid_until.toLocaleString()}
+              </p>
+            )}
+            <p>
+              {t("label-reason")}: {roleRequest.reason}
+            </p>
+            <Button
+              variant="primary"
+              size="medium"
+              disabled={approveMutation.isLoading || rejectMutation.isLoading}
+              onClick={() => approveMutation.mutate(roleRequest.id)}
+            >
+              {t("button-text-approve")}
+            </Button>
+            <Button
+              variant="secondary"
+              size="medium"
+              disabled={approveMutation.isLoading || rejectMutation.isLoading}
+              onClick={() => rejectMutation.mutate(roleRequest.id)}
+            >
+              {t("button-text-reject")}
+            </Button>
+          </div>
+        ))}
+    </div>
+  )
+}
+
+export default PendingRoleRequests
@@ -0,0 +1,187 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  fetchWebhookDeliveries,
+  fetchWebhookDeliveryAttempts,
+  postRedeliverWebhookDelivery,
+} from "../../../../../../services/backend/webhooks"
+import { WebhookDeliveryStatus } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+import { webhookEventTypeLabel } from "./webhookEventTypes"
+
+const DELIVERIES_PER_PAGE = 20
+
+const StatusLabel: React.FC<{ status: WebhookDeliveryStatus }> = ({ status }) => {
+  const { t } = useTranslation()
+  switch (status) {
+    case "Pending":
+      return <>{t("webhook-delivery-status-pending")}</>
+    case "Delivered":
+      return <>{t("webhook-delivery-status-delivered")}</>
+    case "Failed":
+      return <>{t("webhook-delivery-status-failed")}</>
+  }
+}
+
+const DeliveryAttempts: React.FC<{ organizationId: string; deliveryId: string }> = ({
+  organizationId,
+  deliveryId,
+}) => {
+  const { t } = useTranslation()
+  const getAttempts = useQuery([`webhook-delivery-attempts`, deliveryId], () =>
+    fetchWebhookDeliveryAttempts(organizationId, deliveryId),
+  )
+  if (getAttempts.isError) {
+    return <ErrorBanner variant="readOnly" error={getAttempts.error} />
+  }
+  if (getAttempts.isLoading) {
+    return <Spinner variant="small" />
+  }
+  if (getAttempts.data.length === 0) {
+    return <p>{t("webhook-no-delivery-attempts")}</p>
+  }
+  return (
+    <ul>
+      {getAttempts.data.map((attempt) => (
+        <li key={attempt.id}>
+          {attempt.created_at.toLocaleString()}:{" "}
+          {attempt.response_status !== null
+            ? t("webhook-attempt-response", {
+                status: attempt.response_status,
+                duration: attempt.duration_ms,
+              })
+            : attempt.error}
+        </li>
+      ))}
+    </ul>
+  )
+}
+
+interface Props {
+  organizationId: string
+  webhookId: string
+}
+
+const WebhookDeliveries: React.FC<React.PropsWithChildren<Props>> = ({
+  organizationId,
+  webhookId,
+}) => {
+  const { t } = useTranslation()
+  const [page, setPage] = useState(1)
+  const [openDeliveryId, setOpenDeliveryId] = useState<string | null>(null)
+  const getDeliveries = useQuery([`webhook-deliveries`, webhookId, page], () =>
+    fetchWebhookDeliveries(organizationId, webhookId, page, DELIVERIES_PER_PAGE),
+  )
+  const redeliverMutation = useToastMutation(
+    (deliveryId: string) => postRedeliverWebhookDelivery(organizationId, deliveryId),
+    { notify: true, method: "POST" },
+    { onSuccess: () => getDeliveries.refetch() },
+  )
+
+  return (
+    <div>
+      <h4>{t("title-webhook-deliveries")}</h4>
+      {getDeliveries.isError && <ErrorBanner variant="readOnly" error={getDeliveries.error} />}
+      {getDeliveries.isLoading && <Spinner variant="medium" />}
+      {getDeliveries.isSuccess && getDeliveries.data.length === 0 && (
+        <p>{t("webhook-no-deliveries")}</p>
+      )}
+      {getDeliveries.isSuccess && getDeliveries.data.length > 0 && (
+        <table
+          className={css`
+            width: 100%;
+            border-collapse: collapse;
+            td,
+            th {
+              padding: 0.5rem;
+              text-align: left;
+              vertical-align: top;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            }
+          `}
+        >
+          <thead>
+            <tr>
+              <th>{t("label-created-at")}</th>
+              <th>{t("label-event")}</th>
+              <th>{t("status")}</th>
+              <th>{t("label-attempts")}</th>
+              <th />
+            </tr>
+          </thead>
+          <tbody>
+            {getDeliveries.data.map((delivery) => (
+              <tr key={delivery.id}>
+                <td>{delivery.created_at.toLocaleString()}</td>
+                <td>{webhookEventTypeLabel(t, delivery.event_type)}</td>
+                <td>
+                  <StatusLabel status={delivery.status} />
+                  {delivery.status === "Pending" && delivery.attempts > 0 && (
+                    <div>
+                      {t("webhook-next-attempt-at", {
+                        time: delivery.next_attempt_at.toLocaleString(),
+                      })}
+                    </div>
+                  )}
+                </td>
+                <td>
+                  {delivery.attempts}
+                  {openDeliveryId === delivery.id && (
+                    <DeliveryAttempts organizationId={organizationId} deliveryId={delivery.id} />
+                  )}
+                </td>
+                <td>
+                  <Button
+                    variant="tertiary"
+                    size="small"
+                    onClick={() =>
+                      setOpenDeliveryId(openDeliveryId === delivery.id ? null : delivery.id)
+                    }
+                  >
+                    {openDeliveryId === delivery.id
+                      ? t("button-hide-attempts")
+                      : t("button-show-attempts")}
+                  </Button>
+                  <Button
+                    variant="tertiary"
+                    size="small"
+                    disabled={redeliverMutation.isLoading}
+                    onClick={() => redeliverMutation.mutate(delivery.id)}
+                  >
+                    {t("button-redeliver")}
+                  </Button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+      <Button
+        variant="secondary"
+        size="small"
+        disabled={page <= 1}
+        onClick={() => setPage(page - 1)}
+      >
+        {t("button-newer-deliveries")}
+      </Button>
+      <Button
+        variant="secondary"
+        size="small"
+        disabled={!getDeliveries.isSuccess || getDeliveries.data.length < DELIVERIES_PER_PAGE}
+        onClick={() => setPage(page + 1)}
+      >
+        {t("button-older-deliveries")}
+      </Button>
+    </div>
+  )
+}
+
+export default WebhookDeliveries
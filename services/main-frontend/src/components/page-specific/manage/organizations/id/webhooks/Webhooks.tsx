@@ -0,0 +1,173 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  createWebhookSubscription,
+  deleteWebhookSubscription,
+  fetchWebhookSubscriptions,
+  updateWebhookSubscription,
+} from "../../../../../../services/backend/webhooks"
+import { WebhookEventType, WebhookSubscription } from "../../../../../../shared-module/bindings"
+import Button from "../../../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import CheckBox from "../../../../../../shared-module/components/InputFields/CheckBox"
+import TextField from "../../../../../../shared-module/components/InputFields/TextField"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+import WebhookDeliveries from "./WebhookDeliveries"
+import { WEBHOOK_EVENT_TYPES, webhookEventTypeLabel } from "./webhookEventTypes"
+
+interface Props {
+  organizationId: string
+}
+
+const Webhooks: React.FC<React.PropsWithChildren<Props>> = ({ organizationId }) => {
+  const { t } = useTranslation()
+  const [editingId, setEditingId] = useState<string | null>(null)
+  const [url, setUrl] = useState("")
+  const [eventTypes, setEventTypes] = useState<WebhookEventType[]>([])
+  const [enabled, setEnabled] = useState(true)
+  const [openWebhookId, setOpenWebhookId] = useState<string | null>(null)
+
+  const getSubscriptions = useQuery([`webhooks`, organizationId], () =>
+    fetchWebhookSubscriptions(organizationId),
+  )
+
+  const resetForm = () => {
+    setEditingId(null)
+    setUrl("")
+    setEventTypes([])
+    setEnabled(true)
+  }
+  const startEditing = (subscription: WebhookSubscription) => {
+    setEditingId(subscription.id)
+    setUrl(subscription.url)
+    setEventTypes(subscription.event_types)
+    setEnabled(subscription.enabled)
+  }
+
+  const saveMutation = useToastMutation(
+    () => {
+      const subscription = { url, event_types: eventTypes, enabled }
+      return editingId === null
+        ? createWebhookSubscription(organizationId, subscription)
+        : updateWebhookSubscription(organizationId, editingId, subscription)
+    },
+    { notify: true, method: editingId === null ? "POST" : "PUT" },
+    {
+      onSuccess: () => {
+        resetForm()
+        getSubscriptions.refetch()
+      },
+    },
+  )
+  const deleteMutation = useToastMutation(
+    (webhookId: string) => deleteWebhookSubscription(organizationId, webhookId),
+    { notify: true, method: "DELETE" },
+    { onSuccess: () => getSubscriptions.refetch() },
+  )
+
+  return (
+    <div>
+      <p>{t("webhooks-explanation")}</p>
+      {getSubscriptions.isError && (
+        <ErrorBanner variant="readOnly" error={getSubscriptions.error} />
+      )}
+      {getSubscriptions.isLoading && <Spinner variant="medium" />}
+      {getSubscriptions.isSuccess && getSubscriptions.data.length === 0 && (
+        <p>{t("no-webhooks")}</p>
+      )}
+      {getSubscriptions.isSuccess &&
+        getSubscriptions.data.map((subscription) => (
+          <div
+            key={subscription.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <h3>
+              <code>{subscription.url}</code>{" "}
+              {!subscription.enabled && <span>({t("webhook-disabled")})</span>}
+            </h3>
+            <ul>
+              {subscription.event_types.map((eventType) => (
+                <li key={eventType}>{webhookEventTypeLabel(t, eventType)}</li>
+              ))}
+            </ul>
+            <p>
+              {t("label-signing-secret")}: <code>{subscription.secret}</code>
+            </p>
+            <Button variant="tertiary" size="medium" onClick={() => startEditing(subscription)}>
+              {t("edit")}
+            </Button>
+            <Button
+              variant="tertiary"
+              size="medium"
+              onClick={() =>
+                setOpenWebhookId(openWebhookId === subscription.id ? null : subscription.id)
+              }
+            >
+              {openWebhookId === subscription.id
+                ? t("button-hide-deliveries")
+                : t("button-show-deliveries")}
+            </Button>
+            <Button
+              variant="secondary"
+              size="medium"
+              disabled={deleteMutation.isLoading}
+              onClick={() => deleteMutation.mutate(subscription.id)}
+            >
+              {t("button-text-delete")}
+            </Button>
+            {openWebhookId === subscription.id && (
+              <WebhookDeliveries organizationId={organizationId} webhookId={subscription.id} />
+            )}
+          </div>
+        ))}
+      <h3>{editingId === null ? t("title-new-webhook") : t("title-edit-webhook")}</h3>
+      <TextField
+        label={t("label-url")}
+        placeholder={"https://example.com/webhooks"}
+        value={url}
+        onChange={setUrl}
+      />
+      {WEBHOOK_EVENT_TYPES.map((eventType) => (
+        <CheckBox
+          key={eventType}
+          label={webhookEventTypeLabel(t, eventType)}
+          checked={eventTypes.includes(eventType)}
+          onChange={(checked) =>
+            setEventTypes((previous) =>
+              checked ? [...previous, eventType] : previous.filter((e) => e !== eventType),
+            )
+          }
+        />
+      ))}
+      <CheckBox
+        label={t("label-webhook-enabled")}
+        checked={enabled}
+        onChange={(checked) => setEnabled(checked)}
+      />
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={url === "" || eventTypes.length === 0 || saveMutation.isLoading}
+        onClick={() => saveMutation.mutate()}
+      >
+        {editingId === null ? t("button-text-create") : t("button-text-save")}
+      </Button>
+      {editingId !== null && (
+        <Button variant="tertiary" size="medium" onClick={resetForm}>
+          {t("button-text-cancel")}
+        </Button>
+      )}
+    </div>
+  )
+}
+
+export default Webhooks
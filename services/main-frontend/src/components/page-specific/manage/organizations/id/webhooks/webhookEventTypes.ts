@@ -0,0 +1,26 @@
+import { TFunction } from "i18next"
+
+import { WebhookEventType } from "../../../../../../shared-module/bindings"
+
+export const WEBHOOK_EVENT_TYPES: WebhookEventType[] = [
+  "enrollment_created",
+  "module_completion_granted",
+  "exam_ended",
+  "submission_graded",
+  "peer_review_received",
+]
+
+export const webhookEventTypeLabel = (t: TFunction, eventType: WebhookEventType): string => {
+  switch (eventType) {
+    case "enrollment_created":
+      return t("webhook-event-enrollment-created")
+    case "module_completion_granted":
+      return t("webhook-event-module-completion-granted")
+    case "exam_ended":
+      return t("webhook-event-exam-ended")
+    case "submission_graded":
+      return t("webhook-event-submission-graded")
+    case "peer_review_received":
+      return t("webhook-event-peer-review-received")
+  }
+}
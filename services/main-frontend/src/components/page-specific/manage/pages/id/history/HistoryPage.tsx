@@ -68,6 +68,11 @@ const HistoryPage: React.FC<React.PropsWithChildren<Props>> = ({
                   user: h.author_user_id,
                   time: h.created_at,
                 })}
+              {h.history_change_reason === "DraftPublished" &&
+                t("draft-published-by-on", {
+                  user: h.author_user_id,
+                  time: h.created_at,
+                })}
               {h.history_change_reason === "HistoryRestored" &&
                 t("edited-by-on", {
                   id: h.restored_from_id,
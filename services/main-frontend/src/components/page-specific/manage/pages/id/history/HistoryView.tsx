@@ -11,6 +11,7 @@ import MonacoDiffEditor from "../../../../../../shared-module/components/monaco/
 import replaceUuidsWithPlaceholdersInText from "../../../../../../shared-module/utils/testing/replaceUuidsWithPlaceholders"
 
 import HistoryList from "./HistoryList"
+import PageHistoryDiffView from "./PageHistoryDiffView"
 
 interface Props {
   pageId: string
@@ -22,6 +23,8 @@ const HistoryView: React.FC<React.PropsWithChildren<Props>> = ({ pageId }) => {
   const [selectedTitle, setSelectedTitle] = useState<string | null>(null)
   const [currentRevision, setCurrentRevision] = useState<string | null>(null)
   const [selectedRevision, setSelectedRevision] = useState<string | null>(null)
+  const [currentRevisionId, setCurrentRevisionId] = useState<string | null>(null)
+  const [selectedRevisionId, setSelectedRevisionId] = useState<string | null>(null)
 
   const getCurrentPageHistory = useQuery([`page-history-current-${pageId}`], async () => {
     const history = await fetchHistoryForPage(pageId, 1, 1)
@@ -34,6 +37,8 @@ const HistoryView: React.FC<React.PropsWithChildren<Props>> = ({ pageId }) => {
     setSelectedTitle(history[0].title)
     setCurrentRevision(initial)
     setSelectedRevision(initial)
+    setCurrentRevisionId(history[0].id)
+    setSelectedRevisionId(history[0].id)
     return history[0]
   })
 
@@ -53,11 +58,13 @@ const HistoryView: React.FC<React.PropsWithChildren<Props>> = ({ pageId }) => {
   function onCompare(ph: PageHistory) {
     setSelectedTitle(ph.title)
     setSelectedRevision(JSON.stringify(ph.content, null, 2))
+    setSelectedRevisionId(ph.id)
   }
 
   async function onRestore(ph: PageHistory) {
     setCurrentTitle(ph.title)
     setCurrentRevision(JSON.stringify(ph.content, null, 2))
+    setCurrentRevisionId(ph.id)
   }
 
   return (
@@ -86,6 +93,13 @@ const HistoryView: React.FC<React.PropsWithChildren<Props>> = ({ pageId }) => {
             modified={selectedRevision || t("loading-text")}
             options={{ readOnly: true }}
           />
+          {currentRevisionId && selectedRevisionId && currentRevisionId !== selectedRevisionId && (
+            <PageHistoryDiffView
+              pageId={pageId}
+              oldHistoryId={selectedRevisionId}
+              newHistoryId={currentRevisionId}
+            />
+          )}
           <HistoryList
             pageId={pageId}
             initialSelectedRevisionId={getCurrentPageHistory.data.id}
@@ -0,0 +1,173 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import { fetchPageHistoryDiff } from "../../../../../../services/backend/pages"
+import {
+  BlockChange,
+  ChangeKind,
+  ItemChange,
+  TextDiffPart,
+  ValueChange,
+} from "../../../../../../shared-module/bindings"
+import ErrorBanner from "../../../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../../../shared-module/components/Spinner"
+import { baseTheme } from "../../../../../../shared-module/styles"
+
+interface Props {
+  pageId: string
+  oldHistoryId: string
+  newHistoryId: string
+}
+
+const added = css`
+  background-color: ${baseTheme.colors.green[100]};
+  text-decoration: none;
+`
+
+const removed = css`
+  background-color: ${baseTheme.colors.crimson[100]};
+`
+
+const TextDiff: React.FC<{ parts: Array<TextDiffPart> }> = ({ parts }) => (
+  <span>
+    {parts.map((part, i) => {
+      if (part.kind === "Added") {
+        return (
+          <ins key={i} className={added}>
+            {part.text}
+          </ins>
+        )
+      }
+      if (part.kind === "Removed") {
+        return (
+          <del key={i} className={removed}>
+            {part.text}
+          </del>
+        )
+      }
+      return <span key={i}>{part.text}</span>
+    })}
+  </span>
+)
+
+const ValueChanges: React.FC<{ changes: Array<ValueChange> }> = ({ changes }) => (
+  <ul>
+    {changes.map((change) => (
+      <li key={change.key}>
+        <code>{change.key}</code>:{" "}
+        {change.text_diff ? (
+          <TextDiff parts={change.text_diff} />
+        ) : (
+          <>
+            {change.old_value !== null && (
+              <del className={removed}>{JSON.stringify(change.old_value)}</del>
+            )}{" "}
+            {change.new_value !== null && (
+              <ins className={added}>{JSON.stringify(change.new_value)}</ins>
+            )}
+          </>
+        )}
+      </li>
+    ))}
+  </ul>
+)
+
+const ChangeKindLabel: React.FC<{ kind: ChangeKind; moved?: boolean }> = ({ kind, moved }) => {
+  const { t } = useTranslation()
+  return (
+    <strong>
+      {kind === "Added" && t("change-added")}
+      {kind === "Removed" && t("change-removed")}
+      {kind === "Modified" && (moved ? t("change-moved") : t("change-modified"))}
+    </strong>
+  )
+}
+
+const BlockChanges: React.FC<{ changes: Array<BlockChange> }> = ({ changes }) => (
+  <>
+    {changes.map((change) => (
+      <div key={change.client_id}>
+        <ChangeKindLabel kind={change.kind} moved={change.moved} /> <code>{change.name}</code>
+        <ValueChanges changes={change.attribute_changes} />
+      </div>
+    ))}
+  </>
+)
+
+const ItemChanges: React.FC<{ title: string; changes: Array<ItemChange> }> = ({
+  title,
+  changes,
+}) => {
+  if (changes.length === 0) {
+    return null
+  }
+  return (
+    <>
+      <h3>{title}</h3>
+      {changes.map((change) => (
+        <div key={change.id}>
+          <ChangeKindLabel kind={change.kind} /> <code>{change.id}</code>
+          <ValueChanges changes={change.field_changes} />
+        </div>
+      ))}
+    </>
+  )
+}
+
+const PageHistoryDiffView: React.FC<React.PropsWithChildren<Props>> = ({
+  pageId,
+  oldHistoryId,
+  newHistoryId,
+}) => {
+  const { t } = useTranslation()
+  const getDiff = useQuery([`page-history-diff-${pageId}-${oldHistoryId}-${newHistoryId}`], () =>
+    fetchPageHistoryDiff(pageId, oldHistoryId, newHistoryId),
+  )
+
+  if (getDiff.isError) {
+    return <ErrorBanner variant={"readOnly"} error={getDiff.error} />
+  }
+  if (getDiff.isLoading) {
+    return <Spinner variant={"medium"} />
+  }
+
+  const diff = getDiff.data
+  const noChanges =
+    diff.title_diff === null &&
+    diff.blocks.length === 0 &&
+    diff.exercises.length === 0 &&
+    diff.exercise_slides.length === 0 &&
+    diff.exercise_tasks.length === 0 &&
+    diff.peer_review_configs.length === 0 &&
+    diff.peer_review_questions.length === 0
+  return (
+    <div
+      className={css`
+        margin: 1rem 0;
+      `}
+    >
+      <h2>{t("title-changes")}</h2>
+      {noChanges && <p>{t("no-changes")}</p>}
+      {diff.title_diff && (
+        <p>
+          {t("label-title")}: <TextDiff parts={diff.title_diff} />
+        </p>
+      )}
+      {diff.blocks.length > 0 && (
+        <>
+          <h3>{t("title-content")}</h3>
+          <BlockChanges changes={diff.blocks} />
+        </>
+      )}
+      <ItemChanges title={t("title-exercises")} changes={diff.exercises} />
+      <ItemChanges title={t("title-exercise-slides")} changes={diff.exercise_slides} />
+      <ItemChanges title={t("title-exercise-tasks")} changes={diff.exercise_tasks} />
+      <ItemChanges title={t("title-peer-review-configs")} changes={diff.peer_review_configs} />
+      <ItemChanges title={t("title-peer-review-questions")} changes={diff.peer_review_questions} />
+    </div>
+  )
+}
+
+export default PageHistoryDiffView
@@ -0,0 +1,165 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useEffect, useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import {
+  deleteCalibrationSample,
+  fetchCalibrationSampleInfo,
+  putCalibrationSample,
+} from "../../../../services/backend/submissions"
+import { NewPeerReviewCalibrationReferenceAnswer } from "../../../../shared-module/bindings"
+import Button from "../../../../shared-module/components/Button"
+import ErrorBanner from "../../../../shared-module/components/ErrorBanner"
+import TextField from "../../../../shared-module/components/InputFields/TextField"
+import Spinner from "../../../../shared-module/components/Spinner"
+import useToastMutation from "../../../../shared-module/hooks/useToastMutation"
+import { narrowContainerWidthRem } from "../../../../shared-module/styles/constants"
+
+interface CalibrationSampleEditorProps {
+  submissionId: string
+}
+
+const CalibrationSampleEditor: React.FC<
+  React.PropsWithChildren<CalibrationSampleEditorProps>
+> = ({ submissionId }) => {
+  const { t } = useTranslation()
+  const [referenceAnswers, setReferenceAnswers] = useState<
+    Map<string, NewPeerReviewCalibrationReferenceAnswer>
+  >(new Map())
+  const query = useQuery([`submission-${submissionId}-calibration-sample`], () =>
+    fetchCalibrationSampleInfo(submissionId),
+  )
+
+  useEffect(() => {
+    if (!query.data?.calibration_sample) {
+      return
+    }
+    setReferenceAnswers(
+      new Map(
+        query.data.calibration_sample.reference_answers.map((answer) => [
+          answer.peer_review_question_id,
+          {
+            peer_review_question_id: answer.peer_review_question_id,
+            reference_number_data: answer.reference_number_data,
+            explanation: answer.explanation,
+          },
+        ]),
+      ),
+    )
+  }, [query.data])
+
+  const saveMutation = useToastMutation(
+    () => putCalibrationSample(submissionId, Array.from(referenceAnswers.values())),
+    { notify: true, method: "PUT" },
+    { onSuccess: () => query.refetch() },
+  )
+  const deleteMutation = useToastMutation(
+    () => deleteCalibrationSample(submissionId),
+    { notify: true, method: "DELETE" },
+    {
+      onSuccess: () => {
+        setReferenceAnswers(new Map())
+        query.refetch()
+      },
+    },
+  )
+
+  if (query.isError) {
+    return <ErrorBanner variant={"readOnly"} error={query.error} />
+  }
+
+  if (query.isLoading || !query.data) {
+    return <Spinner variant={"medium"} />
+  }
+
+  if (query.data.peer_review_questions.length === 0) {
+    return null
+  }
+
+  return (
+    <div
+      className={css`
+        max-width: ${narrowContainerWidthRem}rem;
+        margin: 2rem auto;
+      `}
+    >
+      <h2>{t("title-calibration-sample")}</h2>
+      <p>{t("calibration-sample-explanation")}</p>
+      {query.data.peer_review_questions
+        .sort((a, b) => a.order_number - b.order_number)
+        .map((question) => {
+          const referenceAnswer = referenceAnswers.get(question.id)
+          return (
+            <div
+              key={question.id}
+              className={css`
+                margin-bottom: 1rem;
+              `}
+            >
+              <TextField
+                label={question.question}
+                type={"number"}
+                min={0}
+                step="1"
+                value={referenceAnswer?.reference_number_data ?? ""}
+                onChange={(value) => {
+                  setReferenceAnswers((prev) => {
+                    const answers = new Map(prev)
+                    if (value.trim() === "") {
+                      answers.delete(question.id)
+                    } else {
+                      answers.set(question.id, {
+                        peer_review_question_id: question.id,
+                        reference_number_data: Number(value),
+                        explanation: referenceAnswer?.explanation ?? null,
+                      })
+                    }
+                    return answers
+                  })
+                }}
+              />
+              {referenceAnswer && (
+                <TextField
+                  label={t("label-explanation")}
+                  value={referenceAnswer.explanation ?? ""}
+                  onChange={(value) => {
+                    setReferenceAnswers((prev) => {
+                      const answers = new Map(prev)
+                      answers.set(question.id, {
+                        ...referenceAnswer,
+                        explanation: value.trim() === "" ? null : value,
+                      })
+                      return answers
+                    })
+                  }}
+                />
+              )}
+            </div>
+          )
+        })}
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={referenceAnswers.size === 0 || saveMutation.isLoading}
+        onClick={() => saveMutation.mutate()}
+      >
+        {query.data.calibration_sample
+          ? t("button-text-update-calibration-sample")
+          : t("button-text-mark-as-calibration-sample")}
+      </Button>
+      {query.data.calibration_sample && (
+        <Button
+          variant="secondary"
+          size="medium"
+          disabled={deleteMutation.isLoading}
+          onClick={() => deleteMutation.mutate()}
+        >
+          {t("button-text-remove-calibration-sample")}
+        </Button>
+      )}
+    </div>
+  )
+}
+
+export default CalibrationSampleEditor
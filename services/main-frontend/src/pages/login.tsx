@@ -1,4 +1,5 @@
 import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
 import Link from "next/link"
 import { useRouter } from "next/router"
 import { useContext, useState } from "react"
@@ -10,7 +11,7 @@ import TextField from "../shared-module/components/InputFields/TextField"
 import LoginStateContext from "../shared-module/contexts/LoginStateContext"
 import useQueryParameter from "../shared-module/hooks/useQueryParameter"
 import useToastMutation from "../shared-module/hooks/useToastMutation"
-import { login } from "../shared-module/services/backend/auth"
+import { fetchIdentityProviders, login } from "../shared-module/services/backend/auth"
 import { baseTheme } from "../shared-module/styles"
 import {
   useCurrentPagePathForReturnTo,
@@ -28,6 +29,7 @@ const Login: React.FC<React.PropsWithChildren<unknown>> = () => {
   const [password, setPassword] = useState("")
   const uncheckedReturnTo = useQueryParameter("return_to")
   const returnToForLinkToSignupPage = useCurrentPagePathForReturnTo(router.asPath)
+  const getIdentityProviders = useQuery([`identity-providers`], fetchIdentityProviders)
 
   const loginMutation = useToastMutation(
     async () => {
@@ -149,6 +151,27 @@ const Login: React.FC<React.PropsWithChildren<unknown>> = () => {
             </a>
           </div>
         </form>
+        {getIdentityProviders.isSuccess &&
+          getIdentityProviders.data.map((identityProvider) => (
+            <div
+              key={identityProvider.id}
+              className={css`
+                margin-bottom: 1.5rem;
+              `}
+            >
+              <a
+                href={`/api/v0/auth/oidc/${identityProvider.id}/login?link=${
+                  loginStateContext.signedIn === true
+                }&return_to=${encodeURIComponent(
+                  validateReturnToRouteOrDefault(uncheckedReturnTo, "/"),
+                )}`}
+              >
+                {loginStateContext.signedIn
+                  ? t("link-account-with-identity-provider", { name: identityProvider.name })
+                  : t("login-with-identity-provider", { name: identityProvider.name })}
+              </a>
+            </div>
+          ))}
       </div>
     </Layout>
   )
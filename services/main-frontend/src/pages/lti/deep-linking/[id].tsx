@@ -0,0 +1,126 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useEffect, useRef } from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../../components/Layout"
+import {
+  createLtiDeepLinkingResponse,
+  fetchLtiDeepLinkingContent,
+} from "../../../services/backend/lti"
+import { LtiDeepLinkingPage, LtiDeepLinkingSelection } from "../../../shared-module/bindings"
+import Button from "../../../shared-module/components/Button"
+import ErrorBanner from "../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../shared-module/components/Spinner"
+import useToastMutation from "../../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../../shared-module/styles"
+import dontRenderUntilQueryParametersReady, {
+  SimplifiedUrlQuery,
+} from "../../../shared-module/utils/dontRenderUntilQueryParametersReady"
+import withErrorBoundary from "../../../shared-module/utils/withErrorBoundary"
+
+interface Props {
+  query: SimplifiedUrlQuery<"id">
+}
+
+const itemStyle = css`
+  display: flex;
+  justify-content: space-between;
+  align-items: center;
+  padding: 0.5rem 0;
+  border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+`
+
+const LtiDeepLinking: React.FC<React.PropsWithChildren<Props>> = ({ query }) => {
+  const { t } = useTranslation()
+  const formRef = useRef<HTMLFormElement>(null)
+  const getContent = useQuery([`lti-deep-linking`, query.id], () =>
+    fetchLtiDeepLinkingContent(query.id),
+  )
+  const selectMutation = useToastMutation(
+    (selection: LtiDeepLinkingSelection) => createLtiDeepLinkingResponse(query.id, selection),
+    { notify: false },
+  )
+
+  // the response is posted to the platform as a form, which navigates the frame back to the platform
+  useEffect(() => {
+    if (selectMutation.isSuccess) {
+      formRef.current?.submit()
+    }
+  }, [selectMutation.isSuccess])
+
+  const renderPage = (page: LtiDeepLinkingPage) => (
+    <li key={page.id} className={itemStyle}>
+      <span>{page.title}</span>
+      <Button
+        variant="tertiary"
+        size="medium"
+        disabled={selectMutation.isLoading || selectMutation.isSuccess}
+        onClick={() => selectMutation.mutate({ page_id: page.id, chapter_id: null })}
+      >
+        {t("button-text-select")}
+      </Button>
+    </li>
+  )
+
+  return (
+    <Layout navVariant="simple">
+      <h1>{t("title-lti-select-content")}</h1>
+      <p>{t("lti-select-content-explanation")}</p>
+      {getContent.isLoading && <Spinner variant="large" />}
+      {getContent.isError && <ErrorBanner variant="readOnly" error={getContent.error} />}
+      {selectMutation.isError && (
+        <ErrorBanner variant="readOnly" error={selectMutation.error} />
+      )}
+      {getContent.isSuccess && getContent.data.length === 0 && <p>{t("no-lti-content")}</p>}
+      {getContent.isSuccess &&
+        getContent.data.map((course) => (
+          <div key={course.id}>
+            <h2>{course.name}</h2>
+            <ul
+              className={css`
+                list-style: none;
+                padding: 0;
+              `}
+            >
+              {course.pages.map(renderPage)}
+            </ul>
+            {course.chapters.map((chapter) => (
+              <div key={chapter.id}>
+                <div className={itemStyle}>
+                  <h3>
+                    {chapter.chapter_number}: {chapter.name}
+                  </h3>
+                  <Button
+                    variant="secondary"
+                    size="medium"
+                    disabled={selectMutation.isLoading || selectMutation.isSuccess}
+                    onClick={() =>
+                      selectMutation.mutate({ page_id: null, chapter_id: chapter.id })
+                    }
+                  >
+                    {t("button-text-select-chapter")}
+                  </Button>
+                </div>
+                <ul
+                  className={css`
+                    list-style: none;
+                    padding-left: 1rem;
+                  `}
+                >
+                  {chapter.pages.map(renderPage)}
+                </ul>
+              </div>
+            ))}
+          </div>
+        ))}
+      {selectMutation.isSuccess && (
+        <form ref={formRef} method="POST" action={selectMutation.data.deep_link_return_url}>
+          <input type="hidden" name="JWT" value={selectMutation.data.jwt} />
+        </form>
+      )}
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(dontRenderUntilQueryParametersReady(LtiDeepLinking))
@@ -0,0 +1,154 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../components/Layout"
+import { createApiToken, fetchApiTokens, revokeApiToken } from "../../services/backend/api-tokens"
+import { ApiTokenScope } from "../../shared-module/bindings"
+import Button from "../../shared-module/components/Button"
+import ErrorBanner from "../../shared-module/components/ErrorBanner"
+import CheckBox from "../../shared-module/components/InputFields/CheckBox"
+import TextField from "../../shared-module/components/InputFields/TextField"
+import Spinner from "../../shared-module/components/Spinner"
+import { withSignedIn } from "../../shared-module/contexts/LoginStateContext"
+import useToastMutation from "../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../shared-module/styles"
+import withErrorBoundary from "../../shared-module/utils/withErrorBoundary"
+
+const ALL_SCOPES: ApiTokenScope[] = ["Read", "Grade", "EditContent"]
+const DEFAULT_VALIDITY_DAYS = 90
+
+const ApiTokensPage: React.FC<React.PropsWithChildren<unknown>> = () => {
+  const { t } = useTranslation()
+  const [name, setName] = useState("")
+  const [scopes, setScopes] = useState<ApiTokenScope[]>(["Read"])
+  const [validityDays, setValidityDays] = useState(DEFAULT_VALIDITY_DAYS)
+  const [createdToken, setCreatedToken] = useState<string | null>(null)
+
+  const getApiTokens = useQuery([`api-tokens`], fetchApiTokens)
+  const createMutation = useToastMutation(
+    () => {
+      const expiresAt = new Date()
+      expiresAt.setDate(expiresAt.getDate() + validityDays)
+      return createApiToken({ name, scopes, expires_at: expiresAt })
+    },
+    { notify: true, method: "POST" },
+    {
+      onSuccess: (created) => {
+        setCreatedToken(created.token)
+        setName("")
+        getApiTokens.refetch()
+      },
+    },
+  )
+  const revokeMutation = useToastMutation(
+    (apiTokenId: string) => revokeApiToken(apiTokenId),
+    { notify: true, method: "DELETE" },
+    { onSuccess: () => getApiTokens.refetch() },
+  )
+
+  const scopeLabel = (scope: ApiTokenScope) => {
+    switch (scope) {
+      case "Read":
+        return t("api-token-scope-read")
+      case "Grade":
+        return t("api-token-scope-grade")
+      case "EditContent":
+        return t("api-token-scope-edit-content")
+    }
+  }
+
+  return (
+    <Layout navVariant={"simple"}>
+      <h1>{t("title-api-tokens")}</h1>
+      <p>{t("api-tokens-description")}</p>
+      {createdToken && (
+        <div
+          className={css`
+            padding: 1rem;
+            margin-bottom: 2rem;
+            border: 2px solid ${baseTheme.colors.green[400]};
+          `}
+        >
+          <p>{t("api-token-created-copy-now")}</p>
+          <pre
+            className={css`
+              white-space: pre-wrap;
+              word-break: break-all;
+            `}
+          >
+            {createdToken}
+          </pre>
+        </div>
+      )}
+      <form
+        onSubmit={(event) => {
+          event.preventDefault()
+          createMutation.mutate()
+        }}
+      >
+        <TextField label={t("label-name")} value={name} onChange={setName} required />
+        {ALL_SCOPES.map((scope) => (
+          <CheckBox
+            key={scope}
+            label={scopeLabel(scope)}
+            checked={scopes.includes(scope)}
+            onChange={(checked) =>
+              setScopes(checked ? [...scopes, scope] : scopes.filter((s) => s !== scope))
+            }
+          />
+        ))}
+        <TextField
+          type="number"
+          min={1}
+          label={t("label-valid-for-days")}
+          value={validityDays}
+          onChange={(value) => setValidityDays(Number(value))}
+          required
+        />
+        <Button
+          variant="primary"
+          size="medium"
+          disabled={name.trim() === "" || scopes.length === 0 || createMutation.isLoading}
+        >
+          {t("button-text-create")}
+        </Button>
+      </form>
+      <h2>{t("title-your-api-tokens")}</h2>
+      {getApiTokens.isError && <ErrorBanner variant={"readOnly"} error={getApiTokens.error} />}
+      {getApiTokens.isLoading && <Spinner variant={"medium"} />}
+      {getApiTokens.isSuccess && getApiTokens.data.length === 0 && <p>{t("no-api-tokens")}</p>}
+      {getApiTokens.isSuccess &&
+        getApiTokens.data.map((apiToken) => (
+          <div
+            key={apiToken.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <h3>{apiToken.name}</h3>
+            <p>{apiToken.scopes.map(scopeLabel).join(", ")}</p>
+            <p>
+              {t("label-expires-at")}: {apiToken.expires_at.toLocaleString()}
+            </p>
+            <p>
+              {t("label-last-used-at")}:{" "}
+              {apiToken.last_used_at ? apiToken.last_used_at.toLocaleString() : t("never")}
+            </p>
+            <Button
+              variant="reject"
+              size="medium"
+              disabled={revokeMutation.isLoading}
+              onClick={() => revokeMutation.mutate(apiToken.id)}
+            >
+              {t("button-text-revoke")}
+            </Button>
+          </div>
+        ))}
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(withSignedIn(ApiTokensPage))
@@ -0,0 +1,160 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../components/Layout"
+import FullWidthTable, { FullWidthTableRow } from "../../components/tables/FullWidthTable"
+import {
+  auditLogExportUrl,
+  fetchAuditLogEntries,
+  fetchAuditLogEntryCount,
+} from "../../services/backend/audit-log"
+import { AuditLogAction, AuditLogFilter } from "../../shared-module/bindings"
+import ErrorBanner from "../../shared-module/components/ErrorBanner"
+import SelectField from "../../shared-module/components/InputFields/SelectField"
+import TextField from "../../shared-module/components/InputFields/TextField"
+import Pagination from "../../shared-module/components/Pagination"
+import Spinner from "../../shared-module/components/Spinner"
+import { withSignedIn } from "../../shared-module/contexts/LoginStateContext"
+import usePaginationInfo from "../../shared-module/hooks/usePaginationInfo"
+import { isUuid } from "../../shared-module/utils/fetching"
+import { dateToString } from "../../shared-module/utils/time"
+import withErrorBoundary from "../../shared-module/utils/withErrorBoundary"
+
+const ALL_ACTIONS: AuditLogAction[] = [
+  "RoleAdded",
+  "RoleRemoved",
+  "ManualCompletionsAdded",
+  "TeacherGradingDecisionAdded",
+  "RegradingCreated",
+  "PageRestored",
+  "CourseDeleted",
+  "UserErased",
+  "ImpersonationStarted",
+  "ImpersonationEnded",
+]
+
+// Ids that have not been typed completely yet are not used for filtering
+const uuidOrNull = (value: string): string | null => {
+  const trimmed = value.trim()
+  return isUuid(trimmed) ? trimmed : null
+}
+
+const AuditLogPage: React.FC<React.PropsWithChildren<unknown>> = () => {
+  const { t } = useTranslation()
+  const paginationInfo = usePaginationInfo()
+  const [action, setAction] = useState<AuditLogAction | "">("")
+  const [actorUserId, setActorUserId] = useState("")
+  const [courseId, setCourseId] = useState("")
+  const [resourceId, setResourceId] = useState("")
+
+  const filter: AuditLogFilter = {
+    actor_user_id: uuidOrNull(actorUserId),
+    action: action === "" ? null : action,
+    course_id: uuidOrNull(courseId),
+    resource_id: uuidOrNull(resourceId),
+    created_after: null,
+    created_before: null,
+  }
+  const auditLogQuery = useQuery(
+    ["audit-log", JSON.stringify(filter), JSON.stringify(paginationInfo)],
+    () => fetchAuditLogEntries(filter, paginationInfo),
+  )
+  const auditLogCountQuery = useQuery(["audit-log-count", JSON.stringify(filter)], () =>
+    fetchAuditLogEntryCount(filter),
+  )
+
+  const actionLabel = (action: AuditLogAction) => {
+    switch (action) {
+      case "RoleAdded":
+        return t("audit-log-action-role-added")
+      case "RoleRemoved":
+        return t("audit-log-action-role-removed")
+      case "ManualCompletionsAdded":
+        return t("audit-log-action-manual-completions-added")
+      case "TeacherGradingDecisionAdded":
+        return t("audit-log-action-teacher-grading-decision-added")
+      case "RegradingCreated":
+        return t("audit-log-action-regrading-created")
+      case "PageRestored":
+        return t("audit-log-action-page-restored")
+      case "CourseDeleted":
+        return t("audit-log-action-course-deleted")
+      case "UserErased":
+        return t("audit-log-action-user-erased")
+      case "ImpersonationStarted":
+        return t("audit-log-action-impersonation-started")
+      case "ImpersonationEnded":
+        return t("audit-log-action-impersonation-ended")
+    }
+  }
+
+  return (
+    <Layout navVariant={"simple"}>
+      <h1>{t("title-audit-log")}</h1>
+      <SelectField<AuditLogAction | "">
+        id={"audit-log-action"}
+        label={t("label-action")}
+        value={action}
+        options={[
+          { value: "", label: t("option-all-actions") },
+          ...ALL_ACTIONS.map((action) => ({ value: action, label: actionLabel(action) })),
+        ]}
+        onChange={setAction}
+      />
+      <TextField label={t("label-actor-user-id")} value={actorUserId} onChange={setActorUserId} />
+      <TextField label={t("label-course-id")} value={courseId} onChange={setCourseId} />
+      <TextField label={t("label-resource-id")} value={resourceId} onChange={setResourceId} />
+      <a href={auditLogExportUrl(filter)} download>
+        {t("link-export-audit-log")}
+      </a>
+      {auditLogQuery.isError && <ErrorBanner variant={"readOnly"} error={auditLogQuery.error} />}
+      {auditLogQuery.isLoading && <Spinner variant={"medium"} />}
+      {auditLogQuery.isSuccess && auditLogQuery.data.length === 0 && (
+        <p>{t("no-audit-log-entries")}</p>
+      )}
+      {auditLogQuery.isSuccess && auditLogQuery.data.length > 0 && (
+        <FullWidthTable>
+          <thead>
+            <tr
+              className={css`
+                text-align: left;
+                font-size: 13px;
+              `}
+            >
+              <th>{t("label-time")}</th>
+              <th>{t("label-actor")}</th>
+              <th>{t("label-action")}</th>
+              <th>{t("label-resource-id")}</th>
+              <th>{t("label-before")}</th>
+              <th>{t("label-after")}</th>
+              <th>{t("label-request-id")}</th>
+            </tr>
+          </thead>
+          <tbody>
+            {auditLogQuery.data.map((entry) => (
+              <FullWidthTableRow key={entry.id}>
+                <td>{dateToString(entry.created_at)}</td>
+                <td>{entry.actor_email ?? entry.actor_user_id}</td>
+                <td>{actionLabel(entry.action)}</td>
+                <td>{entry.resource_id}</td>
+                <td>{entry.before_summary !== null && JSON.stringify(entry.before_summary)}</td>
+                <td>{entry.after_summary !== null && JSON.stringify(entry.after_summary)}</td>
+                <td>{entry.request_id}</td>
+              </FullWidthTableRow>
+            ))}
+          </tbody>
+        </FullWidthTable>
+      )}
+      {auditLogCountQuery.data !== undefined && (
+        <Pagination
+          totalPages={Math.ceil(auditLogCountQuery.data / paginationInfo.limit)}
+          paginationInfo={paginationInfo}
+        />
+      )}
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(withSignedIn(AuditLogPage))
@@ -3,6 +3,7 @@ import { useTranslation } from "react-i18next"
 
 import Layout from "../../../../components/Layout"
 import CourseChangeRequests from "../../../../components/page-specific/manage/courses/id/change-request/CourseChangeRequests"
+import CourseContentIssues from "../../../../components/page-specific/manage/courses/id/content-issues/CourseContentIssues"
 import CourseCourseInstances from "../../../../components/page-specific/manage/courses/id/course-instances/CourseCourseInstances"
 import CourseExercises from "../../../../components/page-specific/manage/courses/id/exercises/CourseExercises"
 import CourseFeedback from "../../../../components/page-specific/manage/courses/id/feedback/CourseFeedback"
@@ -14,6 +15,7 @@ import CoursePages from "../../../../components/page-specific/manage/courses/id/
 import CoursePermissions from "../../../../components/page-specific/manage/courses/id/permissions/CoursePermissions"
 import References from "../../../../components/page-specific/manage/courses/id/references"
 import CourseStatsPage from "../../../../components/page-specific/manage/courses/id/stats/CourseStatsPage"
+import CourseUrlRedirections from "../../../../components/page-specific/manage/courses/id/url-redirections/CourseUrlRedirections"
 import createPendingChangeRequestCountHook from "../../../../hooks/count/usePendingChangeRequestCount"
 import createUnreadFeedbackCountHook from "../../../../hooks/count/useUnreadFeedbackCount"
 import TabLink from "../../../../shared-module/components/Navigation/TabLinks/TabLink"
@@ -50,6 +52,8 @@ const CourseManagementPageTabs: {
   permissions: CoursePermissions,
   glossary: CourseGlossary,
   stats: CourseStatsPage,
+  "content-issues": CourseContentIssues,
+  "url-redirections": CourseUrlRedirections,
 }
 
 const CourseManagementPage: React.FC<React.PropsWithChildren<CourseManagementPageProps>> = ({
@@ -110,6 +114,12 @@ const CourseManagementPage: React.FC<React.PropsWithChildren<CourseManagementPag
         <TabLink url={"stats"} isActive={path === "stats"}>
           {t("link-stats")}
         </TabLink>
+        <TabLink url={"content-issues"} isActive={path === "content-issues"}>
+          {t("link-content-issues")}
+        </TabLink>
+        <TabLink url={"url-redirections"} isActive={path === "url-redirections"}>
+          {t("link-url-redirections")}
+        </TabLink>
       </TabLinkNavigation>
       <TabLinkPanel>
         <PageToRender courseId={courseId} />
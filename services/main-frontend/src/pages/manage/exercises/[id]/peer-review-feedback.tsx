@@ -0,0 +1,37 @@
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../../../components/Layout"
+import FlaggedPeerReviewList from "../../../../components/page-specific/manage/exercises/id/peer-review-feedback/FlaggedPeerReviewList"
+import ReviewerStatisticsTable from "../../../../components/page-specific/manage/exercises/id/peer-review-feedback/ReviewerStatisticsTable"
+import { withSignedIn } from "../../../../shared-module/contexts/LoginStateContext"
+import {
+  dontRenderUntilQueryParametersReady,
+  SimplifiedUrlQuery,
+} from "../../../../shared-module/utils/dontRenderUntilQueryParametersReady"
+import withErrorBoundary from "../../../../shared-module/utils/withErrorBoundary"
+
+interface PeerReviewFeedbackPageProps {
+  query: SimplifiedUrlQuery<"id">
+}
+
+const PeerReviewFeedbackPage: React.FC<React.PropsWithChildren<PeerReviewFeedbackPageProps>> = ({
+  query,
+}) => {
+  const { t } = useTranslation()
+
+  return (
+    <Layout navVariant="simple">
+      <div>
+        <h4>{t("title-flagged-peer-reviews")}</h4>
+        <FlaggedPeerReviewList exerciseId={query.id} />
+        <h4>{t("title-reviewer-statistics")}</h4>
+        <ReviewerStatisticsTable exerciseId={query.id} />
+      </div>
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(
+  withSignedIn(dontRenderUntilQueryParametersReady(PeerReviewFeedbackPage)),
+)
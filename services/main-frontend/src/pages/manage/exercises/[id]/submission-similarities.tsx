@@ -0,0 +1,35 @@
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../../../components/Layout"
+import SubmissionSimilarityList from "../../../../components/page-specific/manage/exercises/id/submission-similarities/SubmissionSimilarityList"
+import { withSignedIn } from "../../../../shared-module/contexts/LoginStateContext"
+import {
+  dontRenderUntilQueryParametersReady,
+  SimplifiedUrlQuery,
+} from "../../../../shared-module/utils/dontRenderUntilQueryParametersReady"
+import withErrorBoundary from "../../../../shared-module/utils/withErrorBoundary"
+
+interface SubmissionSimilaritiesPageProps {
+  query: SimplifiedUrlQuery<"id">
+}
+
+const SubmissionSimilaritiesPage: React.FC<
+  React.PropsWithChildren<SubmissionSimilaritiesPageProps>
+> = ({ query }) => {
+  const { t } = useTranslation()
+
+  return (
+    <Layout navVariant="simple">
+      <div>
+        <h4>{t("title-similar-submissions")}</h4>
+        <p>{t("similar-submissions-description")}</p>
+        <SubmissionSimilarityList exerciseId={query.id} />
+      </div>
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(
+  withSignedIn(dontRenderUntilQueryParametersReady(SubmissionSimilaritiesPage)),
+)
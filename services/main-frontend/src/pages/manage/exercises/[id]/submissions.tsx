@@ -1,4 +1,6 @@
+import { css } from "@emotion/css"
 import { useQuery } from "@tanstack/react-query"
+import Link from "next/link"
 import React from "react"
 import { useTranslation } from "react-i18next"
 
@@ -33,6 +35,25 @@ const SubmissionsPage: React.FC<React.PropsWithChildren<SubmissionPageProps>> =
     <Layout navVariant="simple">
       <div>
         <h4>{t("header-submissions")}</h4>
+        <Link
+          href={{
+            pathname: "/manage/exercises/[id]/peer-review-feedback",
+            query: { id: query.id },
+          }}
+        >
+          {t("link-peer-review-feedback")}
+        </Link>
+        <Link
+          href={{
+            pathname: "/manage/exercises/[id]/submission-similarities",
+            query: { id: query.id },
+          }}
+          className={css`
+            margin-left: 1rem;
+          `}
+        >
+          {t("link-similar-submissions")}
+        </Link>
         {getExerciseSubmissions.isError && (
           <ErrorBanner variant={"readOnly"} error={getExerciseSubmissions.error} />
         )}
@@ -37,6 +37,12 @@ const ManageOrganization: React.FC<React.PropsWithChildren<Props>> = ({ query })
         />
         <a href={`/manage/organizations/${organization.data.id}/permissions`}>
           {t("link-manage-permissions")}
+        </a>{" "}
+        <a href={`/manage/organizations/${organization.data.id}/webhooks`}>
+          {t("link-manage-webhooks")}
+        </a>{" "}
+        <a href={`/manage/organizations/${organization.data.id}/lti`}>
+          {t("link-manage-lti-platforms")}
         </a>
       </>
     )
@@ -0,0 +1,54 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../../../components/Layout"
+import LtiRegistrations from "../../../../components/page-specific/manage/organizations/id/lti/LtiRegistrations"
+import { fetchOrganization } from "../../../../services/backend/organizations"
+import ErrorBanner from "../../../../shared-module/components/ErrorBanner"
+import Spinner from "../../../../shared-module/components/Spinner"
+import { withSignedIn } from "../../../../shared-module/contexts/LoginStateContext"
+import { respondToOrLarger } from "../../../../shared-module/styles/respond"
+import {
+  dontRenderUntilQueryParametersReady,
+  SimplifiedUrlQuery,
+} from "../../../../shared-module/utils/dontRenderUntilQueryParametersReady"
+import withErrorBoundary from "../../../../shared-module/utils/withErrorBoundary"
+
+interface Props {
+  query: SimplifiedUrlQuery<"id">
+}
+
+const OrganizationLtiRegistrations: React.FC<React.PropsWithChildren<Props>> = ({ query }) => {
+  const { t } = useTranslation()
+  const organization = useQuery([`organization-${query.id}`], () => fetchOrganization(query.id))
+
+  return (
+    <Layout navVariant="simple">
+      <div
+        className={css`
+          margin-top: 40px;
+          ${respondToOrLarger.sm} {
+            margin-top: 80px;
+          }
+        `}
+      >
+        {organization.isLoading && <Spinner variant="large" />}
+        {organization.isError && <ErrorBanner variant="readOnly" error={organization.error} />}
+        {organization.isSuccess && (
+          <>
+            <h1>
+              {t("title-lti-platforms")}: {organization.data.name}
+            </h1>
+            <LtiRegistrations organizationId={organization.data.id} />
+          </>
+        )}
+      </div>
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(
+  withSignedIn(dontRenderUntilQueryParametersReady(OrganizationLtiRegistrations)),
+)
@@ -5,6 +5,8 @@ import { useTranslation } from "react-i18next"
 
 import Layout from "../../../../components/Layout"
 import { PermissionPage } from "../../../../components/PermissionPage"
+import CustomRoles from "../../../../components/page-specific/manage/organizations/id/permissions/CustomRoles"
+import PendingRoleRequests from "../../../../components/page-specific/manage/organizations/id/permissions/PendingRoleRequests"
 import { fetchOrganization } from "../../../../services/backend/organizations"
 import ErrorBanner from "../../../../shared-module/components/ErrorBanner"
 import Spinner from "../../../../shared-module/components/Spinner"
@@ -47,7 +49,10 @@ const OrganizationPermissions: React.FC<React.PropsWithChildren<Props>> = ({ que
                 tag: "Organization",
                 id: organization.data.id,
               }}
+              organizationId={organization.data.id}
             />
+            <PendingRoleRequests organizationId={organization.data.id} />
+            <CustomRoles organizationId={organization.data.id} />
           </>
         )}
       </div>
@@ -0,0 +1,133 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import { TFunction } from "i18next"
+import React, { useState } from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../components/Layout"
+import { createRoleRequest, fetchOwnRoleRequests } from "../../services/backend/role-requests"
+import { RoleRequestStatus, UserRole } from "../../shared-module/bindings"
+import Button from "../../shared-module/components/Button"
+import ErrorBanner from "../../shared-module/components/ErrorBanner"
+import DateTimeLocal from "../../shared-module/components/InputFields/DateTimeLocal"
+import SelectField from "../../shared-module/components/InputFields/SelectField"
+import TextAreaField from "../../shared-module/components/InputFields/TextAreaField"
+import TextField from "../../shared-module/components/InputFields/TextField"
+import Spinner from "../../shared-module/components/Spinner"
+import { withSignedIn } from "../../shared-module/contexts/LoginStateContext"
+import useToastMutation from "../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../shared-module/styles"
+import withErrorBoundary from "../../shared-module/utils/withErrorBoundary"
+
+const ASSISTANT: UserRole = "Assistant"
+const REVIEWER: UserRole = "Reviewer"
+const TEACHER: UserRole = "Teacher"
+const MATERIAL_VIEWER: UserRole = "MaterialViewer"
+
+const roleOptions = (t: TFunction) => {
+  return [
+    { value: ASSISTANT, label: t("role-assistant") },
+    { value: REVIEWER, label: t("role-reviewer") },
+    { value: TEACHER, label: t("role-teacher") },
+    { value: MATERIAL_VIEWER, label: t("role-material-viewer") },
+  ]
+}
+
+const statusLabel = (t: TFunction, status: RoleRequestStatus) => {
+  switch (status) {
+    case "Pending":
+      return t("role-request-status-pending")
+    case "Approved":
+      return t("role-request-status-approved")
+    case "Rejected":
+      return t("role-request-status-rejected")
+  }
+}
+
+const RoleRequestsPage: React.FC<React.PropsWithChildren<unknown>> = () => {
+  const { t } = useTranslation()
+  const [courseId, setCourseId] = useState("")
+  const [role, setRole] = useState<UserRole>(ASSISTANT)
+  const [validUntil, setValidUntil] = useState("")
+  const [reason, setReason] = useState("")
+
+  const getOwnRoleRequests = useQuery([`own-role-requests`], fetchOwnRoleRequests)
+  const createMutation = useToastMutation(
+    () =>
+      createRoleRequest({
+        course_id: courseId,
+        role,
+        valid_from: null,
+        valid_until: validUntil ? new Date(validUntil) : null,
+        reason,
+      }),
+    { notify: true, method: "POST" },
+    {
+      onSuccess: () => {
+        setReason("")
+        getOwnRoleRequests.refetch()
+      },
+    },
+  )
+
+  return (
+    <Layout navVariant={"simple"}>
+      <h1>{t("title-role-requests")}</h1>
+      <p>{t("role-requests-description")}</p>
+      <TextField
+        label={t("label-course-id")}
+        value={courseId}
+        onChange={(value) => setCourseId(value)}
+      />
+      <SelectField
+        id={"role-request-role"}
+        label={t("label-role")}
+        onChange={(value) => setRole(value)}
+        options={roleOptions(t)}
+        defaultValue={ASSISTANT}
+      />
+      <DateTimeLocal label={t("label-valid-until")} onChange={(value) => setValidUntil(value)} />
+      <TextAreaField
+        label={t("label-reason")}
+        value={reason}
+        onChange={(value) => setReason(value)}
+      />
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={courseId === "" || reason === "" || createMutation.isLoading}
+        onClick={() => createMutation.mutate()}
+      >
+        {t("button-text-request-role")}
+      </Button>
+      {getOwnRoleRequests.isError && (
+        <ErrorBanner variant={"readOnly"} error={getOwnRoleRequests.error} />
+      )}
+      {getOwnRoleRequests.isLoading && <Spinner variant={"medium"} />}
+      {getOwnRoleRequests.isSuccess &&
+        getOwnRoleRequests.data.map((roleRequest) => (
+          <div
+            key={roleRequest.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <h3>
+              {roleRequest.course_name}: {roleRequest.role}
+            </h3>
+            <p>
+              {t("status")}: {statusLabel(t, roleRequest.status)}
+            </p>
+            {roleRequest.valid_until && (
+              <p>
+                {t("label-valid-until")}: {roleRequest.valid_until.toLocaleString()}
+              </p>
+            )}
+          </div>
+        ))}
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(withSignedIn(RoleRequestsPage))
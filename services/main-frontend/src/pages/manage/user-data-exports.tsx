@@ -0,0 +1,85 @@
+import { css } from "@emotion/css"
+import { useQuery } from "@tanstack/react-query"
+import React from "react"
+import { useTranslation } from "react-i18next"
+
+import Layout from "../../components/Layout"
+import {
+  createUserDataExport,
+  fetchUserDataExports,
+  userDataExportDownloadUrl,
+} from "../../services/backend/user-data-exports"
+import { UserDataExport } from "../../shared-module/bindings"
+import Button from "../../shared-module/components/Button"
+import ErrorBanner from "../../shared-module/components/ErrorBanner"
+import Spinner from "../../shared-module/components/Spinner"
+import { withSignedIn } from "../../shared-module/contexts/LoginStateContext"
+import useToastMutation from "../../shared-module/hooks/useToastMutation"
+import { baseTheme } from "../../shared-module/styles"
+import withErrorBoundary from "../../shared-module/utils/withErrorBoundary"
+
+const UserDataExportsPage: React.FC<React.PropsWithChildren<unknown>> = () => {
+  const { t } = useTranslation()
+
+  const getUserDataExports = useQuery([`user-data-exports`], fetchUserDataExports)
+  const createMutation = useToastMutation(
+    createUserDataExport,
+    { notify: true, method: "POST" },
+    { onSuccess: () => getUserDataExports.refetch() },
+  )
+
+  const pending =
+    getUserDataExports.isSuccess &&
+    getUserDataExports.data.some((userDataExport) => userDataExport.completed_at === null)
+
+  const status = (userDataExport: UserDataExport) => {
+    if (userDataExport.completed_at === null || userDataExport.expires_at === null) {
+      return <p>{t("user-data-export-pending")}</p>
+    }
+    if (userDataExport.expires_at < new Date()) {
+      return <p>{t("user-data-export-expired")}</p>
+    }
+    return (
+      <>
+        <p>
+          {t("label-expires-at")}: {userDataExport.expires_at.toLocaleString()}
+        </p>
+        <a href={userDataExportDownloadUrl(userDataExport.id)}>{t("link-download")}</a>
+      </>
+    )
+  }
+
+  return (
+    <Layout navVariant={"simple"}>
+      <h1>{t("title-your-data")}</h1>
+      <p>{t("user-data-exports-description")}</p>
+      <Button
+        variant="primary"
+        size="medium"
+        disabled={pending || createMutation.isLoading}
+        onClick={() => createMutation.mutate()}
+      >
+        {t("button-text-request-data-export")}
+      </Button>
+      {getUserDataExports.isError && (
+        <ErrorBanner variant={"readOnly"} error={getUserDataExports.error} />
+      )}
+      {getUserDataExports.isLoading && <Spinner variant={"medium"} />}
+      {getUserDataExports.isSuccess &&
+        getUserDataExports.data.map((userDataExport) => (
+          <div
+            key={userDataExport.id}
+            className={css`
+              padding: 1rem 0;
+              border-bottom: 1px solid ${baseTheme.colors.clear[300]};
+            `}
+          >
+            <h3>{userDataExport.created_at.toLocaleString()}</h3>
+            {status(userDataExport)}
+          </div>
+        ))}
+    </Layout>
+  )
+}
+
+export default withErrorBoundary(withSignedIn(UserDataExportsPage))
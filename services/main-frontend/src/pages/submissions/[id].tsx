@@ -6,6 +6,7 @@ import React from "react"
 import { useTranslation } from "react-i18next"
 
 import Layout from "../../components/Layout"
+import CalibrationSampleEditor from "../../components/page-specific/submissions/id/CalibrationSampleEditor"
 import SubmissionIFrame from "../../components/page-specific/submissions/id/SubmissionIFrame"
 import { fetchSubmissionInfo } from "../../services/backend/submissions"
 import DebugModal from "../../shared-module/components/DebugModal"
@@ -127,6 +128,9 @@ const Submission: React.FC<React.PropsWithChildren<SubmissionPageProps>> = ({ qu
               .map((task) => (
                 <SubmissionIFrame key={task.id} coursematerialExerciseTask={task} />
               ))}
+            {getSubmissionInfo.data.exercise.needs_peer_review && (
+              <CalibrationSampleEditor submissionId={query.id} />
+            )}
           </>
         )}
         <div
@@ -0,0 +1,20 @@
+import { ApiToken, CreatedApiToken, NewApiToken } from "../../shared-module/bindings"
+import { isApiToken, isCreatedApiToken } from "../../shared-module/bindings.guard"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchApiTokens = async (): Promise<Array<ApiToken>> => {
+  const response = await mainFrontendClient.get(`/api-tokens`, { responseType: "json" })
+  return validateResponse(response, isArray(isApiToken))
+}
+
+export const createApiToken = async (newApiToken: NewApiToken): Promise<CreatedApiToken> => {
+  const response = await mainFrontendClient.post(`/api-tokens`, newApiToken, {
+    responseType: "json",
+  })
+  return validateResponse(response, isCreatedApiToken)
+}
+
+export const revokeApiToken = async (apiTokenId: string): Promise<void> => {
+  await mainFrontendClient.delete(`/api-tokens/${apiTokenId}`)
+}
@@ -0,0 +1,36 @@
+import { isNumber } from "lodash"
+
+import { AuditLogEntry, AuditLogFilter } from "../../shared-module/bindings"
+import { isAuditLogEntry } from "../../shared-module/bindings.guard"
+import { PaginationInfo } from "../../shared-module/hooks/usePaginationInfo"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchAuditLogEntries = async (
+  filter: AuditLogFilter,
+  pagination: PaginationInfo,
+): Promise<AuditLogEntry[]> => {
+  const response = await mainFrontendClient.get(`/audit-log`, {
+    params: { ...filter, page: pagination.page, limit: pagination.limit },
+    responseType: "json",
+  })
+  return validateResponse(response, isArray(isAuditLogEntry))
+}
+
+export const fetchAuditLogEntryCount = async (filter: AuditLogFilter): Promise<number> => {
+  const response = await mainFrontendClient.get(`/audit-log/count`, {
+    params: filter,
+    responseType: "json",
+  })
+  return validateResponse(response, isNumber)
+}
+
+export const auditLogExportUrl = (filter: AuditLogFilter): string => {
+  const params = new URLSearchParams()
+  Object.entries(filter).forEach(([key, value]) => {
+    if (value !== null) {
+      params.append(key, value instanceof Date ? value.toISOString() : value)
+    }
+  })
+  return `/api/v0/main-frontend/audit-log/export?${params.toString()}`
+}
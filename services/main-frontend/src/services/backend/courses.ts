@@ -3,6 +3,7 @@ import { isBoolean } from "lodash"
 import {
   Chapter,
   Course,
+  CourseContentLintReport,
   CourseInstance,
   CourseInstanceForm,
   CourseStructure,
@@ -15,12 +16,16 @@ import {
   MaterialReference,
   NewCourse,
   NewMaterialReference,
+  NewUrlRedirection,
   Page,
   Term,
   TermUpdate,
+  UrlRedirection,
+  UrlRedirectionWithResolution,
 } from "../../shared-module/bindings"
 import {
   isCourse,
+  isCourseContentLintReport,
   isCourseInstance,
   isCourseStructure,
   isExercise,
@@ -28,8 +33,10 @@ import {
   isExerciseSlideSubmissionCountByWeekAndHour,
   isExerciseUserCounts,
   isTerm,
+  isUrlRedirection,
+  isUrlRedirectionWithResolution,
 } from "../../shared-module/bindings.guard"
-import { isArray, isString, validateResponse } from "../../shared-module/utils/fetching"
+import { isArray, isNumber, isString, validateResponse } from "../../shared-module/utils/fetching"
 import { mainFrontendClient } from "../mainFrontendClient"
 
 export const getCourse = async (courseId: string): Promise<Course> => {
@@ -238,3 +245,61 @@ export const postUpdatePeerReviewQueueReviewsReceived = async (
   )
   return validateResponse(res, isBoolean)
 }
+
+export const fetchContentLintReport = async (
+  courseId: string,
+): Promise<CourseContentLintReport> => {
+  const response = await mainFrontendClient.get(`/courses/${courseId}/content-lint-report`, {
+    responseType: "json",
+  })
+  return validateResponse(response, isCourseContentLintReport)
+}
+
+export const postContentLintCheck = async (courseId: string): Promise<CourseContentLintReport> => {
+  const response = await mainFrontendClient.post(`/courses/${courseId}/content-lint-report`)
+  return validateResponse(response, isCourseContentLintReport)
+}
+
+export const fetchUrlRedirections = async (
+  courseId: string,
+): Promise<Array<UrlRedirectionWithResolution>> => {
+  const response = await mainFrontendClient.get(`/courses/${courseId}/url-redirections`, {
+    responseType: "json",
+  })
+  return validateResponse(response, isArray(isUrlRedirectionWithResolution))
+}
+
+export const postUrlRedirection = async (
+  courseId: string,
+  redirection: NewUrlRedirection,
+): Promise<UrlRedirection> => {
+  const response = await mainFrontendClient.post(
+    `/courses/${courseId}/url-redirections`,
+    redirection,
+  )
+  return validateResponse(response, isUrlRedirection)
+}
+
+export const putUrlRedirection = async (
+  courseId: string,
+  redirectionId: string,
+  redirection: NewUrlRedirection,
+): Promise<UrlRedirection> => {
+  const response = await mainFrontendClient.put(
+    `/courses/${courseId}/url-redirections/${redirectionId}`,
+    redirection,
+  )
+  return validateResponse(response, isUrlRedirection)
+}
+
+export const deleteUrlRedirection = async (
+  courseId: string,
+  redirectionId: string,
+): Promise<void> => {
+  await mainFrontendClient.delete(`/courses/${courseId}/url-redirections/${redirectionId}`)
+}
+
+export const postFlattenUrlRedirections = async (courseId: string): Promise<number> => {
+  const response = await mainFrontendClient.post(`/courses/${courseId}/url-redirections/flatten`)
+  return validateResponse(response, isNumber)
+}
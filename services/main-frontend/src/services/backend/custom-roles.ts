@@ -0,0 +1,55 @@
+import {
+  CustomRoleUpdate,
+  CustomRoleWithPermissions,
+  NewCustomRole,
+  RolePermission,
+  UserRole,
+} from "../../shared-module/bindings"
+import { isCustomRoleWithPermissions, isRolePermission } from "../../shared-module/bindings.guard"
+import { isArray, isString, validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchCustomRolesForOrganization = async (
+  organizationId: string,
+): Promise<Array<CustomRoleWithPermissions>> => {
+  const response = await mainFrontendClient.get(`/custom-roles/organization/${organizationId}`, {
+    responseType: "json",
+  })
+  return validateResponse(response, isArray(isCustomRoleWithPermissions))
+}
+
+export const createCustomRole = async (
+  organizationId: string,
+  newCustomRole: NewCustomRole,
+): Promise<string> => {
+  const response = await mainFrontendClient.post(
+    `/custom-roles/organization/${organizationId}`,
+    newCustomRole,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isString)
+}
+
+export const updateCustomRole = async (
+  customRoleId: string,
+  customRoleUpdate: CustomRoleUpdate,
+): Promise<void> => {
+  await mainFrontendClient.put(`/custom-roles/${customRoleId}`, customRoleUpdate, {
+    responseType: "json",
+  })
+}
+
+export const deleteCustomRole = async (customRoleId: string): Promise<void> => {
+  await mainFrontendClient.delete(`/custom-roles/${customRoleId}`)
+}
+
+export const fetchBuiltInRolePermissions = async (
+  organizationId: string,
+  role: UserRole,
+): Promise<Array<RolePermission>> => {
+  const response = await mainFrontendClient.get(
+    `/custom-roles/organization/${organizationId}/built-in-permissions/${role}`,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isArray(isRolePermission))
+}
@@ -1,6 +1,19 @@
-import { ExerciseSubmissions } from "../../shared-module/bindings"
-import { isExerciseSubmissions } from "../../shared-module/bindings.guard"
-import { validateResponse } from "../../shared-module/utils/fetching"
+import {
+  ExerciseSubmissions,
+  ExerciseTaskSubmissionSimilarityInfo,
+  FlaggedPeerReview,
+  PeerReviewReviewerStatistics,
+  PeerReviewSubmissionFlag,
+  ResolvePeerReviewSubmissionFlag,
+} from "../../shared-module/bindings"
+import {
+  isExerciseSubmissions,
+  isExerciseTaskSubmissionSimilarityInfo,
+  isFlaggedPeerReview,
+  isPeerReviewReviewerStatistics,
+  isPeerReviewSubmissionFlag,
+} from "../../shared-module/bindings.guard"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
 import { mainFrontendClient } from "../mainFrontendClient"
 
 export const fetchExerciseSubmissions = async (
@@ -16,3 +29,51 @@ export const fetchExerciseSubmissions = async (
   )
   return validateResponse(response, isExerciseSubmissions)
 }
+
+export const fetchFlaggedPeerReviews = async (
+  exerciseId: string,
+): Promise<Array<FlaggedPeerReview>> => {
+  const response = await mainFrontendClient.get(`/exercises/${exerciseId}/flagged-peer-reviews`, {
+    responseType: "json",
+  })
+  return validateResponse(response, isArray(isFlaggedPeerReview))
+}
+
+export const postResolvePeerReviewFlag = async (
+  exerciseId: string,
+  flagId: string,
+  resolve: ResolvePeerReviewSubmissionFlag,
+): Promise<PeerReviewSubmissionFlag> => {
+  const response = await mainFrontendClient.post(
+    `/exercises/${exerciseId}/flagged-peer-reviews/${flagId}/resolve`,
+    resolve,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isPeerReviewSubmissionFlag)
+}
+
+export const fetchPeerReviewReviewerStatistics = async (
+  exerciseId: string,
+): Promise<Array<PeerReviewReviewerStatistics>> => {
+  const response = await mainFrontendClient.get(
+    `/exercises/${exerciseId}/peer-review-reviewer-statistics`,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isArray(isPeerReviewReviewerStatistics))
+}
+
+export const fetchSubmissionSimilarities = async (
+  exerciseId: string,
+): Promise<Array<ExerciseTaskSubmissionSimilarityInfo>> => {
+  const response = await mainFrontendClient.get(
+    `/exercises/${exerciseId}/submission-similarities`,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isArray(isExerciseTaskSubmissionSimilarityInfo))
+}
@@ -0,0 +1,13 @@
+import { Impersonation, NewImpersonation } from "../../shared-module/bindings"
+import { isImpersonation } from "../../shared-module/bindings.guard"
+import { validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const startImpersonation = async (
+  newImpersonation: NewImpersonation,
+): Promise<Impersonation> => {
+  const response = await mainFrontendClient.post(`/impersonation/start`, newImpersonation, {
+    responseType: "json",
+  })
+  return validateResponse(response, isImpersonation)
+}
@@ -0,0 +1,63 @@
+import {
+  LtiDeepLinkingCourse,
+  LtiDeepLinkingResponse,
+  LtiDeepLinkingSelection,
+  LtiRegistration,
+  NewLtiRegistration,
+} from "../../shared-module/bindings"
+import {
+  isLtiDeepLinkingCourse,
+  isLtiDeepLinkingResponse,
+  isLtiRegistration,
+} from "../../shared-module/bindings.guard"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
+import { ltiClient } from "../ltiClient"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchLtiRegistrations = async (
+  organizationId: string,
+): Promise<Array<LtiRegistration>> => {
+  const response = await mainFrontendClient.get(
+    `/organizations/${organizationId}/lti-registrations`,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isArray(isLtiRegistration))
+}
+
+export const createLtiRegistration = async (
+  organizationId: string,
+  newRegistration: NewLtiRegistration,
+): Promise<LtiRegistration> => {
+  const response = await mainFrontendClient.post(
+    `/organizations/${organizationId}/lti-registrations`,
+    newRegistration,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isLtiRegistration)
+}
+
+export const deleteLtiRegistration = async (
+  organizationId: string,
+  ltiRegistrationId: string,
+): Promise<void> => {
+  await mainFrontendClient.delete(
+    `/organizations/${organizationId}/lti-registrations/${ltiRegistrationId}`,
+  )
+}
+
+export const fetchLtiDeepLinkingContent = async (
+  requestId: string,
+): Promise<Array<LtiDeepLinkingCourse>> => {
+  const response = await ltiClient.get(`/deep-linking/${requestId}`, { responseType: "json" })
+  return validateResponse(response, isArray(isLtiDeepLinkingCourse))
+}
+
+export const createLtiDeepLinkingResponse = async (
+  requestId: string,
+  selection: LtiDeepLinkingSelection,
+): Promise<LtiDeepLinkingResponse> => {
+  const response = await ltiClient.post(`/deep-linking/${requestId}`, selection, {
+    responseType: "json",
+  })
+  return validateResponse(response, isLtiDeepLinkingResponse)
+}
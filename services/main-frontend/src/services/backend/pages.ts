@@ -3,9 +3,16 @@ import {
   NewPage,
   Page,
   PageHistory,
+  PageHistoryDiff,
+  PageHistoryDiffQuery,
   PageInfo,
 } from "../../shared-module/bindings"
-import { isPage, isPageHistory, isPageInfo } from "../../shared-module/bindings.guard"
+import {
+  isPage,
+  isPageHistory,
+  isPageHistoryDiff,
+  isPageInfo,
+} from "../../shared-module/bindings.guard"
 import { isArray, isNumber, isString, validateResponse } from "../../shared-module/utils/fetching"
 import { mainFrontendClient } from "../mainFrontendClient"
 
@@ -37,6 +44,19 @@ export const fetchHistoryCountForPage = async (pageId: string): Promise<number>
   return validateResponse(response, isNumber)
 }
 
+export const fetchPageHistoryDiff = async (
+  pageId: string,
+  oldHistoryId: string,
+  newHistoryId: string,
+): Promise<PageHistoryDiff> => {
+  const params: PageHistoryDiffQuery = {
+    old_history_id: oldHistoryId,
+    new_history_id: newHistoryId,
+  }
+  const response = await mainFrontendClient.get(`/pages/${pageId}/history-diff`, { params })
+  return validateResponse(response, isPageHistoryDiff)
+}
+
 export const restorePage = async (pageId: string, historyId: string): Promise<string> => {
   const data: HistoryRestoreData = { history_id: historyId }
   const response = await mainFrontendClient.post(`/pages/${pageId}/restore`, data, {
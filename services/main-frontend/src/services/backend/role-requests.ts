@@ -0,0 +1,33 @@
+import { NewRoleRequest, RoleRequest } from "../../shared-module/bindings"
+import { isRoleRequest } from "../../shared-module/bindings.guard"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchOwnRoleRequests = async (): Promise<Array<RoleRequest>> => {
+  const response = await mainFrontendClient.get(`/role-requests`, { responseType: "json" })
+  return validateResponse(response, isArray(isRoleRequest))
+}
+
+export const createRoleRequest = async (newRoleRequest: NewRoleRequest): Promise<RoleRequest> => {
+  const response = await mainFrontendClient.post(`/role-requests`, newRoleRequest, {
+    responseType: "json",
+  })
+  return validateResponse(response, isRoleRequest)
+}
+
+export const fetchPendingRoleRequestsForOrganization = async (
+  organizationId: string,
+): Promise<Array<RoleRequest>> => {
+  const response = await mainFrontendClient.get(`/role-requests/organization/${organizationId}`, {
+    responseType: "json",
+  })
+  return validateResponse(response, isArray(isRoleRequest))
+}
+
+export const approveRoleRequest = async (roleRequestId: string): Promise<void> => {
+  await mainFrontendClient.post(`/role-requests/${roleRequestId}/approve`)
+}
+
+export const rejectRoleRequest = async (roleRequestId: string): Promise<void> => {
+  await mainFrontendClient.post(`/role-requests/${roleRequestId}/reject`)
+}
@@ -15,11 +15,16 @@ export const giveRole = async (
   email: string,
   role: UserRole,
   domain: RoleDomain,
+  validUntil: Date | null = null,
+  customRoleId: string | null = null,
 ): Promise<void> => {
   const data: RoleInfo = {
     email,
     role,
     domain,
+    valid_from: null,
+    valid_until: validUntil,
+    custom_role_id: customRoleId,
   }
   await mainFrontendClient.post(`/roles/add`, data, { responseType: "json" })
 }
@@ -33,6 +38,9 @@ export const removeRole = async (
     email,
     role,
     domain,
+    valid_from: null,
+    valid_until: null,
+    custom_role_id: null,
   }
   await mainFrontendClient.post(`/roles/remove`, data, { responseType: "json" })
 }
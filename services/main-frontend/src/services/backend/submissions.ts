@@ -1,5 +1,14 @@
-import { ExerciseSlideSubmissionInfo } from "../../shared-module/bindings"
-import { isExerciseSlideSubmissionInfo } from "../../shared-module/bindings.guard"
+import {
+  ExerciseSlideSubmissionInfo,
+  NewPeerReviewCalibrationReferenceAnswer,
+  PeerReviewCalibrationSampleInfo,
+  PeerReviewCalibrationSampleWithReferenceAnswers,
+} from "../../shared-module/bindings"
+import {
+  isExerciseSlideSubmissionInfo,
+  isPeerReviewCalibrationSampleInfo,
+  isPeerReviewCalibrationSampleWithReferenceAnswers,
+} from "../../shared-module/bindings.guard"
 import { validateResponse } from "../../shared-module/utils/fetching"
 import { mainFrontendClient } from "../mainFrontendClient"
 
@@ -14,3 +23,33 @@ export const fetchSubmissionInfo = async (
   )
   return validateResponse(response, isExerciseSlideSubmissionInfo)
 }
+
+export const fetchCalibrationSampleInfo = async (
+  submissionId: string,
+): Promise<PeerReviewCalibrationSampleInfo> => {
+  const response = await mainFrontendClient.get(
+    `/exercise-slide-submissions/${submissionId}/calibration-sample`,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isPeerReviewCalibrationSampleInfo)
+}
+
+export const putCalibrationSample = async (
+  submissionId: string,
+  referenceAnswers: NewPeerReviewCalibrationReferenceAnswer[],
+): Promise<PeerReviewCalibrationSampleWithReferenceAnswers> => {
+  const response = await mainFrontendClient.put(
+    `/exercise-slide-submissions/${submissionId}/calibration-sample`,
+    referenceAnswers,
+    {
+      responseType: "json",
+    },
+  )
+  return validateResponse(response, isPeerReviewCalibrationSampleWithReferenceAnswers)
+}
+
+export const deleteCalibrationSample = async (submissionId: string): Promise<void> => {
+  await mainFrontendClient.delete(`/exercise-slide-submissions/${submissionId}/calibration-sample`)
+}
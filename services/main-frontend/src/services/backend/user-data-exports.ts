@@ -0,0 +1,20 @@
+import { UserDataExport } from "../../shared-module/bindings"
+import { isUserDataExport } from "../../shared-module/bindings.guard"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchUserDataExports = async (): Promise<Array<UserDataExport>> => {
+  const response = await mainFrontendClient.get(`/user-data-exports`, { responseType: "json" })
+  return validateResponse(response, isArray(isUserDataExport))
+}
+
+export const createUserDataExport = async (): Promise<UserDataExport> => {
+  const response = await mainFrontendClient.post(`/user-data-exports`, null, {
+    responseType: "json",
+  })
+  return validateResponse(response, isUserDataExport)
+}
+
+export const userDataExportDownloadUrl = (userDataExportId: string): string => {
+  return `/api/v0/main-frontend/user-data-exports/${userDataExportId}/download`
+}
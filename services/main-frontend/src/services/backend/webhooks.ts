@@ -0,0 +1,88 @@
+import {
+  NewWebhookSubscription,
+  WebhookDelivery,
+  WebhookDeliveryAttempt,
+  WebhookSubscription,
+} from "../../shared-module/bindings"
+import {
+  isWebhookDelivery,
+  isWebhookDeliveryAttempt,
+  isWebhookSubscription,
+} from "../../shared-module/bindings.guard"
+import { isArray, validateResponse } from "../../shared-module/utils/fetching"
+import { mainFrontendClient } from "../mainFrontendClient"
+
+export const fetchWebhookSubscriptions = async (
+  organizationId: string,
+): Promise<Array<WebhookSubscription>> => {
+  const response = await mainFrontendClient.get(`/organizations/${organizationId}/webhooks`, {
+    responseType: "json",
+  })
+  return validateResponse(response, isArray(isWebhookSubscription))
+}
+
+export const createWebhookSubscription = async (
+  organizationId: string,
+  newSubscription: NewWebhookSubscription,
+): Promise<WebhookSubscription> => {
+  const response = await mainFrontendClient.post(
+    `/organizations/${organizationId}/webhooks`,
+    newSubscription,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isWebhookSubscription)
+}
+
+export const updateWebhookSubscription = async (
+  organizationId: string,
+  webhookId: string,
+  update: NewWebhookSubscription,
+): Promise<WebhookSubscription> => {
+  const response = await mainFrontendClient.put(
+    `/organizations/${organizationId}/webhooks/${webhookId}`,
+    update,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isWebhookSubscription)
+}
+
+export const deleteWebhookSubscription = async (
+  organizationId: string,
+  webhookId: string,
+): Promise<void> => {
+  await mainFrontendClient.delete(`/organizations/${organizationId}/webhooks/${webhookId}`)
+}
+
+export const fetchWebhookDeliveries = async (
+  organizationId: string,
+  webhookId: string,
+  page: number,
+  limit: number,
+): Promise<Array<WebhookDelivery>> => {
+  const response = await mainFrontendClient.get(
+    `/organizations/${organizationId}/webhooks/${webhookId}/deliveries`,
+    { responseType: "json", params: { page, limit } },
+  )
+  return validateResponse(response, isArray(isWebhookDelivery))
+}
+
+export const fetchWebhookDeliveryAttempts = async (
+  organizationId: string,
+  deliveryId: string,
+): Promise<Array<WebhookDeliveryAttempt>> => {
+  const response = await mainFrontendClient.get(
+    `/organizations/${organizationId}/webhooks/deliveries/${deliveryId}/attempts`,
+    { responseType: "json" },
+  )
+  return validateResponse(response, isArray(isWebhookDeliveryAttempt))
+}
+
+export const postRedeliverWebhookDelivery = async (
+  organizationId: string,
+  deliveryId: string,
+): Promise<WebhookDelivery> => {
+  const response = await mainFrontendClient.post(
+    `/organizations/${organizationId}/webhooks/deliveries/${deliveryId}/redeliver`,
+  )
+  return validateResponse(response, isWebhookDelivery)
+}
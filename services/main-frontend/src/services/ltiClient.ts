@@ -0,0 +1,7 @@
+import axios from "axios"
+
+import { interceptor } from "../shared-module/services/backend/interceptor"
+
+export const ltiClient = axios.create({ baseURL: "/api/v0/lti" })
+
+interceptor(ltiClient)
@@ -12,10 +12,17 @@ import {
   AnswerRequiringAttention,
   AnswerRequiringAttentionWithTasks,
   AnswersRequiringAttention,
+  ApiToken,
+  ApiTokenScope,
+  AuditLogAction,
+  AuditLogEntry,
+  AuditLogFilter,
   AutomaticCompletionRequirements,
+  BlockChange,
   BlockProposal,
   BlockProposalAction,
   BlockProposalInfo,
+  ChangeKind,
   Chapter,
   ChapterScore,
   ChapterStatus,
@@ -31,12 +38,16 @@ import {
   CmsPeerReviewQuestion,
   CompletionPolicy,
   CompletionRegistrationLink,
+  ContentLintIssue,
+  ContentLintIssueType,
+  ContentLintRun,
   ContentManagementPage,
   Course,
   CourseBackgroundQuestion,
   CourseBackgroundQuestionAnswer,
   CourseBackgroundQuestionsAndAnswers,
   CourseBackgroundQuestionType,
+  CourseContentLintReport,
   CourseCount,
   CourseExam,
   CourseInstance,
@@ -59,6 +70,10 @@ import {
   CourseStructure,
   CourseUpdate,
   CreateAccountDetails,
+  CreatedApiToken,
+  CustomRole,
+  CustomRoleUpdate,
+  CustomRoleWithPermissions,
   DatabaseChapter,
   EditProposalInfo,
   EmailTemplate,
@@ -93,62 +108,113 @@ import {
   ExerciseTaskGrading,
   ExerciseTaskGradingResult,
   ExerciseTaskSubmission,
+  ExerciseTaskSubmissionSimilarity,
+  ExerciseTaskSubmissionSimilarityInfo,
   ExerciseUserCounts,
   ExerciseWithExerciseTasks,
   Feedback,
   FeedbackBlock,
   FeedbackCount,
+  FlaggedPeerReview,
   GetEditProposalsQuery,
   GetFeedbackQuery,
+  GradeExportFormat,
   GradingProgress,
   HistoryChangeReason,
   HistoryRestoreData,
+  IdentityProviderInfo,
+  Impersonation,
   IsChapterFrontPage,
+  ItemChange,
   Login,
+  LtiDeepLinkingChapter,
+  LtiDeepLinkingCourse,
+  LtiDeepLinkingPage,
+  LtiDeepLinkingResponse,
+  LtiDeepLinkingSelection,
+  LtiRegistration,
   ManualCompletionPreview,
   ManualCompletionPreviewUser,
   MarkAsRead,
   MaterialReference,
   ModifiedModule,
   ModuleUpdates,
+  NewApiToken,
   NewChapter,
   NewCourse,
   NewCourseBackgroundQuestionAnswer,
   NewCourseModule,
+  NewCustomRole,
   NewExam,
   NewExerciseRepository,
   NewFeedback,
+  NewIdentityProvider,
+  NewImpersonation,
+  NewLtiRegistration,
   NewMaterialReference,
   NewModule,
   NewPage,
+  NewPeerReviewCalibrationReferenceAnswer,
+  NewPeerReviewSubmissionFlag,
+  NewPeerReviewSubmissionRating,
   NewProposedBlockEdit,
   NewProposedPageEdits,
   NewRegrading,
+  NewRolePermission,
+  NewRoleRequest,
   NewTeacherGradingDecision,
+  NewUrlRedirection,
+  NewWebhookSubscription,
   OEmbedResponse,
   Organization,
   OrgExam,
   Page,
   PageChapterAndCourseInformation,
+  PageDraft,
+  PageDraftUpdate,
+  PageEditLock,
+  PageEditor,
+  PageEditStatus,
   PageHistory,
+  PageHistoryDiff,
+  PageHistoryDiffQuery,
   PageInfo,
   PageNavigationInformation,
   PageProposal,
+  PagePublishingSchedule,
   PageRoutingData,
+  PageSaveConflict,
   PageSearchRequest,
   PageSearchResult,
   PageWithExercises,
   Pagination,
   PeerReviewAcceptingStrategy,
   PeerReviewAnswer,
+  PeerReviewCalibrationFeedback,
+  PeerReviewCalibrationQuestionFeedback,
+  PeerReviewCalibrationReferenceAnswer,
+  PeerReviewCalibrationSample,
+  PeerReviewCalibrationSampleInfo,
+  PeerReviewCalibrationSampleWithReferenceAnswers,
   PeerReviewConfig,
   PeerReviewQuestion,
   PeerReviewQuestionAndAnswer,
   PeerReviewQuestionSubmission,
   PeerReviewQuestionType,
+  PeerReviewReviewerStatistics,
+  PeerReviewRubric,
+  PeerReviewRubricCriterion,
+  PeerReviewRubricLevel,
+  PeerReviewRubricSelectedLevel,
   PeerReviewsRecieved,
+  PeerReviewSubmissionFlag,
+  PeerReviewSubmissionFlagResolution,
+  PeerReviewSubmissionRating,
+  PeerReviewTimeoutAction,
   PeerReviewWithQuestionsAndAnswers,
   PendingRole,
+  PermissionAction,
+  PermissionResourceType,
   PlaygroundExample,
   PlaygroundExampleData,
   PointMap,
@@ -159,13 +225,18 @@ import {
   RegradingInfo,
   RegradingSubmissionInfo,
   RepositoryExercise,
+  ResolvePeerReviewSubmissionFlag,
   Resource,
   ReviewingStage,
   RoleDomain,
   RoleInfo,
+  RolePermission,
   RoleQuery,
+  RoleRequest,
+  RoleRequestStatus,
   RoleUser,
   SaveCourseSettingsPayload,
+  SimilarSubmission,
   SpecRequest,
   StudentExerciseSlideSubmission,
   StudentExerciseSlideSubmissionResult,
@@ -177,7 +248,13 @@ import {
   TeacherManualCompletionRequest,
   Term,
   TermUpdate,
+  TextDiffPart,
+  TextDiffPartKind,
   UploadResult,
+  UrlRedirection,
+  UrlRedirectionProblem,
+  UrlRedirectionResolution,
+  UrlRedirectionWithResolution,
   User,
   UserCompletionInformation,
   UserCourseInstanceChapterExerciseProgress,
@@ -186,12 +263,19 @@ import {
   UserCourseInstanceProgress,
   UserCourseModuleCompletion,
   UserCourseSettings,
+  UserDataExport,
   UserExerciseState,
   UserInfo,
   UserModuleCompletionStatus,
   UserPointsUpdateStrategy,
   UserRole,
   UserWithModuleCompletions,
+  ValueChange,
+  WebhookDelivery,
+  WebhookDeliveryAttempt,
+  WebhookDeliveryStatus,
+  WebhookEventType,
+  WebhookSubscription,
 } from "./bindings"
 
 export function isAction(obj: unknown): obj is Action {
@@ -221,7 +305,9 @@ export function isAction(obj: unknown): obj is Action {
     (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
       typedObj["type"] === "usually_unacceptable_deletion") ||
     (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
-      typedObj["type"] === "upload_file")
+      typedObj["type"] === "upload_file") ||
+    (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+      typedObj["type"] === "impersonate")
   )
 }
 
@@ -234,6 +320,17 @@ export function isActionOnResource(obj: unknown): obj is ActionOnResource {
   )
 }
 
+export function isImpersonation(obj: unknown): obj is Impersonation {
+  const typedObj = obj as Impersonation
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["impersonated_user_id"] === "string" &&
+    typeof typedObj["course_id"] === "string" &&
+    typedObj["started_at"] instanceof Date &&
+    typedObj["expires_at"] instanceof Date
+  )
+}
+
 export function isResource(obj: unknown): obj is Resource {
   const typedObj = obj as Resource
   return (
@@ -293,8 +390,12 @@ export function isResource(obj: unknown): obj is Resource {
 export function isErrorData(obj: unknown): obj is ErrorData {
   const typedObj = obj as ErrorData
   return (
-    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
-    typeof typedObj["block_id"] === "string"
+    (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+      typeof typedObj["block_id"] === "string") ||
+    (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+      (isPageSaveConflict(typedObj["page_save_conflict"]) as boolean)) ||
+    (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+      (isPageEditLock(typedObj["page_edit_lock"]) as boolean))
   )
 }
 
@@ -309,6 +410,11 @@ export function isErrorResponse(obj: unknown): obj is ErrorResponse {
   )
 }
 
+export function isGradeExportFormat(obj: unknown): obj is GradeExportFormat {
+  const typedObj = obj as GradeExportFormat
+  return typedObj === "Csv" || typedObj === "Xlsx" || typedObj === "MoodleCsv"
+}
+
 export function isSpecRequest(obj: unknown): obj is SpecRequest {
   const typedObj = obj as SpecRequest
   return (
@@ -317,6 +423,83 @@ export function isSpecRequest(obj: unknown): obj is SpecRequest {
   )
 }
 
+export function isApiToken(obj: unknown): obj is ApiToken {
+  const typedObj = obj as ApiToken
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    typeof typedObj["user_id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    Array.isArray(typedObj["scopes"]) &&
+    typedObj["scopes"].every((e: any) => isApiTokenScope(e) as boolean) &&
+    typedObj["expires_at"] instanceof Date &&
+    (typedObj["last_used_at"] === null || typedObj["last_used_at"] instanceof Date)
+  )
+}
+
+export function isApiTokenScope(obj: unknown): obj is ApiTokenScope {
+  const typedObj = obj as ApiTokenScope
+  return typedObj === "Read" || typedObj === "Grade" || typedObj === "EditContent"
+}
+
+export function isNewApiToken(obj: unknown): obj is NewApiToken {
+  const typedObj = obj as NewApiToken
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["name"] === "string" &&
+    Array.isArray(typedObj["scopes"]) &&
+    typedObj["scopes"].every((e: any) => isApiTokenScope(e) as boolean) &&
+    typedObj["expires_at"] instanceof Date
+  )
+}
+
+export function isAuditLogAction(obj: unknown): obj is AuditLogAction {
+  const typedObj = obj as AuditLogAction
+  return (
+    typedObj === "RoleAdded" ||
+    typedObj === "RoleRemoved" ||
+    typedObj === "ManualCompletionsAdded" ||
+    typedObj === "TeacherGradingDecisionAdded" ||
+    typedObj === "RegradingCreated" ||
+    typedObj === "PageRestored" ||
+    typedObj === "CourseDeleted" ||
+    typedObj === "UserErased" ||
+    typedObj === "ImpersonationStarted" ||
+    typedObj === "ImpersonationEnded"
+  )
+}
+
+export function isAuditLogEntry(obj: unknown): obj is AuditLogEntry {
+  const typedObj = obj as AuditLogEntry
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    (typedObj["actor_user_id"] === null || typeof typedObj["actor_user_id"] === "string") &&
+    (typedObj["actor_email"] === null || typeof typedObj["actor_email"] === "string") &&
+    (typedObj["api_token_id"] === null || typeof typedObj["api_token_id"] === "string") &&
+    (typedObj["request_id"] === null || typeof typedObj["request_id"] === "string") &&
+    (isAuditLogAction(typedObj["action"]) as boolean) &&
+    typeof typedObj["resource_id"] === "string" &&
+    (typedObj["course_id"] === null || typeof typedObj["course_id"] === "string")
+  )
+}
+
+export function isAuditLogFilter(obj: unknown): obj is AuditLogFilter {
+  const typedObj = obj as AuditLogFilter
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (typedObj["actor_user_id"] === null || typeof typedObj["actor_user_id"] === "string") &&
+    (typedObj["action"] === null || (isAuditLogAction(typedObj["action"]) as boolean)) &&
+    (typedObj["course_id"] === null || typeof typedObj["course_id"] === "string") &&
+    (typedObj["resource_id"] === null || typeof typedObj["resource_id"] === "string") &&
+    (typedObj["created_after"] === null || typedObj["created_after"] instanceof Date) &&
+    (typedObj["created_before"] === null || typedObj["created_before"] instanceof Date)
+  )
+}
+
 export function isChapter(obj: unknown): obj is Chapter {
   const typedObj = obj as Chapter
   return (
@@ -334,7 +517,9 @@ export function isChapter(obj: unknown): obj is Chapter {
     (typedObj["opens_at"] === null || typedObj["opens_at"] instanceof Date) &&
     (typedObj["deadline"] === null || typedObj["deadline"] instanceof Date) &&
     (typedObj["copied_from"] === null || typeof typedObj["copied_from"] === "string") &&
-    typeof typedObj["course_module_id"] === "string"
+    typeof typedObj["course_module_id"] === "string" &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date) &&
+    (typedObj["unpublish_at"] === null || typedObj["unpublish_at"] instanceof Date)
   )
 }
 
@@ -352,7 +537,9 @@ export function isChapterUpdate(obj: unknown): obj is ChapterUpdate {
     (typedObj["front_page_id"] === null || typeof typedObj["front_page_id"] === "string") &&
     (typedObj["deadline"] === null || typedObj["deadline"] instanceof Date) &&
     (typedObj["opens_at"] === null || typedObj["opens_at"] instanceof Date) &&
-    (typedObj["course_module_id"] === null || typeof typedObj["course_module_id"] === "string")
+    (typedObj["course_module_id"] === null || typeof typedObj["course_module_id"] === "string") &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date) &&
+    (typedObj["unpublish_at"] === null || typedObj["unpublish_at"] instanceof Date)
   )
 }
 
@@ -394,7 +581,9 @@ export function isDatabaseChapter(obj: unknown): obj is DatabaseChapter {
     (typedObj["opens_at"] === null || typedObj["opens_at"] instanceof Date) &&
     (typedObj["deadline"] === null || typedObj["deadline"] instanceof Date) &&
     (typedObj["copied_from"] === null || typeof typedObj["copied_from"] === "string") &&
-    typeof typedObj["course_module_id"] === "string"
+    typeof typedObj["course_module_id"] === "string" &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date) &&
+    (typedObj["unpublish_at"] === null || typedObj["unpublish_at"] instanceof Date)
   )
 }
 
@@ -427,6 +616,56 @@ export function isUserCourseInstanceChapterProgress(
   )
 }
 
+export function isContentLintIssue(obj: unknown): obj is ContentLintIssue {
+  const typedObj = obj as ContentLintIssue
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["page_id"] === "string" &&
+    typeof typedObj["page_title"] === "string" &&
+    typeof typedObj["page_url_path"] === "string" &&
+    (typedObj["block_id"] === null || typeof typedObj["block_id"] === "string") &&
+    (isContentLintIssueType(typedObj["issue_type"]) as boolean) &&
+    typeof typedObj["target"] === "string" &&
+    (typedObj["suggestion"] === null || typeof typedObj["suggestion"] === "string")
+  )
+}
+
+export function isContentLintIssueType(obj: unknown): obj is ContentLintIssueType {
+  const typedObj = obj as ContentLintIssueType
+  return (
+    typedObj === "LinkToMissingPage" ||
+    typedObj === "LinkToDeletedPage" ||
+    typedObj === "LinkToHiddenPage" ||
+    typedObj === "LinkThroughRedirection" ||
+    typedObj === "MissingMediaFile" ||
+    typedObj === "UnsupportedEmbed" ||
+    typedObj === "ImageWithoutAltText" ||
+    typedObj === "MissingExercise"
+  )
+}
+
+export function isContentLintRun(obj: unknown): obj is ContentLintRun {
+  const typedObj = obj as ContentLintRun
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typeof typedObj["course_id"] === "string" &&
+    typeof typedObj["pages_checked"] === "number"
+  )
+}
+
+export function isCourseContentLintReport(obj: unknown): obj is CourseContentLintReport {
+  const typedObj = obj as CourseContentLintReport
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (typedObj["run"] === null || (isContentLintRun(typedObj["run"]) as boolean)) &&
+    Array.isArray(typedObj["issues"]) &&
+    typedObj["issues"].every((e: any) => isContentLintIssue(e) as boolean)
+  )
+}
+
 export function isCourseInstanceEnrollment(obj: unknown): obj is CourseInstanceEnrollment {
   const typedObj = obj as CourseInstanceEnrollment
   return (
@@ -784,6 +1023,50 @@ export function isNewCourse(obj: unknown): obj is NewCourse {
   )
 }
 
+export function isCustomRole(obj: unknown): obj is CustomRole {
+  const typedObj = obj as CustomRole
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    typeof typedObj["organization_id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    (isUserRole(typedObj["base_role"]) as boolean)
+  )
+}
+
+export function isCustomRoleUpdate(obj: unknown): obj is CustomRoleUpdate {
+  const typedObj = obj as CustomRoleUpdate
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["name"] === "string" &&
+    Array.isArray(typedObj["permissions"]) &&
+    typedObj["permissions"].every((e: any) => isNewRolePermission(e) as boolean)
+  )
+}
+
+export function isCustomRoleWithPermissions(obj: unknown): obj is CustomRoleWithPermissions {
+  const typedObj = obj as CustomRoleWithPermissions
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isCustomRole(typedObj["custom_role"]) as boolean) &&
+    Array.isArray(typedObj["permissions"]) &&
+    typedObj["permissions"].every((e: any) => isRolePermission(e) as boolean)
+  )
+}
+
+export function isNewCustomRole(obj: unknown): obj is NewCustomRole {
+  const typedObj = obj as NewCustomRole
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["name"] === "string" &&
+    (isUserRole(typedObj["base_role"]) as boolean) &&
+    Array.isArray(typedObj["permissions"]) &&
+    typedObj["permissions"].every((e: any) => isNewRolePermission(e) as boolean)
+  )
+}
+
 export function isEmailTemplate(obj: unknown): obj is EmailTemplate {
   const typedObj = obj as EmailTemplate
   return (
@@ -1149,6 +1432,55 @@ export function isUserPointsUpdateStrategy(obj: unknown): obj is UserPointsUpdat
   )
 }
 
+export function isExerciseTaskSubmissionSimilarity(
+  obj: unknown,
+): obj is ExerciseTaskSubmissionSimilarity {
+  const typedObj = obj as ExerciseTaskSubmissionSimilarity
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    (typedObj["deleted_at"] === null || typedObj["deleted_at"] instanceof Date) &&
+    typeof typedObj["exercise_id"] === "string" &&
+    typeof typedObj["course_instance_id"] === "string" &&
+    typeof typedObj["exercise_task_id"] === "string" &&
+    typeof typedObj["first_exercise_task_submission_id"] === "string" &&
+    typeof typedObj["second_exercise_task_submission_id"] === "string" &&
+    typeof typedObj["similarity"] === "number"
+  )
+}
+
+export function isExerciseTaskSubmissionSimilarityInfo(
+  obj: unknown,
+): obj is ExerciseTaskSubmissionSimilarityInfo {
+  const typedObj = obj as ExerciseTaskSubmissionSimilarityInfo
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["course_instance_id"] === "string" &&
+    typeof typedObj["exercise_task_id"] === "string" &&
+    typeof typedObj["similarity"] === "number" &&
+    (isSimilarSubmission(typedObj["first"]) as boolean) &&
+    (isSimilarSubmission(typedObj["second"]) as boolean)
+  )
+}
+
+export function isSimilarSubmission(obj: unknown): obj is SimilarSubmission {
+  const typedObj = obj as SimilarSubmission
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["exercise_task_submission_id"] === "string" &&
+    typeof typedObj["exercise_slide_submission_id"] === "string" &&
+    typeof typedObj["user_id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    (typedObj["user_exercise_state_id"] === null ||
+      typeof typedObj["user_exercise_state_id"] === "string") &&
+    (typedObj["latest_teacher_decision"] === null ||
+      (isTeacherDecisionType(typedObj["latest_teacher_decision"]) as boolean))
+  )
+}
+
 export function isExerciseTaskSubmission(obj: unknown): obj is ExerciseTaskSubmission {
   const typedObj = obj as ExerciseTaskSubmission
   return (
@@ -1174,6 +1506,14 @@ export function isPeerReviewsRecieved(obj: unknown): obj is PeerReviewsRecieved
     Array.isArray(typedObj["peer_review_question_submissions"]) &&
     typedObj["peer_review_question_submissions"].every(
       (e: any) => isPeerReviewQuestionSubmission(e) as boolean,
+    ) &&
+    Array.isArray(typedObj["peer_review_submission_ratings"]) &&
+    typedObj["peer_review_submission_ratings"].every(
+      (e: any) => isPeerReviewSubmissionRating(e) as boolean,
+    ) &&
+    Array.isArray(typedObj["peer_review_submission_flags"]) &&
+    typedObj["peer_review_submission_flags"].every(
+      (e: any) => isPeerReviewSubmissionFlag(e) as boolean,
     )
   )
 }
@@ -1365,6 +1705,33 @@ export function isTermUpdate(obj: unknown): obj is TermUpdate {
   )
 }
 
+export function isIdentityProviderInfo(obj: unknown): obj is IdentityProviderInfo {
+  const typedObj = obj as IdentityProviderInfo
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["organization_id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    typeof typedObj["issuer_url"] === "string"
+  )
+}
+
+export function isNewIdentityProvider(obj: unknown): obj is NewIdentityProvider {
+  const typedObj = obj as NewIdentityProvider
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["name"] === "string" &&
+    typeof typedObj["issuer_url"] === "string" &&
+    typeof typedObj["client_id"] === "string" &&
+    (typedObj["client_secret"] === null || typeof typedObj["client_secret"] === "string") &&
+    typeof typedObj["scopes"] === "string" &&
+    typeof typedObj["email_claim"] === "string" &&
+    typeof typedObj["first_name_claim"] === "string" &&
+    typeof typedObj["last_name_claim"] === "string" &&
+    typeof typedObj["link_existing_users_by_email"] === "boolean"
+  )
+}
+
 export function isAnswerRequiringAttentionWithTasks(
   obj: unknown,
 ): obj is AnswerRequiringAttentionWithTasks {
@@ -1468,7 +1835,8 @@ export function isCourseMaterialPeerReviewData(obj: unknown): obj is CourseMater
     (isPeerReviewConfig(typedObj["peer_review_config"]) as boolean) &&
     Array.isArray(typedObj["peer_review_questions"]) &&
     typedObj["peer_review_questions"].every((e: any) => isPeerReviewQuestion(e) as boolean) &&
-    typeof typedObj["num_peer_reviews_given"] === "number"
+    typeof typedObj["num_peer_reviews_given"] === "number" &&
+    typeof typedObj["is_calibration_sample"] === "boolean"
   )
 }
 
@@ -1494,7 +1862,10 @@ export function isCourseMaterialPeerReviewQuestionAnswer(
     ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
     typeof typedObj["peer_review_question_id"] === "string" &&
     (typedObj["text_data"] === null || typeof typedObj["text_data"] === "string") &&
-    (typedObj["number_data"] === null || typeof typedObj["number_data"] === "number")
+    (typedObj["number_data"] === null || typeof typedObj["number_data"] === "number") &&
+    (typedObj["rubric_level_ids"] === null ||
+      (Array.isArray(typedObj["rubric_level_ids"]) &&
+        typedObj["rubric_level_ids"].every((e: any) => typeof e === "string")))
   )
 }
 
@@ -1513,6 +1884,90 @@ export function isCourseMaterialPeerReviewSubmission(
   )
 }
 
+export function isFlaggedPeerReview(obj: unknown): obj is FlaggedPeerReview {
+  const typedObj = obj as FlaggedPeerReview
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isPeerReviewSubmissionFlag(typedObj["flag"]) as boolean) &&
+    typeof typedObj["reviewer_user_id"] === "string" &&
+    typeof typedObj["exercise_slide_submission_id"] === "string" &&
+    (typedObj["peer_review"] === null ||
+      (isPeerReviewWithQuestionsAndAnswers(typedObj["peer_review"]) as boolean))
+  )
+}
+
+export function isNewPeerReviewSubmissionFlag(obj: unknown): obj is NewPeerReviewSubmissionFlag {
+  const typedObj = obj as NewPeerReviewSubmissionFlag
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["reason"] === "string"
+  )
+}
+
+export function isNewPeerReviewSubmissionRating(
+  obj: unknown,
+): obj is NewPeerReviewSubmissionRating {
+  const typedObj = obj as NewPeerReviewSubmissionRating
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["helpfulness"] === "number"
+  )
+}
+
+export function isPeerReviewCalibrationFeedback(
+  obj: unknown,
+): obj is PeerReviewCalibrationFeedback {
+  const typedObj = obj as PeerReviewCalibrationFeedback
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    Array.isArray(typedObj["question_feedback"]) &&
+    typedObj["question_feedback"].every(
+      (e: any) => isPeerReviewCalibrationQuestionFeedback(e) as boolean,
+    ) &&
+    typeof typedObj["calibration_samples_reviewed"] === "number" &&
+    typeof typedObj["calibration_samples_to_review"] === "number"
+  )
+}
+
+export function isPeerReviewCalibrationQuestionFeedback(
+  obj: unknown,
+): obj is PeerReviewCalibrationQuestionFeedback {
+  const typedObj = obj as PeerReviewCalibrationQuestionFeedback
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["peer_review_question_id"] === "string" &&
+    (typedObj["given_number_data"] === null || typeof typedObj["given_number_data"] === "number") &&
+    typeof typedObj["reference_number_data"] === "number" &&
+    (typedObj["difference"] === null || typeof typedObj["difference"] === "number") &&
+    (typedObj["explanation"] === null || typeof typedObj["explanation"] === "string")
+  )
+}
+
+export function isPeerReviewCalibrationSampleInfo(
+  obj: unknown,
+): obj is PeerReviewCalibrationSampleInfo {
+  const typedObj = obj as PeerReviewCalibrationSampleInfo
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (typedObj["calibration_sample"] === null ||
+      (isPeerReviewCalibrationSampleWithReferenceAnswers(
+        typedObj["calibration_sample"],
+      ) as boolean)) &&
+    Array.isArray(typedObj["peer_review_questions"]) &&
+    typedObj["peer_review_questions"].every((e: any) => isPeerReviewQuestion(e) as boolean)
+  )
+}
+
+export function isResolvePeerReviewSubmissionFlag(
+  obj: unknown,
+): obj is ResolvePeerReviewSubmissionFlag {
+  const typedObj = obj as ResolvePeerReviewSubmissionFlag
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isPeerReviewSubmissionFlagResolution(typedObj["resolution"]) as boolean)
+  )
+}
+
 export function isCompletionRegistrationLink(obj: unknown): obj is CompletionRegistrationLink {
   const typedObj = obj as CompletionRegistrationLink
   return (
@@ -1640,6 +2095,38 @@ export function isUserWithModuleCompletions(obj: unknown): obj is UserWithModule
   )
 }
 
+export function isLtiRegistration(obj: unknown): obj is LtiRegistration {
+  const typedObj = obj as LtiRegistration
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    typeof typedObj["organization_id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    typeof typedObj["issuer"] === "string" &&
+    typeof typedObj["client_id"] === "string" &&
+    typeof typedObj["auth_login_url"] === "string" &&
+    typeof typedObj["auth_token_url"] === "string" &&
+    typeof typedObj["jwks_url"] === "string" &&
+    typeof typedObj["link_existing_users_by_email"] === "boolean"
+  )
+}
+
+export function isNewLtiRegistration(obj: unknown): obj is NewLtiRegistration {
+  const typedObj = obj as NewLtiRegistration
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["name"] === "string" &&
+    typeof typedObj["issuer"] === "string" &&
+    typeof typedObj["client_id"] === "string" &&
+    typeof typedObj["auth_login_url"] === "string" &&
+    typeof typedObj["auth_token_url"] === "string" &&
+    typeof typedObj["jwks_url"] === "string" &&
+    typeof typedObj["link_existing_users_by_email"] === "boolean"
+  )
+}
+
 export function isMaterialReference(obj: unknown): obj is MaterialReference {
   const typedObj = obj as MaterialReference
   return (
@@ -1679,55 +2166,140 @@ export function isOrganization(obj: unknown): obj is Organization {
   )
 }
 
-export function isHistoryChangeReason(obj: unknown): obj is HistoryChangeReason {
-  const typedObj = obj as HistoryChangeReason
-  return typedObj === "PageSaved" || typedObj === "HistoryRestored"
-}
-
-export function isPageHistory(obj: unknown): obj is PageHistory {
-  const typedObj = obj as PageHistory
+export function isPageDraft(obj: unknown): obj is PageDraft {
+  const typedObj = obj as PageDraft
   return (
     ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
     typeof typedObj["id"] === "string" &&
+    typeof typedObj["page_id"] === "string" &&
     typedObj["created_at"] instanceof Date &&
-    typeof typedObj["title"] === "string" &&
-    (isHistoryChangeReason(typedObj["history_change_reason"]) as boolean) &&
-    (typedObj["restored_from_id"] === null || typeof typedObj["restored_from_id"] === "string") &&
-    typeof typedObj["author_user_id"] === "string"
+    typedObj["updated_at"] instanceof Date &&
+    (typedObj["author_user_id"] === null || typeof typedObj["author_user_id"] === "string") &&
+    (isCmsPageUpdate(typedObj["content"]) as boolean) &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date)
   )
 }
 
-export function isCmsPageExercise(obj: unknown): obj is CmsPageExercise {
-  const typedObj = obj as CmsPageExercise
+export function isPageDraftUpdate(obj: unknown): obj is PageDraftUpdate {
+  const typedObj = obj as PageDraftUpdate
   return (
     ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
-    typeof typedObj["id"] === "string" &&
-    typeof typedObj["name"] === "string" &&
-    typeof typedObj["order_number"] === "number" &&
-    typeof typedObj["score_maximum"] === "number" &&
-    (typedObj["max_tries_per_slide"] === null ||
-      typeof typedObj["max_tries_per_slide"] === "number") &&
-    typeof typedObj["limit_number_of_tries"] === "boolean" &&
-    (typedObj["deadline"] === null || typedObj["deadline"] instanceof Date) &&
-    typeof typedObj["needs_peer_review"] === "boolean" &&
-    (typedObj["peer_review_config"] === null ||
-      (isCmsPeerReviewConfig(typedObj["peer_review_config"]) as boolean)) &&
-    (typedObj["peer_review_questions"] === null ||
-      (Array.isArray(typedObj["peer_review_questions"]) &&
-        typedObj["peer_review_questions"].every(
-          (e: any) => isCmsPeerReviewQuestion(e) as boolean,
-        ))) &&
-    typeof typedObj["use_course_default_peer_review_config"] === "boolean"
+    (isCmsPageUpdate(typedObj["content"]) as boolean) &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date)
   )
 }
 
-export function isCmsPageExerciseSlide(obj: unknown): obj is CmsPageExerciseSlide {
-  const typedObj = obj as CmsPageExerciseSlide
+export function isPageEditLock(obj: unknown): obj is PageEditLock {
+  const typedObj = obj as PageEditLock
   return (
     ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
-    typeof typedObj["id"] === "string" &&
-    typeof typedObj["exercise_id"] === "string" &&
-    typeof typedObj["order_number"] === "number"
+    typeof typedObj["page_id"] === "string" &&
+    typeof typedObj["user_id"] === "string" &&
+    (typedObj["first_name"] === null || typeof typedObj["first_name"] === "string") &&
+    (typedObj["last_name"] === null || typeof typedObj["last_name"] === "string") &&
+    typeof typedObj["email"] === "string" &&
+    typedObj["expires_at"] instanceof Date
+  )
+}
+
+export function isPageEditStatus(obj: unknown): obj is PageEditStatus {
+  const typedObj = obj as PageEditStatus
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["page_id"] === "string" &&
+    (typedObj["lock"] === null || (isPageEditLock(typedObj["lock"]) as boolean)) &&
+    Array.isArray(typedObj["editors"]) &&
+    typedObj["editors"].every((e: any) => isPageEditor(e) as boolean)
+  )
+}
+
+export function isPageEditor(obj: unknown): obj is PageEditor {
+  const typedObj = obj as PageEditor
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["user_id"] === "string" &&
+    (typedObj["first_name"] === null || typeof typedObj["first_name"] === "string") &&
+    (typedObj["last_name"] === null || typeof typedObj["last_name"] === "string") &&
+    typeof typedObj["email"] === "string" &&
+    typedObj["last_seen_at"] instanceof Date
+  )
+}
+
+export function isHistoryChangeReason(obj: unknown): obj is HistoryChangeReason {
+  const typedObj = obj as HistoryChangeReason
+  return (
+    typedObj === "PageSaved" || typedObj === "HistoryRestored" || typedObj === "DraftPublished"
+  )
+}
+
+export function isPageHistory(obj: unknown): obj is PageHistory {
+  const typedObj = obj as PageHistory
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typeof typedObj["title"] === "string" &&
+    (isHistoryChangeReason(typedObj["history_change_reason"]) as boolean) &&
+    (typedObj["restored_from_id"] === null || typeof typedObj["restored_from_id"] === "string") &&
+    typeof typedObj["author_user_id"] === "string"
+  )
+}
+
+export function isPageHistoryDiff(obj: unknown): obj is PageHistoryDiff {
+  const typedObj = obj as PageHistoryDiff
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["old_history_id"] === "string" &&
+    typeof typedObj["new_history_id"] === "string" &&
+    (typedObj["title_diff"] === null ||
+      (Array.isArray(typedObj["title_diff"]) &&
+        typedObj["title_diff"].every((e: any) => isTextDiffPart(e) as boolean))) &&
+    Array.isArray(typedObj["blocks"]) &&
+    typedObj["blocks"].every((e: any) => isBlockChange(e) as boolean) &&
+    Array.isArray(typedObj["exercises"]) &&
+    typedObj["exercises"].every((e: any) => isItemChange(e) as boolean) &&
+    Array.isArray(typedObj["exercise_slides"]) &&
+    typedObj["exercise_slides"].every((e: any) => isItemChange(e) as boolean) &&
+    Array.isArray(typedObj["exercise_tasks"]) &&
+    typedObj["exercise_tasks"].every((e: any) => isItemChange(e) as boolean) &&
+    Array.isArray(typedObj["peer_review_configs"]) &&
+    typedObj["peer_review_configs"].every((e: any) => isItemChange(e) as boolean) &&
+    Array.isArray(typedObj["peer_review_questions"]) &&
+    typedObj["peer_review_questions"].every((e: any) => isItemChange(e) as boolean)
+  )
+}
+
+export function isCmsPageExercise(obj: unknown): obj is CmsPageExercise {
+  const typedObj = obj as CmsPageExercise
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    typeof typedObj["order_number"] === "number" &&
+    typeof typedObj["score_maximum"] === "number" &&
+    (typedObj["max_tries_per_slide"] === null ||
+      typeof typedObj["max_tries_per_slide"] === "number") &&
+    typeof typedObj["limit_number_of_tries"] === "boolean" &&
+    (typedObj["deadline"] === null || typedObj["deadline"] instanceof Date) &&
+    typeof typedObj["needs_peer_review"] === "boolean" &&
+    (typedObj["peer_review_config"] === null ||
+      (isCmsPeerReviewConfig(typedObj["peer_review_config"]) as boolean)) &&
+    (typedObj["peer_review_questions"] === null ||
+      (Array.isArray(typedObj["peer_review_questions"]) &&
+        typedObj["peer_review_questions"].every(
+          (e: any) => isCmsPeerReviewQuestion(e) as boolean,
+        ))) &&
+    typeof typedObj["use_course_default_peer_review_config"] === "boolean"
+  )
+}
+
+export function isCmsPageExerciseSlide(obj: unknown): obj is CmsPageExerciseSlide {
+  const typedObj = obj as CmsPageExerciseSlide
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["exercise_id"] === "string" &&
+    typeof typedObj["order_number"] === "number"
   )
 }
 
@@ -1754,7 +2326,8 @@ export function isCmsPageUpdate(obj: unknown): obj is CmsPageUpdate {
     typedObj["exercise_tasks"].every((e: any) => isCmsPageExerciseTask(e) as boolean) &&
     typeof typedObj["url_path"] === "string" &&
     typeof typedObj["title"] === "string" &&
-    (typedObj["chapter_id"] === null || typeof typedObj["chapter_id"] === "string")
+    (typedObj["chapter_id"] === null || typeof typedObj["chapter_id"] === "string") &&
+    (typedObj["base_history_id"] === null || typeof typedObj["base_history_id"] === "string")
   )
 }
 
@@ -1773,7 +2346,8 @@ export function isContentManagementPage(obj: unknown): obj is ContentManagementP
     typedObj["peer_review_configs"].every((e: any) => isCmsPeerReviewConfig(e) as boolean) &&
     Array.isArray(typedObj["peer_review_questions"]) &&
     typedObj["peer_review_questions"].every((e: any) => isCmsPeerReviewQuestion(e) as boolean) &&
-    typeof typedObj["organization_id"] === "string"
+    typeof typedObj["organization_id"] === "string" &&
+    (typedObj["history_id"] === null || typeof typedObj["history_id"] === "string")
   )
 }
 
@@ -1785,7 +2359,10 @@ export function isCoursePageWithUserData(obj: unknown): obj is CoursePageWithUse
     (typedObj["instance"] === null || (isCourseInstance(typedObj["instance"]) as boolean)) &&
     (typedObj["settings"] === null || (isUserCourseSettings(typedObj["settings"]) as boolean)) &&
     typeof typedObj["was_redirected"] === "boolean" &&
-    typeof typedObj["is_test_mode"] === "boolean"
+    (typedObj["redirected_course_slug"] === null ||
+      typeof typedObj["redirected_course_slug"] === "string") &&
+    typeof typedObj["is_test_mode"] === "boolean" &&
+    typeof typedObj["is_draft"] === "boolean"
   )
 }
 
@@ -1860,7 +2437,9 @@ export function isPage(obj: unknown): obj is Page {
     (typedObj["deleted_at"] === null || typedObj["deleted_at"] instanceof Date) &&
     typeof typedObj["order_number"] === "number" &&
     (typedObj["copied_from"] === null || typeof typedObj["copied_from"] === "string") &&
-    typeof typedObj["hidden"] === "boolean"
+    typeof typedObj["hidden"] === "boolean" &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date) &&
+    (typedObj["unpublish_at"] === null || typedObj["unpublish_at"] instanceof Date)
   )
 }
 
@@ -1907,6 +2486,15 @@ export function isPageNavigationInformation(obj: unknown): obj is PageNavigation
   )
 }
 
+export function isPagePublishingSchedule(obj: unknown): obj is PagePublishingSchedule {
+  const typedObj = obj as PagePublishingSchedule
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date) &&
+    (typedObj["unpublish_at"] === null || typedObj["unpublish_at"] instanceof Date)
+  )
+}
+
 export function isPageRoutingData(obj: unknown): obj is PageRoutingData {
   const typedObj = obj as PageRoutingData
   return (
@@ -1922,6 +2510,15 @@ export function isPageRoutingData(obj: unknown): obj is PageRoutingData {
   )
 }
 
+export function isPageSaveConflict(obj: unknown): obj is PageSaveConflict {
+  const typedObj = obj as PageSaveConflict
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isContentManagementPage(typedObj["current"]) as boolean) &&
+    (typedObj["merged"] === null || (isCmsPageUpdate(typedObj["merged"]) as boolean))
+  )
+}
+
 export function isPageSearchRequest(obj: unknown): obj is PageSearchRequest {
   const typedObj = obj as PageSearchRequest
   return (
@@ -1958,11 +2555,69 @@ export function isPageWithExercises(obj: unknown): obj is PageWithExercises {
     typeof typedObj["order_number"] === "number" &&
     (typedObj["copied_from"] === null || typeof typedObj["copied_from"] === "string") &&
     typeof typedObj["hidden"] === "boolean" &&
+    (typedObj["publish_at"] === null || typedObj["publish_at"] instanceof Date) &&
+    (typedObj["unpublish_at"] === null || typedObj["unpublish_at"] instanceof Date) &&
     Array.isArray(typedObj["exercises"]) &&
     typedObj["exercises"].every((e: any) => isExercise(e) as boolean)
   )
 }
 
+export function isNewPeerReviewCalibrationReferenceAnswer(
+  obj: unknown,
+): obj is NewPeerReviewCalibrationReferenceAnswer {
+  const typedObj = obj as NewPeerReviewCalibrationReferenceAnswer
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["peer_review_question_id"] === "string" &&
+    typeof typedObj["reference_number_data"] === "number" &&
+    (typedObj["explanation"] === null || typeof typedObj["explanation"] === "string")
+  )
+}
+
+export function isPeerReviewCalibrationReferenceAnswer(
+  obj: unknown,
+): obj is PeerReviewCalibrationReferenceAnswer {
+  const typedObj = obj as PeerReviewCalibrationReferenceAnswer
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    (typedObj["deleted_at"] === null || typedObj["deleted_at"] instanceof Date) &&
+    typeof typedObj["peer_review_calibration_sample_id"] === "string" &&
+    typeof typedObj["peer_review_question_id"] === "string" &&
+    typeof typedObj["reference_number_data"] === "number" &&
+    (typedObj["explanation"] === null || typeof typedObj["explanation"] === "string")
+  )
+}
+
+export function isPeerReviewCalibrationSample(obj: unknown): obj is PeerReviewCalibrationSample {
+  const typedObj = obj as PeerReviewCalibrationSample
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    (typedObj["deleted_at"] === null || typedObj["deleted_at"] instanceof Date) &&
+    typeof typedObj["exercise_slide_submission_id"] === "string" &&
+    typeof typedObj["exercise_id"] === "string"
+  )
+}
+
+export function isPeerReviewCalibrationSampleWithReferenceAnswers(
+  obj: unknown,
+): obj is PeerReviewCalibrationSampleWithReferenceAnswers {
+  const typedObj = obj as PeerReviewCalibrationSampleWithReferenceAnswers
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isPeerReviewCalibrationSample(typedObj["calibration_sample"]) as boolean) &&
+    Array.isArray(typedObj["reference_answers"]) &&
+    typedObj["reference_answers"].every(
+      (e: any) => isPeerReviewCalibrationReferenceAnswer(e) as boolean,
+    )
+  )
+}
+
 export function isCourseMaterialPeerReviewConfig(
   obj: unknown,
 ): obj is CourseMaterialPeerReviewConfig {
@@ -1987,7 +2642,12 @@ export function isCmsPeerReviewConfig(obj: unknown): obj is CmsPeerReviewConfig
     typeof typedObj["peer_reviews_to_give"] === "number" &&
     typeof typedObj["peer_reviews_to_receive"] === "number" &&
     typeof typedObj["accepting_threshold"] === "number" &&
-    (isPeerReviewAcceptingStrategy(typedObj["accepting_strategy"]) as boolean)
+    (isPeerReviewAcceptingStrategy(typedObj["accepting_strategy"]) as boolean) &&
+    typeof typedObj["calibration_samples_to_review"] === "number" &&
+    typeof typedObj["timeout_in_days"] === "number" &&
+    (isPeerReviewTimeoutAction(typedObj["timeout_action"]) as boolean) &&
+    (typedObj["manual_review_timeout_in_days"] === null ||
+      typeof typedObj["manual_review_timeout_in_days"] === "number")
   )
 }
 
@@ -2006,7 +2666,8 @@ export function isPeerReviewAcceptingStrategy(obj: unknown): obj is PeerReviewAc
   return (
     typedObj === "AutomaticallyAcceptOrRejectByAverage" ||
     typedObj === "AutomaticallyAcceptOrManualReviewByAverage" ||
-    typedObj === "ManualReviewEverything"
+    typedObj === "ManualReviewEverything" ||
+    typedObj === "AutomaticallyGradeByRubricAverage"
   )
 }
 
@@ -2023,7 +2684,21 @@ export function isPeerReviewConfig(obj: unknown): obj is PeerReviewConfig {
     typeof typedObj["peer_reviews_to_give"] === "number" &&
     typeof typedObj["peer_reviews_to_receive"] === "number" &&
     typeof typedObj["accepting_threshold"] === "number" &&
-    (isPeerReviewAcceptingStrategy(typedObj["accepting_strategy"]) as boolean)
+    (isPeerReviewAcceptingStrategy(typedObj["accepting_strategy"]) as boolean) &&
+    typeof typedObj["calibration_samples_to_review"] === "number" &&
+    typeof typedObj["timeout_in_days"] === "number" &&
+    (isPeerReviewTimeoutAction(typedObj["timeout_action"]) as boolean) &&
+    (typedObj["manual_review_timeout_in_days"] === null ||
+      typeof typedObj["manual_review_timeout_in_days"] === "number")
+  )
+}
+
+export function isPeerReviewTimeoutAction(obj: unknown): obj is PeerReviewTimeoutAction {
+  const typedObj = obj as PeerReviewTimeoutAction
+  return (
+    typedObj === "MoveToManualReview" ||
+    typedObj === "GiveFullPoints" ||
+    typedObj === "AcceptByCurrentAverage"
   )
 }
 
@@ -2036,7 +2711,8 @@ export function isCmsPeerReviewQuestion(obj: unknown): obj is CmsPeerReviewQuest
     typeof typedObj["order_number"] === "number" &&
     typeof typedObj["question"] === "string" &&
     (isPeerReviewQuestionType(typedObj["question_type"]) as boolean) &&
-    typeof typedObj["answer_required"] === "boolean"
+    typeof typedObj["answer_required"] === "boolean" &&
+    (typedObj["rubric"] === null || (isPeerReviewRubric(typedObj["rubric"]) as boolean))
   )
 }
 
@@ -2052,13 +2728,44 @@ export function isPeerReviewQuestion(obj: unknown): obj is PeerReviewQuestion {
     typeof typedObj["order_number"] === "number" &&
     typeof typedObj["question"] === "string" &&
     (isPeerReviewQuestionType(typedObj["question_type"]) as boolean) &&
-    typeof typedObj["answer_required"] === "boolean"
+    typeof typedObj["answer_required"] === "boolean" &&
+    (typedObj["rubric"] === null || (isPeerReviewRubric(typedObj["rubric"]) as boolean))
   )
 }
 
 export function isPeerReviewQuestionType(obj: unknown): obj is PeerReviewQuestionType {
   const typedObj = obj as PeerReviewQuestionType
-  return typedObj === "Essay" || typedObj === "Scale"
+  return typedObj === "Essay" || typedObj === "Scale" || typedObj === "Rubric"
+}
+
+export function isPeerReviewRubric(obj: unknown): obj is PeerReviewRubric {
+  const typedObj = obj as PeerReviewRubric
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    Array.isArray(typedObj["criteria"]) &&
+    typedObj["criteria"].every((e: any) => isPeerReviewRubricCriterion(e) as boolean)
+  )
+}
+
+export function isPeerReviewRubricCriterion(obj: unknown): obj is PeerReviewRubricCriterion {
+  const typedObj = obj as PeerReviewRubricCriterion
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    Array.isArray(typedObj["levels"]) &&
+    typedObj["levels"].every((e: any) => isPeerReviewRubricLevel(e) as boolean)
+  )
+}
+
+export function isPeerReviewRubricLevel(obj: unknown): obj is PeerReviewRubricLevel {
+  const typedObj = obj as PeerReviewRubricLevel
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["description"] === "string" &&
+    typeof typedObj["points"] === "number"
+  )
 }
 
 export function isPeerReviewAnswer(obj: unknown): obj is PeerReviewAnswer {
@@ -2071,7 +2778,15 @@ export function isPeerReviewAnswer(obj: unknown): obj is PeerReviewAnswer {
       typeof typedObj["value"] === "string") ||
     (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
       typedObj["type"] === "scale" &&
-      typeof typedObj["value"] === "number")
+      typeof typedObj["value"] === "number") ||
+    (((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+      typedObj["type"] === "rubric" &&
+      Array.isArray(typedObj["selected_levels"]) &&
+      typedObj["selected_levels"].every(
+        (e: any) => isPeerReviewRubricSelectedLevel(e) as boolean,
+      ) &&
+      typeof typedObj["points"] === "number" &&
+      typeof typedObj["max_points"] === "number")
   )
 }
 
@@ -2101,7 +2816,24 @@ export function isPeerReviewQuestionSubmission(obj: unknown): obj is PeerReviewQ
     typeof typedObj["peer_review_question_id"] === "string" &&
     typeof typedObj["peer_review_submission_id"] === "string" &&
     (typedObj["text_data"] === null || typeof typedObj["text_data"] === "string") &&
-    (typedObj["number_data"] === null || typeof typedObj["number_data"] === "number")
+    (typedObj["number_data"] === null || typeof typedObj["number_data"] === "number") &&
+    (typedObj["rubric_level_ids"] === null ||
+      (Array.isArray(typedObj["rubric_level_ids"]) &&
+        typedObj["rubric_level_ids"].every((e: any) => typeof e === "string")))
+  )
+}
+
+export function isPeerReviewRubricSelectedLevel(
+  obj: unknown,
+): obj is PeerReviewRubricSelectedLevel {
+  const typedObj = obj as PeerReviewRubricSelectedLevel
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["criterion_id"] === "string" &&
+    typeof typedObj["criterion_name"] === "string" &&
+    typeof typedObj["level_id"] === "string" &&
+    typeof typedObj["level_description"] === "string" &&
+    typeof typedObj["points"] === "number"
   )
 }
 
@@ -2117,6 +2849,59 @@ export function isPeerReviewWithQuestionsAndAnswers(
   )
 }
 
+export function isPeerReviewSubmissionFlag(obj: unknown): obj is PeerReviewSubmissionFlag {
+  const typedObj = obj as PeerReviewSubmissionFlag
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    (typedObj["deleted_at"] === null || typedObj["deleted_at"] instanceof Date) &&
+    typeof typedObj["peer_review_submission_id"] === "string" &&
+    typeof typedObj["flagged_by"] === "string" &&
+    typeof typedObj["reason"] === "string" &&
+    (typedObj["resolved_at"] === null || typedObj["resolved_at"] instanceof Date) &&
+    (typedObj["resolved_by"] === null || typeof typedObj["resolved_by"] === "string") &&
+    (typedObj["resolution"] === null ||
+      (isPeerReviewSubmissionFlagResolution(typedObj["resolution"]) as boolean))
+  )
+}
+
+export function isPeerReviewSubmissionFlagResolution(
+  obj: unknown,
+): obj is PeerReviewSubmissionFlagResolution {
+  const typedObj = obj as PeerReviewSubmissionFlagResolution
+  return typedObj === "ReviewKept" || typedObj === "ReviewRemoved"
+}
+
+export function isPeerReviewSubmissionRating(obj: unknown): obj is PeerReviewSubmissionRating {
+  const typedObj = obj as PeerReviewSubmissionRating
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    (typedObj["deleted_at"] === null || typedObj["deleted_at"] instanceof Date) &&
+    typeof typedObj["peer_review_submission_id"] === "string" &&
+    typeof typedObj["user_id"] === "string" &&
+    typeof typedObj["helpfulness"] === "number"
+  )
+}
+
+export function isPeerReviewReviewerStatistics(obj: unknown): obj is PeerReviewReviewerStatistics {
+  const typedObj = obj as PeerReviewReviewerStatistics
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["user_id"] === "string" &&
+    typeof typedObj["peer_reviews_given"] === "number" &&
+    typeof typedObj["helpfulness_ratings_received"] === "number" &&
+    (typedObj["average_helpfulness"] === null ||
+      typeof typedObj["average_helpfulness"] === "number") &&
+    typeof typedObj["flags_received"] === "number" &&
+    typeof typedObj["reviews_removed"] === "number"
+  )
+}
+
 export function isPendingRole(obj: unknown): obj is PendingRole {
   const typedObj = obj as PendingRole
   return (
@@ -2311,6 +3096,113 @@ export function isRepositoryExercise(obj: unknown): obj is RepositoryExercise {
   )
 }
 
+export function isNewRolePermission(obj: unknown): obj is NewRolePermission {
+  const typedObj = obj as NewRolePermission
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isPermissionAction(typedObj["action"]) as boolean) &&
+    (typedObj["action_role"] === null || (isUserRole(typedObj["action_role"]) as boolean)) &&
+    (typedObj["resource_type"] === null ||
+      (isPermissionResourceType(typedObj["resource_type"]) as boolean))
+  )
+}
+
+export function isPermissionAction(obj: unknown): obj is PermissionAction {
+  const typedObj = obj as PermissionAction
+  return (
+    typedObj === "ViewMaterial" ||
+    typedObj === "View" ||
+    typedObj === "Edit" ||
+    typedObj === "Grade" ||
+    typedObj === "Teach" ||
+    typedObj === "Download" ||
+    typedObj === "Duplicate" ||
+    typedObj === "DeleteAnswer" ||
+    typedObj === "EditRole" ||
+    typedObj === "CreateCoursesOrExams" ||
+    typedObj === "UsuallyUnacceptableDeletion" ||
+    typedObj === "UploadFile" ||
+    typedObj === "Impersonate"
+  )
+}
+
+export function isPermissionResourceType(obj: unknown): obj is PermissionResourceType {
+  const typedObj = obj as PermissionResourceType
+  return (
+    typedObj === "GlobalPermissions" ||
+    typedObj === "Chapter" ||
+    typedObj === "Course" ||
+    typedObj === "CourseInstance" ||
+    typedObj === "Exam" ||
+    typedObj === "Exercise" ||
+    typedObj === "ExerciseSlideSubmission" ||
+    typedObj === "ExerciseTask" ||
+    typedObj === "ExerciseTaskGrading" ||
+    typedObj === "ExerciseTaskSubmission" ||
+    typedObj === "Organization" ||
+    typedObj === "Page" ||
+    typedObj === "StudyRegistry" ||
+    typedObj === "AnyCourse" ||
+    typedObj === "Role" ||
+    typedObj === "User" ||
+    typedObj === "PlaygroundExample" ||
+    typedObj === "ExerciseService" ||
+    typedObj === "MaterialReference"
+  )
+}
+
+export function isRolePermission(obj: unknown): obj is RolePermission {
+  const typedObj = obj as RolePermission
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    (typedObj["built_in_role"] === null || (isUserRole(typedObj["built_in_role"]) as boolean)) &&
+    (typedObj["custom_role_id"] === null || typeof typedObj["custom_role_id"] === "string") &&
+    (isPermissionAction(typedObj["action"]) as boolean) &&
+    (typedObj["action_role"] === null || (isUserRole(typedObj["action_role"]) as boolean)) &&
+    (typedObj["resource_type"] === null ||
+      (isPermissionResourceType(typedObj["resource_type"]) as boolean))
+  )
+}
+
+export function isNewRoleRequest(obj: unknown): obj is NewRoleRequest {
+  const typedObj = obj as NewRoleRequest
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["course_id"] === "string" &&
+    (isUserRole(typedObj["role"]) as boolean) &&
+    (typedObj["valid_from"] === null || typedObj["valid_from"] instanceof Date) &&
+    (typedObj["valid_until"] === null || typedObj["valid_until"] instanceof Date) &&
+    typeof typedObj["reason"] === "string"
+  )
+}
+
+export function isRoleRequest(obj: unknown): obj is RoleRequest {
+  const typedObj = obj as RoleRequest
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typeof typedObj["user_id"] === "string" &&
+    typeof typedObj["user_email"] === "string" &&
+    typeof typedObj["course_id"] === "string" &&
+    typeof typedObj["course_name"] === "string" &&
+    (isUserRole(typedObj["role"]) as boolean) &&
+    (typedObj["valid_from"] === null || typedObj["valid_from"] instanceof Date) &&
+    (typedObj["valid_until"] === null || typedObj["valid_until"] instanceof Date) &&
+    typeof typedObj["reason"] === "string" &&
+    (isRoleRequestStatus(typedObj["status"]) as boolean) &&
+    (typedObj["decided_by_user_id"] === null ||
+      typeof typedObj["decided_by_user_id"] === "string") &&
+    (typedObj["decided_at"] === null || typedObj["decided_at"] instanceof Date)
+  )
+}
+
+export function isRoleRequestStatus(obj: unknown): obj is RoleRequestStatus {
+  const typedObj = obj as RoleRequestStatus
+  return typedObj === "Pending" || typedObj === "Approved" || typedObj === "Rejected"
+}
+
 export function isRoleDomain(obj: unknown): obj is RoleDomain {
   const typedObj = obj as RoleDomain
   return (
@@ -2337,7 +3229,10 @@ export function isRoleInfo(obj: unknown): obj is RoleInfo {
     ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
     typeof typedObj["email"] === "string" &&
     (isUserRole(typedObj["role"]) as boolean) &&
-    (isRoleDomain(typedObj["domain"]) as boolean)
+    (isRoleDomain(typedObj["domain"]) as boolean) &&
+    (typedObj["valid_from"] === null || typedObj["valid_from"] instanceof Date) &&
+    (typedObj["valid_until"] === null || typedObj["valid_until"] instanceof Date) &&
+    (typedObj["custom_role_id"] === null || typeof typedObj["custom_role_id"] === "string")
   )
 }
 
@@ -2349,7 +3244,11 @@ export function isRoleUser(obj: unknown): obj is RoleUser {
     (typedObj["first_name"] === null || typeof typedObj["first_name"] === "string") &&
     (typedObj["last_name"] === null || typeof typedObj["last_name"] === "string") &&
     typeof typedObj["email"] === "string" &&
-    (isUserRole(typedObj["role"]) as boolean)
+    (isUserRole(typedObj["role"]) as boolean) &&
+    (typedObj["valid_from"] === null || typedObj["valid_from"] instanceof Date) &&
+    (typedObj["valid_until"] === null || typedObj["valid_until"] instanceof Date) &&
+    (typedObj["custom_role_id"] === null || typeof typedObj["custom_role_id"] === "string") &&
+    (typedObj["custom_role_name"] === null || typeof typedObj["custom_role_name"] === "string")
   )
 }
 
@@ -2400,6 +3299,66 @@ export function isTeacherGradingDecision(obj: unknown): obj is TeacherGradingDec
   )
 }
 
+export function isNewUrlRedirection(obj: unknown): obj is NewUrlRedirection {
+  const typedObj = obj as NewUrlRedirection
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["old_url_path"] === "string" &&
+    (typedObj["destination_page_id"] === null ||
+      typeof typedObj["destination_page_id"] === "string") &&
+    (typedObj["destination_course_id"] === null ||
+      typeof typedObj["destination_course_id"] === "string") &&
+    (typedObj["destination_url_path"] === null ||
+      typeof typedObj["destination_url_path"] === "string")
+  )
+}
+
+export function isUrlRedirection(obj: unknown): obj is UrlRedirection {
+  const typedObj = obj as UrlRedirection
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    typeof typedObj["course_id"] === "string" &&
+    typeof typedObj["old_url_path"] === "string" &&
+    (typedObj["destination_page_id"] === null ||
+      typeof typedObj["destination_page_id"] === "string") &&
+    (typedObj["destination_course_id"] === null ||
+      typeof typedObj["destination_course_id"] === "string") &&
+    (typedObj["destination_url_path"] === null ||
+      typeof typedObj["destination_url_path"] === "string") &&
+    typeof typedObj["hit_count"] === "number" &&
+    (typedObj["last_hit_at"] === null || typedObj["last_hit_at"] instanceof Date)
+  )
+}
+
+export function isUrlRedirectionProblem(obj: unknown): obj is UrlRedirectionProblem {
+  const typedObj = obj as UrlRedirectionProblem
+  return typedObj === "Chain" || typedObj === "Cycle" || typedObj === "BrokenDestination"
+}
+
+export function isUrlRedirectionResolution(obj: unknown): obj is UrlRedirectionResolution {
+  const typedObj = obj as UrlRedirectionResolution
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (typedObj["page_id"] === null || typeof typedObj["page_id"] === "string") &&
+    (typedObj["course_id"] === null || typeof typedObj["course_id"] === "string") &&
+    (typedObj["url_path"] === null || typeof typedObj["url_path"] === "string") &&
+    typeof typedObj["hops"] === "number" &&
+    (typedObj["problem"] === null || (isUrlRedirectionProblem(typedObj["problem"]) as boolean))
+  )
+}
+
+export function isUrlRedirectionWithResolution(obj: unknown): obj is UrlRedirectionWithResolution {
+  const typedObj = obj as UrlRedirectionWithResolution
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isUrlRedirection(typedObj["redirection"]) as boolean) &&
+    (isUrlRedirectionResolution(typedObj["resolution"]) as boolean)
+  )
+}
+
 export function isUserCourseSettings(obj: unknown): obj is UserCourseSettings {
   const typedObj = obj as UserCourseSettings
   return (
@@ -2414,6 +3373,18 @@ export function isUserCourseSettings(obj: unknown): obj is UserCourseSettings {
   )
 }
 
+export function isUserDataExport(obj: unknown): obj is UserDataExport {
+  const typedObj = obj as UserDataExport
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typeof typedObj["user_id"] === "string" &&
+    (typedObj["completed_at"] === null || typedObj["completed_at"] instanceof Date) &&
+    (typedObj["expires_at"] === null || typedObj["expires_at"] instanceof Date)
+  )
+}
+
 export function isExerciseUserCounts(obj: unknown): obj is ExerciseUserCounts {
   const typedObj = obj as ExerciseUserCounts
   return (
@@ -2507,6 +3478,78 @@ export function isUser(obj: unknown): obj is User {
   )
 }
 
+export function isWebhookDelivery(obj: unknown): obj is WebhookDelivery {
+  const typedObj = obj as WebhookDelivery
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    typeof typedObj["webhook_subscription_id"] === "string" &&
+    (isWebhookEventType(typedObj["event_type"]) as boolean) &&
+    (isWebhookDeliveryStatus(typedObj["status"]) as boolean) &&
+    typeof typedObj["attempts"] === "number" &&
+    typedObj["next_attempt_at"] instanceof Date &&
+    (typedObj["delivered_at"] === null || typedObj["delivered_at"] instanceof Date)
+  )
+}
+
+export function isWebhookDeliveryStatus(obj: unknown): obj is WebhookDeliveryStatus {
+  const typedObj = obj as WebhookDeliveryStatus
+  return typedObj === "Pending" || typedObj === "Delivered" || typedObj === "Failed"
+}
+
+export function isWebhookDeliveryAttempt(obj: unknown): obj is WebhookDeliveryAttempt {
+  const typedObj = obj as WebhookDeliveryAttempt
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typeof typedObj["webhook_delivery_id"] === "string" &&
+    (typedObj["response_status"] === null || typeof typedObj["response_status"] === "number") &&
+    (typedObj["error"] === null || typeof typedObj["error"] === "string") &&
+    typeof typedObj["duration_ms"] === "number"
+  )
+}
+
+export function isNewWebhookSubscription(obj: unknown): obj is NewWebhookSubscription {
+  const typedObj = obj as NewWebhookSubscription
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["url"] === "string" &&
+    Array.isArray(typedObj["event_types"]) &&
+    typedObj["event_types"].every((e: any) => isWebhookEventType(e) as boolean) &&
+    typeof typedObj["enabled"] === "boolean"
+  )
+}
+
+export function isWebhookEventType(obj: unknown): obj is WebhookEventType {
+  const typedObj = obj as WebhookEventType
+  return (
+    typedObj === "enrollment_created" ||
+    typedObj === "module_completion_granted" ||
+    typedObj === "exam_ended" ||
+    typedObj === "submission_graded" ||
+    typedObj === "peer_review_received"
+  )
+}
+
+export function isWebhookSubscription(obj: unknown): obj is WebhookSubscription {
+  const typedObj = obj as WebhookSubscription
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typedObj["created_at"] instanceof Date &&
+    typedObj["updated_at"] instanceof Date &&
+    typeof typedObj["organization_id"] === "string" &&
+    typeof typedObj["url"] === "string" &&
+    typeof typedObj["secret"] === "string" &&
+    Array.isArray(typedObj["event_types"]) &&
+    typedObj["event_types"].every((e: any) => isWebhookEventType(e) as boolean) &&
+    typeof typedObj["enabled"] === "boolean"
+  )
+}
+
 export function isUserCourseInstanceExerciseServiceVariable(
   obj: unknown,
 ): obj is UserCourseInstanceExerciseServiceVariable {
@@ -2560,7 +3603,8 @@ export function isUserInfo(obj: unknown): obj is UserInfo {
   const typedObj = obj as UserInfo
   return (
     ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
-    typeof typedObj["user_id"] === "string"
+    typeof typedObj["user_id"] === "string" &&
+    (typedObj["impersonation"] === null || (isImpersonation(typedObj["impersonation"]) as boolean))
   )
 }
 
@@ -2630,6 +3674,68 @@ export function isSaveCourseSettingsPayload(obj: unknown): obj is SaveCourseSett
   )
 }
 
+export function isLtiDeepLinkingChapter(obj: unknown): obj is LtiDeepLinkingChapter {
+  const typedObj = obj as LtiDeepLinkingChapter
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    typeof typedObj["chapter_number"] === "number" &&
+    Array.isArray(typedObj["pages"]) &&
+    typedObj["pages"].every((e: any) => isLtiDeepLinkingPage(e) as boolean)
+  )
+}
+
+export function isLtiDeepLinkingCourse(obj: unknown): obj is LtiDeepLinkingCourse {
+  const typedObj = obj as LtiDeepLinkingCourse
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    Array.isArray(typedObj["pages"]) &&
+    typedObj["pages"].every((e: any) => isLtiDeepLinkingPage(e) as boolean) &&
+    Array.isArray(typedObj["chapters"]) &&
+    typedObj["chapters"].every((e: any) => isLtiDeepLinkingChapter(e) as boolean)
+  )
+}
+
+export function isLtiDeepLinkingPage(obj: unknown): obj is LtiDeepLinkingPage {
+  const typedObj = obj as LtiDeepLinkingPage
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    typeof typedObj["title"] === "string" &&
+    typeof typedObj["url_path"] === "string"
+  )
+}
+
+export function isLtiDeepLinkingResponse(obj: unknown): obj is LtiDeepLinkingResponse {
+  const typedObj = obj as LtiDeepLinkingResponse
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["deep_link_return_url"] === "string" &&
+    typeof typedObj["jwt"] === "string"
+  )
+}
+
+export function isLtiDeepLinkingSelection(obj: unknown): obj is LtiDeepLinkingSelection {
+  const typedObj = obj as LtiDeepLinkingSelection
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (typedObj["page_id"] === null || typeof typedObj["page_id"] === "string") &&
+    (typedObj["chapter_id"] === null || typeof typedObj["chapter_id"] === "string")
+  )
+}
+
+export function isCreatedApiToken(obj: unknown): obj is CreatedApiToken {
+  const typedObj = obj as CreatedApiToken
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isApiToken(typedObj["api_token"]) as boolean) &&
+    typeof typedObj["token"] === "string"
+  )
+}
+
 export function isGetFeedbackQuery(obj: unknown): obj is GetFeedbackQuery {
   const typedObj = obj as GetFeedbackQuery
   return (
@@ -2677,6 +3783,24 @@ export function isMarkAsRead(obj: unknown): obj is MarkAsRead {
   )
 }
 
+export function isNewImpersonation(obj: unknown): obj is NewImpersonation {
+  const typedObj = obj as NewImpersonation
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["course_id"] === "string" &&
+    typeof typedObj["email"] === "string"
+  )
+}
+
+export function isPageHistoryDiffQuery(obj: unknown): obj is PageHistoryDiffQuery {
+  const typedObj = obj as PageHistoryDiffQuery
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["old_history_id"] === "string" &&
+    typeof typedObj["new_history_id"] === "string"
+  )
+}
+
 export function isGetEditProposalsQuery(obj: unknown): obj is GetEditProposalsQuery {
   const typedObj = obj as GetEditProposalsQuery
   return (
@@ -2703,6 +3827,61 @@ export function isRoleQuery(obj: unknown): obj is RoleQuery {
   )
 }
 
+export function isBlockChange(obj: unknown): obj is BlockChange {
+  const typedObj = obj as BlockChange
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["client_id"] === "string" &&
+    typeof typedObj["name"] === "string" &&
+    (isChangeKind(typedObj["kind"]) as boolean) &&
+    (typedObj["parent_client_id"] === null || typeof typedObj["parent_client_id"] === "string") &&
+    typeof typedObj["moved"] === "boolean" &&
+    Array.isArray(typedObj["attribute_changes"]) &&
+    typedObj["attribute_changes"].every((e: any) => isValueChange(e) as boolean)
+  )
+}
+
+export function isChangeKind(obj: unknown): obj is ChangeKind {
+  const typedObj = obj as ChangeKind
+  return typedObj === "Added" || typedObj === "Removed" || typedObj === "Modified"
+}
+
+export function isItemChange(obj: unknown): obj is ItemChange {
+  const typedObj = obj as ItemChange
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["id"] === "string" &&
+    (isChangeKind(typedObj["kind"]) as boolean) &&
+    Array.isArray(typedObj["field_changes"]) &&
+    typedObj["field_changes"].every((e: any) => isValueChange(e) as boolean)
+  )
+}
+
+export function isTextDiffPart(obj: unknown): obj is TextDiffPart {
+  const typedObj = obj as TextDiffPart
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    (isTextDiffPartKind(typedObj["kind"]) as boolean) &&
+    typeof typedObj["text"] === "string"
+  )
+}
+
+export function isTextDiffPartKind(obj: unknown): obj is TextDiffPartKind {
+  const typedObj = obj as TextDiffPartKind
+  return typedObj === "Unchanged" || typedObj === "Added" || typedObj === "Removed"
+}
+
+export function isValueChange(obj: unknown): obj is ValueChange {
+  const typedObj = obj as ValueChange
+  return (
+    ((typedObj !== null && typeof typedObj === "object") || typeof typedObj === "function") &&
+    typeof typedObj["key"] === "string" &&
+    (typedObj["text_diff"] === null ||
+      (Array.isArray(typedObj["text_diff"]) &&
+        typedObj["text_diff"].every((e: any) => isTextDiffPart(e) as boolean)))
+  )
+}
+
 export function isPagination(obj: unknown): obj is Pagination {
   const typedObj = obj as Pagination
   return (
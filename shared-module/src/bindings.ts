@@ -19,12 +19,20 @@ export type Action =
   | { type: "create_courses_or_exams" }
   | { type: "usually_unacceptable_deletion" }
   | { type: "upload_file" }
+  | { type: "impersonate" }
 
 export interface ActionOnResource {
   action: Action
   resource: Resource
 }
 
+export interface Impersonation {
+  impersonated_user_id: string
+  course_id: string
+  started_at: Date
+  expires_at: Date
+}
+
 export type Resource =
   | { type: "global_permissions" }
   | { type: "chapter"; id: string }
@@ -46,7 +54,10 @@ export type Resource =
   | { type: "exercise_service" }
   | { type: "material_reference" }
 
-export type ErrorData = { block_id: string }
+export type ErrorData =
+  | { block_id: string }
+  | { page_save_conflict: PageSaveConflict }
+  | { page_edit_lock: PageEditLock }
 
 export interface ErrorResponse {
   title: string
@@ -55,11 +66,67 @@ export interface ErrorResponse {
   data: ErrorData | null
 }
 
+export type GradeExportFormat = "Csv" | "Xlsx" | "MoodleCsv"
+
 export interface SpecRequest {
   private_spec: unknown | null
   upload_url: string | null
 }
 
+export interface ApiToken {
+  id: string
+  created_at: Date
+  updated_at: Date
+  user_id: string
+  name: string
+  scopes: Array<ApiTokenScope>
+  expires_at: Date
+  last_used_at: Date | null
+}
+
+export type ApiTokenScope = "Read" | "Grade" | "EditContent"
+
+export interface NewApiToken {
+  name: string
+  scopes: Array<ApiTokenScope>
+  expires_at: Date
+}
+
+export type AuditLogAction =
+  | "RoleAdded"
+  | "RoleRemoved"
+  | "ManualCompletionsAdded"
+  | "TeacherGradingDecisionAdded"
+  | "RegradingCreated"
+  | "PageRestored"
+  | "CourseDeleted"
+  | "UserErased"
+  | "ImpersonationStarted"
+  | "ImpersonationEnded"
+
+export interface AuditLogEntry {
+  id: string
+  created_at: Date
+  actor_user_id: string | null
+  actor_email: string | null
+  api_token_id: string | null
+  request_id: string | null
+  action: AuditLogAction
+  resource_id: string
+  course_id: string | null
+  before_summary: unknown | null
+  after_summary: unknown | null
+}
+
+export interface AuditLogFilter {
+  actor_user_id: string | null
+  action: AuditLogAction | null
+  course_id: string | null
+  resource_id: string | null
+  created_after: Date | null
+  created_before: Date | null
+}
+
 export interface Chapter {
   id: string
   created_at: Date
@@ -75,6 +142,8 @@ export interface Chapter {
   deadline: Date | null
   copied_from: string | null
   course_module_id: string
+  publish_at: Date | null
+  unpublish_at: Date | null
 }
 
 export type ChapterStatus = "open" | "closed"
@@ -86,6 +155,8 @@ export interface ChapterUpdate {
   deadline: Date | null
   opens_at: Date | null
   course_module_id: string | null
+  publish_at: Date | null
+  unpublish_at: Date | null
 }
 
 export interface ChapterWithStatus {
@@ -119,6 +190,8 @@ export interface DatabaseChapter {
   deadline: Date | null
   copied_from: string | null
   course_module_id: string
+  publish_at: Date | null
+  unpublish_at: Date | null
 }
 
 export interface NewChapter {
@@ -139,6 +212,39 @@ export interface UserCourseInstanceChapterProgress {
   attempted_exercises: number | null
 }
 
+export interface ContentLintIssue {
+  id: string
+  page_id: string
+  page_title: string
+  page_url_path: string
+  block_id: string | null
+  issue_type: ContentLintIssueType
+  target: string
+  suggestion: string | null
+}
+
+export type ContentLintIssueType =
+  | "LinkToMissingPage"
+  | "LinkToDeletedPage"
+  | "LinkToHiddenPage"
+  | "LinkThroughRedirection"
+  | "MissingMediaFile"
+  | "UnsupportedEmbed"
+  | "ImageWithoutAltText"
+  | "MissingExercise"
+
+export interface ContentLintRun {
+  id: string
+  created_at: Date
+  course_id: string
+  pages_checked: number
+}
+
+export interface CourseContentLintReport {
+  run: ContentLintRun | null
+  issues: Array<ContentLintIssue>
+}
+
 export interface CourseInstanceEnrollment {
   user_id: string
   course_id: string
@@ -355,6 +461,31 @@ export interface NewCourse {
   is_test_mode: boolean
 }
 
+export interface CustomRole {
+  id: string
+  created_at: Date
+  updated_at: Date
+  organization_id: string
+  name: string
+  base_role: UserRole
+}
+
+export interface CustomRoleUpdate {
+  name: string
+  permissions: Array<NewRolePermission>
+}
+
+export interface CustomRoleWithPermissions {
+  custom_role: CustomRole
+  permissions: Array<RolePermission>
+}
+
+export interface NewCustomRole {
+  name: string
+  base_role: UserRole
+  permissions: Array<NewRolePermission>
+}
+
 export interface EmailTemplate {
   id: string
   created_at: Date
@@ -591,6 +722,38 @@ export type UserPointsUpdateStrategy =
   | "CanAddPointsButCannotRemovePoints"
   | "CanAddPointsAndCanRemovePoints"
 
+export interface ExerciseTaskSubmissionSimilarity {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  exercise_id: string
+  course_instance_id: string
+  exercise_task_id: string
+  first_exercise_task_submission_id: string
+  second_exercise_task_submission_id: string
+  similarity: number
+}
+
+export interface ExerciseTaskSubmissionSimilarityInfo {
+  id: string
+  course_instance_id: string
+  exercise_task_id: string
+  similarity: number
+  first: SimilarSubmission
+  second: SimilarSubmission
+}
+
+export interface SimilarSubmission {
+  exercise_task_submission_id: string
+  exercise_slide_submission_id: string
+  user_id: string
+  created_at: Date
+  data_json: unknown | null
+  user_exercise_state_id: string | null
+  latest_teacher_decision: TeacherDecisionType | null
+}
+
 export interface ExerciseTaskSubmission {
   id: string
   created_at: Date
@@ -607,6 +770,8 @@ export interface ExerciseTaskSubmission {
 export interface PeerReviewsRecieved {
   peer_review_questions: Array<PeerReviewQuestion>
   peer_review_question_submissions: Array<PeerReviewQuestionSubmission>
+  peer_review_submission_ratings: Array<PeerReviewSubmissionRating>
+  peer_review_submission_flags: Array<PeerReviewSubmissionFlag>
 }
 
 export interface CourseMaterialExerciseTask {
@@ -723,6 +888,25 @@ export interface TermUpdate {
   definition: string
 }
 
+export interface IdentityProviderInfo {
+  id: string
+  organization_id: string
+  name: string
+  issuer_url: string
+}
+
+export interface NewIdentityProvider {
+  name: string
+  issuer_url: string
+  client_id: string
+  client_secret: string | null
+  scopes: string
+  email_claim: string
+  first_name_claim: string
+  last_name_claim: string
+  link_existing_users_by_email: boolean
+}
+
 export interface AnswerRequiringAttentionWithTasks {
   id: string
   user_id: string
@@ -773,6 +957,7 @@ export interface CourseMaterialPeerReviewData {
   peer_review_config: PeerReviewConfig
   peer_review_questions: Array<PeerReviewQuestion>
   num_peer_reviews_given: number
+  is_calibration_sample: boolean
 }
 
 export interface CourseMaterialPeerReviewDataAnswerToReview {
@@ -784,6 +969,7 @@ export interface CourseMaterialPeerReviewQuestionAnswer {
   peer_review_question_id: string
   text_data: string | null
   number_data: number | null
+  rubric_level_ids: Array<string> | null
 }
 
 export interface CourseMaterialPeerReviewSubmission {
@@ -792,6 +978,44 @@ export interface CourseMaterialPeerReviewSubmission {
   peer_review_question_answers: Array<CourseMaterialPeerReviewQuestionAnswer>
 }
 
+export interface FlaggedPeerReview {
+  flag: PeerReviewSubmissionFlag
+  reviewer_user_id: string
+  exercise_slide_submission_id: string
+  peer_review: PeerReviewWithQuestionsAndAnswers | null
+}
+
+export interface NewPeerReviewSubmissionFlag {
+  reason: string
+}
+
+export interface NewPeerReviewSubmissionRating {
+  helpfulness: number
+}
+
+export interface PeerReviewCalibrationFeedback {
+  question_feedback: Array<PeerReviewCalibrationQuestionFeedback>
+  calibration_samples_reviewed: number
+  calibration_samples_to_review: number
+}
+
+export interface PeerReviewCalibrationQuestionFeedback {
+  peer_review_question_id: string
+  given_number_data: number | null
+  reference_number_data: number
+  difference: number | null
+  explanation: string | null
+}
+
+export interface PeerReviewCalibrationSampleInfo {
+  calibration_sample: PeerReviewCalibrationSampleWithReferenceAnswers | null
+  peer_review_questions: Array<PeerReviewQuestion>
+}
+
+export interface ResolvePeerReviewSubmissionFlag {
+  resolution: PeerReviewSubmissionFlagResolution
+}
+
 export interface CompletionRegistrationLink {
   url: string
 }
@@ -860,6 +1084,30 @@ export interface UserWithModuleCompletions {
   user_id: string
 }
 
+export interface LtiRegistration {
+  id: string
+  created_at: Date
+  updated_at: Date
+  organization_id: string
+  name: string
+  issuer: string
+  client_id: string
+  auth_login_url: string
+  auth_token_url: string
+  jwks_url: string
+  link_existing_users_by_email: boolean
+}
+
+export interface NewLtiRegistration {
+  name: string
+  issuer: string
+  client_id: string
+  auth_login_url: string
+  auth_token_url: string
+  jwks_url: string
+  link_existing_users_by_email: boolean
+}
+
 export interface MaterialReference {
   id: string
   course_id: string
@@ -886,7 +1134,45 @@ export interface Organization {
   deleted_at: Date | null
 }
 
-export type HistoryChangeReason = "PageSaved" | "HistoryRestored"
+export interface PageDraft {
+  id: string
+  page_id: string
+  created_at: Date
+  updated_at: Date
+  author_user_id: string | null
+  content: CmsPageUpdate
+  publish_at: Date | null
+}
+
+export interface PageDraftUpdate {
+  content: CmsPageUpdate
+  publish_at: Date | null
+}
+
+export interface PageEditLock {
+  page_id: string
+  user_id: string
+  first_name: string | null
+  last_name: string | null
+  email: string
+  expires_at: Date
+}
+
+export interface PageEditStatus {
+  page_id: string
+  lock: PageEditLock | null
+  editors: Array<PageEditor>
+}
+
+export interface PageEditor {
+  user_id: string
+  first_name: string | null
+  last_name: string | null
+  email: string
+  last_seen_at: Date
+}
+
+export type HistoryChangeReason = "PageSaved" | "HistoryRestored" | "DraftPublished"
 
 export interface PageHistory {
   id: string
@@ -898,6 +1184,18 @@ export interface PageHistory {
   author_user_id: string
 }
 
+export interface PageHistoryDiff {
+  old_history_id: string
+  new_history_id: string
+  title_diff: Array<TextDiffPart> | null
+  blocks: Array<BlockChange>
+  exercises: Array<ItemChange>
+  exercise_slides: Array<ItemChange>
+  exercise_tasks: Array<ItemChange>
+  peer_review_configs: Array<ItemChange>
+  peer_review_questions: Array<ItemChange>
+}
+
 export interface CmsPageExercise {
   id: string
   name: string
@@ -935,6 +1233,7 @@ export interface CmsPageUpdate {
   url_path: string
   title: string
   chapter_id: string | null
+  base_history_id: string | null
 }
 
 export interface ContentManagementPage {
@@ -945,6 +1244,7 @@ export interface ContentManagementPage {
   peer_review_configs: Array<CmsPeerReviewConfig>
   peer_review_questions: Array<CmsPeerReviewQuestion>
   organization_id: string
+  history_id: string | null
 }
 
 export interface CoursePageWithUserData {
@@ -952,7 +1252,9 @@ export interface CoursePageWithUserData {
   instance: CourseInstance | null
   settings: UserCourseSettings | null
   was_redirected: boolean
+  redirected_course_slug: string | null
   is_test_mode: boolean
+  is_draft: boolean
 }
 
 export interface ExerciseWithExerciseTasks {
@@ -1004,6 +1306,8 @@ export interface Page {
   order_number: number
   copied_from: string | null
   hidden: boolean
+  publish_at: Date | null
+  unpublish_at: Date | null
 }
 
 export interface PageChapterAndCourseInformation {
@@ -1031,6 +1335,11 @@ export interface PageNavigationInformation {
   previous_page: PageRoutingData | null
 }
 
+export interface PagePublishingSchedule {
+  publish_at: Date | null
+  unpublish_at: Date | null
+}
+
 export interface PageRoutingData {
   url_path: string
   title: string
@@ -1041,6 +1350,11 @@ export interface PageRoutingData {
   chapter_front_page_id: string | null
 }
 
+export interface PageSaveConflict {
+  current: ContentManagementPage
+  merged: CmsPageUpdate | null
+}
+
 export interface PageSearchRequest {
   query: string
 }
@@ -1067,9 +1381,42 @@ export interface PageWithExercises {
   order_number: number
   copied_from: string | null
   hidden: boolean
+  publish_at: Date | null
+  unpublish_at: Date | null
   exercises: Array<Exercise>
 }
 
+export interface NewPeerReviewCalibrationReferenceAnswer {
+  peer_review_question_id: string
+  reference_number_data: number
+  explanation: string | null
+}
+
+export interface PeerReviewCalibrationReferenceAnswer {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  peer_review_calibration_sample_id: string
+  peer_review_question_id: string
+  reference_number_data: number
+  explanation: string | null
+}
+
+export interface PeerReviewCalibrationSample {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  exercise_slide_submission_id: string
+  exercise_id: string
+}
+
+export interface PeerReviewCalibrationSampleWithReferenceAnswers {
+  calibration_sample: PeerReviewCalibrationSample
+  reference_answers: Array<PeerReviewCalibrationReferenceAnswer>
+}
+
 export interface CourseMaterialPeerReviewConfig {
   id: string
   course_id: string
@@ -1086,6 +1433,10 @@ export interface CmsPeerReviewConfig {
   peer_reviews_to_receive: number
   accepting_threshold: number
   accepting_strategy: PeerReviewAcceptingStrategy
+  calibration_samples_to_review: number
+  timeout_in_days: number
+  timeout_action: PeerReviewTimeoutAction
+  manual_review_timeout_in_days: number | null
 }
 
 export interface CmsPeerReviewConfiguration {
@@ -1097,6 +1448,7 @@ export type PeerReviewAcceptingStrategy =
   | "AutomaticallyAcceptOrRejectByAverage"
   | "AutomaticallyAcceptOrManualReviewByAverage"
   | "ManualReviewEverything"
+  | "AutomaticallyGradeByRubricAverage"
 
 export interface PeerReviewConfig {
   id: string
@@ -1109,8 +1461,17 @@ export interface PeerReviewConfig {
   peer_reviews_to_receive: number
   accepting_threshold: number
   accepting_strategy: PeerReviewAcceptingStrategy
+  calibration_samples_to_review: number
+  timeout_in_days: number
+  timeout_action: PeerReviewTimeoutAction
+  manual_review_timeout_in_days: number | null
 }
 
+export type PeerReviewTimeoutAction =
+  | "MoveToManualReview"
+  | "GiveFullPoints"
+  | "AcceptByCurrentAverage"
+
 export interface CmsPeerReviewQuestion {
   id: string
   peer_review_config_id: string
@@ -1118,6 +1479,7 @@ export interface CmsPeerReviewQuestion {
   question: string
   question_type: PeerReviewQuestionType
   answer_required: boolean
+  rubric: PeerReviewRubric | null
 }
 
 export interface PeerReviewQuestion {
@@ -1130,14 +1492,37 @@ export interface PeerReviewQuestion {
   question: string
   question_type: PeerReviewQuestionType
   answer_required: boolean
+  rubric: PeerReviewRubric | null
 }
 
-export type PeerReviewQuestionType = "Essay" | "Scale"
+export type PeerReviewQuestionType = "Essay" | "Scale" | "Rubric"
+
+export interface PeerReviewRubric {
+  criteria: Array<PeerReviewRubricCriterion>
+}
+
+export interface PeerReviewRubricCriterion {
+  id: string
+  name: string
+  levels: Array<PeerReviewRubricLevel>
+}
+
+export interface PeerReviewRubricLevel {
+  id: string
+  description: string
+  points: number
+}
 
 export type PeerReviewAnswer =
   | { type: "no-answer" }
   | { type: "essay"; value: string }
   | { type: "scale"; value: number }
+  | {
+      type: "rubric"
+      selected_levels: Array<PeerReviewRubricSelectedLevel>
+      points: number
+      max_points: number
+    }
 
 export interface PeerReviewQuestionAndAnswer {
   peer_review_config_id: string
@@ -1159,6 +1544,15 @@ export interface PeerReviewQuestionSubmission {
   peer_review_submission_id: string
   text_data: string | null
   number_data: number | null
+  rubric_level_ids: Array<string> | null
+}
+
+export interface PeerReviewRubricSelectedLevel {
+  criterion_id: string
+  criterion_name: string
+  level_id: string
+  level_description: string
+  points: number
 }
 
 export interface PeerReviewWithQuestionsAndAnswers {
@@ -1166,6 +1560,40 @@ export interface PeerReviewWithQuestionsAndAnswers {
   questions_and_answers: Array<PeerReviewQuestionAndAnswer>
 }
 
+export interface PeerReviewSubmissionFlag {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  peer_review_submission_id: string
+  flagged_by: string
+  reason: string
+  resolved_at: Date | null
+  resolved_by: string | null
+  resolution: PeerReviewSubmissionFlagResolution | null
+}
+
+export type PeerReviewSubmissionFlagResolution = "ReviewKept" | "ReviewRemoved"
+
+export interface PeerReviewSubmissionRating {
+  id: string
+  created_at: Date
+  updated_at: Date
+  deleted_at: Date | null
+  peer_review_submission_id: string
+  user_id: string
+  helpfulness: number
+}
+
+export interface PeerReviewReviewerStatistics {
+  user_id: string
+  peer_reviews_given: number
+  helpfulness_ratings_received: number
+  average_helpfulness: number | null
+  flags_received: number
+  reviews_removed: number
+}
+
 export interface PendingRole {
   id: string
   user_email: string
@@ -1281,6 +1709,83 @@ export interface RepositoryExercise {
   download_url: string
 }
 
+export interface NewRolePermission {
+  action: PermissionAction
+  action_role: UserRole | null
+  resource_type: PermissionResourceType | null
+}
+
+export type PermissionAction =
+  | "ViewMaterial"
+  | "View"
+  | "Edit"
+  | "Grade"
+  | "Teach"
+  | "Download"
+  | "Duplicate"
+  | "DeleteAnswer"
+  | "EditRole"
+  | "CreateCoursesOrExams"
+  | "UsuallyUnacceptableDeletion"
+  | "UploadFile"
+  | "Impersonate"
+
+export type PermissionResourceType =
+  | "GlobalPermissions"
+  | "Chapter"
+  | "Course"
+  | "CourseInstance"
+  | "Exam"
+  | "Exercise"
+  | "ExerciseSlideSubmission"
+  | "ExerciseTask"
+  | "ExerciseTaskGrading"
+  | "ExerciseTaskSubmission"
+  | "Organization"
+  | "Page"
+  | "StudyRegistry"
+  | "AnyCourse"
+  | "Role"
+  | "User"
+  | "PlaygroundExample"
+  | "ExerciseService"
+  | "MaterialReference"
+
+export interface RolePermission {
+  id: string
+  built_in_role: UserRole | null
+  custom_role_id: string | null
+  action: PermissionAction
+  action_role: UserRole | null
+  resource_type: PermissionResourceType | null
+}
+
+export interface NewRoleRequest {
+  course_id: string
+  role: UserRole
+  valid_from: Date | null
+  valid_until: Date | null
+  reason: string
+}
+
+export interface RoleRequest {
+  id: string
+  created_at: Date
+  user_id: string
+  user_email: string
+  course_id: string
+  course_name: string
+  role: UserRole
+  valid_from: Date | null
+  valid_until: Date | null
+  reason: string
+  status: RoleRequestStatus
+  decided_by_user_id: string | null
+  decided_at: Date | null
+}
+
+export type RoleRequestStatus = "Pending" | "Approved" | "Rejected"
+
 export type RoleDomain =
   | { tag: "Global" }
   | { tag: "Organization"; id: string }
@@ -1292,6 +1797,9 @@ export interface RoleInfo {
   email: string
   role: UserRole
   domain: RoleDomain
+  valid_from: Date | null
+  valid_until: Date | null
+  custom_role_id: string | null
 }
 
 export interface RoleUser {
@@ -1300,6 +1808,10 @@ export interface RoleUser {
   last_name: string | null
   email: string
   role: UserRole
+  valid_from: Date | null
+  valid_until: Date | null
+  custom_role_id: string | null
+  custom_role_name: string | null
 }
 
 export type UserRole =
@@ -1333,6 +1845,41 @@ export interface TeacherGradingDecision {
   teacher_decision: TeacherDecisionType
 }
 
+export interface NewUrlRedirection {
+  old_url_path: string
+  destination_page_id: string | null
+  destination_course_id: string | null
+  destination_url_path: string | null
+}
+
+export interface UrlRedirection {
+  id: string
+  created_at: Date
+  updated_at: Date
+  course_id: string
+  old_url_path: string
+  destination_page_id: string | null
+  destination_course_id: string | null
+  destination_url_path: string | null
+  hit_count: number
+  last_hit_at: Date | null
+}
+
+export type UrlRedirectionProblem = "Chain" | "Cycle" | "BrokenDestination"
+
+export interface UrlRedirectionResolution {
+  page_id: string | null
+  course_id: string | null
+  url_path: string | null
+  hops: number
+  problem: UrlRedirectionProblem | null
+}
+
+export interface UrlRedirectionWithResolution {
+  redirection: UrlRedirection
+  resolution: UrlRedirectionResolution
+}
+
 export interface UserCourseSettings {
   user_id: string
   course_language_group_id: string
@@ -1343,6 +1890,14 @@ export interface UserCourseSettings {
   current_course_instance_id: string
 }
 
+export interface UserDataExport {
+  id: string
+  created_at: Date
+  user_id: string
+  completed_at: Date | null
+  expires_at: Date | null
+}
+
 export interface ExerciseUserCounts {
   exercise_name: string
   exercise_order_number: number
@@ -1406,6 +1961,54 @@ export interface User {
   email: string
 }
 
+export interface WebhookDelivery {
+  id: string
+  created_at: Date
+  updated_at: Date
+  webhook_subscription_id: string
+  event_type: WebhookEventType
+  payload: unknown
+  status: WebhookDeliveryStatus
+  attempts: number
+  next_attempt_at: Date
+  delivered_at: Date | null
+}
+
+export type WebhookDeliveryStatus = "Pending" | "Delivered" | "Failed"
+
+export interface WebhookDeliveryAttempt {
+  id: string
+  created_at: Date
+  webhook_delivery_id: string
+  response_status: number | null
+  error: string | null
+  duration_ms: number
+}
+
+export interface NewWebhookSubscription {
+  url: string
+  event_types: Array<WebhookEventType>
+  enabled: boolean
+}
+
+export type WebhookEventType =
+  | "enrollment_created"
+  | "module_completion_granted"
+  | "exam_ended"
+  | "submission_graded"
+  | "peer_review_received"
+
+export interface WebhookSubscription {
+  id: string
+  created_at: Date
+  updated_at: Date
+  organization_id: string
+  url: string
+  secret: string
+  event_types: Array<WebhookEventType>
+  enabled: boolean
+}
+
 export interface UserCourseInstanceExerciseServiceVariable {
   id: string
   created_at: Date
@@ -1439,6 +2042,7 @@ export interface Login {
 
 export interface UserInfo {
   user_id: string
+  impersonation: Impersonation | null
 }
 
 export interface ChaptersWithStatus {
@@ -1475,6 +2079,41 @@ export interface SaveCourseSettingsPayload {
   background_question_answers: Array<NewCourseBackgroundQuestionAnswer>
 }
 
+export interface LtiDeepLinkingChapter {
+  id: string
+  name: string
+  chapter_number: number
+  pages: Array<LtiDeepLinkingPage>
+}
+
+export interface LtiDeepLinkingCourse {
+  id: string
+  name: string
+  pages: Array<LtiDeepLinkingPage>
+  chapters: Array<LtiDeepLinkingChapter>
+}
+
+export interface LtiDeepLinkingPage {
+  id: string
+  title: string
+  url_path: string
+}
+
+export interface LtiDeepLinkingResponse {
+  deep_link_return_url: string
+  jwt: string
+}
+
+export interface LtiDeepLinkingSelection {
+  page_id: string | null
+  chapter_id: string | null
+}
+
+export interface CreatedApiToken {
+  api_token: ApiToken
+  token: string
+}
+
 export interface GetFeedbackQuery {
   read: boolean
   page: number | undefined
@@ -1501,6 +2140,16 @@ export interface MarkAsRead {
   read: boolean
 }
 
+export interface NewImpersonation {
+  course_id: string
+  email: string
+}
+
+export interface PageHistoryDiffQuery {
+  old_history_id: string
+  new_history_id: string
+}
+
 export interface GetEditProposalsQuery {
   pending: boolean
   page: number | undefined
@@ -1515,6 +2164,37 @@ export interface RoleQuery {
   exam_id?: string
 }
 
+export interface BlockChange {
+  client_id: string
+  name: string
+  kind: ChangeKind
+  parent_client_id: string | null
+  moved: boolean
+  attribute_changes: Array<ValueChange>
+}
+
+export type ChangeKind = "Added" | "Removed" | "Modified"
+
+export interface ItemChange {
+  id: string
+  kind: ChangeKind
+  field_changes: Array<ValueChange>
+}
+
+export interface TextDiffPart {
+  kind: TextDiffPartKind
+  text: string
+}
+
+export type TextDiffPartKind = "Unchanged" | "Added" | "Removed"
+
+export interface ValueChange {
+  key: string
+  old_value: unknown | null
+  new_value: unknown | null
+  text_diff: Array<TextDiffPart> | null
+}
+
 export interface Pagination {
   page: number | undefined
   limit: number | undefined
@@ -140,7 +140,7 @@ const ErrorBanner: React.FC<React.PropsWithChildren<React.PropsWithChildren<Bann
       const data: ErrorResponse = error.data
       const errorData = data.data
       let linkComponent = <></>
-      if (isErrorData(errorData)) {
+      if (isErrorData(errorData) && "block_id" in errorData) {
         const url = window.location.href.replace(location.hash, "")
         // eslint-disable-next-line i18next/no-literal-string
         linkComponent = <a href={`${url}#${errorData.block_id}`}>Go to error</a>
@@ -1,9 +1,14 @@
 /* eslint-disable i18next/no-literal-string */
 import axios from "axios"
 
-import { ActionOnResource, CreateAccountDetails, UserInfo } from "../../bindings"
-import { isUserInfo } from "../../bindings.guard"
-import { isNull, isUnion, validateResponse } from "../../utils/fetching"
+import {
+  ActionOnResource,
+  CreateAccountDetails,
+  IdentityProviderInfo,
+  UserInfo,
+} from "../../bindings"
+import { isIdentityProviderInfo, isUserInfo } from "../../bindings.guard"
+import { isArray, isNull, isUnion, validateResponse } from "../../utils/fetching"
 
 export const loggedIn = async (): Promise<boolean> => {
   const url = `/api/v0/auth/logged-in`
@@ -24,11 +29,22 @@ export const login = async (email: string, password: string): Promise<void> => {
   })
 }
 
+export const fetchIdentityProviders = async (): Promise<IdentityProviderInfo[]> => {
+  const url = `/api/v0/auth/identity-providers`
+  const response = await axios.get(url, { responseType: "json" })
+  return validateResponse(response, isArray(isIdentityProviderInfo))
+}
+
 export const logout = async (): Promise<void> => {
   const url = `/api/v0/auth/logout`
   await axios.post(url)
 }
 
+export const stopImpersonation = async (): Promise<void> => {
+  const url = `/api/v0/main-frontend/impersonation/stop`
+  await axios.post(url)
+}
+
 export const authorize = async (action: ActionOnResource): Promise<boolean> => {
   return (await axios.post("/api/v0/auth/authorize", action)).data
 }
@@ -11,6 +11,8 @@ export const interceptor = (axiosClient: AxiosInstance): AxiosResponse<unknown>
     // Any status code that fall outside of the range 2xx
     // Rejects the response for useQuery to catch
     (err: AxiosError) => {
+      // error responses can carry data too, e.g. the current version of a page after a save conflict
+      ISOStringToDateTime(err.response?.data)
       return Promise.reject(err.response)
     },
   )